@@ -0,0 +1,194 @@
+//! Live bottleneck alerts
+//!
+//! Long unattended captures need to flag a serious problem, such as critical
+//! temperatures, while it happens rather than when the run is analyzed.
+//! `watch_bottlenecks` keeps the most recent samples and re-runs a reduced
+//! rule set over them on a fixed interval. Bottlenecks at or above the
+//! configured severity are handed on as alerts, once per condition.
+//! Acknowledged bottlenecks never alert.
+
+use crate::analysis::insights::{is_acknowledged, BottleneckAcknowledgment};
+use crate::analysis::rules::{analyze_bottlenecks_at, BottleneckRule};
+use crate::analysis::rules::registry::{CoolingHeadroomRule, GpuThermalRule, ThermalRule, WorkloadRule};
+use crate::core::domain::{BottleneckType, MetricSample};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::MissedTickBehavior;
+
+/// Default lowest severity that raises an alert
+///
+/// Severity is 0 just over a rule's threshold, so this is halfway from the
+/// threshold to saturation: a CPU-bound run at 92.5% against the 85% default.
+pub const DEFAULT_ALERT_MIN_SEVERITY: u8 = 50;
+
+/// Default time before a cleared condition may alert again
+pub const DEFAULT_ALERT_COOLDOWN_SECONDS: u64 = 300;
+
+/// Time between rule passes over the recent samples
+pub const ALERT_CHECK_INTERVAL_MS: u64 = 5000;
+
+/// Seconds of recent samples each rule pass looks at
+pub const ALERT_WINDOW_SECONDS: i64 = 30;
+
+/// Bottleneck detected during live collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BottleneckAlert {
+    pub bottleneck_type: BottleneckType,
+    pub severity: u8,
+    pub summary: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// When the watcher raises alerts
+#[derive(Debug, Clone, Copy)]
+pub struct AlertConfig {
+    /// Lowest severity that raises an alert
+    pub min_severity: u8,
+    /// Time before a condition that cleared may alert again
+    pub cooldown: chrono::Duration,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            min_severity: DEFAULT_ALERT_MIN_SEVERITY,
+            cooldown: chrono::Duration::seconds(DEFAULT_ALERT_COOLDOWN_SECONDS as i64),
+        }
+    }
+}
+
+/// Rules cheap enough to run repeatedly during collection
+///
+/// Thermal limits and sustained CPU/GPU/RAM saturation; the remaining
+/// built-in rules need longer or less common streams and are left to the
+/// full analysis.
+pub fn alert_rules() -> Vec<Box<dyn BottleneckRule>> {
+    vec![
+        Box::new(ThermalRule),
+        Box::new(CoolingHeadroomRule),
+        Box::new(GpuThermalRule),
+        Box::new(WorkloadRule),
+    ]
+}
+
+/// Recent samples and the alert state of each bottleneck type
+///
+/// A bottleneck type alerts when it first reaches the minimum severity. It
+/// stays quiet while the condition persists, and after it clears it may alert
+/// again only once the cooldown since its last alert has passed, so a
+/// temperature hovering around a limit does not alert on every pass.
+pub struct AlertWatcher {
+    config: AlertConfig,
+    rules: Vec<Box<dyn BottleneckRule>>,
+    acknowledgments: Vec<BottleneckAcknowledgment>,
+    samples: Vec<MetricSample>,
+    active: HashSet<BottleneckType>,
+    last_alerted: HashMap<BottleneckType, DateTime<Utc>>,
+}
+
+impl AlertWatcher {
+    /// Watcher running `alert_rules`
+    pub fn new(config: AlertConfig) -> Self {
+        Self::with_rules(config, alert_rules())
+    }
+
+    /// Watcher running the given rules
+    pub fn with_rules(config: AlertConfig, rules: Vec<Box<dyn BottleneckRule>>) -> Self {
+        Self {
+            config,
+            rules,
+            acknowledgments: Vec::new(),
+            samples: Vec::new(),
+            active: HashSet::new(),
+            last_alerted: HashMap::new(),
+        }
+    }
+
+    /// Suppress alerts for the bottlenecks these acknowledgments cover
+    pub fn with_acknowledgments(mut self, acknowledgments: Vec<BottleneckAcknowledgment>) -> Self {
+        self.acknowledgments = acknowledgments;
+        self
+    }
+
+    /// Add newly collected samples
+    pub fn push(&mut self, samples: Vec<MetricSample>) {
+        self.samples.extend(samples);
+    }
+
+    /// Run the rules over the window ending at `now` and return new alerts
+    ///
+    /// Samples older than the window are dropped.
+    pub fn evaluate(&mut self, now: DateTime<Utc>) -> Vec<BottleneckAlert> {
+        let window_start = now - chrono::Duration::seconds(ALERT_WINDOW_SECONDS);
+        self.samples.retain(|s| s.timestamp >= window_start);
+
+        let result = analyze_bottlenecks_at(&self.samples, ALERT_WINDOW_SECONDS, None, &self.rules, now);
+        let mut alerts = Vec::new();
+        let mut active = HashSet::new();
+        for bottleneck in result.bottlenecks {
+            if bottleneck.severity < self.config.min_severity
+                || is_acknowledged(&bottleneck, &self.acknowledgments)
+                || !active.insert(bottleneck.bottleneck_type.clone())
+            {
+                continue;
+            }
+            if self.active.contains(&bottleneck.bottleneck_type) {
+                continue;
+            }
+            let cooled_down = match self.last_alerted.get(&bottleneck.bottleneck_type) {
+                Some(last) => now - *last >= self.config.cooldown,
+                None => true,
+            };
+            if !cooled_down {
+                continue;
+            }
+
+            self.last_alerted.insert(bottleneck.bottleneck_type.clone(), now);
+            alerts.push(BottleneckAlert {
+                bottleneck_type: bottleneck.bottleneck_type,
+                severity: bottleneck.severity,
+                summary: bottleneck.summary,
+                detected_at: now,
+            });
+        }
+        self.active = active;
+        alerts
+    }
+}
+
+/// Feed samples from `receiver` to `watcher` and pass alerts to `emit`
+///
+/// Rules run once per `check_interval`. Returns once the sender side closes.
+pub async fn watch_bottlenecks<F>(
+    mut receiver: broadcast::Receiver<Vec<MetricSample>>,
+    check_interval: Duration,
+    mut watcher: AlertWatcher,
+    mut emit: F,
+) where
+    F: FnMut(BottleneckAlert),
+{
+    let mut check = tokio::time::interval(check_interval);
+    check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // The first tick completes immediately
+    check.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = check.tick() => {
+                for alert in watcher.evaluate(Utc::now()) {
+                    emit(alert);
+                }
+            }
+            received = receiver.recv() => match received {
+                Ok(batch) => watcher.push(batch),
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("Bottleneck alert watcher fell behind; {} batches were dropped", skipped);
+                }
+                Err(RecvError::Closed) => return,
+            },
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Idle baseline subtraction
+//!
+//! This module removes the idle footprint of the system from captured metrics
+//! so that analysis reports the load attributable to the workload.
+
+use crate::core::domain::MetricSample;
+use crate::core::settings::IdleBaseline;
+
+/// Subtract idle averages from metric samples
+///
+/// Each sample is matched to the idle average of its own metric type and
+/// source component; samples without a baseline entry are returned unchanged.
+/// Results are clamped at zero.
+pub fn subtract_baseline(metrics: &[MetricSample], baseline: &IdleBaseline) -> Vec<MetricSample> {
+    metrics
+        .iter()
+        .map(|sample| {
+            let mut adjusted = sample.clone();
+            if let Some(idle) = baseline.average_for(&sample.metric_type, &sample.source_component) {
+                adjusted.value = (sample.value - idle).max(0.0);
+            }
+            adjusted
+        })
+        .collect()
+}
@@ -0,0 +1,202 @@
+//! Boost clock sustainability
+//!
+//! CPUs and GPUs boost to their highest clocks while they are cool and settle
+//! lower once temperatures rise. This module measures how long a run held its
+//! boost clock and how far clocks fell afterwards, which tells the user how
+//! much of the advertised boost their cooling can actually sustain.
+
+use crate::core::domain::{MetricSample, MetricType, Run};
+use crate::metrics::utils::mean;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Clocks within this fraction of the peak still count as boosting
+pub const BOOST_TOLERANCE: f64 = 0.03;
+
+/// Consecutive readings a clock must hold to set the peak or to count as settled
+pub const BOOST_HOLD_SAMPLES: usize = 3;
+
+/// Clock readings needed before sustainability is judged
+pub const BOOST_MIN_SAMPLES: usize = 10;
+
+/// Final share of the run whose clocks are treated as settled
+pub const SETTLED_FRACTION: f64 = 0.25;
+
+/// Component whose boost behaviour was measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoostComponent {
+    Cpu,
+    Gpu,
+}
+
+impl BoostComponent {
+    fn label(self) -> &'static str {
+        match self {
+            BoostComponent::Cpu => "CPU",
+            BoostComponent::Gpu => "GPU",
+        }
+    }
+
+    fn metrics(self) -> (MetricType, MetricType) {
+        match self {
+            BoostComponent::Cpu => (MetricType::CpuClock, MetricType::Temperature),
+            BoostComponent::Gpu => (MetricType::GpuClock, MetricType::GpuTemperature),
+        }
+    }
+}
+
+/// Sustained versus peak clock of one component during a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoostSustainability {
+    pub component: BoostComponent,
+    /// Highest clock held for `BOOST_HOLD_SAMPLES` consecutive readings
+    pub peak_clock_mhz: f64,
+    /// Average clock over the final `SETTLED_FRACTION` of the run
+    pub settled_clock_mhz: f64,
+    /// Settled clock relative to the peak in percent; negative when clocks dropped
+    pub settled_delta_percent: f64,
+    /// Seconds clocks stayed near the peak; `None` if they never left it
+    pub sustained_seconds: Option<f64>,
+    /// Average temperature while boosting
+    pub boost_temperature: f64,
+    /// Average temperature over the settled part of the run
+    pub settled_temperature: f64,
+    pub summary: String,
+}
+
+/// Measure how long the CPU and GPU of a run sustained their boost clocks
+///
+/// A component is included when the run recorded both its clock and its
+/// temperature with at least `BOOST_MIN_SAMPLES` clock readings. Each is
+/// read from one source, see `primary_source`, so a second GPU or a board
+/// sensor does not blend into the component's readings.
+pub fn analyze_boost_sustainability(run: &Run) -> Vec<BoostSustainability> {
+    let samples: Vec<&MetricSample> = run.metrics_streams.values().flatten().collect();
+    [BoostComponent::Cpu, BoostComponent::Gpu]
+        .into_iter()
+        .filter_map(|component| component_sustainability(component, &samples))
+        .collect()
+}
+
+fn component_sustainability(component: BoostComponent, samples: &[&MetricSample]) -> Option<BoostSustainability> {
+    let (clock_metric, temperature_metric) = component.metrics();
+    let clock_source = primary_source(samples, &clock_metric, component.label())?;
+    let clocks = readings_by_time(samples, &clock_metric, clock_source);
+    if clocks.len() < BOOST_MIN_SAMPLES {
+        return None;
+    }
+    // Prefer the temperature of the sensor the clock came from
+    let temperature_source = if samples
+        .iter()
+        .any(|s| s.metric_type == temperature_metric && s.source_component == clock_source)
+    {
+        clock_source
+    } else {
+        primary_source(samples, &temperature_metric, component.label())?
+    };
+    let temperatures = readings_by_time(samples, &temperature_metric, temperature_source);
+    if temperatures.is_empty() {
+        return None;
+    }
+
+    // A single spike is not a boost clock the component can hold
+    let peak = clocks
+        .windows(BOOST_HOLD_SAMPLES)
+        .map(|window| window.iter().map(|&(_, clock)| clock).fold(f64::INFINITY, f64::min))
+        .fold(f64::NEG_INFINITY, f64::max);
+    if peak <= 0.0 {
+        return None;
+    }
+    let boosting = |clock: f64| clock >= peak * (1.0 - BOOST_TOLERANCE);
+
+    let boost_start_idx = clocks.iter().position(|&(_, clock)| boosting(clock))?;
+    let boost_start = clocks[boost_start_idx].0;
+    let settle_time = clocks[boost_start_idx..]
+        .windows(BOOST_HOLD_SAMPLES)
+        .find(|window| window.iter().all(|&(_, clock)| !boosting(clock)))
+        .map(|window| window[0].0);
+
+    let run_start = clocks[0].0;
+    let run_end = clocks[clocks.len() - 1].0;
+    let settled_start = run_end - scaled(run_end - run_start, SETTLED_FRACTION);
+    let settled_clock = mean(clocks.iter().filter(|&&(t, _)| t >= settled_start).map(|&(_, clock)| clock))?;
+    let boost_end = settle_time.unwrap_or(run_end);
+    let boost_temperature = mean(
+        temperatures
+            .iter()
+            .filter(|&&(t, _)| t >= boost_start && t <= boost_end)
+            .map(|&(_, temp)| temp),
+    )?;
+    let settled_temperature = mean(temperatures.iter().filter(|&&(t, _)| t >= settled_start).map(|&(_, temp)| temp))?;
+
+    let settled_delta_percent = (settled_clock - peak) / peak * 100.0;
+    let sustained_seconds = settle_time.map(|t| (t - boost_start).num_milliseconds() as f64 / 1000.0);
+    let summary = match sustained_seconds {
+        Some(seconds) => format!(
+            "{} sustains boost for {:.0} seconds, then {:+.1}% ({:.0} MHz settling at {:.0} MHz, {:.0}°C)",
+            component.label(),
+            seconds,
+            settled_delta_percent,
+            peak,
+            settled_clock,
+            settled_temperature
+        ),
+        None => format!(
+            "{} sustains boost for the whole run ({:.0} MHz at {:.0}°C)",
+            component.label(),
+            peak,
+            settled_temperature
+        ),
+    };
+
+    Some(BoostSustainability {
+        component,
+        peak_clock_mhz: peak,
+        settled_clock_mhz: settled_clock,
+        settled_delta_percent,
+        sustained_seconds,
+        boost_temperature,
+        settled_temperature,
+        summary,
+    })
+}
+
+/// Source a component's readings of a metric are taken from
+///
+/// The collector records the CPU package and the primary GPU under the
+/// component's label, which wins when present. Otherwise the source with
+/// the most readings is used.
+fn primary_source<'a>(samples: &[&'a MetricSample], metric_type: &MetricType, label: &str) -> Option<&'a str> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for sample in samples.iter().filter(|s| &s.metric_type == metric_type) {
+        *counts.entry(sample.source_component.as_str()).or_default() += 1;
+    }
+    if let Some((&source, _)) = counts.get_key_value(label) {
+        return Some(source);
+    }
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+        .map(|(source, _)| source)
+}
+
+/// Readings of a metric from one source ordered by time, taking the highest at each timestamp
+fn readings_by_time(samples: &[&MetricSample], metric_type: &MetricType, source: &str) -> Vec<(DateTime<Utc>, f64)> {
+    let mut by_time: BTreeMap<DateTime<Utc>, f64> = BTreeMap::new();
+    for sample in samples
+        .iter()
+        .filter(|s| &s.metric_type == metric_type && s.source_component == source && s.value.is_finite())
+    {
+        by_time
+            .entry(sample.timestamp)
+            .and_modify(|value| *value = value.max(sample.value))
+            .or_insert(sample.value);
+    }
+    by_time.into_iter().collect()
+}
+
+fn scaled(duration: Duration, fraction: f64) -> Duration {
+    Duration::milliseconds((duration.num_milliseconds() as f64 * fraction) as i64)
+}
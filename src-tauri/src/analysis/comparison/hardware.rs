@@ -0,0 +1,218 @@
+//! Hardware differences between an imported session and the local rig
+//!
+//! Deltas against someone else's session mix the effect of what was changed
+//! with the effect of different hardware. This module spells out how the
+//! imported CPU, GPU and RAM compare with the local ones, judging CPUs and
+//! GPUs by their class in the bundled reference dataset.
+
+use crate::analysis::reference::{cpu_reference_tier, gpu_reference_tier, tier_rank};
+use crate::analysis::rules::{classify_gpu, GpuKind};
+use crate::core::domain::{GPUInfo, HardwareConfig};
+use crate::hardware::change::HardwareComponent;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// How an imported component's class compares with the local one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClassDifference {
+    /// The imported component is of a faster class
+    Higher,
+    Same,
+    /// The imported component is of a slower class
+    Lower,
+    /// At least one model is not in the reference dataset
+    Unknown,
+}
+
+/// One component that differs between the imported and local configurations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentDiff {
+    pub component: HardwareComponent,
+    /// Imported and local model, or capacity for memory; "none" when absent
+    pub imported: String,
+    pub local: String,
+    /// Reference tier name, or capacity class for memory
+    pub imported_class: Option<String>,
+    pub local_class: Option<String>,
+    pub difference: ClassDifference,
+    pub description: String,
+}
+
+/// Differences between an imported session's hardware and the local hardware
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HardwareDiff {
+    /// Differing components, in CPU, GPU, memory order
+    pub differences: Vec<ComponentDiff>,
+    /// One-line caption for comparison reports
+    pub caption: String,
+}
+
+/// Compare an imported session's hardware snapshot with the local configuration
+///
+/// The CPU and the primary GPU (the first discrete GPU, else the first GPU)
+/// are compared by model and reference class; memory by capacity and then
+/// speed. Components with identical models are left out.
+pub fn diff_hardware(imported: &HardwareConfig, local: &HardwareConfig) -> HardwareDiff {
+    let mut differences = Vec::new();
+
+    if imported.cpu.model != local.cpu.model {
+        let imported_tier = cpu_reference_tier(&imported.cpu.model).map(|(tier, _)| tier);
+        let local_tier = cpu_reference_tier(&local.cpu.model).map(|(tier, _)| tier);
+        differences.push(component_diff(
+            HardwareComponent::Cpu,
+            format!("{} ({}C/{}T)", imported.cpu.model, imported.cpu.cores, imported.cpu.threads),
+            format!("{} ({}C/{}T)", local.cpu.model, local.cpu.cores, local.cpu.threads),
+            imported_tier.map(|tier| tier.name.clone()),
+            local_tier.map(|tier| tier.name.clone()),
+            compare_ranks(imported_tier.and_then(tier_rank), local_tier.and_then(tier_rank)),
+        ));
+    }
+
+    let imported_gpu = primary_gpu(&imported.gpus);
+    let local_gpu = primary_gpu(&local.gpus);
+    if imported_gpu.map(|g| &g.model) != local_gpu.map(|g| &g.model) {
+        let imported_tier = imported_gpu.and_then(|g| gpu_reference_tier(&g.model)).map(|(tier, _)| tier);
+        let local_tier = local_gpu.and_then(|g| gpu_reference_tier(&g.model)).map(|(tier, _)| tier);
+        let difference = match (imported_gpu, local_gpu) {
+            (Some(_), None) => ClassDifference::Higher,
+            (None, Some(_)) => ClassDifference::Lower,
+            _ => compare_ranks(imported_tier.and_then(tier_rank), local_tier.and_then(tier_rank)),
+        };
+        differences.push(component_diff(
+            HardwareComponent::Gpu,
+            gpu_label(imported_gpu),
+            gpu_label(local_gpu),
+            imported_tier.map(|tier| tier.name.clone()),
+            local_tier.map(|tier| tier.name.clone()),
+            difference,
+        ));
+    }
+
+    let imported_gb = capacity_gb(imported.memory.total_mb);
+    let local_gb = capacity_gb(local.memory.total_mb);
+    let speed_difference = match (imported.memory.speed_mhz, local.memory.speed_mhz) {
+        (Some(imported_speed), Some(local_speed)) => imported_speed.cmp(&local_speed),
+        _ => Ordering::Equal,
+    };
+    if imported_gb != local_gb || speed_difference != Ordering::Equal {
+        let difference = match imported_gb.cmp(&local_gb).then(speed_difference) {
+            Ordering::Greater => ClassDifference::Higher,
+            Ordering::Equal => ClassDifference::Same,
+            Ordering::Less => ClassDifference::Lower,
+        };
+        differences.push(component_diff(
+            HardwareComponent::Memory,
+            memory_label(imported_gb, imported.memory.speed_mhz),
+            memory_label(local_gb, local.memory.speed_mhz),
+            Some(format!("{} GB", imported_gb)),
+            Some(format!("{} GB", local_gb)),
+            difference,
+        ));
+    }
+
+    let caption = if differences.is_empty() {
+        "The imported session ran on the same CPU, GPU and RAM as this PC.".to_string()
+    } else {
+        let parts: Vec<String> = differences
+            .iter()
+            .map(|d| format!("{} {} vs {}", component_name(d.component), d.imported, d.local))
+            .collect();
+        let interpretation = if differences.iter().all(|d| d.difference == ClassDifference::Same) {
+            "the hardware is of the same class, so deltas mostly reflect settings and software"
+        } else {
+            "deltas partly reflect these hardware differences"
+        };
+        format!(
+            "The imported session ran on different hardware than this PC ({}); {}.",
+            parts.join("; "),
+            interpretation
+        )
+    };
+
+    HardwareDiff { differences, caption }
+}
+
+fn component_diff(
+    component: HardwareComponent,
+    imported: String,
+    local: String,
+    imported_class: Option<String>,
+    local_class: Option<String>,
+    difference: ClassDifference,
+) -> ComponentDiff {
+    let name = component_name(component);
+    let mut description = format!(
+        "{}: imported {}{} vs local {}{}",
+        name,
+        imported,
+        class_suffix(component, &imported_class),
+        local,
+        class_suffix(component, &local_class),
+    );
+    match difference {
+        ClassDifference::Higher => description.push_str(&format!(", the imported {} is a higher class", name)),
+        ClassDifference::Lower => description.push_str(&format!(", the imported {} is a lower class", name)),
+        ClassDifference::Same => description.push_str(", same class"),
+        ClassDifference::Unknown => {}
+    }
+    ComponentDiff { component, imported, local, imported_class, local_class, difference, description }
+}
+
+/// Compare reference tier ranks, 0 being the fastest class
+fn compare_ranks(imported: Option<usize>, local: Option<usize>) -> ClassDifference {
+    match (imported, local) {
+        (Some(imported), Some(local)) => match imported.cmp(&local) {
+            Ordering::Less => ClassDifference::Higher,
+            Ordering::Equal => ClassDifference::Same,
+            Ordering::Greater => ClassDifference::Lower,
+        },
+        _ => ClassDifference::Unknown,
+    }
+}
+
+/// First discrete GPU, falling back to the first GPU
+fn primary_gpu(gpus: &[GPUInfo]) -> Option<&GPUInfo> {
+    gpus.iter()
+        .find(|gpu| classify_gpu(gpu) == Some(GpuKind::Discrete))
+        .or_else(|| gpus.first())
+}
+
+fn gpu_label(gpu: Option<&GPUInfo>) -> String {
+    match gpu {
+        Some(gpu) => match gpu.vram_total_mb {
+            Some(vram_mb) => format!("{} ({} GB)", gpu.model, capacity_gb(vram_mb)),
+            None => gpu.model.clone(),
+        },
+        None => "none".to_string(),
+    }
+}
+
+fn memory_label(capacity_gb: u64, speed_mhz: Option<u64>) -> String {
+    match speed_mhz {
+        Some(speed) => format!("{} GB @ {} MHz", capacity_gb, speed),
+        None => format!("{} GB", capacity_gb),
+    }
+}
+
+/// Capacity rounded to whole GB, so 16 GB kits reporting 15.9 GB still match
+fn capacity_gb(total_mb: u64) -> u64 {
+    (total_mb as f64 / 1024.0).round() as u64
+}
+
+/// Reference class in parentheses; memory labels already carry their capacity
+fn class_suffix(component: HardwareComponent, class: &Option<String>) -> String {
+    match class {
+        Some(class) if component != HardwareComponent::Memory => format!(" ({})", class),
+        _ => String::new(),
+    }
+}
+
+fn component_name(component: HardwareComponent) -> &'static str {
+    match component {
+        HardwareComponent::Cpu => "CPU",
+        HardwareComponent::Gpu => "GPU",
+        HardwareComponent::Memory => "RAM",
+        HardwareComponent::Storage => "storage",
+    }
+}
@@ -3,9 +3,25 @@
 //! This module provides functionality for comparing runs and sessions
 //! following AGENT.md Section 6.5.1 and IMPLEMENTATION_PLAN.md Phase 3.1.
 
-use crate::core::domain::{BottleneckAnalysisResult, MetricSample, Run};
+pub mod hardware;
+
+use crate::core::domain::{
+    BottleneckAnalysisResult, HardwareConfig, MetricSample, MetricType, Run, RunEnvironment, Session,
+};
+use crate::metrics::utils::{harmonic_mean, mean, std_dev};
 use std::collections::HashMap;
 
+pub use hardware::{diff_hardware, ClassDifference, ComponentDiff, HardwareDiff};
+
+/// Default smallest change, in percent, reported as significant
+pub const DEFAULT_MIN_EFFECT_PERCENT: f64 = 5.0;
+
+/// Standard deviations of run noise a change must exceed to be significant
+pub const NOISE_STDDEV_MULTIPLIER: f64 = 2.0;
+
+/// Relative difference in background process count treated as material
+pub const BACKGROUND_PROCESS_DIFFERENCE_RATIO: f64 = 0.25;
+
 /// Comparison result between two runs
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ComparisonResult {
@@ -14,17 +30,33 @@ pub struct ComparisonResult {
     pub metric_deltas: HashMap<String, MetricDelta>,
     pub bottleneck_changes: Vec<BottleneckChange>,
     pub summary: String,
+    /// Material differences between the environments the runs were captured in
+    #[serde(default)]
+    pub environment_warnings: Vec<String>,
+    /// Hardware differences when run 1 comes from an imported session, set by
+    /// `compare_imported_run`; omitted from JSON otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hardware_diff: Option<HardwareDiff>,
 }
 
 /// Delta for a specific metric
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MetricDelta {
     pub metric_type: String,
+    /// Average of each run; see `run_average` for how it is taken per metric
     pub run1_avg: f64,
     pub run2_avg: f64,
     pub delta: f64,
     pub delta_percent: f64,
     pub unit: String,
+    /// Within-run standard deviation of each run
+    #[serde(default)]
+    pub run1_stddev: f64,
+    #[serde(default)]
+    pub run2_stddev: f64,
+    /// Whether the change exceeds both the minimum effect size and the run noise
+    #[serde(default)]
+    pub significant: bool,
 }
 
 /// Change in bottleneck between runs
@@ -50,6 +82,19 @@ pub enum BottleneckStatus {
 
 /// Compare two runs
 pub fn compare_runs(run1: &Run, run2: &Run) -> ComparisonResult {
+    compare_runs_with_min_effect(run1, run2, DEFAULT_MIN_EFFECT_PERCENT)
+}
+
+/// Compare two runs, treating changes below `min_effect_percent` as insignificant
+///
+/// A metric is only reported as changed when its delta is at least
+/// `min_effect_percent` and larger than `NOISE_STDDEV_MULTIPLIER` standard
+/// deviations of the noisier run, so ordinary run-to-run fluctuation is not
+/// mistaken for a change.
+///
+/// Averages are arithmetic except for FPS, which is averaged through frame
+/// times (see `run_average`).
+pub fn compare_runs_with_min_effect(run1: &Run, run2: &Run, min_effect_percent: f64) -> ComparisonResult {
     let mut metric_deltas = HashMap::new();
     
     // Compare metrics by type
@@ -61,10 +106,12 @@ pub fn compare_runs(run1: &Run, run2: &Run) -> ComparisonResult {
     let run2_by_type = group_metrics_by_type(&run2_metrics);
     
     // Calculate deltas for common metric types
+    // Metrics without a finite average in both runs are skipped
     for (metric_type, run1_values) in &run1_by_type {
-        if let Some(run2_values) = run2_by_type.get(metric_type) {
-            let run1_avg = run1_values.iter().sum::<f64>() / run1_values.len() as f64;
-            let run2_avg = run2_values.iter().sum::<f64>() / run2_values.len() as f64;
+        let run2_values = run2_by_type.get(metric_type);
+        let averages = run_average(metric_type, run1_values)
+            .zip(run2_values.and_then(|values| run_average(metric_type, values)));
+        if let Some((run1_avg, run2_avg)) = averages {
             let delta = run2_avg - run1_avg;
             let delta_percent = if run1_avg != 0.0 {
                 (delta / run1_avg) * 100.0
@@ -79,6 +126,12 @@ pub fn compare_runs(run1: &Run, run2: &Run) -> ComparisonResult {
                 .map(|m| m.unit.clone())
                 .unwrap_or_else(|| "".to_string());
             
+            let run1_stddev = std_dev(run1_values).unwrap_or(0.0);
+            let run2_stddev = run2_values.and_then(|values| std_dev(values)).unwrap_or(0.0);
+            let noise = run1_stddev.max(run2_stddev);
+            let significant = delta_percent.abs() >= min_effect_percent
+                && delta.abs() > NOISE_STDDEV_MULTIPLIER * noise;
+            
             metric_deltas.insert(
                 metric_type.clone(),
                 MetricDelta {
@@ -88,6 +141,9 @@ pub fn compare_runs(run1: &Run, run2: &Run) -> ComparisonResult {
                     delta,
                     delta_percent,
                     unit,
+                    run1_stddev,
+                    run2_stddev,
+                    significant,
                 },
             );
         }
@@ -108,6 +164,170 @@ pub fn compare_runs(run1: &Run, run2: &Run) -> ComparisonResult {
         metric_deltas,
         bottleneck_changes,
         summary,
+        environment_warnings: environment_warnings(run1, run2),
+        hardware_diff: None,
+    }
+}
+
+/// Compare a run from an imported session with a local run
+///
+/// Like `compare_runs_with_min_effect`, with `hardware_diff` set when the
+/// imported session's hardware differs from `local`. Sessions recorded on
+/// this PC leave it unset.
+pub fn compare_imported_run(
+    run1: &Run,
+    imported_hardware: &HardwareConfig,
+    run2: &Run,
+    local_hardware: &HardwareConfig,
+    min_effect_percent: f64,
+) -> ComparisonResult {
+    let mut result = compare_runs_with_min_effect(run1, run2, min_effect_percent);
+    let diff = diff_hardware(imported_hardware, local_hardware);
+    if !diff.differences.is_empty() {
+        result.hardware_diff = Some(diff);
+    }
+    result
+}
+
+/// Comparison of a session's latest run with the run recorded before it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PreviousRunComparison {
+    pub previous_run_name: String,
+    pub latest_run_name: String,
+    /// Previous run as run 1, latest run as run 2
+    pub comparison: ComparisonResult,
+    /// One-line verdict, e.g. "Better than the previous run (bottleneck severity -20)"
+    pub verdict: String,
+}
+
+/// Compare the two most recently recorded runs of a session
+///
+/// Runs are taken in the order they were added to the session. Returns
+/// `None` when the session has fewer than two runs.
+pub fn compare_to_previous_run(session: &Session, min_effect_percent: f64) -> Option<PreviousRunComparison> {
+    let [.., previous, latest] = session.runs.as_slice() else {
+        return None;
+    };
+    let comparison = compare_runs_with_min_effect(previous, latest, min_effect_percent);
+    let verdict = run_verdict(&comparison);
+    
+    Some(PreviousRunComparison {
+        previous_run_name: previous.name.clone(),
+        latest_run_name: latest.name.clone(),
+        comparison,
+        verdict,
+    })
+}
+
+/// Judge run 2 against run 1 by total bottleneck severity and significant FPS change
+fn run_verdict(comparison: &ComparisonResult) -> String {
+    let severity_change: i32 = comparison
+        .bottleneck_changes
+        .iter()
+        .map(|c| c.severity_delta as i32)
+        .sum();
+    let fps_change = comparison
+        .metric_deltas
+        .get(&format!("{:?}", MetricType::Fps))
+        .filter(|d| d.significant)
+        .map(|d| d.delta_percent);
+    
+    let mut reasons = Vec::new();
+    if severity_change != 0 {
+        reasons.push(format!("bottleneck severity {:+}", severity_change));
+    }
+    if let Some(percent) = fps_change {
+        reasons.push(format!("FPS {:+.1}%", percent));
+    }
+    
+    let better = severity_change < 0 || fps_change.is_some_and(|p| p > 0.0);
+    let worse = severity_change > 0 || fps_change.is_some_and(|p| p < 0.0);
+    match (better, worse) {
+        (true, false) => format!("Better than the previous run ({})", reasons.join(", ")),
+        (false, true) => format!("Worse than the previous run ({})", reasons.join(", ")),
+        (true, true) => format!("Mixed results against the previous run ({})", reasons.join(", ")),
+        (false, false) => "No significant change from the previous run".to_string(),
+    }
+}
+
+/// Describe environment differences that make two runs hard to compare
+///
+/// Runs saved before environments were captured produce no warnings, and
+/// fields unknown in either run are skipped.
+pub fn environment_warnings(run1: &Run, run2: &Run) -> Vec<String> {
+    match (&run1.environment, &run2.environment) {
+        (Some(env1), Some(env2)) => environment_differences(env1, env2),
+        _ => Vec::new(),
+    }
+}
+
+/// List material differences between two run environments
+pub fn environment_differences(env1: &RunEnvironment, env2: &RunEnvironment) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if let (Some(os1), Some(os2)) = (&env1.os_version, &env2.os_version) {
+        if os1 != os2 {
+            warnings.push(format!("Operating system changed from {} to {}", os1, os2));
+        }
+    }
+
+    if !env1.gpu_driver_versions.is_empty()
+        && !env2.gpu_driver_versions.is_empty()
+        && env1.gpu_driver_versions != env2.gpu_driver_versions
+    {
+        warnings.push(format!(
+            "GPU driver changed from {} to {}",
+            env1.gpu_driver_versions.join(", "),
+            env2.gpu_driver_versions.join(", ")
+        ));
+    }
+
+    if let (Some(plan1), Some(plan2)) = (&env1.power_plan, &env2.power_plan) {
+        if plan1 != plan2 {
+            warnings.push(format!("Power plan changed from {} to {}", plan1, plan2));
+        }
+    }
+
+    if let (Some(count1), Some(count2)) = (env1.background_process_count, env2.background_process_count) {
+        let larger = count1.max(count2);
+        if larger > 0 && count1.abs_diff(count2) as f64 / larger as f64 > BACKGROUND_PROCESS_DIFFERENCE_RATIO {
+            warnings.push(format!(
+                "Background process count changed from {} to {}",
+                count1, count2
+            ));
+        }
+    }
+
+    if let (Some(vm1), Some(vm2)) = (env1.virtual_machine, env2.virtual_machine) {
+        if vm1 != vm2 {
+            let describe = |vm: bool| if vm { "a virtual machine" } else { "bare metal" };
+            warnings.push(format!(
+                "Run 1 was captured on {} and run 2 on {}",
+                describe(vm1),
+                describe(vm2)
+            ));
+        }
+    }
+
+    warnings
+}
+
+/// Average of one metric's values in a run
+///
+/// | Metric | Aggregation |
+/// |---|---|
+/// | `Fps` | Harmonic mean, i.e. mean frame time inverted |
+/// | `FrameTime` and all others | Arithmetic mean |
+///
+/// An arithmetic mean of FPS readings overweights fast frames: a frame at
+/// 30 FPS and one at 120 FPS take 41.7 ms for two frames, 48 FPS, where the
+/// plain mean reports 75 FPS. Frame times already weigh each frame by its
+/// duration, so they keep the arithmetic mean.
+pub fn run_average(metric_type: &str, values: &[f64]) -> Option<f64> {
+    if metric_type == format!("{:?}", MetricType::Fps) {
+        harmonic_mean(values.iter().copied())
+    } else {
+        mean(values.iter().copied())
     }
 }
 
@@ -129,6 +349,9 @@ fn group_metrics_by_type(metrics: &[MetricSample]) -> HashMap<String, Vec<f64>>
 }
 
 /// Compare bottlenecks between two analysis results
+///
+/// Changes follow run 2's bottleneck order (most severe first), then
+/// bottlenecks resolved since run 1 in run 1's order.
 fn compare_bottlenecks(
     result1: Option<&BottleneckAnalysisResult>,
     result2: Option<&BottleneckAnalysisResult>,
@@ -153,12 +376,16 @@ fn compare_bottlenecks(
         })
         .unwrap_or_default();
     
-    // Check all bottleneck types
-    let all_types: std::collections::HashSet<String> = bottlenecks1
-        .keys()
-        .chain(bottlenecks2.keys())
-        .cloned()
-        .collect();
+    // Check all bottleneck types, each once, in analysis order
+    let mut all_types: Vec<String> = Vec::new();
+    for result in [result2, result1].into_iter().flatten() {
+        for bottleneck in &result.bottlenecks {
+            let bottleneck_type = format!("{:?}", bottleneck.bottleneck_type);
+            if !all_types.contains(&bottleneck_type) {
+                all_types.push(bottleneck_type);
+            }
+        }
+    }
     
     for bottleneck_type in all_types {
         let severity1 = bottlenecks1.get(&bottleneck_type).copied();
@@ -208,7 +435,7 @@ fn generate_comparison_summary(
     // Summarize metric changes
     let significant_deltas: Vec<&MetricDelta> = metric_deltas
         .values()
-        .filter(|d| d.delta_percent.abs() > 5.0) // >5% change
+        .filter(|d| d.significant)
         .collect();
     
     if !significant_deltas.is_empty() {
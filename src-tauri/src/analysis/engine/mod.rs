@@ -3,16 +3,60 @@
 //! This module implements the main bottleneck analysis engine following
 //! AGENT.md Section 6.4.
 
-use crate::core::domain::{BottleneckAnalysisResult, MetricSample, WorkloadProfile};
-use crate::analysis::rules;
+use crate::core::domain::{BottleneckAnalysisResult, HardwareConfig, MetricSample, WorkloadProfile};
+use crate::core::settings::IdleBaseline;
+use crate::analysis::{baseline, rules};
+use crate::analysis::rules::{
+    BottleneckRule, CpuClockThrottleRule, FrameCapRule, HeterogeneousCoreRule, HybridGraphicsRule, RuleTrace,
+};
+use chrono::{DateTime, Utc};
 
 /// Bottleneck analysis engine
-pub struct AnalysisEngine;
+pub struct AnalysisEngine {
+    rules: Vec<Box<dyn BottleneckRule>>,
+}
 
 impl AnalysisEngine {
-    /// Create a new analysis engine
+    /// Create a new analysis engine with the built-in rules
     pub fn new() -> Self {
-        Self
+        Self::with_rules(rules::default_rules())
+    }
+    
+    /// Create an analysis engine that evaluates exactly the given rules
+    pub fn with_rules(rules: Vec<Box<dyn BottleneckRule>>) -> Self {
+        Self { rules }
+    }
+    
+    /// Create an analysis engine with the built-in rules and those that
+    /// depend on the given hardware
+    ///
+    /// Hybrid core, clock, frame cap, and hybrid graphics analysis need the
+    /// CPU, displays, and GPUs the metrics were recorded on.
+    pub fn for_hardware(hardware: &HardwareConfig) -> Self {
+        let mut engine = Self::new();
+        if let Some(rule) = HeterogeneousCoreRule::from_cpu(&hardware.cpu) {
+            engine.register_rule(Box::new(rule));
+        }
+        if let Some(rule) = CpuClockThrottleRule::from_cpu(&hardware.cpu) {
+            engine.register_rule(Box::new(rule));
+        }
+        if let Some(rule) = FrameCapRule::from_displays(&hardware.displays) {
+            engine.register_rule(Box::new(rule));
+        }
+        if let Some(rule) = HybridGraphicsRule::from_gpus(&hardware.gpus) {
+            engine.register_rule(Box::new(rule));
+        }
+        engine
+    }
+    
+    /// Register an additional rule, evaluated after the existing ones
+    pub fn register_rule(&mut self, rule: Box<dyn BottleneckRule>) {
+        self.rules.push(rule);
+    }
+    
+    /// Names of the registered rules in evaluation order
+    pub fn rule_names(&self) -> Vec<String> {
+        self.rules.iter().map(|r| r.name().to_string()).collect()
     }
     
     /// Analyze metrics and detect bottlenecks
@@ -27,7 +71,72 @@ impl AnalysisEngine {
         profile: Option<&WorkloadProfile>,
     ) -> BottleneckAnalysisResult {
         let window = time_window_seconds.unwrap_or(rules::SUSTAINED_WINDOW_SECONDS);
-        rules::analyze_bottlenecks(metrics, window, profile)
+        rules::analyze_bottlenecks_with_rules(metrics, window, profile, &self.rules)
+    }
+    
+    /// Analyze metrics in a window ending at an explicit reference time
+    pub fn analyze_at(
+        &self,
+        metrics: &[MetricSample],
+        time_window_seconds: Option<i64>,
+        profile: Option<&WorkloadProfile>,
+        window_end: DateTime<Utc>,
+    ) -> BottleneckAnalysisResult {
+        let window = time_window_seconds.unwrap_or(rules::SUSTAINED_WINDOW_SECONDS);
+        rules::analyze_bottlenecks_at(metrics, window, profile, &self.rules, window_end)
+    }
+    
+    /// Explain every rule's decision for the window ending at `window_end`
+    ///
+    /// Runs the same rules as `analyze_at`, recording their inputs. Meant for
+    /// debugging disputed verdicts, not for regular analysis.
+    pub fn trace_at(
+        &self,
+        metrics: &[MetricSample],
+        time_window_seconds: Option<i64>,
+        profile: Option<&WorkloadProfile>,
+        window_end: DateTime<Utc>,
+    ) -> Vec<RuleTrace> {
+        let window = time_window_seconds.unwrap_or(rules::SUSTAINED_WINDOW_SECONDS);
+        rules::trace_bottlenecks_at(metrics, window, profile, &self.rules, window_end)
+    }
+    
+    /// Analyze previously recorded metrics
+    ///
+    /// The window ends at the newest sample rather than the current time, so
+    /// saved runs are analyzed the same way they would have been live.
+    pub fn analyze_recorded(
+        &self,
+        metrics: &[MetricSample],
+        time_window_seconds: Option<i64>,
+        profile: Option<&WorkloadProfile>,
+    ) -> BottleneckAnalysisResult {
+        let window_end = metrics
+            .iter()
+            .map(|m| m.timestamp)
+            .max()
+            .unwrap_or_else(Utc::now);
+        self.analyze_at(metrics, time_window_seconds, profile, window_end)
+    }
+    
+    /// Analyze metrics after subtracting an idle baseline
+    ///
+    /// Reports the load attributable to the workload rather than the total
+    /// system load. Without a baseline this is equivalent to `analyze`.
+    pub fn analyze_with_baseline(
+        &self,
+        metrics: &[MetricSample],
+        time_window_seconds: Option<i64>,
+        profile: Option<&WorkloadProfile>,
+        idle_baseline: Option<&IdleBaseline>,
+    ) -> BottleneckAnalysisResult {
+        match idle_baseline {
+            Some(idle) => {
+                let adjusted = baseline::subtract_baseline(metrics, idle);
+                self.analyze(&adjusted, time_window_seconds, profile)
+            }
+            None => self.analyze(metrics, time_window_seconds, profile),
+        }
     }
 }
 
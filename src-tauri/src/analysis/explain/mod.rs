@@ -0,0 +1,97 @@
+//! One-call analysis for library consumers
+//!
+//! Runs the analysis engine on raw samples and bundles the bottlenecks,
+//! insights, subsystem grades, health score and primary bottleneck, so
+//! integrators embedding the crate without Tauri do not have to combine the
+//! individual modules themselves.
+
+use crate::analysis::baseline::subtract_baseline;
+use crate::analysis::insights::{
+    generate_acknowledged_insights, grade_subsystems, is_acknowledged, BottleneckAcknowledgment,
+    UserFacingInsights,
+};
+use crate::analysis::trend::SessionTrend;
+use crate::analysis::AnalysisEngine;
+use crate::core::domain::{
+    Bottleneck, BottleneckAnalysisResult, BottleneckType, HardwareConfig, MetricSample, WorkloadProfile,
+};
+use crate::core::settings::IdleBaseline;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Options for `analyze_and_explain`
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisConfig {
+    /// Analysis window ending at the newest sample; `None` analyzes every sample
+    pub time_window_seconds: Option<i64>,
+    /// Idle load subtracted from the samples before analysis
+    pub idle_baseline: Option<IdleBaseline>,
+    /// Bottlenecks to dismiss from the insights and health score
+    pub acknowledgments: Vec<BottleneckAcknowledgment>,
+    /// Temperature history used to tailor thermal recommendations
+    pub trends: Vec<SessionTrend>,
+}
+
+/// Everything an integrator needs to present the analysis of a capture
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisReport {
+    /// Bottlenecks found by the engine, most severe first
+    pub analysis: BottleneckAnalysisResult,
+    pub insights: UserFacingInsights,
+    /// A–F grade per subsystem with data
+    pub grades: HashMap<BottleneckType, char>,
+    /// 0-100, 100 minus the severity of the worst active bottleneck
+    pub health_score: u8,
+    /// Most severe bottleneck that was not dismissed
+    pub primary_bottleneck: Option<Bottleneck>,
+}
+
+/// Analyze raw samples and explain the result in one call
+///
+/// Uses the engine for the given hardware, with the window ending at the
+/// newest sample as for saved runs. Acknowledged bottlenecks stay in
+/// `analysis` but do not count towards the insights, health score or primary
+/// bottleneck.
+pub fn analyze_and_explain(
+    metrics: &[MetricSample],
+    hardware: &HardwareConfig,
+    profile: &WorkloadProfile,
+    config: &AnalysisConfig,
+) -> AnalysisReport {
+    let adjusted;
+    let metrics = match &config.idle_baseline {
+        Some(idle) => {
+            adjusted = subtract_baseline(metrics, idle);
+            &adjusted[..]
+        }
+        None => metrics,
+    };
+
+    let window = config.time_window_seconds.or_else(|| {
+        let earliest = metrics.iter().map(|m| m.timestamp).min()?;
+        let latest = metrics.iter().map(|m| m.timestamp).max()?;
+        Some((latest - earliest).num_seconds() + 1)
+    });
+    let analysis = AnalysisEngine::for_hardware(hardware).analyze_recorded(metrics, window, Some(profile));
+
+    let insights = generate_acknowledged_insights(
+        &analysis,
+        Some(profile),
+        &config.trends,
+        Some(hardware),
+        &config.acknowledgments,
+    );
+    let primary_bottleneck = analysis
+        .bottlenecks
+        .iter()
+        .find(|b| !is_acknowledged(b, &config.acknowledgments))
+        .cloned();
+
+    AnalysisReport {
+        grades: grade_subsystems(&analysis, hardware, metrics),
+        health_score: 100u8.saturating_sub(insights.severity),
+        primary_bottleneck,
+        insights,
+        analysis,
+    }
+}
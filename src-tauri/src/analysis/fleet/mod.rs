@@ -0,0 +1,98 @@
+//! Issue summaries across many sessions
+//!
+//! Admins looking after several machines store one or more sessions per rig
+//! and want to know which rigs need attention. Each session is reduced to the
+//! worst bottleneck of each of its runs, and only sessions with a bottleneck
+//! at or above the requested severity are reported.
+
+use crate::analysis::rules::sort_bottlenecks;
+use crate::analysis::AnalysisEngine;
+use crate::core::domain::{BottleneckType, HardwareConfig, Session};
+use crate::core::sessions::analyze_run;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use uuid::Uuid;
+
+/// Worst bottleneck of one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunIssue {
+    pub run_id: Uuid,
+    pub run_name: String,
+    pub bottleneck_type: BottleneckType,
+    pub severity: u8,
+    pub summary: String,
+}
+
+/// Session with at least one run over the severity threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionIssueSummary {
+    pub session_id: Uuid,
+    /// Sessions are listed under their profile's name
+    pub session_name: String,
+    pub start_time: DateTime<Utc>,
+    /// CPU, GPUs, memory and platform of the machine the session was recorded on
+    pub hardware: String,
+    pub worst_severity: u8,
+    /// One line describing the worst issue and how many runs are affected
+    pub summary: String,
+    /// Runs over the threshold, most severe first
+    pub runs: Vec<RunIssue>,
+}
+
+/// Summarize the runs of a session whose worst bottleneck reaches `min_severity`
+///
+/// Runs keep their saved analysis; runs saved without one are analyzed with
+/// the rules for the session's hardware. Returns `None` when no run reaches
+/// the threshold.
+pub fn summarize_session_issues(session: &Session, min_severity: u8) -> Option<SessionIssueSummary> {
+    let engine = AnalysisEngine::for_hardware(&session.hardware_config_snapshot);
+    let mut runs: Vec<RunIssue> = session
+        .runs
+        .iter()
+        .filter_map(|run| {
+            let mut bottlenecks = match &run.analysis_result {
+                Some(result) => result.bottlenecks.clone(),
+                None => analyze_run(&engine, run, &session.profile, None)?.bottlenecks,
+            };
+            // Results saved by older versions are not sorted
+            sort_bottlenecks(&mut bottlenecks);
+            let worst = bottlenecks.into_iter().next()?;
+            (worst.severity >= min_severity).then(|| RunIssue {
+                run_id: run.id,
+                run_name: run.name.clone(),
+                bottleneck_type: worst.bottleneck_type,
+                severity: worst.severity,
+                summary: worst.summary,
+            })
+        })
+        .collect();
+    runs.sort_by_key(|run| Reverse(run.severity));
+
+    let worst = runs.first()?;
+    let summary = format!(
+        "{} (severity {}) in {}; {} of {} runs affected",
+        worst.summary,
+        worst.severity,
+        worst.run_name,
+        runs.len(),
+        session.runs.len()
+    );
+    Some(SessionIssueSummary {
+        session_id: session.id,
+        session_name: session.profile.name.clone(),
+        start_time: session.start_time,
+        hardware: hardware_identity(&session.hardware_config_snapshot),
+        worst_severity: worst.severity,
+        summary,
+        runs,
+    })
+}
+
+/// Short description of a machine, e.g. "Ryzen 7 7800X3D / RTX 4080 / 32 GB RAM (Windows)"
+pub fn hardware_identity(hardware: &HardwareConfig) -> String {
+    let mut parts = vec![hardware.cpu.model.clone()];
+    parts.extend(hardware.gpus.iter().map(|gpu| gpu.model.clone()));
+    parts.push(format!("{} GB RAM", (hardware.memory.total_mb as f64 / 1024.0).round()));
+    format!("{} ({})", parts.join(" / "), hardware.metadata.platform)
+}
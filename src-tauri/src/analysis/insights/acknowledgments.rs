@@ -0,0 +1,62 @@
+//! Acknowledged bottlenecks
+//!
+//! Some bottlenecks are expected on a given machine, such as a thin laptop
+//! that is GPU-bound by design. Acknowledging one keeps insights, reports,
+//! and live alerts from re-surfacing it. Analysis results still record every
+//! bottleneck; acknowledgments only decide which ones are shown as dismissed.
+
+use crate::core::domain::{Bottleneck, BottleneckAnalysisResult, BottleneckType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A bottleneck type the user accepts on this machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BottleneckAcknowledgment {
+    pub bottleneck_type: BottleneckType,
+    /// Highest severity (0-100) covered; `None` covers every severity
+    #[serde(default)]
+    pub max_severity: Option<u8>,
+    /// Why the bottleneck is acceptable, e.g. "GPU-bound by design"
+    #[serde(default)]
+    pub note: Option<String>,
+    pub acknowledged_at: DateTime<Utc>,
+}
+
+impl BottleneckAcknowledgment {
+    /// Whether this acknowledgment covers the bottleneck
+    ///
+    /// A bottleneck more severe than the ceiling is not covered, so a
+    /// problem that gets worse is surfaced again.
+    pub fn covers(&self, bottleneck: &Bottleneck) -> bool {
+        if self.bottleneck_type != bottleneck.bottleneck_type {
+            return false;
+        }
+        match self.max_severity {
+            Some(max) => bottleneck.severity <= max,
+            None => true,
+        }
+    }
+}
+
+/// Whether any acknowledgment covers the bottleneck
+pub fn is_acknowledged(bottleneck: &Bottleneck, acknowledgments: &[BottleneckAcknowledgment]) -> bool {
+    acknowledgments.iter().any(|a| a.covers(bottleneck))
+}
+
+/// Split a result into its unacknowledged bottlenecks and the dismissed ones
+pub fn split_acknowledged(
+    result: &BottleneckAnalysisResult,
+    acknowledgments: &[BottleneckAcknowledgment],
+) -> (BottleneckAnalysisResult, Vec<Bottleneck>) {
+    let (dismissed, active): (Vec<Bottleneck>, Vec<Bottleneck>) = result
+        .bottlenecks
+        .iter()
+        .cloned()
+        .partition(|b| is_acknowledged(b, acknowledgments));
+    let active = BottleneckAnalysisResult {
+        bottlenecks: active,
+        timestamp: result.timestamp,
+        coverage: result.coverage.clone(),
+    };
+    (active, dismissed)
+}
@@ -0,0 +1,161 @@
+//! Foreground application attribution
+//!
+//! For gaming and rendering, the load of the focused application matters more
+//! than the load of the whole system. Comparing the application's own CPU and
+//! GPU usage with the system totals tells whether it is limited by its own
+//! work or contended by other processes.
+
+use crate::core::domain::{ForegroundProcess, MetricSample, MetricType};
+use crate::metrics::utils::mean;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// System utilization above which a resource counts as busy (percent)
+pub const CONTENDED_SYSTEM_MIN_PERCENT: f64 = 50.0;
+
+/// Share of a busy resource the application must account for to be the one limiting it (percent)
+pub const APP_LIMITED_MIN_SHARE_PERCENT: f64 = 60.0;
+
+/// Use of one resource by the foreground application
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceAttribution {
+    /// Average utilization of the whole system, 0-100
+    pub system_percent: f64,
+    /// Average utilization caused by the application, 0-100
+    pub process_percent: f64,
+    /// Share of the system's utilization caused by the application, 0-100
+    pub share_percent: f64,
+}
+
+/// Whether the application or the rest of the system drives the load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForegroundScenario {
+    /// The application accounts for most of the load on every busy resource
+    AppLimited,
+    /// Other processes take a large share of a busy resource
+    SystemContended,
+}
+
+/// CPU and GPU use attributed to the foreground application
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForegroundAttribution {
+    pub process: ForegroundProcess,
+    /// `None` when per-process CPU usage was not sampled
+    pub cpu: Option<ResourceAttribution>,
+    /// `None` when per-process GPU usage was not sampled
+    pub gpu: Option<ResourceAttribution>,
+    pub scenario: ForegroundScenario,
+}
+
+impl ForegroundAttribution {
+    /// Resources that are busy but mostly used by other processes
+    pub fn contended_resources(&self) -> Vec<&'static str> {
+        [("CPU", &self.cpu), ("GPU", &self.gpu)]
+            .into_iter()
+            .filter_map(|(label, resource)| resource.as_ref().filter(|r| is_contended(r)).map(|_| label))
+            .collect()
+    }
+
+    /// One-line description, e.g. "game.exe used 40% of total CPU (85% of all CPU use)."
+    pub fn describe(&self) -> String {
+        let name = self.process_name();
+        let uses: Vec<String> = [("CPU", &self.cpu), ("GPU", &self.gpu)]
+            .into_iter()
+            .filter_map(|(label, resource)| {
+                resource.as_ref().map(|r| {
+                    format!("{:.0}% of total {} ({:.0}% of all {} use)", r.process_percent, label, r.share_percent, label)
+                })
+            })
+            .collect();
+        let conclusion = match self.scenario {
+            ForegroundScenario::AppLimited => "performance is limited by the application itself".to_string(),
+            ForegroundScenario::SystemContended => format!(
+                "other processes are competing for the {}",
+                self.contended_resources().join(" and ")
+            ),
+        };
+        format!("{} used {}; {}.", name, uses.join(" and "), conclusion)
+    }
+
+    /// Executable name of the application, or its PID when unknown
+    pub fn process_name(&self) -> String {
+        match &self.process.name {
+            Some(name) => name.clone(),
+            None => format!("pid {}", self.process.pid),
+        }
+    }
+}
+
+/// Attribute CPU and GPU usage to the foreground application
+///
+/// Per-process samples only cover the busiest processes each tick, so the
+/// application counts as idle in sampled ticks where it is missing. Returns
+/// `None` when neither resource has both per-process and system samples.
+pub fn attribute_foreground(metrics: &[MetricSample], foreground: &ForegroundProcess) -> Option<ForegroundAttribution> {
+    let cpu = attribute_resource(metrics, foreground.pid, MetricType::CpuUtilization, MetricType::ProcessCpuUtilization);
+    let gpu = attribute_resource(metrics, foreground.pid, MetricType::GpuUtilization, MetricType::ProcessGpuUtilization);
+    if cpu.is_none() && gpu.is_none() {
+        return None;
+    }
+
+    let contended = [&cpu, &gpu].into_iter().flatten().any(is_contended);
+    Some(ForegroundAttribution {
+        process: foreground.clone(),
+        cpu,
+        gpu,
+        scenario: if contended {
+            ForegroundScenario::SystemContended
+        } else {
+            ForegroundScenario::AppLimited
+        },
+    })
+}
+
+/// Whether a resource is busy while the application accounts for little of it
+fn is_contended(resource: &ResourceAttribution) -> bool {
+    resource.system_percent >= CONTENDED_SYSTEM_MIN_PERCENT && resource.share_percent < APP_LIMITED_MIN_SHARE_PERCENT
+}
+
+fn attribute_resource(
+    metrics: &[MetricSample],
+    pid: u32,
+    system_type: MetricType,
+    process_type: MetricType,
+) -> Option<ResourceAttribution> {
+    let process_samples: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| m.metric_type == process_type && m.value.is_finite())
+        .collect();
+    let ticks: HashSet<_> = process_samples.iter().map(|m| m.timestamp).collect();
+    if ticks.is_empty() {
+        return None;
+    }
+    let process_total: f64 = process_samples
+        .iter()
+        .filter(|m| is_process_source(&m.source_component, pid))
+        .map(|m| m.value)
+        .sum();
+    let process_percent = process_total / ticks.len() as f64;
+
+    let system_percent = mean(
+        metrics
+            .iter()
+            .filter(|m| m.metric_type == system_type && m.value.is_finite())
+            .map(|m| m.value),
+    )?;
+    if system_percent <= 0.0 {
+        return None;
+    }
+
+    Some(ResourceAttribution {
+        system_percent,
+        process_percent,
+        share_percent: (process_percent / system_percent * 100.0).min(100.0),
+    })
+}
+
+/// Whether a per-process sample source, e.g. "game.exe (pid 1234)" or "pid 1234", names `pid`
+fn is_process_source(source: &str, pid: u32) -> bool {
+    source == format!("pid {}", pid) || source.ends_with(&format!("(pid {})", pid))
+}
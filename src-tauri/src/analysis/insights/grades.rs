@@ -0,0 +1,129 @@
+//! Subsystem letter grades
+//!
+//! Maps analysis results to A–F grades per subsystem for casual users.
+
+use crate::core::domain::{
+    BottleneckAnalysisResult, BottleneckType, HardwareConfig, MetricSample, MetricType,
+};
+use std::collections::HashMap;
+
+/// Subsystems that receive a grade, in display order
+pub const GRADED_SUBSYSTEMS: [BottleneckType; 5] = [
+    BottleneckType::Cpu,
+    BottleneckType::Gpu,
+    BottleneckType::Ram,
+    BottleneckType::Thermal,
+    BottleneckType::Storage,
+];
+
+/// Grade subsystems from bottleneck severity
+///
+/// Subsystems without a bottleneck grade A. Thermal is additionally graded
+/// on the peak measured temperature, so a hot system is marked down even if
+/// no thermal bottleneck fired. Subsystems with no data (no GPU detected,
+/// no temperature sensors, ...) are omitted; see `grade_label`.
+pub fn grade_subsystems(
+    result: &BottleneckAnalysisResult,
+    hardware: &HardwareConfig,
+    metrics: &[MetricSample],
+) -> HashMap<BottleneckType, char> {
+    let mut grades = HashMap::new();
+    
+    for subsystem in GRADED_SUBSYSTEMS {
+        // VRAM pressure counts against the GPU, a starved input pipeline against storage
+        let severity = result
+            .bottlenecks
+            .iter()
+            .filter(|b| {
+                b.bottleneck_type == subsystem
+                    || (subsystem == BottleneckType::Gpu && b.bottleneck_type == BottleneckType::Vram)
+                    || (subsystem == BottleneckType::Storage
+                        && b.bottleneck_type == BottleneckType::InputPipeline)
+            })
+            .map(|b| b.severity)
+            .max();
+        
+        let has_data = severity.is_some()
+            || match subsystem {
+                BottleneckType::Gpu => !hardware.gpus.is_empty(),
+                BottleneckType::Storage => !hardware.storage_devices.is_empty(),
+                BottleneckType::Thermal => peak_temperature(metrics).is_some(),
+                _ => true,
+            };
+        if !has_data {
+            continue;
+        }
+        
+        let mut grade = severity_grade(severity.unwrap_or(0));
+        if subsystem == BottleneckType::Thermal {
+            if let Some(peak) = peak_temperature(metrics) {
+                grade = grade.max(temperature_grade(peak));
+            }
+        }
+        grades.insert(subsystem, grade);
+    }
+    
+    grades
+}
+
+/// Letter grade for a bottleneck severity (0 = no bottleneck)
+pub fn severity_grade(severity: u8) -> char {
+    match severity {
+        0 => 'A',
+        1..=24 => 'B',
+        25..=49 => 'C',
+        50..=74 => 'D',
+        _ => 'F',
+    }
+}
+
+/// Letter grade for a peak component temperature in °C
+pub fn temperature_grade(celsius: f64) -> char {
+    if celsius < 70.0 {
+        'A'
+    } else if celsius < 80.0 {
+        'B'
+    } else if celsius < 85.0 {
+        'C'
+    } else if celsius < 90.0 {
+        'D'
+    } else {
+        'F'
+    }
+}
+
+/// Display label for a subsystem's grade, "N/A" when it was not graded
+pub fn grade_label(grades: &HashMap<BottleneckType, char>, subsystem: &BottleneckType) -> String {
+    grades
+        .get(subsystem)
+        .map(|g| g.to_string())
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// Display name of a graded subsystem
+pub fn subsystem_name(subsystem: &BottleneckType) -> &'static str {
+    match subsystem {
+        BottleneckType::Cpu => "CPU",
+        BottleneckType::Gpu => "GPU",
+        BottleneckType::Ram => "RAM",
+        BottleneckType::Vram => "VRAM",
+        BottleneckType::Storage => "Storage",
+        BottleneckType::Thermal => "Thermal",
+        BottleneckType::Bandwidth => "Bandwidth",
+        BottleneckType::Responsiveness => "Responsiveness",
+        BottleneckType::InputPipeline => "Input Pipeline",
+        BottleneckType::FrameCap => "Frame Cap",
+        BottleneckType::CombinedLoad => "Combined Load",
+        BottleneckType::BackgroundDownload => "Background Download",
+        BottleneckType::HybridGraphics => "Hybrid Graphics",
+    }
+}
+
+/// Highest CPU or GPU temperature in the samples
+fn peak_temperature(metrics: &[MetricSample]) -> Option<f64> {
+    metrics
+        .iter()
+        .filter(|m| matches!(m.metric_type, MetricType::Temperature | MetricType::GpuTemperature))
+        .map(|m| m.value)
+        .reduce(f64::max)
+}
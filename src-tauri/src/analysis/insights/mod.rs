@@ -3,27 +3,261 @@
 //! This module converts bottleneck analysis results into human-readable
 //! insights and actionable recommendations following AGENT.md Section 6.4.4.
 
-use crate::core::domain::{Bottleneck, BottleneckAnalysisResult, WorkloadProfile, WorkloadType};
-use serde::{Deserialize, Serialize};
+pub mod acknowledgments;
+pub mod foreground;
+pub mod grades;
+
+use crate::analysis::memory::{detect_channel_configuration, platform_channel_count};
+use crate::analysis::trend::{CoolingDiagnosis, SessionTrend};
+use crate::core::domain::{
+    Bottleneck, BottleneckAnalysisResult, BottleneckType, HardwareConfig, StorageInfo, StorageType,
+    WorkloadProfile, WorkloadType,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+
+pub use acknowledgments::{is_acknowledged, split_acknowledged, BottleneckAcknowledgment};
+pub use foreground::{attribute_foreground, ForegroundAttribution, ForegroundScenario, ResourceAttribution};
+pub use grades::{grade_label, grade_subsystems, subsystem_name, GRADED_SUBSYSTEMS};
 
 /// User-facing insights generated from analysis results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserFacingInsights {
     pub summary: String,
-    pub recommendations: Vec<String>,
+    #[serde(deserialize_with = "deserialize_recommendations")]
+    pub recommendations: Vec<Recommendation>,
     pub severity: u8, // 0-100, highest severity from bottlenecks
+    /// Acknowledged bottlenecks, left out of the summary and recommendations
+    #[serde(default)]
+    pub dismissed: Vec<Bottleneck>,
+    /// Load of the focused application, when it could be attributed
+    #[serde(default)]
+    pub foreground: Option<ForegroundAttribution>,
+}
+
+impl UserFacingInsights {
+    /// Add how much of the load the focused application caused
+    ///
+    /// The summary gains the application's share of CPU and GPU use. When
+    /// other processes compete for a busy resource, closing them is
+    /// recommended.
+    pub fn with_foreground_attribution(mut self, attribution: ForegroundAttribution) -> Self {
+        self.summary = format!("{} {}", self.summary, attribution.describe());
+        if attribution.scenario == ForegroundScenario::SystemContended {
+            self.recommendations.push(Recommendation {
+                id: "foreground-contention".to_string(),
+                bottleneck_type: None,
+                priority: self.severity,
+                category: RecommendationCategory::Config,
+                text: format!(
+                    "Close background applications using the {} while {} runs; they take resources the application could use.",
+                    attribution.contended_resources().join(" and "),
+                    attribution.process_name()
+                ),
+                difficulty: RecommendationDifficulty::Easy,
+            });
+        }
+        self.foreground = Some(attribution);
+        self
+    }
+}
+
+/// A single actionable recommendation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recommendation {
+    /// Stable identifier within one set of insights, e.g. "cpu-0-2"
+    pub id: String,
+    /// Bottleneck this recommendation addresses, if any
+    pub bottleneck_type: Option<BottleneckType>,
+    /// 0-100, the severity of the bottleneck it addresses
+    pub priority: u8,
+    pub category: RecommendationCategory,
+    pub text: String,
+    pub difficulty: RecommendationDifficulty,
+}
+
+impl Recommendation {
+    /// Recommendation text as shown to the user
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Kind of change a recommendation asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecommendationCategory {
+    /// Buy or replace hardware
+    Upgrade,
+    /// Change settings, software, or usage habits
+    Config,
+    /// Improve or maintain cooling
+    Cooling,
+}
+
+/// Effort needed to follow a recommendation, ordered easiest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecommendationDifficulty {
+    Easy,
+    Moderate,
+    Advanced,
+}
+
+/// Category, difficulty, and text of a recommendation before it is tied to a bottleneck
+type RecommendationTemplate = (RecommendationCategory, RecommendationDifficulty, &'static str);
+
+/// Accept both structured recommendations and the bare strings written by older versions
+fn deserialize_recommendations<'de, D>(deserializer: D) -> Result<Vec<Recommendation>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StoredRecommendation {
+        Structured(Recommendation),
+        Text(String),
+    }
+    
+    let stored = Vec::<StoredRecommendation>::deserialize(deserializer)?;
+    Ok(stored
+        .into_iter()
+        .enumerate()
+        .map(|(idx, rec)| match rec {
+            StoredRecommendation::Structured(rec) => rec,
+            StoredRecommendation::Text(text) => Recommendation {
+                id: format!("legacy-{}", idx),
+                bottleneck_type: None,
+                priority: 0,
+                category: RecommendationCategory::Config,
+                text,
+                difficulty: RecommendationDifficulty::Moderate,
+            },
+        })
+        .collect())
 }
 
 /// Generate user-facing insights from analysis results
 pub fn generate_insights(
     result: &BottleneckAnalysisResult,
     profile: Option<&WorkloadProfile>,
+) -> UserFacingInsights {
+    generate_insights_with_trends(result, profile, &[])
+}
+
+/// Generate user-facing insights, using temperature history across sessions
+///
+/// Thermal recommendations are tailored when a trend for the affected
+/// temperature shows degrading or undersized cooling.
+pub fn generate_insights_with_trends(
+    result: &BottleneckAnalysisResult,
+    profile: Option<&WorkloadProfile>,
+    trends: &[SessionTrend],
+) -> UserFacingInsights {
+    build_insights(result, profile, trends, &[])
+}
+
+/// Generate user-facing insights tailored to the machine the metrics came from
+///
+/// Storage recommendations take the drives' free space and type into
+/// account: nearly full drives are told to free up space first, and
+/// defragmenting is only suggested for hard drives. Memory running on fewer
+/// channels than the platform supports gets its own recommendation.
+pub fn generate_insights_for_hardware(
+    result: &BottleneckAnalysisResult,
+    profile: Option<&WorkloadProfile>,
+    trends: &[SessionTrend],
+    hardware: &HardwareConfig,
+) -> UserFacingInsights {
+    let mut insights = build_insights(result, profile, trends, &hardware.storage_devices);
+    insights.recommendations.extend(memory_channel_recommendation(hardware));
+    insights
+}
+
+/// Generate user-facing insights with acknowledged bottlenecks dismissed
+///
+/// Bottlenecks covered by an acknowledgment are moved to `dismissed` and do
+/// not contribute to the summary, severity, or recommendations. Without
+/// hardware details the insights are generated as by
+/// `generate_insights_with_trends`.
+pub fn generate_acknowledged_insights(
+    result: &BottleneckAnalysisResult,
+    profile: Option<&WorkloadProfile>,
+    trends: &[SessionTrend],
+    hardware: Option<&HardwareConfig>,
+    acknowledgments: &[BottleneckAcknowledgment],
+) -> UserFacingInsights {
+    let (active, dismissed) = split_acknowledged(result, acknowledgments);
+    let mut insights = match hardware {
+        Some(hardware) => generate_insights_for_hardware(&active, profile, trends, hardware),
+        None => generate_insights_with_trends(&active, profile, trends),
+    };
+    if active.bottlenecks.is_empty() && !dismissed.is_empty() {
+        insights.summary = format!(
+            "No new bottlenecks detected; {} acknowledged bottleneck(s) dismissed.",
+            dismissed.len()
+        );
+    }
+    insights.dismissed = dismissed;
+    insights
+}
+
+/// Recommendation to populate more memory channels or match module sizes
+fn memory_channel_recommendation(hardware: &HardwareConfig) -> Option<Recommendation> {
+    let status = detect_channel_configuration(&hardware.memory);
+    let platform_channels = platform_channel_count(&hardware.cpu);
+    let text = if status.below_potential(platform_channels) {
+        let active = status.active_channels.unwrap_or_default();
+        format!(
+            "Memory is running in {} instead of {} mode, which cuts memory bandwidth; install matched modules in the slots the motherboard manual lists for {} operation.",
+            channel_mode(active),
+            channel_mode(platform_channels),
+            channel_mode(platform_channels)
+        )
+    } else if status.mismatched_modules {
+        "Installed memory modules differ in size, so part of the memory runs single-channel; a matched kit keeps all of it at full bandwidth.".to_string()
+    } else {
+        return None;
+    };
+    Some(Recommendation {
+        id: "memory-channels".to_string(),
+        bottleneck_type: None,
+        priority: 0,
+        category: RecommendationCategory::Upgrade,
+        text,
+        difficulty: RecommendationDifficulty::Moderate,
+    })
+}
+
+/// "single-channel", "dual-channel", ...
+fn channel_mode(channels: u32) -> String {
+    match channels {
+        1 => "single-channel".to_string(),
+        2 => "dual-channel".to_string(),
+        4 => "quad-channel".to_string(),
+        n => format!("{}-channel", n),
+    }
+}
+
+fn build_insights(
+    result: &BottleneckAnalysisResult,
+    profile: Option<&WorkloadProfile>,
+    trends: &[SessionTrend],
+    storage_devices: &[StorageInfo],
 ) -> UserFacingInsights {
     if result.bottlenecks.is_empty() {
         return UserFacingInsights {
             summary: "No significant bottlenecks detected. System appears to be performing well.".to_string(),
-            recommendations: vec!["Continue monitoring to identify any performance issues.".to_string()],
+            recommendations: vec![Recommendation {
+                id: "general-0".to_string(),
+                bottleneck_type: None,
+                priority: 0,
+                category: RecommendationCategory::Config,
+                text: "Continue monitoring to identify any performance issues.".to_string(),
+                difficulty: RecommendationDifficulty::Easy,
+            }],
             severity: 0,
+            dismissed: Vec::new(),
+            foreground: None,
         };
     }
     
@@ -35,9 +269,25 @@ pub fn generate_insights(
     let mut summary_parts = Vec::new();
     let mut recommendations = Vec::new();
     
-    for bottleneck in &result.bottlenecks {
-        summary_parts.push(bottleneck.summary.clone());
-        recommendations.extend(generate_recommendations(bottleneck, profile));
+    for (idx, bottleneck) in result.bottlenecks.iter().enumerate() {
+        summary_parts.push(match active_time_phrase(bottleneck) {
+            Some(phrase) => format!("{} ({})", bottleneck.summary, phrase),
+            None => bottleneck.summary.clone(),
+        });
+        let slug = bottleneck_slug(&bottleneck.bottleneck_type);
+        recommendations.extend(
+            generate_recommendations(bottleneck, profile, trends, storage_devices)
+                .into_iter()
+                .enumerate()
+                .map(|(n, (category, difficulty, text))| Recommendation {
+                    id: format!("{}-{}-{}", slug, idx, n),
+                    bottleneck_type: Some(bottleneck.bottleneck_type.clone()),
+                    priority: bottleneck.severity,
+                    category,
+                    text: text.to_string(),
+                    difficulty,
+                }),
+        );
     }
     
     let summary = if summary_parts.len() == 1 {
@@ -50,39 +300,171 @@ pub fn generate_insights(
         summary,
         recommendations,
         severity: highest_severity,
+        dismissed: Vec::new(),
+        foreground: None,
+    }
+}
+
+/// Share of a drive's capacity below which it counts as nearly full
+pub const NEARLY_FULL_FREE_RATIO: f64 = 0.1;
+
+/// Whether a drive's free space is known and below `NEARLY_FULL_FREE_RATIO`
+pub fn is_nearly_full(drive: &StorageInfo) -> bool {
+    match drive.free_mb {
+        Some(free_mb) if drive.capacity_mb > 0 => {
+            (free_mb as f64) < drive.capacity_mb as f64 * NEARLY_FULL_FREE_RATIO
+        }
+        _ => false,
+    }
+}
+
+/// Drive a storage bottleneck is on, when it can be identified
+///
+/// Storage metrics are collected across all drives under the "Storage"
+/// source, so the drive is only known when the evidence names exactly one
+/// detected drive or the system has a single drive. Otherwise advice about
+/// one drive could be applied to another, and none is returned.
+fn bottlenecked_drives<'a>(bottleneck: &Bottleneck, storage_devices: &'a [StorageInfo]) -> Vec<&'a StorageInfo> {
+    let named: Vec<&StorageInfo> = storage_devices
+        .iter()
+        .filter(|drive| {
+            bottleneck.evidence.iter().any(|e| e.source_component.as_deref() == Some(drive.model.as_str()))
+        })
+        .collect();
+    match (named.len(), storage_devices) {
+        (1, _) => named,
+        (0, [only]) => vec![only],
+        _ => Vec::new(),
+    }
+}
+
+/// Adjust generic storage recommendations to the drives' free space and type
+///
+/// Without drive details the recommendations are returned unchanged.
+fn tailor_storage_recommendations(
+    mut recs: Vec<RecommendationTemplate>,
+    drives: &[&StorageInfo],
+) -> Vec<RecommendationTemplate> {
+    use RecommendationCategory::Config;
+    use RecommendationDifficulty::{Easy, Moderate};
+    
+    if drives.is_empty() {
+        return recs;
+    }
+    let is_free_space_tip = |text: &str| text.starts_with("Free up disk space");
+    let is_defrag_tip = |text: &str| text.to_lowercase().contains("defragment");
+    
+    let has_hdd = drives.iter().any(|d| matches!(d.storage_type, StorageType::HDD));
+    let solid_state_only = drives.iter().all(|d| matches!(d.storage_type, StorageType::SSD | StorageType::NVMe));
+    if has_hdd {
+        recs.retain(|(_, _, text)| !is_defrag_tip(text));
+        recs.push((Config, Moderate, "Defragment the hard drive; HDD reads slow down as files fragment."));
+    } else if solid_state_only {
+        recs.retain(|(_, _, text)| !is_defrag_tip(text));
+    }
+    
+    if drives.iter().any(|d| is_nearly_full(d)) {
+        recs.retain(|(_, _, text)| !is_free_space_tip(text));
+        recs.insert(0, (Config, Easy, "Free up space on the nearly full drive first; drives slow down sharply with less than 10% free."));
+    } else if drives.iter().all(|d| d.free_mb.is_some()) {
+        // Every drive has enough room, so generic free-space advice does not apply
+        recs.retain(|(_, _, text)| !is_free_space_tip(text));
+    }
+    
+    recs
+}
+
+/// Order recommendations for "easy wins first": easiest, then most urgent
+pub fn sort_easy_wins_first(recommendations: &mut [Recommendation]) {
+    recommendations.sort_by(|a, b| {
+        a.difficulty
+            .cmp(&b.difficulty)
+            .then_with(|| b.priority.cmp(&a.priority))
+    });
+}
+
+/// How much of the analyzed window a bottleneck was active, e.g. "GPU-bound 72% of the time"
+///
+/// `None` when the share could not be judged per sample.
+pub fn active_time_phrase(bottleneck: &Bottleneck) -> Option<String> {
+    if bottleneck.active_fraction <= 0.0 {
+        return None;
     }
+    let percent = (bottleneck.active_fraction * 100.0).round();
+    let name = subsystem_name(&bottleneck.bottleneck_type);
+    Some(match bottleneck.bottleneck_type {
+        BottleneckType::Cpu | BottleneckType::Gpu | BottleneckType::Ram | BottleneckType::Vram | BottleneckType::Storage => {
+            format!("{}-bound {:.0}% of the time", name, percent)
+        }
+        _ => format!("{} bottleneck active {:.0}% of the time", name, percent),
+    })
+}
+
+/// Serialized name of a bottleneck type, used in recommendation ids
+fn bottleneck_slug(bottleneck_type: &BottleneckType) -> String {
+    serde_json::to_value(bottleneck_type)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
 }
 
 /// Generate recommendations for a specific bottleneck
 fn generate_recommendations(
     bottleneck: &Bottleneck,
     profile: Option<&WorkloadProfile>,
-) -> Vec<String> {
+    trends: &[SessionTrend],
+    storage_devices: &[StorageInfo],
+) -> Vec<RecommendationTemplate> {
+    use RecommendationCategory::{Config, Cooling, Upgrade};
+    use RecommendationDifficulty::{Advanced, Easy, Moderate};
+    
     let workload_type = profile.map(|p| &p.workload_type);
     
     match bottleneck.bottleneck_type {
         crate::core::domain::BottleneckType::Cpu => {
+            // Hybrid core analysis attaches per-core evidence
+            let per_core = bottleneck.evidence.iter()
+                .any(|e| e.metric_type == crate::core::domain::MetricType::CpuUtilizationPerCore);
+            // Clock throttling analysis attaches clock evidence
+            let low_clocks = bottleneck.evidence.iter()
+                .any(|e| e.metric_type == crate::core::domain::MetricType::CpuClock);
+            if low_clocks {
+                return vec![
+                    (Config, Easy, "Switch the power plan to Balanced or High performance; power-saving plans cap CPU clocks."),
+                    (Config, Easy, "On laptops, plug in the charger and select the performance mode in the vendor utility."),
+                    (Config, Advanced, "Check BIOS power limits (PL1/PL2, PPT/TDC/EDC) and any undervolting or power-limiting tools."),
+                    (Cooling, Moderate, "Check for VRM overheating on the motherboard, which can trigger current limits."),
+                ];
+            }
+            if per_core {
+                return vec![
+                    (Config, Easy, "Keep the application in the foreground or set its power mode to high performance so the scheduler uses performance cores."),
+                    (Config, Moderate, "Check that process affinity or a game launcher/tool is not restricting it to efficiency cores."),
+                    (Config, Moderate, "On Windows 11, make sure the power plan is not set to a battery-saving or efficiency mode."),
+                    (Config, Moderate, "Update the OS and chipset drivers so the scheduler recognizes the hybrid CPU layout."),
+                ];
+            }
             match workload_type {
                 Some(WorkloadType::Gaming) => vec![
-                    "For gaming: Consider upgrading to a CPU with higher single-core performance.".to_string(),
-                    "Close background applications and browser tabs while gaming.".to_string(),
-                    "Check if your game is CPU-limited by monitoring per-core utilization.".to_string(),
-                    "Consider overclocking if your CPU and cooling allow (advanced users only).".to_string(),
+                    (Upgrade, Advanced, "For gaming: Consider upgrading to a CPU with higher single-core performance."),
+                    (Config, Easy, "Close background applications and browser tabs while gaming."),
+                    (Config, Moderate, "Check if your game is CPU-limited by monitoring per-core utilization."),
+                    (Config, Advanced, "Consider overclocking if your CPU and cooling allow (advanced users only)."),
                 ],
                 Some(WorkloadType::Rendering) => vec![
-                    "For rendering: Consider upgrading to a CPU with more cores (e.g., Ryzen 9, Threadripper, or Intel Xeon).".to_string(),
-                    "Ensure your rendering software is using all available CPU cores.".to_string(),
-                    "Consider using GPU-accelerated rendering if available (e.g., CUDA, OpenCL).".to_string(),
+                    (Upgrade, Advanced, "For rendering: Consider upgrading to a CPU with more cores (e.g., Ryzen 9, Threadripper, or Intel Xeon)."),
+                    (Config, Moderate, "Ensure your rendering software is using all available CPU cores."),
+                    (Config, Moderate, "Consider using GPU-accelerated rendering if available (e.g., CUDA, OpenCL)."),
                 ],
                 Some(WorkloadType::AI) => vec![
-                    "For AI/ML: Consider upgrading to a CPU with more cores for data preprocessing.".to_string(),
-                    "Optimize data loading pipeline to reduce CPU bottleneck.".to_string(),
-                    "Consider using a faster storage solution (NVMe SSD) for dataset access.".to_string(),
+                    (Upgrade, Advanced, "For AI/ML: Consider upgrading to a CPU with more cores for data preprocessing."),
+                    (Config, Moderate, "Optimize data loading pipeline to reduce CPU bottleneck."),
+                    (Upgrade, Advanced, "Consider using a faster storage solution (NVMe SSD) for dataset access."),
                 ],
                 _ => vec![
-                    "Consider upgrading to a faster CPU with more cores.".to_string(),
-                    "Close background applications to free CPU resources.".to_string(),
-                    "Check for CPU-intensive processes and optimize them.".to_string(),
+                    (Upgrade, Advanced, "Consider upgrading to a faster CPU with more cores."),
+                    (Config, Easy, "Close background applications to free CPU resources."),
+                    (Config, Moderate, "Check for CPU-intensive processes and optimize them."),
                 ],
             }
         }
@@ -90,9 +472,9 @@ fn generate_recommendations(
             match workload_type {
                 Some(WorkloadType::Gaming) => {
                     let mut recs = vec![
-                        "For gaming: Consider upgrading to a more powerful GPU.".to_string(),
-                        "Lower graphics settings: Reduce texture quality, shadows, and anti-aliasing.".to_string(),
-                        "Reduce resolution or use upscaling (DLSS/FSR) if available.".to_string(),
+                        (Upgrade, Advanced, "For gaming: Consider upgrading to a more powerful GPU."),
+                        (Config, Easy, "Lower graphics settings: Reduce texture quality, shadows, and anti-aliasing."),
+                        (Config, Easy, "Reduce resolution or use upscaling (DLSS/FSR) if available."),
                     ];
                     
                     // Check profile parameters for resolution-specific advice
@@ -100,9 +482,9 @@ fn generate_recommendations(
                         if let Some(resolution) = profile.parameters.get("resolution") {
                             if let Some(res_str) = resolution.as_str() {
                                 if res_str.contains("3840x2160") || res_str.contains("4K") {
-                                    recs.push("For 4K gaming, a high-end GPU (RTX 3080/4080 or RX 6800 XT/7800 XT) is recommended.".to_string());
+                                    recs.push((Upgrade, Advanced, "For 4K gaming, a high-end GPU (RTX 3080/4080 or RX 6800 XT/7800 XT) is recommended."));
                                 } else if res_str.contains("2560x1440") || res_str.contains("1440p") {
-                                    recs.push("For 1440p gaming, a mid-to-high-end GPU (RTX 3070/4070 or RX 6700 XT/7700 XT) is recommended.".to_string());
+                                    recs.push((Upgrade, Advanced, "For 1440p gaming, a mid-to-high-end GPU (RTX 3070/4070 or RX 6700 XT/7700 XT) is recommended."));
                                 }
                             }
                         }
@@ -111,116 +493,212 @@ fn generate_recommendations(
                     recs
                 }
                 Some(WorkloadType::Rendering) => vec![
-                    "For rendering: Consider upgrading to a professional GPU (Quadro, Radeon Pro) or high-end consumer GPU.".to_string(),
-                    "Use GPU-accelerated rendering engines (e.g., Cycles GPU, Octane, Redshift).".to_string(),
-                    "Reduce scene complexity or use proxy objects for complex geometry.".to_string(),
-                    "Optimize texture sizes and use compression where appropriate.".to_string(),
+                    (Upgrade, Advanced, "For rendering: Consider upgrading to a professional GPU (Quadro, Radeon Pro) or high-end consumer GPU."),
+                    (Config, Moderate, "Use GPU-accelerated rendering engines (e.g., Cycles GPU, Octane, Redshift)."),
+                    (Config, Moderate, "Reduce scene complexity or use proxy objects for complex geometry."),
+                    (Config, Moderate, "Optimize texture sizes and use compression where appropriate."),
                 ],
                 Some(WorkloadType::AI) => vec![
-                    "For AI/ML: Consider upgrading to a GPU with more CUDA cores and VRAM (e.g., RTX 3090/4090, A100).".to_string(),
-                    "Reduce batch size to fit within available VRAM.".to_string(),
-                    "Use mixed precision training (FP16) to reduce VRAM usage.".to_string(),
-                    "Consider using model quantization or pruning to reduce model size.".to_string(),
+                    (Upgrade, Advanced, "For AI/ML: Consider upgrading to a GPU with more CUDA cores and VRAM (e.g., RTX 3090/4090, A100)."),
+                    (Config, Moderate, "Reduce batch size to fit within available VRAM."),
+                    (Config, Moderate, "Use mixed precision training (FP16) to reduce VRAM usage."),
+                    (Config, Moderate, "Consider using model quantization or pruning to reduce model size."),
                 ],
                 _ => vec![
-                    "Consider upgrading to a more powerful GPU.".to_string(),
-                    "Lower graphics settings in games or rendering applications.".to_string(),
-                    "Reduce resolution or disable resource-intensive visual effects.".to_string(),
+                    (Upgrade, Advanced, "Consider upgrading to a more powerful GPU."),
+                    (Config, Easy, "Lower graphics settings in games or rendering applications."),
+                    (Config, Easy, "Reduce resolution or disable resource-intensive visual effects."),
                 ],
             }
         }
         crate::core::domain::BottleneckType::Ram => {
             match workload_type {
                 Some(WorkloadType::Gaming) => vec![
-                    "For gaming: Consider adding more RAM (16GB+ recommended for modern games).".to_string(),
-                    "Close unnecessary applications and browser tabs while gaming.".to_string(),
-                    "Check if your game has memory leaks or high memory requirements.".to_string(),
+                    (Upgrade, Advanced, "For gaming: Consider adding more RAM (16GB+ recommended for modern games)."),
+                    (Config, Easy, "Close unnecessary applications and browser tabs while gaming."),
+                    (Config, Moderate, "Check if your game has memory leaks or high memory requirements."),
                 ],
                 Some(WorkloadType::Rendering) => vec![
-                    "For rendering: Consider adding more RAM (32GB+ recommended for 4K/8K projects).".to_string(),
-                    "Use proxy files or lower resolution previews during editing.".to_string(),
-                    "Close other applications to free up RAM for rendering.".to_string(),
+                    (Upgrade, Advanced, "For rendering: Consider adding more RAM (32GB+ recommended for 4K/8K projects)."),
+                    (Config, Moderate, "Use proxy files or lower resolution previews during editing."),
+                    (Config, Easy, "Close other applications to free up RAM for rendering."),
                 ],
                 Some(WorkloadType::AI) => vec![
-                    "For AI/ML: Consider adding more RAM (32GB+ recommended for large datasets).".to_string(),
-                    "Use data streaming or batch loading instead of loading entire datasets into memory.".to_string(),
-                    "Optimize data preprocessing to reduce memory footprint.".to_string(),
+                    (Upgrade, Advanced, "For AI/ML: Consider adding more RAM (32GB+ recommended for large datasets)."),
+                    (Config, Moderate, "Use data streaming or batch loading instead of loading entire datasets into memory."),
+                    (Config, Moderate, "Optimize data preprocessing to reduce memory footprint."),
                 ],
                 Some(WorkloadType::Productivity) => vec![
-                    "For productivity: Consider adding more RAM (16GB+ recommended for multitasking).".to_string(),
-                    "Close unused browser tabs and applications.".to_string(),
-                    "Check for memory leaks in frequently used applications.".to_string(),
+                    (Upgrade, Advanced, "For productivity: Consider adding more RAM (16GB+ recommended for multitasking)."),
+                    (Config, Easy, "Close unused browser tabs and applications."),
+                    (Config, Moderate, "Check for memory leaks in frequently used applications."),
                 ],
                 _ => vec![
-                    "Consider adding more RAM to your system.".to_string(),
-                    "Close unnecessary applications to free memory.".to_string(),
-                    "Check for memory leaks in running applications.".to_string(),
+                    (Upgrade, Advanced, "Consider adding more RAM to your system."),
+                    (Config, Easy, "Close unnecessary applications to free memory."),
+                    (Config, Moderate, "Check for memory leaks in running applications."),
                 ],
             }
         }
+        crate::core::domain::BottleneckType::Vram if bottleneck.evidence.iter().any(|e| {
+            e.metric_type == crate::core::domain::MetricType::GpuMemoryControllerLoad
+        }) => vec![
+            (Config, Easy, "Lower texture resolution or texture filtering quality to cut the data read from VRAM each frame."),
+            (Config, Easy, "Reduce resolution or MSAA; both multiply the memory traffic per frame."),
+            (Config, Moderate, "If the GPU memory is underclocked or running a conservative profile, restore its rated memory clock."),
+            (Upgrade, Advanced, "Consider a GPU with faster memory or a wider memory bus; more VRAM alone will not help."),
+        ],
         crate::core::domain::BottleneckType::Vram => {
             match workload_type {
                 Some(WorkloadType::Gaming) => vec![
-                    "For gaming: Consider upgrading to a GPU with more VRAM (8GB+ recommended for modern games).".to_string(),
-                    "Lower texture quality settings in games (e.g., High → Medium).".to_string(),
-                    "Reduce resolution or disable high-resolution texture packs.".to_string(),
-                    "Close other GPU-intensive applications.".to_string(),
+                    (Upgrade, Advanced, "For gaming: Consider upgrading to a GPU with more VRAM (8GB+ recommended for modern games)."),
+                    (Config, Easy, "Lower texture quality settings in games (e.g., High → Medium)."),
+                    (Config, Easy, "Reduce resolution or disable high-resolution texture packs."),
+                    (Config, Easy, "Close other GPU-intensive applications."),
                 ],
                 Some(WorkloadType::Rendering) => vec![
-                    "For rendering: Consider upgrading to a GPU with more VRAM (12GB+ recommended).".to_string(),
-                    "Reduce texture resolution and use compression.".to_string(),
-                    "Use out-of-core rendering or render in passes if available.".to_string(),
-                    "Optimize scene geometry and reduce polygon count.".to_string(),
+                    (Upgrade, Advanced, "For rendering: Consider upgrading to a GPU with more VRAM (12GB+ recommended)."),
+                    (Config, Moderate, "Reduce texture resolution and use compression."),
+                    (Config, Moderate, "Use out-of-core rendering or render in passes if available."),
+                    (Config, Moderate, "Optimize scene geometry and reduce polygon count."),
                 ],
                 Some(WorkloadType::AI) => vec![
-                    "For AI/ML: Consider upgrading to a GPU with more VRAM (24GB+ recommended for large models).".to_string(),
-                    "Reduce batch size to fit within available VRAM.".to_string(),
-                    "Use gradient checkpointing to reduce memory usage.".to_string(),
-                    "Consider using model sharding or distributed training.".to_string(),
+                    (Upgrade, Advanced, "For AI/ML: Consider upgrading to a GPU with more VRAM (24GB+ recommended for large models)."),
+                    (Config, Moderate, "Reduce batch size to fit within available VRAM."),
+                    (Config, Moderate, "Use gradient checkpointing to reduce memory usage."),
+                    (Config, Advanced, "Consider using model sharding or distributed training."),
                 ],
                 _ => vec![
-                    "Consider upgrading to a GPU with more VRAM.".to_string(),
-                    "Lower texture quality and resolution in games.".to_string(),
-                    "Reduce model complexity in rendering/AI workloads.".to_string(),
+                    (Upgrade, Advanced, "Consider upgrading to a GPU with more VRAM."),
+                    (Config, Easy, "Lower texture quality and resolution in games."),
+                    (Config, Moderate, "Reduce model complexity in rendering/AI workloads."),
                 ],
             }
         }
         crate::core::domain::BottleneckType::Storage => {
-            match workload_type {
+            let recs = match workload_type {
                 Some(WorkloadType::Rendering) => vec![
-                    "For rendering: Consider upgrading to a faster NVMe SSD for project files and cache.".to_string(),
-                    "Use separate drives for OS, projects, and cache to improve I/O performance.".to_string(),
-                    "Free up disk space on your project drive (keep 20%+ free).".to_string(),
+                    (Upgrade, Advanced, "For rendering: Consider upgrading to a faster NVMe SSD for project files and cache."),
+                    (Upgrade, Moderate, "Use separate drives for OS, projects, and cache to improve I/O performance."),
+                    (Config, Easy, "Free up disk space on your project drive (keep 20%+ free)."),
                 ],
                 Some(WorkloadType::AI) => vec![
-                    "For AI/ML: Consider using a fast NVMe SSD for dataset storage.".to_string(),
-                    "Use data prefetching and caching to reduce I/O wait times.".to_string(),
-                    "Consider using RAM disk for frequently accessed small datasets.".to_string(),
+                    (Upgrade, Moderate, "For AI/ML: Consider using a fast NVMe SSD for dataset storage."),
+                    (Config, Moderate, "Use data prefetching and caching to reduce I/O wait times."),
+                    (Config, Moderate, "Consider using RAM disk for frequently accessed small datasets."),
                 ],
                 Some(WorkloadType::Productivity) => vec![
-                    "For productivity: Consider upgrading to an SSD if using an HDD.".to_string(),
-                    "Free up disk space (keep 15%+ free for optimal performance).".to_string(),
-                    "Defragment HDD if applicable (not needed for SSDs).".to_string(),
+                    (Upgrade, Advanced, "For productivity: Consider upgrading to an SSD if using an HDD."),
+                    (Config, Easy, "Free up disk space (keep 15%+ free for optimal performance)."),
+                    (Config, Moderate, "Defragment HDD if applicable (not needed for SSDs)."),
                 ],
                 _ => vec![
-                    "Consider upgrading to a faster SSD or NVMe drive.".to_string(),
-                    "Free up disk space to improve performance.".to_string(),
-                    "Check for disk fragmentation and defragment if needed.".to_string(),
+                    (Upgrade, Advanced, "Consider upgrading to a faster SSD or NVMe drive."),
+                    (Config, Easy, "Free up disk space to improve performance."),
+                    (Config, Moderate, "Check for disk fragmentation and defragment if needed."),
+                ],
+            };
+            tailor_storage_recommendations(recs, &bottlenecked_drives(bottleneck, storage_devices))
+        }
+        crate::core::domain::BottleneckType::Thermal => {
+            // GPU thermal analysis reports the hotspot-to-edge gap against its own threshold
+            let hotspot_gap = bottleneck.evidence.iter().any(|e| {
+                e.source_component.as_deref() == Some(crate::analysis::rules::advanced::GPU_HOTSPOT_GAP_SOURCE)
+            });
+            // Cooling headroom analysis attaches fan duty evidence
+            let fan_duty = bottleneck.evidence.iter()
+                .find(|e| e.metric_type == crate::core::domain::MetricType::FanSpeed);
+            // History of the affected temperature across saved sessions
+            let diagnosis = trends.iter()
+                .filter(|t| bottleneck.evidence.iter().any(|e| e.metric_type == t.metric_type))
+                .find_map(|t| t.cooling_diagnosis());
+            match fan_duty {
+                _ if hotspot_gap => vec![
+                    (Cooling, Moderate, "Hotspot runs far above edge temperature: the GPU cooler is likely making poor contact with the die."),
+                    (Cooling, Advanced, "Repaste the GPU or have it serviced; check that thermal pads are the right thickness and the cooler is mounted evenly."),
+                    (Cooling, Easy, "Avoid GPU support brackets or cables that put uneven pressure on the card."),
+                ],
+                Some(fan) if fan.actual_value < fan.threshold => vec![
+                    (Cooling, Moderate, "Fans are not at full speed: adjust the fan curve in BIOS or vendor software so fans ramp up earlier."),
+                    (Cooling, Moderate, "Check that fan headers are set to PWM/DC correctly and fans are not capped by a silent profile."),
+                    (Cooling, Moderate, "Verify the fan curve is driven by the right sensor (CPU or GPU temperature)."),
+                ],
+                _ if diagnosis == Some(CoolingDiagnosis::Degradation) => vec![
+                    (Cooling, Moderate, "Temperatures have risen steadily across past sessions: this points to cooling degradation rather than an undersized cooler."),
+                    (Cooling, Moderate, "Clean dust from heatsinks, fans and filters; dust buildup is the most common cause of gradual temperature creep."),
+                    (Cooling, Advanced, "Replace the thermal paste on the CPU/GPU if the system is more than a few years old or was last repasted long ago."),
+                    (Cooling, Advanced, "For AIO coolers, check that the pump is running; permeation lowers coolant levels over the years."),
+                ],
+                _ if diagnosis == Some(CoolingDiagnosis::UndersizedCooling) => vec![
+                    (Cooling, Moderate, "Temperatures have been high since the first recorded session: the cooler appears undersized for this hardware rather than degraded."),
+                    (Cooling, Advanced, "Upgrade to a cooler rated for the CPU/GPU's power draw, or add case fans to improve airflow."),
+                    (Cooling, Advanced, "Consider undervolting or power-limiting CPU/GPU (advanced users only) to bring heat output within the cooler's capacity."),
+                ],
+                Some(_) => vec![
+                    (Cooling, Moderate, "Fans are already at full speed: the cooler lacks capacity for this load."),
+                    (Cooling, Advanced, "Upgrade to a larger air cooler or AIO, or add case fans to improve airflow."),
+                    (Cooling, Moderate, "Clean dust from heatsinks and filters, and check thermal paste."),
+                    (Cooling, Advanced, "Consider undervolting or power-limiting CPU/GPU (advanced users only) to reduce heat output."),
+                ],
+                None => vec![
+                    (Cooling, Advanced, "Improve system cooling: Add case fans, upgrade CPU cooler, or improve case airflow."),
+                    (Cooling, Moderate, "Clean dust from system components (CPU heatsink, GPU fans, case filters)."),
+                    (Cooling, Moderate, "Check thermal paste on CPU/GPU - consider reapplying if temperatures are very high."),
+                    (Cooling, Moderate, "Ensure proper case ventilation and cable management for better airflow."),
+                    (Cooling, Advanced, "Consider undervolting CPU/GPU (advanced users only) to reduce heat generation."),
                 ],
             }
         }
-        crate::core::domain::BottleneckType::Thermal => vec![
-            "Improve system cooling: Add case fans, upgrade CPU cooler, or improve case airflow.".to_string(),
-            "Clean dust from system components (CPU heatsink, GPU fans, case filters).".to_string(),
-            "Check thermal paste on CPU/GPU - consider reapplying if temperatures are very high.".to_string(),
-            "Ensure proper case ventilation and cable management for better airflow.".to_string(),
-            "Consider undervolting CPU/GPU (advanced users only) to reduce heat generation.".to_string(),
+        crate::core::domain::BottleneckType::Bandwidth if bottleneck.evidence.iter().any(|e| {
+            matches!(
+                e.metric_type,
+                crate::core::domain::MetricType::PcieLinkWidth | crate::core::domain::MetricType::PcieLinkGen
+            )
+        }) => vec![
+            (Config, Moderate, "Check the motherboard manual for slots that share lanes: populated M.2 drives or a second card can drop the GPU slot to x8 or x4."),
+            (Config, Moderate, "Move the GPU to the top x16 slot wired directly to the CPU, and reseat it firmly."),
+            (Config, Moderate, "In BIOS, set the GPU slot to its highest PCIe generation instead of Auto, and disable ASPM if the link stays slow."),
+            (Config, Easy, "In the power plan, set PCI Express Link State Power Management to Off so the link is not held in a low-power state."),
+            (Config, Moderate, "If the link stays narrow, inspect the slot and card contacts, and try removing any PCIe riser cable."),
         ],
         crate::core::domain::BottleneckType::Bandwidth => vec![
-            "Check PCIe slot configuration - ensure GPU is in the fastest available slot (usually x16).".to_string(),
-            "Verify PCIe generation (PCIe 4.0/5.0) and ensure components support it.".to_string(),
-            "Check for loose connections or damaged PCIe slots.".to_string(),
-            "Consider upgrading motherboard if PCIe bandwidth is limiting performance.".to_string(),
+            (Config, Moderate, "Check PCIe slot configuration - ensure GPU is in the fastest available slot (usually x16)."),
+            (Config, Moderate, "Verify PCIe generation (PCIe 4.0/5.0) and ensure components support it."),
+            (Config, Moderate, "Check for loose connections or damaged PCIe slots."),
+            (Upgrade, Advanced, "Consider upgrading motherboard if PCIe bandwidth is limiting performance."),
+        ],
+        crate::core::domain::BottleneckType::Responsiveness => vec![
+            (Config, Moderate, "Update network, audio, storage and chipset drivers; outdated drivers are the most common cause of high DPC time."),
+            (Config, Moderate, "Temporarily disable devices one at a time in Device Manager (Wi-Fi, Bluetooth, audio interfaces) to find the offending driver."),
+            (Config, Moderate, "Use a tool such as LatencyMon to identify which driver has the highest DPC execution time."),
+            (Config, Moderate, "Disable aggressive power saving (e.g. USB selective suspend, PCIe link state power management) if latency spikes persist."),
+        ],
+        crate::core::domain::BottleneckType::InputPipeline => vec![
+            (Config, Moderate, "Increase the number of data loader workers (e.g. PyTorch DataLoader num_workers) so preprocessing runs in parallel."),
+            (Config, Moderate, "Enable prefetching and pinned memory so the next batches are ready before the GPU asks for them."),
+            (Config, Moderate, "Move the dataset to a faster NVMe SSD, or cache it in RAM if it fits."),
+            (Config, Moderate, "Preprocess and store data in a ready-to-train format (e.g. resized images, sharded records) to cut per-step CPU work."),
+        ],
+        crate::core::domain::BottleneckType::FrameCap => vec![
+            (Config, Easy, "Check whether VSync is enabled in the game or the GPU control panel; disable it or use adaptive sync (G-Sync/FreeSync) instead."),
+            (Config, Easy, "Look for a frame rate limiter in the game settings, the GPU driver, or overlay tools (e.g. RTSS) and raise it to the display's refresh rate."),
+            (Config, Easy, "Confirm the display is actually running at its highest refresh rate in the OS display settings and that the cable supports it."),
+        ],
+        crate::core::domain::BottleneckType::CombinedLoad => vec![
+            (Upgrade, Advanced, "Plan a balanced upgrade of CPU, GPU and memory together; upgrading only one part will move the bottleneck to another."),
+            (Config, Easy, "Lower the workload across the board (e.g. resolution, quality preset, or batch size) to regain headroom on every component."),
+            (Config, Easy, "Close background applications that add load to several components at once."),
+        ],
+        crate::core::domain::BottleneckType::BackgroundDownload => vec![
+            (Config, Easy, "Pause downloads and updates in Steam, other game launchers and Windows Update before benchmarking, then re-run the test."),
+            (Config, Easy, "Schedule launcher and Windows updates outside the hours you play or benchmark."),
+            (Config, Easy, "Disregard the storage results of this run; the disk load came from the download, not from the workload."),
+        ],
+        crate::core::domain::BottleneckType::HybridGraphics => vec![
+            (Config, Easy, "Set the game to \"High performance\" in Windows Graphics settings or the NVIDIA/AMD control panel so it runs on the discrete GPU."),
+            (Config, Easy, "Plug in the charger; many laptops keep the discrete GPU asleep or power-limited on battery."),
+            (Config, Easy, "Turn off battery saver and switch to a balanced or performance power mode, which can otherwise force games onto the integrated GPU."),
+            (Config, Moderate, "If the laptop has a MUX switch or a discrete-only GPU mode in its vendor app or BIOS, enable it while gaming."),
         ],
     }
 }
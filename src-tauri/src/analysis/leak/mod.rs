@@ -0,0 +1,324 @@
+//! Memory leak patterns within a run
+//!
+//! An application that leaks memory makes RAM usage climb for as long as it
+//! runs, with little of it ever given back. That is a different problem from
+//! a workload that simply needs a lot of memory: the RAM bottleneck looks at
+//! how full memory is, while this module looks at how usage develops over
+//! the run and flags a steady, sustained rise.
+//!
+//! Video memory creeps the same way when a driver leaks or a game keeps
+//! loading assets without evicting old ones. It only hurts once the creep
+//! nears the card's capacity, when textures start spilling into system
+//! memory and late-session stutter sets in, so VRAM creep is judged against
+//! the card's total.
+
+use crate::analysis::insights::{Recommendation, RecommendationCategory, RecommendationDifficulty};
+use crate::core::domain::{MetricSample, MetricType, Run};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Memory readings needed before a trend is judged
+pub const LEAK_MIN_SAMPLES: usize = 20;
+
+/// Seconds the readings must span before a trend is judged
+pub const LEAK_MIN_DURATION_SECONDS: f64 = 60.0;
+
+/// Growth of the fitted trend over the run, relative to its start, treated as a leak
+pub const LEAK_MIN_GROWTH_PERCENT: f64 = 10.0;
+
+/// Fit quality (R²) the rise must reach to count as steady
+pub const LEAK_MIN_R_SQUARED: f64 = 0.8;
+
+/// Largest drop from a previous high, as a share of the overall rise, still treated as a leak
+pub const LEAK_MAX_RECOVERY_FRACTION: f64 = 0.25;
+
+/// Share of total VRAM the fitted trend must reach by the end of the run to count as creep
+pub const VRAM_CREEP_NEAR_CAPACITY_PERCENT: f64 = 80.0;
+
+/// Steady rise in memory usage over a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryLeakTrend {
+    /// `MemoryUsed` in MB when recorded, otherwise `MemoryUsage` in percent
+    pub metric_type: MetricType,
+    /// Fitted usage at the start and end of the run
+    pub start_value: f64,
+    pub end_value: f64,
+    /// Least-squares growth per minute, in the unit of `metric_type`
+    pub slope_per_minute: f64,
+    /// Growth of the fitted trend relative to its start
+    pub growth_percent: f64,
+    /// How closely usage follows a straight line, 0-1
+    pub r_squared: f64,
+    /// Largest drop from a previous high during the run
+    pub max_recovery: f64,
+    pub duration_seconds: f64,
+    pub summary: String,
+    pub recommendation: Recommendation,
+}
+
+/// Detect a steadily climbing memory usage trend in a run
+///
+/// Fits a line to memory usage over the run and flags a likely leak when the
+/// fitted usage grows by at least `LEAK_MIN_GROWTH_PERCENT`, the readings
+/// follow the line closely (`LEAK_MIN_R_SQUARED`) and usage never gives
+/// back more than `LEAK_MAX_RECOVERY_FRACTION` of the rise. Returns `None`
+/// when memory use is flat, however high, or when the run has fewer than
+/// `LEAK_MIN_SAMPLES` readings spanning `LEAK_MIN_DURATION_SECONDS`.
+pub fn detect_memory_leak_trend(run: &Run) -> Option<MemoryLeakTrend> {
+    [MetricType::MemoryUsed, MetricType::MemoryUsage]
+        .into_iter()
+        .find_map(|metric_type| leak_trend(run, metric_type))
+}
+
+fn leak_trend(run: &Run, metric_type: MetricType) -> Option<MemoryLeakTrend> {
+    let readings: Vec<_> = run
+        .metrics_streams
+        .values()
+        .flatten()
+        .filter(|s| s.metric_type == metric_type)
+        .collect();
+    let trend = rising_trend(&readings)?;
+
+    let unit = if metric_type == MetricType::MemoryUsed { " MB" } else { "%" };
+    let slope_per_minute = trend.slope_per_second * 60.0;
+    let summary = format!(
+        "Memory use climbed steadily from {:.0}{} to {:.0}{} over {:.0} minutes ({:+.1}{} per minute) without being released, which points to a memory leak in the running application.",
+        trend.start_value,
+        unit,
+        trend.end_value,
+        unit,
+        trend.duration_seconds / 60.0,
+        slope_per_minute,
+        unit,
+    );
+    let recommendation = Recommendation {
+        id: "memory-leak".to_string(),
+        bottleneck_type: None,
+        priority: trend.growth_percent.min(100.0) as u8,
+        category: RecommendationCategory::Config,
+        text: "Restart the application to reclaim the memory, and report the leak to its developer, including how long it ran before memory climbed.".to_string(),
+        difficulty: RecommendationDifficulty::Easy,
+    };
+
+    Some(MemoryLeakTrend {
+        metric_type,
+        start_value: trend.start_value,
+        end_value: trend.end_value,
+        slope_per_minute,
+        growth_percent: trend.growth_percent,
+        r_squared: trend.r_squared,
+        max_recovery: trend.max_recovery,
+        duration_seconds: trend.duration_seconds,
+        summary,
+        recommendation,
+    })
+}
+
+/// Steady rise in one GPU's VRAM usage toward its capacity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VramCreepTrend {
+    /// GPU whose `GpuVramUsage` samples crept
+    pub source_component: String,
+    /// Fitted VRAM usage in MB at the start and end of the run
+    pub start_mb: f64,
+    pub end_mb: f64,
+    pub total_mb: u64,
+    /// Least-squares growth in MB per minute
+    pub slope_mb_per_minute: f64,
+    /// Growth of the fitted trend relative to its start
+    pub growth_percent: f64,
+    /// How closely usage follows a straight line, 0-1
+    pub r_squared: f64,
+    /// Largest drop in MB from a previous high during the run
+    pub max_recovery_mb: f64,
+    pub duration_seconds: f64,
+    /// Minutes until the fitted trend reaches total VRAM, `None` once it has
+    pub minutes_to_full: Option<f64>,
+    pub summary: String,
+    pub recommendation: Recommendation,
+}
+
+/// Detect VRAM usage creeping toward the GPU's capacity over a run
+///
+/// Each GPU's `GpuVramUsage` stream is fitted like system memory in
+/// `detect_memory_leak_trend`, and flagged when the rise is steady and the
+/// fitted usage ends at or above `VRAM_CREEP_NEAR_CAPACITY_PERCENT` of
+/// `vram_total_mb`. VRAM that is high but flat is left to the VRAM
+/// bottleneck. When several GPUs creep, the one ending closest to capacity
+/// is reported.
+///
+/// Each GPU is judged against its own total, recovered from the
+/// `GpuVramUsagePercent` samples recorded alongside its usage. GPUs without
+/// them fall back to `vram_total_mb`, and are not judged when that is 0.
+pub fn detect_vram_creep(run: &Run, vram_total_mb: u64) -> Option<VramCreepTrend> {
+    let mut by_source: BTreeMap<&str, Vec<&MetricSample>> = BTreeMap::new();
+    let mut percent_at: HashMap<(&str, DateTime<Utc>), f64> = HashMap::new();
+    for sample in run.metrics_streams.values().flatten() {
+        match sample.metric_type {
+            MetricType::GpuVramUsage => {
+                by_source.entry(sample.source_component.as_str()).or_default().push(sample);
+            }
+            MetricType::GpuVramUsagePercent => {
+                percent_at.insert((sample.source_component.as_str(), sample.timestamp), sample.value);
+            }
+            _ => {}
+        }
+    }
+
+    by_source
+        .into_iter()
+        .filter_map(|(source, readings)| {
+            let total = recorded_vram_total(source, &readings, &percent_at).unwrap_or(vram_total_mb);
+            vram_creep(source, &readings, total)
+        })
+        .max_by(|a, b| a.end_mb.total_cmp(&b.end_mb))
+}
+
+/// Total VRAM of a GPU implied by its latest usage reading and the percentage recorded with it
+fn recorded_vram_total(
+    source: &str,
+    readings: &[&MetricSample],
+    percent_at: &HashMap<(&str, DateTime<Utc>), f64>,
+) -> Option<u64> {
+    readings
+        .iter()
+        .rev()
+        .find_map(|reading| {
+            let percent = *percent_at.get(&(source, reading.timestamp))?;
+            (percent > 0.0).then(|| (reading.value * 100.0 / percent).round() as u64)
+        })
+        .filter(|&total| total > 0)
+}
+
+fn vram_creep(source: &str, readings: &[&MetricSample], vram_total_mb: u64) -> Option<VramCreepTrend> {
+    if vram_total_mb == 0 {
+        return None;
+    }
+    let trend = rising_trend(readings)?;
+    let total = vram_total_mb as f64;
+    if trend.end_value < total * VRAM_CREEP_NEAR_CAPACITY_PERCENT / 100.0 {
+        return None;
+    }
+
+    let slope_mb_per_minute = trend.slope_per_second * 60.0;
+    let minutes_to_full = (trend.end_value < total).then(|| (total - trend.end_value) / slope_mb_per_minute);
+    let outlook = match minutes_to_full {
+        Some(minutes) => format!("at this rate it fills in about {:.0} more minutes", minutes),
+        None => "it has reached the card's capacity".to_string(),
+    };
+    let summary = format!(
+        "VRAM use on {} crept from {:.0} MB to {:.0} MB of {} MB over {:.0} minutes ({:+.1} MB per minute) without being released; {}, after which textures spill into system memory and cause stutter.",
+        source,
+        trend.start_value,
+        trend.end_value,
+        vram_total_mb,
+        trend.duration_seconds / 60.0,
+        slope_mb_per_minute,
+        outlook,
+    );
+    let recommendation = Recommendation {
+        id: "vram-creep".to_string(),
+        bottleneck_type: None,
+        priority: (trend.end_value / total * 100.0).min(100.0) as u8,
+        category: RecommendationCategory::Config,
+        text: "Update the GPU driver, and restart the game or application periodically during long sessions to release video memory that builds up.".to_string(),
+        difficulty: RecommendationDifficulty::Easy,
+    };
+
+    Some(VramCreepTrend {
+        source_component: source.to_string(),
+        start_mb: trend.start_value,
+        end_mb: trend.end_value,
+        total_mb: vram_total_mb,
+        slope_mb_per_minute,
+        growth_percent: trend.growth_percent,
+        r_squared: trend.r_squared,
+        max_recovery_mb: trend.max_recovery,
+        duration_seconds: trend.duration_seconds,
+        minutes_to_full,
+        summary,
+        recommendation,
+    })
+}
+
+/// Straight-line fit of a steadily rising stream
+struct RisingTrend {
+    start_value: f64,
+    end_value: f64,
+    slope_per_second: f64,
+    growth_percent: f64,
+    r_squared: f64,
+    max_recovery: f64,
+    duration_seconds: f64,
+}
+
+/// Fit a line to `samples` and keep it when it passes the `LEAK_*` criteria
+fn rising_trend(samples: &[&MetricSample]) -> Option<RisingTrend> {
+    let mut readings: Vec<(DateTime<Utc>, f64)> = samples
+        .iter()
+        .filter(|s| s.value.is_finite())
+        .map(|s| (s.timestamp, s.value))
+        .collect();
+    if readings.len() < LEAK_MIN_SAMPLES {
+        return None;
+    }
+    readings.sort_by_key(|&(timestamp, _)| timestamp);
+
+    let start = readings[0].0;
+    let seconds: Vec<f64> = readings
+        .iter()
+        .map(|&(timestamp, _)| (timestamp - start).num_milliseconds() as f64 / 1000.0)
+        .collect();
+    let duration_seconds = seconds[seconds.len() - 1];
+    if duration_seconds < LEAK_MIN_DURATION_SECONDS {
+        return None;
+    }
+
+    let n = readings.len() as f64;
+    let mean_x = seconds.iter().sum::<f64>() / n;
+    let mean_y = readings.iter().map(|&(_, value)| value).sum::<f64>() / n;
+    let covariance: f64 = seconds
+        .iter()
+        .zip(&readings)
+        .map(|(x, &(_, y))| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance_x: f64 = seconds.iter().map(|x| (x - mean_x).powi(2)).sum();
+    let variance_y: f64 = readings.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+    if variance_x <= 0.0 || variance_y <= 0.0 {
+        return None;
+    }
+    let slope = covariance / variance_x;
+    let r_squared = covariance * covariance / (variance_x * variance_y);
+
+    let start_value = mean_y - slope * mean_x;
+    let end_value = start_value + slope * duration_seconds;
+    if start_value <= 0.0 {
+        return None;
+    }
+    let growth_percent = (end_value - start_value) / start_value * 100.0;
+    if growth_percent < LEAK_MIN_GROWTH_PERCENT || r_squared < LEAK_MIN_R_SQUARED {
+        return None;
+    }
+
+    // Memory that is released again, e.g. by a garbage collector, is not leaked
+    let mut high = f64::NEG_INFINITY;
+    let mut max_recovery: f64 = 0.0;
+    for &(_, value) in &readings {
+        high = high.max(value);
+        max_recovery = max_recovery.max(high - value);
+    }
+    if max_recovery > (end_value - start_value) * LEAK_MAX_RECOVERY_FRACTION {
+        return None;
+    }
+
+    Some(RisingTrend {
+        start_value,
+        end_value,
+        slope_per_second: slope,
+        growth_percent,
+        r_squared,
+        max_recovery,
+        duration_seconds,
+    })
+}
@@ -0,0 +1,79 @@
+//! Memory channel configuration
+//!
+//! A single DIMM, or modules spread so that only one channel is populated,
+//! halves memory bandwidth. Integrated graphics and memory-bound games suffer
+//! most. Adapters rarely report the active channel count, so it is inferred
+//! from the detected modules where needed.
+
+use crate::core::domain::{CPUInfo, MemoryInfo};
+use serde::{Deserialize, Serialize};
+
+/// CPU model substrings of platforms with four or more memory channels
+const QUAD_CHANNEL_PLATFORMS: &[&str] = &["threadripper", "xeon", "epyc"];
+
+/// Inferred memory channel configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelStatus {
+    /// Channels in use; `None` when neither the adapter nor the modules tell
+    pub active_channels: Option<u32>,
+    /// Installed modules differ in size, so part of the memory runs
+    /// single-channel even in a dual-channel layout
+    pub mismatched_modules: bool,
+}
+
+impl ChannelStatus {
+    /// Whether fewer channels are in use than the platform supports
+    pub fn below_potential(&self, platform_channels: u32) -> bool {
+        match self.active_channels {
+            Some(channels) => channels < platform_channels,
+            None => false,
+        }
+    }
+}
+
+/// Infer single, dual or quad channel operation from the detected memory
+///
+/// One module is always single channel. Otherwise the channel count from
+/// the adapter is used, capped by the number of modules; without it, two
+/// or three modules count as dual channel and four or more as quad.
+///
+/// Older Windows snapshots recorded a single placeholder module spanning all
+/// memory, without speed or manufacturer; it says nothing about the modules
+/// and is treated as no module information.
+pub fn detect_channel_configuration(memory: &MemoryInfo) -> ChannelStatus {
+    let placeholder = matches!(
+        memory.modules.as_slice(),
+        [module] if module.size_mb == memory.total_mb && module.speed_mhz.is_none() && module.manufacturer.is_none()
+    );
+    let modules = if placeholder { 0 } else { memory.modules.len() as u32 };
+    let active_channels = match (modules, memory.channels) {
+        (1, _) => Some(1),
+        (0, Some(channels)) if channels > 0 => Some(channels),
+        (0, _) => None,
+        (modules, Some(channels)) if channels > 0 => Some(channels.min(modules)),
+        (2..=3, _) => Some(2),
+        _ => Some(4),
+    };
+    let mismatched_modules = memory
+        .modules
+        .windows(2)
+        .any(|pair| pair[0].size_mb != pair[1].size_mb);
+
+    ChannelStatus {
+        active_channels,
+        mismatched_modules,
+    }
+}
+
+/// Memory channels the CPU's platform supports
+///
+/// Workstation and server platforms have four or more channels; everything
+/// else is treated as dual channel.
+pub fn platform_channel_count(cpu: &CPUInfo) -> u32 {
+    let model = cpu.model.to_lowercase();
+    if QUAD_CHANNEL_PLATFORMS.iter().any(|platform| model.contains(platform)) {
+        4
+    } else {
+        2
+    }
+}
@@ -2,10 +2,30 @@
 //!
 //! This module provides bottleneck analysis functionality following AGENT.md Section 6.4.
 
+pub mod alerts;
+pub mod baseline;
+pub mod boost;
 pub mod engine;
+pub mod explain;
+pub mod fleet;
 pub mod insights;
+pub mod leak;
+pub mod memory;
 pub mod rules;
 pub mod comparison;
+pub mod reference;
+pub mod trend;
 
 pub use engine::AnalysisEngine;
-pub use comparison::{compare_runs, ComparisonResult, MetricDelta, BottleneckChange, BottleneckStatus};
+pub use explain::{analyze_and_explain, AnalysisConfig, AnalysisReport};
+pub use alerts::{watch_bottlenecks, AlertConfig, AlertWatcher, BottleneckAlert};
+pub use boost::{analyze_boost_sustainability, BoostComponent, BoostSustainability};
+pub use comparison::{
+    compare_runs, compare_runs_with_min_effect, compare_to_previous_run, ComparisonResult, MetricDelta,
+    BottleneckChange, BottleneckStatus, PreviousRunComparison,
+};
+pub use fleet::{summarize_session_issues, RunIssue, SessionIssueSummary};
+pub use leak::{detect_memory_leak_trend, detect_vram_creep, MemoryLeakTrend, VramCreepTrend};
+pub use memory::{detect_channel_configuration, platform_channel_count, ChannelStatus};
+pub use reference::{compare_to_reference, ReferenceComparison};
+pub use trend::{analyze_session_trend, CoolingDiagnosis, SessionTrend};
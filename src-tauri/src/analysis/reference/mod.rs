@@ -0,0 +1,231 @@
+//! Reference comparison against typical hardware of the same class
+//!
+//! This module compares a run with a bundled, offline dataset of median
+//! metric values per hardware tier and workload, so users can tell whether
+//! their rig performs normally for its class. No network access is involved.
+
+use crate::core::domain::{HardwareConfig, MetricSample, MetricType, Run, WorkloadType};
+use crate::metrics::utils::mean;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Relative difference from the median still considered typical (percent)
+pub const TYPICAL_TOLERANCE_PERCENT: f64 = 10.0;
+
+/// A hardware tier in the reference dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceTier {
+    pub id: String,
+    pub name: String,
+    /// GPU model names belonging to this tier
+    pub gpu_models: Vec<String>,
+    /// CPU model names belonging to this tier
+    pub cpu_models: Vec<String>,
+    /// Median metric values per workload
+    pub workloads: HashMap<WorkloadType, HashMap<MetricType, f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReferenceDataset {
+    tiers: Vec<ReferenceTier>,
+}
+
+/// How a run compares with the reference median
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceRating {
+    /// Better than typical for the hardware class
+    Above,
+    Typical,
+    /// Worse than typical for the hardware class
+    Below,
+}
+
+/// Comparison of one metric with its reference median
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceMetricComparison {
+    pub metric_type: MetricType,
+    pub run_average: f64,
+    pub reference_median: f64,
+    /// Difference from the median, positive when the run value is higher
+    pub delta_percent: f64,
+    pub rating: ReferenceRating,
+}
+
+/// Result of comparing a run with its hardware class
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceComparison {
+    pub tier_id: String,
+    pub tier_name: String,
+    /// Reference model name the hardware was matched on
+    pub matched_model: String,
+    pub workload_type: WorkloadType,
+    pub metrics: Vec<ReferenceMetricComparison>,
+    pub overall: ReferenceRating,
+    pub summary: String,
+}
+
+/// Tiers of the bundled reference dataset, fastest class first
+pub fn reference_tiers() -> &'static [ReferenceTier] {
+    static TIERS: OnceLock<Vec<ReferenceTier>> = OnceLock::new();
+    TIERS.get_or_init(|| {
+        serde_json::from_str::<ReferenceDataset>(include_str!("reference_data.json"))
+            .map(|dataset| dataset.tiers)
+            .unwrap_or_else(|e| {
+                log::error!("Bundled reference dataset is invalid: {}", e);
+                Vec::new()
+            })
+    })
+}
+
+/// Find the reference tier closest to the given hardware
+///
+/// GPUs are matched first, then the CPU. The longest matching model name
+/// wins, so "RTX 4060 Ti" is not mistaken for "RTX 4060".
+pub fn match_reference_tier(hardware: &HardwareConfig) -> Option<(&'static ReferenceTier, String)> {
+    hardware
+        .gpus
+        .iter()
+        .filter_map(|gpu| gpu_reference_tier(&gpu.model))
+        .max_by_key(|(_, reference)| reference.len())
+        .or_else(|| cpu_reference_tier(&hardware.cpu.model))
+}
+
+/// Reference tier of a GPU model, with the reference model name it matched
+pub fn gpu_reference_tier(model: &str) -> Option<(&'static ReferenceTier, String)> {
+    best_model_match(model, |tier| &tier.gpu_models)
+}
+
+/// Reference tier of a CPU model, with the reference model name it matched
+pub fn cpu_reference_tier(model: &str) -> Option<(&'static ReferenceTier, String)> {
+    best_model_match(model, |tier| &tier.cpu_models)
+}
+
+/// Rank of a tier in the dataset, 0 being the fastest class
+pub fn tier_rank(tier: &ReferenceTier) -> Option<usize> {
+    reference_tiers().iter().position(|t| t.id == tier.id)
+}
+
+/// Longest reference model name contained in `model`, case-insensitively
+fn best_model_match(
+    model: &str,
+    models_of: fn(&ReferenceTier) -> &Vec<String>,
+) -> Option<(&'static ReferenceTier, String)> {
+    let model = model.to_uppercase();
+    reference_tiers()
+        .iter()
+        .flat_map(|tier| models_of(tier).iter().map(move |m| (tier, m)))
+        .filter(|(_, reference)| model.contains(&reference.to_uppercase()))
+        .max_by_key(|(_, reference)| reference.len())
+        .map(|(tier, reference)| (tier, reference.clone()))
+}
+
+/// Compare a run with the typical values for its hardware class
+///
+/// Returns `None` if the hardware matches no reference tier or the run has
+/// none of the metrics recorded for the workload.
+pub fn compare_to_reference(
+    run: &Run,
+    hardware: &HardwareConfig,
+    workload_type: &WorkloadType,
+) -> Option<ReferenceComparison> {
+    let (tier, matched_model) = match_reference_tier(hardware)?;
+    let medians = tier
+        .workloads
+        .get(workload_type)
+        .or_else(|| tier.workloads.get(&WorkloadType::General))?;
+
+    let samples: Vec<&MetricSample> = run.metrics_streams.values().flatten().collect();
+    let mut metrics: Vec<ReferenceMetricComparison> = medians
+        .iter()
+        .filter_map(|(metric_type, median)| {
+            if *median == 0.0 {
+                return None;
+            }
+            let run_average = mean(
+                samples
+                    .iter()
+                    .filter(|s| s.metric_type == *metric_type)
+                    .map(|s| s.value),
+            )?;
+            let delta_percent = (run_average - median) / median * 100.0;
+            Some(ReferenceMetricComparison {
+                metric_type: metric_type.clone(),
+                run_average,
+                reference_median: *median,
+                delta_percent,
+                rating: rate(metric_type, delta_percent),
+            })
+        })
+        .collect();
+    if metrics.is_empty() {
+        return None;
+    }
+    metrics.sort_by_key(|m| format!("{:?}", m.metric_type));
+
+    let overall = if metrics.iter().any(|m| m.rating == ReferenceRating::Below) {
+        ReferenceRating::Below
+    } else if metrics.iter().any(|m| m.rating == ReferenceRating::Above) {
+        ReferenceRating::Above
+    } else {
+        ReferenceRating::Typical
+    };
+
+    let summary = match overall {
+        ReferenceRating::Below => {
+            let below: Vec<String> = metrics
+                .iter()
+                .filter(|m| m.rating == ReferenceRating::Below)
+                .map(|m| format!("{:?} ({:+.0}%)", m.metric_type, m.delta_percent))
+                .collect();
+            format!(
+                "Below typical for {} hardware ({}): {}",
+                tier.name,
+                matched_model,
+                below.join(", ")
+            )
+        }
+        ReferenceRating::Above => format!("Above typical for {} hardware ({})", tier.name, matched_model),
+        ReferenceRating::Typical => format!("Typical for {} hardware ({})", tier.name, matched_model),
+    };
+
+    Some(ReferenceComparison {
+        tier_id: tier.id.clone(),
+        tier_name: tier.name.clone(),
+        matched_model,
+        workload_type: workload_type.clone(),
+        metrics,
+        overall,
+        summary,
+    })
+}
+
+/// Rate a deviation from the median, accounting for metric direction
+fn rate(metric_type: &MetricType, delta_percent: f64) -> ReferenceRating {
+    if delta_percent.abs() <= TYPICAL_TOLERANCE_PERCENT {
+        return ReferenceRating::Typical;
+    }
+    let better = if higher_is_better(metric_type) {
+        delta_percent > 0.0
+    } else {
+        delta_percent < 0.0
+    };
+    if better {
+        ReferenceRating::Above
+    } else {
+        ReferenceRating::Below
+    }
+}
+
+/// Whether a higher value of the metric means better performance
+fn higher_is_better(metric_type: &MetricType) -> bool {
+    !matches!(
+        metric_type,
+        MetricType::FrameTime
+            | MetricType::RenderTime
+            | MetricType::Temperature
+            | MetricType::GpuTemperature
+            | MetricType::GpuHotspotTemperature
+    )
+}
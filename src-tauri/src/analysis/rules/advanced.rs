@@ -4,11 +4,29 @@
 //! - PCIe saturation detection
 //! - Memory bus bandwidth analysis
 //! - Enhanced thermal analysis with predictive warnings
+//! - Cooling headroom analysis (fan curve vs. cooling capacity)
 //! - Multi-GPU scenarios (SLI/CrossFire)
+//! - Heterogeneous core loading (P-core vs. E-core)
+//! - CPU clock throttling from power limits and power plans
+//! - System responsiveness (DPC and interrupt time)
+//! - AI input pipeline starvation (storage and preprocessing feeding the GPU)
+//! - Frame rate caps below the display refresh rate (VSync and frame limiters)
+//! - GPU PCIe links running below their maximum generation or width
+//! - Thermal throttling confirmed by clocks or FPS dropping as temperature rises
+//! - VRAM bandwidth pressure (busy memory controller with capacity to spare)
+//! - Combined pressure (several subsystems near their thresholds at once)
+//! - Background downloads (updaters and launchers writing to disk mid-run)
+//! - Hybrid graphics misrouting (a game on the integrated GPU while the discrete GPU idles)
 
+use super::{
+    calculate_severity, hotspot_gaps, scaled_severity, summed_ticks, sustained_violation, window_statistic, CPU_HIGH_THRESHOLD, GPU_HIGH_THRESHOLD,
+    RAM_HIGH_THRESHOLD, SUSTAINED_MIN_SECONDS, VRAM_HIGH_THRESHOLD,
+};
 use crate::core::domain::{
-    Bottleneck, BottleneckType, EvidenceItem, MetricSample, MetricType,
+    Bottleneck, BottleneckType, CPUInfo, EvidenceItem, GPUInfo, MetricSample, MetricType, ThresholdOverrides,
 };
+use crate::metrics::utils::{mean, percentile};
+use std::collections::{HashMap, HashSet};
 
 /// PCIe bandwidth thresholds (in MB/s)
 /// These are theoretical maximums for common PCIe generations
@@ -39,181 +57,124 @@ pub const TEMP_RISE_RATE_THRESHOLD: f64 = 2.0; // °C/minute - rapid rise indica
 ///
 /// Checks if PCIe bandwidth is approaching saturation, which can indicate
 /// a bottleneck in data transfer between CPU and GPU or other PCIe devices.
+/// Usage must stay saturated for `SUSTAINED_MIN_SECONDS`.
 pub fn detect_pcie_saturation(metrics: &[MetricSample]) -> Option<Bottleneck> {
-    // Look for PCIe-related metrics
+    detect_pcie_saturation_with_min_duration(metrics, SUSTAINED_MIN_SECONDS)
+}
+
+/// Detect PCIe bandwidth saturation lasting at least `min_seconds`
+///
+/// A one-off transfer burst that saturates the link briefly is not reported.
+pub fn detect_pcie_saturation_with_min_duration(metrics: &[MetricSample], min_seconds: f64) -> Option<Bottleneck> {
     // Note: Actual PCIe bandwidth metrics would need to be collected from platform-specific APIs
-    // For now, we infer from GPU utilization and data transfer patterns
-    
-    let pcie_metrics: Vec<&MetricSample> = metrics
-        .iter()
-        .filter(|m| {
-            matches!(
-                m.metric_type,
-                MetricType::GpuUtilization
-                    | MetricType::GpuMemoryTransfer
-                    | MetricType::StorageReadThroughput
-                    | MetricType::StorageWriteThroughput
-            )
-        })
-        .collect();
-    
-    if pcie_metrics.is_empty() {
-        return None;
-    }
-    
-    // Calculate average GPU utilization and storage throughput
-    let _avg_gpu_util: f64 = pcie_metrics
-        .iter()
-        .filter(|m| matches!(m.metric_type, MetricType::GpuUtilization))
-        .map(|m| m.value)
-        .sum::<f64>()
-        / pcie_metrics
-            .iter()
-            .filter(|m| matches!(m.metric_type, MetricType::GpuUtilization))
-            .count()
-            .max(1) as f64;
-    
-    let avg_storage_read: f64 = pcie_metrics
-        .iter()
-        .filter(|m| matches!(m.metric_type, MetricType::StorageReadThroughput))
-        .map(|m| m.value)
-        .last()
-        .unwrap_or(0.0);
-    
-    let avg_storage_write: f64 = pcie_metrics
-        .iter()
-        .filter(|m| matches!(m.metric_type, MetricType::StorageWriteThroughput))
-        .map(|m| m.value)
-        .last()
-        .unwrap_or(0.0);
-    
-    // Estimate PCIe bandwidth usage
+    // For now, we estimate PCIe bandwidth usage from storage throughput
     // This is a simplified heuristic - real implementation would need actual PCIe counters
-    let estimated_pcie_usage_mb_s = avg_storage_read + avg_storage_write;
+    let ticks = summed_ticks(
+        metrics,
+        &[MetricType::StorageReadThroughput, MetricType::StorageWriteThroughput],
+    );
     
     // Assume PCIe 3.0 x16 as baseline (can be enhanced with hardware detection)
     let pcie_max_bandwidth = PCIE_3_0_X16_MAX;
+    let threshold = pcie_max_bandwidth * (PCIE_SATURATION_THRESHOLD / 100.0);
+    let violation = sustained_violation(&ticks, min_seconds, |usage| usage >= threshold)?;
+    
+    let estimated_pcie_usage_mb_s = violation.mean;
     let pcie_utilization_percent = (estimated_pcie_usage_mb_s / pcie_max_bandwidth) * 100.0;
+    let severity = if pcie_utilization_percent >= 95.0 {
+        90
+    } else if pcie_utilization_percent >= 90.0 {
+        75
+    } else {
+        60
+    };
     
-    if pcie_utilization_percent >= PCIE_SATURATION_THRESHOLD {
-        let severity = if pcie_utilization_percent >= 95.0 {
-            90
-        } else if pcie_utilization_percent >= 90.0 {
-            75
-        } else {
-            60
-        };
-        
-        let evidence = vec![EvidenceItem {
-            metric_type: MetricType::StorageReadThroughput,
-            threshold: pcie_max_bandwidth * (PCIE_SATURATION_THRESHOLD / 100.0),
-            actual_value: estimated_pcie_usage_mb_s,
-            time_range_start: pcie_metrics.first().unwrap().timestamp,
-            time_range_end: pcie_metrics.last().unwrap().timestamp,
-        }];
-        
-        return Some(Bottleneck {
-            bottleneck_type: BottleneckType::Bandwidth,
-            severity,
-            evidence,
-            summary: "PCIe bandwidth saturation detected".to_string(),
-            details: format!(
-                "Estimated PCIe bandwidth usage: {:.1}% ({:.1} MB/s of {:.1} MB/s max). \
-                 This may limit data transfer between CPU and GPU or storage devices.",
-                pcie_utilization_percent,
-                estimated_pcie_usage_mb_s,
-                pcie_max_bandwidth
-            ),
-        });
-    }
+    let evidence = vec![EvidenceItem {
+        metric_type: MetricType::StorageReadThroughput,
+        threshold,
+        actual_value: estimated_pcie_usage_mb_s,
+        time_range_start: violation.start,
+        time_range_end: violation.end,
+        source_component: None,
+    }];
     
-    None
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::Bandwidth,
+        severity,
+        evidence,
+        summary: "PCIe bandwidth saturation detected".to_string(),
+        details: format!(
+            "Estimated PCIe bandwidth usage: {:.1}% ({:.1} MB/s of {:.1} MB/s max) for {:.0} seconds. \
+             This may limit data transfer between CPU and GPU or storage devices.",
+            pcie_utilization_percent,
+            estimated_pcie_usage_mb_s,
+            pcie_max_bandwidth,
+            violation.duration_seconds()
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
 }
 
 /// Detect memory bus bandwidth saturation
 ///
 /// Checks if memory bus bandwidth is approaching saturation, which can indicate
-/// a bottleneck in memory access patterns.
+/// a bottleneck in memory access patterns. Usage must stay saturated for
+/// `SUSTAINED_MIN_SECONDS`.
 pub fn detect_memory_bus_saturation(metrics: &[MetricSample]) -> Option<Bottleneck> {
-    // Look for memory-related metrics
-    let memory_metrics: Vec<&MetricSample> = metrics
-        .iter()
-        .filter(|m| {
-            matches!(
-                m.metric_type,
-                MetricType::MemoryUsage
-                    | MetricType::MemoryReadThroughput
-                    | MetricType::MemoryWriteThroughput
-            )
-        })
-        .collect();
-    
-    if memory_metrics.is_empty() {
-        return None;
-    }
-    
-    // Calculate average memory throughput
-    let avg_read: f64 = memory_metrics
-        .iter()
-        .filter(|m| matches!(m.metric_type, MetricType::MemoryReadThroughput))
-        .map(|m| m.value)
-        .sum::<f64>()
-        / memory_metrics
-            .iter()
-            .filter(|m| matches!(m.metric_type, MetricType::MemoryReadThroughput))
-            .count()
-            .max(1) as f64;
-    
-    let avg_write: f64 = memory_metrics
-        .iter()
-        .filter(|m| matches!(m.metric_type, MetricType::MemoryWriteThroughput))
-        .map(|m| m.value)
-        .sum::<f64>()
-        / memory_metrics
-            .iter()
-            .filter(|m| matches!(m.metric_type, MetricType::MemoryWriteThroughput))
-            .count()
-            .max(1) as f64;
-    
-    let total_memory_bandwidth = avg_read + avg_write;
+    detect_memory_bus_saturation_with_min_duration(metrics, SUSTAINED_MIN_SECONDS)
+}
+
+/// Detect memory bus bandwidth saturation lasting at least `min_seconds`
+pub fn detect_memory_bus_saturation_with_min_duration(
+    metrics: &[MetricSample],
+    min_seconds: f64,
+) -> Option<Bottleneck> {
+    let ticks = summed_ticks(
+        metrics,
+        &[MetricType::MemoryReadThroughput, MetricType::MemoryWriteThroughput],
+    );
     
     // Assume DDR4 3200 dual channel as baseline (can be enhanced with hardware detection)
     let memory_max_bandwidth = DDR4_3200_DUAL_CHANNEL_MAX;
+    let threshold = memory_max_bandwidth * (MEMORY_BUS_SATURATION_THRESHOLD / 100.0);
+    let violation = sustained_violation(&ticks, min_seconds, |bandwidth| bandwidth >= threshold)?;
+    
+    let total_memory_bandwidth = violation.mean;
     let memory_utilization_percent = (total_memory_bandwidth / memory_max_bandwidth) * 100.0;
+    let severity = if memory_utilization_percent >= 95.0 {
+        85
+    } else if memory_utilization_percent >= 90.0 {
+        70
+    } else {
+        55
+    };
     
-    if memory_utilization_percent >= MEMORY_BUS_SATURATION_THRESHOLD {
-        let severity = if memory_utilization_percent >= 95.0 {
-            85
-        } else if memory_utilization_percent >= 90.0 {
-            70
-        } else {
-            55
-        };
-        
-        let evidence = vec![EvidenceItem {
-            metric_type: MetricType::MemoryReadThroughput,
-            threshold: memory_max_bandwidth * (MEMORY_BUS_SATURATION_THRESHOLD / 100.0),
-            actual_value: total_memory_bandwidth,
-            time_range_start: memory_metrics.first().unwrap().timestamp,
-            time_range_end: memory_metrics.last().unwrap().timestamp,
-        }];
-        
-        return Some(Bottleneck {
-            bottleneck_type: BottleneckType::Bandwidth,
-            severity,
-            evidence,
-            summary: "Memory bus bandwidth saturation detected".to_string(),
-            details: format!(
-                "Memory bus bandwidth usage: {:.1}% ({:.1} MB/s of {:.1} MB/s max). \
-                 This may limit memory access performance.",
-                memory_utilization_percent,
-                total_memory_bandwidth,
-                memory_max_bandwidth
-            ),
-        });
-    }
+    let evidence = vec![EvidenceItem {
+        metric_type: MetricType::MemoryReadThroughput,
+        threshold,
+        actual_value: total_memory_bandwidth,
+        time_range_start: violation.start,
+        time_range_end: violation.end,
+        source_component: None,
+    }];
     
-    None
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::Bandwidth,
+        severity,
+        evidence,
+        summary: "Memory bus bandwidth saturation detected".to_string(),
+        details: format!(
+            "Memory bus bandwidth usage: {:.1}% ({:.1} MB/s of {:.1} MB/s max) for {:.0} seconds. \
+             This may limit memory access performance.",
+            memory_utilization_percent,
+            total_memory_bandwidth,
+            memory_max_bandwidth,
+            violation.duration_seconds()
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
 }
 
 /// Enhanced thermal analysis with predictive warnings
@@ -266,6 +227,7 @@ pub fn detect_enhanced_thermal_bottleneck(metrics: &[MetricSample]) -> Option<Bo
             actual_value: latest_temp,
             time_range_start: first_temp.timestamp,
             time_range_end: last_temp.timestamp,
+            source_component: None,
         }];
         
         return Some(Bottleneck {
@@ -279,6 +241,8 @@ pub fn detect_enhanced_thermal_bottleneck(metrics: &[MetricSample]) -> Option<Bo
                 latest_temp,
                 TEMP_CRITICAL_THRESHOLD
             ),
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
@@ -306,6 +270,7 @@ pub fn detect_enhanced_thermal_bottleneck(metrics: &[MetricSample]) -> Option<Bo
             actual_value: latest_temp,
             time_range_start: first_temp.timestamp,
             time_range_end: last_temp.timestamp,
+            source_component: None,
         }];
         
         return Some(Bottleneck {
@@ -321,6 +286,8 @@ pub fn detect_enhanced_thermal_bottleneck(metrics: &[MetricSample]) -> Option<Bo
                 temp_rise_rate,
                 predicted_time_to_throttle
             ),
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
@@ -334,6 +301,7 @@ pub fn detect_enhanced_thermal_bottleneck(metrics: &[MetricSample]) -> Option<Bo
             actual_value: latest_temp,
             time_range_start: first_temp.timestamp,
             time_range_end: last_temp.timestamp,
+            source_component: None,
         }];
         
         return Some(Bottleneck {
@@ -347,12 +315,242 @@ pub fn detect_enhanced_thermal_bottleneck(metrics: &[MetricSample]) -> Option<Bo
                 latest_temp,
                 TEMP_WARNING_THRESHOLD
             ),
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
     None
 }
 
+/// Fan duty cycle above which fans are considered to be at maximum (percent)
+pub const FAN_MAX_DUTY_THRESHOLD: f64 = 90.0;
+
+/// Degrees above the hot limit at which cooling headroom severity reaches 100 (°C)
+pub const COOLING_HEADROOM_FULL_SEVERITY_EXCESS: f64 = 20.0;
+
+/// Detect whether high temperatures leave cooling headroom unused
+///
+/// Distinguishes a fan-curve problem (hot while fans are not at max) from
+/// insufficient cooling capacity (hot with fans at max). A component counts
+/// as hot once its temperature stays at or above its limit for
+/// `SUSTAINED_MIN_SECONDS`: the warning level for the CPU and
+/// `GPU_EDGE_THROTTLE_TEMP` for the GPU. Fans are averaged over that
+/// stretch. Only fan samples reported as a duty cycle percentage are
+/// considered.
+pub fn detect_cooling_headroom(metrics: &[MetricSample]) -> Option<Bottleneck> {
+    // (temperature metric, fan source component, hot limit)
+    let components = [
+        (MetricType::Temperature, "CPU", TEMP_WARNING_THRESHOLD),
+        (MetricType::GpuTemperature, "GPU", GPU_EDGE_THROTTLE_TEMP),
+    ];
+    
+    let mut worst: Option<Bottleneck> = None;
+    
+    for (temp_type, component, limit) in components {
+        let mut temps: Vec<(chrono::DateTime<chrono::Utc>, f64)> = metrics
+            .iter()
+            .filter(|m| m.metric_type == temp_type && m.source_component == component)
+            .map(|m| (m.timestamp, m.value))
+            .collect();
+        temps.sort_by_key(|&(timestamp, _)| timestamp);
+        let fans: Vec<&MetricSample> = metrics
+            .iter()
+            .filter(|m| {
+                m.metric_type == MetricType::FanSpeed
+                    && m.source_component == component
+                    && m.unit == "percent"
+            })
+            .collect();
+        
+        if fans.is_empty() {
+            continue;
+        }
+        let Some(hot) = sustained_violation(&temps, SUSTAINED_MIN_SECONDS, |t| t >= limit) else {
+            continue;
+        };
+        let max_temp = hot.peak;
+        
+        let during = fans.iter().filter(|m| m.timestamp >= hot.start && m.timestamp <= hot.end);
+        let avg_fan = match mean(during.map(|m| m.value)).or_else(|| mean(fans.iter().map(|m| m.value))) {
+            Some(avg) => avg,
+            None => continue,
+        };
+        let fans_at_max = avg_fan >= FAN_MAX_DUTY_THRESHOLD;
+        
+        let severity = scaled_severity(max_temp, limit, limit + COOLING_HEADROOM_FULL_SEVERITY_EXCESS);
+        // Unused fan headroom is cheaper to fix than missing cooling capacity
+        let severity = if fans_at_max { severity } else { severity - severity / 4 };
+        
+        let (time_range_start, time_range_end) = (hot.start, hot.end);
+        let evidence = vec![
+            EvidenceItem {
+                metric_type: temp_type.clone(),
+                threshold: limit,
+                actual_value: max_temp,
+                time_range_start,
+                time_range_end,
+                source_component: None,
+            },
+            EvidenceItem {
+                metric_type: MetricType::FanSpeed,
+                threshold: FAN_MAX_DUTY_THRESHOLD,
+                actual_value: avg_fan,
+                time_range_start,
+                time_range_end,
+                source_component: None,
+            },
+        ];
+        
+        let (summary, details) = if fans_at_max {
+            (
+                format!("{} cooling capacity insufficient", component),
+                format!(
+                    "{} temperature reached {:.1}°C while fans averaged {:.0}% duty. \
+                     The cooler is already at maximum and cannot remove more heat; \
+                     improve the cooler, airflow, or reduce the heat load.",
+                    component, max_temp, avg_fan
+                ),
+            )
+        } else {
+            (
+                format!("{} fan curve leaves cooling headroom unused", component),
+                format!(
+                    "{} temperature reached {:.1}°C while fans averaged only {:.0}% duty. \
+                     The fans have headroom; adjust the fan curve to ramp up earlier.",
+                    component, max_temp, avg_fan
+                ),
+            )
+        };
+        
+        if worst.as_ref().map_or(true, |w| severity > w.severity) {
+            worst = Some(Bottleneck {
+                bottleneck_type: BottleneckType::Thermal,
+                severity,
+                evidence,
+                summary,
+                details,
+                onset: None,
+                active_fraction: 0.0,
+            });
+        }
+    }
+    
+    worst
+}
+
+/// GPU edge temperature at which consumer GPUs start to throttle (°C)
+pub const GPU_EDGE_THROTTLE_TEMP: f64 = 83.0;
+
+/// GPU hotspot (junction) temperature at which GPUs start to throttle (°C)
+pub const GPU_HOTSPOT_THROTTLE_TEMP: f64 = 105.0;
+
+/// Average hotspot-to-edge gap above which the cooler mount is suspect (°C)
+pub const GPU_HOTSPOT_DELTA_THRESHOLD: f64 = 20.0;
+
+/// Degrees past a GPU sensor's throttle limit at which throttle severity reaches 100 (°C)
+pub const GPU_THROTTLE_FULL_SEVERITY_EXCESS: f64 = 10.0;
+
+/// Average hotspot-to-edge gap at which cooler contact severity reaches its maximum (°C)
+pub const GPU_HOTSPOT_DELTA_FULL_SEVERITY: f64 = 35.0;
+
+/// Highest cooler contact severity; a remount fixes it without new hardware
+pub const GPU_HOTSPOT_GAP_MAX_SEVERITY: u8 = 80;
+
+/// Source component of hotspot-to-edge gap evidence, whose value is the
+/// difference between two sensors rather than a reading of either
+pub const GPU_HOTSPOT_GAP_SOURCE: &str = "GPU Hotspot Gap";
+
+/// Detect GPU throttling and poor cooler contact from edge and hotspot temperatures
+///
+/// Edge and hotspot sensors throttle at very different temperatures, so each
+/// is compared against its own limit. A large gap between the two at the same
+/// tick of the same GPU points at poor cooler contact rather than a lack of
+/// airflow.
+pub fn detect_gpu_thermal(metrics: &[MetricSample]) -> Vec<Bottleneck> {
+    let edge: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::GpuTemperature)
+        .collect();
+    let hotspot: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::GpuHotspotTemperature)
+        .collect();
+    
+    let mut bottlenecks = Vec::new();
+    
+    // Report the sensor furthest past its own limit
+    let throttling = [
+        (&edge, MetricType::GpuTemperature, GPU_EDGE_THROTTLE_TEMP, "edge"),
+        (&hotspot, MetricType::GpuHotspotTemperature, GPU_HOTSPOT_THROTTLE_TEMP, "hotspot"),
+    ]
+    .into_iter()
+    .filter_map(|(samples, metric_type, limit, sensor)| {
+        let max_temp = samples.iter().map(|m| m.value).filter(|v| v.is_finite()).reduce(f64::max)?;
+        (max_temp >= limit).then_some((samples, metric_type, limit, sensor, max_temp))
+    })
+    .max_by(|a, b| (a.4 - a.2).total_cmp(&(b.4 - b.2)));
+    
+    if let Some((samples, metric_type, limit, sensor, max_temp)) = throttling {
+        if let Some((_, start, end)) = average_with_range(samples) {
+            let severity = scaled_severity(max_temp, limit, limit + GPU_THROTTLE_FULL_SEVERITY_EXCESS);
+            bottlenecks.push(Bottleneck {
+                bottleneck_type: BottleneckType::Thermal,
+                severity,
+                evidence: vec![EvidenceItem {
+                    metric_type,
+                    threshold: limit,
+                    actual_value: max_temp,
+                    time_range_start: start,
+                    time_range_end: end,
+                    source_component: None,
+                }],
+                summary: format!("GPU {} temperature at throttle limit", sensor),
+                details: format!(
+                    "GPU {} temperature reached {:.1}°C (limit: {:.1}°C). \
+                     The GPU lowers its clocks to stay below this limit.",
+                    sensor, max_temp, limit
+                ),
+                onset: None,
+                active_fraction: 0.0,
+            });
+        }
+    }
+    
+    // Hotspot-to-edge gap, pairing each GPU's sensors on the same tick
+    let gaps: Vec<f64> = hotspot_gaps(metrics).into_iter().map(|(_, gap)| gap).collect();
+    if let (Some(avg_gap), Some((_, start, end))) = (mean(gaps.iter().copied()), average_with_range(&hotspot)) {
+        if avg_gap >= GPU_HOTSPOT_DELTA_THRESHOLD {
+            let max_gap = gaps.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let severity = scaled_severity(avg_gap, GPU_HOTSPOT_DELTA_THRESHOLD, GPU_HOTSPOT_DELTA_FULL_SEVERITY)
+                .min(GPU_HOTSPOT_GAP_MAX_SEVERITY);
+            bottlenecks.push(Bottleneck {
+                bottleneck_type: BottleneckType::Thermal,
+                severity,
+                evidence: vec![EvidenceItem {
+                    metric_type: MetricType::GpuHotspotTemperature,
+                    threshold: GPU_HOTSPOT_DELTA_THRESHOLD,
+                    actual_value: avg_gap,
+                    time_range_start: start,
+                    time_range_end: end,
+                    source_component: Some(GPU_HOTSPOT_GAP_SOURCE.to_string()),
+                }],
+                summary: format!("GPU hotspot runs {:.0}°C above edge temperature", avg_gap),
+                details: format!(
+                    "The GPU hotspot averaged {:.1}°C above the edge temperature (peak gap {:.1}°C, \
+                     expected below {:.0}°C). A large gap points at poor cooler contact: uneven \
+                     mounting pressure, dried-out thermal paste, or misaligned thermal pads.",
+                    avg_gap, max_gap, GPU_HOTSPOT_DELTA_THRESHOLD
+                ),
+                onset: None,
+                active_fraction: 0.0,
+            });
+        }
+    }
+    
+    bottlenecks
+}
+
 /// Detect multi-GPU scenarios and workload distribution
 ///
 /// Analyzes GPU utilization across multiple GPUs to detect
@@ -397,8 +595,7 @@ pub fn detect_multi_gpu_bottleneck(metrics: &[MetricSample]) -> Option<Bottlenec
             .map(|m| m.value)
             .collect();
         
-        if !utilizations.is_empty() {
-            let avg_util = utilizations.iter().sum::<f64>() / utilizations.len() as f64;
+        if let Some(avg_util) = mean(utilizations.iter().copied()) {
             gpu_utilizations.push((source.clone(), avg_util));
         }
     }
@@ -436,6 +633,7 @@ pub fn detect_multi_gpu_bottleneck(metrics: &[MetricSample]) -> Option<Bottlenec
             actual_value: max_util,
             time_range_start: gpu_metrics.first().unwrap().timestamp,
             time_range_end: gpu_metrics.last().unwrap().timestamp,
+            source_component: None,
         }];
         
         return Some(Bottleneck {
@@ -451,6 +649,8 @@ pub fn detect_multi_gpu_bottleneck(metrics: &[MetricSample]) -> Option<Bottlenec
                 max_util,
                 min_util
             ),
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
@@ -468,6 +668,7 @@ pub fn detect_multi_gpu_bottleneck(metrics: &[MetricSample]) -> Option<Bottlenec
             actual_value: avg_util,
             time_range_start: gpu_metrics.first().unwrap().timestamp,
             time_range_end: gpu_metrics.last().unwrap().timestamp,
+            source_component: None,
         }];
         
         return Some(Bottleneck {
@@ -481,9 +682,1366 @@ pub fn detect_multi_gpu_bottleneck(metrics: &[MetricSample]) -> Option<Bottlenec
                 gpu_utilizations.len(),
                 avg_util
             ),
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
     None
 }
 
+
+/// Cores whose maximum frequency is below this fraction of the fastest core
+/// are classified as efficiency cores
+pub const EFFICIENCY_CORE_FREQUENCY_RATIO: f64 = 0.85;
+
+/// Average efficiency-core utilization above which E-cores are considered busy (percent)
+pub const ECORE_BUSY_THRESHOLD: f64 = 70.0;
+
+/// Average performance-core utilization below which P-cores are considered idle (percent)
+pub const PCORE_IDLE_THRESHOLD: f64 = 30.0;
+
+/// Core type on hybrid CPUs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreType {
+    Performance,
+    Efficiency,
+}
+
+/// Classify logical cores by their maximum frequency
+///
+/// Cores clocked well below the fastest core are efficiency cores. On CPUs
+/// with uniform cores every core is classified as a performance core.
+pub fn classify_core_types(core_max_frequencies_mhz: &[f64]) -> Vec<CoreType> {
+    let fastest = core_max_frequencies_mhz.iter().cloned().fold(0.0, f64::max);
+    
+    core_max_frequencies_mhz
+        .iter()
+        .map(|&freq| {
+            if freq < fastest * EFFICIENCY_CORE_FREQUENCY_RATIO {
+                CoreType::Efficiency
+            } else {
+                CoreType::Performance
+            }
+        })
+        .collect()
+}
+
+/// Detect asymmetric loading of efficiency cores on hybrid CPUs
+///
+/// Flags workloads that run mostly on E-cores while P-cores sit idle, which
+/// usually means the OS scheduler has demoted the process (e.g. because its
+/// window is in the background) or its affinity excludes the P-cores.
+pub fn detect_heterogeneous_core_loading(
+    metrics: &[MetricSample],
+    core_types: &[CoreType],
+) -> Option<Bottleneck> {
+    if !core_types.contains(&CoreType::Efficiency) || !core_types.contains(&CoreType::Performance) {
+        return None;
+    }
+    
+    let per_core: Vec<(usize, &MetricSample)> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::CpuUtilizationPerCore)
+        .filter_map(|m| {
+            m.source_component
+                .strip_prefix("CPU Core ")
+                .and_then(|idx| idx.parse::<usize>().ok())
+                .filter(|idx| *idx < core_types.len())
+                .map(|idx| (idx, m))
+        })
+        .collect();
+    
+    let average_for = |core_type: CoreType| -> Option<f64> {
+        mean(
+            per_core
+                .iter()
+                .filter(|(idx, _)| core_types[*idx] == core_type)
+                .map(|(_, m)| m.value),
+        )
+    };
+    
+    let e_core_avg = average_for(CoreType::Efficiency)?;
+    let p_core_avg = average_for(CoreType::Performance)?;
+    
+    if e_core_avg < ECORE_BUSY_THRESHOLD || p_core_avg > PCORE_IDLE_THRESHOLD {
+        return None;
+    }
+    
+    let time_range_start = per_core.iter().map(|(_, m)| m.timestamp).min().unwrap();
+    let time_range_end = per_core.iter().map(|(_, m)| m.timestamp).max().unwrap();
+    let severity = scaled_severity(e_core_avg - p_core_avg, ECORE_BUSY_THRESHOLD - PCORE_IDLE_THRESHOLD, 100.0);
+    
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::Cpu,
+        severity,
+        evidence: vec![
+            EvidenceItem {
+                metric_type: MetricType::CpuUtilizationPerCore,
+                threshold: ECORE_BUSY_THRESHOLD,
+                actual_value: e_core_avg,
+                time_range_start,
+                time_range_end,
+                source_component: None,
+            },
+            EvidenceItem {
+                metric_type: MetricType::CpuUtilizationPerCore,
+                threshold: PCORE_IDLE_THRESHOLD,
+                actual_value: p_core_avg,
+                time_range_start,
+                time_range_end,
+                source_component: None,
+            },
+        ],
+        summary: "Workload is running on efficiency cores while performance cores idle".to_string(),
+        details: format!(
+            "Efficiency cores averaged {:.1}% utilization while performance cores averaged only {:.1}%. \
+             The scheduler is keeping this workload off the faster cores.",
+            e_core_avg, p_core_avg
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
+
+/// Loaded clocks below this fraction of the base clock (or boost clock when
+/// the base clock is unknown) are considered throttled
+pub const CPU_CLOCK_THROTTLE_RATIO: f64 = 0.8;
+
+/// Ratio of loaded to rated clock at which clock throttle severity reaches 100
+pub const CPU_CLOCK_FULL_SEVERITY_RATIO: f64 = 0.5;
+
+/// CPU utilization at or above which the CPU should be running at full clocks (percent)
+pub const CPU_CLOCK_LOAD_THRESHOLD: f64 = 70.0;
+
+/// Loaded ticks needed before low clocks count as sustained
+pub const CPU_CLOCK_MIN_SAMPLES: usize = 5;
+
+/// Detect CPU clocks held well below their rated speed under load
+///
+/// Clock samples are paired with overall CPU utilization taken on the same
+/// tick, and only loaded ticks are considered so that idle downclocking is
+/// not reported. When the CPU temperature reached the critical threshold the
+/// cause is reported as thermal; otherwise the clocks are being held down by
+/// power or current limits, or by a power-saving power plan.
+pub fn detect_cpu_clock_throttle(metrics: &[MetricSample], cpu: &CPUInfo) -> Option<Bottleneck> {
+    let rated_clock = cpu.base_clock_mhz.or(cpu.boost_clock_mhz).filter(|mhz| *mhz > 0.0)?;
+    let clock_threshold = rated_clock * CPU_CLOCK_THROTTLE_RATIO;
+    
+    let utilization_by_time: HashMap<_, f64> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::CpuUtilization)
+        .map(|m| (m.timestamp, m.value))
+        .collect();
+    let loaded_clocks: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::CpuClock && m.value.is_finite())
+        .filter(|m| {
+            utilization_by_time
+                .get(&m.timestamp)
+                .is_some_and(|util| *util >= CPU_CLOCK_LOAD_THRESHOLD)
+        })
+        .collect();
+    if loaded_clocks.len() < CPU_CLOCK_MIN_SAMPLES {
+        return None;
+    }
+    
+    let (avg_clock, start, end) = average_with_range(&loaded_clocks)?;
+    if avg_clock >= clock_threshold {
+        return None;
+    }
+    
+    let loaded_times: HashSet<_> = loaded_clocks.iter().map(|m| m.timestamp).collect();
+    let max_temp = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::Temperature && m.source_component == "CPU")
+        .filter(|m| loaded_times.contains(&m.timestamp))
+        .map(|m| m.value)
+        .filter(|v| v.is_finite())
+        .reduce(f64::max);
+    let thermal = max_temp.is_some_and(|temp| temp >= TEMP_CRITICAL_THRESHOLD);
+    
+    let clock_ratio = avg_clock / rated_clock;
+    // Scaled on the shortfall from the rated clock
+    let severity = scaled_severity(1.0 - clock_ratio, 1.0 - CPU_CLOCK_THROTTLE_RATIO, 1.0 - CPU_CLOCK_FULL_SEVERITY_RATIO);
+    let mut evidence = vec![EvidenceItem {
+        metric_type: MetricType::CpuClock,
+        threshold: clock_threshold,
+        actual_value: avg_clock,
+        time_range_start: start,
+        time_range_end: end,
+        source_component: None,
+    }];
+    if let Some(temp) = max_temp {
+        evidence.push(EvidenceItem {
+            metric_type: MetricType::Temperature,
+            threshold: TEMP_CRITICAL_THRESHOLD,
+            actual_value: temp,
+            time_range_start: start,
+            time_range_end: end,
+            source_component: None,
+        });
+    }
+    
+    let clocks = format!(
+        "Under load the CPU averaged {:.0} MHz, {:.0}% of its rated {:.0} MHz.",
+        avg_clock,
+        clock_ratio * 100.0,
+        rated_clock
+    );
+    let (bottleneck_type, summary, details) = if thermal {
+        (
+            BottleneckType::Thermal,
+            "CPU clocks throttled by temperature".to_string(),
+            format!(
+                "{} The CPU reached {:.1}°C, so it is lowering clocks to stay within its thermal limit.",
+                clocks,
+                max_temp.unwrap_or_default()
+            ),
+        )
+    } else {
+        let temperature = match max_temp {
+            Some(temp) => format!("The CPU peaked at only {:.1}°C, so this is not thermal throttling. ", temp),
+            None => String::new(),
+        };
+        (
+            BottleneckType::Cpu,
+            "CPU clocks held below rated speed under load".to_string(),
+            format!(
+                "{} {}Clocks are being limited by power or current limits, or by a power-saving power plan.",
+                clocks, temperature
+            ),
+        )
+    };
+    
+    Some(Bottleneck {
+        bottleneck_type,
+        severity,
+        evidence,
+        summary,
+        details,
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
+
+/// Average DPC time above which drivers are considered to hurt responsiveness (percent)
+pub const DPC_TIME_THRESHOLD: f64 = 5.0;
+
+/// Average interrupt time above which device interrupts are considered excessive (percent)
+pub const INTERRUPT_TIME_THRESHOLD: f64 = 5.0;
+
+/// Average DPC or interrupt time at which responsiveness severity reaches 100 (percent)
+pub const LATENCY_FULL_SEVERITY_TIME: f64 = 15.0;
+
+/// Detect system responsiveness issues caused by DPC/interrupt latency
+///
+/// Drivers that spend too long in deferred procedure calls or interrupt
+/// handlers cause stutter and audio dropouts even when utilization is low.
+pub fn detect_dpc_latency(metrics: &[MetricSample]) -> Option<Bottleneck> {
+    let average = |metric_type: MetricType| -> Option<(f64, &MetricSample, &MetricSample)> {
+        let samples: Vec<&MetricSample> = metrics
+            .iter()
+            .filter(|m| m.metric_type == metric_type)
+            .collect();
+        let first = *samples.iter().min_by_key(|m| m.timestamp)?;
+        let last = *samples.iter().max_by_key(|m| m.timestamp)?;
+        let avg = mean(samples.iter().map(|m| m.value))?;
+        Some((avg, first, last))
+    };
+    
+    let dpc = average(MetricType::DpcTime);
+    let interrupt = average(MetricType::InterruptTime);
+    
+    let dpc_high = dpc.is_some_and(|(avg, _, _)| avg > DPC_TIME_THRESHOLD);
+    let interrupt_high = interrupt.is_some_and(|(avg, _, _)| avg > INTERRUPT_TIME_THRESHOLD);
+    if !dpc_high && !interrupt_high {
+        return None;
+    }
+    
+    let mut evidence = Vec::new();
+    let mut severity = 0;
+    for (sample, metric_type, threshold) in [
+        (dpc, MetricType::DpcTime, DPC_TIME_THRESHOLD),
+        (interrupt, MetricType::InterruptTime, INTERRUPT_TIME_THRESHOLD),
+    ] {
+        if let Some((avg, first, last)) = sample {
+            severity = severity.max(scaled_severity(avg, threshold, LATENCY_FULL_SEVERITY_TIME));
+            evidence.push(EvidenceItem {
+                metric_type,
+                threshold,
+                actual_value: avg,
+                time_range_start: first.timestamp,
+                time_range_end: last.timestamp,
+                source_component: None,
+            });
+        }
+    }
+    
+    let dpc_avg = dpc.map(|(avg, _, _)| avg).unwrap_or(0.0);
+    let interrupt_avg = interrupt.map(|(avg, _, _)| avg).unwrap_or(0.0);
+    
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::Responsiveness,
+        severity,
+        evidence,
+        summary: format!(
+            "High driver latency: {:.1}% DPC time, {:.1}% interrupt time",
+            dpc_avg, interrupt_avg
+        ),
+        details: format!(
+            "The CPU spent {:.1}% of its time in deferred procedure calls and {:.1}% servicing interrupts. \
+             Drivers holding the CPU this long cause stutter and audio dropouts regardless of utilization.",
+            dpc_avg, interrupt_avg
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
+
+/// Average GPU utilization below which the GPU is considered starved (percent)
+pub const GPU_STARVED_THRESHOLD: f64 = 50.0;
+
+/// Spread between min and max GPU utilization that indicates stop-start work (percent)
+pub const GPU_STARVED_VARIANCE_THRESHOLD: f64 = 30.0;
+
+/// Storage read throughput that indicates the dataset is being streamed (MB/s)
+pub const INPUT_PIPELINE_READ_THRESHOLD: f64 = 100.0;
+
+/// Storage queue depth that indicates reads are backing up
+pub const INPUT_PIPELINE_QUEUE_DEPTH_THRESHOLD: f64 = 2.0;
+
+/// Per-core utilization above which a core is busy preprocessing (percent)
+pub const PREPROCESSING_CORE_BUSY_THRESHOLD: f64 = 80.0;
+
+/// Detect an AI training input pipeline that cannot keep the GPU fed
+///
+/// Requires three conditions at once: a starved GPU (low, fluctuating
+/// utilization), storage actively reading while the GPU waits, and at least
+/// one CPU core saturated by data preprocessing. A starved GPU without the
+/// other two is left to the generic GPU-starved check.
+pub fn detect_input_pipeline_bottleneck(metrics: &[MetricSample]) -> Option<Bottleneck> {
+    let gpu: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::GpuUtilization)
+        .collect();
+    let (avg_gpu, gpu_start, gpu_end) = average_with_range(&gpu)?;
+    let min_gpu = gpu.iter().map(|m| m.value).fold(f64::INFINITY, f64::min);
+    let max_gpu = gpu.iter().map(|m| m.value).fold(f64::NEG_INFINITY, f64::max);
+    if avg_gpu >= GPU_STARVED_THRESHOLD || max_gpu - min_gpu <= GPU_STARVED_VARIANCE_THRESHOLD {
+        return None;
+    }
+    
+    // Storage activity while the GPU is idle; fall back to the whole window
+    // when storage and GPU samples were not taken on the same ticks
+    let starved_ticks: HashSet<_> = gpu
+        .iter()
+        .filter(|m| m.value < GPU_STARVED_THRESHOLD)
+        .map(|m| m.timestamp)
+        .collect();
+    let storage_during_starvation = |metric_type: MetricType| -> Vec<&MetricSample> {
+        let all: Vec<&MetricSample> = metrics
+            .iter()
+            .filter(|m| m.metric_type == metric_type)
+            .collect();
+        let during: Vec<&MetricSample> = all
+            .iter()
+            .copied()
+            .filter(|m| starved_ticks.contains(&m.timestamp))
+            .collect();
+        if during.is_empty() { all } else { during }
+    };
+    let reads = average_with_range(&storage_during_starvation(MetricType::StorageReadThroughput));
+    let queue = average_with_range(&storage_during_starvation(MetricType::StorageQueueDepth));
+    let reads_busy = reads.is_some_and(|(avg, _, _)| avg >= INPUT_PIPELINE_READ_THRESHOLD);
+    let queue_busy = queue.is_some_and(|(avg, _, _)| avg >= INPUT_PIPELINE_QUEUE_DEPTH_THRESHOLD);
+    if !reads_busy && !queue_busy {
+        return None;
+    }
+    
+    // Busiest core, standing in for the data loader workers
+    let mut per_core: HashMap<&str, Vec<&MetricSample>> = HashMap::new();
+    for m in metrics.iter().filter(|m| m.metric_type == MetricType::CpuUtilizationPerCore) {
+        per_core.entry(m.source_component.as_str()).or_default().push(m);
+    }
+    let cpu = if per_core.is_empty() {
+        let overall: Vec<&MetricSample> = metrics
+            .iter()
+            .filter(|m| m.metric_type == MetricType::CpuUtilization)
+            .collect();
+        average_with_range(&overall).map(|avg| (MetricType::CpuUtilization, avg))
+    } else {
+        per_core
+            .values()
+            .filter_map(|samples| average_with_range(samples))
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|avg| (MetricType::CpuUtilizationPerCore, avg))
+    };
+    let (cpu_type, (busiest_core, cpu_start, cpu_end)) = cpu?;
+    if busiest_core < PREPROCESSING_CORE_BUSY_THRESHOLD {
+        return None;
+    }
+    
+    let mut evidence = vec![EvidenceItem {
+        metric_type: MetricType::GpuUtilization,
+        threshold: GPU_STARVED_THRESHOLD,
+        actual_value: avg_gpu,
+        time_range_start: gpu_start,
+        time_range_end: gpu_end,
+        source_component: None,
+    }];
+    for (sample, metric_type, threshold) in [
+        (reads, MetricType::StorageReadThroughput, INPUT_PIPELINE_READ_THRESHOLD),
+        (queue, MetricType::StorageQueueDepth, INPUT_PIPELINE_QUEUE_DEPTH_THRESHOLD),
+    ] {
+        if let Some((avg, start, end)) = sample {
+            evidence.push(EvidenceItem {
+                metric_type,
+                threshold,
+                actual_value: avg,
+                time_range_start: start,
+                time_range_end: end,
+                source_component: None,
+            });
+        }
+    }
+    evidence.push(EvidenceItem {
+        metric_type: cpu_type,
+        threshold: PREPROCESSING_CORE_BUSY_THRESHOLD,
+        actual_value: busiest_core,
+        time_range_start: cpu_start,
+        time_range_end: cpu_end,
+        source_component: None,
+    });
+    
+    let read_avg = reads.map(|(avg, _, _)| avg).unwrap_or(0.0);
+    let severity = ((GPU_STARVED_THRESHOLD - avg_gpu) / GPU_STARVED_THRESHOLD * 100.0).max(40.0) as u8;
+    
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::InputPipeline,
+        severity,
+        evidence,
+        summary: format!(
+            "Input pipeline bound: GPU averaged {:.1}% while storage read {:.1} MB/s and the busiest core was at {:.1}%",
+            avg_gpu, read_avg, busiest_core
+        ),
+        details: format!(
+            "GPU utilization averaged {:.1}% and swung between {:.1}% and {:.1}%, while storage kept reading and \
+             preprocessing saturated at least one CPU core. The GPU is waiting on data loading rather than on compute.",
+            avg_gpu, min_gpu, max_gpu
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
+
+/// FPS ceilings below this fraction of the refresh rate are considered capped
+pub const FRAME_CAP_REFRESH_RATIO: f64 = 0.9;
+
+/// FPS ceilings at or below this fraction of the refresh rate score the highest frame cap severity
+pub const FRAME_CAP_FULL_SEVERITY_RATIO: f64 = 0.5;
+
+/// Highest frame cap severity; a cap is a setting to change, not a hardware limit
+pub const FRAME_CAP_MAX_SEVERITY: u8 = 70;
+
+/// Average CPU and GPU utilization below which the hardware has headroom (percent)
+pub const FRAME_CAP_HEADROOM_THRESHOLD: f64 = 70.0;
+
+/// Samples within this distance of the ceiling count as sitting on it (fps)
+pub const FRAME_CAP_TOLERANCE_FPS: f64 = 2.0;
+
+/// Fraction of samples that must sit on the ceiling for it to be a hard cap
+pub const FRAME_CAP_PLATEAU_RATIO: f64 = 0.8;
+
+/// FPS samples needed before a ceiling is considered
+pub const FRAME_CAP_MIN_SAMPLES: usize = 10;
+
+/// Detect a frame rate held at a fixed ceiling well below the display refresh rate
+///
+/// A hardware limit makes the frame rate fluctuate with scene load, while
+/// VSync or a frame limiter pins it to a flat ceiling. When the frame rate
+/// sits on such a ceiling below the refresh rate and neither the CPU nor the
+/// GPU is busy, the cap is a configuration issue rather than a bottleneck.
+pub fn detect_frame_cap(metrics: &[MetricSample], refresh_rate_hz: f64) -> Option<Bottleneck> {
+    let fps: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::Fps && m.value.is_finite())
+        .collect();
+    if fps.len() < FRAME_CAP_MIN_SAMPLES {
+        return None;
+    }
+    
+    let values: Vec<f64> = fps.iter().map(|m| m.value).collect();
+    let ceiling = percentile(&values, 95.0)?;
+    if ceiling <= 0.0 || ceiling >= refresh_rate_hz * FRAME_CAP_REFRESH_RATIO {
+        return None;
+    }
+    let on_ceiling = values
+        .iter()
+        .filter(|v| (*v - ceiling).abs() <= FRAME_CAP_TOLERANCE_FPS)
+        .count();
+    if (on_ceiling as f64) < values.len() as f64 * FRAME_CAP_PLATEAU_RATIO {
+        return None;
+    }
+    
+    // Both processors need headroom; without GPU data a GPU limit cannot be ruled out
+    let of_type = |metric_type: MetricType| -> Vec<&MetricSample> {
+        metrics.iter().filter(|m| m.metric_type == metric_type).collect()
+    };
+    let gpu = average_with_range(&of_type(MetricType::GpuUtilization))?;
+    let cpu = average_with_range(&of_type(MetricType::CpuUtilization));
+    if gpu.0 >= FRAME_CAP_HEADROOM_THRESHOLD
+        || cpu.is_some_and(|(avg, _, _)| avg >= FRAME_CAP_HEADROOM_THRESHOLD)
+    {
+        return None;
+    }
+    
+    let (_, start, end) = average_with_range(&fps)?;
+    let mut evidence = vec![
+        EvidenceItem {
+            metric_type: MetricType::Fps,
+            threshold: refresh_rate_hz * FRAME_CAP_REFRESH_RATIO,
+            actual_value: ceiling,
+            time_range_start: start,
+            time_range_end: end,
+            source_component: None,
+        },
+        EvidenceItem {
+            metric_type: MetricType::GpuUtilization,
+            threshold: FRAME_CAP_HEADROOM_THRESHOLD,
+            actual_value: gpu.0,
+            time_range_start: gpu.1,
+            time_range_end: gpu.2,
+            source_component: None,
+        },
+    ];
+    if let Some((avg_cpu, cpu_start, cpu_end)) = cpu {
+        evidence.push(EvidenceItem {
+            metric_type: MetricType::CpuUtilization,
+            threshold: FRAME_CAP_HEADROOM_THRESHOLD,
+            actual_value: avg_cpu,
+            time_range_start: cpu_start,
+            time_range_end: cpu_end,
+            source_component: None,
+        });
+    }
+    
+    // Scaled on the shortfall from the refresh rate
+    let severity = scaled_severity(
+        1.0 - ceiling / refresh_rate_hz,
+        1.0 - FRAME_CAP_REFRESH_RATIO,
+        1.0 - FRAME_CAP_FULL_SEVERITY_RATIO,
+    )
+    .min(FRAME_CAP_MAX_SEVERITY);
+    
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::FrameCap,
+        severity,
+        evidence,
+        summary: format!(
+            "Frame rate capped at {:.0} fps on a {:.0} Hz display",
+            ceiling, refresh_rate_hz
+        ),
+        details: format!(
+            "The frame rate stayed at about {:.0} fps for {:.0}% of the run while the GPU averaged only {:.1}% \
+             utilization. The hardware has headroom, so the frame rate is being held down by VSync, a frame \
+             limiter, or the display running below its {:.0} Hz maximum.",
+            ceiling,
+            on_ceiling as f64 / values.len() as f64 * 100.0,
+            gpu.0,
+            refresh_rate_hz
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
+
+/// GPU utilization at or above which the PCIe link should be at full speed (percent)
+pub const PCIE_LINK_LOAD_THRESHOLD: f64 = 50.0;
+
+/// Loaded ticks needed before a narrower or slower link counts as sustained
+pub const PCIE_LINK_MIN_SAMPLES: usize = 3;
+
+/// Detect GPUs whose PCIe link runs below its maximum generation or width under load
+///
+/// Links are paired with GPU utilization from the same device and tick, and
+/// only loaded ticks are considered: GPUs drop to a lower generation at idle
+/// to save power, which is expected. Each PCIe generation roughly doubles
+/// per-lane bandwidth, which is used to estimate the bandwidth lost.
+pub fn detect_pcie_link_downgrade(metrics: &[MetricSample]) -> Vec<Bottleneck> {
+    let loaded_ticks: HashSet<_> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::GpuUtilization && m.value >= PCIE_LINK_LOAD_THRESHOLD)
+        .map(|m| (m.source_component.as_str(), m.timestamp))
+        .collect();
+    
+    let mut by_device: HashMap<&str, Vec<&MetricSample>> = HashMap::new();
+    for sample in metrics.iter().filter(|m| {
+        matches!(
+            m.metric_type,
+            MetricType::PcieLinkGen
+                | MetricType::PcieLinkWidth
+                | MetricType::PcieLinkGenMax
+                | MetricType::PcieLinkWidthMax
+        ) && loaded_ticks.contains(&(m.source_component.as_str(), m.timestamp))
+    }) {
+        by_device.entry(sample.source_component.as_str()).or_default().push(sample);
+    }
+    
+    let mut devices: Vec<_> = by_device.into_iter().collect();
+    devices.sort_by(|a, b| a.0.cmp(b.0));
+    
+    devices
+        .into_iter()
+        .filter_map(|(device, samples)| {
+            let of_type = |metric_type: MetricType| -> Vec<&MetricSample> {
+                samples.iter().copied().filter(|m| m.metric_type == metric_type).collect()
+            };
+            let widths = of_type(MetricType::PcieLinkWidth);
+            let gens = of_type(MetricType::PcieLinkGen);
+            if widths.len() < PCIE_LINK_MIN_SAMPLES || gens.len() < PCIE_LINK_MIN_SAMPLES {
+                return None;
+            }
+            
+            let (width, start, end) = average_with_range(&widths)?;
+            let (gen, _, _) = average_with_range(&gens)?;
+            let width_max = of_type(MetricType::PcieLinkWidthMax).iter().map(|m| m.value).reduce(f64::max)?;
+            let gen_max = of_type(MetricType::PcieLinkGenMax).iter().map(|m| m.value).reduce(f64::max)?;
+            if width >= width_max && gen >= gen_max {
+                return None;
+            }
+            
+            let bandwidth_ratio = (width / width_max) * 2f64.powf(gen - gen_max);
+            let severity = ((1.0 - bandwidth_ratio) * 100.0).clamp(0.0, 100.0) as u8;
+            let mut evidence = Vec::new();
+            if width < width_max {
+                evidence.push(EvidenceItem {
+                    metric_type: MetricType::PcieLinkWidth,
+                    threshold: width_max,
+                    actual_value: width,
+                    time_range_start: start,
+                    time_range_end: end,
+                    source_component: None,
+                });
+            }
+            if gen < gen_max {
+                evidence.push(EvidenceItem {
+                    metric_type: MetricType::PcieLinkGen,
+                    threshold: gen_max,
+                    actual_value: gen,
+                    time_range_start: start,
+                    time_range_end: end,
+                    source_component: None,
+                });
+            }
+            
+            Some(Bottleneck {
+                bottleneck_type: BottleneckType::Bandwidth,
+                severity,
+                evidence,
+                summary: format!(
+                    "{} PCIe link running at Gen{:.0} x{:.0} instead of Gen{:.0} x{:.0}",
+                    device, gen, width, gen_max, width_max
+                ),
+                details: format!(
+                    "Under load, the {} PCIe link averaged Gen{:.0} x{:.0} although the GPU and slot support \
+                     Gen{:.0} x{:.0}, leaving about {:.0}% of the link bandwidth. Common causes are a slot \
+                     sharing lanes with M.2 drives or other cards, a slot wired for fewer lanes, or PCIe \
+                     power management holding the link in a low-power state.",
+                    device, gen, width, gen_max, width_max, bandwidth_ratio * 100.0
+                ),
+                onset: None,
+                active_fraction: 0.0,
+            })
+        })
+        .collect()
+}
+
+/// GPU memory controller load at or above which VRAM bandwidth is saturated (percent)
+pub const VRAM_BANDWIDTH_LOAD_THRESHOLD: f64 = 80.0;
+
+/// VRAM capacity usage at or above which the VRAM capacity rule reports instead (percent)
+pub const VRAM_BANDWIDTH_CAPACITY_CEILING: f64 = super::VRAM_HIGH_THRESHOLD;
+
+/// Controller load samples needed before high load counts as sustained
+pub const VRAM_BANDWIDTH_MIN_SAMPLES: usize = 3;
+
+/// Detect GPUs limited by VRAM bandwidth rather than VRAM capacity
+///
+/// A memory controller that is busy most of the time while VRAM is only
+/// moderately full means the GPU is waiting on memory transfers, not running
+/// out of memory. Each device is judged on its own samples; devices whose
+/// VRAM is nearly full are left to the capacity rule.
+pub fn detect_vram_bandwidth_bottleneck(metrics: &[MetricSample]) -> Vec<Bottleneck> {
+    let mut by_device: HashMap<&str, Vec<&MetricSample>> = HashMap::new();
+    for sample in metrics.iter().filter(|m| {
+        matches!(m.metric_type, MetricType::GpuMemoryControllerLoad | MetricType::GpuVramUsagePercent)
+            && m.value.is_finite()
+    }) {
+        by_device.entry(sample.source_component.as_str()).or_default().push(sample);
+    }
+    
+    let mut devices: Vec<_> = by_device.into_iter().collect();
+    devices.sort_by(|a, b| a.0.cmp(b.0));
+    
+    devices
+        .into_iter()
+        .filter_map(|(device, samples)| {
+            let (loads, capacity): (Vec<&MetricSample>, Vec<&MetricSample>) = samples
+                .into_iter()
+                .partition(|m| m.metric_type == MetricType::GpuMemoryControllerLoad);
+            if loads.len() < VRAM_BANDWIDTH_MIN_SAMPLES {
+                return None;
+            }
+            
+            let (avg_load, start, end) = average_with_range(&loads)?;
+            if avg_load < VRAM_BANDWIDTH_LOAD_THRESHOLD {
+                return None;
+            }
+            let avg_capacity = mean(capacity.iter().map(|m| m.value));
+            if avg_capacity.is_some_and(|used| used >= VRAM_BANDWIDTH_CAPACITY_CEILING) {
+                return None;
+            }
+            
+            let severity = calculate_severity(avg_load, VRAM_BANDWIDTH_LOAD_THRESHOLD);
+            let mut evidence = vec![EvidenceItem {
+                metric_type: MetricType::GpuMemoryControllerLoad,
+                threshold: VRAM_BANDWIDTH_LOAD_THRESHOLD,
+                actual_value: avg_load,
+                time_range_start: start,
+                time_range_end: end,
+                source_component: None,
+            }];
+            if let Some(used) = avg_capacity {
+                evidence.push(EvidenceItem {
+                    metric_type: MetricType::GpuVramUsagePercent,
+                    threshold: VRAM_BANDWIDTH_CAPACITY_CEILING,
+                    actual_value: used,
+                    time_range_start: start,
+                    time_range_end: end,
+                    source_component: None,
+                });
+            }
+            
+            let capacity_note = match avg_capacity {
+                Some(used) => format!(" while only {:.0}% of VRAM was in use", used),
+                None => String::new(),
+            };
+            Some(Bottleneck {
+                bottleneck_type: BottleneckType::Vram,
+                severity,
+                evidence,
+                summary: format!(
+                    "{} VRAM bandwidth-bound: memory controller load averages {:.0}%",
+                    device, avg_load
+                ),
+                details: format!(
+                    "The {} memory controller was busy {:.0}% of the time{}. The GPU is waiting on \
+                     transfers to and from VRAM rather than running out of it, so lowering texture \
+                     resolution or moving to a GPU with faster memory helps more than adding capacity.",
+                    device, avg_load, capacity_note
+                ),
+                onset: None,
+                active_fraction: 0.0,
+            })
+        })
+        .collect()
+}
+
+/// Relative drop in clocks or FPS above the throttle point that confirms throttling
+pub const THERMAL_COUPLING_DROP_RATIO: f64 = 0.05;
+
+/// Samples needed on each side of the throttle point before comparing them
+pub const THERMAL_COUPLING_MIN_SAMPLES: usize = 3;
+
+/// Oldest temperature reading, relative to a clock or FPS sample, that still
+/// describes the conditions the sample was taken in (seconds)
+pub const THERMAL_COUPLING_MAX_GAP_SECONDS: i64 = 2;
+
+/// Severity of a hot component whose performance does not drop
+pub const HOT_WITHOUT_THROTTLING_SEVERITY: u8 = 40;
+
+/// Performance of one stream above and below the throttle point
+struct ThermalCoupling {
+    component: String,
+    temp_type: MetricType,
+    metric_type: MetricType,
+    max_temp: f64,
+    throttle_temp: f64,
+    cool_avg: f64,
+    hot_avg: f64,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+}
+
+impl ThermalCoupling {
+    fn drop_ratio(&self) -> f64 {
+        if self.cool_avg > 0.0 {
+            1.0 - self.hot_avg / self.cool_avg
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Compare a performance stream on ticks above and below the throttle point
+///
+/// Each sample is paired with the latest temperature reading taken at most
+/// `THERMAL_COUPLING_MAX_GAP_SECONDS` before it, so streams recorded by other
+/// tools line up with collected temperatures. `temps` must be sorted by time.
+/// Returns `None` unless both sides have enough samples to compare.
+fn thermal_coupling(
+    component: &str,
+    temp_type: &MetricType,
+    temps: &[(chrono::DateTime<chrono::Utc>, f64)],
+    throttle_temp: f64,
+    performance: &[&MetricSample],
+) -> Option<ThermalCoupling> {
+    let max_gap = chrono::Duration::seconds(THERMAL_COUPLING_MAX_GAP_SECONDS);
+    let temp_at = |timestamp: chrono::DateTime<chrono::Utc>| {
+        let idx = temps.partition_point(|(t, _)| *t <= timestamp);
+        let (taken, temp) = temps.get(idx.checked_sub(1)?)?;
+        (timestamp - *taken <= max_gap).then_some(*temp)
+    };
+    
+    let mut hot = Vec::new();
+    let mut cool = Vec::new();
+    let mut max_temp = f64::NEG_INFINITY;
+    for sample in performance.iter().filter(|m| m.value.is_finite()) {
+        if let Some(temp) = temp_at(sample.timestamp) {
+            if temp >= throttle_temp {
+                max_temp = max_temp.max(temp);
+                hot.push(*sample);
+            } else {
+                cool.push(*sample);
+            }
+        }
+    }
+    if hot.len() < THERMAL_COUPLING_MIN_SAMPLES || cool.len() < THERMAL_COUPLING_MIN_SAMPLES {
+        return None;
+    }
+    
+    let (hot_avg, start, end) = average_with_range(&hot)?;
+    let cool_avg = mean(cool.iter().map(|m| m.value))?;
+    Some(ThermalCoupling {
+        component: component.to_string(),
+        temp_type: temp_type.clone(),
+        metric_type: performance[0].metric_type.clone(),
+        max_temp,
+        throttle_temp,
+        cool_avg,
+        hot_avg,
+        start,
+        end,
+    })
+}
+
+/// Detect thermal throttling from its effect on clocks and frame rate
+///
+/// High temperature alone does not mean a component is throttling. For the
+/// CPU and each GPU, clock and FPS samples taken while the temperature was at
+/// or above the throttle point are compared with those taken below it. A drop
+/// of `THERMAL_COUPLING_DROP_RATIO` or more confirms throttling and is
+/// reported with high severity; otherwise the component is only reported as
+/// running hot. Returns `None` when no component crossed its throttle point
+/// with enough samples on both sides to tell.
+pub fn detect_thermal_coupling(metrics: &[MetricSample]) -> Option<Bottleneck> {
+    let of_source = |metric_type: MetricType, source: &str| -> Vec<&MetricSample> {
+        metrics
+            .iter()
+            .filter(|m| m.metric_type == metric_type && m.source_component == source)
+            .collect()
+    };
+    let fps: Vec<&MetricSample> = metrics.iter().filter(|m| m.metric_type == MetricType::Fps).collect();
+    
+    // (component, temperature metric, throttle point, clock metric)
+    let mut components = vec![("CPU".to_string(), MetricType::Temperature, TEMP_CRITICAL_THRESHOLD, MetricType::CpuClock)];
+    let gpu_sources: Vec<&str> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::GpuTemperature)
+        .map(|m| m.source_component.as_str())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    for source in gpu_sources {
+        components.push((source.to_string(), MetricType::GpuTemperature, GPU_EDGE_THROTTLE_TEMP, MetricType::GpuClock));
+    }
+    
+    let mut couplings = Vec::new();
+    for (component, temp_type, throttle_temp, clock_type) in components {
+        let mut temps: Vec<_> = of_source(temp_type.clone(), &component)
+            .iter()
+            .filter(|m| m.value.is_finite())
+            .map(|m| (m.timestamp, m.value))
+            .collect();
+        temps.sort_by_key(|(timestamp, _)| *timestamp);
+        if temps.is_empty() {
+            continue;
+        }
+        let clocks = of_source(clock_type, &component);
+        couplings.extend(
+            [clocks.as_slice(), fps.as_slice()]
+                .into_iter()
+                .filter(|samples| !samples.is_empty())
+                .filter_map(|samples| thermal_coupling(&component, &temp_type, &temps, throttle_temp, samples)),
+        );
+    }
+    
+    let strongest = couplings
+        .into_iter()
+        .max_by(|a, b| a.drop_ratio().total_cmp(&b.drop_ratio()))?;
+    let drop_ratio = strongest.drop_ratio();
+    let coupled = drop_ratio >= THERMAL_COUPLING_DROP_RATIO;
+    
+    let evidence = vec![
+        EvidenceItem {
+            metric_type: strongest.temp_type.clone(),
+            threshold: strongest.throttle_temp,
+            actual_value: strongest.max_temp,
+            time_range_start: strongest.start,
+            time_range_end: strongest.end,
+            source_component: None,
+        },
+        EvidenceItem {
+            metric_type: strongest.metric_type.clone(),
+            threshold: strongest.cool_avg,
+            actual_value: strongest.hot_avg,
+            time_range_start: strongest.start,
+            time_range_end: strongest.end,
+            source_component: None,
+        },
+    ];
+    let stream = if strongest.metric_type == MetricType::Fps { "frame rate" } else { "clocks" };
+    
+    if coupled {
+        Some(Bottleneck {
+            bottleneck_type: BottleneckType::Thermal,
+            severity: (70.0 + drop_ratio * 100.0).min(100.0) as u8,
+            evidence,
+            summary: format!(
+                "{} thermal throttling: {} drop {:.0}% above {:.0}°C",
+                strongest.component, stream, drop_ratio * 100.0, strongest.throttle_temp
+            ),
+            details: format!(
+                "While the {} was at or above {:.0}°C (peak {:.1}°C), {} averaged {:.1} compared with {:.1} \
+                 below it. Performance falls as temperature rises, so the component is throttling; \
+                 improve cooling to recover it.",
+                strongest.component, strongest.throttle_temp, strongest.max_temp, stream,
+                strongest.hot_avg, strongest.cool_avg
+            ),
+            onset: None,
+            active_fraction: 0.0,
+        })
+    } else {
+        Some(Bottleneck {
+            bottleneck_type: BottleneckType::Thermal,
+            severity: HOT_WITHOUT_THROTTLING_SEVERITY,
+            evidence,
+            summary: format!("{} high temperature without throttling", strongest.component),
+            details: format!(
+                "The {} reached {:.1}°C, but {} held steady above {:.0}°C ({:.1} compared with {:.1} below it), \
+                 so it is not throttling yet. Keep an eye on cooling.",
+                strongest.component, strongest.max_temp, stream, strongest.throttle_temp,
+                strongest.hot_avg, strongest.cool_avg
+            ),
+            onset: None,
+            active_fraction: 0.0,
+        })
+    }
+}
+
+/// Average value and time range of the given samples
+fn average_with_range(
+    samples: &[&MetricSample],
+) -> Option<(f64, chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> {
+    let start = samples.iter().map(|m| m.timestamp).min()?;
+    let end = samples.iter().map(|m| m.timestamp).max()?;
+    let avg = mean(samples.iter().map(|m| m.value))?;
+    Some((avg, start, end))
+}
+
+/// Updaters and launchers known to download in the background
+///
+/// Matched case-insensitively against process names without their extension.
+pub const BACKGROUND_DOWNLOADERS: &[&str] = &[
+    "steam",
+    "steamservice",
+    "epicgameslauncher",
+    "epicwebhelper",
+    "battle.net",
+    "eadesktop",
+    "easteamproxy",
+    "galaxyclient",
+    "upc",
+    "ubisoftconnect",
+    "origin",
+    "tiworker",
+    "trustedinstaller",
+    "mousocoreworker",
+    "usocoreworker",
+    "wuauclt",
+    "onedrive",
+    "dropbox",
+    "packagekitd",
+    "softwareupdated",
+];
+
+/// Average write rate of known downloaders that counts as interference (MB/s)
+pub const BACKGROUND_DOWNLOAD_WRITE_THRESHOLD: f64 = 20.0;
+
+/// Share of the system's disk writes the downloaders must account for
+pub const BACKGROUND_DOWNLOAD_WRITE_SHARE: f64 = 0.5;
+
+/// Downloader write rate reported at full severity (MB/s)
+pub const BACKGROUND_DOWNLOAD_SATURATED_WRITE: f64 = 200.0;
+
+/// Whether a process sample source names a known background downloader
+///
+/// Sources are "name (pid N)", as written by the per-process disk sampler.
+pub fn is_background_downloader(source: &str) -> bool {
+    let name = source.split(" (pid ").next().unwrap_or(source).to_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    BACKGROUND_DOWNLOADERS.contains(&name)
+}
+
+/// Detect an updater or launcher downloading while the workload ran
+///
+/// Known downloaders must average `BACKGROUND_DOWNLOAD_WRITE_THRESHOLD` of
+/// disk writes and, when the system's write throughput was recorded, account
+/// for at least `BACKGROUND_DOWNLOAD_WRITE_SHARE` of it. A process is averaged
+/// over every tick with per-process samples and counts as idle in ticks where
+/// it is missing. Network throughput is not collected, so downloads are
+/// recognized by the writes they cause.
+pub fn detect_background_download(metrics: &[MetricSample]) -> Option<Bottleneck> {
+    let process_samples: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::ProcessDiskWriteThroughput && m.value.is_finite())
+        .collect();
+    let ticks: HashSet<_> = process_samples.iter().map(|m| m.timestamp).collect();
+    
+    let mut downloaders: HashMap<&str, Vec<&MetricSample>> = HashMap::new();
+    for sample in process_samples.iter().filter(|m| is_background_downloader(&m.source_component)) {
+        downloaders.entry(sample.source_component.as_str()).or_default().push(sample);
+    }
+    let mut averages: Vec<(&str, f64)> = downloaders
+        .iter()
+        .map(|(source, samples)| (*source, samples.iter().map(|m| m.value).sum::<f64>() / ticks.len() as f64))
+        .collect();
+    averages.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let download_write: f64 = averages.iter().map(|(_, avg)| avg).sum();
+    if download_write < BACKGROUND_DOWNLOAD_WRITE_THRESHOLD {
+        return None;
+    }
+    
+    let system_writes: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::StorageWriteThroughput)
+        .collect();
+    let system_write = average_with_range(&system_writes).filter(|(avg, _, _)| *avg > 0.0);
+    if let Some((avg, _, _)) = system_write {
+        if download_write < avg * BACKGROUND_DOWNLOAD_WRITE_SHARE {
+            return None;
+        }
+    }
+    
+    let start = process_samples.iter().map(|m| m.timestamp).min()?;
+    let end = process_samples.iter().map(|m| m.timestamp).max()?;
+    let mut evidence: Vec<EvidenceItem> = averages
+        .iter()
+        .map(|(source, avg)| EvidenceItem {
+            metric_type: MetricType::ProcessDiskWriteThroughput,
+            threshold: BACKGROUND_DOWNLOAD_WRITE_THRESHOLD,
+            actual_value: *avg,
+            time_range_start: start,
+            time_range_end: end,
+            source_component: Some(source.to_string()),
+        })
+        .collect();
+    if let Some((avg, system_start, system_end)) = system_write {
+        evidence.push(EvidenceItem {
+            metric_type: MetricType::StorageWriteThroughput,
+            threshold: BACKGROUND_DOWNLOAD_WRITE_THRESHOLD,
+            actual_value: avg,
+            time_range_start: system_start,
+            time_range_end: system_end,
+            source_component: None,
+        });
+    }
+    
+    let names: Vec<&str> = averages.iter().map(|(source, _)| *source).collect();
+    let severity = (download_write / BACKGROUND_DOWNLOAD_SATURATED_WRITE * 100.0).clamp(40.0, 100.0) as u8;
+    
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::BackgroundDownload,
+        severity,
+        evidence,
+        summary: format!(
+            "Background download interference: {} wrote {:.1} MB/s to disk during the run",
+            names.join(", "),
+            download_write
+        ),
+        details: format!(
+            "Updaters or launchers ({}) averaged {:.1} MB/s of disk writes while the workload ran. Storage load \
+             from a background download is not caused by the workload, so storage results from this run are \
+             not representative.",
+            names.join(", "),
+            download_write
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
+
+/// Share of each subsystem's threshold that counts as near it
+pub const COMBINED_PRESSURE_FRACTION: f64 = 0.9;
+
+/// Subsystems that must be near their thresholds together
+pub const COMBINED_PRESSURE_MIN_SUBSYSTEMS: usize = 3;
+
+/// Detect balanced high load across CPU, GPU, RAM and VRAM
+///
+/// Each subsystem is summarized with the profile's statistic and compared
+/// with its threshold. When at least `COMBINED_PRESSURE_MIN_SUBSYSTEMS` reach
+/// the near fraction (`combined_pressure` from the overrides, defaulting to
+/// `COMBINED_PRESSURE_FRACTION`) while none exceeds its threshold, the system
+/// as a whole is close to its limits with no single part to blame. Once any
+/// subsystem exceeds its threshold, that subsystem's own bottleneck is the
+/// finding and this returns `None`.
+pub fn detect_combined_pressure(
+    metrics: &[MetricSample],
+    thresholds: Option<&ThresholdOverrides>,
+) -> Option<Bottleneck> {
+    let fraction = thresholds
+        .and_then(|t| t.combined_pressure)
+        .filter(|f| *f > 0.0 && *f <= 1.0)
+        .unwrap_or(COMBINED_PRESSURE_FRACTION);
+    let statistic = thresholds.map(|t| t.statistic).unwrap_or_default();
+    
+    // (subsystem, metric, threshold)
+    let subsystems = [
+        ("CPU", MetricType::CpuUtilization, thresholds.and_then(|t| t.cpu_high).unwrap_or(CPU_HIGH_THRESHOLD)),
+        ("GPU", MetricType::GpuUtilization, thresholds.and_then(|t| t.gpu_high).unwrap_or(GPU_HIGH_THRESHOLD)),
+        ("RAM", MetricType::MemoryUsage, thresholds.and_then(|t| t.ram_high).unwrap_or(RAM_HIGH_THRESHOLD)),
+        (
+            "VRAM",
+            MetricType::GpuVramUsagePercent,
+            thresholds.and_then(|t| t.vram_high).unwrap_or(VRAM_HIGH_THRESHOLD),
+        ),
+    ];
+    
+    let mut near = Vec::new();
+    let mut evidence = Vec::new();
+    for (name, metric_type, threshold) in subsystems {
+        let samples: Vec<&MetricSample> = metrics
+            .iter()
+            .filter(|m| m.metric_type == metric_type && m.value.is_finite())
+            .collect();
+        let (Some(start), Some(end)) = (
+            samples.iter().map(|m| m.timestamp).min(),
+            samples.iter().map(|m| m.timestamp).max(),
+        ) else {
+            continue;
+        };
+        let values: Vec<f64> = samples.iter().map(|m| m.value).collect();
+        let value = window_statistic(&values, statistic);
+        if value > threshold {
+            return None;
+        }
+        if value >= threshold * fraction {
+            near.push((name, value, threshold));
+            evidence.push(EvidenceItem {
+                metric_type,
+                threshold: threshold * fraction,
+                actual_value: value,
+                time_range_start: start,
+                time_range_end: end,
+                source_component: None,
+            });
+        }
+    }
+    
+    if near.len() < COMBINED_PRESSURE_MIN_SUBSYSTEMS {
+        return None;
+    }
+    
+    // Capped below a subsystem well past its threshold: 30 at the near line, 50 at the thresholds
+    let closeness = mean(near.iter().map(|&(_, value, threshold)| value / threshold))?;
+    let severity = (30.0 + (closeness - fraction) / (1.0 - fraction).max(f64::EPSILON) * 20.0).clamp(30.0, 50.0) as u8;
+    let loads = near
+        .iter()
+        .map(|(name, value, threshold)| format!("{} at {:.0}% (threshold {:.0}%)", name, value, threshold))
+        .collect::<Vec<_>>()
+        .join(", ");
+    
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::CombinedLoad,
+        severity,
+        evidence,
+        summary: format!("Balanced high load: {} subsystems near their limits", near.len()),
+        details: format!(
+            "No single component exceeds its threshold, but {} are all within {:.0}% of it. \
+             The system is running close to its limits as a whole, so upgrading one part \
+             would soon move the bottleneck to another.",
+            loads,
+            (1.0 - fraction) * 100.0
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
+
+/// Model name fragments of integrated GPUs, matched case-insensitively
+pub const INTEGRATED_GPU_MARKERS: &[&str] = &[
+    "integrated",
+    "uhd graphics",
+    "hd graphics",
+    "iris",
+    "arc(tm) graphics",
+    "arc graphics",
+    "radeon graphics",
+    "radeon(tm) graphics",
+    "vega 3",
+    "vega 6",
+    "vega 7",
+    "vega 8",
+    "vega 11",
+    "radeon 610m",
+    "radeon 660m",
+    "radeon 680m",
+    "radeon 740m",
+    "radeon 760m",
+    "radeon 780m",
+    "radeon 880m",
+    "radeon 890m",
+    "apple",
+];
+
+/// Average integrated GPU utilization that marks it as rendering the game (percent)
+pub const HYBRID_IGPU_LOADED_THRESHOLD: f64 = 60.0;
+
+/// Average discrete GPU utilization below which it counts as idle (percent)
+pub const HYBRID_DGPU_IDLE_THRESHOLD: f64 = 10.0;
+
+/// Integrated GPU samples needed before misrouting is considered
+pub const HYBRID_MIN_SAMPLES: usize = 5;
+
+/// Lowest severity reported for a misrouted game
+///
+/// Running on the integrated GPU costs far more performance than any
+/// threshold excess suggests, so misrouting is always a high-severity finding.
+pub const HYBRID_MISROUTING_MIN_SEVERITY: u8 = 80;
+
+/// Kind of GPU in a hybrid graphics laptop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuKind {
+    Integrated,
+    Discrete,
+}
+
+/// Classify a detected GPU by its model name
+///
+/// Returns `None` for placeholder entries whose model is unknown.
+pub fn classify_gpu(gpu: &GPUInfo) -> Option<GpuKind> {
+    let model = gpu.model.to_lowercase();
+    if model.is_empty() || model.starts_with("unknown") {
+        return None;
+    }
+    if INTEGRATED_GPU_MARKERS.iter().any(|marker| model.contains(marker)) {
+        Some(GpuKind::Integrated)
+    } else {
+        Some(GpuKind::Discrete)
+    }
+}
+
+/// Detect a game rendered on the integrated GPU while the discrete GPU idles
+///
+/// Needs per-GPU utilization samples whose source is the GPU model, as
+/// listed in `integrated` and `discrete`, which the collector records as
+/// `GpuAdapterUtilization`. The busiest integrated GPU must
+/// average `HYBRID_IGPU_LOADED_THRESHOLD` over at least `HYBRID_MIN_SAMPLES`
+/// samples while every discrete GPU stays below `HYBRID_DGPU_IDLE_THRESHOLD`.
+/// A discrete GPU counts as idle in ticks where it has no sample, since a
+/// sleeping GPU reports no utilization.
+pub fn detect_hybrid_graphics_misrouting(
+    metrics: &[MetricSample],
+    integrated: &[String],
+    discrete: &[String],
+) -> Option<Bottleneck> {
+    if integrated.is_empty() || discrete.is_empty() {
+        return None;
+    }
+    let utilization = |model: &String| -> Vec<&MetricSample> {
+        metrics
+            .iter()
+            .filter(|m| {
+                matches!(m.metric_type, MetricType::GpuUtilization | MetricType::GpuAdapterUtilization)
+                    && m.value.is_finite()
+                    && m.source_component == *model
+            })
+            .collect()
+    };
+    
+    let (igpu_model, igpu_samples, igpu_avg) = integrated
+        .iter()
+        .filter_map(|model| {
+            let samples = utilization(model);
+            if samples.len() < HYBRID_MIN_SAMPLES {
+                return None;
+            }
+            let avg = mean(samples.iter().map(|m| m.value))?;
+            Some((model, samples, avg))
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))?;
+    if igpu_avg < HYBRID_IGPU_LOADED_THRESHOLD {
+        return None;
+    }
+    
+    let mut ticks: HashSet<_> = igpu_samples.iter().map(|m| m.timestamp).collect();
+    let discrete_samples: Vec<(&String, Vec<&MetricSample>)> =
+        discrete.iter().map(|model| (model, utilization(model))).collect();
+    ticks.extend(discrete_samples.iter().flat_map(|(_, samples)| samples.iter().map(|m| m.timestamp)));
+    let (dgpu_model, dgpu_avg) = discrete_samples
+        .iter()
+        .map(|(model, samples)| (*model, samples.iter().map(|m| m.value).sum::<f64>() / ticks.len() as f64))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    if dgpu_avg >= HYBRID_DGPU_IDLE_THRESHOLD {
+        return None;
+    }
+    
+    let start = ticks.iter().min().copied()?;
+    let end = ticks.iter().max().copied()?;
+    let evidence = vec![
+        EvidenceItem {
+            metric_type: MetricType::GpuUtilization,
+            threshold: HYBRID_IGPU_LOADED_THRESHOLD,
+            actual_value: igpu_avg,
+            time_range_start: start,
+            time_range_end: end,
+            source_component: Some(igpu_model.clone()),
+        },
+        EvidenceItem {
+            metric_type: MetricType::GpuUtilization,
+            threshold: HYBRID_DGPU_IDLE_THRESHOLD,
+            actual_value: dgpu_avg,
+            time_range_start: start,
+            time_range_end: end,
+            source_component: Some(dgpu_model.clone()),
+        },
+    ];
+    let severity = calculate_severity(igpu_avg, HYBRID_IGPU_LOADED_THRESHOLD).max(HYBRID_MISROUTING_MIN_SEVERITY);
+    
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::HybridGraphics,
+        severity,
+        evidence,
+        summary: format!("Game running on the integrated GPU ({}) instead of {}", igpu_model, dgpu_model),
+        details: format!(
+            "The integrated {} averaged {:.1}% utilization while the discrete {} averaged only {:.1}%. \
+             The game is being rendered on the integrated GPU, which usually happens when the GPU \
+             preference is set to power saving, the laptop runs on battery, or battery saver is on.",
+            igpu_model, igpu_avg, dgpu_model, dgpu_avg
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
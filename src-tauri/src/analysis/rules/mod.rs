@@ -4,16 +4,32 @@
 //! following AGENT.md Section 6.4.3.
 
 pub mod advanced;
+pub mod registry;
+pub mod trace;
 
 use crate::core::domain::{
-    Bottleneck, BottleneckAnalysisResult, BottleneckType, EvidenceItem, MetricSample, MetricType,
-    WorkloadProfile, WorkloadType,
+    AnalysisCoverage, Bottleneck, BottleneckAnalysisResult, BottleneckType, EvidenceItem, MetricSample,
+    MetricType, ThresholdStatistic, WorkloadProfile, WorkloadType, GPU_HOTSPOT_SOURCE,
 };
-use chrono::{Duration, Utc};
+use crate::metrics::providers::gpu_processes::top_gpu_process;
+use crate::metrics::utils::{mean, percentile, trimmed_mean};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 pub use advanced::{
-    detect_enhanced_thermal_bottleneck, detect_memory_bus_saturation, detect_multi_gpu_bottleneck,
-    detect_pcie_saturation,
+    classify_core_types, detect_background_download, detect_cooling_headroom, detect_cpu_clock_throttle, detect_dpc_latency,
+    detect_enhanced_thermal_bottleneck, detect_frame_cap, detect_gpu_thermal,
+    detect_heterogeneous_core_loading,
+    detect_input_pipeline_bottleneck, detect_memory_bus_saturation, detect_memory_bus_saturation_with_min_duration,
+    detect_multi_gpu_bottleneck, detect_combined_pressure, detect_pcie_link_downgrade, detect_pcie_saturation,
+    detect_pcie_saturation_with_min_duration, detect_thermal_coupling,
+    detect_vram_bandwidth_bottleneck, classify_gpu, detect_hybrid_graphics_misrouting, CoreType, GpuKind,
+    GPU_HOTSPOT_GAP_SOURCE,
 };
+pub use registry::{
+    default_rules, AnalysisContext, BottleneckRule, CombinedPressureRule, CoolingHeadroomRule, CpuClockThrottleRule, FrameCapRule,
+    HeterogeneousCoreRule, HybridGraphicsRule,
+};
+pub use trace::{trace_bottlenecks_at, RuleTrace, TraceCheck, TraceComparison};
 
 /// Threshold constants for bottleneck detection
 pub const CPU_HIGH_THRESHOLD: f64 = 85.0; // 85% utilization
@@ -21,94 +37,465 @@ pub const GPU_HIGH_THRESHOLD: f64 = 90.0; // 90% utilization
 pub const RAM_HIGH_THRESHOLD: f64 = 90.0; // 90% usage
 pub const VRAM_HIGH_THRESHOLD: f64 = 90.0; // 90% usage
 
+/// GPU utilization below which a busy CPU is the limiting factor
+const CPU_BOUND_MAX_GPU: f64 = 70.0;
+
+/// CPU utilization below which a busy GPU is the limiting factor
+const GPU_BOUND_MAX_CPU: f64 = 80.0;
+
 /// Time window for sustained threshold violations (in seconds)
 pub const SUSTAINED_WINDOW_SECONDS: i64 = 30;
 
-/// Analyze metrics to detect bottlenecks
+/// Consecutive violating ticks that mark the onset of a bottleneck
+pub const ONSET_MIN_SAMPLES: usize = 3;
+
+/// Seconds a storage or bandwidth limit must be exceeded without a break
+/// before it counts as a bottleneck, unless a profile overrides it
+pub const SUSTAINED_MIN_SECONDS: f64 = 5.0;
+
+/// I/O queue depth above which storage counts as saturated
+pub const STORAGE_QUEUE_DEPTH_THRESHOLD: f64 = 10.0;
+
+/// I/O queue depth at which storage severity reaches 100
+pub const STORAGE_QUEUE_FULL_SEVERITY_DEPTH: f64 = 40.0;
+
+/// Share of the requested window the data must span to avoid a coverage warning
+pub const COVERAGE_WARNING_RATIO: f64 = 0.5;
+
+/// VRAM use, in percent of the device's total, that shows a GPU is working
+pub const GPU_ACTIVE_VRAM_PERCENT: f64 = 50.0;
+
+/// Board power draw, in watts, that shows a GPU is working
+pub const GPU_ACTIVE_POWER_WATTS: f64 = 75.0;
+
+/// Share of working ticks without utilization that marks the readings unreliable
+pub const GPU_ZERO_UTILIZATION_RATIO: f64 = 0.8;
+
+/// Working ticks needed before a GPU's utilization readings are judged
+pub const GPU_ZERO_UTILIZATION_MIN_SAMPLES: usize = 3;
+
+/// Analyze metrics to detect bottlenecks using the built-in rules
 pub fn analyze_bottlenecks(
     metrics: &[MetricSample],
     time_window_seconds: i64,
     profile: Option<&WorkloadProfile>,
 ) -> BottleneckAnalysisResult {
-    let now = Utc::now();
-    let window_start = now - Duration::seconds(time_window_seconds);
+    analyze_bottlenecks_with_rules(metrics, time_window_seconds, profile, &default_rules())
+}
+
+/// Analyze metrics to detect bottlenecks using the given rules
+pub fn analyze_bottlenecks_with_rules(
+    metrics: &[MetricSample],
+    time_window_seconds: i64,
+    profile: Option<&WorkloadProfile>,
+    rules: &[Box<dyn BottleneckRule>],
+) -> BottleneckAnalysisResult {
+    analyze_bottlenecks_at(metrics, time_window_seconds, profile, rules, Utc::now())
+}
+
+/// Analyze metrics in the window ending at `window_end`
+///
+/// Live analysis ends the window at the current time; recorded runs end it at
+/// their newest sample so that past data still falls inside the window.
+pub fn analyze_bottlenecks_at(
+    metrics: &[MetricSample],
+    time_window_seconds: i64,
+    profile: Option<&WorkloadProfile>,
+    rules: &[Box<dyn BottleneckRule>],
+    window_end: DateTime<Utc>,
+) -> BottleneckAnalysisResult {
+    let window_start = window_end - Duration::seconds(time_window_seconds);
     
     // Filter metrics to the time window
     let recent_metrics: Vec<MetricSample> = metrics
         .iter()
-        .filter(|m| m.timestamp >= window_start && m.timestamp <= now)
+        .filter(|m| m.timestamp >= window_start && m.timestamp <= window_end)
         .cloned()
         .collect();
     
-    let mut bottlenecks = Vec::new();
+    let ctx = AnalysisContext {
+        profile,
+        window_start,
+        window_end,
+    };
     
-    // Check for enhanced thermal throttling (applies to all workloads)
-    // Use enhanced thermal detection if available, fallback to basic
-    if let Some(thermal_bottleneck) = detect_enhanced_thermal_bottleneck(&recent_metrics) {
-        bottlenecks.push(thermal_bottleneck);
-    } else if let Some(thermal_bottleneck) = detect_thermal_throttling(&recent_metrics) {
-        bottlenecks.push(thermal_bottleneck);
+    let mut bottlenecks: Vec<Bottleneck> = rules
+        .iter()
+        .flat_map(|rule| rule.detect_all(&recent_metrics, &ctx))
+        .collect();
+    for bottleneck in &mut bottlenecks {
+        if bottleneck.onset.is_none() {
+            bottleneck.onset = bottleneck_onset(bottleneck, &recent_metrics);
+        }
+        bottleneck.active_fraction = bottleneck_active_fraction(bottleneck, &recent_metrics);
+        if matches!(bottleneck.bottleneck_type, BottleneckType::Gpu | BottleneckType::Vram) {
+            attribute_gpu_process(bottleneck, &recent_metrics, window_start, window_end);
+        }
     }
+    sort_bottlenecks(&mut bottlenecks);
     
-    // Check for bandwidth bottlenecks (PCIe and memory bus)
-    if let Some(pcie_bottleneck) = detect_pcie_saturation(&recent_metrics) {
-        bottlenecks.push(pcie_bottleneck);
+    BottleneckAnalysisResult {
+        bottlenecks,
+        timestamp: Utc::now(),
+        coverage: Some(analysis_coverage(&recent_metrics, time_window_seconds)),
     }
+}
+
+/// Sort bottlenecks by severity, most severe first, then by bottleneck type
+///
+/// The sort is stable, so bottlenecks of the same type and severity keep the
+/// order their rules reported them in. Rule and workload detection order
+/// therefore never shows through in the result.
+pub fn sort_bottlenecks(bottlenecks: &mut [Bottleneck]) {
+    bottlenecks.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.bottleneck_type.cmp(&b.bottleneck_type))
+    });
+}
+
+/// Name the process using the most GPU time during a GPU bottleneck
+///
+/// Adds the top process from per-process GPU samples as evidence, so users can
+/// confirm the load comes from their workload and not a background app. Does
+/// nothing where per-process usage is not collected.
+fn attribute_gpu_process(
+    bottleneck: &mut Bottleneck,
+    metrics: &[MetricSample],
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+) {
+    let Some((process, utilization)) = top_gpu_process(metrics, window_start, window_end) else {
+        return;
+    };
+    bottleneck.details.push_str(&format!(
+        " The busiest GPU process was {} at {:.1}% average GPU utilization.",
+        process, utilization
+    ));
+    bottleneck.evidence.push(EvidenceItem {
+        metric_type: MetricType::ProcessGpuUtilization,
+        threshold: 0.0,
+        actual_value: utilization,
+        time_range_start: window_start,
+        time_range_end: window_end,
+        source_component: Some(process),
+    });
+}
+
+/// Measure how much of the requested window the analyzed samples span
+///
+/// Warns when they cover less than `COVERAGE_WARNING_RATIO` of it, e.g. a
+/// 60-minute window requested over 30 seconds of data.
+pub fn analysis_coverage(metrics: &[MetricSample], requested_seconds: i64) -> AnalysisCoverage {
+    let first = metrics.iter().map(|m| m.timestamp).min();
+    let last = metrics.iter().map(|m| m.timestamp).max();
+    let covered_seconds = match (first, last) {
+        (Some(first), Some(last)) => (last - first).num_seconds(),
+        _ => 0,
+    };
     
-    if let Some(memory_bus_bottleneck) = detect_memory_bus_saturation(&recent_metrics) {
-        bottlenecks.push(memory_bus_bottleneck);
-    }
+    let warning = if first.is_none() {
+        Some(format!("No samples fall within the requested {} window", format_window(requested_seconds)))
+    } else if (covered_seconds as f64) < requested_seconds as f64 * COVERAGE_WARNING_RATIO {
+        Some(format!(
+            "Analyzed the last {} of a requested {} window",
+            format_window(covered_seconds),
+            format_window(requested_seconds)
+        ))
+    } else {
+        None
+    };
     
-    // Check for multi-GPU bottlenecks
-    if let Some(multi_gpu_bottleneck) = detect_multi_gpu_bottleneck(&recent_metrics) {
-        bottlenecks.push(multi_gpu_bottleneck);
+    AnalysisCoverage {
+        requested_seconds,
+        covered_seconds,
+        warning,
+        data_warnings: gpu_utilization_warnings(metrics),
     }
-    
-    // Use workload-specific analysis if profile is provided
-    if let Some(profile) = profile {
-        match profile.workload_type {
-            WorkloadType::Gaming => {
-                if let Some(b) = detect_gaming_bottlenecks(&recent_metrics, profile) {
-                    bottlenecks.extend(b);
-                }
-            }
-            WorkloadType::Rendering => {
-                if let Some(b) = detect_rendering_bottlenecks(&recent_metrics, profile) {
-                    bottlenecks.extend(b);
-                }
+}
+
+/// Warn about GPUs whose utilization reads 0% while VRAM use or power draw shows load
+///
+/// Driver counters such as nvidia-smi's `utilization.gpu` cover only some
+/// engines and can read 0% throughout compute or video work. The collector
+/// drops zero readings, so without this check such a GPU looks idle. A tick
+/// counts as working when the GPU's VRAM use or power draw is substantial,
+/// and as unread when it has no positive utilization sample from that GPU.
+pub fn gpu_utilization_warnings(metrics: &[MetricSample]) -> Vec<String> {
+    let mut working: BTreeMap<&str, BTreeSet<DateTime<Utc>>> = BTreeMap::new();
+    let mut utilized: HashSet<(&str, DateTime<Utc>)> = HashSet::new();
+    for sample in metrics {
+        let source = sample.source_component.as_str();
+        match sample.metric_type {
+            MetricType::GpuVramUsagePercent if sample.value >= GPU_ACTIVE_VRAM_PERCENT => {
+                working.entry(source).or_default().insert(sample.timestamp);
             }
-            WorkloadType::AI => {
-                if let Some(b) = detect_ai_ml_bottlenecks(&recent_metrics, profile) {
-                    bottlenecks.extend(b);
-                }
+            MetricType::GpuPower if sample.value >= GPU_ACTIVE_POWER_WATTS => {
+                working.entry(source).or_default().insert(sample.timestamp);
             }
-            WorkloadType::Productivity | WorkloadType::General => {
-                if let Some(b) = detect_productivity_bottlenecks(&recent_metrics, profile) {
-                    bottlenecks.extend(b);
-                }
+            MetricType::GpuUtilization if sample.value > 0.0 => {
+                utilized.insert((source, sample.timestamp));
             }
+            _ => {}
         }
+    }
+    
+    working
+        .into_iter()
+        .filter_map(|(source, ticks)| {
+            let unread = ticks.iter().filter(|t| !utilized.contains(&(source, **t))).count();
+            let unreliable = ticks.len() >= GPU_ZERO_UTILIZATION_MIN_SAMPLES
+                && unread as f64 >= ticks.len() as f64 * GPU_ZERO_UTILIZATION_RATIO;
+            unreliable.then(|| {
+                format!(
+                    "{} utilization read 0% in {} of {} samples with substantial VRAM use or power draw; \
+                     its utilization readings are unreliable, so a different GPU utilization source may be needed",
+                    source,
+                    unread,
+                    ticks.len()
+                )
+            })
+        })
+        .collect()
+}
+
+/// Format a window length as "45s", "60m" or "2m 30s"
+fn format_window(seconds: i64) -> String {
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds % 60 == 0 {
+        format!("{}m", seconds / 60)
     } else {
-        // Fallback to generic analysis
-        if let Some(cpu_bottleneck) = detect_cpu_bottleneck(&recent_metrics, None) {
-            bottlenecks.push(cpu_bottleneck);
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    }
+}
+
+/// Find when a bottleneck's threshold first started being violated
+///
+/// Evidence whose actual value is at or above its threshold is a ceiling
+/// (e.g. utilization); one below it is a floor (e.g. FPS or clocks). Each
+/// tick's most extreme reading of the evidence metric is compared against the
+/// threshold, and the onset is the first tick of `ONSET_MIN_SAMPLES`
+/// consecutive violations. The earliest onset across evidence items wins.
+pub fn bottleneck_onset(bottleneck: &Bottleneck, metrics: &[MetricSample]) -> Option<DateTime<Utc>> {
+    bottleneck
+        .evidence
+        .iter()
+        .filter_map(|evidence| {
+            evidence_ticks(evidence, metrics)
+                .windows(ONSET_MIN_SAMPLES)
+                .find(|window| window.iter().all(|&(_, violated)| violated))
+                .map(|window| window[0].0)
+        })
+        .min()
+}
+
+/// Share of the window a bottleneck was active, from 0 to 1
+///
+/// Classifies each tick of the primary (first) evidence metric against its
+/// threshold as `bottleneck_onset` does. Only runs of at least
+/// `ONSET_MIN_SAMPLES` consecutive violating ticks count as active, so
+/// isolated spikes do not add to the share. Returns 0 when the evidence
+/// metric has no readings, e.g. for values derived from several metrics.
+pub fn bottleneck_active_fraction(bottleneck: &Bottleneck, metrics: &[MetricSample]) -> f64 {
+    let Some(evidence) = bottleneck.evidence.first() else {
+        return 0.0;
+    };
+    let ticks = evidence_ticks(evidence, metrics);
+    if ticks.is_empty() {
+        return 0.0;
+    }
+    
+    let mut active = 0;
+    let mut streak = 0;
+    for &(_, violated) in &ticks {
+        if violated {
+            streak += 1;
+        } else {
+            if streak >= ONSET_MIN_SAMPLES {
+                active += streak;
+            }
+            streak = 0;
         }
-        
-        if let Some(gpu_bottleneck) = detect_gpu_bottleneck(&recent_metrics, None) {
-            bottlenecks.push(gpu_bottleneck);
+    }
+    if streak >= ONSET_MIN_SAMPLES {
+        active += streak;
+    }
+    active as f64 / ticks.len() as f64
+}
+
+/// Each tick's most extreme reading of an evidence metric, and whether it
+/// violates the evidence threshold
+///
+/// Evidence whose actual value is at or above its threshold is a ceiling;
+/// one below it is a floor. Evidence naming a source component only counts
+/// that component's readings. The hotspot-to-edge gap is recomputed per tick
+/// from both sensors; other evidence whose actual value lies outside the
+/// range of its readings (sums, estimates) has no per-tick value and yields
+/// no ticks.
+fn evidence_ticks(evidence: &EvidenceItem, metrics: &[MetricSample]) -> Vec<(DateTime<Utc>, bool)> {
+    let ceiling = evidence.actual_value >= evidence.threshold;
+    let mut ticks: Vec<(DateTime<Utc>, f64)> = Vec::new();
+    let mut samples: Vec<(DateTime<Utc>, f64)> = match evidence.source_component.as_deref() {
+        Some(GPU_HOTSPOT_GAP_SOURCE) => hotspot_gaps(metrics),
+        source => {
+            let readings: Vec<(DateTime<Utc>, f64)> = metrics
+                .iter()
+                .filter(|m| m.metric_type == evidence.metric_type && m.value.is_finite())
+                .filter(|m| source.map_or(true, |source| m.source_component == source))
+                .map(|m| (m.timestamp, m.value))
+                .collect();
+            let lowest = readings.iter().map(|&(_, value)| value).fold(f64::INFINITY, f64::min);
+            let highest = readings.iter().map(|&(_, value)| value).fold(f64::NEG_INFINITY, f64::max);
+            if !(lowest..=highest).contains(&evidence.actual_value) {
+                return Vec::new();
+            }
+            readings
         }
-        
-        if let Some(ram_bottleneck) = detect_ram_bottleneck(&recent_metrics, None) {
-            bottlenecks.push(ram_bottleneck);
+    };
+    samples.sort_by_key(|&(timestamp, _)| timestamp);
+    for (sample_timestamp, sample_value) in samples {
+        match ticks.last_mut() {
+            Some((timestamp, value)) if *timestamp == sample_timestamp => {
+                *value = if ceiling { value.max(sample_value) } else { value.min(sample_value) };
+            }
+            _ => ticks.push((sample_timestamp, sample_value)),
         }
     }
     
-    BottleneckAnalysisResult {
-        bottlenecks,
-        timestamp: now,
+    ticks
+        .into_iter()
+        .map(|(timestamp, value)| {
+            let violated = if ceiling {
+                value >= evidence.threshold
+            } else {
+                value <= evidence.threshold
+            };
+            (timestamp, violated)
+        })
+        .collect()
+}
+
+/// Hotspot minus edge temperature at each tick where both were read on the same GPU
+///
+/// Each hotspot sample is paired with the edge sample of its own source. Runs
+/// recorded before hotspot samples named their GPU use `GPU_HOTSPOT_SOURCE`;
+/// those are paired only when a single GPU reported an edge temperature.
+fn hotspot_gaps(metrics: &[MetricSample]) -> Vec<(DateTime<Utc>, f64)> {
+    let edge: HashMap<(&str, DateTime<Utc>), f64> = metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::GpuTemperature)
+        .map(|m| ((m.source_component.as_str(), m.timestamp), m.value))
+        .collect();
+    let edge_sources: HashSet<&str> = edge.keys().map(|(source, _)| *source).collect();
+    let sole_edge_source = if edge_sources.len() == 1 { edge_sources.into_iter().next() } else { None };
+    metrics
+        .iter()
+        .filter(|m| m.metric_type == MetricType::GpuHotspotTemperature)
+        .filter_map(|m| {
+            let source = if m.source_component == GPU_HOTSPOT_SOURCE {
+                sole_edge_source?
+            } else {
+                m.source_component.as_str()
+            };
+            edge.get(&(source, m.timestamp)).map(|e| (m.timestamp, m.value - e))
+        })
+        .filter(|(_, gap)| gap.is_finite())
+        .collect()
+}
+
+/// Unbroken stretch of ticks exceeding a limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SustainedViolation {
+    /// First violating tick
+    pub start: DateTime<Utc>,
+    /// Last violating tick
+    pub end: DateTime<Utc>,
+    /// Mean value over the stretch
+    pub mean: f64,
+    /// Highest value over the stretch
+    pub peak: f64,
+}
+
+impl SustainedViolation {
+    /// Time from the first to the last violating tick
+    pub fn duration_seconds(&self) -> f64 {
+        (self.end - self.start).num_milliseconds() as f64 / 1000.0
     }
 }
 
+/// Longest unbroken stretch of `ticks` for which `violated` holds, if it
+/// lasts at least `min_seconds`
+///
+/// `ticks` must be in time order. A stretch lasts from its first to its last
+/// violating tick, so a lone reading lasts zero seconds; non-finite readings
+/// break a stretch. Of equally long stretches the earliest is returned.
+pub fn sustained_violation(
+    ticks: &[(DateTime<Utc>, f64)],
+    min_seconds: f64,
+    violated: impl Fn(f64) -> bool,
+) -> Option<SustainedViolation> {
+    let longest = ticks
+        .split(|&(_, value)| !(value.is_finite() && violated(value)))
+        .filter(|stretch| !stretch.is_empty())
+        .map(|stretch| {
+            let values = stretch.iter().map(|&(_, value)| value);
+            SustainedViolation {
+                start: stretch[0].0,
+                end: stretch[stretch.len() - 1].0,
+                mean: values.clone().sum::<f64>() / stretch.len() as f64,
+                peak: values.fold(f64::MIN, f64::max),
+            }
+        })
+        .fold(None, |longest: Option<SustainedViolation>, stretch| match longest {
+            Some(longest) if longest.duration_seconds() >= stretch.duration_seconds() => Some(longest),
+            _ => Some(stretch),
+        })?;
+    (longest.duration_seconds() >= min_seconds).then_some(longest)
+}
+
+/// Minimum duration of a sustained violation configured by a profile
+pub fn sustained_min_seconds(profile: Option<&WorkloadProfile>) -> f64 {
+    profile
+        .and_then(|p| p.threshold_overrides.as_ref())
+        .and_then(|t| t.sustained_seconds)
+        .unwrap_or(SUSTAINED_MIN_SECONDS)
+}
+
+/// Each tick's highest finite reading of a metric, in time order
+pub(crate) fn peak_ticks(metrics: &[MetricSample], metric_type: MetricType) -> Vec<(DateTime<Utc>, f64)> {
+    let mut ticks: BTreeMap<DateTime<Utc>, f64> = BTreeMap::new();
+    for sample in metrics.iter().filter(|m| m.metric_type == metric_type && m.value.is_finite()) {
+        let value = ticks.entry(sample.timestamp).or_insert(sample.value);
+        *value = value.max(sample.value);
+    }
+    ticks.into_iter().collect()
+}
+
+/// Combined throughput of several metrics at each tick, in time order
+///
+/// Sources report at slightly different instants, so each tick sums the
+/// latest reading of every metric and source seen so far.
+pub(crate) fn summed_ticks(metrics: &[MetricSample], metric_types: &[MetricType]) -> Vec<(DateTime<Utc>, f64)> {
+    let mut samples: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| metric_types.contains(&m.metric_type) && m.value.is_finite())
+        .collect();
+    samples.sort_by_key(|m| m.timestamp);
+    
+    let mut latest: HashMap<(&MetricType, &str), f64> = HashMap::new();
+    let mut ticks: Vec<(DateTime<Utc>, f64)> = Vec::new();
+    for sample in samples {
+        latest.insert((&sample.metric_type, sample.source_component.as_str()), sample.value);
+        let total = latest.values().sum();
+        match ticks.last_mut() {
+            Some((timestamp, value)) if *timestamp == sample.timestamp => *value = total,
+            _ => ticks.push((sample.timestamp, total)),
+        }
+    }
+    ticks
+}
+
 /// Detect gaming-specific bottlenecks
 fn detect_gaming_bottlenecks(
     metrics: &[MetricSample],
@@ -133,18 +520,20 @@ fn detect_gaming_bottlenecks(
         .and_then(|t| t.vram_high)
         .unwrap_or(VRAM_HIGH_THRESHOLD);
     
+    let statistic = threshold_statistic(profile);
+    
     // Check for GPU-bound (most common in gaming)
-    if let Some(gpu_bottleneck) = detect_gpu_bottleneck(metrics, Some(gpu_threshold)) {
+    if let Some(gpu_bottleneck) = detect_gpu_bottleneck(metrics, Some(gpu_threshold), statistic) {
         bottlenecks.push(gpu_bottleneck);
     }
     
     // Check for CPU-bound (less common but possible)
-    if let Some(cpu_bottleneck) = detect_cpu_bottleneck(metrics, Some(cpu_threshold)) {
+    if let Some(cpu_bottleneck) = detect_cpu_bottleneck(metrics, Some(cpu_threshold), statistic) {
         bottlenecks.push(cpu_bottleneck);
     }
     
     // Check for VRAM-bound
-    if let Some(vram_bottleneck) = detect_vram_bottleneck(metrics, Some(vram_threshold)) {
+    if let Some(vram_bottleneck) = detect_vram_bottleneck(metrics, Some(vram_threshold), statistic) {
         bottlenecks.push(vram_bottleneck);
     }
     
@@ -178,18 +567,20 @@ fn detect_rendering_bottlenecks(
         .and_then(|t| t.vram_high)
         .unwrap_or(90.0);
     
+    let statistic = threshold_statistic(profile);
+    
     // CPU-bound render (CPU pegged, GPU idle)
-    if let Some(cpu_bottleneck) = detect_cpu_bottleneck(metrics, Some(cpu_threshold)) {
+    if let Some(cpu_bottleneck) = detect_cpu_bottleneck(metrics, Some(cpu_threshold), statistic) {
         bottlenecks.push(cpu_bottleneck);
     }
     
     // GPU-bound render
-    if let Some(gpu_bottleneck) = detect_gpu_bottleneck(metrics, Some(gpu_threshold)) {
+    if let Some(gpu_bottleneck) = detect_gpu_bottleneck(metrics, Some(gpu_threshold), statistic) {
         bottlenecks.push(gpu_bottleneck);
     }
     
     // VRAM-limited
-    if let Some(vram_bottleneck) = detect_vram_bottleneck(metrics, Some(vram_threshold)) {
+    if let Some(vram_bottleneck) = detect_vram_bottleneck(metrics, Some(vram_threshold), statistic) {
         bottlenecks.push(vram_bottleneck);
     }
     
@@ -218,14 +609,21 @@ fn detect_ai_ml_bottlenecks(
         .and_then(|t| t.vram_high)
         .unwrap_or(95.0);
     
+    let statistic = threshold_statistic(profile);
+    
+    // Input pipeline bound: a starved GPU explained by storage reads and
+    // preprocessing load, reported instead of the generic GPU-starved finding
+    let input_pipeline = detect_input_pipeline_bottleneck(metrics);
+    
     // GPU-starved: GPU usage fluctuating low, CPU/disk pegged
     let gpu_metrics: Vec<&MetricSample> = metrics
         .iter()
         .filter(|m| m.metric_type == MetricType::GpuUtilization)
         .collect();
     
-    if !gpu_metrics.is_empty() {
-        let avg_gpu = gpu_metrics.iter().map(|m| m.value).sum::<f64>() / gpu_metrics.len() as f64;
+    if let Some(pipeline_bottleneck) = input_pipeline {
+        bottlenecks.push(pipeline_bottleneck);
+    } else if let Some(avg_gpu) = mean(gpu_metrics.iter().map(|m| m.value)) {
         let min_gpu = gpu_metrics.iter().map(|m| m.value).fold(f64::INFINITY, f64::min);
         let max_gpu = gpu_metrics.iter().map(|m| m.value).fold(f64::NEG_INFINITY, f64::max);
         let variance = max_gpu - min_gpu;
@@ -242,18 +640,21 @@ fn detect_ai_ml_bottlenecks(
                     actual_value: avg_gpu,
                     time_range_start: gpu_metrics.first().unwrap().timestamp,
                     time_range_end: gpu_metrics.last().unwrap().timestamp,
+                    source_component: None,
                 }],
                 summary: format!("GPU-starved: Average GPU utilization is {:.1}% with high variance ({:.1}%), indicating GPU is waiting for CPU/disk", avg_gpu, variance),
                 details: format!(
                     "GPU utilization averaged {:.1}% with variance of {:.1}%, suggesting the GPU is frequently idle while waiting for data from CPU or disk. This is common in AI/ML workloads when data preprocessing or I/O is the bottleneck.",
                     avg_gpu, variance
                 ),
+                onset: None,
+                active_fraction: 0.0,
             });
         }
     }
     
     // VRAM-limited
-    if let Some(vram_bottleneck) = detect_vram_bottleneck(metrics, Some(vram_threshold)) {
+    if let Some(vram_bottleneck) = detect_vram_bottleneck(metrics, Some(vram_threshold), statistic) {
         bottlenecks.push(vram_bottleneck);
     }
     
@@ -278,13 +679,16 @@ fn detect_productivity_bottlenecks(
         .unwrap_or(RAM_HIGH_THRESHOLD);
     
     // Memory-bound
-    if let Some(ram_bottleneck) = detect_ram_bottleneck(metrics, Some(ram_threshold)) {
+    if let Some(ram_bottleneck) = detect_ram_bottleneck(metrics, Some(ram_threshold), threshold_statistic(profile)) {
         bottlenecks.push(ram_bottleneck);
     }
     
-    // Storage-bound
-    if let Some(storage_bottleneck) = detect_storage_bottleneck(metrics) {
-        bottlenecks.push(storage_bottleneck);
+    // Storage-bound, unless a background download explains the storage load;
+    // `BackgroundDownloadRule` reports that instead
+    if detect_background_download(metrics).is_none() {
+        if let Some(storage_bottleneck) = detect_storage_bottleneck(metrics, sustained_min_seconds(Some(profile))) {
+            bottlenecks.push(storage_bottleneck);
+        }
     }
     
     if bottlenecks.is_empty() {
@@ -294,10 +698,121 @@ fn detect_productivity_bottlenecks(
     }
 }
 
+/// Checks made by the workload detectors, for rule traces
+///
+/// Mirrors the thresholds of `detect_gaming_bottlenecks`,
+/// `detect_rendering_bottlenecks`, and `detect_productivity_bottlenecks`.
+/// AI workloads have no fixed checks and return an empty list.
+pub(crate) fn workload_checks(metrics: &[MetricSample], profile: Option<&WorkloadProfile>) -> Vec<TraceCheck> {
+    let Some(profile) = profile else {
+        return [
+            cpu_bound_checks(metrics, CPU_HIGH_THRESHOLD, ThresholdStatistic::Mean),
+            gpu_bound_checks(metrics, GPU_HIGH_THRESHOLD, ThresholdStatistic::Mean),
+            vec![ram_check(metrics, RAM_HIGH_THRESHOLD, ThresholdStatistic::Mean)],
+        ]
+        .concat();
+    };
+    let overrides = profile.threshold_overrides.as_ref();
+    let statistic = threshold_statistic(profile);
+    let (cpu_default, gpu_default) = match profile.workload_type {
+        WorkloadType::Gaming => (CPU_HIGH_THRESHOLD, GPU_HIGH_THRESHOLD),
+        WorkloadType::Rendering => (95.0, 95.0),
+        WorkloadType::AI => return Vec::new(),
+        WorkloadType::Productivity | WorkloadType::General => {
+            let ram_threshold = overrides.and_then(|t| t.ram_high).unwrap_or(RAM_HIGH_THRESHOLD);
+            return vec![ram_check(metrics, ram_threshold, statistic)];
+        }
+    };
+    let cpu_threshold = overrides.and_then(|t| t.cpu_high).unwrap_or(cpu_default);
+    let gpu_threshold = overrides.and_then(|t| t.gpu_high).unwrap_or(gpu_default);
+    [
+        gpu_bound_checks(metrics, gpu_threshold, statistic),
+        cpu_bound_checks(metrics, cpu_threshold, statistic),
+    ]
+    .concat()
+}
+
+/// Checks of `detect_cpu_bottleneck`
+fn cpu_bound_checks(metrics: &[MetricSample], threshold: f64, statistic: ThresholdStatistic) -> Vec<TraceCheck> {
+    vec![
+        TraceCheck::new(metrics, MetricType::CpuUtilization, statistic, threshold, TraceComparison::Above),
+        TraceCheck::new(
+            metrics,
+            MetricType::GpuUtilization,
+            ThresholdStatistic::Mean,
+            CPU_BOUND_MAX_GPU,
+            TraceComparison::Below,
+        ),
+    ]
+}
+
+/// Checks of `detect_gpu_bottleneck`
+fn gpu_bound_checks(metrics: &[MetricSample], threshold: f64, statistic: ThresholdStatistic) -> Vec<TraceCheck> {
+    vec![
+        TraceCheck::new(metrics, MetricType::GpuUtilization, statistic, threshold, TraceComparison::Above),
+        TraceCheck::new(
+            metrics,
+            MetricType::CpuUtilization,
+            ThresholdStatistic::Mean,
+            GPU_BOUND_MAX_CPU,
+            TraceComparison::Below,
+        ),
+    ]
+}
+
+/// Usage check of `detect_ram_bottleneck`; swap use also fires the rule
+fn ram_check(metrics: &[MetricSample], threshold: f64, statistic: ThresholdStatistic) -> TraceCheck {
+    TraceCheck::new(metrics, MetricType::MemoryUsage, statistic, threshold, TraceComparison::Above)
+}
+
+/// Threshold statistic configured by a profile, defaulting to the mean
+fn threshold_statistic(profile: &WorkloadProfile) -> ThresholdStatistic {
+    profile
+        .threshold_overrides
+        .as_ref()
+        .map(|t| t.statistic)
+        .unwrap_or_default()
+}
+
+/// Summarize windowed sample values with the given statistic
+///
+/// Returns 0.0 when `values` holds no finite value.
+pub fn window_statistic(values: &[f64], statistic: ThresholdStatistic) -> f64 {
+    let average = || mean(values.iter().copied()).unwrap_or(0.0);
+    match statistic {
+        ThresholdStatistic::Mean => average(),
+        ThresholdStatistic::Percentile(p) => percentile(values, p).unwrap_or_else(average),
+        ThresholdStatistic::TrimmedMean(fraction) => trimmed_mean(values, fraction).unwrap_or(0.0),
+    }
+}
+
+/// Label for a statistic in bottleneck summaries (e.g. "Average", "P95")
+fn statistic_label(statistic: ThresholdStatistic) -> String {
+    match statistic {
+        ThresholdStatistic::Mean => "Average".to_string(),
+        ThresholdStatistic::Percentile(p) => format!("P{:.0}", p),
+        ThresholdStatistic::TrimmedMean(_) => "Trimmed average".to_string(),
+    }
+}
+
+/// Verb phrase for a statistic in bottleneck details (e.g. "averaged")
+fn statistic_phrase(statistic: ThresholdStatistic) -> String {
+    match statistic {
+        ThresholdStatistic::Mean => "averaged".to_string(),
+        ThresholdStatistic::Percentile(p) => format!("had a p{:.0} of", p),
+        ThresholdStatistic::TrimmedMean(_) => "had a trimmed average of".to_string(),
+    }
+}
+
 /// Detect CPU-bound bottleneck
+///
+/// CPU-bound needs the GPU to have headroom. When `gpu_utilization_warnings`
+/// flags the GPU's utilization as unreliable, that headroom cannot be told
+/// apart from readings that are missing, so the rule does not fire.
 fn detect_cpu_bottleneck(
     metrics: &[MetricSample],
     threshold_override: Option<f64>,
+    statistic: ThresholdStatistic,
 ) -> Option<Bottleneck> {
     let cpu_metrics: Vec<&MetricSample> = metrics
         .iter()
@@ -309,7 +824,8 @@ fn detect_cpu_bottleneck(
     }
     
     // Check if CPU utilization is consistently high
-    let avg_cpu = cpu_metrics.iter().map(|m| m.value).sum::<f64>() / cpu_metrics.len() as f64;
+    let cpu_values: Vec<f64> = cpu_metrics.iter().map(|m| m.value).collect();
+    let cpu_value = window_statistic(&cpu_values, statistic);
     let max_cpu = cpu_metrics.iter().map(|m| m.value).fold(0.0, f64::max);
     
     // Check GPU utilization to confirm CPU-bound (GPU should be lower)
@@ -318,35 +834,44 @@ fn detect_cpu_bottleneck(
         .filter(|m| m.metric_type == MetricType::GpuUtilization)
         .collect();
     
-    let avg_gpu = if !gpu_metrics.is_empty() {
-        gpu_metrics.iter().map(|m| m.value).sum::<f64>() / gpu_metrics.len() as f64
-    } else {
-        0.0
-    };
+    let avg_gpu = mean(gpu_metrics.iter().map(|m| m.value));
     
     let threshold = threshold_override.unwrap_or(CPU_HIGH_THRESHOLD);
     
     // CPU-bound: High CPU (above threshold), GPU not saturated (<70%)
-    if avg_cpu > threshold && avg_gpu < 70.0 {
-        let severity = calculate_severity(avg_cpu, CPU_HIGH_THRESHOLD);
+    if cpu_value > threshold && avg_gpu.unwrap_or(0.0) < CPU_BOUND_MAX_GPU {
+        if !gpu_utilization_warnings(metrics).is_empty() {
+            return None;
+        }
+        
+        let severity = calculate_severity(cpu_value, threshold);
         
         let evidence = vec![EvidenceItem {
             metric_type: MetricType::CpuUtilization,
             threshold,
-            actual_value: avg_cpu,
+            actual_value: cpu_value,
             time_range_start: cpu_metrics.first().unwrap().timestamp,
             time_range_end: cpu_metrics.last().unwrap().timestamp,
+            source_component: None,
         }];
         
         return Some(Bottleneck {
             bottleneck_type: BottleneckType::Cpu,
             severity,
             evidence,
-            summary: format!("CPU-bound: Average CPU utilization is {:.1}% (threshold: {:.1}%)", avg_cpu, threshold),
-            details: format!(
-                "CPU utilization averaged {:.1}% over the analysis period, indicating CPU is the limiting factor. GPU utilization is {:.1}%, suggesting GPU has headroom.",
-                avg_cpu, avg_gpu
-            ),
+            summary: format!("CPU-bound: {} CPU utilization is {:.1}% (threshold: {:.1}%)", statistic_label(statistic), cpu_value, threshold),
+            details: match avg_gpu {
+                Some(avg_gpu) => format!(
+                    "CPU utilization {} {:.1}% over the analysis period, indicating CPU is the limiting factor. GPU utilization is {:.1}%, suggesting GPU has headroom.",
+                    statistic_phrase(statistic), cpu_value, avg_gpu
+                ),
+                None => format!(
+                    "CPU utilization {} {:.1}% over the analysis period, indicating CPU is the limiting factor. No GPU utilization was recorded.",
+                    statistic_phrase(statistic), cpu_value
+                ),
+            },
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
@@ -357,6 +882,7 @@ fn detect_cpu_bottleneck(
 fn detect_gpu_bottleneck(
     metrics: &[MetricSample],
     threshold_override: Option<f64>,
+    statistic: ThresholdStatistic,
 ) -> Option<Bottleneck> {
     let gpu_metrics: Vec<&MetricSample> = metrics
         .iter()
@@ -367,7 +893,8 @@ fn detect_gpu_bottleneck(
         return None;
     }
     
-    let avg_gpu = gpu_metrics.iter().map(|m| m.value).sum::<f64>() / gpu_metrics.len() as f64;
+    let gpu_values: Vec<f64> = gpu_metrics.iter().map(|m| m.value).collect();
+    let gpu_value = window_statistic(&gpu_values, statistic);
     let _max_gpu = gpu_metrics.iter().map(|m| m.value).fold(0.0, f64::max);
     
     // Check CPU utilization to confirm GPU-bound
@@ -376,35 +903,34 @@ fn detect_gpu_bottleneck(
         .filter(|m| m.metric_type == MetricType::CpuUtilization)
         .collect();
     
-    let avg_cpu = if !cpu_metrics.is_empty() {
-        cpu_metrics.iter().map(|m| m.value).sum::<f64>() / cpu_metrics.len() as f64
-    } else {
-        0.0
-    };
+    let avg_cpu = mean(cpu_metrics.iter().map(|m| m.value)).unwrap_or(0.0);
     
     let threshold = threshold_override.unwrap_or(GPU_HIGH_THRESHOLD);
     
     // GPU-bound: High GPU (above threshold), CPU not saturated (<80%)
-    if avg_gpu > threshold && avg_cpu < 80.0 {
-        let severity = calculate_severity(avg_gpu, threshold);
+    if gpu_value > threshold && avg_cpu < GPU_BOUND_MAX_CPU {
+        let severity = calculate_severity(gpu_value, threshold);
         
         let evidence = vec![EvidenceItem {
             metric_type: MetricType::GpuUtilization,
             threshold,
-            actual_value: avg_gpu,
+            actual_value: gpu_value,
             time_range_start: gpu_metrics.first().unwrap().timestamp,
             time_range_end: gpu_metrics.last().unwrap().timestamp,
+            source_component: None,
         }];
         
         return Some(Bottleneck {
             bottleneck_type: BottleneckType::Gpu,
             severity,
             evidence,
-            summary: format!("GPU-bound: Average GPU utilization is {:.1}% (threshold: {:.1}%)", avg_gpu, threshold),
+            summary: format!("GPU-bound: {} GPU utilization is {:.1}% (threshold: {:.1}%)", statistic_label(statistic), gpu_value, threshold),
             details: format!(
-                "GPU utilization averaged {:.1}% over the analysis period, indicating GPU is the limiting factor. CPU utilization is {:.1}%, suggesting CPU has headroom.",
-                avg_gpu, avg_cpu
+                "GPU utilization {} {:.1}% over the analysis period, indicating GPU is the limiting factor. CPU utilization is {:.1}%, suggesting CPU has headroom.",
+                statistic_phrase(statistic), gpu_value, avg_cpu
             ),
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
@@ -412,10 +938,18 @@ fn detect_gpu_bottleneck(
 }
 
 /// Detect VRAM-bound bottleneck
+///
+/// Uses VRAM percentage samples when the collector could compute them, and
+/// falls back to absolute MB samples otherwise.
 fn detect_vram_bottleneck(
     metrics: &[MetricSample],
     threshold_override: Option<f64>,
+    statistic: ThresholdStatistic,
 ) -> Option<Bottleneck> {
+    if metrics.iter().any(|m| m.metric_type == MetricType::GpuVramUsagePercent) {
+        return detect_vram_percent_bottleneck(metrics, threshold_override.unwrap_or(VRAM_HIGH_THRESHOLD), statistic);
+    }
+    
     let vram_metrics: Vec<&MetricSample> = metrics
         .iter()
         .filter(|m| m.metric_type == MetricType::GpuVramUsage)
@@ -427,7 +961,7 @@ fn detect_vram_bottleneck(
     
     // Get VRAM total from metrics (would need to be passed or stored)
     // For now, check if VRAM usage is consistently high
-    let avg_vram = vram_metrics.iter().map(|m| m.value).sum::<f64>() / vram_metrics.len() as f64;
+    let avg_vram = mean(vram_metrics.iter().map(|m| m.value))?;
     let max_vram = vram_metrics.iter().map(|m| m.value).fold(0.0, f64::max);
     
     // Need VRAM total to calculate percentage - placeholder for now
@@ -448,68 +982,110 @@ fn detect_vram_bottleneck(
                 actual_value: avg_vram,
                 time_range_start: vram_metrics.first().unwrap().timestamp,
                 time_range_end: vram_metrics.last().unwrap().timestamp,
+                source_component: None,
             }],
             summary: format!("VRAM-bound: Average VRAM usage is {:.1} MB", avg_vram),
             details: format!(
                 "VRAM usage averaged {:.1} MB over the analysis period. High VRAM usage can cause stuttering and performance degradation in games and rendering workloads.",
                 avg_vram
             ),
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
     None
 }
 
-/// Detect storage-bound bottleneck
-fn detect_storage_bottleneck(metrics: &[MetricSample]) -> Option<Bottleneck> {
-    let _read_metrics: Vec<&MetricSample> = metrics
-        .iter()
-        .filter(|m| m.metric_type == MetricType::StorageReadThroughput)
-        .collect();
-    
-    let _write_metrics: Vec<&MetricSample> = metrics
-        .iter()
-        .filter(|m| m.metric_type == MetricType::StorageWriteThroughput)
-        .collect();
+/// Detect a VRAM-bound bottleneck from per-device VRAM percentages
+///
+/// Each GPU is judged against its own total; the fullest device is reported.
+fn detect_vram_percent_bottleneck(
+    metrics: &[MetricSample],
+    threshold: f64,
+    statistic: ThresholdStatistic,
+) -> Option<Bottleneck> {
+    let mut by_device: HashMap<&str, Vec<&MetricSample>> = HashMap::new();
+    for sample in metrics.iter().filter(|m| m.metric_type == MetricType::GpuVramUsagePercent) {
+        by_device.entry(sample.source_component.as_str()).or_default().push(sample);
+    }
     
-    let queue_metrics: Vec<&MetricSample> = metrics
-        .iter()
-        .filter(|m| m.metric_type == MetricType::StorageQueueDepth)
-        .collect();
+    let (device, samples, vram_percent) = by_device
+        .into_iter()
+        .filter_map(|(device, samples)| {
+            let values: Vec<f64> = samples.iter().map(|m| m.value).collect();
+            let value = window_statistic(&values, statistic);
+            Some((device, samples, value))
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))?;
     
-    // Check for high queue depth (indicates I/O saturation)
-    if let Some(max_queue) = queue_metrics.iter().map(|m| m.value).max_by(|a, b| a.partial_cmp(b).unwrap()) {
-        if max_queue > 10.0 {
-            // High queue depth indicates storage bottleneck
-            let avg_queue = queue_metrics.iter().map(|m| m.value).sum::<f64>() / queue_metrics.len() as f64;
-            let severity = (avg_queue.min(100.0) as u8).max(50);
-            
-            return Some(Bottleneck {
-                bottleneck_type: BottleneckType::Storage,
-                severity,
-                evidence: vec![EvidenceItem {
-                    metric_type: MetricType::StorageQueueDepth,
-                    threshold: 10.0,
-                    actual_value: avg_queue,
-                    time_range_start: queue_metrics.first().unwrap().timestamp,
-                    time_range_end: queue_metrics.last().unwrap().timestamp,
-                }],
-                summary: format!("Storage-bound: Average I/O queue depth is {:.1} (threshold: 10.0)", avg_queue),
-                details: format!(
-                    "Storage I/O queue depth averaged {:.1} over the analysis period, indicating storage is saturated. This can cause application slowdowns and stuttering.",
-                    avg_queue
-                ),
-            });
-        }
+    if vram_percent <= threshold {
+        return None;
     }
     
-    None
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::Vram,
+        severity: calculate_severity(vram_percent, threshold),
+        evidence: vec![EvidenceItem {
+            metric_type: MetricType::GpuVramUsagePercent,
+            threshold,
+            actual_value: vram_percent,
+            time_range_start: samples.iter().map(|m| m.timestamp).min()?,
+            time_range_end: samples.iter().map(|m| m.timestamp).max()?,
+            source_component: None,
+        }],
+        summary: format!(
+            "VRAM-bound: {} VRAM usage on {} is {:.1}% (threshold: {:.1}%)",
+            statistic_label(statistic), device, vram_percent, threshold
+        ),
+        details: format!(
+            "VRAM usage on {} {} {:.1}% over the analysis period. Running out of VRAM forces textures and buffers into system memory, causing stuttering and performance drops in games and rendering workloads.",
+            device, statistic_phrase(statistic), vram_percent
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
+}
+
+/// Detect storage-bound bottleneck
+///
+/// Fires when the I/O queue depth stays above 10 for at least `min_seconds`
+/// without a break, so a brief burst of I/O is not reported.
+fn detect_storage_bottleneck(metrics: &[MetricSample], min_seconds: f64) -> Option<Bottleneck> {
+    let ticks = peak_ticks(metrics, MetricType::StorageQueueDepth);
+    let violation = sustained_violation(&ticks, min_seconds, |queue| queue > STORAGE_QUEUE_DEPTH_THRESHOLD)?;
+    let severity = scaled_severity(violation.mean, STORAGE_QUEUE_DEPTH_THRESHOLD, STORAGE_QUEUE_FULL_SEVERITY_DEPTH);
+    
+    Some(Bottleneck {
+        bottleneck_type: BottleneckType::Storage,
+        severity,
+        evidence: vec![EvidenceItem {
+            metric_type: MetricType::StorageQueueDepth,
+            threshold: STORAGE_QUEUE_DEPTH_THRESHOLD,
+            actual_value: violation.mean,
+            time_range_start: violation.start,
+            time_range_end: violation.end,
+            source_component: None,
+        }],
+        summary: format!(
+            "Storage-bound: Average I/O queue depth is {:.1} (threshold: {:.1})",
+            violation.mean, STORAGE_QUEUE_DEPTH_THRESHOLD
+        ),
+        details: format!(
+            "Storage I/O queue depth averaged {:.1} over {:.0} seconds without a break, indicating storage is saturated. This can cause application slowdowns and stuttering.",
+            violation.mean,
+            violation.duration_seconds()
+        ),
+        onset: None,
+        active_fraction: 0.0,
+    })
 }
 
 /// Detect RAM-bound bottleneck
 fn detect_ram_bottleneck(
     metrics: &[MetricSample],
     threshold_override: Option<f64>,
+    statistic: ThresholdStatistic,
 ) -> Option<Bottleneck> {
     let memory_metrics: Vec<&MetricSample> = metrics
         .iter()
@@ -520,7 +1096,8 @@ fn detect_ram_bottleneck(
         return None;
     }
     
-    let avg_memory = memory_metrics.iter().map(|m| m.value).sum::<f64>() / memory_metrics.len() as f64;
+    let memory_values: Vec<f64> = memory_metrics.iter().map(|m| m.value).collect();
+    let memory_value = window_statistic(&memory_values, statistic);
     let _max_memory = memory_metrics.iter().map(|m| m.value).fold(0.0, f64::max);
     
     // Check for swap usage
@@ -534,41 +1111,43 @@ fn detect_ram_bottleneck(
     let threshold = threshold_override.unwrap_or(RAM_HIGH_THRESHOLD);
     
     // RAM-bound: High RAM usage (above threshold) or significant swap usage
-    if avg_memory > threshold || has_swap_usage {
+    if memory_value > threshold || has_swap_usage {
         let severity = if has_swap_usage {
             // Swap usage indicates more severe memory pressure
-            (avg_memory.min(100.0) as u8).max(80)
+            (memory_value.min(100.0) as u8).max(80)
         } else {
-            calculate_severity(avg_memory, threshold)
+            calculate_severity(memory_value, threshold)
         };
         
         let mut evidence = vec![EvidenceItem {
             metric_type: MetricType::MemoryUsage,
             threshold,
-            actual_value: avg_memory,
+            actual_value: memory_value,
             time_range_start: memory_metrics.first().unwrap().timestamp,
             time_range_end: memory_metrics.last().unwrap().timestamp,
+            source_component: None,
         }];
         
         if has_swap_usage {
             evidence.push(EvidenceItem {
                 metric_type: MetricType::MemorySwapUsage,
                 threshold: 0.0,
-                actual_value: swap_metrics.iter().map(|m| m.value).sum::<f64>() / swap_metrics.len() as f64,
+                actual_value: mean(swap_metrics.iter().map(|m| m.value)).unwrap_or(0.0),
                 time_range_start: swap_metrics.first().unwrap().timestamp,
                 time_range_end: swap_metrics.last().unwrap().timestamp,
+                source_component: None,
             });
         }
         
         let details = if has_swap_usage {
             format!(
-                "Memory usage averaged {:.1}% with swap usage detected, indicating severe memory pressure. System is likely paging to disk, causing performance degradation.",
-                avg_memory
+                "Memory usage {} {:.1}% with swap usage detected, indicating severe memory pressure. System is likely paging to disk, causing performance degradation.",
+                statistic_phrase(statistic), memory_value
             )
         } else {
             format!(
-                "Memory usage averaged {:.1}% over the analysis period, indicating memory is approaching capacity.",
-                avg_memory
+                "Memory usage {} {:.1}% over the analysis period, indicating memory is approaching capacity.",
+                statistic_phrase(statistic), memory_value
             )
         };
         
@@ -576,8 +1155,10 @@ fn detect_ram_bottleneck(
             bottleneck_type: BottleneckType::Ram,
             severity,
             evidence,
-            summary: format!("RAM-bound: Average memory usage is {:.1}% (threshold: {:.1}%)", avg_memory, threshold),
+            summary: format!("RAM-bound: {} memory usage is {:.1}% (threshold: {:.1}%)", statistic_label(statistic), memory_value, threshold),
             details,
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
@@ -597,7 +1178,7 @@ fn detect_thermal_throttling(metrics: &[MetricSample]) -> Option<Bottleneck> {
     }
     
     let max_temp = temp_metrics.iter().map(|m| m.value).fold(f64::NEG_INFINITY, f64::max);
-    let avg_temp = temp_metrics.iter().map(|m| m.value).sum::<f64>() / temp_metrics.len() as f64;
+    let avg_temp = mean(temp_metrics.iter().map(|m| m.value))?;
     
     // Thermal throttling thresholds (typical CPU/GPU limits)
     const CPU_THROTTLE_TEMP: f64 = 90.0; // Celsius
@@ -622,29 +1203,44 @@ fn detect_thermal_throttling(metrics: &[MetricSample]) -> Option<Bottleneck> {
                 actual_value: max_temp,
                 time_range_start: temp_metrics.first().unwrap().timestamp,
                 time_range_end: temp_metrics.last().unwrap().timestamp,
+                source_component: None,
             }],
             summary: format!("Thermal throttling: Maximum temperature reached {:.1}°C (threshold: {:.1}°C)", max_temp, CPU_THROTTLE_TEMP),
             details: format!(
                 "Temperature reached {:.1}°C (average: {:.1}°C), indicating thermal throttling. The CPU/GPU is reducing clock speeds to prevent overheating, causing performance degradation. Consider improving cooling.",
                 max_temp, avg_temp
             ),
+            onset: None,
+            active_fraction: 0.0,
         });
     }
     
     None
 }
 
-/// Calculate severity score (0-100) based on how much the value exceeds the threshold
-fn calculate_severity(actual_value: f64, threshold: f64) -> u8 {
-    if actual_value <= threshold {
+/// Calculate severity score (0-100) based on how much a percentage exceeds the threshold
+///
+/// Scales linearly from 0 at the threshold to 100 at 100%, so a reading just
+/// over the threshold is a marginal finding rather than a severe one. Values
+/// at or below the threshold, and NaN, score 0; a threshold of 100% or more
+/// scores any value above it as 100.
+pub fn calculate_severity(actual_value: f64, threshold: f64) -> u8 {
+    scaled_severity(actual_value, threshold, 100.0)
+}
+
+/// Severity on the `calculate_severity` scale for values that are not percentages
+///
+/// Scales linearly from 0 at the threshold to 100 at `full_at`. Rules with
+/// their own units use this so their findings rank alongside the CPU, GPU
+/// and memory detectors rather than starting from a fixed floor.
+pub fn scaled_severity(actual_value: f64, threshold: f64, full_at: f64) -> u8 {
+    if actual_value.is_nan() || actual_value <= threshold {
         return 0;
     }
+    if full_at <= threshold {
+        return 100;
+    }
     
-    // Severity increases as value exceeds threshold
-    // At threshold: 0, at 100%: 100, linear scaling
-    let excess = actual_value - threshold;
-    let max_excess = 100.0 - threshold;
-    let severity_ratio = (excess / max_excess).min(1.0);
-    
-    (threshold + (severity_ratio * (100.0 - threshold))) as u8
+    let severity_ratio = ((actual_value - threshold) / (full_at - threshold)).clamp(0.0, 1.0);
+    (severity_ratio * 100.0).round() as u8
 }
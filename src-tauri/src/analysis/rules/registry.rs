@@ -0,0 +1,432 @@
+//! Pluggable bottleneck rules
+//!
+//! This module defines the `BottleneckRule` trait that the analysis engine
+//! iterates, and wraps the built-in detectors as rules so that custom
+//! heuristics can be composed with them.
+
+use super::{
+    classify_core_types, classify_gpu, detect_ai_ml_bottlenecks, detect_background_download, detect_combined_pressure, detect_cooling_headroom,
+    detect_cpu_bottleneck,
+    detect_cpu_clock_throttle, detect_frame_cap,
+    detect_dpc_latency, detect_enhanced_thermal_bottleneck, detect_gaming_bottlenecks, detect_gpu_bottleneck,
+    detect_gpu_thermal,
+    detect_heterogeneous_core_loading, detect_hybrid_graphics_misrouting, detect_memory_bus_saturation_with_min_duration,
+    detect_multi_gpu_bottleneck, detect_pcie_link_downgrade, detect_pcie_saturation_with_min_duration,
+    detect_productivity_bottlenecks, detect_ram_bottleneck, detect_rendering_bottlenecks, detect_thermal_coupling,
+    detect_thermal_throttling, detect_vram_bandwidth_bottleneck, sustained_min_seconds, workload_checks,
+};
+use super::advanced::{CoreType, GpuKind};
+use super::trace::RuleTrace;
+use crate::core::domain::{
+    Bottleneck, CPUInfo, DisplayInfo, GPUInfo, MetricSample, ThresholdStatistic, WorkloadProfile, WorkloadType,
+};
+use chrono::{DateTime, Utc};
+
+/// Context passed to every rule during an analysis pass
+#[derive(Debug, Clone, Copy)]
+pub struct AnalysisContext<'a> {
+    /// Workload profile, if one was selected
+    pub profile: Option<&'a WorkloadProfile>,
+    /// Start of the analysis window
+    pub window_start: DateTime<Utc>,
+    /// End of the analysis window
+    pub window_end: DateTime<Utc>,
+}
+
+/// A bottleneck detection heuristic
+///
+/// Rules receive the metrics already filtered to the analysis window.
+pub trait BottleneckRule: Send + Sync {
+    /// Short identifier for the rule
+    fn name(&self) -> &str;
+
+    /// Detect a single bottleneck
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck>;
+
+    /// Detect all bottlenecks reported by this rule
+    ///
+    /// Rules that can report more than one bottleneck override this.
+    fn detect_all(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Vec<Bottleneck> {
+        self.detect(metrics, ctx).into_iter().collect()
+    }
+
+    /// Explain the rule's decision for a rule trace
+    ///
+    /// The default records the evidence of the bottlenecks the rule reports.
+    /// Rules that compare known thresholds override this to record their
+    /// inputs whether or not they fire.
+    fn trace(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> RuleTrace {
+        RuleTrace::from_detection(self.name(), metrics, &self.detect_all(metrics, ctx))
+    }
+}
+
+/// Thermal rule: throttling confirmed by clocks or FPS, then enhanced thermal
+/// and basic throttling
+///
+/// When temperatures crossed the throttle point and clock or FPS samples show
+/// whether performance dropped, that verdict replaces the temperature-only checks.
+pub struct ThermalRule;
+
+impl BottleneckRule for ThermalRule {
+    fn name(&self) -> &str {
+        "thermal"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_thermal_coupling(metrics)
+            .or_else(|| detect_enhanced_thermal_bottleneck(metrics))
+            .or_else(|| detect_thermal_throttling(metrics))
+    }
+}
+
+/// Cooling headroom rule: sustained heat with fans below or at maximum
+///
+/// Kept apart from `ThermalRule` so a fan-curve diagnosis adds to a
+/// throttling verdict instead of replacing it.
+pub struct CoolingHeadroomRule;
+
+impl BottleneckRule for CoolingHeadroomRule {
+    fn name(&self) -> &str {
+        "cooling_headroom"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_cooling_headroom(metrics)
+    }
+}
+
+/// GPU thermal rule: edge and hotspot limits, and cooler contact quality
+pub struct GpuThermalRule;
+
+impl BottleneckRule for GpuThermalRule {
+    fn name(&self) -> &str {
+        "gpu_thermal"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck> {
+        self.detect_all(metrics, ctx).into_iter().next()
+    }
+
+    fn detect_all(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Vec<Bottleneck> {
+        detect_gpu_thermal(metrics)
+    }
+}
+
+/// PCIe bandwidth saturation rule
+///
+/// Uses the profile's `sustained_seconds` as the minimum saturated duration.
+pub struct PcieSaturationRule;
+
+impl BottleneckRule for PcieSaturationRule {
+    fn name(&self) -> &str {
+        "pcie_saturation"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_pcie_saturation_with_min_duration(metrics, sustained_min_seconds(ctx.profile))
+    }
+}
+
+/// PCIe link downgrade rule: GPU links narrower or slower than supported under load
+pub struct PcieLinkDowngradeRule;
+
+impl BottleneckRule for PcieLinkDowngradeRule {
+    fn name(&self) -> &str {
+        "pcie_link_downgrade"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck> {
+        self.detect_all(metrics, ctx).into_iter().next()
+    }
+
+    fn detect_all(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Vec<Bottleneck> {
+        detect_pcie_link_downgrade(metrics)
+    }
+}
+
+/// VRAM bandwidth rule: busy GPU memory controller with VRAM capacity to spare
+pub struct VramBandwidthRule;
+
+impl BottleneckRule for VramBandwidthRule {
+    fn name(&self) -> &str {
+        "vram_bandwidth"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck> {
+        self.detect_all(metrics, ctx).into_iter().next()
+    }
+
+    fn detect_all(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Vec<Bottleneck> {
+        detect_vram_bandwidth_bottleneck(metrics)
+    }
+}
+
+/// Memory bus saturation rule
+///
+/// Uses the profile's `sustained_seconds` as the minimum saturated duration.
+pub struct MemoryBusSaturationRule;
+
+impl BottleneckRule for MemoryBusSaturationRule {
+    fn name(&self) -> &str {
+        "memory_bus_saturation"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_memory_bus_saturation_with_min_duration(metrics, sustained_min_seconds(ctx.profile))
+    }
+}
+
+/// Multi-GPU imbalance rule
+pub struct MultiGpuRule;
+
+impl BottleneckRule for MultiGpuRule {
+    fn name(&self) -> &str {
+        "multi_gpu"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_multi_gpu_bottleneck(metrics)
+    }
+}
+
+/// DPC/interrupt latency rule
+pub struct DpcLatencyRule;
+
+impl BottleneckRule for DpcLatencyRule {
+    fn name(&self) -> &str {
+        "dpc_latency"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_dpc_latency(metrics)
+    }
+}
+
+/// Hybrid CPU rule: workloads confined to efficiency cores
+///
+/// Needs the core layout from hardware detection, so it is not part of
+/// `default_rules` and is registered when the CPU is known to be hybrid.
+pub struct HeterogeneousCoreRule {
+    core_types: Vec<CoreType>,
+}
+
+impl HeterogeneousCoreRule {
+    /// Create the rule for the given core layout
+    pub fn new(core_types: Vec<CoreType>) -> Self {
+        Self { core_types }
+    }
+    
+    /// Create the rule from detected CPU information
+    ///
+    /// Returns `None` unless the CPU has both performance and efficiency cores.
+    pub fn from_cpu(cpu: &CPUInfo) -> Option<Self> {
+        let core_types = classify_core_types(&cpu.core_max_frequencies_mhz);
+        let hybrid = core_types.contains(&CoreType::Efficiency)
+            && core_types.contains(&CoreType::Performance);
+        hybrid.then(|| Self::new(core_types))
+    }
+}
+
+impl BottleneckRule for HeterogeneousCoreRule {
+    fn name(&self) -> &str {
+        "heterogeneous_cores"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_heterogeneous_core_loading(metrics, &self.core_types)
+    }
+}
+
+/// CPU clock rule: clocks held below rated speed under load
+///
+/// Needs the rated clocks from hardware detection, so it is not part of
+/// `default_rules` and is registered when the CPU clocks are known.
+pub struct CpuClockThrottleRule {
+    cpu: CPUInfo,
+}
+
+impl CpuClockThrottleRule {
+    /// Create the rule from detected CPU information
+    ///
+    /// Returns `None` when neither the base nor the boost clock is known.
+    pub fn from_cpu(cpu: &CPUInfo) -> Option<Self> {
+        (cpu.base_clock_mhz.is_some() || cpu.boost_clock_mhz.is_some())
+            .then(|| Self { cpu: cpu.clone() })
+    }
+}
+
+impl BottleneckRule for CpuClockThrottleRule {
+    fn name(&self) -> &str {
+        "cpu_clock_throttle"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_cpu_clock_throttle(metrics, &self.cpu)
+    }
+}
+
+/// Frame cap rule: frame rate pinned below the display refresh rate
+///
+/// Needs the refresh rate from hardware detection, so it is not part of
+/// `default_rules` and is registered when a display reports one.
+pub struct FrameCapRule {
+    refresh_rate_hz: f64,
+}
+
+impl FrameCapRule {
+    /// Create the rule from detected displays, using the fastest refresh rate
+    ///
+    /// Returns `None` when no display reports a refresh rate.
+    pub fn from_displays(displays: &[DisplayInfo]) -> Option<Self> {
+        displays
+            .iter()
+            .filter_map(|d| d.refresh_rate_hz)
+            .filter(|hz| *hz > 0)
+            .max()
+            .map(|hz| Self { refresh_rate_hz: hz as f64 })
+    }
+}
+
+impl BottleneckRule for FrameCapRule {
+    fn name(&self) -> &str {
+        "frame_cap"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_frame_cap(metrics, self.refresh_rate_hz)
+    }
+}
+
+/// Hybrid graphics rule: a game rendered on the integrated GPU while the discrete GPU idles
+///
+/// Needs the GPU list from hardware detection, so it is not part of
+/// `default_rules` and is registered on machines with both kinds of GPU.
+/// Only gaming workloads are checked.
+pub struct HybridGraphicsRule {
+    integrated: Vec<String>,
+    discrete: Vec<String>,
+}
+
+impl HybridGraphicsRule {
+    /// Create the rule from detected GPUs
+    ///
+    /// Returns `None` unless both an integrated and a discrete GPU were detected.
+    pub fn from_gpus(gpus: &[GPUInfo]) -> Option<Self> {
+        let models_of = |kind: GpuKind| -> Vec<String> {
+            gpus.iter()
+                .filter(|gpu| classify_gpu(gpu) == Some(kind))
+                .map(|gpu| gpu.model.clone())
+                .collect()
+        };
+        let integrated = models_of(GpuKind::Integrated);
+        let discrete = models_of(GpuKind::Discrete);
+        (!integrated.is_empty() && !discrete.is_empty()).then_some(Self { integrated, discrete })
+    }
+}
+
+impl BottleneckRule for HybridGraphicsRule {
+    fn name(&self) -> &str {
+        "hybrid_graphics"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck> {
+        if !ctx.profile.is_some_and(|p| p.workload_type == WorkloadType::Gaming) {
+            return None;
+        }
+        detect_hybrid_graphics_misrouting(metrics, &self.integrated, &self.discrete)
+    }
+}
+
+/// Background download rule: updaters and launchers writing to disk mid-run
+pub struct BackgroundDownloadRule;
+
+impl BottleneckRule for BackgroundDownloadRule {
+    fn name(&self) -> &str {
+        "background_download"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_background_download(metrics)
+    }
+}
+
+/// Workload rule: profile-specific heuristics, or generic CPU/GPU/RAM checks
+pub struct WorkloadRule;
+
+impl BottleneckRule for WorkloadRule {
+    fn name(&self) -> &str {
+        "workload"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck> {
+        self.detect_all(metrics, ctx).into_iter().next()
+    }
+
+    fn detect_all(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Vec<Bottleneck> {
+        match ctx.profile {
+            Some(profile) => {
+                let detected = match profile.workload_type {
+                    WorkloadType::Gaming => detect_gaming_bottlenecks(metrics, profile),
+                    WorkloadType::Rendering => detect_rendering_bottlenecks(metrics, profile),
+                    WorkloadType::AI => detect_ai_ml_bottlenecks(metrics, profile),
+                    WorkloadType::Productivity | WorkloadType::General => {
+                        detect_productivity_bottlenecks(metrics, profile)
+                    }
+                };
+                detected.unwrap_or_default()
+            }
+            None => [
+                detect_cpu_bottleneck(metrics, None, ThresholdStatistic::Mean),
+                detect_gpu_bottleneck(metrics, None, ThresholdStatistic::Mean),
+                detect_ram_bottleneck(metrics, None, ThresholdStatistic::Mean),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        }
+    }
+
+    fn trace(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> RuleTrace {
+        let bottlenecks = self.detect_all(metrics, ctx);
+        let checks = workload_checks(metrics, ctx.profile);
+        if checks.is_empty() {
+            RuleTrace::from_detection(self.name(), metrics, &bottlenecks)
+        } else {
+            RuleTrace::new(self.name(), metrics, checks, &bottlenecks)
+        }
+    }
+}
+
+/// Combined pressure rule: several subsystems near their thresholds at once
+///
+/// Uses the profile's thresholds and `combined_pressure` fraction when a
+/// profile is selected.
+pub struct CombinedPressureRule;
+
+impl BottleneckRule for CombinedPressureRule {
+    fn name(&self) -> &str {
+        "combined_pressure"
+    }
+
+    fn detect(&self, metrics: &[MetricSample], ctx: &AnalysisContext) -> Option<Bottleneck> {
+        detect_combined_pressure(metrics, ctx.profile.and_then(|p| p.threshold_overrides.as_ref()))
+    }
+}
+
+/// Built-in rules in evaluation order
+pub fn default_rules() -> Vec<Box<dyn BottleneckRule>> {
+    vec![
+        Box::new(ThermalRule),
+        Box::new(CoolingHeadroomRule),
+        Box::new(GpuThermalRule),
+        Box::new(PcieSaturationRule),
+        Box::new(PcieLinkDowngradeRule),
+        Box::new(VramBandwidthRule),
+        Box::new(MemoryBusSaturationRule),
+        Box::new(MultiGpuRule),
+        Box::new(DpcLatencyRule),
+        Box::new(BackgroundDownloadRule),
+        Box::new(WorkloadRule),
+        Box::new(CombinedPressureRule),
+    ]
+}
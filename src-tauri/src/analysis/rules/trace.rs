@@ -0,0 +1,179 @@
+//! Rule explanation traces
+//!
+//! When a verdict is disputed, maintainers need to see why each rule fired
+//! or stayed quiet. Tracing runs the same rules as an analysis pass and
+//! records the values each rule compared against its thresholds alongside
+//! its decision. It is only run on request and never by `analyze_bottlenecks`.
+
+use super::registry::{AnalysisContext, BottleneckRule};
+use super::{statistic_label, window_statistic};
+use crate::core::domain::{Bottleneck, BottleneckType, MetricSample, MetricType, ThresholdStatistic, WorkloadProfile};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Direction a value must cross its threshold for a check to pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceComparison {
+    Above,
+    Below,
+}
+
+/// One threshold comparison made by a rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceCheck {
+    pub metric_type: MetricType,
+    /// Statistic the value was computed with (e.g. "Average", "P95")
+    pub statistic: String,
+    /// `None` when the window held no sample of the metric
+    pub value: Option<f64>,
+    pub threshold: f64,
+    pub comparison: TraceComparison,
+    /// Samples of the metric in the analysis window
+    pub sample_count: usize,
+    pub passed: bool,
+}
+
+impl TraceCheck {
+    /// Compare the statistic of a metric's samples against a threshold
+    ///
+    /// Comparisons are strict, as in the detectors.
+    pub fn new(
+        metrics: &[MetricSample],
+        metric_type: MetricType,
+        statistic: ThresholdStatistic,
+        threshold: f64,
+        comparison: TraceComparison,
+    ) -> Self {
+        let values: Vec<f64> = metrics
+            .iter()
+            .filter(|m| m.metric_type == metric_type)
+            .map(|m| m.value)
+            .collect();
+        let value = (!values.is_empty()).then(|| window_statistic(&values, statistic));
+        let passed = value.is_some_and(|v| match comparison {
+            TraceComparison::Above => v > threshold,
+            TraceComparison::Below => v < threshold,
+        });
+        Self {
+            metric_type,
+            statistic: statistic_label(statistic),
+            value,
+            threshold,
+            comparison,
+            sample_count: values.len(),
+            passed,
+        }
+    }
+
+    /// Check recorded as evidence by a bottleneck the rule reported
+    fn from_evidence(metrics: &[MetricSample], metric_type: MetricType, value: f64, threshold: f64) -> Self {
+        let sample_count = metrics.iter().filter(|m| m.metric_type == metric_type).count();
+        Self {
+            metric_type,
+            statistic: "Reported".to_string(),
+            value: Some(value),
+            threshold,
+            comparison: if value < threshold { TraceComparison::Below } else { TraceComparison::Above },
+            sample_count,
+            passed: true,
+        }
+    }
+}
+
+/// Inputs and decision of one rule for one analysis window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleTrace {
+    /// Name of the rule, as returned by `BottleneckRule::name`
+    pub rule: String,
+    /// Samples in the analysis window
+    pub sample_count: usize,
+    pub checks: Vec<TraceCheck>,
+    pub fired: bool,
+    /// Bottlenecks the rule reported, with their severities
+    pub bottlenecks: Vec<(BottleneckType, u8)>,
+    /// One line explaining the decision
+    pub decision: String,
+}
+
+impl RuleTrace {
+    /// Trace of a rule that made the given checks and reported `bottlenecks`
+    pub fn new(rule: &str, metrics: &[MetricSample], checks: Vec<TraceCheck>, bottlenecks: &[Bottleneck]) -> Self {
+        let decision = if !bottlenecks.is_empty() {
+            let reported: Vec<String> = bottlenecks
+                .iter()
+                .map(|b| format!("{:?} (severity {})", b.bottleneck_type, b.severity))
+                .collect();
+            format!("Fired: {}", reported.join(", "))
+        } else if checks.is_empty() {
+            "Not fired: no bottleneck detected".to_string()
+        } else {
+            let failed: Vec<String> = checks.iter().filter(|c| !c.passed).map(describe_failure).collect();
+            if failed.is_empty() {
+                "Not fired: all checks passed but no bottleneck was reported".to_string()
+            } else {
+                format!("Not fired: {}", failed.join("; "))
+            }
+        };
+        Self {
+            rule: rule.to_string(),
+            sample_count: metrics.len(),
+            checks,
+            fired: !bottlenecks.is_empty(),
+            bottlenecks: bottlenecks.iter().map(|b| (b.bottleneck_type.clone(), b.severity)).collect(),
+            decision,
+        }
+    }
+
+    /// Trace built from the evidence of the bottlenecks a rule reported
+    ///
+    /// Used for rules that do not expose their checks; a rule that did not
+    /// fire has no checks.
+    pub fn from_detection(rule: &str, metrics: &[MetricSample], bottlenecks: &[Bottleneck]) -> Self {
+        let checks = bottlenecks
+            .iter()
+            .flat_map(|b| &b.evidence)
+            .map(|e| TraceCheck::from_evidence(metrics, e.metric_type.clone(), e.actual_value, e.threshold))
+            .collect();
+        Self::new(rule, metrics, checks, bottlenecks)
+    }
+}
+
+fn describe_failure(check: &TraceCheck) -> String {
+    let direction = match check.comparison {
+        TraceComparison::Above => "above",
+        TraceComparison::Below => "below",
+    };
+    match check.value {
+        Some(value) => format!(
+            "{} {:?} {:.1} is not {} {:.1}",
+            check.statistic, check.metric_type, value, direction, check.threshold
+        ),
+        None => format!("no {:?} samples", check.metric_type),
+    }
+}
+
+/// Trace every rule over the window ending at `window_end`
+///
+/// Filters the window the same way `analyze_bottlenecks_at` does, so the
+/// decisions match those of the analysis.
+pub fn trace_bottlenecks_at(
+    metrics: &[MetricSample],
+    time_window_seconds: i64,
+    profile: Option<&WorkloadProfile>,
+    rules: &[Box<dyn BottleneckRule>],
+    window_end: DateTime<Utc>,
+) -> Vec<RuleTrace> {
+    let window_start = window_end - Duration::seconds(time_window_seconds);
+    let recent_metrics: Vec<MetricSample> = metrics
+        .iter()
+        .filter(|m| m.timestamp >= window_start && m.timestamp <= window_end)
+        .cloned()
+        .collect();
+    let ctx = AnalysisContext {
+        profile,
+        window_start,
+        window_end,
+    };
+    rules.iter().map(|rule| rule.trace(&recent_metrics, &ctx)).collect()
+}
@@ -0,0 +1,125 @@
+//! Long-term trends across sessions
+//!
+//! This module tracks how a metric develops over weeks and months of saved
+//! sessions. For temperatures, the trend tells cooling that is degrading
+//! (dust, drying thermal paste) apart from cooling that was never sufficient.
+
+use crate::analysis::rules::advanced::TEMP_WARNING_THRESHOLD;
+use crate::core::domain::{MetricType, Session};
+use crate::metrics::utils::percentile;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Sessions needed before a trend is reported
+pub const TREND_MIN_SESSIONS: usize = 3;
+
+/// Days the sessions must span before a thermal diagnosis is made
+pub const TREND_MIN_SPAN_DAYS: f64 = 14.0;
+
+/// Temperature rise per 30 days treated as cooling degradation
+pub const THERMAL_AGING_SLOPE_PER_MONTH: f64 = 2.0; // °C
+
+/// Value of a metric in one session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub session_id: Uuid,
+    pub start_time: DateTime<Utc>,
+    /// 95th percentile across the session's runs, i.e. the value under load
+    pub value: f64,
+}
+
+/// Development of a metric across sessions, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionTrend {
+    pub metric_type: MetricType,
+    pub points: Vec<TrendPoint>,
+    /// Least-squares change per 30 days
+    pub slope_per_month: f64,
+    /// Days between the first and last session
+    pub span_days: f64,
+}
+
+/// Likely cause of high temperatures, judged from their history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoolingDiagnosis {
+    /// Temperatures have been climbing over time
+    Degradation,
+    /// Temperatures were already high in the first session and stayed there
+    UndersizedCooling,
+}
+
+/// Analyze how a metric develops across sessions
+///
+/// Sessions without samples of `metric_type` are skipped. Returns `None` with fewer than
+/// `TREND_MIN_SESSIONS` usable sessions.
+pub fn analyze_session_trend(sessions: &[Session], metric_type: MetricType) -> Option<SessionTrend> {
+    let mut points: Vec<TrendPoint> = sessions
+        .iter()
+        .filter_map(|session| {
+            let values: Vec<f64> = session
+                .runs
+                .iter()
+                .flat_map(|run| run.metrics_streams.values().flatten())
+                .filter(|sample| sample.metric_type == metric_type)
+                .map(|sample| sample.value)
+                .collect();
+            Some(TrendPoint {
+                session_id: session.id,
+                start_time: session.start_time,
+                value: percentile(&values, 95.0)?,
+            })
+        })
+        .collect();
+    if points.len() < TREND_MIN_SESSIONS {
+        return None;
+    }
+    points.sort_by_key(|point| point.start_time);
+
+    let first_time = points[0].start_time;
+    let days: Vec<f64> = points
+        .iter()
+        .map(|point| (point.start_time - first_time).num_seconds() as f64 / 86_400.0)
+        .collect();
+    let span_days = days.last().copied().unwrap_or(0.0);
+
+    let n = points.len() as f64;
+    let mean_day = days.iter().sum::<f64>() / n;
+    let mean_value = points.iter().map(|point| point.value).sum::<f64>() / n;
+    let covariance: f64 = days
+        .iter()
+        .zip(&points)
+        .map(|(day, point)| (day - mean_day) * (point.value - mean_value))
+        .sum();
+    let variance: f64 = days.iter().map(|day| (day - mean_day).powi(2)).sum();
+    let slope_per_day = if variance > 0.0 { covariance / variance } else { 0.0 };
+
+    Some(SessionTrend {
+        metric_type,
+        points,
+        slope_per_month: slope_per_day * 30.0,
+        span_days,
+    })
+}
+
+impl SessionTrend {
+    /// Judge whether high temperatures come from aging or undersized cooling
+    ///
+    /// Needs at least `TREND_MIN_SPAN_DAYS` of history. A rise of
+    /// `THERMAL_AGING_SLOPE_PER_MONTH` or more points to degradation; a flat
+    /// history that started above `TEMP_WARNING_THRESHOLD` points to cooling
+    /// that was undersized from the start.
+    pub fn cooling_diagnosis(&self) -> Option<CoolingDiagnosis> {
+        if self.span_days < TREND_MIN_SPAN_DAYS {
+            return None;
+        }
+        if self.slope_per_month >= THERMAL_AGING_SLOPE_PER_MONTH {
+            Some(CoolingDiagnosis::Degradation)
+        } else if self.points.first()?.value >= TEMP_WARNING_THRESHOLD {
+            Some(CoolingDiagnosis::UndersizedCooling)
+        } else {
+            None
+        }
+    }
+}
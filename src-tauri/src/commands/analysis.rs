@@ -2,8 +2,24 @@
 //!
 //! This module exposes bottleneck analysis functionality to the frontend.
 
-use crate::analysis::{AnalysisEngine, insights};
-use crate::core::domain::{BottleneckAnalysisResult, MetricSample, WorkloadProfile};
+use crate::analysis::{AnalysisEngine, insights, trend};
+use crate::analysis::boost::{self, BoostSustainability};
+use crate::analysis::fleet::SessionIssueSummary;
+use crate::analysis::leak::{self, MemoryLeakTrend, VramCreepTrend};
+use crate::analysis::rules::RuleTrace;
+use crate::analysis::rules::advanced::{classify_gpu, GpuKind};
+use crate::analysis::trend::SessionTrend;
+use crate::commands::sessions::parse_session_id;
+use crate::commands::settings::{current_acknowledgments, current_idle_baseline, find_analysis_profile};
+use crate::core::domain::{
+    BottleneckAnalysisResult, ForegroundProcess, MetricSample, MetricType, Session, WorkloadProfile,
+};
+use crate::core::error::{CommandError, SessionError};
+use crate::core::sessions;
+use crate::metrics::{derive_missing_metrics, get_metrics_collector};
+use crate::persistence::init_session_storage;
+use tauri::AppHandle;
+use uuid::Uuid;
 
 /// Analyze metrics and detect bottlenecks
 #[tauri::command]
@@ -11,20 +27,293 @@ pub async fn analyze_bottlenecks(
     metrics: Vec<MetricSample>,
     time_window_seconds: Option<i64>,
     profile: Option<WorkloadProfile>,
-) -> Result<BottleneckAnalysisResult, String> {
-    let engine = AnalysisEngine::new();
-    Ok(engine.analyze(
+    subtract_baseline: Option<bool>,
+) -> Result<BottleneckAnalysisResult, CommandError> {
+    // Hybrid core, clock, and frame cap analysis need the detected hardware
+    let engine = match crate::hardware::get_hardware_config().await {
+        Ok(hardware) => AnalysisEngine::for_hardware(&hardware),
+        Err(_) => AnalysisEngine::new(),
+    };
+    let idle_baseline = if subtract_baseline.unwrap_or(false) {
+        Some(current_idle_baseline()?.ok_or_else(|| CommandError::Analysis {
+            code: "ANALYSIS_NO_BASELINE".to_string(),
+            message: "No idle baseline has been captured".to_string(),
+        })?)
+    } else {
+        None
+    };
+    
+    Ok(engine.analyze_with_baseline(
         &metrics,
         time_window_seconds,
         profile.as_ref(),
+        idle_baseline.as_ref(),
     ))
 }
 
+/// Analyze a saved run
+///
+/// The analysis window ends at the run's newest sample instead of the current
+/// time; without `time_window_seconds` the whole run is analyzed.
+#[tauri::command]
+pub async fn analyze_run(
+    app: AppHandle,
+    run_id: String,
+    time_window_seconds: Option<i64>,
+) -> Result<BottleneckAnalysisResult, CommandError> {
+    let (session, run_index) = find_run(&app, &run_id).await?;
+    // Use the hardware of the machine the run was recorded on
+    let engine = AnalysisEngine::for_hardware(&session.hardware_config_snapshot);
+    
+    sessions::analyze_run(&engine, &session.runs[run_index], &session.profile, time_window_seconds)
+        .ok_or_else(|| no_samples(&run_id))
+}
+
+/// Analyze a saved run with a named analysis profile
+///
+/// The profile's window, statistic, and thresholds are used instead of those
+/// of the session's workload profile, so the same analysis can be repeated.
+#[tauri::command]
+pub async fn analyze_with_profile(
+    app: AppHandle,
+    run_id: String,
+    analysis_profile_id: String,
+) -> Result<BottleneckAnalysisResult, CommandError> {
+    let analysis_profile = find_analysis_profile(&analysis_profile_id)?;
+    let (session, run_index) = find_run(&app, &run_id).await?;
+    let engine = AnalysisEngine::for_hardware(&session.hardware_config_snapshot);
+    
+    sessions::analyze_run_with_profile(
+        &engine,
+        &session.runs[run_index],
+        &session.profile,
+        &analysis_profile,
+    )
+    .ok_or_else(|| no_samples(&run_id))
+}
+
+/// Derive FPS from frame times, or frame times from FPS, for a saved run
+///
+/// Stores the derived stream when the run recorded only one of the two and
+/// returns the number of samples added.
+#[tauri::command]
+pub async fn derive_run_metrics(app: AppHandle, run_id: String) -> Result<usize, CommandError> {
+    let (mut session, run_index) = find_run(&app, &run_id).await?;
+    let added = derive_missing_metrics(&mut session.runs[run_index]);
+    if added > 0 {
+        let storage = init_session_storage(&app)
+            .map_err(CommandError::from)?;
+        storage.save_session(&session)
+            .await
+            .map_err(CommandError::from)?;
+    }
+    Ok(added)
+}
+
+/// Explain why each rule fired or did not fire for a saved run
+///
+/// Debugging aid for disputed verdicts: uses the same window and rules as
+/// `analyze_run` and returns each rule's inputs and decision.
+#[tauri::command]
+pub async fn trace_run_analysis(
+    app: AppHandle,
+    run_id: String,
+    time_window_seconds: Option<i64>,
+) -> Result<Vec<RuleTrace>, CommandError> {
+    let (session, run_index) = find_run(&app, &run_id).await?;
+    let engine = AnalysisEngine::for_hardware(&session.hardware_config_snapshot);
+    
+    sessions::trace_run(&engine, &session.runs[run_index], &session.profile, time_window_seconds)
+        .ok_or_else(|| no_samples(&run_id))
+}
+
+/// Measure how long a saved run sustained its CPU and GPU boost clocks
+///
+/// Components without both clock and temperature samples are left out.
+#[tauri::command]
+pub async fn analyze_boost_sustainability(
+    app: AppHandle,
+    run_id: String,
+) -> Result<Vec<BoostSustainability>, CommandError> {
+    let (session, run_index) = find_run(&app, &run_id).await?;
+    Ok(boost::analyze_boost_sustainability(&session.runs[run_index]))
+}
+
+/// Check a saved run for memory use that climbs steadily, a sign of a leak
+///
+/// Resolves to `None` when memory use is flat or the run is too short to judge.
+#[tauri::command]
+pub async fn detect_memory_leak_trend(
+    app: AppHandle,
+    run_id: String,
+) -> Result<Option<MemoryLeakTrend>, CommandError> {
+    let (session, run_index) = find_run(&app, &run_id).await?;
+    Ok(leak::detect_memory_leak_trend(&session.runs[run_index]))
+}
+
+/// Check a saved run for VRAM use that creeps toward the GPU's capacity
+///
+/// Each GPU's capacity is recovered from the run's VRAM percentage samples.
+/// Runs recorded without them fall back to the primary discrete GPU in the
+/// session's hardware snapshot. Resolves to `None` when VRAM use is flat,
+/// the run is too short to judge, or no capacity is known.
+#[tauri::command]
+pub async fn detect_vram_creep(
+    app: AppHandle,
+    run_id: String,
+) -> Result<Option<VramCreepTrend>, CommandError> {
+    let (session, run_index) = find_run(&app, &run_id).await?;
+    let gpus = &session.hardware_config_snapshot.gpus;
+    let vram_total_mb = gpus
+        .iter()
+        .filter(|gpu| classify_gpu(gpu) == Some(GpuKind::Discrete))
+        .chain(gpus.iter())
+        .find_map(|gpu| gpu.vram_total_mb)
+        .unwrap_or(0);
+    Ok(leak::detect_vram_creep(&session.runs[run_index], vram_total_mb))
+}
+
 /// Generate user-facing insights from analysis results
+///
+/// Temperature trends from `analyze_session_trend` tailor the thermal
+/// recommendations to aging or undersized cooling, and the detected drives
+/// tailor the storage recommendations. Acknowledged bottlenecks are
+/// dismissed rather than recommended on.
+///
+/// With `metrics`, the summary reports how much of the CPU and GPU load the
+/// foreground application caused. `foreground` defaults to the application
+/// that took focus during the current collection.
 #[tauri::command]
-pub fn generate_insights(
+pub async fn generate_insights(
     result: BottleneckAnalysisResult,
     profile: Option<WorkloadProfile>,
-) -> Result<insights::UserFacingInsights, String> {
-    Ok(insights::generate_insights(&result, profile.as_ref()))
+    trends: Option<Vec<SessionTrend>>,
+    metrics: Option<Vec<MetricSample>>,
+    foreground: Option<ForegroundProcess>,
+) -> Result<insights::UserFacingInsights, CommandError> {
+    let trends = trends.unwrap_or_default();
+    let hardware = crate::hardware::get_hardware_config().await.ok();
+    let generated = insights::generate_acknowledged_insights(
+        &result,
+        profile.as_ref(),
+        &trends,
+        hardware.as_ref(),
+        &current_acknowledgments(),
+    );
+    
+    let foreground = match foreground {
+        Some(process) => Some(process),
+        None => match get_metrics_collector() {
+            Some(collector) => collector.foreground_process().await,
+            None => None,
+        },
+    };
+    let attribution = metrics
+        .zip(foreground)
+        .and_then(|(metrics, process)| insights::attribute_foreground(&metrics, &process));
+    Ok(match attribution {
+        Some(attribution) => generated.with_foreground_attribution(attribution),
+        None => generated,
+    })
+}
+
+/// Regenerate the insights of a saved run from its stored analysis result
+///
+/// Runs that were never analyzed get insights without recommendations.
+#[tauri::command]
+pub async fn get_run_insights(
+    app: AppHandle,
+    session_id: String,
+    run_id: String,
+) -> Result<insights::UserFacingInsights, CommandError> {
+    let session_uuid = parse_session_id(&session_id)?;
+    let run_uuid = Uuid::parse_str(&run_id)
+        .map_err(|e| CommandError::validation("INVALID_RUN_ID", format!("Invalid run ID: {}", e)))?;
+    let storage = init_session_storage(&app).map_err(CommandError::from)?;
+    let session = storage.load_session(&session_uuid).await.map_err(CommandError::from)?;
+    let run = session
+        .runs
+        .iter()
+        .find(|r| r.id == run_uuid)
+        .ok_or_else(|| SessionError::RunNotFound(format!("Run {} not found", run_id)))?;
+    
+    Ok(sessions::run_insights(&session, run, &current_acknowledgments()))
+}
+
+/// Analyze how a metric develops across all saved sessions
+///
+/// Returns `None` when too few sessions recorded the metric.
+#[tauri::command]
+pub async fn analyze_session_trend(
+    app: AppHandle,
+    metric_type: MetricType,
+) -> Result<Option<SessionTrend>, CommandError> {
+    let storage = init_session_storage(&app)
+        .map_err(CommandError::from)?;
+    let session_ids = storage.list_sessions()
+        .await
+        .map_err(CommandError::from)?;
+    
+    let mut sessions = Vec::with_capacity(session_ids.len());
+    for session_id in session_ids {
+        match storage.load_session(&session_id).await {
+            Ok(session) => sessions.push(session),
+            Err(e) => log::warn!("Skipping session {} in session trend: {}", session_id, e),
+        }
+    }
+    
+    Ok(trend::analyze_session_trend(&sessions, metric_type))
+}
+
+/// List saved sessions whose runs have a bottleneck of at least `min_severity`
+///
+/// Each entry names the machine and its worst issue, most severe first.
+#[tauri::command]
+pub async fn analyze_all_sessions(
+    app: AppHandle,
+    min_severity: u8,
+) -> Result<Vec<SessionIssueSummary>, CommandError> {
+    if min_severity > 100 {
+        return Err(CommandError::validation(
+            "INVALID_SEVERITY",
+            "Minimum severity must be between 0 and 100",
+        ));
+    }
+    
+    let storage = init_session_storage(&app)
+        .map_err(CommandError::from)?;
+    storage.analyze_all_sessions(min_severity)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Find the saved session containing a run, returning the run's index
+async fn find_run(app: &AppHandle, run_id: &str) -> Result<(Session, usize), CommandError> {
+    let run_uuid = Uuid::parse_str(run_id)
+        .map_err(|e| CommandError::validation("INVALID_RUN_ID", format!("Invalid run ID: {}", e)))?;
+    
+    let storage = init_session_storage(app)
+        .map_err(CommandError::from)?;
+    let session_ids = storage.list_sessions()
+        .await
+        .map_err(CommandError::from)?;
+    
+    for session_id in session_ids {
+        let session = storage.load_session(&session_id)
+            .await
+            .map_err(CommandError::from)?;
+        if let Some(run_index) = session.runs.iter().position(|r| r.id == run_uuid) {
+            return Ok((session, run_index));
+        }
+    }
+    
+    Err(SessionError::RunNotFound(format!("Run {} not found", run_id)).into())
+}
+
+/// Error returned when a run has no recorded metrics
+fn no_samples(run_id: &str) -> CommandError {
+    CommandError::Analysis {
+        code: "ANALYSIS_NO_SAMPLES".to_string(),
+        message: format!("Run {} has no recorded metrics", run_id),
+    }
 }
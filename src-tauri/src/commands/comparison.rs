@@ -2,15 +2,103 @@
 //!
 //! This module exposes comparison functionality to the frontend.
 
-use crate::analysis::comparison::compare_runs;
-use crate::core::domain::Run;
+use crate::analysis::comparison::{
+    self, compare_imported_run, compare_runs_with_min_effect, HardwareDiff, PreviousRunComparison,
+    DEFAULT_MIN_EFFECT_PERCENT,
+};
+use crate::analysis::reference::{compare_to_reference, ReferenceComparison};
+use crate::commands::sessions::parse_session_id;
+use crate::commands::settings::current_min_comparison_effect_percent;
+use crate::core::domain::{HardwareConfig, Run, WorkloadType};
+use crate::core::error::CommandError;
+use crate::hardware;
+use crate::persistence::init_session_storage;
+use tauri::AppHandle;
 
 /// Compare two runs
+///
+/// With the session run 1 belongs to, the result lists how that session's
+/// hardware differs from this PC's, as for a session imported from another PC.
 #[tauri::command]
-pub fn compare_runs_command(
+pub async fn compare_runs_command(
+    app: AppHandle,
     run1: Run,
     run2: Run,
-) -> Result<crate::analysis::ComparisonResult, String> {
-    Ok(compare_runs(&run1, &run2))
+    run1_session_id: Option<String>,
+) -> Result<crate::analysis::ComparisonResult, CommandError> {
+    let min_effect_percent = current_min_comparison_effect_percent()
+        .unwrap_or(DEFAULT_MIN_EFFECT_PERCENT);
+    let session_id = match run1_session_id {
+        Some(session_id) => session_id,
+        None => return Ok(compare_runs_with_min_effect(&run1, &run2, min_effect_percent)),
+    };
+    
+    let uuid = parse_session_id(&session_id)?;
+    let storage = init_session_storage(&app).map_err(CommandError::from)?;
+    let session = storage.load_session(&uuid).await.map_err(CommandError::from)?;
+    match hardware::get_hardware_config().await {
+        Ok(local) => Ok(compare_imported_run(
+            &run1,
+            &session.hardware_config_snapshot,
+            &run2,
+            &local,
+            min_effect_percent,
+        )),
+        Err(e) => {
+            log::warn!("Hardware detection failed, comparing without hardware differences: {}", e);
+            Ok(compare_runs_with_min_effect(&run1, &run2, min_effect_percent))
+        }
+    }
 }
 
+/// Compare a run with typical values for its hardware class
+#[tauri::command]
+pub fn compare_run_to_reference(
+    run: Run,
+    hardware: HardwareConfig,
+    workload_type: WorkloadType,
+) -> Result<ReferenceComparison, CommandError> {
+    compare_to_reference(&run, &hardware, &workload_type).ok_or_else(|| CommandError::Analysis {
+        code: "ANALYSIS_NO_REFERENCE".to_string(),
+        message: "No reference data matches this hardware and run".to_string(),
+    })
+}
+
+/// Compare the latest run of a saved session with the run before it
+#[tauri::command]
+pub async fn compare_to_previous_run(
+    app: AppHandle,
+    session_id: String,
+) -> Result<PreviousRunComparison, CommandError> {
+    let uuid = parse_session_id(&session_id)?;
+    let storage = init_session_storage(&app).map_err(CommandError::from)?;
+    let session = storage.load_session(&uuid).await.map_err(CommandError::from)?;
+    
+    let min_effect_percent = current_min_comparison_effect_percent()
+        .unwrap_or(DEFAULT_MIN_EFFECT_PERCENT);
+    comparison::compare_to_previous_run(&session, min_effect_percent).ok_or_else(|| {
+        CommandError::validation(
+            "NOT_ENOUGH_RUNS",
+            format!(
+                "This session has {} run(s); record at least two runs to compare with the previous one",
+                session.runs.len()
+            ),
+        )
+    })
+}
+
+/// Compare the hardware of a saved, imported session with this PC's hardware
+///
+/// The resulting `HardwareDiff` captions comparisons against the session's runs.
+#[tauri::command]
+pub async fn compare_imported_hardware(
+    app: AppHandle,
+    session_id: String,
+) -> Result<HardwareDiff, CommandError> {
+    let uuid = parse_session_id(&session_id)?;
+    let storage = init_session_storage(&app).map_err(CommandError::from)?;
+    let session = storage.load_session(&uuid).await.map_err(CommandError::from)?;
+    let local = hardware::get_hardware_config().await.map_err(CommandError::from)?;
+    
+    Ok(comparison::diff_hardware(&session.hardware_config_snapshot, &local))
+}
@@ -3,20 +3,110 @@
 //! This module exposes hardware detection functionality to the frontend.
 
 use crate::core::domain::HardwareConfig;
+use crate::core::error::CommandError;
 use crate::hardware;
+use crate::hardware::change::{detect_hardware_change, HardwareChange};
+use crate::metrics::get_metrics_collector;
+use crate::persistence::export_import::{self, Snapshot};
+use crate::persistence::init_hardware_snapshot_storage;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// Event emitted when hardware changed since the previous launch
+pub const HARDWARE_CHANGED_EVENT: &str = "hardware-changed";
+
+/// Seconds of buffered metrics included in a snapshot by default
+const DEFAULT_SNAPSHOT_WINDOW_SECONDS: i64 = 10;
+
+// Changes detected on startup, kept until dismissed by the user
+static HARDWARE_CHANGES: Mutex<Vec<HardwareChange>> = Mutex::new(Vec::new());
 
 /// Get the current hardware configuration
 #[tauri::command]
-pub async fn get_hardware_config() -> Result<HardwareConfig, String> {
+pub async fn get_hardware_config() -> Result<HardwareConfig, CommandError> {
     hardware::get_hardware_config()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Refresh hardware configuration (force new detection)
 #[tauri::command]
-pub async fn refresh_hardware_config() -> Result<HardwareConfig, String> {
+pub async fn refresh_hardware_config() -> Result<HardwareConfig, CommandError> {
     hardware::refresh_hardware_config()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Compare the detected hardware against the configuration saved on the previous launch
+///
+/// Stores and emits any changes, then saves the current configuration as the new snapshot.
+pub async fn check_hardware_changes(app: AppHandle) -> Result<Vec<HardwareChange>, CommandError> {
+    let storage = init_hardware_snapshot_storage(&app)?;
+    let current = hardware::get_hardware_config().await?;
+    
+    let changes = match storage.load().await {
+        Ok(Some(previous)) => detect_hardware_change(&previous, &current),
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            log::warn!("Ignoring unreadable hardware snapshot: {}", e);
+            Vec::new()
+        }
+    };
+    storage.save(&current).await?;
+    
+    if !changes.is_empty() {
+        *HARDWARE_CHANGES.lock().unwrap() = changes.clone();
+        if let Err(e) = app.emit(HARDWARE_CHANGED_EVENT, changes.clone()) {
+            log::warn!("Failed to emit hardware change event: {}", e);
+        }
+    }
+    
+    Ok(changes)
+}
+
+/// Get hardware changes detected since the previous launch
+#[tauri::command]
+pub fn get_hardware_changes() -> Result<Vec<HardwareChange>, CommandError> {
+    Ok(HARDWARE_CHANGES.lock().unwrap().clone())
+}
+
+/// Dismiss the detected hardware changes
+#[tauri::command]
+pub fn dismiss_hardware_changes() -> Result<(), CommandError> {
+    HARDWARE_CHANGES.lock().unwrap().clear();
+    Ok(())
+}
+
+/// Export the current hardware and the latest buffered metrics as a shareable JSON file
+#[tauri::command]
+pub async fn export_snapshot(
+    path: String,
+    window_seconds: Option<i64>,
+) -> Result<Snapshot, CommandError> {
+    let window_seconds = window_seconds.unwrap_or(DEFAULT_SNAPSHOT_WINDOW_SECONDS);
+    if window_seconds <= 0 {
+        return Err(CommandError::validation(
+            "INVALID_SNAPSHOT_WINDOW",
+            "Snapshot window must be a positive number of seconds",
+        ));
+    }
+    
+    let hardware = hardware::get_hardware_config().await?;
+    let buffer = match get_metrics_collector() {
+        Some(collector) => collector.get_buffer().await,
+        None => Vec::new(),
+    };
+    
+    let snapshot = Snapshot::new(hardware, &buffer, window_seconds);
+    export_import::export_snapshot(&snapshot, &PathBuf::from(path)).await?;
+    Ok(snapshot)
+}
+
+/// Load a snapshot file for read-only inspection
+#[tauri::command]
+pub async fn import_snapshot(path: String) -> Result<Snapshot, CommandError> {
+    export_import::import_snapshot(&PathBuf::from(path))
+        .await
+        .map_err(CommandError::from)
 }
@@ -2,44 +2,165 @@
 //!
 //! This module exposes metrics collection functionality to the frontend.
 
-use crate::core::domain::MetricSample;
-use crate::metrics::{get_metrics_collector, init_metrics_collector, MetricsCollectorConfig, aggregate_metrics};
-use chrono::Utc;
+use crate::analysis::alerts::{watch_bottlenecks, AlertWatcher, BottleneckAlert, ALERT_CHECK_INTERVAL_MS};
+use crate::core::domain::{MetricSample, Run};
+use crate::commands::settings::{
+    current_acknowledgments, current_alert_settings, current_event_flush_interval_ms, current_gpu_utilization_source, current_missed_tick_behavior,
+    current_noise_floors, current_overflow_policy, current_trim_fraction,
+};
+use crate::core::error::CommandError;
+use crate::hardware::environment::capture_run_environment;
+use crate::metrics::coalescer::{coalesce, DEFAULT_FLUSH_INTERVAL_MS};
+use crate::metrics::{get_metrics_collector, init_metrics_collector, CollectorHealth, LivePercentiles, MetricsCollectorConfig, MetricsDelta, aggregate_metrics_trimmed};
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+
+/// Event carrying live metric samples batched since the previous emit
+pub const METRICS_BATCH_EVENT: &str = "metrics-batch";
+
+/// Event carrying a severe bottleneck detected during live collection
+///
+/// The frontend is responsible for turning these into desktop notifications.
+pub const BOTTLENECK_ALERT_EVENT: &str = "bottleneck-alert";
+
+// Task forwarding batched samples to the frontend while collection runs
+static METRICS_FORWARDER: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+// Task checking recent samples for severe bottlenecks while collection runs
+static ALERT_WATCHER: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
 
 /// Start metrics collection
 #[tauri::command]
 pub async fn start_metrics_collection(
+    app: AppHandle,
     sampling_interval_ms: Option<u64>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let config = MetricsCollectorConfig {
         sampling_interval_ms: sampling_interval_ms.unwrap_or(1000),
         buffer_size: 600, // 10 minutes at 1s intervals
+        overflow_policy: current_overflow_policy().unwrap_or_default(),
+        missed_tick_behavior: current_missed_tick_behavior().unwrap_or_default(),
+        gpu_utilization_source: current_gpu_utilization_source().unwrap_or_default(),
+        noise_floors: current_noise_floors().unwrap_or_default(),
     };
     
     let collector = init_metrics_collector(config);
+    // The environment is captured now, as the run is recorded in it rather than where it is saved
+    let mut environment = None;
+    if let Ok(hardware) = crate::hardware::get_hardware_config().await {
+        collector.set_hardware_config(&hardware).await;
+        environment = Some(capture_run_environment(&hardware).await);
+    }
     collector.start().await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)?;
+    if let Some(environment) = environment {
+        collector.set_run_environment(environment).await;
+    }
+    
+    let flush_interval_ms = current_event_flush_interval_ms()
+        .unwrap_or(DEFAULT_FLUSH_INTERVAL_MS)
+        .max(1);
+    let receiver = collector.subscribe();
+    let alert_settings = current_alert_settings().unwrap_or_default();
+    let acknowledgments = current_acknowledgments();
+    let alert_watcher = alert_settings.enabled.then(|| {
+        let alert_app = app.clone();
+        tauri::async_runtime::spawn(watch_bottlenecks(
+            collector.subscribe(),
+            Duration::from_millis(ALERT_CHECK_INTERVAL_MS),
+            AlertWatcher::new(alert_settings.alert_config()).with_acknowledgments(acknowledgments),
+            move |alert: BottleneckAlert| {
+                if let Err(e) = alert_app.emit(BOTTLENECK_ALERT_EVENT, alert) {
+                    log::warn!("Failed to emit bottleneck alert: {}", e);
+                }
+            },
+        ))
+    });
+    let forwarder = tauri::async_runtime::spawn(coalesce(
+        receiver,
+        Duration::from_millis(flush_interval_ms),
+        move |samples| {
+            if let Err(e) = app.emit(METRICS_BATCH_EVENT, samples) {
+                log::warn!("Failed to emit metrics batch: {}", e);
+            }
+        },
+    ));
+    if let Some(previous) = METRICS_FORWARDER.lock().unwrap().replace(forwarder) {
+        previous.abort();
+    }
+    let previous_watcher = std::mem::replace(&mut *ALERT_WATCHER.lock().unwrap(), alert_watcher);
+    if let Some(previous) = previous_watcher {
+        previous.abort();
+    }
+    
+    Ok(())
 }
 
 /// Stop metrics collection
 #[tauri::command]
-pub async fn stop_metrics_collection() -> Result<(), String> {
+pub async fn stop_metrics_collection() -> Result<(), CommandError> {
     if let Some(collector) = get_metrics_collector() {
         collector.stop().await;
+        if let Some(forwarder) = METRICS_FORWARDER.lock().unwrap().take() {
+            forwarder.abort();
+        }
+        if let Some(watcher) = ALERT_WATCHER.lock().unwrap().take() {
+            watcher.abort();
+        }
         Ok(())
     } else {
-        Err("Metrics collector not initialized".to_string())
+        Err(collector_not_initialized())
     }
 }
 
 /// Get current metrics buffer
 #[tauri::command]
-pub async fn get_metrics_buffer() -> Result<Vec<MetricSample>, String> {
+pub async fn get_metrics_buffer() -> Result<Vec<MetricSample>, CommandError> {
     if let Some(collector) = get_metrics_collector() {
         Ok(collector.get_buffer().await)
     } else {
-        Err("Metrics collector not initialized".to_string())
+        Err(collector_not_initialized())
+    }
+}
+
+/// Get the buffered samples newer than `last_timestamp`
+///
+/// Polling with the timestamp of the newest sample already received avoids
+/// re-sending the whole buffer. Pass `None` on the first call; a result with
+/// `resync` set replaces the caller's copy instead of extending it.
+#[tauri::command]
+pub async fn get_metrics_since(last_timestamp: Option<DateTime<Utc>>) -> Result<MetricsDelta, CommandError> {
+    if let Some(collector) = get_metrics_collector() {
+        Ok(collector.get_metrics_since(last_timestamp).await)
+    } else {
+        Err(collector_not_initialized())
+    }
+}
+
+/// Get collector health, including whether early samples are being dropped
+#[tauri::command]
+pub async fn get_metrics_health() -> Result<CollectorHealth, CommandError> {
+    if let Some(collector) = get_metrics_collector() {
+        Ok(collector.health().await)
+    } else {
+        Err(collector_not_initialized())
+    }
+}
+
+/// Get percentiles of every sample collected since collection started
+///
+/// Unlike `get_aggregated_metrics`, these include samples that have already
+/// left the buffer.
+#[tauri::command]
+pub async fn get_live_percentiles() -> Result<HashMap<String, LivePercentiles>, CommandError> {
+    if let Some(collector) = get_metrics_collector() {
+        Ok(collector.live_percentiles().await)
+    } else {
+        Err(collector_not_initialized())
     }
 }
 
@@ -48,18 +169,47 @@ pub async fn get_metrics_buffer() -> Result<Vec<MetricSample>, String> {
 pub async fn get_metrics_in_range(
     start: chrono::DateTime<Utc>,
     end: chrono::DateTime<Utc>,
-) -> Result<Vec<MetricSample>, String> {
+) -> Result<Vec<MetricSample>, CommandError> {
     if let Some(collector) = get_metrics_collector() {
         Ok(collector.get_metrics_in_range(start, end).await)
     } else {
-        Err("Metrics collector not initialized".to_string())
+        Err(collector_not_initialized())
+    }
+}
+
+/// Capture CPU and memory metrics at a high rate for a short benchmark
+///
+/// Returns the captured run without adding it to a session or to the
+/// metrics buffer.
+#[tauri::command]
+pub async fn benchmark_capture(
+    duration_ms: u64,
+    interval_ms: u64,
+) -> Result<Run, CommandError> {
+    if let Some(collector) = get_metrics_collector() {
+        collector.benchmark_capture(Duration::from_millis(duration_ms), interval_ms)
+            .await
+            .map_err(CommandError::from)
+    } else {
+        Err(collector_not_initialized())
     }
 }
 
 /// Get aggregated metrics statistics
+///
+/// The trimmed average uses the trim fraction from the threshold settings.
 #[tauri::command]
 pub fn get_aggregated_metrics(
     metrics: Vec<MetricSample>,
-) -> Result<HashMap<String, crate::metrics::MetricAggregation>, String> {
-    Ok(aggregate_metrics(&metrics))
+) -> Result<HashMap<String, crate::metrics::MetricAggregation>, CommandError> {
+    let trim_fraction = current_trim_fraction().unwrap_or(0.0);
+    Ok(aggregate_metrics_trimmed(&metrics, trim_fraction))
+}
+
+/// Error returned when the metrics collector has not been started
+fn collector_not_initialized() -> CommandError {
+    CommandError::Metrics {
+        code: "METRICS_COLLECTOR_NOT_INITIALIZED".to_string(),
+        message: "Metrics collector not initialized".to_string(),
+    }
 }
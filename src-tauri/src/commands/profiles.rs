@@ -2,8 +2,10 @@
 //!
 //! This module exposes workload profile functionality to the frontend.
 
+use crate::commands::settings::custom_workload_profiles;
 use crate::core::profiles::WorkloadProfiles;
 use crate::core::domain::WorkloadProfile;
+use crate::core::error::CommandError;
 
 /// Get all preset workload profiles
 #[tauri::command]
@@ -11,10 +13,20 @@ pub fn get_preset_profiles() -> Vec<WorkloadProfile> {
     WorkloadProfiles::get_presets()
 }
 
-/// Get a workload profile by ID
+/// Get all user-defined workload profiles
 #[tauri::command]
-pub fn get_profile_by_id(id: String) -> Result<WorkloadProfile, String> {
-    WorkloadProfiles::get_by_id(&id)
-        .ok_or_else(|| format!("Profile with ID '{}' not found", id))
+pub fn get_custom_profiles() -> Result<Vec<WorkloadProfile>, CommandError> {
+    custom_workload_profiles()
+}
+
+/// Get a preset or custom workload profile by ID
+#[tauri::command]
+pub fn get_profile_by_id(id: String) -> Result<WorkloadProfile, CommandError> {
+    let custom = custom_workload_profiles()?;
+    WorkloadProfiles::resolve(&id, &custom)
+        .ok_or_else(|| CommandError::validation(
+            "PROFILE_NOT_FOUND",
+            format!("Profile with ID '{}' not found", id),
+        ))
 }
 
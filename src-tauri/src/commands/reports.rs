@@ -3,14 +3,49 @@
 //! This module exposes report generation functionality to the frontend.
 
 use crate::core::domain::{HardwareConfig, Run, Session};
+use crate::commands::sessions::parse_session_id;
+use crate::commands::settings::{current_acknowledgments, current_display_timezone};
+use crate::core::error::CommandError;
+use crate::persistence::export_import::{self, Contribution};
+use crate::persistence::init_session_storage;
 use crate::persistence::reports::{
-    generate_comparison_report, generate_session_report, ReportConfig, ReportFormat,
+    self, generate_analysis_json, generate_comparison_report, generate_session_report_incremental,
+    ReportConfig, ReportDetailLevel, ReportFormat, ReportProgress, SeverityBands,
 };
 use crate::analysis::comparison::ComparisonResult;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Emitter};
+
+/// Event emitted after each run of a session report is processed
+pub const REPORT_PROGRESS_EVENT: &str = "report-progress";
+
+/// Payload of `report-progress` events, tagged with the report it belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportProgressEvent {
+    pub report_id: String,
+    #[serde(flatten)]
+    pub progress: ReportProgress,
+}
+
+/// Cancellation flags of the session reports being generated, by report id
+fn active_reports() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    static ACTIVE_REPORTS: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+    ACTIVE_REPORTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
 
 /// Generate a session report
+///
+/// The report is built on the blocking thread pool and emits
+/// `report-progress` events tagged with `report_id` as runs are processed.
+/// Pass the same id to `cancel_report_generation` to stop it.
 #[tauri::command]
-pub fn generate_report(
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_report(
+    app: AppHandle,
+    report_id: String,
     session: Session,
     hardware: HardwareConfig,
     format: String,
@@ -18,14 +53,23 @@ pub fn generate_report(
     include_metrics: Option<bool>,
     include_analysis: Option<bool>,
     include_recommendations: Option<bool>,
-) -> Result<String, String> {
+    sections: Option<Vec<String>>,
+    detail_level: Option<ReportDetailLevel>,
+) -> Result<String, CommandError> {
     let report_format = match format.as_str() {
         "text" => ReportFormat::Text,
         "html" => ReportFormat::Html,
         "json" => ReportFormat::Json,
         "pdf" => ReportFormat::Pdf,
-        _ => return Err("Invalid format. Must be 'text', 'html', 'json', or 'pdf'".to_string()),
+        _ => return Err(CommandError::validation(
+            "INVALID_REPORT_FORMAT",
+            "Invalid format. Must be 'text', 'html', 'json', or 'pdf'",
+        )),
     };
+    let sections = sections
+        .map(|names| reports::parse_sections(&names))
+        .transpose()
+        .map_err(|message| CommandError::validation("INVALID_REPORT_SECTION", message))?;
 
     let config = ReportConfig {
         include_hardware: include_hardware.unwrap_or(true),
@@ -34,9 +78,125 @@ pub fn generate_report(
         include_recommendations: include_recommendations.unwrap_or(true),
         include_comparison: false,
         format: report_format,
+        timezone: current_display_timezone(),
+        acknowledgments: current_acknowledgments(),
+        sections,
+        severity_bands: SeverityBands::default(),
+        detail_level,
+    };
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut active = active_reports().lock().unwrap();
+        if active.contains_key(&report_id) {
+            return Err(CommandError::validation(
+                "REPORT_ID_IN_USE",
+                format!("Report {} is already being generated", report_id),
+            ));
+        }
+        active.insert(report_id.clone(), cancel.clone());
+    }
+    
+    let event_report_id = report_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        generate_session_report_incremental(
+            &session,
+            &hardware,
+            &config,
+            &cancel,
+            |progress| {
+                let event = ReportProgressEvent {
+                    report_id: event_report_id.clone(),
+                    progress,
+                };
+                if let Err(e) = app.emit(REPORT_PROGRESS_EVENT, event) {
+                    log::warn!("Failed to emit report progress: {}", e);
+                }
+            },
+        )
+    })
+    .await;
+    active_reports().lock().unwrap().remove(&report_id);
+    
+    result
+        .map_err(|e| CommandError::Internal {
+            code: "INTERNAL_TASK_FAILED".to_string(),
+            message: format!("Report generation task failed: {}", e),
+        })?
+        .map_err(CommandError::from)
+}
+
+/// Cancel the session report generated under `report_id`
+///
+/// Ids of reports that already finished are ignored.
+#[tauri::command]
+pub fn cancel_report_generation(report_id: String) -> Result<(), CommandError> {
+    if let Some(cancel) = active_reports().lock().unwrap().get(&report_id) {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Generate a report for a saved session and write it to `out_path`
+///
+/// The file extension (.txt, .html, .md, .json, .csv or .pdf) picks the
+/// format when it disagrees with `config.format`. Hardware comes from the
+/// session's snapshot. Returns the number of bytes written.
+#[tauri::command]
+pub async fn write_report(
+    app: AppHandle,
+    session_id: String,
+    config: ReportConfig,
+    out_path: String,
+) -> Result<u64, CommandError> {
+    if let Some(sections) = &config.sections {
+        reports::validate_sections(sections)
+            .map_err(|message| CommandError::validation("INVALID_REPORT_SECTION", message))?;
+    }
+    config
+        .severity_bands
+        .validate()
+        .map_err(|message| CommandError::validation("INVALID_SEVERITY_BANDS", message))?;
+    let uuid = parse_session_id(&session_id)?;
+    let storage = init_session_storage(&app).map_err(CommandError::from)?;
+    let session = storage.load_session(&uuid).await.map_err(CommandError::from)?;
+    
+    let config = ReportConfig {
+        timezone: config.timezone.or_else(current_display_timezone),
+        acknowledgments: current_acknowledgments(),
+        ..config
     };
+    reports::write_report(
+        &session,
+        &session.hardware_config_snapshot,
+        &config,
+        &std::path::PathBuf::from(out_path),
+    )
+    .await
+    .map_err(CommandError::from)
+}
 
-    Ok(generate_session_report(&session, &hardware, &config))
+/// Export the full analysis result of every run in a session as JSON
+#[tauri::command]
+pub fn export_analysis_json(session: Session) -> Result<String, CommandError> {
+    generate_analysis_json(&session).map_err(CommandError::from)
+}
+
+/// Export a run as a wide CSV with metrics resampled onto a common time grid
+#[tauri::command]
+pub fn export_run_wide_csv(run: Run, interval_ms: u64) -> Result<String, CommandError> {
+    reports::export_run_wide_csv(&run, interval_ms)
+        .map_err(|message| CommandError::validation("INVALID_RESAMPLE_INTERVAL", message))
+}
+
+/// Export an anonymized contribution of a session for the reference dataset
+///
+/// Fails without writing anything if the contribution would leak an identifier.
+#[tauri::command]
+pub async fn export_anonymized_contribution(session: Session, path: String) -> Result<Contribution, CommandError> {
+    export_import::export_anonymized_contribution(&session, &std::path::PathBuf::from(path))
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Generate a comparison report
@@ -46,13 +206,16 @@ pub fn generate_comparison_report_command(
     run1: Run,
     run2: Run,
     format: String,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let report_format = match format.as_str() {
         "text" => ReportFormat::Text,
         "html" => ReportFormat::Html,
         "json" => ReportFormat::Json,
         "pdf" => ReportFormat::Pdf,
-        _ => return Err("Invalid format. Must be 'text', 'html', 'json', or 'pdf'".to_string()),
+        _ => return Err(CommandError::validation(
+            "INVALID_REPORT_FORMAT",
+            "Invalid format. Must be 'text', 'html', 'json', or 'pdf'",
+        )),
     };
 
     let config = ReportConfig {
@@ -62,6 +225,11 @@ pub fn generate_comparison_report_command(
         include_recommendations: false,
         include_comparison: true,
         format: report_format,
+        timezone: current_display_timezone(),
+        acknowledgments: current_acknowledgments(),
+        sections: None,
+        severity_bands: SeverityBands::default(),
+        detail_level: None,
     };
 
     Ok(generate_comparison_report(&comparison, &run1, &run2, &config))
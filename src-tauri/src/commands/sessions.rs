@@ -2,9 +2,21 @@
 //!
 //! This module exposes session management functionality to the frontend.
 
+use crate::commands::settings::{
+    current_decimation_policy, current_retention_policy, store_retention_policy,
+};
 use crate::core::domain::{Run, Session, WorkloadProfile};
-use crate::persistence::init_session_storage;
+use crate::core::error::CommandError;
+use crate::core::settings::AdvancedSettings;
+use crate::hardware::environment::capture_run_environment;
+use crate::metrics::get_metrics_collector;
+use crate::persistence::export_import::{self, ImportConflictPolicy, ImportOutcome};
+use crate::persistence::{get_sessions_dir, init_session_storage, open_session_database};
+use crate::persistence::models::{RunMatch, SessionSummary};
+use crate::persistence::retention::{self, CleanupReport, RetentionOverview, RetentionPolicy};
+use crate::persistence::size::{self, SizeEstimate};
 use chrono::Utc;
+use std::path::PathBuf;
 use tauri::AppHandle;
 use uuid::Uuid;
 
@@ -15,7 +27,7 @@ pub async fn create_session(
     _name: String,
     profile: WorkloadProfile,
     hardware_config: crate::core::domain::HardwareConfig,
-) -> Result<Session, String> {
+) -> Result<Session, CommandError> {
     let session = Session {
         id: Uuid::new_v4(),
         start_time: Utc::now(),
@@ -27,10 +39,10 @@ pub async fn create_session(
     
     // Save session immediately
     let storage = init_session_storage(&app)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     storage.save_session(&session)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     
     Ok(session)
 }
@@ -40,12 +52,12 @@ pub async fn create_session(
 pub async fn save_session(
     app: AppHandle,
     session: Session,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let storage = init_session_storage(&app)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     storage.save_session(&session)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Load a session
@@ -53,45 +65,58 @@ pub async fn save_session(
 pub async fn load_session(
     app: AppHandle,
     session_id: String,
-) -> Result<Session, String> {
-    let uuid = Uuid::parse_str(&session_id)
-        .map_err(|e| format!("Invalid session ID: {}", e))?;
+) -> Result<Session, CommandError> {
+    let uuid = parse_session_id(&session_id)?;
     
     let storage = init_session_storage(&app)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     storage.load_session(&uuid)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// List all sessions
 #[tauri::command]
 pub async fn list_sessions(
     app: AppHandle,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, CommandError> {
     let storage = init_session_storage(&app)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     let session_ids = storage.list_sessions()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     
     Ok(session_ids.iter().map(|id| id.to_string()).collect())
 }
 
+/// List saved sessions with their names, times and run counts, newest first
+///
+/// Much cheaper than loading each session, as metric streams are never
+/// deserialized.
+#[tauri::command]
+pub async fn list_session_summaries(
+    app: AppHandle,
+) -> Result<Vec<SessionSummary>, CommandError> {
+    let storage = init_session_storage(&app)
+        .map_err(CommandError::from)?;
+    storage.list_session_summaries()
+        .await
+        .map_err(CommandError::from)
+}
+
 /// Delete a session
 #[tauri::command]
 pub async fn delete_session(
     app: AppHandle,
     session_id: String,
-) -> Result<(), String> {
-    let uuid = Uuid::parse_str(&session_id)
-        .map_err(|e| format!("Invalid session ID: {}", e))?;
+) -> Result<(), CommandError> {
+    let uuid = parse_session_id(&session_id)?;
     
     let storage = init_session_storage(&app)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     storage.delete_session(&uuid)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Add a run to a session
@@ -99,23 +124,48 @@ pub async fn delete_session(
 pub async fn add_run_to_session(
     app: AppHandle,
     session_id: String,
-    run: Run,
-) -> Result<Session, String> {
-    let uuid = Uuid::parse_str(&session_id)
-        .map_err(|e| format!("Invalid session ID: {}", e))?;
+    mut run: Run,
+) -> Result<Session, CommandError> {
+    let uuid = parse_session_id(&session_id)?;
     
     let storage = init_session_storage(&app)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     
     let mut session = storage.load_session(&uuid)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     
+    // The environment recorded when collection started describes the run;
+    // one captured now is only a fallback for runs recorded without it
+    if run.environment.is_none() {
+        let collector = get_metrics_collector();
+        let recorded = match &collector {
+            Some(collector) => collector.run_environment().await,
+            None => None,
+        };
+        let mut environment = match recorded {
+            Some(environment) => environment,
+            None => capture_run_environment(&session.hardware_config_snapshot).await,
+        };
+        if let Some(collector) = &collector {
+            environment.foreground_process = collector.foreground_process().await;
+        }
+        run.environment = Some(environment);
+    }
+    // Runs arrive with their live analysis, so decimation only affects the stored samples
+    if let Some(policy) = current_decimation_policy() {
+        policy.apply(&mut run);
+    }
     session.runs.push(run);
     
     storage.save_session(&session)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
+    
+    // The captured metrics are persisted, so a grown buffer can shrink again
+    if let Some(collector) = get_metrics_collector() {
+        collector.mark_saved().await;
+    }
     
     Ok(session)
 }
@@ -125,22 +175,226 @@ pub async fn add_run_to_session(
 pub async fn end_session(
     app: AppHandle,
     session_id: String,
-) -> Result<Session, String> {
-    let uuid = Uuid::parse_str(&session_id)
-        .map_err(|e| format!("Invalid session ID: {}", e))?;
+) -> Result<Session, CommandError> {
+    let uuid = parse_session_id(&session_id)?;
     
     let storage = init_session_storage(&app)
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     
     let mut session = storage.load_session(&uuid)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     
     session.end_time = Some(Utc::now());
     
     storage.save_session(&session)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(CommandError::from)?;
     
     Ok(session)
 }
+
+/// Merge several runs of a session into one
+#[tauri::command]
+pub async fn merge_runs(
+    app: AppHandle,
+    session_id: String,
+    run_ids: Vec<String>,
+    merged_name: String,
+) -> Result<Run, CommandError> {
+    let uuid = parse_session_id(&session_id)?;
+    let run_uuids = run_ids
+        .iter()
+        .map(|id| {
+            Uuid::parse_str(id)
+                .map_err(|e| CommandError::validation("INVALID_RUN_ID", format!("Invalid run ID: {}", e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    let storage = init_session_storage(&app)
+        .map_err(CommandError::from)?;
+    
+    let mut session = storage.load_session(&uuid)
+        .await
+        .map_err(CommandError::from)?;
+    
+    let merged = crate::core::sessions::merge_runs(&mut session, &run_uuids, &merged_name)
+        .map_err(CommandError::from)?;
+    
+    storage.save_session(&session)
+        .await
+        .map_err(CommandError::from)?;
+    
+    Ok(merged)
+}
+
+/// Duplicate a saved session as the starting point for a variant test
+///
+/// The clone and its runs get new IDs and are saved alongside the original,
+/// which is left unchanged. Returns the ID of the clone.
+#[tauri::command]
+pub async fn clone_session(
+    app: AppHandle,
+    session_id: String,
+    new_name: String,
+) -> Result<Uuid, CommandError> {
+    let uuid = parse_session_id(&session_id)?;
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(CommandError::validation("INVALID_SESSION_NAME", "Session name must not be empty"));
+    }
+    
+    let storage = init_session_storage(&app)
+        .map_err(CommandError::from)?;
+    
+    let session = storage.load_session(&uuid)
+        .await
+        .map_err(CommandError::from)?;
+    
+    let clone = crate::core::sessions::clone_session(&session, new_name);
+    storage.save_session(&clone)
+        .await
+        .map_err(CommandError::from)?;
+    
+    Ok(clone.id)
+}
+
+/// Export a saved session to a file
+///
+/// The format follows the extension: `.stio` writes the binary format,
+/// anything else JSON.
+#[tauri::command]
+pub async fn export_session(
+    app: AppHandle,
+    session_id: String,
+    path: String,
+) -> Result<(), CommandError> {
+    let uuid = parse_session_id(&session_id)?;
+    
+    let storage = init_session_storage(&app)
+        .map_err(CommandError::from)?;
+    let session = storage.load_session(&uuid)
+        .await
+        .map_err(CommandError::from)?;
+    
+    export_import::export_session(&session, &PathBuf::from(path))
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Import a session file into storage
+///
+/// The format follows the extension as in `export_session`. An ID clash with
+/// a stored session is resolved with `policy`, by default a fresh ID.
+#[tauri::command]
+pub async fn import_session(
+    app: AppHandle,
+    path: String,
+    policy: Option<ImportConflictPolicy>,
+) -> Result<ImportOutcome, CommandError> {
+    let session = export_import::import_session(&PathBuf::from(path))
+        .await
+        .map_err(CommandError::from)?;
+    
+    let storage = init_session_storage(&app)
+        .map_err(CommandError::from)?;
+    storage.import_session(session, policy.unwrap_or_default())
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Search run names and notes across all saved sessions
+///
+/// Runs are searched through the session database's full-text index.
+/// Installs without a database fall back to a substring scan of the
+/// session files.
+#[tauri::command]
+pub async fn search_runs(
+    app: AppHandle,
+    query: String,
+) -> Result<Vec<RunMatch>, CommandError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(CommandError::validation("EMPTY_SEARCH_QUERY", "Search query must not be empty"));
+    }
+    
+    if let Some(database) = open_session_database(&app).map_err(CommandError::from)? {
+        return database.search_runs(query)
+            .map_err(CommandError::from);
+    }
+    
+    let storage = init_session_storage(&app)
+        .map_err(CommandError::from)?;
+    storage.search_runs(query)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Estimate how much disk space a session will take once saved
+#[tauri::command]
+pub fn estimate_session_size(session: Session) -> Result<SizeEstimate, CommandError> {
+    Ok(size::estimate_session_size(&session))
+}
+
+/// Get retention statistics for the session files and database
+#[tauri::command]
+pub async fn get_retention_stats_command(app: AppHandle) -> Result<RetentionOverview, CommandError> {
+    retention_overview(&app, &retention_policy()).await
+}
+
+/// Delete sessions past retention now
+///
+/// At least `min_sessions_to_keep` sessions are kept in each store. Returns
+/// the number of sessions deleted and the statistics afterwards.
+#[tauri::command]
+pub async fn cleanup_now_command(app: AppHandle) -> Result<CleanupReport, CommandError> {
+    let sessions_dir = get_sessions_dir(&app)
+        .map_err(CommandError::from)?;
+    let database = open_session_database(&app)
+        .map_err(CommandError::from)?;
+    
+    retention::cleanup_now(&sessions_dir, database.as_ref(), &retention_policy())
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Store a new retention policy and return the statistics under it
+#[tauri::command]
+pub async fn update_retention_policy(
+    app: AppHandle,
+    policy: RetentionPolicy,
+) -> Result<RetentionOverview, CommandError> {
+    if policy.retention_days == 0 {
+        return Err(CommandError::validation(
+            "INVALID_RETENTION_DAYS",
+            "Retention period must be at least one day",
+        ));
+    }
+    
+    store_retention_policy(&policy)?;
+    retention_overview(&app, &policy).await
+}
+
+/// Retention policy from settings, or the default settings' policy
+fn retention_policy() -> RetentionPolicy {
+    current_retention_policy()
+        .unwrap_or_else(|| AdvancedSettings::default().retention_policy())
+}
+
+/// Retention statistics of every session store under `policy`
+async fn retention_overview(app: &AppHandle, policy: &RetentionPolicy) -> Result<RetentionOverview, CommandError> {
+    let sessions_dir = get_sessions_dir(app)
+        .map_err(CommandError::from)?;
+    let database = open_session_database(app)
+        .map_err(CommandError::from)?;
+    
+    retention::retention_overview(&sessions_dir, database.as_ref(), policy)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Parse a session ID passed from the frontend
+pub(crate) fn parse_session_id(session_id: &str) -> Result<Uuid, CommandError> {
+    Uuid::parse_str(session_id)
+        .map_err(|e| CommandError::validation("INVALID_SESSION_ID", format!("Invalid session ID: {}", e)))
+}
@@ -2,10 +2,18 @@
 //!
 //! This module exposes settings functionality to the frontend.
 
+use crate::analysis::insights::BottleneckAcknowledgment;
+use crate::core::domain::{BottleneckType, ThresholdStatistic, WorkloadProfile};
+use crate::core::error::CommandError;
+use crate::core::profiles::WorkloadProfiles;
 use crate::core::settings::{
-    SamplingSettings, SettingsManager, ThresholdSettings, ThemePreferences,
+    AlertSettings, AnalysisProfile, BufferOverflowPolicy, GpuUtilizationSource, IdleBaseline, MissedTickBehavior, NoiseFloors, SamplingSettings, SettingsManager, ThresholdSettings, ThemePreferences,
     UnitPreferences, UserSettings,
 };
+use crate::metrics::{get_metrics_collector, init_metrics_collector, MetricsCollectorConfig};
+use crate::persistence::decimation::DecimationPolicy;
+use crate::persistence::retention::RetentionPolicy;
+use chrono::Utc;
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 
@@ -32,104 +40,549 @@ pub fn init_settings_manager(app_handle: AppHandle) -> Result<(), String> {
 
 /// Get current settings
 #[tauri::command]
-pub fn get_settings() -> Result<UserSettings, String> {
+pub fn get_settings() -> Result<UserSettings, CommandError> {
     let manager_guard = SETTINGS_MANAGER
         .lock()
         .unwrap();
     let manager = manager_guard
         .as_ref()
-        .ok_or("Settings manager not initialized")?;
+        .ok_or_else(settings_not_initialized)?;
 
     Ok(manager.get_settings().clone())
 }
 
 /// Update settings
 #[tauri::command]
-pub fn update_settings(settings: UserSettings) -> Result<(), String> {
+pub fn update_settings(settings: UserSettings) -> Result<(), CommandError> {
     let mut manager_guard = SETTINGS_MANAGER
         .lock()
         .unwrap();
     let manager = manager_guard
         .as_mut()
-        .ok_or("Settings manager not initialized")?;
+        .ok_or_else(settings_not_initialized)?;
 
     manager
         .update_settings(settings)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Update threshold settings
 #[tauri::command]
-pub fn update_thresholds(thresholds: ThresholdSettings) -> Result<(), String> {
+pub fn update_thresholds(thresholds: ThresholdSettings) -> Result<(), CommandError> {
+    if !valid_trim_fraction(thresholds.trim_fraction) {
+        return Err(CommandError::validation(
+            "INVALID_TRIM_FRACTION",
+            format!("Trim fraction must be between 0 and 0.5, got {}", thresholds.trim_fraction),
+        ));
+    }
+    
     let mut manager_guard = SETTINGS_MANAGER
         .lock()
         .unwrap();
     let manager = manager_guard
         .as_mut()
-        .ok_or("Settings manager not initialized")?;
+        .ok_or_else(settings_not_initialized)?;
 
     manager
         .update_thresholds(thresholds)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Update sampling settings
+///
+/// A collector that already exists adopts the new settings immediately,
+/// restarting its sampling loop if it is running.
 #[tauri::command]
-pub fn update_sampling(sampling: SamplingSettings) -> Result<(), String> {
-    let mut manager_guard = SETTINGS_MANAGER
-        .lock()
-        .unwrap();
-    let manager = manager_guard
-        .as_mut()
-        .ok_or("Settings manager not initialized")?;
+pub async fn update_sampling(sampling: SamplingSettings) -> Result<(), CommandError> {
+    let config = MetricsCollectorConfig::from_sampling(&sampling);
+    {
+        let mut manager_guard = SETTINGS_MANAGER
+            .lock()
+            .unwrap();
+        let manager = manager_guard
+            .as_mut()
+            .ok_or_else(settings_not_initialized)?;
 
-    manager
-        .update_sampling(sampling)
-        .map_err(|e| e.to_string())
+        manager
+            .update_sampling(sampling)
+            .map_err(CommandError::from)?;
+    }
+
+    if let Some(collector) = get_metrics_collector() {
+        collector.reconfigure(config).await.map_err(CommandError::from)?;
+    }
+    Ok(())
 }
 
 /// Update unit preferences
 #[tauri::command]
-pub fn update_units(units: UnitPreferences) -> Result<(), String> {
+pub fn update_units(units: UnitPreferences) -> Result<(), CommandError> {
     let mut manager_guard = SETTINGS_MANAGER
         .lock()
         .unwrap();
     let manager = manager_guard
         .as_mut()
-        .ok_or("Settings manager not initialized")?;
+        .ok_or_else(settings_not_initialized)?;
 
     manager
         .update_units(units)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Update theme preferences
 #[tauri::command]
-pub fn update_theme(theme: ThemePreferences) -> Result<(), String> {
+pub fn update_theme(theme: ThemePreferences) -> Result<(), CommandError> {
     let mut manager_guard = SETTINGS_MANAGER
         .lock()
         .unwrap();
     let manager = manager_guard
         .as_mut()
-        .ok_or("Settings manager not initialized")?;
+        .ok_or_else(settings_not_initialized)?;
 
     manager
         .update_theme(theme)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// Reset to default settings
 #[tauri::command]
-pub fn reset_settings() -> Result<(), String> {
+pub fn reset_settings() -> Result<(), CommandError> {
     let mut manager_guard = SETTINGS_MANAGER
         .lock()
         .unwrap();
     let manager = manager_guard
         .as_mut()
-        .ok_or("Settings manager not initialized")?;
+        .ok_or_else(settings_not_initialized)?;
 
     manager
         .reset_to_defaults()
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Save a named analysis profile, replacing one with the same ID
+#[tauri::command]
+pub fn save_analysis_profile(profile: AnalysisProfile) -> Result<(), CommandError> {
+    if profile.id.trim().is_empty() || profile.name.trim().is_empty() {
+        return Err(CommandError::validation(
+            "INVALID_ANALYSIS_PROFILE",
+            "Analysis profile ID and name must not be empty",
+        ));
+    }
+    if profile.window_seconds.is_some_and(|seconds| seconds <= 0) {
+        return Err(CommandError::validation(
+            "INVALID_ANALYSIS_PROFILE",
+            "Analysis window must be greater than zero",
+        ));
+    }
+    match profile.thresholds.statistic {
+        ThresholdStatistic::Percentile(p) if !(0.0..=100.0).contains(&p) => {
+            return Err(CommandError::validation(
+                "INVALID_ANALYSIS_PROFILE",
+                format!("Percentile must be between 0 and 100, got {}", p),
+            ));
+        }
+        ThresholdStatistic::TrimmedMean(fraction) if !valid_trim_fraction(fraction) => {
+            return Err(CommandError::validation(
+                "INVALID_ANALYSIS_PROFILE",
+                format!("Trim fraction must be between 0 and 0.5, got {}", fraction),
+            ));
+        }
+        _ => {}
+    }
+
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    manager
+        .save_analysis_profile(profile)
+        .map_err(CommandError::from)
+}
+
+/// Delete a named analysis profile
+#[tauri::command]
+pub fn delete_analysis_profile(id: String) -> Result<(), CommandError> {
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    if manager.delete_analysis_profile(&id).map_err(CommandError::from)? {
+        Ok(())
+    } else {
+        Err(analysis_profile_not_found(&id))
+    }
+}
+
+/// Create a custom workload profile
+///
+/// The ID must not be used by a preset or another custom profile.
+#[tauri::command]
+pub fn create_custom_profile(profile: WorkloadProfile) -> Result<WorkloadProfile, CommandError> {
+    WorkloadProfiles::validate_custom(&profile)
+        .map_err(|message| CommandError::validation("INVALID_CUSTOM_PROFILE", message))?;
+
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    if manager.get_settings().custom_profiles.iter().any(|p| p.id == profile.id) {
+        return Err(CommandError::validation(
+            "CUSTOM_PROFILE_EXISTS",
+            format!("A custom profile with ID '{}' already exists", profile.id),
+        ));
+    }
+    manager
+        .save_custom_profile(profile.clone())
+        .map_err(CommandError::from)?;
+    Ok(profile)
+}
+
+/// Replace an existing custom workload profile with the same ID
+#[tauri::command]
+pub fn update_custom_profile(profile: WorkloadProfile) -> Result<WorkloadProfile, CommandError> {
+    WorkloadProfiles::validate_custom(&profile)
+        .map_err(|message| CommandError::validation("INVALID_CUSTOM_PROFILE", message))?;
+
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    if !manager.get_settings().custom_profiles.iter().any(|p| p.id == profile.id) {
+        return Err(custom_profile_not_found(&profile.id));
+    }
+    manager
+        .save_custom_profile(profile.clone())
+        .map_err(CommandError::from)?;
+    Ok(profile)
+}
+
+/// Delete a custom workload profile
+#[tauri::command]
+pub fn delete_custom_profile(id: String) -> Result<(), CommandError> {
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    if manager.delete_custom_profile(&id).map_err(CommandError::from)? {
+        Ok(())
+    } else {
+        Err(custom_profile_not_found(&id))
+    }
+}
+
+/// Acknowledge a bottleneck type so it is dismissed in insights, reports, and alerts
+///
+/// Bottlenecks more severe than `max_severity` are still surfaced.
+/// Acknowledging a type again replaces its previous acknowledgment.
+#[tauri::command]
+pub fn acknowledge_bottleneck(
+    bottleneck_type: BottleneckType,
+    max_severity: Option<u8>,
+    note: Option<String>,
+) -> Result<BottleneckAcknowledgment, CommandError> {
+    if let Some(max_severity) = max_severity.filter(|severity| *severity > 100) {
+        return Err(CommandError::validation(
+            "INVALID_ACKNOWLEDGMENT",
+            format!("Severity ceiling must be between 0 and 100, got {}", max_severity),
+        ));
+    }
+    let acknowledgment = BottleneckAcknowledgment {
+        bottleneck_type,
+        max_severity,
+        note: note.filter(|note| !note.trim().is_empty()),
+        acknowledged_at: Utc::now(),
+    };
+
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    manager
+        .acknowledge_bottleneck(acknowledgment.clone())
+        .map_err(CommandError::from)?;
+    Ok(acknowledgment)
+}
+
+/// Remove the acknowledgment of a bottleneck type
+#[tauri::command]
+pub fn unacknowledge_bottleneck(bottleneck_type: BottleneckType) -> Result<(), CommandError> {
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    if manager.unacknowledge_bottleneck(&bottleneck_type).map_err(CommandError::from)? {
+        Ok(())
+    } else {
+        Err(CommandError::Settings {
+            code: "ACKNOWLEDGMENT_NOT_FOUND".to_string(),
+            message: format!("No acknowledgment for {:?} bottlenecks", bottleneck_type),
+        })
+    }
+}
+
+/// Get a stored analysis profile by ID
+pub(crate) fn find_analysis_profile(id: &str) -> Result<AnalysisProfile, CommandError> {
+    let manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_ref()
+        .ok_or_else(settings_not_initialized)?;
+
+    manager
+        .get_settings()
+        .analysis_profiles
+        .iter()
+        .find(|p| p.id == id)
+        .cloned()
+        .ok_or_else(|| analysis_profile_not_found(id))
+}
+
+/// Get the stored custom workload profiles
+pub(crate) fn custom_workload_profiles() -> Result<Vec<WorkloadProfile>, CommandError> {
+    let manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_ref()
+        .ok_or_else(settings_not_initialized)?;
+
+    Ok(manager.get_settings().custom_profiles.clone())
+}
+
+/// Get the stored idle baseline, if one has been captured
+pub(crate) fn current_idle_baseline() -> Result<Option<IdleBaseline>, CommandError> {
+    let manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_ref()
+        .ok_or_else(settings_not_initialized)?;
+
+    Ok(manager.get_settings().idle_baseline.clone())
+}
+
+/// Get the configured display timezone, if settings are available
+pub(crate) fn current_display_timezone() -> Option<String> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().units.display_timezone.clone())
+}
+
+/// Get the configured missed-tick behavior, if settings are available
+pub(crate) fn current_missed_tick_behavior() -> Option<MissedTickBehavior> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().sampling.missed_tick_behavior)
+}
+
+/// Get the configured GPU utilization source, if settings are available
+pub(crate) fn current_gpu_utilization_source() -> Option<GpuUtilizationSource> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().sampling.gpu_utilization_source)
+}
+
+/// Get the configured collection noise floors, if settings are available
+pub(crate) fn current_noise_floors() -> Option<NoiseFloors> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().sampling.noise_floors)
+}
+
+/// Get the configured live-metric event flush interval, if settings are available
+pub(crate) fn current_event_flush_interval_ms() -> Option<u64> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().sampling.event_flush_interval_ms)
+}
+
+/// Get the configured live bottleneck alert settings, if settings are available
+pub(crate) fn current_alert_settings() -> Option<AlertSettings> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().alerts.clone())
+}
+
+/// Get the acknowledged bottlenecks, empty when settings are unavailable
+pub(crate) fn current_acknowledgments() -> Vec<BottleneckAcknowledgment> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().acknowledgments.clone())
+        .unwrap_or_default()
+}
+
+/// Get the configured pre-save decimation policy, if settings are available
+pub(crate) fn current_decimation_policy() -> Option<DecimationPolicy> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().advanced.decimation.clone())
+}
+
+/// Get the configured trim fraction for trimmed means, if settings are available
+pub(crate) fn current_trim_fraction() -> Option<f64> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().thresholds.trim_fraction)
+}
+
+/// Get the configured minimum effect size for run comparisons, if settings are available
+pub(crate) fn current_min_comparison_effect_percent() -> Option<f64> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().thresholds.min_comparison_effect_percent)
+}
+
+/// Get the configured buffer overflow policy, if settings are available
+pub(crate) fn current_overflow_policy() -> Option<BufferOverflowPolicy> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().sampling.overflow_policy)
+}
+
+/// Get the configured retention policy, if settings are available
+pub(crate) fn current_retention_policy() -> Option<RetentionPolicy> {
+    SETTINGS_MANAGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|manager| manager.get_settings().advanced.retention_policy())
+}
+
+/// Store a retention policy in settings
+pub(crate) fn store_retention_policy(policy: &RetentionPolicy) -> Result<(), CommandError> {
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    manager
+        .update_retention_policy(policy)
+        .map_err(CommandError::from)
 }
 
+/// Capture an idle baseline and store it in settings
+///
+/// Samples the system for `duration_seconds` while it should be idle. The
+/// metrics collector is started for the capture if it is not already running.
+#[tauri::command]
+pub async fn capture_idle_baseline(duration_seconds: u64) -> Result<IdleBaseline, CommandError> {
+    if duration_seconds == 0 {
+        return Err(CommandError::validation(
+            "INVALID_BASELINE_DURATION",
+            "Baseline duration must be greater than zero",
+        ));
+    }
+
+    let collector = match get_metrics_collector() {
+        Some(collector) => collector,
+        None => init_metrics_collector(MetricsCollectorConfig::default()),
+    };
+    let started_here = !collector.is_running().await;
+    if started_here {
+        collector.start().await.map_err(CommandError::from)?;
+    }
+
+    let start = Utc::now();
+    tokio::time::sleep(std::time::Duration::from_secs(duration_seconds)).await;
+    let samples = collector.get_metrics_in_range(start, Utc::now()).await;
+
+    if started_here {
+        collector.stop().await;
+    }
+
+    if samples.is_empty() {
+        return Err(CommandError::Metrics {
+            code: "METRICS_NO_SAMPLES".to_string(),
+            message: "No metrics were collected during baseline capture".to_string(),
+        });
+    }
+    let baseline = IdleBaseline::from_samples(&samples, duration_seconds);
+
+    let mut manager_guard = SETTINGS_MANAGER
+        .lock()
+        .unwrap();
+    let manager = manager_guard
+        .as_mut()
+        .ok_or_else(settings_not_initialized)?;
+
+    manager
+        .update_idle_baseline(Some(baseline.clone()))
+        .map_err(CommandError::from)?;
+
+    Ok(baseline)
+}
+
+/// Error returned when the settings manager has not been initialized
+fn settings_not_initialized() -> CommandError {
+    CommandError::Settings {
+        code: "SETTINGS_NOT_INITIALIZED".to_string(),
+        message: "Settings manager not initialized".to_string(),
+    }
+}
+
+/// Error returned when no analysis profile has the requested ID
+fn analysis_profile_not_found(id: &str) -> CommandError {
+    CommandError::Settings {
+        code: "ANALYSIS_PROFILE_NOT_FOUND".to_string(),
+        message: format!("Analysis profile {} not found", id),
+    }
+}
+
+/// Error returned when no custom workload profile has the requested ID
+fn custom_profile_not_found(id: &str) -> CommandError {
+    CommandError::Settings {
+        code: "CUSTOM_PROFILE_NOT_FOUND".to_string(),
+        message: format!("Custom profile {} not found", id),
+    }
+}
+
+/// Whether a share of samples trimmed from each end is usable
+fn valid_trim_fraction(fraction: f64) -> bool {
+    (0.0..=0.5).contains(&fraction)
+}
@@ -31,6 +31,12 @@ pub struct CPUInfo {
     pub base_clock_mhz: Option<f64>,
     pub boost_clock_mhz: Option<f64>,
     pub architecture: Option<String>,
+    /// Maximum frequency of each logical core, indexed by core number
+    ///
+    /// Empty when the platform does not expose per-core limits. Hybrid CPUs
+    /// report distinct values for performance and efficiency cores.
+    #[serde(default)]
+    pub core_max_frequencies_mhz: Vec<f64>,
 }
 
 /// GPU information
@@ -67,6 +73,9 @@ pub struct StorageInfo {
     pub capacity_mb: u64,
     pub storage_type: StorageType,
     pub interface: Option<String>,
+    /// Free space across the device's volumes, `None` if it could not be read
+    #[serde(default)]
+    pub free_mb: Option<u64>,
 }
 
 /// Storage device type
@@ -127,12 +136,44 @@ pub struct WorkloadProfile {
     pub id: String,
     pub name: String,
     pub workload_type: WorkloadType,
+    #[serde(with = "profile_parameters")]
     pub parameters: HashMap<String, serde_json::Value>,
     pub threshold_overrides: Option<ThresholdOverrides>,
 }
 
+/// Profile parameters as a JSON object in JSON and as JSON text in binary
+/// formats, which cannot decode self-describing `serde_json::Value`s
+mod profile_parameters {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    
+    pub fn serialize<S: Serializer>(
+        parameters: &HashMap<String, serde_json::Value>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            parameters.serialize(serializer)
+        } else {
+            serde_json::to_string(parameters)
+                .map_err(serde::ser::Error::custom)?
+                .serialize(serializer)
+        }
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, serde_json::Value>, D::Error> {
+        if deserializer.is_human_readable() {
+            HashMap::deserialize(deserializer)
+        } else {
+            let text = String::deserialize(deserializer)?;
+            serde_json::from_str(&text).map_err(de::Error::custom)
+        }
+    }
+}
+
 /// Workload type
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum WorkloadType {
     Gaming,
@@ -149,6 +190,35 @@ pub struct ThresholdOverrides {
     pub gpu_high: Option<f64>,
     pub ram_high: Option<f64>,
     pub vram_high: Option<f64>,
+    /// Statistic of the windowed samples compared against the thresholds
+    #[serde(default)]
+    pub statistic: ThresholdStatistic,
+    /// Share of each threshold (0-1] at which several subsystems together
+    /// count as combined pressure; `None` uses the default
+    #[serde(default)]
+    pub combined_pressure: Option<f64>,
+    /// Seconds a storage or bandwidth limit must be exceeded without a break
+    /// before it counts as a bottleneck; `None` uses the default
+    #[serde(default)]
+    pub sustained_seconds: Option<f64>,
+}
+
+/// Statistic used to summarize windowed samples for threshold checks
+///
+/// The mean hides sustained plateaus behind brief dips; a high percentile
+/// reflects a component that is mostly pegged with occasional dips.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ThresholdStatistic {
+    #[default]
+    Mean,
+    /// Percentile of the samples (0-100), e.g. 95.0 for p95
+    Percentile(f64),
+    /// Mean after dropping this share of samples from each end (0.0 - 0.5)
+    ///
+    /// Ignores single bad readings such as a 0% blip from a missed read.
+    #[serde(rename = "trimmed_mean")]
+    TrimmedMean(f64),
 }
 
 /// Session containing multiple runs
@@ -170,11 +240,46 @@ pub struct Run {
     pub metrics_streams: HashMap<String, Vec<MetricSample>>,
     pub analysis_result: Option<BottleneckAnalysisResult>,
     pub notes: Option<String>,
+    /// System state the run was captured under, `None` for older runs
+    #[serde(default)]
+    pub environment: Option<RunEnvironment>,
+}
+
+/// System state that affects whether two runs are comparable
+///
+/// Captured when metrics collection for the run starts, or when the run is
+/// saved if collection recorded none. Fields are `None` when the platform
+/// does not expose them.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RunEnvironment {
+    /// Full OS name including version and build
+    pub os_version: Option<String>,
+    /// Driver version of each GPU, labelled with the GPU model
+    #[serde(default)]
+    pub gpu_driver_versions: Vec<String>,
+    /// Active power plan or CPU frequency governor
+    pub power_plan: Option<String>,
+    /// Processes running when the run was captured
+    pub background_process_count: Option<usize>,
+    /// Whether the system runs inside a virtual machine
+    pub virtual_machine: Option<bool>,
+    /// Application that had focus when the run started
+    #[serde(default)]
+    pub foreground_process: Option<ForegroundProcess>,
+}
+
+/// Process owning the focused window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForegroundProcess {
+    pub pid: u32,
+    /// Executable name, when the process could be looked up
+    pub name: Option<String>,
 }
 
 /// Metric sample with timestamp
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricSample {
+    #[serde(with = "sample_timestamp")]
     pub timestamp: DateTime<Utc>,
     pub metric_type: MetricType,
     pub value: f64,
@@ -182,6 +287,51 @@ pub struct MetricSample {
     pub source_component: String,
 }
 
+impl MetricSample {
+    /// Create a sample taken now, using the canonical unit for its type
+    pub fn new(metric_type: MetricType, value: f64, source_component: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            unit: unit_for(&metric_type).to_string(),
+            metric_type,
+            value,
+            source_component: source_component.into(),
+        }
+    }
+    
+    /// Set the time the sample was taken
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+}
+
+/// Sample timestamps as RFC 3339 text in JSON and as seconds and nanoseconds
+/// in binary formats, where parsing the text would dominate load time
+mod sample_timestamp {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+    
+    pub fn serialize<S: Serializer>(timestamp: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            timestamp.serialize(serializer)
+        } else {
+            (timestamp.timestamp(), timestamp.timestamp_subsec_nanos()).serialize(serializer)
+        }
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+        if deserializer.is_human_readable() {
+            DateTime::<Utc>::deserialize(deserializer)
+        } else {
+            let (secs, nanos) = <(i64, u32)>::deserialize(deserializer)?;
+            Utc.timestamp_opt(secs, nanos)
+                .single()
+                .ok_or_else(|| de::Error::custom("sample timestamp out of range"))
+        }
+    }
+}
+
 /// Metric type enumeration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -190,8 +340,11 @@ pub enum MetricType {
     CpuUtilizationPerCore,
     GpuUtilization,
     GpuVramUsage,
+    /// VRAM in use as a share of the device's total VRAM
+    GpuVramUsagePercent,
     GpuTemperature,
     GpuClock,
+    CpuClock,
     MemoryUsage,
     MemorySwapUsage,
     StorageReadThroughput,
@@ -200,11 +353,91 @@ pub enum MetricType {
     MemoryReadThroughput,
     MemoryWriteThroughput,
     GpuMemoryTransfer,
+    /// PCIe generation the GPU link is currently running at
+    PcieLinkGen,
+    /// PCIe lanes the GPU link is currently using
+    PcieLinkWidth,
+    /// Highest PCIe generation supported by both the GPU and the slot
+    PcieLinkGenMax,
+    /// Widest PCIe link supported by both the GPU and the slot
+    PcieLinkWidthMax,
     Temperature,
     FanSpeed,
+    DpcTime,
+    InterruptTime,
     Fps,
     FrameTime,
     RenderTime,
+    /// Share of time the GPU memory controller was busy reading or writing VRAM
+    GpuMemoryControllerLoad,
+    /// Supply voltage reported by a motherboard or component sensor
+    Voltage,
+    /// GPU engine utilization of a single process; the source names the process
+    ProcessGpuUtilization,
+    /// RAM in use in MB; `MemoryUsage` is the same reading as a percent of total
+    MemoryUsed,
+    /// GPU board power draw
+    GpuPower,
+    /// Disk write rate of a single process; the source names the process
+    ProcessDiskWriteThroughput,
+    /// Share of total CPU capacity used by a single process; the source names the process
+    ProcessCpuUtilization,
+    /// Motherboard or component sensor temperature (VRM, chipset, NVMe, ...);
+    /// the source names the sensor. `Temperature` is the CPU package alone.
+    SensorTemperature,
+    /// Utilization of one GPU adapter on a system with several; the source
+    /// names the GPU model. `GpuUtilization` is the primary GPU alone.
+    GpuAdapterUtilization,
+    /// GPU hotspot (junction) temperature. `GpuTemperature` is the edge sensor alone.
+    GpuHotspotTemperature,
+}
+
+/// Source component of GPU hotspot (junction) temperature samples in older runs
+///
+/// Hotspot samples now carry the source of their GPU, like its edge
+/// temperature. Older runs recorded every hotspot reading under this source,
+/// and runs from before `GpuHotspotTemperature` existed stored them as
+/// `GpuTemperature` under it.
+pub const GPU_HOTSPOT_SOURCE: &str = "GPU Hotspot";
+
+/// Canonical unit for samples of a metric type
+///
+/// Fan speed is recorded as a duty cycle; providers that can only read RPM
+/// set that unit explicitly instead.
+pub fn unit_for(metric_type: &MetricType) -> &'static str {
+    match metric_type {
+        MetricType::CpuUtilization
+        | MetricType::CpuUtilizationPerCore
+        | MetricType::GpuUtilization
+        | MetricType::GpuVramUsagePercent
+        | MetricType::GpuMemoryControllerLoad
+        | MetricType::MemoryUsage
+        | MetricType::FanSpeed
+        | MetricType::DpcTime
+        | MetricType::InterruptTime
+        | MetricType::ProcessGpuUtilization
+        | MetricType::ProcessCpuUtilization
+        | MetricType::GpuAdapterUtilization => "percent",
+        MetricType::GpuVramUsage | MetricType::MemorySwapUsage | MetricType::MemoryUsed => "MB",
+        MetricType::GpuTemperature
+        | MetricType::GpuHotspotTemperature
+        | MetricType::Temperature
+        | MetricType::SensorTemperature => "Celsius",
+        MetricType::GpuClock | MetricType::CpuClock => "MHz",
+        MetricType::StorageReadThroughput
+        | MetricType::StorageWriteThroughput
+        | MetricType::MemoryReadThroughput
+        | MetricType::MemoryWriteThroughput
+        | MetricType::GpuMemoryTransfer
+        | MetricType::ProcessDiskWriteThroughput => "MB/s",
+        MetricType::StorageQueueDepth => "requests",
+        MetricType::PcieLinkGen | MetricType::PcieLinkGenMax => "generation",
+        MetricType::PcieLinkWidth | MetricType::PcieLinkWidthMax => "lanes",
+        MetricType::Fps => "fps",
+        MetricType::FrameTime | MetricType::RenderTime => "ms",
+        MetricType::Voltage => "volts",
+        MetricType::GpuPower => "watts",
+    }
 }
 
 /// Bottleneck analysis result
@@ -212,6 +445,22 @@ pub enum MetricType {
 pub struct BottleneckAnalysisResult {
     pub bottlenecks: Vec<Bottleneck>,
     pub timestamp: DateTime<Utc>,
+    /// How much of the requested window the analyzed samples covered
+    #[serde(default)]
+    pub coverage: Option<AnalysisCoverage>,
+}
+
+/// Time span of the data behind an analysis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisCoverage {
+    pub requested_seconds: i64,
+    /// Seconds between the first and last analyzed sample
+    pub covered_seconds: i64,
+    /// Set when the data covers much less than the requested window
+    pub warning: Option<String>,
+    /// Readings in the window that look wrong and should not be trusted
+    #[serde(default)]
+    pub data_warnings: Vec<String>,
 }
 
 /// Detected bottleneck
@@ -222,10 +471,19 @@ pub struct Bottleneck {
     pub evidence: Vec<EvidenceItem>,
     pub summary: String,
     pub details: String,
+    /// When the threshold first started being violated in a sustained way
+    #[serde(default)]
+    pub onset: Option<DateTime<Utc>>,
+    /// Share of the analyzed window (0-1) the threshold was violated in a
+    /// sustained way; 0 when it cannot be judged per sample
+    #[serde(default)]
+    pub active_fraction: f64,
 }
 
 /// Bottleneck type
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+///
+/// Ordered by declaration, which breaks severity ties when sorting bottlenecks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
 pub enum BottleneckType {
     Cpu,
@@ -235,6 +493,22 @@ pub enum BottleneckType {
     Storage,
     Thermal,
     Bandwidth,
+    Responsiveness,
+    /// Data loading and preprocessing cannot keep the GPU fed
+    #[serde(rename = "input_pipeline")]
+    InputPipeline,
+    /// Frame rate held at a ceiling by VSync or a frame limiter, not by hardware
+    #[serde(rename = "frame_cap")]
+    FrameCap,
+    /// Several subsystems near their thresholds at once, none over
+    #[serde(rename = "combined_load")]
+    CombinedLoad,
+    /// An updater or launcher downloading in the background, not the workload
+    #[serde(rename = "background_download")]
+    BackgroundDownload,
+    /// A game rendered on the integrated GPU while the discrete GPU idles
+    #[serde(rename = "hybrid_graphics")]
+    HybridGraphics,
 }
 
 /// Evidence item for bottleneck diagnosis
@@ -245,5 +519,8 @@ pub struct EvidenceItem {
     pub actual_value: f64,
     pub time_range_start: DateTime<Utc>,
     pub time_range_end: DateTime<Utc>,
+    /// Component or process the evidence is about, when narrower than the metric
+    #[serde(default)]
+    pub source_component: Option<String>,
 }
 
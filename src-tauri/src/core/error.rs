@@ -3,6 +3,8 @@
 //! This module defines all error types used throughout the application,
 //! following the error handling strategy outlined in AGENT.md Section 10.4.
 
+use crate::core::settings::SettingsError;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Hardware detection errors
@@ -23,6 +25,9 @@ pub enum HardwareError {
     #[error("Detection failed: {0}")]
     DetectionFailed(String),
     
+    #[error("Detection timed out: {0}")]
+    Timeout(String),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
@@ -45,6 +50,9 @@ pub enum MetricsError {
     #[error("Invalid metric value: {0}")]
     InvalidValue(String),
     
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
@@ -92,6 +100,25 @@ pub enum PersistenceError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
+    #[error("Operation cancelled: {0}")]
+    Cancelled(String),
+    
+    #[error("Privacy check failed: {0}")]
+    PrivacyLeak(String),
+    
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+/// Session manipulation errors
+#[derive(Error, Debug)]
+pub enum SessionError {
+    #[error("Run not found: {0}")]
+    RunNotFound(String),
+    
+    #[error("Invalid operation: {0}")]
+    InvalidOperation(String),
+    
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
@@ -111,9 +138,207 @@ pub enum AppError {
     #[error("Persistence error: {0}")]
     Persistence(#[from] PersistenceError),
     
+    #[error("Session error: {0}")]
+    Session(#[from] SessionError),
+    
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
+/// Error returned from Tauri commands
+///
+/// Serializes as `{ "kind": ..., "code": ..., "message": ... }` so the frontend
+/// can branch on the kind and code of a failure instead of parsing messages.
+#[derive(Error, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandError {
+    #[error("{message}")]
+    Hardware { code: String, message: String },
+    
+    #[error("{message}")]
+    Metrics { code: String, message: String },
+    
+    #[error("{message}")]
+    Analysis { code: String, message: String },
+    
+    #[error("{message}")]
+    Persistence { code: String, message: String },
+    
+    #[error("{message}")]
+    Session { code: String, message: String },
+    
+    #[error("{message}")]
+    Settings { code: String, message: String },
+    
+    #[error("{message}")]
+    Validation { code: String, message: String },
+    
+    #[error("{message}")]
+    Internal { code: String, message: String },
+}
+
+impl CommandError {
+    /// Create an error for invalid command input
+    pub fn validation(code: &str, message: impl Into<String>) -> Self {
+        Self::Validation { code: code.to_string(), message: message.into() }
+    }
+    
+    /// Machine-readable error code, e.g. `PERSISTENCE_DISK_FULL`
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Hardware { code, .. }
+            | Self::Metrics { code, .. }
+            | Self::Analysis { code, .. }
+            | Self::Persistence { code, .. }
+            | Self::Session { code, .. }
+            | Self::Settings { code, .. }
+            | Self::Validation { code, .. }
+            | Self::Internal { code, .. } => code,
+        }
+    }
+    
+    /// Human-readable error message
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Hardware { message, .. }
+            | Self::Metrics { message, .. }
+            | Self::Analysis { message, .. }
+            | Self::Persistence { message, .. }
+            | Self::Session { message, .. }
+            | Self::Settings { message, .. }
+            | Self::Validation { message, .. }
+            | Self::Internal { message, .. } => message,
+        }
+    }
+    
+    /// Error kind as serialized to the frontend
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Hardware { .. } => "hardware",
+            Self::Metrics { .. } => "metrics",
+            Self::Analysis { .. } => "analysis",
+            Self::Persistence { .. } => "persistence",
+            Self::Session { .. } => "session",
+            Self::Settings { .. } => "settings",
+            Self::Validation { .. } => "validation",
+            Self::Internal { .. } => "internal",
+        }
+    }
+}
+
+/// Error code suffix for an IO error
+fn io_error_code(error: &std::io::Error) -> &'static str {
+    // ENOSPC on Unix; ERROR_HANDLE_DISK_FULL and ERROR_DISK_FULL on Windows
+    #[cfg(windows)]
+    const DISK_FULL_CODES: &[i32] = &[39, 112];
+    #[cfg(not(windows))]
+    const DISK_FULL_CODES: &[i32] = &[28];
+    
+    if error.raw_os_error().is_some_and(|code| DISK_FULL_CODES.contains(&code)) {
+        return "DISK_FULL";
+    }
+    match error.kind() {
+        std::io::ErrorKind::NotFound => "NOT_FOUND",
+        std::io::ErrorKind::PermissionDenied => "PERMISSION_DENIED",
+        _ => "IO",
+    }
+}
+
+impl From<HardwareError> for CommandError {
+    fn from(error: HardwareError) -> Self {
+        let code = match &error {
+            HardwareError::PermissionDenied(_) => "HARDWARE_PERMISSION_DENIED".to_string(),
+            HardwareError::PlatformNotSupported(_) | HardwareError::UnsupportedPlatform(_) => {
+                "HARDWARE_UNSUPPORTED_PLATFORM".to_string()
+            }
+            HardwareError::SensorNotAvailable(_) => "HARDWARE_SENSOR_UNAVAILABLE".to_string(),
+            HardwareError::DetectionFailed(_) => "HARDWARE_DETECTION_FAILED".to_string(),
+            HardwareError::Timeout(_) => "HARDWARE_TIMEOUT".to_string(),
+            HardwareError::Io(e) => format!("HARDWARE_{}", io_error_code(e)),
+            HardwareError::Unknown(_) => "HARDWARE_UNKNOWN".to_string(),
+        };
+        Self::Hardware { code, message: error.to_string() }
+    }
+}
+
+impl From<MetricsError> for CommandError {
+    fn from(error: MetricsError) -> Self {
+        let code = match &error {
+            MetricsError::ProviderNotAvailable(_) => "METRICS_PROVIDER_UNAVAILABLE".to_string(),
+            MetricsError::SamplingFailed(_) => "METRICS_SAMPLING_FAILED".to_string(),
+            MetricsError::CollectionFailed(_) => "METRICS_COLLECTION_FAILED".to_string(),
+            MetricsError::InvalidValue(_) => "METRICS_INVALID_VALUE".to_string(),
+            MetricsError::Timeout(_) => "METRICS_TIMEOUT".to_string(),
+            MetricsError::Io(e) => format!("METRICS_{}", io_error_code(e)),
+            MetricsError::Unknown(_) => "METRICS_UNKNOWN".to_string(),
+        };
+        Self::Metrics { code, message: error.to_string() }
+    }
+}
+
+impl From<AnalysisError> for CommandError {
+    fn from(error: AnalysisError) -> Self {
+        let code = match &error {
+            AnalysisError::InsufficientData(_) => "ANALYSIS_INSUFFICIENT_DATA",
+            AnalysisError::InvalidConfiguration(_) => "ANALYSIS_INVALID_CONFIGURATION",
+            AnalysisError::AnalysisFailed(_) => "ANALYSIS_FAILED",
+            AnalysisError::Unknown(_) => "ANALYSIS_UNKNOWN",
+        };
+        Self::Analysis { code: code.to_string(), message: error.to_string() }
+    }
+}
+
+impl From<PersistenceError> for CommandError {
+    fn from(error: PersistenceError) -> Self {
+        let code = match &error {
+            PersistenceError::Serialization(_) => "PERSISTENCE_SERIALIZATION".to_string(),
+            PersistenceError::Deserialization(_) => "PERSISTENCE_DESERIALIZATION".to_string(),
+            PersistenceError::FileNotFound(_) => "PERSISTENCE_FILE_NOT_FOUND".to_string(),
+            PersistenceError::SchemaVersionMismatch { .. } => "PERSISTENCE_SCHEMA_MISMATCH".to_string(),
+            PersistenceError::MigrationFailed(_) => "PERSISTENCE_MIGRATION_FAILED".to_string(),
+            PersistenceError::Database(_) => "PERSISTENCE_DATABASE".to_string(),
+            PersistenceError::Io(e) => format!("PERSISTENCE_{}", io_error_code(e)),
+            PersistenceError::Cancelled(_) => "PERSISTENCE_CANCELLED".to_string(),
+            PersistenceError::PrivacyLeak(_) => "PERSISTENCE_PRIVACY_LEAK".to_string(),
+            PersistenceError::Unknown(_) => "PERSISTENCE_UNKNOWN".to_string(),
+        };
+        Self::Persistence { code, message: error.to_string() }
+    }
+}
+
+impl From<SessionError> for CommandError {
+    fn from(error: SessionError) -> Self {
+        let code = match &error {
+            SessionError::RunNotFound(_) => "SESSION_RUN_NOT_FOUND",
+            SessionError::InvalidOperation(_) => "SESSION_INVALID_OPERATION",
+            SessionError::Unknown(_) => "SESSION_UNKNOWN",
+        };
+        Self::Session { code: code.to_string(), message: error.to_string() }
+    }
+}
+
+impl From<SettingsError> for CommandError {
+    fn from(error: SettingsError) -> Self {
+        let code = match &error {
+            SettingsError::IoError(_) => "SETTINGS_IO",
+            SettingsError::SerializationError(_) => "SETTINGS_SERIALIZATION",
+            SettingsError::DeserializationError(_) => "SETTINGS_DESERIALIZATION",
+        };
+        Self::Settings { code: code.to_string(), message: error.to_string() }
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(error: AppError) -> Self {
+        match error {
+            AppError::Hardware(e) => e.into(),
+            AppError::Metrics(e) => e.into(),
+            AppError::Analysis(e) => e.into(),
+            AppError::Persistence(e) => e.into(),
+            AppError::Session(e) => e.into(),
+            AppError::Unknown(message) => Self::Internal { code: "UNKNOWN".to_string(), message },
+        }
+    }
+}
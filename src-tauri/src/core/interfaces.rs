@@ -37,6 +37,15 @@ pub trait CpuMetricsProvider: Send + Sync {
 pub trait GpuMetricsProvider: Send + Sync {
     /// Get current GPU metrics
     async fn get_gpu_metrics(&self) -> Result<crate::metrics::models::GpuMetrics, MetricsError>;
+    
+    /// Get the utilization of every GPU adapter, not just the primary one
+    ///
+    /// Providers that can only read the primary GPU return an empty list.
+    async fn get_adapter_utilization(
+        &self,
+    ) -> Result<Vec<crate::metrics::models::GpuAdapterUtilization>, MetricsError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Memory metrics provider trait
@@ -53,6 +62,17 @@ pub trait StorageMetricsProvider: Send + Sync {
     async fn get_storage_metrics(&self) -> Result<crate::metrics::models::StorageMetrics, MetricsError>;
 }
 
+/// Hardware sensor provider trait
+///
+/// Enumerates temperature, fan and voltage sensors from whatever source the
+/// platform offers. Platforms or systems without readable sensors return an
+/// empty list rather than an error.
+#[async_trait]
+pub trait SensorProvider: Send + Sync {
+    /// Read every available sensor once
+    async fn get_sensors(&self) -> Result<Vec<crate::metrics::models::SensorReading>, MetricsError>;
+}
+
 /// Workload KPI provider trait
 ///
 /// Provides workload-specific key performance indicators (FPS, render times, etc.)
@@ -2,5 +2,6 @@ pub mod domain;
 pub mod interfaces;
 pub mod error;
 pub mod profiles;
+pub mod sessions;
 pub mod settings;
 
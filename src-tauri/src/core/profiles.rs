@@ -3,7 +3,7 @@
 //! This module provides preset workload profiles and profile management
 //! following AGENT.md Section 3.5 and IMPLEMENTATION_PLAN.md Phase 2.1.
 
-use crate::core::domain::{WorkloadProfile, WorkloadType, ThresholdOverrides};
+use crate::core::domain::{WorkloadProfile, WorkloadType, ThresholdOverrides, ThresholdStatistic};
 use std::collections::HashMap;
 
 /// Preset workload profiles
@@ -41,6 +41,9 @@ impl WorkloadProfiles {
                 gpu_high: Some(90.0),
                 ram_high: Some(80.0),
                 vram_high: Some(85.0),
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         }
     }
@@ -62,6 +65,9 @@ impl WorkloadProfiles {
                 gpu_high: Some(95.0),
                 ram_high: Some(80.0),
                 vram_high: Some(90.0),
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         }
     }
@@ -83,6 +89,9 @@ impl WorkloadProfiles {
                 gpu_high: Some(98.0),
                 ram_high: Some(75.0),
                 vram_high: Some(95.0),
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         }
     }
@@ -104,6 +113,9 @@ impl WorkloadProfiles {
                 gpu_high: Some(85.0),
                 ram_high: Some(85.0),
                 vram_high: Some(80.0),
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         }
     }
@@ -124,6 +136,9 @@ impl WorkloadProfiles {
                 gpu_high: Some(95.0),
                 ram_high: Some(90.0),
                 vram_high: Some(90.0),
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         }
     }
@@ -145,6 +160,9 @@ impl WorkloadProfiles {
                 gpu_high: Some(85.0),
                 ram_high: Some(70.0),
                 vram_high: Some(85.0),
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         }
     }
@@ -166,6 +184,9 @@ impl WorkloadProfiles {
                 gpu_high: Some(90.0),
                 ram_high: Some(80.0),
                 vram_high: Some(95.0),
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         }
     }
@@ -182,6 +203,9 @@ impl WorkloadProfiles {
                 gpu_high: Some(50.0),
                 ram_high: Some(85.0),
                 vram_high: None,
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         }
     }
@@ -190,5 +214,52 @@ impl WorkloadProfiles {
     pub fn get_by_id(id: &str) -> Option<WorkloadProfile> {
         Self::get_presets().into_iter().find(|p| p.id == id)
     }
+
+    /// Get a preset or custom profile by ID, preferring presets
+    pub fn resolve(id: &str, custom: &[WorkloadProfile]) -> Option<WorkloadProfile> {
+        Self::get_by_id(id).or_else(|| custom.iter().find(|p| p.id == id).cloned())
+    }
+
+    /// Check that a user-defined profile can be stored next to the presets
+    ///
+    /// The ID and name must not be empty, the ID must not be taken by a
+    /// preset, and threshold overrides must be percentages.
+    pub fn validate_custom(profile: &WorkloadProfile) -> Result<(), String> {
+        if profile.id.trim().is_empty() || profile.name.trim().is_empty() {
+            return Err("Profile ID and name must not be empty".to_string());
+        }
+        if Self::get_by_id(&profile.id).is_some() {
+            return Err(format!("Profile ID '{}' is reserved for a preset profile", profile.id));
+        }
+        if let Some(overrides) = &profile.threshold_overrides {
+            let thresholds = [
+                ("CPU", overrides.cpu_high),
+                ("GPU", overrides.gpu_high),
+                ("RAM", overrides.ram_high),
+                ("VRAM", overrides.vram_high),
+            ];
+            for (name, threshold) in thresholds {
+                if let Some(value) = threshold.filter(|v| !(0.0..=100.0).contains(v)) {
+                    return Err(format!("{} threshold must be between 0 and 100, got {}", name, value));
+                }
+            }
+            match overrides.statistic {
+                ThresholdStatistic::Percentile(p) if !(0.0..=100.0).contains(&p) => {
+                    return Err(format!("Percentile must be between 0 and 100, got {}", p));
+                }
+                ThresholdStatistic::TrimmedMean(fraction) if !(0.0..=0.5).contains(&fraction) => {
+                    return Err(format!("Trim fraction must be between 0 and 0.5, got {}", fraction));
+                }
+                _ => {}
+            }
+            if let Some(share) = overrides.combined_pressure.filter(|s| !(*s > 0.0 && *s <= 1.0)) {
+                return Err(format!("Combined pressure share must be in (0, 1], got {}", share));
+            }
+            if let Some(seconds) = overrides.sustained_seconds.filter(|s| !(s.is_finite() && *s >= 0.0)) {
+                return Err(format!("Sustained duration must be zero or more seconds, got {}", seconds));
+            }
+        }
+        Ok(())
+    }
 }
 
@@ -0,0 +1,194 @@
+//! Session and run manipulation
+//!
+//! This module provides operations that restructure the runs of a session,
+//! such as combining a capture that was split across several runs.
+
+use crate::analysis::insights::{generate_acknowledged_insights, BottleneckAcknowledgment, UserFacingInsights};
+use crate::analysis::rules::RuleTrace;
+use crate::analysis::AnalysisEngine;
+use crate::core::domain::{BottleneckAnalysisResult, MetricSample, Run, Session, WorkloadProfile};
+use crate::core::error::SessionError;
+use crate::core::settings::AnalysisProfile;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Merge several runs of a session into a single run
+///
+/// Metric streams are concatenated in timestamp order and overlapping samples
+/// are de-duplicated. Notes are concatenated and the analysis is re-run on the
+/// merged data. The merged run replaces the originals at the position of the
+/// first of them.
+pub fn merge_runs(
+    session: &mut Session,
+    run_ids: &[Uuid],
+    merged_name: &str,
+) -> Result<Run, SessionError> {
+    let unique_ids: HashSet<Uuid> = run_ids.iter().copied().collect();
+    if unique_ids.len() < 2 {
+        return Err(SessionError::InvalidOperation(
+            "At least two distinct runs are required to merge".to_string(),
+        ));
+    }
+
+    // Every run must belong to this session
+    for run_id in &unique_ids {
+        if !session.runs.iter().any(|r| r.id == *run_id) {
+            return Err(SessionError::RunNotFound(format!(
+                "Run {} does not belong to session {}",
+                run_id, session.id
+            )));
+        }
+    }
+
+    let insert_at = session
+        .runs
+        .iter()
+        .position(|r| unique_ids.contains(&r.id))
+        .unwrap_or(0);
+    let (originals, remaining): (Vec<Run>, Vec<Run>) = session
+        .runs
+        .drain(..)
+        .partition(|r| unique_ids.contains(&r.id));
+
+    // Concatenate streams per key, in session order
+    let mut metrics_streams: HashMap<String, Vec<MetricSample>> = HashMap::new();
+    for run in &originals {
+        for (key, samples) in &run.metrics_streams {
+            metrics_streams
+                .entry(key.clone())
+                .or_default()
+                .extend(samples.iter().cloned());
+        }
+    }
+
+    for samples in metrics_streams.values_mut() {
+        // Overlapping captures record the same sample twice
+        let mut seen = HashSet::new();
+        samples.retain(|s| {
+            seen.insert((s.timestamp, s.metric_type.clone(), s.source_component.clone()))
+        });
+        samples.sort_by_key(|s| s.timestamp);
+    }
+
+    let notes: Vec<String> = originals
+        .iter()
+        .filter_map(|r| r.notes.clone())
+        .filter(|n| !n.trim().is_empty())
+        .collect();
+    let notes = if notes.is_empty() {
+        None
+    } else {
+        Some(notes.join("\n\n"))
+    };
+
+    let mut merged = Run {
+        id: Uuid::new_v4(),
+        name: merged_name.to_string(),
+        metrics_streams,
+        analysis_result: None,
+        notes,
+        // The captures belong to one sitting, so the first environment stands for all
+        environment: originals.iter().find_map(|r| r.environment.clone()),
+    };
+    // Use the hardware of the machine the runs were recorded on
+    let engine = AnalysisEngine::for_hardware(&session.hardware_config_snapshot);
+    merged.analysis_result = analyze_run(&engine, &merged, &session.profile, None);
+
+    session.runs = remaining;
+    session.runs.insert(insert_at, merged.clone());
+
+    Ok(merged)
+}
+
+/// Deep-copy a session and its runs under fresh IDs
+///
+/// Metric streams, analysis results, and notes are copied as they are.
+/// Sessions are listed under their profile's name, so the clone's profile
+/// is renamed to `new_name`.
+pub fn clone_session(session: &Session, new_name: &str) -> Session {
+    let mut clone = session.clone();
+    clone.id = Uuid::new_v4();
+    clone.profile.name = new_name.to_string();
+    for run in &mut clone.runs {
+        run.id = Uuid::new_v4();
+    }
+    clone
+}
+
+/// Regenerate the insights of a saved run from its stored analysis
+///
+/// Uses the session's workload profile and hardware snapshot, so changes to
+/// the insights engine reach old runs without reanalyzing them. A run that
+/// was never analyzed gets insights without recommendations.
+pub fn run_insights(
+    session: &Session,
+    run: &Run,
+    acknowledgments: &[BottleneckAcknowledgment],
+) -> UserFacingInsights {
+    match &run.analysis_result {
+        Some(result) => generate_acknowledged_insights(
+            result,
+            Some(&session.profile),
+            &[],
+            Some(&session.hardware_config_snapshot),
+            acknowledgments,
+        ),
+        None => UserFacingInsights {
+            summary: "This run has not been analyzed.".to_string(),
+            recommendations: Vec::new(),
+            severity: 0,
+            dismissed: Vec::new(),
+            foreground: None,
+        },
+    }
+}
+
+/// Analyze the recorded metrics of a run
+///
+/// The window ends at the run's newest sample. Without an explicit window the
+/// whole run is analyzed. Returns `None` if the run has no samples.
+pub fn analyze_run(
+    engine: &AnalysisEngine,
+    run: &Run,
+    profile: &WorkloadProfile,
+    time_window_seconds: Option<i64>,
+) -> Option<BottleneckAnalysisResult> {
+    let (samples, window_seconds, latest) = run_window(run, time_window_seconds)?;
+    Some(engine.analyze_at(&samples, Some(window_seconds), Some(profile), latest))
+}
+
+/// Explain every rule's decision for the recorded metrics of a run
+///
+/// Uses the same window as `analyze_run`. Returns `None` if the run has no samples.
+pub fn trace_run(
+    engine: &AnalysisEngine,
+    run: &Run,
+    profile: &WorkloadProfile,
+    time_window_seconds: Option<i64>,
+) -> Option<Vec<RuleTrace>> {
+    let (samples, window_seconds, latest) = run_window(run, time_window_seconds)?;
+    Some(engine.trace_at(&samples, Some(window_seconds), Some(profile), latest))
+}
+
+/// Samples of a run with the analysis window ending at the newest sample
+fn run_window(run: &Run, time_window_seconds: Option<i64>) -> Option<(Vec<MetricSample>, i64, DateTime<Utc>)> {
+    let samples: Vec<MetricSample> = run.metrics_streams.values().flatten().cloned().collect();
+    let earliest = samples.iter().map(|s| s.timestamp).min()?;
+    let latest = samples.iter().map(|s| s.timestamp).max()?;
+    let window_seconds = time_window_seconds.unwrap_or_else(|| (latest - earliest).num_seconds() + 1);
+    Some((samples, window_seconds, latest))
+}
+
+/// Analyze the recorded metrics of a run with a named analysis profile
+///
+/// The profile's window, statistic, and thresholds replace those of the
+/// session's workload profile. Returns `None` if the run has no samples.
+pub fn analyze_run_with_profile(
+    engine: &AnalysisEngine,
+    run: &Run,
+    profile: &WorkloadProfile,
+    analysis_profile: &AnalysisProfile,
+) -> Option<BottleneckAnalysisResult> {
+    analyze_run(engine, run, &analysis_profile.apply_to(profile), analysis_profile.window_seconds)
+}
@@ -3,7 +3,17 @@
 //! This module provides functionality for managing user settings and preferences
 //! following AGENT.md Section 3.5 and IMPLEMENTATION_PLAN.md Phase 3.3.
 
+use crate::analysis::alerts::{AlertConfig, DEFAULT_ALERT_COOLDOWN_SECONDS, DEFAULT_ALERT_MIN_SEVERITY};
+use crate::analysis::comparison::DEFAULT_MIN_EFFECT_PERCENT;
+use crate::analysis::insights::BottleneckAcknowledgment;
+use crate::core::domain::{BottleneckType, MetricSample, MetricType, ThresholdOverrides, WorkloadProfile};
+use crate::metrics::coalescer::DEFAULT_FLUSH_INTERVAL_MS;
+use crate::metrics::utils::mean;
+use crate::persistence::decimation::DecimationPolicy;
+use crate::persistence::retention::RetentionPolicy;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// User settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +23,19 @@ pub struct UserSettings {
     pub units: UnitPreferences,
     pub theme: ThemePreferences,
     pub advanced: AdvancedSettings,
+    #[serde(default)]
+    pub idle_baseline: Option<IdleBaseline>,
+    /// Named analysis profiles, in the order they were created
+    #[serde(default)]
+    pub analysis_profiles: Vec<AnalysisProfile>,
+    /// User-defined workload profiles, in the order they were created
+    #[serde(default)]
+    pub custom_profiles: Vec<WorkloadProfile>,
+    #[serde(default)]
+    pub alerts: AlertSettings,
+    /// Bottlenecks dismissed in insights, reports, and alerts; one per type
+    #[serde(default)]
+    pub acknowledgments: Vec<BottleneckAcknowledgment>,
 }
 
 /// Threshold settings
@@ -24,6 +47,16 @@ pub struct ThresholdSettings {
     pub vram_high: f64,
     pub temperature_warning: f64,
     pub temperature_critical: f64,
+    /// Smallest change, in percent, that run comparisons report as significant
+    #[serde(default = "default_min_comparison_effect_percent")]
+    pub min_comparison_effect_percent: f64,
+    /// Share of samples dropped from each end for trimmed means; 0 disables trimming
+    #[serde(default)]
+    pub trim_fraction: f64,
+}
+
+fn default_min_comparison_effect_percent() -> f64 {
+    DEFAULT_MIN_EFFECT_PERCENT
 }
 
 /// Sampling settings
@@ -31,6 +64,136 @@ pub struct ThresholdSettings {
 pub struct SamplingSettings {
     pub interval_ms: u64,
     pub buffer_size: usize,
+    /// What the collector does once the buffer reaches `buffer_size`
+    #[serde(default)]
+    pub overflow_policy: BufferOverflowPolicy,
+    /// How the sampling loop catches up after a stalled tick
+    #[serde(default)]
+    pub missed_tick_behavior: MissedTickBehavior,
+    /// Where GPU utilization is read from
+    #[serde(default)]
+    pub gpu_utilization_source: GpuUtilizationSource,
+    /// Milliseconds between batched live-metric events sent to the frontend
+    #[serde(default = "default_event_flush_interval_ms")]
+    pub event_flush_interval_ms: u64,
+    /// Readings below these values are dropped at collection time
+    #[serde(default)]
+    pub noise_floors: NoiseFloors,
+}
+
+fn default_event_flush_interval_ms() -> u64 {
+    DEFAULT_FLUSH_INTERVAL_MS
+}
+
+/// Per-metric noise floors applied by the collector
+///
+/// Idle GPUs and disks report tiny nonzero readings that drag averages down
+/// and can tip borderline detections. Samples below the floor are omitted,
+/// the same as a reading of zero.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NoiseFloors {
+    /// GPU utilization (percent)
+    #[serde(default = "default_gpu_utilization_floor")]
+    pub gpu_utilization: f64,
+    /// Storage read throughput (MB/s)
+    #[serde(default = "default_storage_throughput_floor")]
+    pub storage_read_throughput: f64,
+    /// Storage write throughput (MB/s)
+    #[serde(default = "default_storage_throughput_floor")]
+    pub storage_write_throughput: f64,
+}
+
+fn default_gpu_utilization_floor() -> f64 {
+    1.0
+}
+
+fn default_storage_throughput_floor() -> f64 {
+    0.1
+}
+
+impl Default for NoiseFloors {
+    fn default() -> Self {
+        Self {
+            gpu_utilization: default_gpu_utilization_floor(),
+            storage_read_throughput: default_storage_throughput_floor(),
+            storage_write_throughput: default_storage_throughput_floor(),
+        }
+    }
+}
+
+impl NoiseFloors {
+    /// Floor for a metric type, if one applies
+    pub fn floor_for(&self, metric_type: &MetricType) -> Option<f64> {
+        match metric_type {
+            MetricType::GpuUtilization => Some(self.gpu_utilization),
+            MetricType::StorageReadThroughput => Some(self.storage_read_throughput),
+            MetricType::StorageWriteThroughput => Some(self.storage_write_throughput),
+            _ => None,
+        }
+    }
+    
+    /// Drop samples below the floor for their metric type
+    pub fn suppress(&self, samples: &mut Vec<MetricSample>) {
+        samples.retain(|sample| match self.floor_for(&sample.metric_type) {
+            Some(floor) => sample.value >= floor,
+            None => true,
+        });
+    }
+}
+
+/// Behavior of the metrics buffer once it is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BufferOverflowPolicy {
+    /// Drop the oldest samples and report the eviction in collector health
+    #[default]
+    EvictOldest,
+    /// Keep growing the buffer until the run is saved
+    UnboundedUntilSaved,
+}
+
+/// How the sampling loop handles ticks missed while it was stalled
+///
+/// Rate metrics such as storage throughput are computed from the delta
+/// between consecutive samples, so uneven spacing skews them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedTickBehavior {
+    /// Fire all missed ticks back to back. Keeps the long-run sample count
+    /// aligned with wall-clock time, but the catch-up samples are only
+    /// microseconds apart and produce spikes in throughput deltas.
+    Burst,
+    /// Fire one tick immediately, then resume the full interval from there.
+    /// Spacing stays even at the cost of drifting away from the original
+    /// schedule after every stall.
+    #[default]
+    Delay,
+    /// Drop the missed ticks and wait for the next tick on the original
+    /// schedule. Keeps alignment without bursts, but the first interval
+    /// after a stall can be shorter than configured.
+    Skip,
+}
+
+/// Source of the GPU utilization metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuUtilizationSource {
+    /// Vendor tooling (nvidia-smi, amdgpu sysfs), falling back to WMI on Windows
+    #[default]
+    Driver,
+    /// Windows `GPU Engine` performance counters for the 3D engine, as shown
+    /// in Task Manager. Works for NVIDIA, AMD and Intel GPUs.
+    Engine3d,
+}
+
+impl From<MissedTickBehavior> for tokio::time::MissedTickBehavior {
+    fn from(behavior: MissedTickBehavior) -> Self {
+        match behavior {
+            MissedTickBehavior::Burst => tokio::time::MissedTickBehavior::Burst,
+            MissedTickBehavior::Delay => tokio::time::MissedTickBehavior::Delay,
+            MissedTickBehavior::Skip => tokio::time::MissedTickBehavior::Skip,
+        }
+    }
 }
 
 /// Unit preferences
@@ -38,6 +201,13 @@ pub struct SamplingSettings {
 pub struct UnitPreferences {
     pub temperature: TemperatureUnit,
     pub memory: MemoryUnit,
+    /// Timezone for displayed timestamps: "local" or an IANA name such as "Europe/Berlin"
+    #[serde(default = "default_display_timezone")]
+    pub display_timezone: String,
+}
+
+fn default_display_timezone() -> String {
+    "local".to_string()
 }
 
 /// Temperature unit
@@ -77,7 +247,202 @@ pub enum ThemeMode {
 pub struct AdvancedSettings {
     pub enable_debug_logging: bool,
     pub auto_save_sessions: bool,
+    /// `None` keeps sessions forever
     pub session_retention_days: Option<u32>,
+    /// Sessions kept by cleanup even when past retention
+    #[serde(default = "default_min_sessions_to_keep")]
+    pub min_sessions_to_keep: usize,
+    #[serde(default)]
+    pub auto_cleanup_enabled: bool,
+    /// Metric types reduced to fewer samples when a run is saved
+    #[serde(default)]
+    pub decimation: DecimationPolicy,
+}
+
+fn default_min_sessions_to_keep() -> usize {
+    RetentionPolicy::default().min_sessions_to_keep
+}
+
+impl AdvancedSettings {
+    /// Retention policy described by these settings
+    ///
+    /// Unlimited retention maps to a period no session can exceed.
+    pub fn retention_policy(&self) -> RetentionPolicy {
+        RetentionPolicy {
+            retention_days: self.session_retention_days.unwrap_or(u32::MAX),
+            auto_cleanup_enabled: self.auto_cleanup_enabled,
+            min_sessions_to_keep: self.min_sessions_to_keep,
+        }
+    }
+}
+
+/// Live bottleneck alert settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertSettings {
+    /// Raise `bottleneck-alert` events while metrics are collected
+    #[serde(default = "default_alerts_enabled")]
+    pub enabled: bool,
+    /// Lowest severity (0-100) that raises an alert
+    #[serde(default = "default_alert_min_severity")]
+    pub min_severity: u8,
+    /// Seconds before a condition that cleared may alert again
+    #[serde(default = "default_alert_cooldown_seconds")]
+    pub cooldown_seconds: u64,
+}
+
+fn default_alerts_enabled() -> bool {
+    true
+}
+
+fn default_alert_min_severity() -> u8 {
+    DEFAULT_ALERT_MIN_SEVERITY
+}
+
+fn default_alert_cooldown_seconds() -> u64 {
+    DEFAULT_ALERT_COOLDOWN_SECONDS
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_alerts_enabled(),
+            min_severity: default_alert_min_severity(),
+            cooldown_seconds: default_alert_cooldown_seconds(),
+        }
+    }
+}
+
+impl AlertSettings {
+    /// Watcher configuration described by these settings
+    pub fn alert_config(&self) -> AlertConfig {
+        AlertConfig {
+            min_severity: self.min_severity,
+            cooldown: chrono::Duration::seconds(self.cooldown_seconds.min(u32::MAX as u64) as i64),
+        }
+    }
+}
+
+/// Named combination of analysis window, statistic, and thresholds
+///
+/// Saving these settings under a name makes analyses reproducible, e.g. a
+/// "strict 60s p95" profile next to a "lenient 10s mean" one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisProfile {
+    pub id: String,
+    pub name: String,
+    /// Window ending at the newest sample; `None` analyzes the whole run
+    pub window_seconds: Option<i64>,
+    /// Thresholds and statistic; unset thresholds keep the workload profile's
+    pub thresholds: ThresholdOverrides,
+}
+
+impl AnalysisProfile {
+    /// Workload profile with this profile's thresholds and statistic applied
+    pub fn apply_to(&self, profile: &WorkloadProfile) -> WorkloadProfile {
+        let base = profile.threshold_overrides.as_ref();
+        let thresholds = ThresholdOverrides {
+            cpu_high: self.thresholds.cpu_high.or_else(|| base.and_then(|t| t.cpu_high)),
+            gpu_high: self.thresholds.gpu_high.or_else(|| base.and_then(|t| t.gpu_high)),
+            ram_high: self.thresholds.ram_high.or_else(|| base.and_then(|t| t.ram_high)),
+            vram_high: self.thresholds.vram_high.or_else(|| base.and_then(|t| t.vram_high)),
+            statistic: self.thresholds.statistic,
+            combined_pressure: self.thresholds.combined_pressure.or_else(|| base.and_then(|t| t.combined_pressure)),
+            sustained_seconds: self.thresholds.sustained_seconds.or_else(|| base.and_then(|t| t.sustained_seconds)),
+        };
+        
+        WorkloadProfile {
+            threshold_overrides: Some(thresholds),
+            ..profile.clone()
+        }
+    }
+}
+
+/// Idle baseline captured while the system is not running a workload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdleBaseline {
+    pub captured_at: DateTime<Utc>,
+    pub duration_seconds: u64,
+    /// Average idle value per metric type and source component
+    ///
+    /// Baselines saved before sources were recorded load empty and have to be
+    /// captured again.
+    #[serde(default)]
+    pub source_averages: Vec<BaselineAverage>,
+}
+
+/// Idle average of one metric from one source component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineAverage {
+    pub metric_type: MetricType,
+    pub source_component: String,
+    pub average: f64,
+}
+
+impl IdleBaseline {
+    /// Build a baseline from samples collected while idle
+    ///
+    /// Only utilization and throughput metrics are kept; capacity metrics such
+    /// as RAM or VRAM usage would hide memory pressure if subtracted, and
+    /// temperatures, clocks and frame timings are not meaningful to subtract.
+    /// Each source is averaged separately so two GPUs or cores idling at
+    /// different levels keep their own baseline.
+    pub fn from_samples(samples: &[MetricSample], duration_seconds: u64) -> Self {
+        let mut grouped: HashMap<(MetricType, String), Vec<f64>> = HashMap::new();
+        for sample in samples.iter().filter(|s| Self::is_baseline_metric(&s.metric_type)) {
+            grouped
+                .entry((sample.metric_type.clone(), sample.source_component.clone()))
+                .or_default()
+                .push(sample.value);
+        }
+        
+        let mut source_averages: Vec<BaselineAverage> = grouped
+            .into_iter()
+            .filter_map(|((metric_type, source_component), values)| {
+                Some(BaselineAverage {
+                    metric_type,
+                    source_component,
+                    average: mean(values)?,
+                })
+            })
+            .collect();
+        source_averages.sort_by(|a, b| {
+            format!("{:?}", a.metric_type)
+                .cmp(&format!("{:?}", b.metric_type))
+                .then_with(|| a.source_component.cmp(&b.source_component))
+        });
+        
+        Self {
+            captured_at: Utc::now(),
+            duration_seconds,
+            source_averages,
+        }
+    }
+    
+    /// Idle average recorded for a metric type from a specific source
+    pub fn average_for(&self, metric_type: &MetricType, source_component: &str) -> Option<f64> {
+        self.source_averages
+            .iter()
+            .find(|entry| &entry.metric_type == metric_type && entry.source_component == source_component)
+            .map(|entry| entry.average)
+    }
+    
+    /// Whether a metric type represents load that can be attributed to a workload
+    ///
+    /// Capacity metrics (memory, swap and VRAM usage, queue depth) are left out
+    /// because the idle footprint still occupies that capacity under load.
+    pub fn is_baseline_metric(metric_type: &MetricType) -> bool {
+        matches!(
+            metric_type,
+            MetricType::CpuUtilization
+                | MetricType::CpuUtilizationPerCore
+                | MetricType::GpuUtilization
+                | MetricType::StorageReadThroughput
+                | MetricType::StorageWriteThroughput
+                | MetricType::MemoryReadThroughput
+                | MetricType::MemoryWriteThroughput
+                | MetricType::GpuMemoryTransfer
+        )
+    }
 }
 
 impl Default for UserSettings {
@@ -88,6 +453,11 @@ impl Default for UserSettings {
             units: UnitPreferences::default(),
             theme: ThemePreferences::default(),
             advanced: AdvancedSettings::default(),
+            idle_baseline: None,
+            analysis_profiles: Vec::new(),
+            custom_profiles: Vec::new(),
+            alerts: AlertSettings::default(),
+            acknowledgments: Vec::new(),
         }
     }
 }
@@ -101,6 +471,8 @@ impl Default for ThresholdSettings {
             vram_high: 90.0,
             temperature_warning: 80.0,
             temperature_critical: 90.0,
+            min_comparison_effect_percent: DEFAULT_MIN_EFFECT_PERCENT,
+            trim_fraction: 0.0,
         }
     }
 }
@@ -110,6 +482,11 @@ impl Default for SamplingSettings {
         Self {
             interval_ms: 1000, // 1 second
             buffer_size: 3600,  // 1 hour at 1 sample/second
+            overflow_policy: BufferOverflowPolicy::EvictOldest,
+            missed_tick_behavior: MissedTickBehavior::Delay,
+            gpu_utilization_source: GpuUtilizationSource::Driver,
+            event_flush_interval_ms: DEFAULT_FLUSH_INTERVAL_MS,
+            noise_floors: NoiseFloors::default(),
         }
     }
 }
@@ -119,6 +496,7 @@ impl Default for UnitPreferences {
         Self {
             temperature: TemperatureUnit::Celsius,
             memory: MemoryUnit::GB,
+            display_timezone: default_display_timezone(),
         }
     }
 }
@@ -138,6 +516,9 @@ impl Default for AdvancedSettings {
             enable_debug_logging: false,
             auto_save_sessions: true,
             session_retention_days: Some(30),
+            min_sessions_to_keep: default_min_sessions_to_keep(),
+            auto_cleanup_enabled: false,
+            decimation: DecimationPolicy::default(),
         }
     }
 }
@@ -203,6 +584,92 @@ impl SettingsManager {
         Ok(())
     }
 
+    /// Update the session retention policy
+    pub fn update_retention_policy(&mut self, policy: &RetentionPolicy) -> Result<(), SettingsError> {
+        let advanced = &mut self.settings.advanced;
+        advanced.session_retention_days = Some(policy.retention_days);
+        advanced.min_sessions_to_keep = policy.min_sessions_to_keep;
+        advanced.auto_cleanup_enabled = policy.auto_cleanup_enabled;
+        self.save()?;
+        Ok(())
+    }
+
+    /// Update the stored idle baseline
+    pub fn update_idle_baseline(&mut self, baseline: Option<IdleBaseline>) -> Result<(), SettingsError> {
+        self.settings.idle_baseline = baseline;
+        self.save()?;
+        Ok(())
+    }
+
+    /// Add an analysis profile, replacing any profile with the same ID
+    pub fn save_analysis_profile(&mut self, profile: AnalysisProfile) -> Result<(), SettingsError> {
+        let profiles = &mut self.settings.analysis_profiles;
+        match profiles.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    /// Remove an analysis profile, returning whether it existed
+    pub fn delete_analysis_profile(&mut self, id: &str) -> Result<bool, SettingsError> {
+        let before = self.settings.analysis_profiles.len();
+        self.settings.analysis_profiles.retain(|p| p.id != id);
+        let removed = self.settings.analysis_profiles.len() != before;
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// Add a custom workload profile, replacing any profile with the same ID
+    pub fn save_custom_profile(&mut self, profile: WorkloadProfile) -> Result<(), SettingsError> {
+        let profiles = &mut self.settings.custom_profiles;
+        match profiles.iter_mut().find(|p| p.id == profile.id) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    /// Remove a custom workload profile, returning whether it existed
+    pub fn delete_custom_profile(&mut self, id: &str) -> Result<bool, SettingsError> {
+        let before = self.settings.custom_profiles.len();
+        self.settings.custom_profiles.retain(|p| p.id != id);
+        let removed = self.settings.custom_profiles.len() != before;
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// Acknowledge a bottleneck type, replacing its existing acknowledgment
+    pub fn acknowledge_bottleneck(&mut self, acknowledgment: BottleneckAcknowledgment) -> Result<(), SettingsError> {
+        let acknowledgments = &mut self.settings.acknowledgments;
+        match acknowledgments
+            .iter_mut()
+            .find(|a| a.bottleneck_type == acknowledgment.bottleneck_type)
+        {
+            Some(existing) => *existing = acknowledgment,
+            None => acknowledgments.push(acknowledgment),
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    /// Remove the acknowledgment of a bottleneck type, returning whether it existed
+    pub fn unacknowledge_bottleneck(&mut self, bottleneck_type: &BottleneckType) -> Result<bool, SettingsError> {
+        let before = self.settings.acknowledgments.len();
+        self.settings.acknowledgments.retain(|a| &a.bottleneck_type != bottleneck_type);
+        let removed = self.settings.acknowledgments.len() != before;
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
     /// Reset to default settings
     pub fn reset_to_defaults(&mut self) -> Result<(), SettingsError> {
         self.settings = UserSettings::default();
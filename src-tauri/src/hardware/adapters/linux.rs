@@ -4,16 +4,18 @@
 //! and other Linux-specific APIs.
 
 use crate::core::domain::{
-    CoolingInfo, CPUInfo, DetectionMetadata, DisplayInfo, GPUInfo, HardwareConfig, MemoryInfo,
+    CoolingInfo, CPUInfo, DisplayInfo, GPUInfo, HardwareConfig, MemoryInfo,
     MemoryModule, MotherboardInfo, PSUInfo, StorageInfo, StorageType,
 };
 use crate::core::error::HardwareError;
 use crate::core::interfaces::HardwareDetector;
+use crate::hardware::cpuid::physical_core_count;
+use crate::hardware::detection::{detect_components, ComponentDetector, DETECTION_STEP_TIMEOUT};
+use crate::hardware::display::parse_xrandr_displays;
 use async_trait::async_trait;
 use sysinfo::System;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use chrono::Utc;
 
 /// Linux hardware detector implementation
 pub struct LinuxHardwareDetector {
@@ -30,6 +32,13 @@ impl LinuxHardwareDetector {
             system: Arc::new(Mutex::new(system)),
         }
     }
+}
+
+#[async_trait]
+impl ComponentDetector for LinuxHardwareDetector {
+    fn platform(&self) -> &str {
+        "Linux"
+    }
     
     /// Detect CPU information
     async fn detect_cpu(&self) -> Result<CPUInfo, HardwareError> {
@@ -51,20 +60,20 @@ impl LinuxHardwareDetector {
             "Unknown".to_string()
         };
         
-        // Try to get frequency (may not be available on all systems)
-        let frequency = first_cpu.frequency();
-        let base_clock_mhz = if frequency > 0 {
-            Some(frequency as f64)
-        } else {
-            None
-        };
+        // The current frequency follows load and power saving, so the base
+        // clock comes from cpufreq: the rated base frequency where the driver
+        // exposes it (intel_pstate, amd-pstate), otherwise the maximum
+        let base_clock_mhz = read_cpufreq_mhz(0, "base_frequency")
+            .or_else(|| read_cpufreq_mhz(0, "cpuinfo_max_freq"));
         
         // Try to read CPU info from /proc/cpuinfo for more details
         let architecture = Self::detect_architecture().await;
         
-        // Count physical cores (approximation: divide by 2 if hyperthreading)
-        let cores = cpu_count as u32;
+        // Physical cores from /proc/cpuinfo, falling back to CPUID
         let threads = cpu_count as u32;
+        let cores = physical_core_count(system.physical_core_count(), threads);
+        
+        let core_max_frequencies_mhz = Self::detect_core_max_frequencies(cpu_count);
         
         Ok(CPUInfo {
             model,
@@ -74,6 +83,7 @@ impl LinuxHardwareDetector {
             threads,
             base_clock_mhz,
             boost_clock_mhz: None, // Would require additional parsing
+            core_max_frequencies_mhz,
         })
     }
     
@@ -123,6 +133,8 @@ impl LinuxHardwareDetector {
                 capacity_mb: total_gb * 1024, // Convert GB to MB
                 storage_type,
                 interface: None, // Would require additional parsing
+                // sysinfo reads available space with statvfs
+                free_mb: Some(disk.available_space() / 1024 / 1024),
             });
         }
         
@@ -190,20 +202,34 @@ impl LinuxHardwareDetector {
         Ok(gpus)
     }
     
-    /// Detect display information
+    /// Detect display information from xrandr
+    ///
+    /// Wayland sessions answer through XWayland when it is running. Without
+    /// xrandr no displays are reported.
     async fn detect_displays(&self) -> Result<Vec<DisplayInfo>, HardwareError> {
-        // Linux display detection would use:
-        // - xrandr (X11)
-        // - wayland-info (Wayland)
-        // - /sys/class/drm/card*-*/modes
-        
-        // Placeholder implementation
-        Ok(vec![DisplayInfo {
-            name: "Display".to_string(),
-            resolution: None,
-            refresh_rate_hz: None,
-            is_primary: true,
-        }])
+        let output = tokio::process::Command::new("xrandr")
+            .arg("--query")
+            .kill_on_drop(true)
+            .output()
+            .await;
+        match output {
+            Ok(output) if output.status.success() => {
+                Ok(parse_xrandr_displays(&String::from_utf8_lossy(&output.stdout)))
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+impl LinuxHardwareDetector {
+    /// Read the maximum frequency of each logical core from cpufreq
+    ///
+    /// Returns an empty list unless every core reports a value.
+    fn detect_core_max_frequencies(cpu_count: usize) -> Vec<f64> {
+        (0..cpu_count)
+            .map(|idx| read_cpufreq_mhz(idx, "cpuinfo_max_freq"))
+            .collect::<Option<Vec<f64>>>()
+            .unwrap_or_default()
     }
     
     /// Detect architecture
@@ -269,37 +295,18 @@ impl LinuxHardwareDetector {
 #[async_trait]
 impl HardwareDetector for LinuxHardwareDetector {
     async fn get_hardware_config(&self) -> Result<HardwareConfig, HardwareError> {
-        let cpu_info = self.detect_cpu().await?;
-        let memory_info = self.detect_memory().await?;
-        let storage_devices = self.detect_storage().await?;
-        let gpus = self.detect_gpus().await?;
-        let displays = self.detect_displays().await?;
+        let mut config = detect_components(self, DETECTION_STEP_TIMEOUT).await?;
         
         // Collect warnings for unavailable features
-        let mut warnings = Vec::new();
-        if gpus.iter().any(|g| g.vram_total_mb.is_none()) {
+        let warnings = &mut config.metadata.warnings;
+        if config.gpus.iter().any(|g| g.vram_total_mb.is_none()) {
             warnings.push("GPU VRAM detection requires additional tools (nvidia-smi, rocm-smi)".to_string());
         }
-        if memory_info.channels.is_none() || memory_info.speed_mhz.is_none() {
+        if config.memory.channels.is_none() || config.memory.speed_mhz.is_none() {
             warnings.push("Memory channel and speed detection requires dmidecode".to_string());
         }
         
-        Ok(HardwareConfig {
-            cpu: cpu_info,
-            gpus,
-            memory: memory_info,
-            storage_devices,
-            motherboard: None, // Would require dmidecode
-            psu: None,          // Not easily detectable on Linux
-            cooling: None,      // Would require sensors or lm-sensors
-            displays,
-            metadata: DetectionMetadata {
-                detection_time: Utc::now(),
-                platform: "Linux".to_string(),
-                warnings,
-                schema_version: 1,
-            },
-        })
+        Ok(config)
     }
     
     async fn refresh(&self) -> Result<HardwareConfig, HardwareError> {
@@ -310,3 +317,13 @@ impl HardwareDetector for LinuxHardwareDetector {
         self.get_hardware_config().await
     }
 }
+
+/// Read a cpufreq frequency of one CPU, reported in kHz, as MHz
+fn read_cpufreq_mhz(cpu: usize, file: &str) -> Option<f64> {
+    let path = format!("/sys/devices/system/cpu/cpu{}/cpufreq/{}", cpu, file);
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|khz| *khz > 0.0)
+        .map(|khz| khz / 1000.0)
+}
@@ -4,16 +4,18 @@
 //! and System Profiler.
 
 use crate::core::domain::{
-    CoolingInfo, CPUInfo, DetectionMetadata, DisplayInfo, GPUInfo, HardwareConfig, MemoryInfo,
+    CoolingInfo, CPUInfo, DisplayInfo, GPUInfo, HardwareConfig, MemoryInfo,
     MemoryModule, MotherboardInfo, PSUInfo, StorageInfo, StorageType,
 };
 use crate::core::error::HardwareError;
 use crate::core::interfaces::HardwareDetector;
+use crate::hardware::cpuid::physical_core_count;
+use crate::hardware::detection::{detect_components, ComponentDetector, DETECTION_STEP_TIMEOUT};
+use crate::hardware::display::parse_system_profiler_displays;
 use async_trait::async_trait;
 use sysinfo::System;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use chrono::Utc;
 
 /// macOS hardware detector implementation
 pub struct MacOSHardwareDetector {
@@ -30,6 +32,13 @@ impl MacOSHardwareDetector {
             system: Arc::new(Mutex::new(system)),
         }
     }
+}
+
+#[async_trait]
+impl ComponentDetector for MacOSHardwareDetector {
+    fn platform(&self) -> &str {
+        "macOS"
+    }
     
     /// Detect CPU information
     async fn detect_cpu(&self) -> Result<CPUInfo, HardwareError> {
@@ -60,9 +69,9 @@ impl MacOSHardwareDetector {
         // Detect architecture
         let architecture = Self::detect_architecture().await;
         
-        // Count cores
-        let cores = cpu_count as u32;
+        // Physical cores from sysctl, falling back to CPUID on Intel Macs
         let threads = cpu_count as u32;
+        let cores = physical_core_count(system.physical_core_count(), threads);
         
         Ok(CPUInfo {
             model,
@@ -72,6 +81,7 @@ impl MacOSHardwareDetector {
             threads,
             base_clock_mhz,
             boost_clock_mhz: None, // Would require additional parsing
+            core_max_frequencies_mhz: vec![],
         })
     }
     
@@ -121,6 +131,7 @@ impl MacOSHardwareDetector {
                 capacity_mb: total_gb * 1024, // Convert GB to MB
                 storage_type,
                 interface: None,
+                free_mb: Some(disk.available_space() / 1024 / 1024),
             });
         }
         
@@ -166,21 +177,23 @@ impl MacOSHardwareDetector {
         Ok(gpus)
     }
     
-    /// Detect display information
+    /// Detect display information from system_profiler
     async fn detect_displays(&self) -> Result<Vec<DisplayInfo>, HardwareError> {
-        // macOS display detection would use:
-        // - system_profiler SPDisplaysDataType
-        // - CoreGraphics APIs
-        
-        // Placeholder implementation
-        Ok(vec![DisplayInfo {
-            name: "Display".to_string(),
-            resolution: None,
-            refresh_rate_hz: None,
-            is_primary: true,
-        }])
+        let output = tokio::process::Command::new("system_profiler")
+            .arg("SPDisplaysDataType")
+            .kill_on_drop(true)
+            .output()
+            .await;
+        match output {
+            Ok(output) if output.status.success() => {
+                Ok(parse_system_profiler_displays(&String::from_utf8_lossy(&output.stdout)))
+            }
+            _ => Ok(Vec::new()),
+        }
     }
-    
+}
+
+impl MacOSHardwareDetector {
     /// Detect architecture
     async fn detect_architecture() -> String {
         std::env::consts::ARCH.to_string()
@@ -205,37 +218,18 @@ impl MacOSHardwareDetector {
 #[async_trait]
 impl HardwareDetector for MacOSHardwareDetector {
     async fn get_hardware_config(&self) -> Result<HardwareConfig, HardwareError> {
-        let cpu_info = self.detect_cpu().await?;
-        let memory_info = self.detect_memory().await?;
-        let storage_devices = self.detect_storage().await?;
-        let gpus = self.detect_gpus().await?;
-        let displays = self.detect_displays().await?;
+        let mut config = detect_components(self, DETECTION_STEP_TIMEOUT).await?;
         
         // Collect warnings for unavailable features
-        let mut warnings = Vec::new();
-        if gpus.iter().any(|g| g.vram_total_mb.is_none()) {
+        let warnings = &mut config.metadata.warnings;
+        if config.gpus.iter().any(|g| g.vram_total_mb.is_none()) {
             warnings.push("GPU VRAM detection requires system_profiler or IOKit".to_string());
         }
-        if memory_info.channels.is_none() || memory_info.speed_mhz.is_none() {
+        if config.memory.channels.is_none() || config.memory.speed_mhz.is_none() {
             warnings.push("Memory channel and speed detection requires system_profiler".to_string());
         }
         
-        Ok(HardwareConfig {
-            cpu: cpu_info,
-            gpus,
-            memory: memory_info,
-            storage_devices,
-            motherboard: None, // Would require system_profiler
-            psu: None,          // Not applicable for Macs
-            cooling: None,      // Would require IOKit or sensors
-            displays,
-            metadata: DetectionMetadata {
-                detection_time: Utc::now(),
-                platform: "macOS".to_string(),
-                warnings,
-                schema_version: 1,
-            },
-        })
+        Ok(config)
     }
     
     async fn refresh(&self) -> Result<HardwareConfig, HardwareError> {
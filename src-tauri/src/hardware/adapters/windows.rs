@@ -3,11 +3,14 @@
 //! This module implements HardwareDetector for Windows using sysinfo and Windows APIs.
 
 use crate::core::domain::{
-    CoolingInfo, CPUInfo, DetectionMetadata, DisplayInfo, GPUInfo, HardwareConfig, MemoryInfo,
+    CPUInfo, DisplayInfo, GPUInfo, HardwareConfig, MemoryInfo,
     MemoryModule, MotherboardInfo, PSUInfo, StorageInfo, StorageType,
 };
 use crate::core::error::HardwareError;
 use crate::core::interfaces::HardwareDetector;
+use crate::hardware::cpuid::physical_core_count;
+use crate::hardware::detection::{detect_components, ComponentDetector, DETECTION_STEP_TIMEOUT};
+use crate::hardware::memory::parse_physical_memory;
 use async_trait::async_trait;
 use sysinfo::System;
 use std::sync::Arc;
@@ -31,6 +34,13 @@ impl WindowsHardwareDetector {
             system: Arc::new(Mutex::new(system)),
         }
     }
+}
+
+#[async_trait]
+impl ComponentDetector for WindowsHardwareDetector {
+    fn platform(&self) -> &str {
+        "windows"
+    }
     
     /// Detect CPU information
     async fn detect_cpu(&self) -> Result<CPUInfo, HardwareError> {
@@ -58,11 +68,10 @@ impl WindowsHardwareDetector {
             None
         };
         
-        // Threads = logical cores, cores = physical cores (approximation)
+        // Threads = logical cores; physical cores from the OS, falling back to CPUID
         let threads = cpu_count as u32;
-        // On Windows, we can't easily get physical core count from sysinfo alone
-        // Use threads as approximation, or assume 1:1 if hyperthreading not detected
-        let cores = threads; // Conservative estimate
+        let cores = physical_core_count(system.physical_core_count(), threads);
+        let core_max_frequencies_mhz = Self::query_core_max_frequencies(cpu_count);
         
         Ok(CPUInfo {
             model,
@@ -72,11 +81,210 @@ impl WindowsHardwareDetector {
             base_clock_mhz: base_clock,
             boost_clock_mhz: None, // sysinfo doesn't provide boost clock
             architecture: Some("x86_64".to_string()), // Windows typically x86_64
+            core_max_frequencies_mhz,
         })
     }
     
-    /// Detect GPU information using direct WMI COM interface (no process spawning)
+    /// Detect GPU information via WMI
+    ///
+    /// WMI queries block and can hang on broken systems, so they run on a
+    /// blocking thread where the detection timeout can abandon them.
     async fn detect_gpus(&self) -> Result<Vec<GPUInfo>, HardwareError> {
+        tokio::task::spawn_blocking(Self::query_gpus)
+            .await
+            .map_err(|e| HardwareError::DetectionFailed(format!("GPU detection task failed: {}", e)))?
+    }
+    
+    /// Detect memory information
+    ///
+    /// The installed modules come from WMI on a blocking thread. When WMI
+    /// is unavailable no modules are reported, which leaves the channel
+    /// layout unknown rather than guessing it.
+    async fn detect_memory(&self) -> Result<MemoryInfo, HardwareError> {
+        let total_mb = {
+            let system = self.system.lock().await;
+            system.total_memory() / (1024 * 1024) // sysinfo returns bytes, convert to MB
+        };
+        
+        let modules = tokio::task::spawn_blocking(Self::query_memory_modules)
+            .await
+            .map_err(|e| HardwareError::DetectionFailed(format!("Memory detection task failed: {}", e)))?;
+        let speed_mhz = modules.iter().filter_map(|module| module.speed_mhz).min();
+        
+        Ok(MemoryInfo {
+            total_mb,
+            channels: None, // Not reported by Win32_PhysicalMemory
+            speed_mhz,
+            modules,
+        })
+    }
+    
+    /// Detect storage devices via WMI on a blocking thread
+    async fn detect_storage(&self) -> Result<Vec<StorageInfo>, HardwareError> {
+        tokio::task::spawn_blocking(Self::query_storage)
+            .await
+            .map_err(|e| HardwareError::DetectionFailed(format!("Storage detection task failed: {}", e)))?
+    }
+    
+    /// Detect motherboard information
+    async fn detect_motherboard(&self) -> Result<Option<MotherboardInfo>, HardwareError> {
+        // sysinfo doesn't provide motherboard information
+        // For MVP, return None - can be enhanced with WMI in later phases
+        Ok(None)
+    }
+    
+    /// Detect PSU information
+    async fn detect_psu(&self) -> Result<Option<PSUInfo>, HardwareError> {
+        // PSU information is typically not detectable via standard APIs
+        // Return None for MVP - user can enter manually if needed
+        Ok(None)
+    }
+    
+    /// Detect the displays attached to the desktop and their current modes
+    async fn detect_displays(&self) -> Result<Vec<DisplayInfo>, HardwareError> {
+        tokio::task::spawn_blocking(Self::query_displays)
+            .await
+            .map_err(|e| HardwareError::DetectionFailed(format!("Display detection task failed: {}", e)))
+    }
+}
+
+impl WindowsHardwareDetector {
+    /// Maximum frequency of each logical processor from the power manager
+    ///
+    /// sysinfo does not expose per-core limits. Returns an empty list when the
+    /// call fails or reports no frequencies.
+    fn query_core_max_frequencies(cpu_count: usize) -> Vec<f64> {
+        use windows::Win32::System::Power::{CallNtPowerInformation, ProcessorInformation};
+        
+        /// Entry written per logical processor for `ProcessorInformation`
+        #[repr(C)]
+        #[derive(Clone, Copy, Default)]
+        struct ProcessorPowerInformation {
+            number: u32,
+            max_mhz: u32,
+            current_mhz: u32,
+            mhz_limit: u32,
+            max_idle_state: u32,
+            current_idle_state: u32,
+        }
+        
+        let mut entries = vec![ProcessorPowerInformation::default(); cpu_count];
+        let length = std::mem::size_of_val(entries.as_slice()) as u32;
+        // SAFETY: the output buffer holds one entry per logical processor and
+        // its length is passed alongside it
+        let status = unsafe {
+            CallNtPowerInformation(
+                ProcessorInformation,
+                None,
+                0,
+                Some(entries.as_mut_ptr() as *mut std::ffi::c_void),
+                length,
+            )
+        };
+        if status.is_err() {
+            log::debug!("Per-core frequency query failed: {:?}", status);
+            return Vec::new();
+        }
+        
+        if entries.iter().all(|entry| entry.max_mhz == 0) {
+            return Vec::new();
+        }
+        entries.iter().map(|entry| entry.max_mhz as f64).collect()
+    }
+    
+    /// Enumerate display outputs with EnumDisplayDevices and read each one's
+    /// current mode with EnumDisplaySettings
+    fn query_displays() -> Vec<DisplayInfo> {
+        use windows::core::PCWSTR;
+        use windows::Win32::Graphics::Gdi::{
+            EnumDisplayDevicesW, EnumDisplaySettingsW, DEVMODEW, DISPLAY_DEVICEW, ENUM_CURRENT_SETTINGS,
+        };
+        
+        /// `StateFlags` bit of outputs that are part of the desktop
+        const DISPLAY_DEVICE_ATTACHED_TO_DESKTOP: u32 = 0x1;
+        
+        fn wide_to_string(wide: &[u16]) -> String {
+            let len = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
+            String::from_utf16_lossy(&wide[..len])
+        }
+        
+        let mut displays = Vec::new();
+        let mut index = 0;
+        loop {
+            let mut adapter = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+            // SAFETY: the structure is sized through `cb` and only written by the call
+            if !unsafe { EnumDisplayDevicesW(PCWSTR::null(), index, &mut adapter, 0) }.as_bool() {
+                break;
+            }
+            index += 1;
+            if adapter.StateFlags & DISPLAY_DEVICE_ATTACHED_TO_DESKTOP == 0 {
+                continue;
+            }
+            
+            let mut mode = DEVMODEW {
+                dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+                ..Default::default()
+            };
+            // SAFETY: the device name is NUL-terminated and the mode is sized through `dmSize`
+            let has_mode = unsafe {
+                EnumDisplaySettingsW(PCWSTR(adapter.DeviceName.as_ptr()), ENUM_CURRENT_SETTINGS, &mut mode)
+            }
+            .as_bool();
+            if !has_mode {
+                continue;
+            }
+            
+            // Frequencies of 0 and 1 stand for the hardware default
+            let refresh_rate_hz = Some(mode.dmDisplayFrequency).filter(|&hz| hz > 1);
+            
+            // The monitor attached to this output names the display
+            let mut monitor = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+            // SAFETY: as above
+            let name = if unsafe { EnumDisplayDevicesW(PCWSTR(adapter.DeviceName.as_ptr()), 0, &mut monitor, 0) }.as_bool() {
+                wide_to_string(&monitor.DeviceString)
+            } else {
+                wide_to_string(&adapter.DeviceName)
+            };
+            
+            displays.push(DisplayInfo {
+                name,
+                resolution_width: mode.dmPelsWidth,
+                resolution_height: mode.dmPelsHeight,
+                refresh_rate_hz,
+                gpu_attachment: Some(wide_to_string(&adapter.DeviceString)),
+            });
+        }
+        displays
+    }
+    
+    /// Query GPU information using direct WMI COM interface (no process spawning)
+    /// Query the installed memory modules from `Win32_PhysicalMemory`
+    fn query_memory_modules() -> Vec<MemoryModule> {
+        let wmi_con = match WMIConnection::new() {
+            Ok(con) => con,
+            Err(e) => {
+                log::error!("Failed to connect to WMI for memory detection: {}", e);
+                return Vec::new();
+            }
+        };
+        
+        let query = "SELECT Capacity, Speed, ConfiguredClockSpeed, Manufacturer FROM Win32_PhysicalMemory";
+        match wmi_con.raw_query::<serde_json::Value>(query) {
+            Ok(rows) => parse_physical_memory(&rows),
+            Err(e) => {
+                log::warn!("WMI memory module query failed: {}", e);
+                Vec::new()
+            }
+        }
+    }
+    
+    fn query_gpus() -> Result<Vec<GPUInfo>, HardwareError> {
         let mut gpus = Vec::new();
         
         // Use WMI COM interface directly - no process spawning, no windows
@@ -184,30 +392,8 @@ impl WindowsHardwareDetector {
         Ok(gpus)
     }
     
-    /// Detect memory information
-    async fn detect_memory(&self) -> Result<MemoryInfo, HardwareError> {
-        let system = self.system.lock().await;
-        
-        let total_mb = system.total_memory() / (1024 * 1024); // sysinfo returns bytes, convert to MB
-        
-        // sysinfo doesn't provide detailed memory module information
-        // For MVP, we'll use basic info
-        let modules = vec![MemoryModule {
-            size_mb: total_mb,
-            speed_mhz: None,
-            manufacturer: None,
-        }];
-        
-        Ok(MemoryInfo {
-            total_mb,
-            channels: None, // Not available from sysinfo
-            speed_mhz: None, // Not available from sysinfo
-            modules,
-        })
-    }
-    
-    /// Detect storage devices using direct WMI COM interface (no process spawning)
-    async fn detect_storage(&self) -> Result<Vec<StorageInfo>, HardwareError> {
+    /// Query storage devices using direct WMI COM interface (no process spawning)
+    fn query_storage() -> Result<Vec<StorageInfo>, HardwareError> {
         let mut storage_devices = Vec::new();
         
         // Use WMI COM interface directly - no process spawning, no windows
@@ -221,7 +407,7 @@ impl WindowsHardwareDetector {
         
         // Query Win32_DiskDrive using WMI
         // Note: Property names in WMI are case-sensitive
-        let query = "SELECT Model, Size, InterfaceType, MediaType FROM Win32_DiskDrive";
+        let query = "SELECT DeviceID, Model, Size, InterfaceType, MediaType FROM Win32_DiskDrive";
         let results: Result<Vec<serde_json::Value>, _> = wmi_con.raw_query(query);
         
         match results {
@@ -289,14 +475,20 @@ impl WindowsHardwareDetector {
                         StorageType::Unknown
                     };
                     
-                    log::info!("Detected storage: {} ({}), Capacity: {} MB, Type: {:?}", 
-                        model, interface.as_ref().unwrap_or(&"Unknown".to_string()), capacity_mb, storage_type);
+                    let free_mb = drive.get("DeviceID")
+                        .or_else(|| drive.get("deviceID"))
+                        .and_then(|v| v.as_str())
+                        .and_then(|device_id| Self::query_free_space_mb(&wmi_con, device_id));
+                    
+                    log::info!("Detected storage: {} ({}), Capacity: {} MB, Free: {:?} MB, Type: {:?}", 
+                        model, interface.as_ref().unwrap_or(&"Unknown".to_string()), capacity_mb, free_mb, storage_type);
                     
                     storage_devices.push(StorageInfo {
                         model,
                         capacity_mb,
                         storage_type,
                         interface,
+                        free_mb,
                     });
                 }
             }
@@ -312,6 +504,51 @@ impl WindowsHardwareDetector {
         Ok(storage_devices)
     }
     
+    /// Free space of the volumes on a physical drive, from Win32_LogicalDisk
+    ///
+    /// Follows the drive's partitions to their logical disks. Returns `None`
+    /// when the drive has no volumes with a drive letter or WMI fails.
+    fn query_free_space_mb(wmi_con: &WMIConnection, device_id: &str) -> Option<u64> {
+        // Backslashes in object paths must be escaped in WQL
+        let escaped = device_id.replace('\\', "\\\\");
+        let partitions_query = format!(
+            "ASSOCIATORS OF {{Win32_DiskDrive.DeviceID='{}'}} WHERE AssocClass = Win32_DiskDriveToDiskPartition",
+            escaped
+        );
+        let partitions: Vec<serde_json::Value> = wmi_con.raw_query(&partitions_query)
+            .map_err(|e| log::debug!("Partition query for {} failed: {}", device_id, e))
+            .ok()?;
+        
+        let mut free_bytes = None;
+        for partition in partitions {
+            let Some(partition_id) = partition.get("DeviceID").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let volumes_query = format!(
+                "ASSOCIATORS OF {{Win32_DiskPartition.DeviceID='{}'}} WHERE AssocClass = Win32_LogicalDiskToPartition",
+                partition_id
+            );
+            let volumes: Vec<serde_json::Value> = match wmi_con.raw_query(&volumes_query) {
+                Ok(volumes) => volumes,
+                Err(e) => {
+                    log::debug!("Volume query for {} failed: {}", partition_id, e);
+                    continue;
+                }
+            };
+            for volume in volumes {
+                // FreeSpace is a uint64, which WMI may return as a string
+                let free = volume.get("FreeSpace").and_then(|v| {
+                    v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse::<u64>().ok()))
+                });
+                if let Some(free) = free {
+                    free_bytes = Some(free_bytes.unwrap_or(0) + free);
+                }
+            }
+        }
+        
+        free_bytes.map(|bytes| bytes / (1024 * 1024))
+    }
+    
     /// Fallback storage detection using sysinfo
     async fn detect_storage_sysinfo() -> Result<Vec<StorageInfo>, HardwareError> {
         // sysinfo 0.30 on Windows doesn't provide disks() method directly
@@ -319,34 +556,6 @@ impl WindowsHardwareDetector {
         // WMI should work in most cases
         Ok(Vec::new())
     }
-    
-    /// Detect motherboard information
-    async fn detect_motherboard(&self) -> Result<Option<MotherboardInfo>, HardwareError> {
-        // sysinfo doesn't provide motherboard information
-        // For MVP, return None - can be enhanced with WMI in later phases
-        Ok(None)
-    }
-    
-    /// Detect PSU information
-    async fn detect_psu(&self) -> Result<Option<PSUInfo>, HardwareError> {
-        // PSU information is typically not detectable via standard APIs
-        // Return None for MVP - user can enter manually if needed
-        Ok(None)
-    }
-    
-    /// Detect cooling information
-    async fn detect_cooling(&self) -> Result<Option<CoolingInfo>, HardwareError> {
-        // Cooling information requires specialized sensors
-        // Return None for MVP - can be enhanced in later phases
-        Ok(None)
-    }
-    
-    /// Detect display information
-    async fn detect_displays(&self) -> Result<Vec<DisplayInfo>, HardwareError> {
-        // sysinfo doesn't provide display information
-        // For MVP, return empty vector - can be enhanced with Windows APIs
-        Ok(Vec::new())
-    }
 }
 
 #[async_trait]
@@ -358,69 +567,19 @@ impl HardwareDetector for WindowsHardwareDetector {
             system.refresh_all();
         }
         
-        // Detect all components - allow partial failures
-        let cpu = self.detect_cpu().await?; // CPU detection must succeed
-        let memory = self.detect_memory().await?; // Memory detection must succeed
-        
-        // GPU and storage detection can fail gracefully
-        let gpus = self.detect_gpus().await.unwrap_or_else(|e| {
-            log::warn!("GPU detection failed: {}, continuing with empty GPU list", e);
-            Vec::new()
-        });
-        
-        let storage_devices = self.detect_storage().await.unwrap_or_else(|e| {
-            log::warn!("Storage detection failed: {}, continuing with empty storage list", e);
-            Vec::new()
-        });
-        
-        // Optional components
-        let motherboard = self.detect_motherboard().await.unwrap_or_else(|e| {
-            log::warn!("Motherboard detection failed: {}", e);
-            None
-        });
-        
-        let psu = self.detect_psu().await.unwrap_or_else(|e| {
-            log::warn!("PSU detection failed: {}", e);
-            None
-        });
-        
-        let cooling = self.detect_cooling().await.unwrap_or_else(|e| {
-            log::warn!("Cooling detection failed: {}", e);
-            None
-        });
-        
-        let displays = self.detect_displays().await.unwrap_or_else(|e| {
-            log::warn!("Display detection failed: {}", e);
-            Vec::new()
-        });
+        // Detect all components concurrently - allow partial failures and hung WMI queries
+        let mut config = detect_components(self, DETECTION_STEP_TIMEOUT).await?;
         
         // Collect warnings for missing components
-        let mut warnings = Vec::new();
-        if gpus.is_empty() {
+        let warnings = &mut config.metadata.warnings;
+        if config.gpus.is_empty() {
             warnings.push("No GPUs detected. GPU detection may have failed.".to_string());
         }
-        if storage_devices.is_empty() {
+        if config.storage_devices.is_empty() {
             warnings.push("No storage devices detected. Storage detection may have failed.".to_string());
         }
         
-        let metadata = DetectionMetadata {
-            detection_time: chrono::Utc::now(),
-            platform: "windows".to_string(),
-            warnings,
-            schema_version: 1,
-        };
-        
-        Ok(HardwareConfig {
-            cpu,
-            gpus,
-            memory,
-            storage_devices,
-            motherboard,
-            psu,
-            cooling,
-            displays,
-            metadata,
-        })
+        Ok(config)
     }
     
     async fn refresh(&self) -> Result<HardwareConfig, HardwareError> {
@@ -0,0 +1,148 @@
+//! Hardware change detection
+//!
+//! This module compares hardware configurations captured at different times
+//! so that swapped or upgraded components can be reported to the user.
+
+use crate::core::domain::HardwareConfig;
+use serde::{Deserialize, Serialize};
+
+/// Hardware component category affected by a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HardwareComponent {
+    Cpu,
+    Gpu,
+    Memory,
+    Storage,
+}
+
+/// A single difference between two hardware configurations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HardwareChange {
+    pub component: HardwareComponent,
+    /// Previous value, `None` when the component was added
+    pub previous: Option<String>,
+    /// Current value, `None` when the component was removed
+    pub current: Option<String>,
+    /// Human-readable description of the change
+    pub description: String,
+}
+
+/// Detect hardware changes between a previous and the current configuration
+///
+/// Compares CPU, GPUs, memory and storage devices. An empty result means
+/// the configurations are equivalent for comparison purposes.
+pub fn detect_hardware_change(prev: &HardwareConfig, cur: &HardwareConfig) -> Vec<HardwareChange> {
+    let mut changes = Vec::new();
+    
+    if prev.cpu.model != cur.cpu.model {
+        changes.push(HardwareChange {
+            component: HardwareComponent::Cpu,
+            previous: Some(prev.cpu.model.clone()),
+            current: Some(cur.cpu.model.clone()),
+            description: format!("CPU changed from {} to {}", prev.cpu.model, cur.cpu.model),
+        });
+    }
+    
+    // A removed GPU paired with an added one is reported as a swap
+    let (removed_gpus, added_gpus) = diff_models(
+        prev.gpus.iter().map(|g| g.model.as_str()),
+        cur.gpus.iter().map(|g| g.model.as_str()),
+    );
+    let swapped = removed_gpus.len().min(added_gpus.len());
+    for (old, new) in removed_gpus.iter().zip(added_gpus.iter()) {
+        changes.push(HardwareChange {
+            component: HardwareComponent::Gpu,
+            previous: Some(old.clone()),
+            current: Some(new.clone()),
+            description: format!("GPU changed from {} to {}", old, new),
+        });
+    }
+    for old in &removed_gpus[swapped..] {
+        changes.push(removed(HardwareComponent::Gpu, "GPU", old));
+    }
+    for new in &added_gpus[swapped..] {
+        changes.push(added(HardwareComponent::Gpu, "GPU", new));
+    }
+    
+    if prev.memory.total_mb != cur.memory.total_mb {
+        let previous = format_capacity(prev.memory.total_mb);
+        let current = format_capacity(cur.memory.total_mb);
+        let direction = if cur.memory.total_mb > prev.memory.total_mb {
+            "increased"
+        } else {
+            "decreased"
+        };
+        changes.push(HardwareChange {
+            component: HardwareComponent::Memory,
+            description: format!("RAM capacity {} from {} to {}", direction, previous, current),
+            previous: Some(previous),
+            current: Some(current),
+        });
+    }
+    if let (Some(prev_speed), Some(cur_speed)) = (prev.memory.speed_mhz, cur.memory.speed_mhz) {
+        if prev_speed != cur_speed {
+            changes.push(HardwareChange {
+                component: HardwareComponent::Memory,
+                previous: Some(format!("{} MHz", prev_speed)),
+                current: Some(format!("{} MHz", cur_speed)),
+                description: format!("RAM speed changed from {} MHz to {} MHz", prev_speed, cur_speed),
+            });
+        }
+    }
+    
+    let (removed_drives, added_drives) = diff_models(
+        prev.storage_devices.iter().map(|s| s.model.as_str()),
+        cur.storage_devices.iter().map(|s| s.model.as_str()),
+    );
+    for old in &removed_drives {
+        changes.push(removed(HardwareComponent::Storage, "Storage device", old));
+    }
+    for new in &added_drives {
+        changes.push(added(HardwareComponent::Storage, "Storage device", new));
+    }
+    
+    changes
+}
+
+/// Split two model lists into (removed, added), treating duplicates as distinct devices
+fn diff_models<'a>(
+    prev: impl Iterator<Item = &'a str>,
+    cur: impl Iterator<Item = &'a str>,
+) -> (Vec<String>, Vec<String>) {
+    let mut added: Vec<String> = cur.map(String::from).collect();
+    let mut removed = Vec::new();
+    
+    for model in prev {
+        match added.iter().position(|m| m == model) {
+            Some(idx) => {
+                added.remove(idx);
+            }
+            None => removed.push(model.to_string()),
+        }
+    }
+    
+    (removed, added)
+}
+
+fn added(component: HardwareComponent, label: &str, model: &str) -> HardwareChange {
+    HardwareChange {
+        component,
+        previous: None,
+        current: Some(model.to_string()),
+        description: format!("{} added: {}", label, model),
+    }
+}
+
+fn removed(component: HardwareComponent, label: &str, model: &str) -> HardwareChange {
+    HardwareChange {
+        component,
+        previous: Some(model.to_string()),
+        current: None,
+        description: format!("{} removed: {}", label, model),
+    }
+}
+
+fn format_capacity(total_mb: u64) -> String {
+    format!("{:.1} GB", total_mb as f64 / 1024.0)
+}
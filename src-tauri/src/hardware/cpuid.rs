@@ -0,0 +1,76 @@
+//! CPU topology from the CPUID instruction
+//!
+//! The adapters prefer the operating system's physical core count (WMI,
+//! `/proc/cpuinfo` or `sysctl`, as exposed by sysinfo). When that is
+//! unavailable, the SMT width reported by CPUID turns the logical processor
+//! count into a physical core count. CPUID describes the core it runs on, so
+//! hybrid CPUs, whose performance and efficiency cores differ in SMT width,
+//! are not derived this way.
+
+/// Physical core count, preferring the operating system's value
+///
+/// Falls back to CPUID and finally to `threads`, i.e. assumes no SMT.
+pub fn physical_core_count(os_cores: Option<usize>, threads: u32) -> u32 {
+    os_cores
+        .map(|cores| cores as u32)
+        .filter(|&cores| cores > 0 && cores <= threads)
+        .or_else(|| cpuid_core_count(threads))
+        .unwrap_or(threads)
+}
+
+/// Physical core count derived from CPUID for `threads` logical processors
+///
+/// `None` on non-x86 CPUs, hybrid CPUs, and when the SMT width does not
+/// divide the logical processor count, e.g. in a VM exposing an odd number
+/// of vCPUs.
+pub fn cpuid_core_count(threads: u32) -> Option<u32> {
+    let threads_per_core = cpuid_threads_per_core()?;
+    if threads == 0 || threads % threads_per_core != 0 {
+        return None;
+    }
+    Some(threads / threads_per_core)
+}
+
+/// Logical processors per physical core according to CPUID
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn cpuid_threads_per_core() -> Option<u32> {
+    use raw_cpuid::{CpuId, TopologyType};
+
+    let cpuid = CpuId::new();
+
+    // Hybrid CPU (leaf 7, EDX bit 15): the SMT width differs between core types.
+    // raw-cpuid has no accessor for the flag, so the leaf is read directly.
+    if cpuid.get_extended_feature_info().is_some() && raw_cpuid::cpuid!(7, 0).edx & (1 << 15) != 0 {
+        return None;
+    }
+
+    // Extended topology: the SMT level counts the logical processors per core
+    if let Some(mut levels) = cpuid.get_extended_topology_info() {
+        let smt = levels.find(|level| level.level_type() == TopologyType::SMT);
+        if let Some(logical) = smt.map(|level| level.processors() as u32).filter(|&logical| logical > 0) {
+            return Some(logical);
+        }
+    }
+
+    // AMD topology extensions report threads per compute unit
+    let has_topology_extensions = cpuid
+        .get_extended_processor_and_feature_identifiers()
+        .is_some_and(|features| features.has_topology_extensions());
+    if has_topology_extensions {
+        if let Some(topology) = cpuid.get_processor_topology_info() {
+            return Some(topology.threads_per_core() as u32);
+        }
+    }
+
+    // Without the HTT flag there is one logical processor per core
+    if cpuid.get_feature_info().is_some_and(|features| !features.has_htt()) {
+        return Some(1);
+    }
+    None
+}
+
+/// Logical processors per physical core according to CPUID
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn cpuid_threads_per_core() -> Option<u32> {
+    None
+}
@@ -0,0 +1,144 @@
+//! Concurrent hardware detection with per-step timeouts
+//!
+//! Platform adapters expose their detection sub-steps through
+//! `ComponentDetector`, and `detect_components` runs the steps concurrently,
+//! each under its own time limit. A step that hangs (e.g. a WMI query on a
+//! broken system) is abandoned with a warning instead of freezing detection,
+//! and the remaining components are still reported.
+
+use crate::core::domain::{
+    CoolingInfo, CPUInfo, DetectionMetadata, DisplayInfo, GPUInfo, HardwareConfig, MemoryInfo,
+    MotherboardInfo, PSUInfo, StorageInfo,
+};
+use crate::core::error::HardwareError;
+use crate::core::interfaces::SensorProvider;
+use crate::metrics::providers::sensors::{cooling_info_from_sensors, PlatformSensorProvider};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::future::Future;
+use std::time::Duration;
+
+/// Time limit for a single detection sub-step
+pub const DETECTION_STEP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Individual hardware detection sub-steps of a platform adapter
+///
+/// CPU and memory detection are required; every other step may fail or time
+/// out without failing detection as a whole.
+#[async_trait]
+pub trait ComponentDetector: Send + Sync {
+    /// Platform name recorded in the detection metadata
+    fn platform(&self) -> &str;
+
+    async fn detect_cpu(&self) -> Result<CPUInfo, HardwareError>;
+
+    async fn detect_memory(&self) -> Result<MemoryInfo, HardwareError>;
+
+    async fn detect_gpus(&self) -> Result<Vec<GPUInfo>, HardwareError>;
+
+    async fn detect_storage(&self) -> Result<Vec<StorageInfo>, HardwareError>;
+
+    async fn detect_displays(&self) -> Result<Vec<DisplayInfo>, HardwareError>;
+
+    async fn detect_motherboard(&self) -> Result<Option<MotherboardInfo>, HardwareError> {
+        Ok(None)
+    }
+
+    async fn detect_psu(&self) -> Result<Option<PSUInfo>, HardwareError> {
+        Ok(None)
+    }
+
+    /// Infer cooling from the fan sensors the platform exposes
+    async fn detect_cooling(&self) -> Result<Option<CoolingInfo>, HardwareError> {
+        let readings = PlatformSensorProvider::new()
+            .get_sensors()
+            .await
+            .map_err(|e| HardwareError::DetectionFailed(e.to_string()))?;
+        Ok(cooling_info_from_sensors(&readings))
+    }
+}
+
+/// Run a detection sub-step, failing with `HardwareError::Timeout` when it
+/// does not finish within `limit`
+///
+/// Only the await is abandoned: work moved to a blocking thread keeps running
+/// in the background until it returns.
+pub async fn with_timeout<T, F>(step: &str, limit: Duration, future: F) -> Result<T, HardwareError>
+where
+    F: Future<Output = Result<T, HardwareError>>,
+{
+    match tokio::time::timeout(limit, future).await {
+        Ok(result) => result,
+        Err(_) => Err(HardwareError::Timeout(format!(
+            "{} detection did not finish within {} ms",
+            step,
+            limit.as_millis()
+        ))),
+    }
+}
+
+/// Unwrap an optional step's result, recording a warning when it failed
+fn or_warn<T>(step: &str, result: Result<T, HardwareError>, fallback: T, warnings: &mut Vec<String>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(HardwareError::Timeout(message)) => {
+            log::warn!("{}", message);
+            warnings.push(format!("{} detection timed out; results may be incomplete.", step));
+            fallback
+        }
+        Err(e) => {
+            log::warn!("{} detection failed: {}", step, e);
+            warnings.push(format!("{} detection failed: {}", step, e));
+            fallback
+        }
+    }
+}
+
+/// Detect all hardware components concurrently
+///
+/// Each sub-step gets `step_timeout`. Fails only when CPU or memory detection
+/// fails or times out; other components fall back to empty results with a
+/// warning in the detection metadata.
+pub async fn detect_components(
+    detector: &dyn ComponentDetector,
+    step_timeout: Duration,
+) -> Result<HardwareConfig, HardwareError> {
+    let (cpu, memory, gpus, storage_devices, displays, motherboard, psu, cooling) = tokio::join!(
+        with_timeout("CPU", step_timeout, detector.detect_cpu()),
+        with_timeout("Memory", step_timeout, detector.detect_memory()),
+        with_timeout("GPU", step_timeout, detector.detect_gpus()),
+        with_timeout("Storage", step_timeout, detector.detect_storage()),
+        with_timeout("Display", step_timeout, detector.detect_displays()),
+        with_timeout("Motherboard", step_timeout, detector.detect_motherboard()),
+        with_timeout("PSU", step_timeout, detector.detect_psu()),
+        with_timeout("Cooling", step_timeout, detector.detect_cooling()),
+    );
+    
+    let cpu = cpu?;
+    let memory = memory?;
+    
+    let mut warnings = Vec::new();
+    let gpus = or_warn("GPU", gpus, Vec::new(), &mut warnings);
+    let storage_devices = or_warn("Storage", storage_devices, Vec::new(), &mut warnings);
+    let displays = or_warn("Display", displays, Vec::new(), &mut warnings);
+    let motherboard = or_warn("Motherboard", motherboard, None, &mut warnings);
+    let psu = or_warn("PSU", psu, None, &mut warnings);
+    let cooling = or_warn("Cooling", cooling, None, &mut warnings);
+    
+    Ok(HardwareConfig {
+        cpu,
+        gpus,
+        memory,
+        storage_devices,
+        motherboard,
+        psu,
+        cooling,
+        displays,
+        metadata: DetectionMetadata {
+            detection_time: Utc::now(),
+            platform: detector.platform().to_string(),
+            warnings,
+            schema_version: 1,
+        },
+    })
+}
@@ -0,0 +1,154 @@
+//! Display mode parsing
+//!
+//! Linux and macOS report connected displays through command-line tools.
+//! These parsers turn their output into `DisplayInfo`, most importantly the
+//! current refresh rate the frame cap rule compares frame rates against.
+
+use crate::core::domain::DisplayInfo;
+
+/// Parse `xrandr --query` output into the connected displays
+///
+/// Each connected output is followed by its modes; the current mode's rate
+/// is marked with `*`. Outputs that are connected but switched off report
+/// no resolution or refresh rate.
+pub fn parse_xrandr_displays(output: &str) -> Vec<DisplayInfo> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    let mut in_connected_output = false;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            let mut tokens = line.split_whitespace();
+            let name = tokens.next().unwrap_or_default();
+            in_connected_output = tokens.next() == Some("connected");
+            if in_connected_output {
+                // e.g. "DP-0 connected primary 2560x1440+0+0 (normal ...)"
+                let (resolution_width, resolution_height) = line
+                    .split_whitespace()
+                    .find_map(|token| parse_resolution(token.split('+').next()?))
+                    .unwrap_or((0, 0));
+                displays.push(DisplayInfo {
+                    name: name.to_string(),
+                    resolution_width,
+                    resolution_height,
+                    refresh_rate_hz: None,
+                    gpu_attachment: None,
+                });
+            }
+            continue;
+        }
+
+        if !in_connected_output {
+            continue;
+        }
+        let display = match displays.last_mut() {
+            Some(display) => display,
+            None => continue,
+        };
+        // e.g. "   2560x1440     59.95 +  143.97*"
+        let mut tokens = line.split_whitespace();
+        let mode = tokens.next().and_then(parse_resolution);
+        let current = tokens.find(|token| token.contains('*'));
+        if let (Some((width, height)), Some(rate)) = (mode, current) {
+            display.resolution_width = width;
+            display.resolution_height = height;
+            display.refresh_rate_hz = parse_refresh_rate(rate.trim_end_matches(['*', '+']));
+        }
+    }
+
+    displays
+}
+
+/// Parse `system_profiler SPDisplaysDataType` output into the connected displays
+///
+/// Displays are listed under each GPU's `Displays:` section. The refresh
+/// rate follows the resolution, or the scaled "UI Looks like" mode on
+/// Retina displays, as `@ 120.00Hz`.
+pub fn parse_system_profiler_displays(output: &str) -> Vec<DisplayInfo> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    let mut gpu: Option<String> = None;
+    let mut displays_indent: Option<usize> = None;
+    let mut display_indent: Option<usize> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some(section) = displays_indent {
+            if indent <= section {
+                displays_indent = None;
+                display_indent = None;
+            }
+        }
+
+        if let Some(model) = trimmed.strip_prefix("Chipset Model:") {
+            gpu = Some(model.trim().to_string());
+            continue;
+        }
+        if trimmed == "Displays:" {
+            displays_indent = Some(indent);
+            continue;
+        }
+        if displays_indent.is_none() {
+            continue;
+        }
+
+        // Display names are the headers one level into the section
+        if trimmed.ends_with(':') && !matches!(display_indent, Some(level) if indent > level) {
+            display_indent = Some(indent);
+            displays.push(DisplayInfo {
+                name: trimmed.trim_end_matches(':').to_string(),
+                resolution_width: 0,
+                resolution_height: 0,
+                refresh_rate_hz: None,
+                gpu_attachment: gpu.clone(),
+            });
+            continue;
+        }
+
+        let display = match displays.last_mut() {
+            Some(display) => display,
+            None => continue,
+        };
+        let (key, value) = match trimmed.split_once(':') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        if key != "Resolution" && key != "UI Looks like" {
+            continue;
+        }
+        let (mode, rate) = match value.split_once('@') {
+            Some((mode, rate)) => (mode, Some(rate)),
+            None => (value, None),
+        };
+        if key == "Resolution" {
+            let numbers: Vec<u32> = mode.split_whitespace().filter_map(|token| token.parse().ok()).collect();
+            if let [width, height, ..] = numbers[..] {
+                display.resolution_width = width;
+                display.resolution_height = height;
+            }
+        }
+        if let Some(hz) = rate.and_then(|rate| parse_refresh_rate(rate.trim().trim_end_matches("Hz"))) {
+            display.refresh_rate_hz = Some(hz);
+        }
+    }
+
+    displays
+}
+
+/// Parse a `WIDTHxHEIGHT` mode
+fn parse_resolution(token: &str) -> Option<(u32, u32)> {
+    let (width, height) = token.split_once('x')?;
+    Some((width.parse().ok()?, height.trim_end_matches('i').parse().ok()?))
+}
+
+/// Round a refresh rate such as `143.97` to whole hertz
+fn parse_refresh_rate(rate: &str) -> Option<u32> {
+    rate.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|hz| hz.is_finite() && *hz > 0.0)
+        .map(|hz| hz.round() as u32)
+}
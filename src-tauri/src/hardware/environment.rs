@@ -0,0 +1,160 @@
+//! Run environment capture
+//!
+//! This module records the system state a run was captured under: OS build,
+//! GPU drivers, power plan, background load and virtualization. Two runs
+//! only compare like for like when these match.
+
+use crate::core::domain::{HardwareConfig, RunEnvironment};
+use sysinfo::System;
+
+/// Vendor and product strings reported by common hypervisors
+const VIRTUAL_MACHINE_VENDORS: &[&str] = &[
+    "vmware",
+    "virtualbox",
+    "innotek",
+    "qemu",
+    "kvm",
+    "xen",
+    "parallels",
+    "bochs",
+    "hyper-v",
+    "virtual machine",
+];
+
+/// Capture the current run environment
+///
+/// Driver versions come from the hardware snapshot the run belongs to; the
+/// remaining fields are read from the running system. The foreground process
+/// is left to the caller, as focus at save time is on this application.
+pub async fn capture_run_environment(hardware: &HardwareConfig) -> RunEnvironment {
+    let mut system = System::new();
+    system.refresh_processes();
+
+    RunEnvironment {
+        os_version: System::long_os_version(),
+        gpu_driver_versions: gpu_driver_versions(hardware),
+        power_plan: detect_power_plan().await,
+        background_process_count: Some(system.processes().len()),
+        virtual_machine: detect_virtual_machine(hardware),
+        foreground_process: None,
+    }
+}
+
+/// Label each GPU driver version with the GPU model
+pub fn gpu_driver_versions(hardware: &HardwareConfig) -> Vec<String> {
+    hardware
+        .gpus
+        .iter()
+        .filter_map(|gpu| {
+            gpu.driver_version
+                .as_ref()
+                .map(|version| format!("{} {}", gpu.model, version))
+        })
+        .collect()
+}
+
+/// Extract the scheme name from `powercfg /getactivescheme` output
+///
+/// The output looks like `Power Scheme GUID: 381b...  (Balanced)`.
+pub fn parse_power_scheme(output: &str) -> Option<String> {
+    let start = output.rfind('(')?;
+    let end = output[start..].find(')')? + start;
+    let name = output[start + 1..end].trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Whether a firmware vendor or product string names a hypervisor
+pub fn is_virtual_machine_vendor(value: &str) -> bool {
+    let value = value.to_lowercase();
+    VIRTUAL_MACHINE_VENDORS.iter().any(|vendor| value.contains(vendor))
+}
+
+/// Detect virtualization from firmware strings and the CPU hypervisor flag
+///
+/// Returns `None` when no source could be read.
+fn detect_virtual_machine(hardware: &HardwareConfig) -> Option<bool> {
+    let hypervisor_flag = read_hypervisor_flag();
+    if hypervisor_flag == Some(true) {
+        return Some(true);
+    }
+
+    let mut sources: Vec<String> = read_firmware_strings();
+    if let Some(board) = &hardware.motherboard {
+        sources.push(format!("{} {}", board.manufacturer, board.model));
+    }
+
+    if sources.is_empty() && hypervisor_flag.is_none() {
+        return None;
+    }
+    Some(sources.iter().any(|source| is_virtual_machine_vendor(source)))
+}
+
+/// Read the system vendor and product name from DMI
+#[cfg(target_os = "linux")]
+fn read_firmware_strings() -> Vec<String> {
+    ["/sys/class/dmi/id/sys_vendor", "/sys/class/dmi/id/product_name"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|value| value.trim().to_string())
+        .collect()
+}
+
+/// Check the `hypervisor` CPU flag set by every major hypervisor
+#[cfg(target_os = "linux")]
+fn read_hypervisor_flag() -> Option<bool> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    Some(
+        cpuinfo
+            .lines()
+            .filter(|line| line.starts_with("flags"))
+            .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor")),
+    )
+}
+
+/// Firmware strings come from the motherboard snapshot on this platform
+#[cfg(not(target_os = "linux"))]
+fn read_firmware_strings() -> Vec<String> {
+    Vec::new()
+}
+
+/// The CPU flags are not readable on this platform
+#[cfg(not(target_os = "linux"))]
+fn read_hypervisor_flag() -> Option<bool> {
+    None
+}
+
+/// Read the active power plan
+#[cfg(target_os = "windows")]
+async fn detect_power_plan() -> Option<String> {
+    // Use CREATE_NO_WINDOW flag to prevent console window from appearing
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let mut cmd = tokio::process::Command::new("powercfg");
+    cmd.arg("/getactivescheme").creation_flags(CREATE_NO_WINDOW);
+
+    let output = cmd.output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_power_scheme(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Read the ACPI platform profile, falling back to the CPU frequency governor
+#[cfg(target_os = "linux")]
+async fn detect_power_plan() -> Option<String> {
+    [
+        "/sys/firmware/acpi/platform_profile",
+        "/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor",
+    ]
+    .iter()
+    .filter_map(|path| std::fs::read_to_string(path).ok())
+    .map(|value| value.trim().to_string())
+    .find(|value| !value.is_empty())
+}
+
+/// Power plans are not exposed on this platform
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+async fn detect_power_plan() -> Option<String> {
+    None
+}
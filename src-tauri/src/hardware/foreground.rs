@@ -0,0 +1,91 @@
+//! Foreground application detection
+//!
+//! For gaming and rendering the load of the focused application matters more
+//! than the load of the whole system. The process owning the focused window
+//! is read with `GetForegroundWindow` on Windows and `xdotool` on X11 Linux;
+//! other platforms, Wayland sessions and missing tools report no foreground
+//! process.
+
+use crate::core::domain::ForegroundProcess;
+use sysinfo::{Pid, System};
+
+/// Detect the process owning the focused window
+///
+/// Returns `None` when the window cannot be read or belongs to this
+/// application, which has focus while the user starts a run. Blocks while
+/// the platform is queried, so call this off the async runtime.
+pub fn detect_foreground_process() -> Option<ForegroundProcess> {
+    let pid = foreground_pid()?;
+    if pid == std::process::id() {
+        return None;
+    }
+
+    let mut system = System::new();
+    system.refresh_processes();
+    Some(ForegroundProcess {
+        pid,
+        name: system.process(Pid::from_u32(pid)).map(|p| p.name().to_string()),
+    })
+}
+
+/// Parse a PID printed on its own line by a platform tool
+pub fn parse_pid(output: &str) -> Option<u32> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?
+        .parse::<u32>()
+        .ok()
+        .filter(|&pid| pid != 0)
+}
+
+#[cfg(target_os = "windows")]
+fn foreground_pid() -> Option<u32> {
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    // SAFETY: both calls only read window state; the PID is written to a local
+    let pid = unsafe {
+        let window = GetForegroundWindow();
+        if window.0 == 0 {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(window, Some(&mut pid as *mut u32));
+        pid
+    };
+    (pid != 0).then_some(pid)
+}
+
+#[cfg(target_os = "linux")]
+fn foreground_pid() -> Option<u32> {
+    // Wayland compositors do not expose the focused window to clients
+    std::env::var_os("DISPLAY")?;
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowpid"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_pid(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "macos")]
+fn foreground_pid() -> Option<u32> {
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            "tell application \"System Events\" to get unix id of first process whose frontmost is true",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_pid(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn foreground_pid() -> Option<u32> {
+    None
+}
@@ -0,0 +1,42 @@
+//! Memory module parsing
+//!
+//! Windows reports installed modules through WMI's `Win32_PhysicalMemory`
+//! class. This parser turns its rows into `MemoryModule`s, which the memory
+//! channel analysis infers the channel layout from.
+
+use crate::core::domain::MemoryModule;
+use serde_json::Value;
+
+/// Parse `Win32_PhysicalMemory` rows into the installed modules
+///
+/// WMI returns the 64-bit `Capacity` as a string of bytes. The configured
+/// clock speed is preferred over the module's rated `Speed`. Rows without a
+/// capacity, such as empty slots some firmware lists, are skipped.
+pub fn parse_physical_memory(rows: &[Value]) -> Vec<MemoryModule> {
+    rows.iter()
+        .filter_map(|row| {
+            let capacity_bytes = row.get("Capacity").and_then(number)?;
+            if capacity_bytes == 0 {
+                return None;
+            }
+            let speed_mhz = ["ConfiguredClockSpeed", "Speed"]
+                .iter()
+                .find_map(|key| row.get(*key).and_then(number).filter(|&speed| speed > 0));
+            let manufacturer = row
+                .get("Manufacturer")
+                .and_then(Value::as_str)
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty());
+            Some(MemoryModule {
+                size_mb: capacity_bytes / (1024 * 1024),
+                speed_mhz,
+                manufacturer,
+            })
+        })
+        .collect()
+}
+
+/// Read a WMI integer, which may arrive as a number or a string
+fn number(value: &Value) -> Option<u64> {
+    value.as_u64().or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+}
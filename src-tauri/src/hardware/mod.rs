@@ -4,6 +4,13 @@
 
 pub mod hal;
 pub mod adapters;
+pub mod change;
+pub mod cpuid;
+pub mod detection;
+pub mod display;
+pub mod environment;
+pub mod foreground;
+pub mod memory;
 
 use crate::core::domain::HardwareConfig;
 use crate::core::error::HardwareError;
@@ -18,8 +25,16 @@ use adapters::windows::WindowsHardwareDetector;
 static HARDWARE_DETECTOR: OnceLock<Arc<dyn HardwareDetector>> = OnceLock::new();
 
 /// Get or create the hardware detector for the current platform
+///
+/// With the `synthetic` feature, setting `STATS_IO_SYNTHETIC_SEED` selects
+/// the synthetic detector instead.
 pub fn get_hardware_detector() -> Arc<dyn HardwareDetector> {
     HARDWARE_DETECTOR.get_or_init(|| {
+        #[cfg(feature = "synthetic")]
+        if let Some(seed) = crate::synthetic::seed_from_env() {
+            log::info!("Using synthetic hardware with seed {}", seed);
+            return Arc::new(crate::synthetic::SyntheticHardwareDetector::new(seed)) as Arc<dyn HardwareDetector>;
+        }
         #[cfg(target_os = "windows")]
         {
             Arc::new(WindowsHardwareDetector::new()) as Arc<dyn HardwareDetector>
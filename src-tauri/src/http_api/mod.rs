@@ -0,0 +1,328 @@
+//! Read-only HTTP JSON API for headless use
+//!
+//! This module serves hardware, live metrics and saved sessions over plain
+//! HTTP so a bench machine can be queried remotely. It is only compiled with
+//! the `http-api` feature and binds to localhost unless configured otherwise.
+//!
+//! Endpoints (all `GET`):
+//! - `/hardware` - detected hardware configuration
+//! - `/metrics/latest` - samples from the most recent collection tick
+//! - `/sessions` - IDs of saved sessions
+//! - `/sessions/{id}` - a saved session
+//!
+//! Errors use the same `{ "kind", "code", "message" }` shape as Tauri commands.
+
+use crate::core::domain::MetricSample;
+use crate::core::error::CommandError;
+use crate::metrics::get_metrics_collector;
+use crate::persistence::SessionStorage;
+use serde::Serialize;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Notify;
+
+/// Default port for the HTTP API
+pub const DEFAULT_HTTP_API_PORT: u16 = 7878;
+
+/// Default time a client has to send its request head
+pub const DEFAULT_HTTP_API_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest request head accepted, in bytes
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+/// HTTP API configuration
+#[derive(Debug, Clone)]
+pub struct HttpApiConfig {
+    /// Address to bind to (localhost by default)
+    pub bind_address: IpAddr,
+    /// Port to listen on; 0 picks a free port
+    pub port: u16,
+    /// Bearer token required on every request, if set
+    pub token: Option<String>,
+    /// Time a client has to send its request before the connection is answered
+    /// with 408 and closed
+    pub read_timeout: Duration,
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: DEFAULT_HTTP_API_PORT,
+            token: None,
+            read_timeout: DEFAULT_HTTP_API_READ_TIMEOUT,
+        }
+    }
+}
+
+impl HttpApiConfig {
+    /// Read the configuration from the environment
+    ///
+    /// Uses `STATS_IO_HTTP_BIND`, `STATS_IO_HTTP_PORT` and `STATS_IO_HTTP_TOKEN`,
+    /// falling back to the defaults for unset or invalid values.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            bind_address: std::env::var("STATS_IO_HTTP_BIND")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.bind_address),
+            port: std::env::var("STATS_IO_HTTP_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.port),
+            token: std::env::var("STATS_IO_HTTP_TOKEN")
+                .ok()
+                .filter(|t| !t.is_empty()),
+            read_timeout: defaults.read_timeout,
+        }
+    }
+}
+
+/// Handle to a running HTTP API server
+pub struct HttpApiServer {
+    local_addr: SocketAddr,
+    shutdown: Arc<Notify>,
+}
+
+impl HttpApiServer {
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop accepting connections
+    pub fn shutdown(&self) {
+        self.shutdown.notify_one();
+    }
+}
+
+/// Start the HTTP API server in a background task
+pub async fn serve(
+    config: HttpApiConfig,
+    storage: SessionStorage,
+) -> Result<HttpApiServer, std::io::Error> {
+    let listener = TcpListener::bind(SocketAddr::new(config.bind_address, config.port)).await?;
+    let local_addr = listener.local_addr()?;
+    let shutdown = Arc::new(Notify::new());
+    let token = Arc::new(config.token);
+    let read_timeout = config.read_timeout;
+    let storage = Arc::new(storage);
+
+    let stop = shutdown.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = stop.notified() => break,
+                accepted = listener.accept() => {
+                    let (stream, _) = match accepted {
+                        Ok(conn) => conn,
+                        Err(e) => {
+                            log::warn!("HTTP API accept failed: {}", e);
+                            continue;
+                        }
+                    };
+                    let token = token.clone();
+                    let storage = storage.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, token.as_deref(), read_timeout, &storage).await {
+                            log::debug!("HTTP API connection error: {}", e);
+                        }
+                    });
+                }
+            }
+        }
+    });
+
+    log::info!("HTTP API listening on {}", local_addr);
+    Ok(HttpApiServer { local_addr, shutdown })
+}
+
+/// Parsed request line and the headers the API cares about
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+}
+
+/// Read one request, route it and write the response
+///
+/// A client that does not finish its request within `read_timeout` gets a
+/// 408, so an idle connection cannot hold its task open.
+async fn handle_connection(
+    mut stream: TcpStream,
+    token: Option<&str>,
+    read_timeout: Duration,
+    storage: &SessionStorage,
+) -> Result<(), std::io::Error> {
+    let (status, body) = match tokio::time::timeout(read_timeout, read_request(&mut stream)).await {
+        Ok(read) => match read? {
+            Some(request) => respond(&request, token, storage).await,
+            None => error_response(400, CommandError::validation("HTTP_BAD_REQUEST", "Malformed HTTP request")),
+        },
+        Err(_) => error_response(408, CommandError::validation("HTTP_REQUEST_TIMEOUT", "Request was not received in time")),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason_phrase(status),
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read the request head; the API has no request bodies
+async fn read_request(stream: &mut TcpStream) -> Result<Option<Request>, std::io::Error> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buf.len() > MAX_REQUEST_SIZE {
+            return Ok(None);
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut lines = head.lines();
+    let mut request_line = match lines.next() {
+        Some(line) => line.split_whitespace(),
+        None => return Ok(None),
+    };
+    let (method, path) = match (request_line.next(), request_line.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Ok(None),
+    };
+    let authorization = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .map(|(_, value)| value.trim().to_string());
+
+    Ok(Some(Request { method, path, authorization }))
+}
+
+/// Authorize and route a request, returning the status and JSON body
+async fn respond(request: &Request, token: Option<&str>, storage: &SessionStorage) -> (u16, String) {
+    if let Some(expected) = token {
+        let provided = request
+            .authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if !provided.is_some_and(|provided| tokens_match(provided, expected)) {
+            return error_response(401, CommandError::validation("HTTP_UNAUTHORIZED", "Missing or invalid API token"));
+        }
+    }
+    if request.method != "GET" {
+        return error_response(405, CommandError::validation("HTTP_METHOD_NOT_ALLOWED", "The API is read-only"));
+    }
+
+    // Ignore any query string
+    let path = request.path.split('?').next().unwrap_or("");
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let result = match segments.as_slice() {
+        ["hardware"] => crate::hardware::get_hardware_config()
+            .await
+            .map_err(CommandError::from)
+            .and_then(|config| to_json(&config)),
+        ["metrics", "latest"] => latest_metrics().await.and_then(|samples| to_json(&samples)),
+        ["sessions"] => list_sessions(storage).await.and_then(|ids| to_json(&ids)),
+        ["sessions", id] => load_session(storage, id).await,
+        _ => Err(CommandError::validation("HTTP_NOT_FOUND", format!("No endpoint at {}", path))),
+    };
+
+    match result {
+        Ok(body) => (200, body),
+        Err(error) => {
+            let status = status_for(&error);
+            error_response(status, error)
+        }
+    }
+}
+
+/// Compare tokens in time independent of where they first differ
+///
+/// Only the length can be learned from timing, not how much of the token matched.
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    let (provided, expected) = (provided.as_bytes(), expected.as_bytes());
+    if provided.len() != expected.len() {
+        return false;
+    }
+    let diff = provided
+        .iter()
+        .zip(expected)
+        .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    std::hint::black_box(diff) == 0
+}
+
+/// Samples from the most recent collection tick
+async fn latest_metrics() -> Result<Vec<MetricSample>, CommandError> {
+    let collector = get_metrics_collector().ok_or_else(|| CommandError::Metrics {
+        code: "METRICS_COLLECTOR_NOT_INITIALIZED".to_string(),
+        message: "Metrics collector not initialized".to_string(),
+    })?;
+    let buffer = collector.get_buffer().await;
+    let latest = buffer.iter().map(|s| s.timestamp).max();
+    Ok(buffer
+        .into_iter()
+        .filter(|s| Some(s.timestamp) == latest)
+        .collect())
+}
+
+/// IDs of all saved sessions
+async fn list_sessions(storage: &SessionStorage) -> Result<Vec<String>, CommandError> {
+    let ids = storage.list_sessions().await.map_err(CommandError::from)?;
+    Ok(ids.iter().map(|id| id.to_string()).collect())
+}
+
+/// A saved session as JSON
+async fn load_session(storage: &SessionStorage, id: &str) -> Result<String, CommandError> {
+    let uuid = uuid::Uuid::parse_str(id)
+        .map_err(|e| CommandError::validation("INVALID_SESSION_ID", format!("Invalid session ID: {}", e)))?;
+    let session = storage.load_session(&uuid).await.map_err(CommandError::from)?;
+    to_json(&session)
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, CommandError> {
+    serde_json::to_string(value).map_err(|e| CommandError::Internal {
+        code: "INTERNAL_SERIALIZATION".to_string(),
+        message: e.to_string(),
+    })
+}
+
+fn error_response(status: u16, error: CommandError) -> (u16, String) {
+    let body = serde_json::to_string(&error).unwrap_or_else(|_| "{}".to_string());
+    (status, body)
+}
+
+/// HTTP status for a command error
+fn status_for(error: &CommandError) -> u16 {
+    match error {
+        _ if error.code().ends_with("NOT_FOUND") => 404,
+        CommandError::Validation { .. } => 400,
+        CommandError::Metrics { .. } => 503,
+        _ => 500,
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
@@ -7,6 +7,12 @@ pub mod hardware;
 pub mod metrics;
 pub mod analysis;
 pub mod persistence;
+#[cfg(feature = "http-api")]
+pub mod http_api;
+#[cfg(feature = "synthetic")]
+pub mod synthetic;
+#[cfg(feature = "test-util")]
+pub mod test_support;
 mod commands;
 
 use commands::hardware::*;
@@ -30,30 +36,67 @@ pub fn run() {
         // Hardware commands
         get_hardware_config,
         refresh_hardware_config,
+        get_hardware_changes,
+        dismiss_hardware_changes,
+        export_snapshot,
+        import_snapshot,
         // Metrics commands
         start_metrics_collection,
         stop_metrics_collection,
         get_metrics_buffer,
+        get_metrics_since,
+        get_metrics_health,
+        get_live_percentiles,
         get_metrics_in_range,
         get_aggregated_metrics,
+        benchmark_capture,
         // Analysis commands
         analyze_bottlenecks,
+        analyze_run,
+        analyze_with_profile,
         generate_insights,
+        get_run_insights,
+        analyze_session_trend,
+        analyze_boost_sustainability,
+        detect_memory_leak_trend,
+        detect_vram_creep,
+        analyze_all_sessions,
+        trace_run_analysis,
+        derive_run_metrics,
         // Session commands
         create_session,
         save_session,
         load_session,
         list_sessions,
+        list_session_summaries,
         delete_session,
         add_run_to_session,
         end_session,
+        merge_runs,
+        clone_session,
+        export_session,
+        import_session,
+        estimate_session_size,
+        get_retention_stats_command,
+        cleanup_now_command,
+        update_retention_policy,
+        search_runs,
         // Profile commands
         get_preset_profiles,
         get_profile_by_id,
+        get_custom_profiles,
         // Comparison commands
         compare_runs_command,
+        compare_run_to_reference,
+        compare_to_previous_run,
+        compare_imported_hardware,
         // Report commands
         generate_report,
+        cancel_report_generation,
+        write_report,
+        export_analysis_json,
+        export_run_wide_csv,
+        export_anonymized_contribution,
         generate_comparison_report_command,
         // Settings commands
         get_settings,
@@ -63,6 +106,14 @@ pub fn run() {
         update_units,
         update_theme,
         reset_settings,
+        capture_idle_baseline,
+        save_analysis_profile,
+        delete_analysis_profile,
+        create_custom_profile,
+        update_custom_profile,
+        delete_custom_profile,
+        acknowledge_bottleneck,
+        unacknowledge_bottleneck,
     ])
     .setup(|app| {
         // Initialize settings manager
@@ -70,6 +121,25 @@ pub fn run() {
         if let Err(e) = commands::settings::init_settings_manager(app_handle.clone()) {
             eprintln!("Failed to initialize settings manager: {}", e);
         }
+        // Compare against the hardware seen on the previous launch
+        let hardware_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = commands::hardware::check_hardware_changes(hardware_handle).await {
+                log::warn!("Hardware change check failed: {}", e);
+            }
+        });
+        // Serve the read-only HTTP API for headless machines
+        #[cfg(feature = "http-api")]
+        match persistence::init_session_storage(app_handle) {
+            Ok(storage) => {
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = http_api::serve(http_api::HttpApiConfig::from_env(), storage).await {
+                        log::warn!("Failed to start HTTP API: {}", e);
+                    }
+                });
+            }
+            Err(e) => log::warn!("HTTP API disabled: {}", e),
+        }
         Ok(())
     })
     .run(tauri::generate_context!())
@@ -0,0 +1,54 @@
+//! Batched delivery of live metric samples
+//!
+//! The collector broadcasts one small batch per sampling tick. Forwarding each
+//! batch as its own frontend event floods the IPC bridge at short sampling
+//! intervals, so `coalesce` gathers batches and hands them on once per flush
+//! interval. Every sample is delivered; only the number of events changes.
+
+use crate::core::domain::MetricSample;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::MissedTickBehavior;
+
+/// Default time between batched emits
+pub const DEFAULT_FLUSH_INTERVAL_MS: u64 = 250;
+
+/// Forward samples from `receiver` to `emit`, at most once per `flush_interval`
+///
+/// Nothing is emitted for intervals without samples. Returns once the sender
+/// side closes, after flushing whatever is still pending.
+pub async fn coalesce<F>(
+    mut receiver: broadcast::Receiver<Vec<MetricSample>>,
+    flush_interval: Duration,
+    mut emit: F,
+) where
+    F: FnMut(Vec<MetricSample>),
+{
+    let mut pending: Vec<MetricSample> = Vec::new();
+    let mut flush = tokio::time::interval(flush_interval);
+    flush.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // The first tick completes immediately
+    flush.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = flush.tick() => {
+                if !pending.is_empty() {
+                    emit(std::mem::take(&mut pending));
+                }
+            }
+            received = receiver.recv() => match received {
+                Ok(batch) => pending.extend(batch),
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("Metrics coalescer fell behind; {} batches were dropped", skipped);
+                }
+                Err(RecvError::Closed) => {
+                    if !pending.is_empty() {
+                        emit(pending);
+                    }
+                    return;
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! High-frequency benchmark capture
+//!
+//! Micro-benchmarks finish in seconds, so the regular sampling interval is far
+//! too coarse for them. A benchmark capture runs its own tight loop for a fixed
+//! duration, writes into a pre-sized buffer instead of the shared one, and
+//! returns the result as a run.
+//!
+//! GPU readings come from driver tools that take longer than a benchmark tick,
+//! so only CPU and memory utilization are captured. The capture reads them
+//! from its own sysinfo `System`, leaving the regular loop's CPU deltas alone,
+//! and skips the temperature and fan queries of the regular CPU provider.
+
+use super::MetricsCollector;
+use crate::core::domain::{MetricSample, MetricType, Run};
+use crate::core::error::MetricsError;
+use chrono::Utc;
+use std::collections::HashMap;
+use sysinfo::{System, MINIMUM_CPU_UPDATE_INTERVAL};
+use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
+use uuid::Uuid;
+
+/// Shortest supported benchmark sampling interval
+///
+/// CPU usage does not update faster than sysinfo's
+/// `MINIMUM_CPU_UPDATE_INTERVAL`; shorter ticks would repeat the last reading.
+pub const BENCHMARK_MIN_INTERVAL_MS: u64 = MINIMUM_CPU_UPDATE_INTERVAL.as_millis() as u64;
+
+/// Longest supported benchmark capture
+pub const BENCHMARK_MAX_DURATION: Duration = Duration::from_secs(60);
+
+/// Upper bound on samples held by a single capture
+pub const BENCHMARK_MAX_SAMPLES: usize = 500_000;
+
+impl MetricsCollector {
+    /// Capture CPU and memory metrics at a high rate for a fixed duration
+    ///
+    /// Runs independently of the regular sampling loop: the shared buffer and
+    /// subscribers are not touched. Intervals below
+    /// `BENCHMARK_MIN_INTERVAL_MS` are rejected. Late ticks are skipped rather than fired back to back, so samples stay
+    /// evenly spaced. The capture stops early once it holds
+    /// `BENCHMARK_MAX_SAMPLES` samples.
+    pub async fn benchmark_capture(
+        &self,
+        duration: Duration,
+        interval_ms: u64,
+    ) -> Result<Run, MetricsError> {
+        if interval_ms < BENCHMARK_MIN_INTERVAL_MS {
+            return Err(MetricsError::InvalidValue(format!(
+                "Benchmark interval must be at least {} ms",
+                BENCHMARK_MIN_INTERVAL_MS
+            )));
+        }
+        if duration.is_zero() || duration > BENCHMARK_MAX_DURATION {
+            return Err(MetricsError::InvalidValue(format!(
+                "Benchmark duration must be between 1 ms and {} s",
+                BENCHMARK_MAX_DURATION.as_secs()
+            )));
+        }
+
+        let expected_ticks = (duration.as_millis() / interval_ms as u128) as usize + 1;
+        let mut samples: Vec<MetricSample> = Vec::new();
+        let mut truncated = false;
+        let mut sampler = BenchmarkSampler::new();
+        let mut ticker = interval(Duration::from_millis(interval_ms));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        // The first tick fires at once; CPU usage needs one interval since the priming refresh
+        ticker.tick().await;
+        let deadline = Instant::now() + duration;
+
+        loop {
+            ticker.tick().await;
+            if Instant::now() >= deadline {
+                break;
+            }
+
+            let tick = sampler.tick();
+            // Size the buffer once the first tick shows how many samples a tick yields
+            if samples.capacity() == 0 {
+                samples.reserve_exact((expected_ticks * tick.len()).min(BENCHMARK_MAX_SAMPLES));
+            }
+            if samples.len() + tick.len() > BENCHMARK_MAX_SAMPLES {
+                log::warn!(
+                    "Benchmark capture reached {} samples and was stopped early",
+                    BENCHMARK_MAX_SAMPLES
+                );
+                truncated = true;
+                break;
+            }
+            samples.extend(tick);
+        }
+
+        let mut metrics_streams: HashMap<String, Vec<MetricSample>> = HashMap::new();
+        for sample in samples {
+            let key = serde_json::to_value(&sample.metric_type)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            metrics_streams.entry(key).or_default().push(sample);
+        }
+
+        Ok(Run {
+            id: Uuid::new_v4(),
+            name: format!("Benchmark capture ({} ms)", interval_ms),
+            metrics_streams,
+            analysis_result: None,
+            notes: truncated.then(|| {
+                format!(
+                    "Capture stopped early after reaching the {} sample limit.",
+                    BENCHMARK_MAX_SAMPLES
+                )
+            }),
+            environment: None,
+        })
+    }
+}
+
+/// Utilization-only reader for benchmark captures
+struct BenchmarkSampler {
+    system: System,
+}
+
+impl BenchmarkSampler {
+    /// Create a sampler and take the CPU reading the first tick is measured against
+    fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_cpu_usage();
+        Self { system }
+    }
+
+    /// Read CPU and memory utilization once
+    fn tick(&mut self) -> Vec<MetricSample> {
+        let timestamp = Utc::now();
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+
+        let per_core: Vec<f64> = self.system.cpus().iter().map(|cpu| cpu.cpu_usage() as f64).collect();
+        let mut samples = Vec::with_capacity(per_core.len() + 3);
+        if !per_core.is_empty() {
+            let overall = per_core.iter().sum::<f64>() / per_core.len() as f64;
+            samples.push(MetricSample::new(MetricType::CpuUtilization, overall, "CPU").with_timestamp(timestamp));
+        }
+        for (idx, util) in per_core.iter().enumerate() {
+            samples.push(MetricSample::new(MetricType::CpuUtilizationPerCore, *util, format!("CPU Core {}", idx))
+                .with_timestamp(timestamp));
+        }
+
+        let total_mb = self.system.total_memory() / (1024 * 1024);
+        let used_mb = self.system.used_memory() / (1024 * 1024);
+        if total_mb > 0 {
+            samples.push(MetricSample::new(MetricType::MemoryUsage, used_mb as f64 / total_mb as f64 * 100.0, "Memory")
+                .with_timestamp(timestamp));
+        }
+        samples.push(MetricSample::new(MetricType::MemoryUsed, used_mb as f64, "Memory")
+            .with_timestamp(timestamp));
+
+        samples
+    }
+}
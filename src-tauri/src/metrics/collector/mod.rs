@@ -3,21 +3,40 @@
 //! This module implements the central metrics collector that runs in a background
 //! Tokio task and collects metrics from all providers at configurable intervals.
 
-use crate::core::domain::{MetricSample, MetricType};
+mod benchmark;
+
+pub use benchmark::{BENCHMARK_MAX_DURATION, BENCHMARK_MAX_SAMPLES, BENCHMARK_MIN_INTERVAL_MS};
+
+use crate::core::domain::{ForegroundProcess, HardwareConfig, MetricSample, MetricType, RunEnvironment};
 use crate::core::error::MetricsError;
 use crate::core::interfaces::{
-    CpuMetricsProvider, GpuMetricsProvider, MemoryMetricsProvider, StorageMetricsProvider,
+    CpuMetricsProvider, GpuMetricsProvider, MemoryMetricsProvider, SensorProvider,
+    StorageMetricsProvider,
+};
+use crate::core::settings::{
+    BufferOverflowPolicy, GpuUtilizationSource, MissedTickBehavior, NoiseFloors, SamplingSettings,
+};
+use crate::metrics::models::{
+    CpuMetrics, GpuAdapterUtilization, GpuMetrics, LatencyMetrics, ProcessCpuUsage, ProcessDiskUsage, ProcessGpuUsage, SensorKind, SensorReading,
 };
 use crate::metrics::providers::{
-        GpuMetricsProviderImpl, SysInfoCpuMetricsProvider, SysInfoMemoryMetricsProvider,
-    SysInfoStorageMetricsProvider,
+        GpuMetricsProviderImpl, PlatformSensorProvider, SysInfoCpuMetricsProvider,
+    SysInfoMemoryMetricsProvider, SysInfoStorageMetricsProvider,
 };
-use chrono::Utc;
-use std::collections::VecDeque;
+use crate::hardware::foreground;
+use crate::metrics::providers::cpu_processes::{self, ProcessCpuSampler};
+use crate::metrics::providers::disk_processes::{self, ProcessDiskSampler};
+use crate::metrics::providers::{gpu_processes, latency, subprocess};
+use crate::metrics::tdigest::TDigest;
+use crate::metrics::validation::validate_samples;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use sysinfo::System;
 use tokio::sync::{broadcast, Mutex, RwLock};
-use tokio::time::{interval, Duration};
+use tokio::time::{interval, Duration, Interval};
 
 /// Metrics collector configuration
 #[derive(Debug, Clone)]
@@ -26,6 +45,14 @@ pub struct MetricsCollectorConfig {
     pub sampling_interval_ms: u64,
     /// Maximum number of samples to keep in buffer
     pub buffer_size: usize,
+    /// What to do once the buffer holds `buffer_size` samples
+    pub overflow_policy: BufferOverflowPolicy,
+    /// How the sampling loop catches up after a stall
+    pub missed_tick_behavior: MissedTickBehavior,
+    /// Where GPU utilization is read from
+    pub gpu_utilization_source: GpuUtilizationSource,
+    /// Readings below these values are dropped before buffering
+    pub noise_floors: NoiseFloors,
 }
 
 impl Default for MetricsCollectorConfig {
@@ -33,20 +60,141 @@ impl Default for MetricsCollectorConfig {
         Self {
             sampling_interval_ms: 1000, // 1 second default
             buffer_size: 600,           // 10 minutes at 1s intervals
+            overflow_policy: BufferOverflowPolicy::EvictOldest,
+            missed_tick_behavior: MissedTickBehavior::Delay,
+            gpu_utilization_source: GpuUtilizationSource::Driver,
+            noise_floors: NoiseFloors::default(),
         }
     }
 }
 
+impl MetricsCollectorConfig {
+    /// Collector configuration for the given sampling settings
+    pub fn from_sampling(sampling: &SamplingSettings) -> Self {
+        Self {
+            sampling_interval_ms: sampling.interval_ms,
+            buffer_size: sampling.buffer_size,
+            overflow_policy: sampling.overflow_policy,
+            missed_tick_behavior: sampling.missed_tick_behavior,
+            gpu_utilization_source: sampling.gpu_utilization_source,
+            noise_floors: sampling.noise_floors,
+        }
+    }
+}
+
+/// Health and buffer state of the metrics collector
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CollectorHealth {
+    /// Samples currently held in the buffer
+    pub buffered_samples: usize,
+    /// Configured buffer size
+    pub buffer_size: usize,
+    pub overflow_policy: BufferOverflowPolicy,
+    /// Samples dropped from the front of the buffer since the last save
+    pub evicted_samples: u64,
+    /// When the buffer first started dropping samples
+    pub eviction_started_at: Option<DateTime<Utc>>,
+    /// Out-of-range readings pulled back into range since collection started
+    pub clamped_samples: u64,
+    /// Physically implausible readings dropped since collection started
+    pub rejected_samples: u64,
+    /// Provider subprocesses killed after their timeout since collection started
+    pub subprocess_timeouts: u64,
+    /// Provider subprocesses that failed since collection started
+    pub subprocess_failures: u64,
+}
+
+impl CollectorHealth {
+    fn new(config: &MetricsCollectorConfig) -> Self {
+        Self {
+            buffered_samples: 0,
+            buffer_size: config.buffer_size,
+            overflow_policy: config.overflow_policy,
+            evicted_samples: 0,
+            eviction_started_at: None,
+            clamped_samples: 0,
+            rejected_samples: 0,
+            subprocess_timeouts: 0,
+            subprocess_failures: 0,
+        }
+    }
+
+    /// Whether samples have been lost from the start of the capture
+    pub fn is_evicting(&self) -> bool {
+        self.evicted_samples > 0
+    }
+}
+
+/// Buffered samples newer than those a client already holds
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsDelta {
+    /// Samples to append to the client's copy, oldest first
+    pub samples: Vec<MetricSample>,
+    /// The client's copy cannot be extended and must be replaced by `samples`
+    ///
+    /// Set when the client has no copy yet or when samples it never received
+    /// have been evicted from the buffer.
+    pub resync: bool,
+    /// Timestamp of the oldest buffered sample; older client samples have been evicted
+    pub oldest_timestamp: Option<DateTime<Utc>>,
+}
+
+/// Percentiles of every sample collected for one metric type
+///
+/// Estimated from a t-digest, so they cover the whole collection rather
+/// than only what is still in the buffer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LivePercentiles {
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl LivePercentiles {
+    fn from_digest(digest: &TDigest) -> Option<Self> {
+        Some(Self {
+            count: digest.count(),
+            min: digest.min()?,
+            max: digest.max()?,
+            p50: digest.percentile(50.0)?,
+            p95: digest.percentile(95.0)?,
+            p99: digest.percentile(99.0)?,
+        })
+    }
+}
+
 /// Central metrics collector
 pub struct MetricsCollector {
-    config: MetricsCollectorConfig,
+    config: RwLock<MetricsCollectorConfig>,
     buffer: Arc<RwLock<VecDeque<MetricSample>>>,
+    health: Arc<RwLock<CollectorHealth>>,
+    /// Streaming percentile state per metric type since collection started
+    live_stats: Arc<RwLock<HashMap<MetricType, TDigest>>>,
     cpu_provider: Arc<dyn CpuMetricsProvider>,
     gpu_provider: Arc<dyn GpuMetricsProvider>,
     memory_provider: Arc<dyn MemoryMetricsProvider>,
     storage_provider: Arc<dyn StorageMetricsProvider>,
+    sensor_provider: Arc<dyn SensorProvider>,
+    /// VRAM totals of the detected GPUs, in detection order
+    detected_vram_totals_mb: Arc<RwLock<Vec<Option<u64>>>>,
+    /// Models of the detected GPUs, in detection order
+    detected_gpu_models: Arc<RwLock<Vec<String>>>,
+    /// Application that took focus after collection started
+    foreground: Arc<RwLock<Option<ForegroundProcess>>>,
+    /// Run environment captured when collection started
+    run_environment: RwLock<Option<RunEnvironment>>,
+    /// Subprocess failure counters when collection started
+    subprocess_baseline: RwLock<subprocess::SubprocessFailures>,
     sender: broadcast::Sender<Vec<MetricSample>>,
     running: Arc<Mutex<bool>>,
+    /// Bumped on every stop so a stopped loop exits even if collection restarts
+    /// before its next tick
+    generation: Arc<AtomicU64>,
+    /// Serializes reconfigurations so concurrent callers cannot both restart the loop
+    reconfiguring: Mutex<()>,
 }
 
 impl MetricsCollector {
@@ -54,52 +202,265 @@ impl MetricsCollector {
     pub fn new(config: MetricsCollectorConfig) -> Self {
         // Create shared system instance for providers
         let system = Arc::new(Mutex::new(System::new_all()));
-        let buffer_size = config.buffer_size;
         
         let cpu_provider = Arc::new(SysInfoCpuMetricsProvider::new(system.clone()))
             as Arc<dyn CpuMetricsProvider>;
-        let gpu_provider = Arc::new(GpuMetricsProviderImpl::new())
+        let gpu_provider = Arc::new(GpuMetricsProviderImpl::with_utilization_source(config.gpu_utilization_source))
             as Arc<dyn GpuMetricsProvider>;
         let memory_provider = Arc::new(SysInfoMemoryMetricsProvider::new(system.clone()))
             as Arc<dyn MemoryMetricsProvider>;
         let storage_provider = Arc::new(SysInfoStorageMetricsProvider::new(system))
             as Arc<dyn StorageMetricsProvider>;
         
+        Self::with_providers(
+            config,
+            cpu_provider,
+            gpu_provider,
+            memory_provider,
+            storage_provider,
+            Arc::new(PlatformSensorProvider::new()),
+        )
+    }
+    
+    /// Create a metrics collector reading from the synthetic source for `seed`
+    #[cfg(feature = "synthetic")]
+    pub fn synthetic(config: MetricsCollectorConfig, seed: u64) -> Self {
+        let provider = Arc::new(crate::synthetic::SyntheticMetricsProvider::new(seed));
+        Self::with_providers(
+            config,
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider,
+        )
+    }
+    
+    /// Create a metrics collector reading from the given providers
+    pub fn with_providers(
+        config: MetricsCollectorConfig,
+        cpu_provider: Arc<dyn CpuMetricsProvider>,
+        gpu_provider: Arc<dyn GpuMetricsProvider>,
+        memory_provider: Arc<dyn MemoryMetricsProvider>,
+        storage_provider: Arc<dyn StorageMetricsProvider>,
+        sensor_provider: Arc<dyn SensorProvider>,
+    ) -> Self {
+        let buffer_size = config.buffer_size;
         let (sender, _) = broadcast::channel(100);
         
         Self {
-            config,
+            health: Arc::new(RwLock::new(CollectorHealth::new(&config))),
+            config: RwLock::new(config),
             buffer: Arc::new(RwLock::new(VecDeque::with_capacity(buffer_size))),
+            live_stats: Arc::new(RwLock::new(HashMap::new())),
             cpu_provider,
             gpu_provider,
             memory_provider,
             storage_provider,
+            sensor_provider,
+            detected_vram_totals_mb: Arc::new(RwLock::new(Vec::new())),
+            detected_gpu_models: Arc::new(RwLock::new(Vec::new())),
+            foreground: Arc::new(RwLock::new(None)),
+            run_environment: RwLock::new(None),
+            subprocess_baseline: RwLock::new(subprocess::SubprocessFailures::default()),
             sender,
             running: Arc::new(Mutex::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
+            reconfiguring: Mutex::new(()),
         }
     }
     
+    /// Application that took focus after collection started, if detected
+    pub async fn foreground_process(&self) -> Option<ForegroundProcess> {
+        self.foreground.read().await.clone()
+    }
+    
+    /// Run environment captured when collection started, if one was recorded
+    pub async fn run_environment(&self) -> Option<RunEnvironment> {
+        self.run_environment.read().await.clone()
+    }
+    
+    /// Record the run environment the current collection started in
+    pub async fn set_run_environment(&self, environment: RunEnvironment) {
+        *self.run_environment.write().await = Some(environment);
+    }
+    
+    /// Use the detected GPUs to fill in VRAM totals the live readings lack
+    /// and to attribute per-adapter utilization to GPU models
+    pub async fn set_hardware_config(&self, hardware: &HardwareConfig) {
+        *self.detected_vram_totals_mb.write().await =
+            hardware.gpus.iter().map(|gpu| gpu.vram_total_mb).collect();
+        *self.detected_gpu_models.write().await = hardware.gpus.iter().map(|gpu| gpu.model.clone()).collect();
+    }
+    
     /// Start the metrics collection loop
     pub async fn start(&self) -> Result<(), MetricsError> {
+        self.mark_running().await?;
+        
+        // Percentiles and validation counts describe the current collection only
+        self.live_stats.write().await.clear();
+        *self.foreground.write().await = None;
+        *self.run_environment.write().await = None;
+        {
+            let mut health = self.health.write().await;
+            health.clamped_samples = 0;
+            health.rejected_samples = 0;
+            health.subprocess_timeouts = 0;
+            health.subprocess_failures = 0;
+        }
+        *self.subprocess_baseline.write().await = subprocess::subprocess_failures();
+        
+        self.spawn_sampling_loop().await;
+        Ok(())
+    }
+    
+    /// Flag the collector as running, failing if it already is
+    async fn mark_running(&self) -> Result<(), MetricsError> {
         let mut running = self.running.lock().await;
         if *running {
             return Err(MetricsError::Unknown("Collector already running".to_string()));
         }
         *running = true;
-        drop(running);
-        
-        let sampling_interval_ms = self.config.sampling_interval_ms;
-        let buffer_size = self.config.buffer_size;
+        Ok(())
+    }
+    
+    /// Spawn the sampling loop with the current configuration
+    ///
+    /// Unlike `start`, this leaves the per-collection state alone, so a
+    /// reconfiguration keeps the live percentiles, foreground application,
+    /// run environment and health counters gathered so far.
+    async fn spawn_sampling_loop(&self) {
+        let subprocess_baseline = *self.subprocess_baseline.read().await;
+        let config = self.config.read().await.clone();
+        let sampling_interval_ms = config.sampling_interval_ms;
+        let missed_tick_behavior = config.missed_tick_behavior;
         let buffer = self.buffer.clone();
+        let health = self.health.clone();
+        let live_stats = self.live_stats.clone();
         let cpu_provider = self.cpu_provider.clone();
         let gpu_provider = self.gpu_provider.clone();
         let memory_provider = self.memory_provider.clone();
         let storage_provider = self.storage_provider.clone();
+        let sensor_provider = self.sensor_provider.clone();
+        let detected_vram_totals_mb = self.detected_vram_totals_mb.clone();
+        let detected_gpu_models = self.detected_gpu_models.clone();
+        let foreground = self.foreground.clone();
         let sender = self.sender.clone();
         let running = self.running.clone();
+        let generation = self.generation.clone();
+        let loop_generation = generation.load(Ordering::SeqCst);
+        
+        // Latency counters take about a second per sample, so they are sampled
+        // in their own task and the latest value is attached to each tick
+        let latest_latency: Arc<RwLock<Option<LatencyMetrics>>> = Arc::new(RwLock::new(None));
+        if latency::is_supported() {
+            let latest_latency = latest_latency.clone();
+            let running = running.clone();
+            let generation = generation.clone();
+            tokio::spawn(async move {
+                while *running.lock().await && generation.load(Ordering::SeqCst) == loop_generation {
+                    match latency::get_latency_metrics().await {
+                        Ok(metrics) => *latest_latency.write().await = Some(metrics),
+                        // A hung typeperf was killed; try again on the next pass
+                        Err(MetricsError::Timeout(e)) => log::debug!("Latency counters timed out: {}", e),
+                        Err(e) => {
+                            log::debug!("Latency counters unavailable: {}", e);
+                            // Stop attaching the last reading to later ticks
+                            *latest_latency.write().await = None;
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+        
+        // Per-process GPU counters are just as slow and follow the same pattern
+        let latest_process_gpu: Arc<RwLock<Vec<ProcessGpuUsage>>> = Arc::new(RwLock::new(Vec::new()));
+        if gpu_processes::is_supported() {
+            let latest_process_gpu = latest_process_gpu.clone();
+            let running = running.clone();
+            let generation = generation.clone();
+            tokio::spawn(async move {
+                while *running.lock().await && generation.load(Ordering::SeqCst) == loop_generation {
+                    match gpu_processes::get_process_gpu_usage().await {
+                        Ok(usage) => *latest_process_gpu.write().await = usage,
+                        Err(MetricsError::Timeout(e)) => log::debug!("Per-process GPU counters timed out: {}", e),
+                        Err(e) => {
+                            log::debug!("Per-process GPU counters unavailable: {}", e);
+                            latest_process_gpu.write().await.clear();
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+        
+        // Per-process disk writes need a process refresh, which blocks, so they
+        // are sampled once a second on the blocking pool
+        let latest_process_disk: Arc<RwLock<Vec<ProcessDiskUsage>>> = Arc::new(RwLock::new(Vec::new()));
+        if disk_processes::is_supported() {
+            let latest_process_disk = latest_process_disk.clone();
+            let running = running.clone();
+            let generation = generation.clone();
+            tokio::spawn(async move {
+                let sampler = Arc::new(std::sync::Mutex::new(ProcessDiskSampler::new()));
+                while *running.lock().await && generation.load(Ordering::SeqCst) == loop_generation {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    let sampler = sampler.clone();
+                    match tokio::task::spawn_blocking(move || sampler.lock().unwrap().sample()).await {
+                        Ok(usage) => *latest_process_disk.write().await = usage,
+                        Err(e) => {
+                            log::debug!("Per-process disk writes unavailable: {}", e);
+                            latest_process_disk.write().await.clear();
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+        
+        // Per-process CPU usage follows the same pattern. The foreground
+        // application is looked up until one other than this app has focus,
+        // as this app is focused while the user starts collection.
+        let latest_process_cpu: Arc<RwLock<Vec<ProcessCpuUsage>>> = Arc::new(RwLock::new(Vec::new()));
+        if cpu_processes::is_supported() {
+            let latest_process_cpu = latest_process_cpu.clone();
+            let running = running.clone();
+            let generation = generation.clone();
+            tokio::spawn(async move {
+                let sampler = Arc::new(std::sync::Mutex::new(ProcessCpuSampler::new()));
+                while *running.lock().await && generation.load(Ordering::SeqCst) == loop_generation {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    let sampler = sampler.clone();
+                    let known = foreground.read().await.clone();
+                    let sampled = tokio::task::spawn_blocking(move || {
+                        let current = known.or_else(foreground::detect_foreground_process);
+                        let usage = sampler.lock().unwrap().sample(current.as_ref().map(|p| p.pid));
+                        (current, usage)
+                    })
+                    .await;
+                    match sampled {
+                        Ok((current, usage)) => {
+                            if current.is_some() {
+                                *foreground.write().await = current;
+                            }
+                            *latest_process_cpu.write().await = usage;
+                        }
+                        Err(e) => {
+                            log::debug!("Per-process CPU usage unavailable: {}", e);
+                            latest_process_cpu.write().await.clear();
+                            break;
+                        }
+                    }
+                }
+            });
+        }
         
         tokio::spawn(async move {
-            let mut interval = interval(Duration::from_millis(sampling_interval_ms));
+            let mut interval = sampling_interval(
+                Duration::from_millis(sampling_interval_ms),
+                missed_tick_behavior,
+            );
             
             loop {
                 interval.tick().await;
@@ -107,7 +468,7 @@ impl MetricsCollector {
                 // Check if we should stop
                 {
                     let r = running.lock().await;
-                    if !*r {
+                    if !*r || generation.load(Ordering::SeqCst) != loop_generation {
                         break;
                     }
                 }
@@ -116,129 +477,94 @@ impl MetricsCollector {
                 let mut samples = Vec::new();
                 let timestamp = Utc::now();
                 
-                // CPU metrics
+                // CPU metrics, temperature and fan speed from a single reading
                 if let Ok(cpu_metrics) = cpu_provider.get_cpu_metrics().await {
-                    samples.push(MetricSample {
-                        timestamp,
-                        metric_type: MetricType::CpuUtilization,
-                        value: cpu_metrics.overall_utilization * 100.0, // Convert to percentage
-                        unit: "percent".to_string(),
-                        source_component: "CPU".to_string(),
-                    });
-                    
-                    // Per-core utilization
-                    for (idx, util) in cpu_metrics.per_core_utilization.iter().enumerate() {
-                        samples.push(MetricSample {
-                            timestamp,
-                            metric_type: MetricType::CpuUtilizationPerCore,
-                            value: *util * 100.0,
-                            unit: "percent".to_string(),
-                            source_component: format!("CPU Core {}", idx),
-                        });
-                    }
+                    samples.extend(cpu_samples(&cpu_metrics, timestamp));
                 }
                 
                 // GPU metrics
-                if let Ok(gpu_metrics) = gpu_provider.get_gpu_metrics().await {
-                    if gpu_metrics.utilization > 0.0 {
-                        samples.push(MetricSample {
-                            timestamp,
-                            metric_type: MetricType::GpuUtilization,
-                            value: gpu_metrics.utilization * 100.0,
-                            unit: "percent".to_string(),
-                            source_component: "GPU".to_string(),
-                        });
-                    }
-                    
-                    if let Some(vram_used) = gpu_metrics.vram_used_mb {
-                        samples.push(MetricSample {
-                            timestamp,
-                            metric_type: MetricType::GpuVramUsage,
-                            value: vram_used as f64,
-                            unit: "MB".to_string(),
-                            source_component: "GPU".to_string(),
-                        });
+                if let Ok(mut gpu_metrics) = gpu_provider.get_gpu_metrics().await {
+                    fill_vram_total(
+                        &mut gpu_metrics,
+                        &detected_vram_totals_mb.read().await,
+                        &detected_gpu_models.read().await,
+                    );
+                    samples.extend(gpu_samples(&gpu_metrics, "GPU", timestamp));
+                }
+                
+                // Utilization of each GPU, to tell which one of several is rendering
+                {
+                    let gpu_models = detected_gpu_models.read().await;
+                    if gpu_models.len() > 1 {
+                        if let Ok(adapters) = gpu_provider.get_adapter_utilization().await {
+                            samples.extend(adapter_utilization_samples(&adapters, &gpu_models, timestamp));
+                        }
                     }
                 }
                 
                 // Memory metrics
                 if let Ok(memory_metrics) = memory_provider.get_memory_metrics().await {
                     let usage_percent = (memory_metrics.used_mb as f64 / memory_metrics.total_mb as f64) * 100.0;
-                    samples.push(MetricSample {
-                        timestamp,
-                        metric_type: MetricType::MemoryUsage,
-                        value: usage_percent,
-                        unit: "percent".to_string(),
-                        source_component: "Memory".to_string(),
-                    });
+                    samples.push(MetricSample::new(MetricType::MemoryUsage, usage_percent, "Memory")
+                        .with_timestamp(timestamp));
+                    samples.push(MetricSample::new(MetricType::MemoryUsed, memory_metrics.used_mb as f64, "Memory")
+                        .with_timestamp(timestamp));
                     
                     if let Some(swap_used) = memory_metrics.swap_used_mb {
-                        samples.push(MetricSample {
-                            timestamp,
-                            metric_type: MetricType::MemorySwapUsage,
-                            value: swap_used as f64,
-                            unit: "MB".to_string(),
-                            source_component: "Memory".to_string(),
-                        });
+                        samples.push(MetricSample::new(MetricType::MemorySwapUsage, swap_used as f64, "Memory")
+                            .with_timestamp(timestamp));
                     }
                 }
                 
                 // Storage metrics
                 if let Ok(storage_metrics) = storage_provider.get_storage_metrics().await {
                     if storage_metrics.read_throughput_mb_per_s > 0.0 {
-                        samples.push(MetricSample {
-                            timestamp,
-                            metric_type: MetricType::StorageReadThroughput,
-                            value: storage_metrics.read_throughput_mb_per_s,
-                            unit: "MB/s".to_string(),
-                            source_component: "Storage".to_string(),
-                        });
+                        samples.push(MetricSample::new(MetricType::StorageReadThroughput, storage_metrics.read_throughput_mb_per_s, "Storage")
+                            .with_timestamp(timestamp));
                     }
                     
                     if storage_metrics.write_throughput_mb_per_s > 0.0 {
-                        samples.push(MetricSample {
-                            timestamp,
-                            metric_type: MetricType::StorageWriteThroughput,
-                            value: storage_metrics.write_throughput_mb_per_s,
-                            unit: "MB/s".to_string(),
-                            source_component: "Storage".to_string(),
-                        });
+                        samples.push(MetricSample::new(MetricType::StorageWriteThroughput, storage_metrics.write_throughput_mb_per_s, "Storage")
+                            .with_timestamp(timestamp));
                     }
                     
                     if let Some(queue_depth) = storage_metrics.queue_depth {
-                        samples.push(MetricSample {
-                            timestamp,
-                            metric_type: MetricType::StorageQueueDepth,
-                            value: queue_depth as f64,
-                            unit: "requests".to_string(),
-                            source_component: "Storage".to_string(),
-                        });
+                        samples.push(MetricSample::new(MetricType::StorageQueueDepth, queue_depth as f64, "Storage")
+                            .with_timestamp(timestamp));
                     }
                 }
                 
-                // CPU temperature (if available)
-                if let Ok(cpu_metrics) = cpu_provider.get_cpu_metrics().await {
-                    if let Some(temp) = cpu_metrics.temperature {
-                        samples.push(MetricSample {
-                            timestamp,
-                            metric_type: MetricType::Temperature,
-                            value: temp,
-                            unit: "Celsius".to_string(),
-                            source_component: "CPU".to_string(),
-                        });
-                    }
+                // Motherboard and component sensors
+                if let Ok(readings) = sensor_provider.get_sensors().await {
+                    samples.extend(sensor_samples(&readings, timestamp));
                 }
                 
-                // Add samples to buffer
+                // DPC and interrupt time (Windows only)
+                if let Some(latency_metrics) = *latest_latency.read().await {
+                    samples.extend(latency::latency_samples(&latency_metrics, timestamp));
+                }
+                
+                // GPU usage of the busiest processes (Windows only)
+                samples.extend(gpu_processes::process_gpu_samples(&latest_process_gpu.read().await, timestamp));
+                
+                // CPU usage of the busiest processes and the foreground application
+                samples.extend(cpu_processes::process_cpu_samples(&latest_process_cpu.read().await, timestamp));
+                
+                // Disk writes of the busiest writers, to tell background downloads apart
+                samples.extend(disk_processes::process_disk_samples(&latest_process_disk.read().await, timestamp));
+                
                 {
-                    let mut buf = buffer.write().await;
-                    for sample in &samples {
-                        buf.push_back(sample.clone());
-                        if buf.len() > buffer_size {
-                            buf.pop_front();
-                        }
-                    }
+                    let failures = subprocess::subprocess_failures().since(&subprocess_baseline);
+                    let mut health = health.write().await;
+                    health.subprocess_timeouts = failures.timeouts;
+                    health.subprocess_failures = failures.failures;
                 }
+                record_validation(&health, &mut samples).await;
+                config.noise_floors.suppress(&mut samples);
+                
+                // Add samples to buffer
+                append_to_buffer(&buffer, &health, &config, &samples).await;
+                record_live_stats(&live_stats, &samples).await;
                 
                 // Broadcast to subscribers (for internal use)
                 let _ = sender.send(samples.clone());
@@ -247,14 +573,58 @@ impl MetricsCollector {
                 // to avoid coupling the collector with Tauri directly
             }
         });
-        
-        Ok(())
     }
     
     /// Stop the metrics collection loop
     pub async fn stop(&self) {
         let mut running = self.running.lock().await;
         *running = false;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+    
+    /// Get the current configuration
+    pub async fn config(&self) -> MetricsCollectorConfig {
+        self.config.read().await.clone()
+    }
+    
+    /// Apply a new configuration, restarting the sampling loop if it is running
+    ///
+    /// Buffered samples, live percentiles and health counters are kept. When the new buffer is smaller and the
+    /// policy evicts, the oldest samples beyond it are dropped and counted as
+    /// evicted. Providers are not rebuilt, so a changed GPU utilization source
+    /// applies from the next application start.
+    pub async fn reconfigure(&self, config: MetricsCollectorConfig) -> Result<(), MetricsError> {
+        let _reconfiguring = self.reconfiguring.lock().await;
+        let was_running = self.is_running().await;
+        self.stop().await;
+        {
+            let mut buf = self.buffer.write().await;
+            let mut health = self.health.write().await;
+            if config.overflow_policy == BufferOverflowPolicy::EvictOldest {
+                let excess = buf.len().saturating_sub(config.buffer_size);
+                if excess > 0 {
+                    buf.drain(..excess);
+                    if health.evicted_samples == 0 {
+                        health.eviction_started_at = Some(Utc::now());
+                    }
+                    health.evicted_samples += excess as u64;
+                }
+            }
+            health.buffered_samples = buf.len();
+            health.buffer_size = config.buffer_size;
+            health.overflow_policy = config.overflow_policy;
+        }
+        *self.config.write().await = config;
+        if was_running {
+            self.mark_running().await?;
+            self.spawn_sampling_loop().await;
+        }
+        Ok(())
+    }
+    
+    /// Check whether the collection loop is running
+    pub async fn is_running(&self) -> bool {
+        *self.running.lock().await
     }
     
     /// Get a receiver for metrics updates
@@ -262,12 +632,91 @@ impl MetricsCollector {
         self.sender.subscribe()
     }
     
+    /// Add samples to the buffer, applying the configured overflow policy
+    ///
+    /// Implausible values are clamped or dropped and the noise floors applied
+    /// first, as in the sampling loop.
+    pub async fn record_samples(&self, samples: &[MetricSample]) {
+        let mut samples = samples.to_vec();
+        record_validation(&self.health, &mut samples).await;
+        let config = self.config.read().await;
+        config.noise_floors.suppress(&mut samples);
+        append_to_buffer(&self.buffer, &self.health, &config, &samples).await;
+        record_live_stats(&self.live_stats, &samples).await;
+    }
+    
+    /// Estimate a percentile (0-100) of every sample of `metric_type` collected so far
+    pub async fn live_percentile(&self, metric_type: &MetricType, p: f64) -> Option<f64> {
+        self.live_stats.read().await.get(metric_type)?.percentile(p)
+    }
+    
+    /// Live percentiles of every collected metric type, keyed like `aggregate_metrics`
+    pub async fn live_percentiles(&self) -> HashMap<String, LivePercentiles> {
+        self.live_stats
+            .read()
+            .await
+            .iter()
+            .filter_map(|(metric_type, digest)| {
+                LivePercentiles::from_digest(digest).map(|stats| (format!("{:?}", metric_type), stats))
+            })
+            .collect()
+    }
+    
+    /// Get the current buffer and eviction state
+    pub async fn health(&self) -> CollectorHealth {
+        self.health.read().await.clone()
+    }
+    
+    /// Record that the buffered metrics have been persisted
+    ///
+    /// Resets the eviction counters and, for `UnboundedUntilSaved`, trims a
+    /// buffer that grew past `buffer_size` back down to the newest samples.
+    pub async fn mark_saved(&self) {
+        let config = self.config.read().await;
+        let mut buf = self.buffer.write().await;
+        let mut health = self.health.write().await;
+        if config.overflow_policy == BufferOverflowPolicy::UnboundedUntilSaved {
+            let excess = buf.len().saturating_sub(config.buffer_size);
+            buf.drain(..excess);
+        }
+        health.buffered_samples = buf.len();
+        health.evicted_samples = 0;
+        health.eviction_started_at = None;
+    }
+    
     /// Get current metrics buffer
     pub async fn get_buffer(&self) -> Vec<MetricSample> {
         let buffer = self.buffer.read().await;
         buffer.iter().cloned().collect()
     }
     
+    /// Get the samples recorded after `last_timestamp`
+    ///
+    /// Lets a client that polls the buffer append only what is new. Samples
+    /// from one sampling tick share a timestamp and are buffered together, so
+    /// returning those strictly newer than the last one the client received
+    /// neither repeats nor skips any. When `last_timestamp` is `None` or
+    /// predates the oldest buffered sample, the whole buffer is returned with
+    /// `resync` set.
+    pub async fn get_metrics_since(&self, last_timestamp: Option<DateTime<Utc>>) -> MetricsDelta {
+        let buffer = self.buffer.read().await;
+        let oldest_timestamp = buffer.front().map(|sample| sample.timestamp);
+        let resync = match last_timestamp {
+            Some(last) => oldest_timestamp.is_some_and(|oldest| last < oldest),
+            None => true,
+        };
+        let samples = buffer
+            .iter()
+            .filter(|sample| resync || last_timestamp.is_some_and(|last| sample.timestamp > last))
+            .cloned()
+            .collect();
+        MetricsDelta {
+            samples,
+            resync,
+            oldest_timestamp,
+        }
+    }
+    
     /// Get metrics for a specific time range
     pub async fn get_metrics_in_range(
         &self,
@@ -282,3 +731,288 @@ impl MetricsCollector {
             .collect()
     }
 }
+
+/// Create the sampling loop interval with the given missed-tick behavior
+pub fn sampling_interval(period: Duration, missed_tick_behavior: MissedTickBehavior) -> Interval {
+    let mut interval = interval(period);
+    interval.set_missed_tick_behavior(missed_tick_behavior.into());
+    interval
+}
+
+/// Fill in a missing VRAM total from the detected GPU the readings came from
+///
+/// nvidia-smi reports the total, but the WMI and AMD fallbacks often report
+/// only the used MB, which leaves no VRAM percentage. The GPU is found by
+/// matching the reported adapter name against `detected_gpu_models`, which
+/// is in the same order as `detected_vram_totals_mb`. Readings without an
+/// adapter name are only attributed when a single GPU was detected, so a
+/// second GPU's total is never borrowed. Totals reported by the provider are
+/// kept.
+pub fn fill_vram_total(
+    gpu_metrics: &mut GpuMetrics,
+    detected_vram_totals_mb: &[Option<u64>],
+    detected_gpu_models: &[String],
+) {
+    if gpu_metrics.vram_total_mb.filter(|&total| total > 0).is_some() {
+        return;
+    }
+    let device_index = match &gpu_metrics.adapter_name {
+        Some(adapter_name) => match_gpu_model(adapter_name, detected_gpu_models)
+            .and_then(|model| detected_gpu_models.iter().position(|detected| detected == model)),
+        None => (detected_vram_totals_mb.len() == 1).then_some(0),
+    };
+    if let Some(total) = device_index.and_then(|index| detected_vram_totals_mb.get(index).copied().flatten()) {
+        gpu_metrics.vram_total_mb = Some(total);
+    }
+}
+
+/// Detected GPU model an adapter name refers to
+///
+/// Tries an exact match, then a single model containing or contained in the
+/// name, then the single model from the same vendor, as some platforms detect
+/// GPUs by vendor only. Returns `None` when the adapter is ambiguous.
+pub fn match_gpu_model<'a>(adapter_name: &str, models: &'a [String]) -> Option<&'a String> {
+    let name = adapter_name.trim().to_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+    let single = |candidates: Vec<&'a String>| if candidates.len() == 1 { Some(candidates[0]) } else { None };
+    
+    if let Some(model) = models.iter().find(|model| model.trim().to_lowercase() == name) {
+        return Some(model);
+    }
+    let containing = models
+        .iter()
+        .filter(|model| {
+            let model = model.trim().to_lowercase();
+            !model.is_empty() && (model.contains(&name) || name.contains(&model))
+        })
+        .collect();
+    if let Some(model) = single(containing) {
+        return Some(model);
+    }
+    let vendor = gpu_vendor(&name)?;
+    single(models.iter().filter(|model| gpu_vendor(&model.to_lowercase()) == Some(vendor)).collect())
+}
+
+/// Vendor named in a lowercase GPU name
+fn gpu_vendor(name: &str) -> Option<&'static str> {
+    if ["nvidia", "geforce", "quadro"].iter().any(|marker| name.contains(marker)) {
+        Some("nvidia")
+    } else if ["amd", "radeon"].iter().any(|marker| name.contains(marker)) {
+        Some("amd")
+    } else if name.contains("intel") {
+        Some("intel")
+    } else {
+        None
+    }
+}
+
+/// Convert per-adapter utilization into samples named by GPU model
+///
+/// Adapters that match no detected model are dropped. When several adapters
+/// match the same model, the busiest is kept.
+pub fn adapter_utilization_samples(
+    adapters: &[GpuAdapterUtilization],
+    models: &[String],
+    timestamp: DateTime<Utc>,
+) -> Vec<MetricSample> {
+    let mut per_model: Vec<(&String, f64)> = Vec::new();
+    for adapter in adapters {
+        let model = match match_gpu_model(&adapter.adapter_name, models) {
+            Some(model) => model,
+            None => continue,
+        };
+        match per_model.iter_mut().find(|(known, _)| *known == model) {
+            Some((_, utilization)) => *utilization = utilization.max(adapter.utilization),
+            None => per_model.push((model, adapter.utilization)),
+        }
+    }
+    
+    per_model
+        .into_iter()
+        .map(|(model, utilization)| {
+            MetricSample::new(MetricType::GpuAdapterUtilization, utilization * 100.0, model.as_str())
+                .with_timestamp(timestamp)
+        })
+        .collect()
+}
+
+/// Convert one CPU reading into samples sharing its timestamp
+///
+/// Utilization, clocks, temperature and fan speed all come from the same
+/// reading, so a tick never mixes values taken at different moments.
+pub fn cpu_samples(cpu_metrics: &CpuMetrics, timestamp: DateTime<Utc>) -> Vec<MetricSample> {
+    // Convert to percentage
+    let mut samples = vec![MetricSample::new(MetricType::CpuUtilization, cpu_metrics.overall_utilization * 100.0, "CPU")
+        .with_timestamp(timestamp)];
+    
+    // Per-core utilization
+    for (idx, util) in cpu_metrics.per_core_utilization.iter().enumerate() {
+        samples.push(MetricSample::new(MetricType::CpuUtilizationPerCore, *util * 100.0, format!("CPU Core {}", idx))
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(clock) = cpu_metrics.clock_mhz {
+        samples.push(MetricSample::new(MetricType::CpuClock, clock, "CPU")
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(temp) = cpu_metrics.temperature {
+        samples.push(MetricSample::new(MetricType::Temperature, temp, "CPU")
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(fan_percent) = cpu_metrics.fan_speed_percent {
+        samples.push(MetricSample::new(MetricType::FanSpeed, fan_percent, "CPU")
+            .with_timestamp(timestamp));
+    } else if let Some(fan_rpm) = cpu_metrics.fan_speed_rpm {
+        // RPM is the one non-canonical unit, kept distinct from duty cycle
+        samples.push(MetricSample {
+            timestamp,
+            metric_type: MetricType::FanSpeed,
+            value: fan_rpm,
+            unit: "RPM".to_string(),
+            source_component: "CPU".to_string(),
+        });
+    }
+    
+    samples
+}
+
+/// Convert one device's GPU readings into samples
+///
+/// VRAM is reported both in MB and, when the device's total is known, as a
+/// percentage of that device's own total so consumers need not look it up.
+pub fn gpu_samples(gpu_metrics: &GpuMetrics, source: &str, timestamp: DateTime<Utc>) -> Vec<MetricSample> {
+    let mut samples = Vec::new();
+    
+    if gpu_metrics.utilization > 0.0 {
+        samples.push(MetricSample::new(MetricType::GpuUtilization, gpu_metrics.utilization * 100.0, source)
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(vram_used) = gpu_metrics.vram_used_mb {
+        samples.push(MetricSample::new(MetricType::GpuVramUsage, vram_used as f64, source)
+            .with_timestamp(timestamp));
+        
+        if let Some(vram_total) = gpu_metrics.vram_total_mb.filter(|&total| total > 0) {
+            let percent = vram_used as f64 / vram_total as f64 * 100.0;
+            samples.push(MetricSample::new(MetricType::GpuVramUsagePercent, percent, source)
+                .with_timestamp(timestamp));
+        }
+    }
+    
+    if let Some(power) = gpu_metrics.power_watts {
+        samples.push(MetricSample::new(MetricType::GpuPower, power, source)
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(load) = gpu_metrics.memory_controller_load {
+        samples.push(MetricSample::new(MetricType::GpuMemoryControllerLoad, load, source)
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(temp) = gpu_metrics.temperature {
+        samples.push(MetricSample::new(MetricType::GpuTemperature, temp, source)
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(hotspot) = gpu_metrics.hotspot_temperature {
+        samples.push(MetricSample::new(MetricType::GpuHotspotTemperature, hotspot, source)
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(fan_percent) = gpu_metrics.fan_speed_percent {
+        samples.push(MetricSample::new(MetricType::FanSpeed, fan_percent, source)
+            .with_timestamp(timestamp));
+    }
+    
+    if let Some(link) = gpu_metrics.pcie_link {
+        for (metric_type, value) in [
+            (MetricType::PcieLinkGen, link.gen_current),
+            (MetricType::PcieLinkWidth, link.width_current),
+            (MetricType::PcieLinkGenMax, link.gen_max),
+            (MetricType::PcieLinkWidthMax, link.width_max),
+        ] {
+            samples.push(MetricSample::new(metric_type, value as f64, source)
+                .with_timestamp(timestamp));
+        }
+    }
+    
+    samples
+}
+
+/// Convert sensor readings into samples sourced from each sensor
+///
+/// Temperatures become `SensorTemperature` samples so motherboard, VRM and
+/// drive sensors are not mistaken for the CPU temperature. Fan readings keep
+/// their RPM unit, like CPU fans without a duty cycle.
+pub fn sensor_samples(readings: &[SensorReading], timestamp: DateTime<Utc>) -> Vec<MetricSample> {
+    readings
+        .iter()
+        .map(|reading| {
+            let metric_type = match reading.kind {
+                SensorKind::Temperature => MetricType::SensorTemperature,
+                SensorKind::Fan => MetricType::FanSpeed,
+                SensorKind::Voltage => MetricType::Voltage,
+            };
+            MetricSample {
+                timestamp,
+                metric_type,
+                value: reading.value,
+                unit: reading.unit.clone(),
+                source_component: reading.name.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Feed samples into the per-metric-type digests
+async fn record_live_stats(live_stats: &RwLock<HashMap<MetricType, TDigest>>, samples: &[MetricSample]) {
+    let mut live_stats = live_stats.write().await;
+    for sample in samples {
+        live_stats
+            .entry(sample.metric_type.clone())
+            .or_default()
+            .add(sample.value);
+    }
+}
+
+/// Clamp or drop implausible samples, counting them in the collector health
+async fn record_validation(health: &RwLock<CollectorHealth>, samples: &mut Vec<MetricSample>) {
+    let counts = validate_samples(samples);
+    if counts.clamped > 0 || counts.rejected > 0 {
+        let mut health = health.write().await;
+        health.clamped_samples += counts.clamped;
+        health.rejected_samples += counts.rejected;
+    }
+}
+
+/// Push samples onto the buffer and update the collector health
+async fn append_to_buffer(
+    buffer: &RwLock<VecDeque<MetricSample>>,
+    health: &RwLock<CollectorHealth>,
+    config: &MetricsCollectorConfig,
+    samples: &[MetricSample],
+) {
+    let mut buf = buffer.write().await;
+    let mut health = health.write().await;
+    for sample in samples {
+        buf.push_back(sample.clone());
+        if config.overflow_policy == BufferOverflowPolicy::EvictOldest
+            && buf.len() > config.buffer_size
+        {
+            buf.pop_front();
+            if health.evicted_samples == 0 {
+                log::warn!(
+                    "Metrics buffer is full ({} samples); the oldest samples are now being dropped",
+                    config.buffer_size
+                );
+                health.eviction_started_at = Some(Utc::now());
+            }
+            health.evicted_samples += 1;
+        }
+    }
+    health.buffered_samples = buf.len();
+}
@@ -4,11 +4,17 @@
 
 pub mod providers;
 pub mod collector;
+pub mod coalescer;
 pub mod models;
+pub mod tdigest;
 pub mod utils;
+pub mod validation;
 
-pub use collector::{MetricsCollector, MetricsCollectorConfig};
-pub use utils::{aggregate_metrics, percentile, MetricAggregation};
+pub use collector::{CollectorHealth, LivePercentiles, MetricsCollector, MetricsCollectorConfig, MetricsDelta};
+pub use utils::{
+    aggregate_metrics, aggregate_metrics_trimmed, derive_missing_metrics, harmonic_mean, mean,
+    normalize_memory_samples, normalize_memory_streams, parse_locale_number, percentile, trimmed_mean, MetricAggregation,
+};
 
 use std::sync::Arc;
 use std::sync::OnceLock;
@@ -17,8 +23,16 @@ use std::sync::OnceLock;
 static METRICS_COLLECTOR: OnceLock<Arc<MetricsCollector>> = OnceLock::new();
 
 /// Initialize the metrics collector
+///
+/// With the `synthetic` feature, setting `STATS_IO_SYNTHETIC_SEED` collects
+/// from the synthetic source instead of the real providers.
 pub fn init_metrics_collector(config: MetricsCollectorConfig) -> Arc<MetricsCollector> {
     METRICS_COLLECTOR.get_or_init(|| {
+        #[cfg(feature = "synthetic")]
+        if let Some(seed) = crate::synthetic::seed_from_env() {
+            log::info!("Collecting synthetic metrics with seed {}", seed);
+            return Arc::new(MetricsCollector::synthetic(config, seed));
+        }
         Arc::new(MetricsCollector::new(config))
     }).clone()
 }
@@ -10,6 +10,12 @@ pub struct CpuMetrics {
     pub overall_utilization: f64, // 0.0 - 1.0
     pub per_core_utilization: Vec<f64>,
     pub temperature: Option<f64>, // Celsius
+    #[serde(default)]
+    pub fan_speed_percent: Option<f64>, // Fan duty cycle 0-100
+    #[serde(default)]
+    pub fan_speed_rpm: Option<f64>,
+    #[serde(default)]
+    pub clock_mhz: Option<f64>, // Average current clock across cores
 }
 
 /// GPU metrics
@@ -18,10 +24,63 @@ pub struct GpuMetrics {
     pub utilization: f64, // 0.0 - 1.0
     pub vram_used_mb: Option<u64>,
     pub vram_total_mb: Option<u64>,
-    pub temperature: Option<f64>, // Celsius
+    pub temperature: Option<f64>, // Celsius, edge sensor
+    #[serde(default)]
+    pub hotspot_temperature: Option<f64>, // Celsius, hotspot/junction sensor
     pub clock_core_mhz: Option<f64>,
     pub clock_memory_mhz: Option<f64>,
     pub power_watts: Option<f64>,
+    #[serde(default)]
+    pub fan_speed_percent: Option<f64>, // Fan duty cycle 0-100
+    #[serde(default)]
+    pub pcie_link: Option<PcieLinkStatus>,
+    #[serde(default)]
+    pub memory_controller_load: Option<f64>, // Percent of time the memory controller was busy
+    /// Adapter the readings came from, as the driver names it
+    #[serde(default)]
+    pub adapter_name: Option<String>,
+}
+
+/// Utilization of one GPU adapter on a system with several
+///
+/// The adapter name is as the driver reports it and is matched against the
+/// detected GPU models, which may be less specific on some platforms.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuAdapterUtilization {
+    pub adapter_name: String,
+    pub utilization: f64, // 0.0 - 1.0
+}
+
+/// Current and maximum PCIe link of a GPU
+///
+/// The current link can drop below the maximum to save power at idle, or
+/// permanently when the slot is shared or wired for fewer lanes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcieLinkStatus {
+    pub gen_current: u32,
+    pub width_current: u32,
+    pub gen_max: u32,
+    pub width_max: u32,
+}
+
+/// Physical quantity measured by a hardware sensor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+}
+
+/// A single reading from a motherboard or component sensor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorReading {
+    /// Chip and sensor label, e.g. "nct6798 CPU Fan"
+    pub name: String,
+    pub kind: SensorKind,
+    pub value: f64,
+    /// "Celsius", "RPM" or "volts"
+    pub unit: String,
 }
 
 /// Memory metrics
@@ -42,6 +101,42 @@ pub struct StorageMetrics {
     pub latency_ms: Option<f64>,
 }
 
+/// System latency metrics (time spent servicing DPCs and interrupts)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LatencyMetrics {
+    pub dpc_time_percent: f64,
+    pub interrupt_time_percent: f64,
+}
+
+/// GPU usage of one process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessGpuUsage {
+    pub pid: u32,
+    /// Executable name, when the process could be looked up
+    pub name: Option<String>,
+    /// Utilization of the process's busiest GPU engine, 0-100
+    pub utilization_percent: f64,
+}
+
+/// CPU usage of one process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessCpuUsage {
+    pub pid: u32,
+    /// Executable name
+    pub name: String,
+    /// Share of total CPU capacity, 0-100
+    pub cpu_percent: f64,
+}
+
+/// Disk write rate of one process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessDiskUsage {
+    pub pid: u32,
+    /// Executable name
+    pub name: String,
+    pub write_mb_per_s: f64,
+}
+
 /// Workload KPIs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkloadKPIs {
@@ -38,13 +38,24 @@ impl CpuMetricsProvider for SysInfoCpuMetricsProvider {
             0.0
         };
         
+        // Platforms without frequency counters report 0 MHz
+        let clock_mhz = crate::metrics::utils::mean(
+            cpus.iter()
+                .map(|cpu| cpu.frequency() as f64)
+                .filter(|&mhz| mhz > 0.0),
+        );
+        
         // Get CPU temperature using platform-specific methods
         let temperature = get_cpu_temperature().await;
+        let (fan_speed_percent, fan_speed_rpm) = get_cpu_fan_speed().await;
         
         Ok(CpuMetrics {
             overall_utilization,
             per_core_utilization,
             temperature,
+            fan_speed_percent,
+            fan_speed_rpm,
+            clock_mhz,
         })
     }
     
@@ -131,3 +142,68 @@ async fn get_cpu_temperature() -> Option<f64> {
     None
 }
 
+/// Get CPU fan speed as (duty cycle percent, RPM) using platform-specific APIs
+#[cfg(target_os = "windows")]
+async fn get_cpu_fan_speed() -> (Option<f64>, Option<f64>) {
+    use wmi::WMIConnection;
+    
+    let wmi_con = match WMIConnection::new() {
+        Ok(con) => con,
+        Err(_) => return (None, None),
+    };
+    
+    // Win32_Fan is only populated by some vendors' ACPI implementations
+    let query = "SELECT DesiredSpeed FROM Win32_Fan";
+    if let Ok(results) = wmi_con.raw_query::<serde_json::Value>(query) {
+        for fan_obj in results {
+            if let Some(rpm) = fan_obj.get("DesiredSpeed").and_then(|v| v.as_u64()) {
+                if rpm > 0 {
+                    return (None, Some(rpm as f64));
+                }
+            }
+        }
+    }
+    
+    (None, None)
+}
+
+#[cfg(target_os = "linux")]
+async fn get_cpu_fan_speed() -> (Option<f64>, Option<f64>) {
+    // Motherboard sensor chips expose the CPU fan as fan1/pwm1 under hwmon
+    use tokio::fs;
+    
+    async fn read_value(path: &str) -> Option<f64> {
+        fs::read_to_string(path).await.ok()?.trim().parse::<f64>().ok()
+    }
+    
+    for hwmon_id in 0..16 {
+        let base = format!("/sys/class/hwmon/hwmon{}", hwmon_id);
+        let name = match fs::read_to_string(format!("{}/name", base)).await {
+            Ok(name) => name,
+            Err(_) => continue,
+        };
+        
+        // GPU drivers expose their own fans
+        if matches!(name.trim(), "amdgpu" | "radeon" | "nouveau") {
+            continue;
+        }
+        
+        let rpm = read_value(&format!("{}/fan1_input", base)).await;
+        // PWM duty cycle is reported as 0-255
+        let percent = read_value(&format!("{}/pwm1", base))
+            .await
+            .map(|pwm| (pwm / 255.0 * 100.0).clamp(0.0, 100.0));
+        
+        if rpm.is_some() || percent.is_some() {
+            return (percent, rpm);
+        }
+    }
+    
+    (None, None)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+async fn get_cpu_fan_speed() -> (Option<f64>, Option<f64>) {
+    // macOS fan speeds require the SMC library
+    (None, None)
+}
@@ -0,0 +1,95 @@
+//! Per-process CPU usage
+//!
+//! Total CPU utilization cannot tell a game that saturates the CPU on its own
+//! apart from one competing with background work. Sampling the CPU usage of
+//! each process, and always of the foreground application, lets the analysis
+//! attribute the load.
+
+use crate::core::domain::{MetricSample, MetricType};
+use crate::metrics::models::ProcessCpuUsage;
+use chrono::{DateTime, Utc};
+use sysinfo::System;
+
+/// Busiest processes kept per sample
+pub const CPU_PROCESS_TOP_N: usize = 5;
+
+/// Whether per-process CPU usage can be sampled on this platform
+pub fn is_supported() -> bool {
+    cfg!(any(target_os = "windows", target_os = "linux", target_os = "macos"))
+}
+
+/// Samples the CPU usage of every process between refreshes
+pub struct ProcessCpuSampler {
+    system: System,
+    logical_cpus: usize,
+}
+
+impl ProcessCpuSampler {
+    /// Create a sampler; the first call to `sample` measures from now
+    pub fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_processes();
+        Self {
+            system,
+            logical_cpus: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        }
+    }
+
+    /// CPU usage of the busiest processes since the previous sample
+    ///
+    /// `include` is kept even when it is not among the busiest, so the
+    /// foreground application is sampled every tick. Refreshing the process
+    /// list blocks, so call this off the async runtime.
+    pub fn sample(&mut self, include: Option<u32>) -> Vec<ProcessCpuUsage> {
+        self.system.refresh_processes();
+        let usage = self
+            .system
+            .processes()
+            .iter()
+            .map(|(pid, process)| ProcessCpuUsage {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                // sysinfo reports a share of one core; samples use a share of all cores
+                cpu_percent: (process.cpu_usage() as f64 / self.logical_cpus as f64).clamp(0.0, 100.0),
+            })
+            .collect();
+        busiest_processes(usage, include)
+    }
+}
+
+impl Default for ProcessCpuSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Busiest `CPU_PROCESS_TOP_N` processes, plus `include` when it is present
+///
+/// Idle processes are left out except for `include`. Returned busiest first.
+pub fn busiest_processes(mut usage: Vec<ProcessCpuUsage>, include: Option<u32>) -> Vec<ProcessCpuUsage> {
+    usage.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent).then(a.pid.cmp(&b.pid)));
+    let mut top: Vec<ProcessCpuUsage> = Vec::with_capacity(CPU_PROCESS_TOP_N + 1);
+    for process in usage {
+        let included = Some(process.pid) == include;
+        if included || (top.len() < CPU_PROCESS_TOP_N && process.cpu_percent > 0.0) {
+            top.push(process);
+        }
+    }
+    top
+}
+
+/// Sample source naming a process, e.g. "game.exe (pid 1234)"
+pub fn process_source(usage: &ProcessCpuUsage) -> String {
+    format!("{} (pid {})", usage.name, usage.pid)
+}
+
+/// Convert per-process CPU usage into samples for the metrics stream
+pub fn process_cpu_samples(usage: &[ProcessCpuUsage], timestamp: DateTime<Utc>) -> Vec<MetricSample> {
+    usage
+        .iter()
+        .map(|process| {
+            MetricSample::new(MetricType::ProcessCpuUtilization, process.cpu_percent, process_source(process))
+                .with_timestamp(timestamp)
+        })
+        .collect()
+}
@@ -5,55 +5,90 @@
 
 use crate::core::error::MetricsError;
 use crate::core::interfaces::GpuMetricsProvider;
-use crate::metrics::models::GpuMetrics;
+use crate::core::settings::GpuUtilizationSource;
+use crate::metrics::collector::match_gpu_model;
+use crate::metrics::models::{GpuAdapterUtilization, GpuMetrics, PcieLinkStatus};
+use crate::metrics::utils::parse_locale_number;
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 // Platform-specific modules are defined inline below
 
+/// Performance counter for per-engine utilization of the GPU 3D engines
+pub const GPU_ENGINE_3D_COUNTER: &str = r"\GPU Engine(*engtype_3D)\Utilization Percentage";
+
 /// GPU metrics provider
 /// 
 /// Platform-specific implementation that detects and uses the best available method
 /// for GPU metrics collection.
-pub struct GpuMetricsProviderImpl;
+pub struct GpuMetricsProviderImpl {
+    utilization_source: GpuUtilizationSource,
+    #[cfg(target_os = "windows")]
+    engine_counters: windows::EngineCounters,
+}
 
 impl GpuMetricsProviderImpl {
     pub fn new() -> Self {
-        Self
+        Self::with_utilization_source(GpuUtilizationSource::default())
+    }
+    
+    /// Create a provider reading utilization from the given source
+    ///
+    /// Engine counters are only available on Windows; other platforms always
+    /// use the driver source.
+    pub fn with_utilization_source(utilization_source: GpuUtilizationSource) -> Self {
+        Self {
+            utilization_source,
+            #[cfg(target_os = "windows")]
+            engine_counters: windows::EngineCounters::default(),
+        }
     }
 }
 
 #[async_trait]
 impl GpuMetricsProvider for GpuMetricsProviderImpl {
     async fn get_gpu_metrics(&self) -> Result<GpuMetrics, MetricsError> {
-        platform_get_gpu_metrics().await
+        #[cfg(target_os = "windows")]
+        return windows::get_gpu_metrics(self.utilization_source, &self.engine_counters).await;
+        #[cfg(not(target_os = "windows"))]
+        platform_get_gpu_metrics(self.utilization_source).await
+    }
+    
+    async fn get_adapter_utilization(&self) -> Result<Vec<GpuAdapterUtilization>, MetricsError> {
+        #[cfg(target_os = "windows")]
+        return windows::get_adapter_utilization(&self.engine_counters).await;
+        #[cfg(target_os = "linux")]
+        return Ok(linux::get_adapter_utilization().await);
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+        Ok(Vec::new())
     }
-}
-
-#[cfg(target_os = "windows")]
-async fn platform_get_gpu_metrics() -> Result<GpuMetrics, MetricsError> {
-    windows::get_gpu_metrics().await
 }
 
 #[cfg(target_os = "linux")]
-async fn platform_get_gpu_metrics() -> Result<GpuMetrics, MetricsError> {
+async fn platform_get_gpu_metrics(_source: GpuUtilizationSource) -> Result<GpuMetrics, MetricsError> {
     linux::get_gpu_metrics().await
 }
 
 #[cfg(target_os = "macos")]
-async fn platform_get_gpu_metrics() -> Result<GpuMetrics, MetricsError> {
+async fn platform_get_gpu_metrics(_source: GpuUtilizationSource) -> Result<GpuMetrics, MetricsError> {
     macos::get_gpu_metrics().await
 }
 
 #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
-async fn platform_get_gpu_metrics() -> Result<GpuMetrics, MetricsError> {
+async fn platform_get_gpu_metrics(_source: GpuUtilizationSource) -> Result<GpuMetrics, MetricsError> {
     Ok(GpuMetrics {
         utilization: 0.0,
         vram_used_mb: None,
         vram_total_mb: None,
         temperature: None,
+        hotspot_temperature: None,
         clock_core_mhz: None,
         clock_memory_mhz: None,
         power_watts: None,
+        fan_speed_percent: None,
+        pcie_link: None,
+        memory_controller_load: None,
+        adapter_name: None,
     })
 }
 
@@ -63,6 +98,128 @@ impl Default for GpuMetricsProviderImpl {
     }
 }
 
+/// Parse a `typeperf` CSV snapshot of the `GPU Engine` 3D utilization counters
+/// into the utilization of each GPU adapter
+///
+/// Each column is one engine instance per process, named like
+/// `pid_1234_luid_0x0_0xC6A3_phys_0_eng_0_engtype_3D`. Processes are summed per
+/// engine and each adapter reports its busiest engine as 0.0 - 1.0, matching
+/// Task Manager. Adapters are keyed by their LUID as formatted by
+/// [`adapter_luid_key`]; instances without a LUID are skipped.
+pub fn parse_engine_utilization_by_adapter(output: &str) -> HashMap<String, f64> {
+    let mut per_adapter: HashMap<String, f64> = HashMap::new();
+    for (engine, percent) in engine_percentages(output).unwrap_or_default() {
+        let luid = engine
+            .strip_prefix("luid_0x")
+            .and_then(|rest| rest.split_once("_0x"))
+            .and_then(|(high, rest)| {
+                let low = rest.split('_').next()?;
+                Some((u32::from_str_radix(high, 16).ok()?, u32::from_str_radix(low, 16).ok()?))
+            });
+        if let Some((high, low)) = luid {
+            let utilization = per_adapter.entry(adapter_luid_key(high, low)).or_insert(0.0);
+            *utilization = utilization.max(percent.clamp(0.0, 100.0) / 100.0);
+        }
+    }
+    per_adapter
+}
+
+/// Engine utilization of the adapter the driver metrics came from
+///
+/// `names` maps adapter LUIDs to their names. The adapter is found by
+/// matching `adapter_name` against those names, or taken as is when there is
+/// only one. An adapter without 3D engine instances is idle. Returns `None`
+/// when the adapter cannot be identified.
+pub fn adapter_engine_utilization(
+    by_luid: &HashMap<String, f64>,
+    names: &HashMap<String, String>,
+    adapter_name: Option<&str>,
+) -> Option<f64> {
+    let luid = if names.len() == 1 {
+        names.keys().next()?
+    } else {
+        let models: Vec<String> = names.values().cloned().collect();
+        let model = match_gpu_model(adapter_name?, &models)?;
+        names.iter().find(|(_, name)| *name == model).map(|(luid, _)| luid)?
+    };
+    Some(by_luid.get(luid).copied().unwrap_or(0.0))
+}
+
+/// Key identifying a GPU adapter by the high and low parts of its LUID
+pub fn adapter_luid_key(high: u32, low: u32) -> String {
+    format!("luid_0x{:08x}_0x{:08x}", high, low)
+}
+
+/// Percent utilization of each 3D engine, summed over processes
+///
+/// Engines are named from the LUID on, like `luid_0x0_0xC6A3_phys_0_eng_0_engtype_3D`.
+/// Returns `None` when the output has no header and data row.
+fn engine_percentages(output: &str) -> Option<HashMap<String, f64>> {
+    let rows: Vec<Vec<String>> = output
+        .lines()
+        .filter(|line| line.starts_with('"'))
+        .map(|line| {
+            line.split("\",\"")
+                .map(|field| field.trim_matches('"').to_string())
+                .collect()
+        })
+        .collect();
+    
+    let header = rows.first()?;
+    let data = rows.iter().skip(1).last()?;
+    
+    let mut per_engine: HashMap<String, f64> = HashMap::new();
+    for (column, value) in header.iter().zip(data).skip(1) {
+        let instance = match column.split_once("GPU Engine(").and_then(|(_, rest)| rest.split_once(')')) {
+            Some((instance, _)) => instance,
+            None => continue,
+        };
+        if !instance.to_lowercase().ends_with("engtype_3d") {
+            continue;
+        }
+        // Strip the pid prefix so processes on the same engine are summed
+        let engine = instance.find("luid_").map_or(instance, |idx| &instance[idx..]);
+        if let Some(value) = parse_locale_number(value) {
+            *per_engine.entry(engine.to_string()).or_insert(0.0) += value;
+        }
+    }
+    Some(per_engine)
+}
+
+/// Parse nvidia-smi `name,utilization.gpu` output, one line per GPU
+///
+/// Lines whose utilization is missing or `[N/A]` are skipped.
+pub fn parse_nvidia_adapter_utilization(output: &str) -> Vec<GpuAdapterUtilization> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, utilization) = line.rsplit_once(',')?;
+            let utilization = utilization.trim().parse::<f64>().ok()?;
+            Some(GpuAdapterUtilization {
+                adapter_name: name.trim().to_string(),
+                utilization: utilization.clamp(0.0, 100.0) / 100.0,
+            })
+        })
+        .filter(|adapter| !adapter.adapter_name.is_empty())
+        .collect()
+}
+
+/// Parse the nvidia-smi `pcie.link.gen.current,pcie.link.width.current,pcie.link.gen.max,pcie.link.width.max` fields
+///
+/// Returns `None` when any field is missing or reported as `[N/A]`.
+pub fn parse_pcie_link(fields: &[&str]) -> Option<PcieLinkStatus> {
+    let values: Vec<u32> = fields.iter().map(|f| f.trim().parse().ok()).collect::<Option<_>>()?;
+    match values[..] {
+        [gen_current, width_current, gen_max, width_max] => Some(PcieLinkStatus {
+            gen_current,
+            width_current,
+            gen_max,
+            width_max,
+        }),
+        _ => None,
+    }
+}
+
 // Keep PlaceholderGpuMetricsProvider for backward compatibility
 /// Placeholder GPU metrics provider (deprecated, use GpuMetricsProviderImpl)
 pub type PlaceholderGpuMetricsProvider = GpuMetricsProviderImpl;
@@ -70,12 +227,150 @@ pub type PlaceholderGpuMetricsProvider = GpuMetricsProviderImpl;
 #[cfg(target_os = "windows")]
 mod windows {
     use crate::core::error::MetricsError;
-    use crate::metrics::models::GpuMetrics;
+    use crate::core::settings::GpuUtilizationSource;
+    use crate::metrics::models::{GpuAdapterUtilization, GpuMetrics};
+    use crate::metrics::providers::subprocess::{output_with_retry, SUBPROCESS_TIMEOUT, TYPEPERF_TIMEOUT};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+    use tokio::sync::RwLock;
+    
+    /// How long the engine counters keep being sampled after the last read
+    const ENGINE_COUNTERS_IDLE: Duration = Duration::from_secs(5);
+    
+    /// Per-adapter 3D engine utilization, sampled in the background
+    ///
+    /// typeperf takes about a second per sample, which would stall every tick
+    /// of the sampling loop. A task started on the first read keeps the latest
+    /// snapshot, as the latency and per-process GPU counters do, and stops once
+    /// nobody has read it for `ENGINE_COUNTERS_IDLE`.
+    #[derive(Default)]
+    pub struct EngineCounters {
+        state: Arc<EngineCountersState>,
+    }
+    
+    struct EngineCountersState {
+        latest: RwLock<Option<HashMap<String, f64>>>,
+        last_read: Mutex<Instant>,
+        sampling: AtomicBool,
+    }
+    
+    impl Default for EngineCountersState {
+        fn default() -> Self {
+            Self {
+                latest: RwLock::new(None),
+                last_read: Mutex::new(Instant::now()),
+                sampling: AtomicBool::new(false),
+            }
+        }
+    }
+    
+    impl EngineCounters {
+        /// Latest snapshot by adapter LUID, or `None` until the first sample
+        pub async fn latest(&self) -> Option<HashMap<String, f64>> {
+            *self.state.last_read.lock().unwrap() = Instant::now();
+            if !self.state.sampling.swap(true, Ordering::SeqCst) {
+                let state = self.state.clone();
+                tokio::spawn(async move {
+                    while state.last_read.lock().unwrap().elapsed() < ENGINE_COUNTERS_IDLE {
+                        match query_engine_utilization().await {
+                            Ok(utilization) => *state.latest.write().await = Some(utilization),
+                            // A hung typeperf was killed; try again on the next pass
+                            Err(MetricsError::Timeout(e)) => log::debug!("GPU engine counters timed out: {}", e),
+                            Err(e) => {
+                                log::debug!("GPU engine counters unavailable: {}", e);
+                                break;
+                            }
+                        }
+                    }
+                    *state.latest.write().await = None;
+                    state.sampling.store(false, Ordering::SeqCst);
+                });
+            }
+            self.state.latest.read().await.clone()
+        }
+    }
+    
+    /// Get the 3D engine utilization of every adapter, named by DXGI
+    ///
+    /// Adapters without any 3D engine instance are idle and report zero.
+    pub async fn get_adapter_utilization(counters: &EngineCounters) -> Result<Vec<GpuAdapterUtilization>, MetricsError> {
+        let by_luid = match counters.latest().await {
+            Some(by_luid) => by_luid,
+            None => return Ok(Vec::new()),
+        };
+        let names = tokio::task::spawn_blocking(adapter_names_by_luid)
+            .await
+            .map_err(|e| MetricsError::CollectionFailed(format!("DXGI adapter query failed: {}", e)))?;
+        
+        Ok(names
+            .into_iter()
+            .map(|(luid, adapter_name)| GpuAdapterUtilization {
+                utilization: by_luid.get(&luid).copied().unwrap_or(0.0),
+                adapter_name,
+            })
+            .collect())
+    }
+    
+    /// Names of the hardware adapters DXGI enumerates, keyed by LUID
+    fn adapter_names_by_luid() -> HashMap<String, String> {
+        use ::windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1};
+        
+        let mut names = HashMap::new();
+        // SAFETY: DXGI enumeration only reads adapter descriptions into locals
+        let factory: IDXGIFactory1 = match unsafe { CreateDXGIFactory1() } {
+            Ok(factory) => factory,
+            Err(e) => {
+                log::debug!("DXGI factory unavailable: {}", e);
+                return names;
+            }
+        };
+        
+        let mut index = 0;
+        while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+            index += 1;
+            let desc = match unsafe { adapter.GetDesc1() } {
+                Ok(desc) => desc,
+                Err(_) => continue,
+            };
+            let len = desc.Description.iter().position(|&c| c == 0).unwrap_or(desc.Description.len());
+            let name = String::from_utf16_lossy(&desc.Description[..len]);
+            // The software rasterizer has no engine counters worth reporting
+            if name.to_uppercase().contains("MICROSOFT BASIC") {
+                continue;
+            }
+            let luid = super::adapter_luid_key(desc.AdapterLuid.HighPart as u32, desc.AdapterLuid.LowPart);
+            names.insert(luid, name);
+        }
+        names
+    }
     
     /// Get GPU metrics on Windows
     /// 
-    /// Tries nvidia-smi first for NVIDIA GPUs, then falls back to WMI
-    pub async fn get_gpu_metrics() -> Result<GpuMetrics, MetricsError> {
+    /// Tries nvidia-smi first for NVIDIA GPUs, then falls back to WMI. With the
+    /// 3D engine source, utilization is replaced by the busiest engine of the
+    /// same adapter from the latest background snapshot of the engine
+    /// counters, once there is one and the adapter can be identified.
+    pub async fn get_gpu_metrics(source: GpuUtilizationSource, counters: &EngineCounters) -> Result<GpuMetrics, MetricsError> {
+        let mut metrics = get_driver_metrics().await?;
+        if source == GpuUtilizationSource::Engine3d {
+            let Some(by_luid) = counters.latest().await else {
+                log::debug!("GPU engine counters not sampled yet");
+                return Ok(metrics);
+            };
+            let names = tokio::task::spawn_blocking(adapter_names_by_luid)
+                .await
+                .map_err(|e| MetricsError::CollectionFailed(format!("DXGI adapter query failed: {}", e)))?;
+            match super::adapter_engine_utilization(&by_luid, &names, metrics.adapter_name.as_deref()) {
+                Some(utilization) => metrics.utilization = utilization,
+                None => log::debug!("No GPU engine counters for adapter {:?}", metrics.adapter_name),
+            }
+        }
+        Ok(metrics)
+    }
+    
+    async fn get_driver_metrics() -> Result<GpuMetrics, MetricsError> {
         // Try nvidia-smi first (most accurate for NVIDIA GPUs)
         if let Ok(metrics) = get_nvidia_metrics().await {
             return Ok(metrics);
@@ -85,6 +380,20 @@ mod windows {
         get_wmi_gpu_metrics().await
     }
     
+    /// Get per-adapter 3D engine utilization from the `GPU Engine` performance counters
+    async fn query_engine_utilization() -> Result<HashMap<String, f64>, MetricsError> {
+        // Use CREATE_NO_WINDOW flag to prevent console window from appearing
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        
+        let mut cmd = tokio::process::Command::new("typeperf");
+        cmd.args([super::GPU_ENGINE_3D_COUNTER, "-sc", "1"])
+            .creation_flags(CREATE_NO_WINDOW);
+        
+        let output = output_with_retry(&mut cmd, TYPEPERF_TIMEOUT).await?;
+        Ok(super::parse_engine_utilization_by_adapter(&String::from_utf8_lossy(&output.stdout)))
+    }
+    
     /// Get NVIDIA GPU metrics using nvidia-smi
     async fn get_nvidia_metrics() -> Result<GpuMetrics, MetricsError> {
         // Use CREATE_NO_WINDOW flag to prevent console window from appearing
@@ -93,25 +402,19 @@ mod windows {
         
         let mut cmd = tokio::process::Command::new("nvidia-smi");
         cmd.args(&[
-            "--query-gpu=utilization.gpu,memory.used,memory.total,temperature.gpu,clocks.current.graphics,clocks.current.memory,power.draw",
+            "--query-gpu=utilization.gpu,memory.used,memory.total,temperature.gpu,clocks.current.graphics,clocks.current.memory,power.draw,fan.speed,pcie.link.gen.current,pcie.link.width.current,pcie.link.gen.max,pcie.link.width.max,utilization.memory,name",
             "--format=csv,noheader,nounits",
         ])
         .creation_flags(CREATE_NO_WINDOW);
         
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| MetricsError::CollectionFailed(format!("nvidia-smi failed: {}", e)))?;
-        
-        if !output.status.success() {
-            return Err(MetricsError::CollectionFailed("nvidia-smi command failed".to_string()));
-        }
+        let output = output_with_retry(&mut cmd, SUBPROCESS_TIMEOUT).await?;
         
         let output_str = String::from_utf8_lossy(&output.stdout);
         let line = output_str.lines().next()
             .ok_or_else(|| MetricsError::CollectionFailed("No output from nvidia-smi".to_string()))?;
         
-        // Parse CSV: utilization.gpu,memory.used,memory.total,temperature.gpu,clocks.current.graphics,clocks.current.memory,power.draw
+        // Parse CSV: utilization.gpu,memory.used,memory.total,temperature.gpu,clocks.current.graphics,clocks.current.memory,power.draw,fan.speed,
+        // pcie.link.gen.current,pcie.link.width.current,pcie.link.gen.max,pcie.link.width.max,utilization.memory,name
         let fields: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
         
         if fields.len() < 7 {
@@ -127,15 +430,26 @@ mod windows {
         let clock_core_mhz = fields[4].parse::<f64>().ok();
         let clock_memory_mhz = fields[5].parse::<f64>().ok();
         let power_watts = fields[6].parse::<f64>().ok();
+        // Reported as [N/A] on GPUs without a controllable fan
+        let fan_speed_percent = fields.get(7).and_then(|f| f.parse::<f64>().ok());
+        let pcie_link = fields.get(8..12).and_then(super::parse_pcie_link);
+        let memory_controller_load = fields.get(12).and_then(|f| f.parse::<f64>().ok());
+        let adapter_name = fields.get(13).filter(|f| !f.is_empty()).map(|f| f.to_string());
         
         Ok(GpuMetrics {
             utilization,
             vram_used_mb,
             vram_total_mb,
             temperature,
+            // nvidia-smi does not expose the hotspot sensor
+            hotspot_temperature: None,
             clock_core_mhz,
             clock_memory_mhz,
             power_watts,
+            fan_speed_percent,
+            pcie_link,
+            memory_controller_load,
+            adapter_name,
         })
     }
     
@@ -192,9 +506,14 @@ mod windows {
                         vram_used_mb: None, // Not available from WMI
                         vram_total_mb,
                         temperature: None, // Not available from WMI
+                        hotspot_temperature: None, // Not available from WMI
                         clock_core_mhz: None, // Not available from WMI
                         clock_memory_mhz: None, // Not available from WMI
                         power_watts: None, // Not available from WMI
+                        fan_speed_percent: None, // Not available from WMI
+                        pcie_link: None, // Not available from WMI
+                        memory_controller_load: None,
+                        adapter_name: Some(name),
                     });
                 }
             }
@@ -209,9 +528,14 @@ mod windows {
             vram_used_mb: None,
             vram_total_mb: None,
             temperature: None,
+            hotspot_temperature: None,
             clock_core_mhz: None,
             clock_memory_mhz: None,
             power_watts: None,
+            fan_speed_percent: None,
+            pcie_link: None,
+            memory_controller_load: None,
+            adapter_name: None,
         })
     }
 }
@@ -219,7 +543,47 @@ mod windows {
 #[cfg(target_os = "linux")]
 mod linux {
     use crate::core::error::MetricsError;
-    use crate::metrics::models::GpuMetrics;
+    use crate::metrics::models::{GpuAdapterUtilization, GpuMetrics};
+    use crate::metrics::providers::subprocess::{output_with_retry, SUBPROCESS_TIMEOUT};
+    
+    /// Get the utilization of every NVIDIA and AMD GPU
+    ///
+    /// amdgpu does not report the marketing name, so AMD GPUs without a
+    /// `product_name` file are reported by vendor alone.
+    pub async fn get_adapter_utilization() -> Vec<GpuAdapterUtilization> {
+        use tokio::fs;
+        
+        let mut adapters = Vec::new();
+        let mut cmd = tokio::process::Command::new("nvidia-smi");
+        cmd.args(["--query-gpu=name,utilization.gpu", "--format=csv,noheader,nounits"]);
+        if let Ok(output) = output_with_retry(&mut cmd, SUBPROCESS_TIMEOUT).await {
+            adapters.extend(super::parse_nvidia_adapter_utilization(&String::from_utf8_lossy(&output.stdout)));
+        }
+        
+        for card_id in 0..8 {
+            let device = format!("/sys/class/drm/card{}/device", card_id);
+            let busy = match fs::read_to_string(format!("{}/gpu_busy_percent", device)).await {
+                Ok(busy) => busy,
+                Err(_) => continue,
+            };
+            let percent = match busy.trim().parse::<f64>() {
+                Ok(percent) => percent,
+                Err(_) => continue,
+            };
+            let adapter_name = fs::read_to_string(format!("{}/product_name", device))
+                .await
+                .ok()
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| "AMD GPU".to_string());
+            adapters.push(GpuAdapterUtilization {
+                adapter_name,
+                utilization: percent.clamp(0.0, 100.0) / 100.0,
+            });
+        }
+        
+        adapters
+    }
     
     /// Get GPU metrics on Linux
     pub async fn get_gpu_metrics() -> Result<GpuMetrics, MetricsError> {
@@ -228,7 +592,11 @@ mod linux {
             return Ok(metrics);
         }
         
-        // TODO: Add AMD GPU support (radeontop, rocm-smi)
+        // AMD GPUs expose utilization and edge/junction temperatures in sysfs
+        if let Some(metrics) = get_amdgpu_metrics().await {
+            return Ok(metrics);
+        }
+        
         // TODO: Add Intel GPU support
         
         // Return zero metrics if no GPU detected
@@ -237,27 +605,101 @@ mod linux {
             vram_used_mb: None,
             vram_total_mb: None,
             temperature: None,
+            hotspot_temperature: None,
             clock_core_mhz: None,
             clock_memory_mhz: None,
             power_watts: None,
+            fan_speed_percent: None,
+            pcie_link: None,
+            memory_controller_load: None,
+            adapter_name: None,
         })
     }
     
+    /// Get AMD GPU metrics from the amdgpu driver's sysfs and hwmon files
+    async fn get_amdgpu_metrics() -> Option<GpuMetrics> {
+        use tokio::fs;
+        
+        async fn read_value(path: &str) -> Option<f64> {
+            fs::read_to_string(path).await.ok()?.trim().parse::<f64>().ok()
+        }
+        
+        for card_id in 0..8 {
+            let device = format!("/sys/class/drm/card{}/device", card_id);
+            let mut hwmon_dirs = match fs::read_dir(format!("{}/hwmon", device)).await {
+                Ok(dirs) => dirs,
+                Err(_) => continue,
+            };
+            
+            while let Ok(Some(entry)) = hwmon_dirs.next_entry().await {
+                let base = entry.path().to_string_lossy().to_string();
+                match fs::read_to_string(format!("{}/name", base)).await {
+                    Ok(name) if name.trim() == "amdgpu" => {}
+                    _ => continue,
+                }
+                
+                // Sensors are labelled "edge", "junction" (hotspot) and "mem"
+                let mut edge = None;
+                let mut junction = None;
+                for temp_id in 1..=4 {
+                    let label = match fs::read_to_string(format!("{}/temp{}_label", base, temp_id)).await {
+                        Ok(label) => label,
+                        Err(_) => continue,
+                    };
+                    let celsius = read_value(&format!("{}/temp{}_input", base, temp_id))
+                        .await
+                        .map(|millidegrees| millidegrees / 1000.0);
+                    match label.trim() {
+                        "edge" => edge = celsius,
+                        "junction" => junction = celsius,
+                        _ => {}
+                    }
+                }
+                
+                let utilization = read_value(&format!("{}/gpu_busy_percent", device))
+                    .await
+                    .unwrap_or(0.0)
+                    / 100.0;
+                let bytes_to_mb = |bytes: f64| (bytes / (1024.0 * 1024.0)) as u64;
+                let adapter_name = fs::read_to_string(format!("{}/product_name", device))
+                    .await
+                    .ok()
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| "AMD GPU".to_string());
+                
+                return Some(GpuMetrics {
+                    utilization,
+                    vram_used_mb: read_value(&format!("{}/mem_info_vram_used", device)).await.map(bytes_to_mb),
+                    vram_total_mb: read_value(&format!("{}/mem_info_vram_total", device)).await.map(bytes_to_mb),
+                    temperature: edge,
+                    hotspot_temperature: junction,
+                    clock_core_mhz: None,
+                    clock_memory_mhz: None,
+                    // Reported in microwatts
+                    power_watts: read_value(&format!("{}/power1_average", base)).await.map(|uw| uw / 1_000_000.0),
+                    fan_speed_percent: read_value(&format!("{}/pwm1", base))
+                        .await
+                        .map(|pwm| (pwm / 255.0 * 100.0).clamp(0.0, 100.0)),
+                    pcie_link: None,
+                    memory_controller_load: None,
+                    adapter_name: Some(adapter_name),
+                });
+            }
+        }
+        
+        None
+    }
+    
     async fn get_nvidia_metrics() -> Result<GpuMetrics, MetricsError> {
         // Same implementation as Windows
         // Note: On Linux, nvidia-smi doesn't spawn visible windows, but we keep it consistent
-        let output = tokio::process::Command::new("nvidia-smi")
-            .args(&[
-                "--query-gpu=utilization.gpu,memory.used,memory.total,temperature.gpu,clocks.current.graphics,clocks.current.memory,power.draw",
-                "--format=csv,noheader,nounits",
-            ])
-            .output()
-            .await
-            .map_err(|e| MetricsError::CollectionFailed(format!("nvidia-smi failed: {}", e)))?;
-        
-        if !output.status.success() {
-            return Err(MetricsError::CollectionFailed("nvidia-smi command failed".to_string()));
-        }
+        let mut cmd = tokio::process::Command::new("nvidia-smi");
+        cmd.args(&[
+            "--query-gpu=utilization.gpu,memory.used,memory.total,temperature.gpu,clocks.current.graphics,clocks.current.memory,power.draw,fan.speed,pcie.link.gen.current,pcie.link.width.current,pcie.link.gen.max,pcie.link.width.max,utilization.memory,name",
+            "--format=csv,noheader,nounits",
+        ]);
+        let output = output_with_retry(&mut cmd, SUBPROCESS_TIMEOUT).await?;
         
         let output_str = String::from_utf8_lossy(&output.stdout);
         let line = output_str.lines().next()
@@ -278,15 +720,26 @@ mod linux {
         let clock_core_mhz = fields[4].parse::<f64>().ok();
         let clock_memory_mhz = fields[5].parse::<f64>().ok();
         let power_watts = fields[6].parse::<f64>().ok();
+        // Reported as [N/A] on GPUs without a controllable fan
+        let fan_speed_percent = fields.get(7).and_then(|f| f.parse::<f64>().ok());
+        let pcie_link = fields.get(8..12).and_then(super::parse_pcie_link);
+        let memory_controller_load = fields.get(12).and_then(|f| f.parse::<f64>().ok());
+        let adapter_name = fields.get(13).filter(|f| !f.is_empty()).map(|f| f.to_string());
         
         Ok(GpuMetrics {
             utilization,
             vram_used_mb,
             vram_total_mb,
             temperature,
+            // nvidia-smi does not expose the hotspot sensor
+            hotspot_temperature: None,
             clock_core_mhz,
             clock_memory_mhz,
             power_watts,
+            fan_speed_percent,
+            pcie_link,
+            memory_controller_load,
+            adapter_name,
         })
     }
 }
@@ -306,9 +759,14 @@ mod macos {
             vram_used_mb: None,
             vram_total_mb: None,
             temperature: None,
+            hotspot_temperature: None,
             clock_core_mhz: None,
             clock_memory_mhz: None,
             power_watts: None,
+            fan_speed_percent: None,
+            pcie_link: None,
+            memory_controller_load: None,
+            adapter_name: None,
         })
     }
 }
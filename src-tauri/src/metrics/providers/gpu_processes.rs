@@ -0,0 +1,175 @@
+//! Per-process GPU usage
+//!
+//! The Windows `GPU Engine` performance counters have one instance per engine
+//! and process, with the PID in the instance name. Sampling them tells which
+//! process is keeping the GPU busy, so a GPU bottleneck can be attributed to
+//! the game rather than a background app. Other platforms report the provider
+//! as unavailable.
+
+use crate::core::domain::{MetricSample, MetricType};
+use crate::core::error::MetricsError;
+use crate::metrics::models::ProcessGpuUsage;
+use crate::metrics::utils::parse_locale_number;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Performance counter for the utilization of every GPU engine per process
+pub const GPU_ENGINE_COUNTER: &str = r"\GPU Engine(*)\Utilization Percentage";
+
+/// Busiest processes kept per sample
+pub const PROCESS_GPU_TOP_N: usize = 5;
+
+/// Whether per-process GPU usage can be sampled on this platform
+pub fn is_supported() -> bool {
+    cfg!(target_os = "windows")
+}
+
+/// Sample the GPU usage of the busiest processes
+///
+/// Takes one `typeperf` sample, which blocks for about a second while the
+/// counters accumulate.
+pub async fn get_process_gpu_usage() -> Result<Vec<ProcessGpuUsage>, MetricsError> {
+    platform_get_process_gpu_usage().await
+}
+
+#[cfg(target_os = "windows")]
+async fn platform_get_process_gpu_usage() -> Result<Vec<ProcessGpuUsage>, MetricsError> {
+    use super::subprocess::{output_with_retry, TYPEPERF_TIMEOUT};
+    // Use CREATE_NO_WINDOW flag to prevent console window from appearing
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let mut cmd = tokio::process::Command::new("typeperf");
+    cmd.args([GPU_ENGINE_COUNTER, "-sc", "1"])
+        .creation_flags(CREATE_NO_WINDOW);
+
+    let output = output_with_retry(&mut cmd, TYPEPERF_TIMEOUT).await?;
+
+    let mut usage = parse_process_gpu_usage(&String::from_utf8_lossy(&output.stdout));
+    usage.truncate(PROCESS_GPU_TOP_N);
+
+    let mut system = sysinfo::System::new();
+    system.refresh_processes();
+    for process in &mut usage {
+        process.name = system
+            .process(sysinfo::Pid::from_u32(process.pid))
+            .map(|p| p.name().to_string());
+    }
+    Ok(usage)
+}
+
+#[cfg(not(target_os = "windows"))]
+async fn platform_get_process_gpu_usage() -> Result<Vec<ProcessGpuUsage>, MetricsError> {
+    Err(MetricsError::ProviderNotAvailable(
+        "Per-process GPU counters are only available on Windows".to_string(),
+    ))
+}
+
+/// Parse a `typeperf` CSV snapshot of the `GPU Engine` utilization counters
+///
+/// Instances are named like `pid_1234_luid_0x0_0xC6A3_phys_0_eng_0_engtype_3D`.
+/// A process's usage is its busiest engine, matching Task Manager. Processes
+/// are returned busiest first, without names, from the last data row; idle
+/// processes are left out.
+pub fn parse_process_gpu_usage(output: &str) -> Vec<ProcessGpuUsage> {
+    let rows: Vec<Vec<String>> = output
+        .lines()
+        .filter(|line| line.starts_with('"'))
+        .map(|line| {
+            line.split("\",\"")
+                .map(|field| field.trim_matches('"').to_string())
+                .collect()
+        })
+        .collect();
+
+    let (Some(header), Some(data)) = (rows.first(), rows.iter().skip(1).last()) else {
+        return Vec::new();
+    };
+
+    let mut per_engine: HashMap<(u32, &str), f64> = HashMap::new();
+    for (column, value) in header.iter().zip(data).skip(1) {
+        let Some((instance, _)) = column.split_once("GPU Engine(").and_then(|(_, rest)| rest.split_once(')')) else {
+            continue;
+        };
+        let Some((pid, engine)) = instance
+            .strip_prefix("pid_")
+            .and_then(|rest| rest.split_once('_'))
+            .and_then(|(pid, engine)| Some((pid.parse::<u32>().ok()?, engine)))
+        else {
+            continue;
+        };
+        if let Some(value) = parse_locale_number(value) {
+            *per_engine.entry((pid, engine)).or_insert(0.0) += value;
+        }
+    }
+
+    let mut per_process: HashMap<u32, f64> = HashMap::new();
+    for ((pid, _), percent) in per_engine {
+        let busiest = per_process.entry(pid).or_insert(0.0);
+        *busiest = busiest.max(percent);
+    }
+
+    let mut usage: Vec<ProcessGpuUsage> = per_process
+        .into_iter()
+        .filter(|&(_, percent)| percent > 0.0)
+        .map(|(pid, percent)| ProcessGpuUsage {
+            pid,
+            name: None,
+            utilization_percent: percent.clamp(0.0, 100.0),
+        })
+        .collect();
+    usage.sort_by(|a, b| b.utilization_percent.total_cmp(&a.utilization_percent).then(a.pid.cmp(&b.pid)));
+    usage
+}
+
+/// Source component of a process's GPU usage samples, e.g. "game.exe (pid 1234)"
+pub fn process_source(usage: &ProcessGpuUsage) -> String {
+    match &usage.name {
+        Some(name) => format!("{} (pid {})", name, usage.pid),
+        None => format!("pid {}", usage.pid),
+    }
+}
+
+/// Convert per-process GPU usage into samples for the metrics stream
+pub fn process_gpu_samples(usage: &[ProcessGpuUsage], timestamp: DateTime<Utc>) -> Vec<MetricSample> {
+    usage
+        .iter()
+        .map(|process| {
+            MetricSample::new(MetricType::ProcessGpuUtilization, process.utilization_percent, process_source(process))
+                .with_timestamp(timestamp)
+        })
+        .collect()
+}
+
+/// Process with the highest average GPU usage between `start` and `end`
+///
+/// Only the busiest processes are sampled each tick, so a process is averaged
+/// over every sampled tick in the window and counts as idle in ticks where it
+/// is missing. Returns the sample source naming the process and its average
+/// usage, or `None` when no per-process samples fall in the window.
+pub fn top_gpu_process(
+    metrics: &[MetricSample],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Option<(String, f64)> {
+    let samples: Vec<&MetricSample> = metrics
+        .iter()
+        .filter(|m| {
+            m.metric_type == MetricType::ProcessGpuUtilization
+                && m.timestamp >= start
+                && m.timestamp <= end
+                && m.value.is_finite()
+        })
+        .collect();
+    let ticks: HashSet<DateTime<Utc>> = samples.iter().map(|m| m.timestamp).collect();
+
+    let mut totals: HashMap<&str, f64> = HashMap::new();
+    for sample in &samples {
+        *totals.entry(sample.source_component.as_str()).or_insert(0.0) += sample.value;
+    }
+
+    totals
+        .into_iter()
+        .map(|(source, total)| (source.to_string(), total / ticks.len() as f64))
+        .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+}
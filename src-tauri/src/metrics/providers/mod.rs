@@ -3,11 +3,18 @@
 //! This module contains implementations of metrics providers for different components.
 
 pub mod cpu;
+pub mod cpu_processes;
+pub mod disk_processes;
 pub mod gpu;
+pub mod gpu_processes;
+pub mod latency;
 pub mod memory;
+pub mod sensors;
 pub mod storage;
+pub mod subprocess;
 
 pub use cpu::SysInfoCpuMetricsProvider;
 pub use gpu::{GpuMetricsProviderImpl, PlaceholderGpuMetricsProvider};
 pub use memory::SysInfoMemoryMetricsProvider;
+pub use sensors::PlatformSensorProvider;
 pub use storage::SysInfoStorageMetricsProvider;
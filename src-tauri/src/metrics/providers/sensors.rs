@@ -0,0 +1,236 @@
+//! Hardware sensor provider
+//!
+//! Temperatures, fan speeds and voltages come from different sources per OS:
+//! LibreHardwareMonitor's WMI namespace or the standard CIM probes on Windows,
+//! `/sys/class/hwmon` on Linux, and the SMC on macOS. `PlatformSensorProvider`
+//! hides the differences behind `SensorProvider` and returns an empty list
+//! when no source is readable.
+
+use crate::core::domain::CoolingInfo;
+use crate::core::error::MetricsError;
+use crate::core::interfaces::SensorProvider;
+use crate::metrics::models::{SensorKind, SensorReading};
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Root of the Linux hardware monitoring sysfs tree
+pub const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+/// hwmon chips whose sensors are already reported by the GPU provider
+const GPU_HWMON_CHIPS: &[&str] = &["amdgpu", "radeon", "nouveau"];
+
+/// Sensor provider for the current platform
+#[derive(Debug, Default)]
+pub struct PlatformSensorProvider;
+
+impl PlatformSensorProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SensorProvider for PlatformSensorProvider {
+    async fn get_sensors(&self) -> Result<Vec<SensorReading>, MetricsError> {
+        read_platform_sensors().await
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn read_platform_sensors() -> Result<Vec<SensorReading>, MetricsError> {
+    tokio::task::spawn_blocking(|| read_hwmon_sensors(Path::new(HWMON_ROOT)))
+        .await
+        .map_err(|e| MetricsError::CollectionFailed(format!("hwmon read failed: {}", e)))
+}
+
+#[cfg(target_os = "windows")]
+async fn read_platform_sensors() -> Result<Vec<SensorReading>, MetricsError> {
+    tokio::task::spawn_blocking(read_wmi_sensors)
+        .await
+        .map_err(|e| MetricsError::CollectionFailed(format!("WMI sensor query failed: {}", e)))
+}
+
+/// macOS sensors live in the SMC, which needs IOKit bindings this build does not include
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+async fn read_platform_sensors() -> Result<Vec<SensorReading>, MetricsError> {
+    Ok(Vec::new())
+}
+
+/// Read every temperature, fan and voltage input below a hwmon root
+///
+/// Each `hwmonN` directory is one sensor chip. Sensors are named after the
+/// chip and their `*_label` file, falling back to the input name (e.g.
+/// "nct6798 fan2"). Unreadable files are skipped; a missing root yields an
+/// empty list.
+pub fn read_hwmon_sensors(root: &Path) -> Vec<SensorReading> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut chips: Vec<_> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect();
+    chips.sort();
+
+    let mut readings = Vec::new();
+    for chip_dir in chips {
+        let Some(chip) = read_trimmed(&chip_dir.join("name")) else {
+            continue;
+        };
+        if GPU_HWMON_CHIPS.contains(&chip.as_str()) {
+            continue;
+        }
+        let Ok(files) = std::fs::read_dir(&chip_dir) else {
+            continue;
+        };
+
+        let mut inputs: Vec<(SensorKind, &'static str, u32)> = files
+            .filter_map(|file| file.ok())
+            .filter_map(|file| parse_hwmon_input(&file.file_name().to_string_lossy()))
+            .collect();
+        inputs.sort_by_key(|&(_, prefix, index)| (prefix, index));
+
+        for (kind, prefix, index) in inputs {
+            let Some(raw) = read_trimmed(&chip_dir.join(format!("{}{}_input", prefix, index)))
+                .and_then(|value| value.parse::<f64>().ok())
+            else {
+                continue;
+            };
+            let (value, unit) = match kind {
+                SensorKind::Temperature => (raw / 1000.0, "Celsius"),
+                SensorKind::Fan => (raw, "RPM"),
+                SensorKind::Voltage => (raw / 1000.0, "volts"),
+            };
+            // Disconnected thermistors report nonsense such as -128 or 255 °C
+            if kind == SensorKind::Temperature && !(-40.0..=150.0).contains(&value) {
+                continue;
+            }
+            let label = read_trimmed(&chip_dir.join(format!("{}{}_label", prefix, index)))
+                .unwrap_or_else(|| format!("{}{}", prefix, index));
+            readings.push(SensorReading {
+                name: format!("{} {}", chip, label),
+                kind,
+                value,
+                unit: unit.to_string(),
+            });
+        }
+    }
+    readings
+}
+
+/// Split a hwmon file name such as `temp3_input` into its kind and index
+fn parse_hwmon_input(file_name: &str) -> Option<(SensorKind, &'static str, u32)> {
+    let stem = file_name.strip_suffix("_input")?;
+    [
+        (SensorKind::Temperature, "temp"),
+        (SensorKind::Fan, "fan"),
+        (SensorKind::Voltage, "in"),
+    ]
+    .into_iter()
+    .find_map(|(kind, prefix)| {
+        let index = stem.strip_prefix(prefix)?.parse().ok()?;
+        Some((kind, prefix, index))
+    })
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    let value = std::fs::read_to_string(path).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Read sensors from LibreHardwareMonitor, falling back to the CIM probes
+///
+/// LibreHardwareMonitor publishes its sensors under its own WMI namespace
+/// while it runs. The standard `Win32_TemperatureProbe`, `Win32_Fan` and
+/// `Win32_VoltageProbe` classes are only populated by some vendors' firmware.
+#[cfg(target_os = "windows")]
+fn read_wmi_sensors() -> Vec<SensorReading> {
+    use wmi::WMIConnection;
+
+    let lhm: Vec<SensorReading> = WMIConnection::with_namespace_path("root\\LibreHardwareMonitor")
+        .ok()
+        .and_then(|con| {
+            con.raw_query::<serde_json::Value>("SELECT Name, SensorType, Value FROM Sensor").ok()
+        })
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|sensor| {
+            let (kind, unit) = match sensor.get("SensorType")?.as_str()? {
+                "Temperature" => (SensorKind::Temperature, "Celsius"),
+                "Fan" => (SensorKind::Fan, "RPM"),
+                "Voltage" => (SensorKind::Voltage, "volts"),
+                _ => return None,
+            };
+            Some(SensorReading {
+                name: sensor.get("Name")?.as_str()?.to_string(),
+                kind,
+                value: sensor.get("Value")?.as_f64()?,
+                unit: unit.to_string(),
+            })
+        })
+        .collect();
+    if !lhm.is_empty() {
+        return lhm;
+    }
+
+    let Ok(wmi_con) = WMIConnection::new() else {
+        return Vec::new();
+    };
+    // (class, reading property, kind, unit, scale to the unit)
+    let probes = [
+        ("Win32_TemperatureProbe", "CurrentReading", SensorKind::Temperature, "Celsius", 0.1),
+        ("Win32_Fan", "DesiredSpeed", SensorKind::Fan, "RPM", 1.0),
+        ("Win32_VoltageProbe", "CurrentReading", SensorKind::Voltage, "volts", 0.001),
+    ];
+    let mut readings = Vec::new();
+    for (class, property, kind, unit, scale) in probes {
+        let query = format!("SELECT Name, {} FROM {}", property, class);
+        let Ok(results) = wmi_con.raw_query::<serde_json::Value>(&query) else {
+            continue;
+        };
+        for (idx, probe) in results.iter().enumerate() {
+            let Some(raw) = probe.get(property).and_then(|v| v.as_f64()) else {
+                continue;
+            };
+            let name = probe
+                .get("Name")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{} {}", class, idx));
+            readings.push(SensorReading {
+                name,
+                kind,
+                value: raw * scale,
+                unit: unit.to_string(),
+            });
+        }
+    }
+    readings
+}
+
+/// Summarize fan sensors as cooling information
+///
+/// The CPU fan is the one labelled "CPU", or the first fan when none is;
+/// every other spinning fan counts as a case fan. A fan labelled "pump"
+/// indicates liquid cooling. Returns `None` without fan sensors.
+pub fn cooling_info_from_sensors(readings: &[SensorReading]) -> Option<CoolingInfo> {
+    let fans: Vec<&SensorReading> = readings.iter().filter(|r| r.kind == SensorKind::Fan).collect();
+    if fans.is_empty() {
+        return None;
+    }
+
+    let name_contains = |reading: &SensorReading, needle: &str| reading.name.to_lowercase().contains(needle);
+    let cpu_fan = fans
+        .iter()
+        .position(|r| name_contains(r, "cpu"))
+        .unwrap_or(0);
+    let case_fans = fans
+        .iter()
+        .enumerate()
+        .filter(|&(idx, r)| idx != cpu_fan && r.value > 0.0 && !name_contains(r, "pump"))
+        .count();
+    let liquid = fans.iter().any(|r| name_contains(r, "pump"));
+
+    Some(CoolingInfo {
+        cpu_cooler_type: liquid.then(|| "Liquid".to_string()),
+        case_fans: Some(case_fans as u32),
+    })
+}
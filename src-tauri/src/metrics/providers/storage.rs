@@ -101,6 +101,7 @@ async fn platform_get_storage_metrics(
 #[cfg(target_os = "windows")]
 mod windows_impl {
     use super::*;
+    use crate::metrics::utils::parse_locale_number;
     use std::time::Instant;
     
     /// Get storage metrics on Windows using WMI Performance Counters
@@ -138,7 +139,7 @@ mod windows_impl {
                                 })
                                 .or_else(|| {
                                     // Try as string and parse
-                                    v.as_str().and_then(|s| s.parse::<u64>().ok())
+                                    v.as_str().and_then(parse_locale_number).map(|f| f as u64)
                                 })
                         })
                         .unwrap_or(0);
@@ -148,7 +149,7 @@ mod windows_impl {
                         .and_then(|v| {
                             v.as_u64()
                                 .or_else(|| v.as_f64().map(|f| f as u64))
-                                .or_else(|| v.as_str().and_then(|s| s.parse::<u64>().ok()))
+                                .or_else(|| v.as_str().and_then(parse_locale_number).map(|f| f as u64))
                         })
                         .unwrap_or(0);
                     
@@ -157,7 +158,7 @@ mod windows_impl {
                         .and_then(|v| {
                             v.as_u64()
                                 .or_else(|| v.as_f64().map(|f| f as u64))
-                                .or_else(|| v.as_str().and_then(|s| s.parse::<u64>().ok()))
+                                .or_else(|| v.as_str().and_then(parse_locale_number).map(|f| f as u64))
                         })
                         .map(|q| q as u32);
                     
@@ -0,0 +1,120 @@
+//! Subprocess execution for command-line providers
+//!
+//! `nvidia-smi` and `typeperf` can hang when a driver stops responding, which
+//! would stall the sampling tick awaiting them. Commands run here are bounded
+//! by a timeout, killed when it expires and retried once after a transient
+//! failure. Failures are counted so the collector can report them in its
+//! health.
+
+use crate::core::error::MetricsError;
+use std::process::{Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Time allowed for a quick query such as `nvidia-smi`
+pub const SUBPROCESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Time allowed for a one-sample `typeperf` run, which blocks for about a second
+pub const TYPEPERF_TIMEOUT: Duration = Duration::from_secs(3);
+
+static TIMEOUTS: AtomicU64 = AtomicU64::new(0);
+static FAILURES: AtomicU64 = AtomicU64::new(0);
+
+/// Subprocess failures recorded since the process started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubprocessFailures {
+    /// Runs killed after exceeding their timeout
+    pub timeouts: u64,
+    /// Runs that failed to start (other than missing programs) or exited unsuccessfully
+    pub failures: u64,
+}
+
+impl SubprocessFailures {
+    /// Failures recorded after the `baseline` snapshot
+    pub fn since(&self, baseline: &SubprocessFailures) -> SubprocessFailures {
+        SubprocessFailures {
+            timeouts: self.timeouts.saturating_sub(baseline.timeouts),
+            failures: self.failures.saturating_sub(baseline.failures),
+        }
+    }
+}
+
+/// Snapshot of the subprocess failure counters
+pub fn subprocess_failures() -> SubprocessFailures {
+    SubprocessFailures {
+        timeouts: TIMEOUTS.load(Ordering::Relaxed),
+        failures: FAILURES.load(Ordering::Relaxed),
+    }
+}
+
+/// Outcome of a single failed run
+enum RunFailure {
+    /// The program could not be started, e.g. because it is not installed
+    Spawn(std::io::Error),
+    /// The program ran but failed; worth another attempt
+    Transient(String),
+    /// The program was killed after exceeding the timeout
+    TimedOut(String),
+}
+
+impl From<RunFailure> for MetricsError {
+    fn from(failure: RunFailure) -> Self {
+        match failure {
+            RunFailure::Spawn(e) => MetricsError::Io(e),
+            RunFailure::Transient(message) => MetricsError::CollectionFailed(message),
+            RunFailure::TimedOut(message) => MetricsError::Timeout(message),
+        }
+    }
+}
+
+/// Run a command to completion, killing it if it exceeds `timeout`
+///
+/// A non-zero exit status is an error. Every failure is counted in
+/// `subprocess_failures`.
+pub async fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> Result<Output, MetricsError> {
+    run_once(cmd, timeout).await.map_err(MetricsError::from)
+}
+
+/// Run a command with a timeout, retrying once after a transient failure
+///
+/// Unsuccessful exits are retried. Timeouts are not, as a hung driver is
+/// unlikely to recover within the same tick, and neither are programs that
+/// cannot be started.
+pub async fn output_with_retry(cmd: &mut Command, timeout: Duration) -> Result<Output, MetricsError> {
+    match run_once(cmd, timeout).await {
+        Err(RunFailure::Transient(message)) => {
+            log::debug!("Retrying after transient failure: {}", message);
+            run_once(cmd, timeout).await.map_err(MetricsError::from)
+        }
+        result => result.map_err(MetricsError::from),
+    }
+}
+
+async fn run_once(cmd: &mut Command, timeout: Duration) -> Result<Output, RunFailure> {
+    let program = cmd.as_std().get_program().to_string_lossy().to_string();
+    // Dropping the child when the timeout expires kills it
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true);
+
+    let result = match cmd.spawn() {
+        Ok(child) => match tokio::time::timeout(timeout, child.wait_with_output()).await {
+            Ok(Ok(output)) if output.status.success() => return Ok(output),
+            Ok(Ok(output)) => Err(RunFailure::Transient(format!("{} command failed ({})", program, output.status))),
+            Ok(Err(e)) => Err(RunFailure::Transient(format!("{} failed: {}", program, e))),
+            Err(_) => Err(RunFailure::TimedOut(format!("{} did not finish within {:?}", program, timeout))),
+        },
+        Err(e) => Err(RunFailure::Spawn(e)),
+    };
+
+    // A missing program means the provider does not apply to this machine
+    match &result {
+        Err(RunFailure::TimedOut(_)) => {
+            TIMEOUTS.fetch_add(1, Ordering::Relaxed);
+        }
+        Err(RunFailure::Spawn(e)) if e.kind() == std::io::ErrorKind::NotFound => {}
+        _ => {
+            FAILURES.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    result
+}
@@ -0,0 +1,177 @@
+//! Streaming percentile estimation
+//!
+//! Exact percentiles need every sample kept and sorted per query, which the
+//! bounded metrics buffer cannot provide for long sessions. A t-digest keeps a
+//! fixed number of weighted centroids instead: dense near the tails, where p95
+//! and p99 live, and coarse around the median. Adding a sample is amortized
+//! O(1) and a query only walks the centroids, whose count is bounded by the
+//! compression regardless of how many samples were added.
+
+use std::f64::consts::PI;
+
+/// Default compression; higher values keep more centroids and are more accurate
+pub const DEFAULT_COMPRESSION: f64 = 100.0;
+
+/// Samples buffered per unit of compression before they are merged in
+const BUFFER_FACTOR: f64 = 5.0;
+
+/// A cluster of nearby samples summarized by their mean and count
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Merging t-digest over a stream of values
+#[derive(Debug, Clone)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    unmerged: Vec<f64>,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMPRESSION)
+    }
+}
+
+impl TDigest {
+    /// Create an empty digest with the given compression
+    pub fn new(compression: f64) -> Self {
+        Self {
+            compression: compression.max(1.0),
+            centroids: Vec::new(),
+            unmerged: Vec::new(),
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Add a value; non-finite values are ignored
+    pub fn add(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        self.unmerged.push(value);
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        if self.unmerged.len() as f64 >= self.compression * BUFFER_FACTOR {
+            self.centroids = self.merged();
+            self.unmerged.clear();
+        }
+    }
+
+    /// Number of values added
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Smallest value added
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Largest value added
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Estimate the `p`th percentile (0-100)
+    ///
+    /// Returns `None` for an empty digest or a percentile outside 0-100.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.count == 0 || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+
+        let merged;
+        let centroids = if self.unmerged.is_empty() {
+            &self.centroids
+        } else {
+            merged = self.merged();
+            &merged
+        };
+        if centroids.len() == 1 {
+            return Some(centroids[0].mean);
+        }
+
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+        let target = p / 100.0 * total;
+
+        // Each centroid's mean sits at the middle of its cumulative weight;
+        // values between two midpoints are interpolated linearly
+        let first = centroids[0];
+        if target < first.weight / 2.0 {
+            return Some(interpolate(self.min, first.mean, target / (first.weight / 2.0)));
+        }
+        let mut cumulative = 0.0;
+        for pair in centroids.windows(2) {
+            let left_mid = cumulative + pair[0].weight / 2.0;
+            let right_mid = cumulative + pair[0].weight + pair[1].weight / 2.0;
+            if target <= right_mid {
+                let fraction = (target - left_mid) / (right_mid - left_mid);
+                return Some(interpolate(pair[0].mean, pair[1].mean, fraction));
+            }
+            cumulative += pair[0].weight;
+        }
+        let last = centroids[centroids.len() - 1];
+        let last_mid = total - last.weight / 2.0;
+        let fraction = (target - last_mid) / (total - last_mid);
+        Some(interpolate(last.mean, self.max, fraction))
+    }
+
+    /// Merge the buffered values into the centroids
+    ///
+    /// Centroids are combined while they stay within one unit of the k1 scale
+    /// function, which keeps them small at the tails and larger in the middle.
+    fn merged(&self) -> Vec<Centroid> {
+        let mut all: Vec<Centroid> = self
+            .centroids
+            .iter()
+            .copied()
+            .chain(self.unmerged.iter().map(|&mean| Centroid { mean, weight: 1.0 }))
+            .collect();
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+        let mut result = Vec::with_capacity(self.compression as usize);
+        let mut iter = all.into_iter();
+        let Some(mut current) = iter.next() else {
+            return result;
+        };
+        let mut weight_before = 0.0;
+        let mut q_limit = self.q_limit(0.0);
+
+        for next in iter {
+            let q = (weight_before + current.weight + next.weight) / total;
+            if q <= q_limit {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_before += current.weight;
+                result.push(current);
+                q_limit = self.q_limit(weight_before / total);
+                current = next;
+            }
+        }
+        result.push(current);
+        result
+    }
+
+    /// Largest quantile a centroid starting at `q0` may extend to
+    fn q_limit(&self, q0: f64) -> f64 {
+        let k = self.compression / (2.0 * PI) * (2.0 * q0 - 1.0).asin();
+        (((k + 1.0) * 2.0 * PI / self.compression).min(PI / 2.0).sin() + 1.0) / 2.0
+    }
+}
+
+fn interpolate(from: f64, to: f64, fraction: f64) -> f64 {
+    from + (to - from) * fraction.clamp(0.0, 1.0)
+}
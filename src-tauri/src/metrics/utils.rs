@@ -2,21 +2,88 @@
 //!
 //! This module provides utility functions for metrics aggregation and analysis.
 
-use crate::core::domain::MetricSample;
-use std::collections::HashMap;
+use crate::core::domain::{unit_for, MetricSample, MetricType, Run};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
 
-/// Calculate percentile from a sorted vector of values
-pub fn percentile(values: &[f64], p: f64) -> Option<f64> {
-    if values.is_empty() {
+/// Arithmetic mean of the finite values
+///
+/// Returns `None` for an empty input or when no value is finite, so callers
+/// skip the metric instead of propagating NaN into reports.
+pub fn mean<I: IntoIterator<Item = f64>>(values: I) -> Option<f64> {
+    let (sum, count) = values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f64)
+    }
+}
+
+/// Harmonic mean of the finite, positive values, or `None` if there are none
+///
+/// For rates such as FPS this equals averaging the per-frame times and
+/// inverting the result, so slow frames weigh as much as they last.
+pub fn harmonic_mean<I: IntoIterator<Item = f64>>(values: I) -> Option<f64> {
+    let (reciprocal_sum, count) = values
+        .into_iter()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .fold((0.0, 0usize), |(sum, count), v| (sum + 1.0 / v, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(count as f64 / reciprocal_sum)
+    }
+}
+
+/// Sample standard deviation of the finite values
+///
+/// Returns `None` when fewer than two values are finite.
+pub fn std_dev(values: &[f64]) -> Option<f64> {
+    let finite: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.len() < 2 {
+        return None;
+    }
+    let avg = mean(finite.iter().copied())?;
+    let variance = finite.iter().map(|v| (v - avg).powi(2)).sum::<f64>() / (finite.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+/// Mean of the finite values after dropping the most extreme ones
+///
+/// `trim_fraction` is the share of values dropped from each end (0.0 - 0.5),
+/// so 0.05 ignores the lowest and highest 5%. At least one value is always
+/// kept. A fraction of 0 is exactly `mean`.
+pub fn trimmed_mean(values: &[f64], trim_fraction: f64) -> Option<f64> {
+    if trim_fraction.is_nan() || trim_fraction <= 0.0 {
+        return mean(values.iter().copied());
+    }
+    
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.is_empty() {
         return None;
     }
+    sorted.sort_by(f64::total_cmp);
     
+    let trim = ((sorted.len() as f64 * trim_fraction.min(0.5)).floor() as usize).min((sorted.len() - 1) / 2);
+    mean(sorted[trim..sorted.len() - trim].iter().copied())
+}
+
+/// Calculate percentile from a vector of values
+///
+/// Non-finite values are ignored.
+pub fn percentile(values: &[f64], p: f64) -> Option<f64> {
     if p < 0.0 || p > 100.0 {
         return None;
     }
     
-    let mut sorted = values.to_vec();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(f64::total_cmp);
     
     let index = (p / 100.0) * (sorted.len() - 1) as f64;
     let lower = index.floor() as usize;
@@ -30,28 +97,191 @@ pub fn percentile(values: &[f64], p: f64) -> Option<f64> {
     }
 }
 
+/// Parse a number written with either `.` or `,` as the decimal separator
+///
+/// Tools such as `typeperf` format numbers for the user's locale, so "1234.56"
+/// may arrive as "1234,56" or "1.234,56". When both separators appear the
+/// last one is the decimal separator. A lone separator is decimal unless it
+/// repeats ("1,234,567"). Spaces, non-breaking spaces and apostrophes are
+/// accepted as thousands separators. Values that only parse this way are
+/// logged at debug level.
+pub fn parse_locale_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if let Ok(value) = text.parse::<f64>() {
+        return Some(value);
+    }
+    
+    let digits: String = text
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\u{a0}' | '\u{202f}' | '\''))
+        .collect();
+    let decimal = match (digits.rfind('.'), digits.rfind(',')) {
+        (Some(dot), Some(comma)) => {
+            let separator = if dot > comma { '.' } else { ',' };
+            if digits.matches(separator).count() > 1 {
+                return None;
+            }
+            Some(dot.max(comma))
+        }
+        (Some(idx), None) | (None, Some(idx)) => {
+            let separator = digits.as_bytes()[idx] as char;
+            (digits.matches(separator).count() == 1).then_some(idx)
+        }
+        (None, None) => None,
+    };
+    let normalized: String = digits
+        .char_indices()
+        .filter_map(|(idx, c)| match c {
+            '.' | ',' if Some(idx) == decimal => Some('.'),
+            '.' | ',' => None,
+            c => Some(c),
+        })
+        .collect();
+    
+    let value = normalized.parse::<f64>().ok()?;
+    log::debug!("Parsed '{}' as {} with locale fallback", text, value);
+    Some(value)
+}
+
+/// Size of an absolute memory unit in MB, or `None` for percentages and unknown units
+fn memory_unit_mb(unit: &str) -> Option<f64> {
+    match unit.trim().to_ascii_lowercase().as_str() {
+        "b" | "bytes" => Some(1.0 / (1024.0 * 1024.0)),
+        "kb" | "kib" => Some(1.0 / 1024.0),
+        "mb" | "mib" => Some(1.0),
+        "gb" | "gib" => Some(1024.0),
+        _ => None,
+    }
+}
+
+/// Express RAM usage samples both as a percent of total and in MB
+///
+/// `MemoryUsage` is a percentage, but imported data sometimes records it in
+/// absolute units. Those samples are converted to `MemoryUsed` in MB so the
+/// RAM detector never reads megabytes as percent. With `total_mb` known,
+/// every timestamp that has only one of the two representations gets the
+/// other added; without it, samples are only relabelled.
+pub fn normalize_memory_samples(samples: &mut Vec<MetricSample>, total_mb: Option<u64>) {
+    normalize_memory_in(vec![samples], total_mb);
+}
+
+/// `normalize_memory_samples` across every stream of a run
+///
+/// A timestamp counts as having a representation when any stream holds it,
+/// so runs that keep percent and MB in separate streams gain no duplicates.
+/// Added samples go to the stream of the sample they were derived from.
+pub fn normalize_memory_streams(streams: &mut HashMap<String, Vec<MetricSample>>, total_mb: Option<u64>) {
+    normalize_memory_in(streams.values_mut().collect(), total_mb);
+}
+
+fn normalize_memory_in(mut streams: Vec<&mut Vec<MetricSample>>, total_mb: Option<u64>) {
+    for sample in streams.iter_mut().flat_map(|samples| samples.iter_mut()) {
+        if !matches!(sample.metric_type, MetricType::MemoryUsage | MetricType::MemoryUsed) {
+            continue;
+        }
+        if let Some(scale) = memory_unit_mb(&sample.unit) {
+            sample.metric_type = MetricType::MemoryUsed;
+            sample.value *= scale;
+            sample.unit = unit_for(&MetricType::MemoryUsed).to_string();
+        }
+    }
+    
+    let Some(total) = total_mb.filter(|&t| t > 0).map(|t| t as f64) else {
+        return;
+    };
+    let timestamps_of = |metric_type: MetricType| -> HashSet<DateTime<Utc>> {
+        streams
+            .iter()
+            .flat_map(|samples| samples.iter())
+            .filter(|s| s.metric_type == metric_type)
+            .map(|s| s.timestamp)
+            .collect()
+    };
+    let percent_at = timestamps_of(MetricType::MemoryUsage);
+    let used_at = timestamps_of(MetricType::MemoryUsed);
+    
+    for samples in streams {
+        let companions: Vec<MetricSample> = samples
+            .iter()
+            .filter_map(|sample| {
+                let (metric_type, value) = match sample.metric_type {
+                    MetricType::MemoryUsed if !percent_at.contains(&sample.timestamp) => {
+                        (MetricType::MemoryUsage, sample.value / total * 100.0)
+                    }
+                    MetricType::MemoryUsage if !used_at.contains(&sample.timestamp) => {
+                        (MetricType::MemoryUsed, sample.value / 100.0 * total)
+                    }
+                    _ => return None,
+                };
+                Some(MetricSample::new(metric_type, value, sample.source_component.clone()).with_timestamp(sample.timestamp))
+            })
+            .collect();
+        samples.extend(companions);
+    }
+}
+
+/// Derive FPS from frame times, or frame times from FPS, when a run lacks one
+///
+/// Imported logs often carry only one of the two. When a run has `FrameTime`
+/// samples but no `Fps` samples, each frame time gets an instantaneous FPS
+/// sample (1000 / ms) at the same timestamp, and vice versa. Derived samples
+/// go to their own stream. Zero, negative, and non-finite readings are
+/// skipped. Returns the number of samples added.
+pub fn derive_missing_metrics(run: &mut Run) -> usize {
+    let has = |metric_type: MetricType| run.metrics_streams.values().flatten().any(|s| s.metric_type == metric_type);
+    let (from, to, stream) = match (has(MetricType::FrameTime), has(MetricType::Fps)) {
+        (true, false) => (MetricType::FrameTime, MetricType::Fps, "fps"),
+        (false, true) => (MetricType::Fps, MetricType::FrameTime, "frame_time"),
+        _ => return 0,
+    };
+    
+    let mut derived: Vec<MetricSample> = run
+        .metrics_streams
+        .values()
+        .flatten()
+        .filter(|s| s.metric_type == from && s.value.is_finite() && s.value > 0.0)
+        .map(|s| MetricSample::new(to.clone(), 1000.0 / s.value, s.source_component.clone()).with_timestamp(s.timestamp))
+        .collect();
+    derived.sort_by_key(|s| s.timestamp);
+    
+    let added = derived.len();
+    if added > 0 {
+        run.metrics_streams.entry(stream.to_string()).or_default().extend(derived);
+    }
+    added
+}
+
 /// Aggregate metrics by type
 pub fn aggregate_metrics(metrics: &[MetricSample]) -> HashMap<String, MetricAggregation> {
+    aggregate_metrics_trimmed(metrics, 0.0)
+}
+
+/// Aggregate metrics by type, with a trimmed mean alongside the plain one
+///
+/// See `trimmed_mean` for `trim_fraction`.
+pub fn aggregate_metrics_trimmed(metrics: &[MetricSample], trim_fraction: f64) -> HashMap<String, MetricAggregation> {
     let mut grouped: HashMap<String, Vec<f64>> = HashMap::new();
     
-    for metric in metrics {
+    // Non-finite readings are dropped; a metric with none left is skipped
+    for metric in metrics.iter().filter(|m| m.value.is_finite()) {
         let key = format!("{:?}", metric.metric_type);
         grouped.entry(key).or_insert_with(Vec::new).push(metric.value);
     }
     
     grouped
         .into_iter()
-        .map(|(key, values)| {
+        .filter_map(|(key, values)| {
             let aggregation = MetricAggregation {
                 min: values.iter().cloned().fold(f64::INFINITY, f64::min),
                 max: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
-                avg: values.iter().sum::<f64>() / values.len() as f64,
+                avg: mean(values.iter().copied())?,
+                trimmed_avg: trimmed_mean(&values, trim_fraction)?,
                 p50: percentile(&values, 50.0),
                 p95: percentile(&values, 95.0),
                 p99: percentile(&values, 99.0),
                 count: values.len(),
             };
-            (key, aggregation)
+            Some((key, aggregation))
         })
         .collect()
 }
@@ -62,6 +292,9 @@ pub struct MetricAggregation {
     pub min: f64,
     pub max: f64,
     pub avg: f64,
+    /// Mean without the most extreme samples; equals `avg` when nothing is trimmed
+    #[serde(default)]
+    pub trimmed_avg: f64,
     pub p50: Option<f64>,
     pub p95: Option<f64>,
     pub p99: Option<f64>,
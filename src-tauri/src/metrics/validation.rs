@@ -0,0 +1,106 @@
+//! Plausibility checks for collected samples
+//!
+//! Faulty sensors and driver counters occasionally report negative
+//! temperatures, utilization above 100% or clocks no chip can reach. Left in,
+//! these values end up as evidence and drive recommendations. Each sample is
+//! checked against the physical range of its metric type when it is
+//! collected: small overshoots of a percentage are clamped, everything else
+//! outside the range is dropped.
+
+use crate::core::domain::{MetricSample, MetricType};
+use std::ops::RangeInclusive;
+
+/// Temperatures a working sensor can report, in Celsius
+pub const PLAUSIBLE_TEMPERATURE_C: RangeInclusive<f64> = 0.0..=150.0;
+
+/// Highest plausible CPU or GPU clock in MHz
+pub const MAX_PLAUSIBLE_CLOCK_MHZ: f64 = 10_000.0;
+
+/// Highest percentage clamped to 100 rather than rejected
+///
+/// Utilization counters summed across engines or sampled over a slightly
+/// longer window than they cover overshoot 100%, but not by half again.
+pub const MAX_CLAMPED_PERCENT: f64 = 150.0;
+
+/// Outcome of checking one sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plausibility {
+    /// Within the metric type's range
+    Valid,
+    /// Pulled back to the edge of the range
+    Clamped,
+    /// Not a reading the hardware could produce
+    Rejected,
+}
+
+/// Number of samples clamped and rejected by `validate_samples`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationCounts {
+    pub clamped: u64,
+    pub rejected: u64,
+}
+
+/// Check a sample against the physical range of its metric type,
+/// clamping its value where that is safe
+///
+/// Percentages are judged by the sample's unit, so fan speeds recorded in
+/// RPM are not mistaken for a duty cycle.
+pub fn validate_sample(sample: &mut MetricSample) -> Plausibility {
+    let value = sample.value;
+    if !value.is_finite() {
+        return Plausibility::Rejected;
+    }
+    
+    if sample.unit == "percent" {
+        return if !(0.0..=MAX_CLAMPED_PERCENT).contains(&value) {
+            Plausibility::Rejected
+        } else if value > 100.0 {
+            sample.value = 100.0;
+            Plausibility::Clamped
+        } else {
+            Plausibility::Valid
+        };
+    }
+    
+    let plausible = match sample.metric_type {
+        MetricType::Temperature
+        | MetricType::GpuTemperature
+        | MetricType::GpuHotspotTemperature
+        | MetricType::SensorTemperature => {
+            PLAUSIBLE_TEMPERATURE_C.contains(&value)
+        }
+        MetricType::CpuClock | MetricType::GpuClock => (0.0..=MAX_PLAUSIBLE_CLOCK_MHZ).contains(&value),
+        // Voltages are signed on some rails
+        MetricType::Voltage => true,
+        _ => value >= 0.0,
+    };
+    if plausible {
+        Plausibility::Valid
+    } else {
+        Plausibility::Rejected
+    }
+}
+
+/// Clamp or drop implausible samples, returning how many were affected
+pub fn validate_samples(samples: &mut Vec<MetricSample>) -> ValidationCounts {
+    let mut counts = ValidationCounts::default();
+    samples.retain_mut(|sample| match validate_sample(sample) {
+        Plausibility::Valid => true,
+        Plausibility::Clamped => {
+            counts.clamped += 1;
+            true
+        }
+        Plausibility::Rejected => {
+            log::debug!(
+                "Dropped implausible {:?} reading of {} {} from {}",
+                sample.metric_type,
+                sample.value,
+                sample.unit,
+                sample.source_component
+            );
+            counts.rejected += 1;
+            false
+        }
+    });
+    counts
+}
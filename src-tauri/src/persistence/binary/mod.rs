@@ -0,0 +1,114 @@
+//! Binary session format
+//!
+//! Large sessions are dominated by metric samples, and parsing them from JSON
+//! is the slowest part of loading. `.stio` files hold the same domain types
+//! encoded with bincode behind a short header. JSON remains the default and
+//! the format used for sharing.
+
+use crate::core::domain::Session;
+use crate::core::error::PersistenceError;
+use crate::persistence::migration::migrate_session_samples;
+use std::path::Path;
+use tokio::fs;
+
+/// File extension that selects the binary format
+pub const BINARY_SESSION_EXTENSION: &str = "stio";
+
+/// Leading bytes of every binary session file
+pub const BINARY_SESSION_MAGIC: &[u8; 4] = b"STIO";
+
+/// Current version of the binary session layout
+///
+/// Bincode records enum variants by position and omits field names, so this
+/// must be bumped whenever a stored domain type changes shape.
+pub const BINARY_SESSION_VERSION: u32 = 2;
+
+/// Magic bytes followed by a little-endian version
+const HEADER_LEN: usize = 8;
+
+/// Encode a session with the binary header
+pub fn encode_session(session: &Session) -> Result<Vec<u8>, PersistenceError> {
+    let body = bincode::serialize(session)
+        .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+    
+    let mut bytes = Vec::with_capacity(HEADER_LEN + body.len());
+    bytes.extend_from_slice(BINARY_SESSION_MAGIC);
+    bytes.extend_from_slice(&BINARY_SESSION_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&body);
+    Ok(bytes)
+}
+
+/// Decode a session written by `encode_session`
+///
+/// Files from a newer layout are rejected rather than misread.
+pub fn decode_session(bytes: &[u8]) -> Result<Session, PersistenceError> {
+    if bytes.len() < HEADER_LEN || &bytes[..4] != BINARY_SESSION_MAGIC {
+        return Err(PersistenceError::Deserialization(
+            "Not a binary session file".to_string(),
+        ));
+    }
+    
+    let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    if version != BINARY_SESSION_VERSION {
+        return Err(PersistenceError::SchemaVersionMismatch {
+            expected: BINARY_SESSION_VERSION,
+            found: version,
+        });
+    }
+    
+    let mut session: Session = bincode::deserialize(&bytes[HEADER_LEN..])
+        .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+    migrate_session_samples(&mut session);
+    Ok(session)
+}
+
+/// Save a session in the binary format
+pub async fn save_session_binary(session: &Session, path: &Path) -> Result<(), PersistenceError> {
+    let bytes = encode_session(session)?;
+    fs::write(path, bytes)
+        .await
+        .map_err(PersistenceError::Io)
+}
+
+/// Load a session saved in the binary format
+pub async fn load_session_binary(path: &Path) -> Result<Session, PersistenceError> {
+    let bytes = fs::read(path)
+        .await
+        .map_err(PersistenceError::Io)?;
+    decode_session(&bytes)
+}
+
+/// Whether a path selects the binary format
+pub fn is_binary_session_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(BINARY_SESSION_EXTENSION))
+}
+
+/// Save a session, choosing the format from the file extension
+///
+/// `.stio` paths are written in the binary format; anything else is JSON.
+pub async fn save_session_file(session: &Session, path: &Path) -> Result<(), PersistenceError> {
+    if is_binary_session_path(path) {
+        return save_session_binary(session, path).await;
+    }
+    
+    let json = serde_json::to_string_pretty(session)
+        .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+    fs::write(path, json)
+        .await
+        .map_err(PersistenceError::Io)
+}
+
+/// Load a session, choosing the format from the file extension
+pub async fn load_session_file(path: &Path) -> Result<Session, PersistenceError> {
+    if is_binary_session_path(path) {
+        return load_session_binary(path).await;
+    }
+    
+    let content = fs::read_to_string(path)
+        .await
+        .map_err(PersistenceError::Io)?;
+    serde_json::from_str(&content)
+        .map_err(|e| PersistenceError::Deserialization(e.to_string()))
+}
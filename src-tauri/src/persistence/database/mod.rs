@@ -3,10 +3,17 @@
 //! This module provides SQLite-based persistence for large datasets,
 //! following IMPLEMENTATION_PLAN.md Phase 4.3.
 
-use crate::core::domain::{Run, Session};
+use crate::core::domain::{MetricSample, MetricType, Run, Session, WorkloadType, GPU_HOTSPOT_SOURCE};
 use crate::core::error::PersistenceError;
+use crate::persistence::decimation::DecimationPolicy;
+use crate::persistence::export_import::{resolve_import_conflict, ImportConflictPolicy, ImportOutcome};
+use crate::persistence::migration::migrate_hotspot_samples;
+use crate::persistence::models::{RunMatch, SessionSummary};
+use crate::persistence::retention::{RetentionPolicy, RetentionStats};
 use chrono::{DateTime, Utc};
 use rusqlite::{Connection, params};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
@@ -14,6 +21,8 @@ use std::sync::{Arc, Mutex};
 /// SQLite database manager for sessions and runs
 pub struct DatabaseStorage {
     conn: Arc<Mutex<Connection>>,
+    /// Applied to the metric streams of every run saved
+    decimation: DecimationPolicy,
 }
 
 impl DatabaseStorage {
@@ -24,12 +33,19 @@ impl DatabaseStorage {
         
         let storage = Self {
             conn: Arc::new(Mutex::new(conn)),
+            decimation: DecimationPolicy::default(),
         };
         
         storage.init_schema()?;
         Ok(storage)
     }
     
+    /// Decimate the metric streams of runs with `policy` when they are saved
+    pub fn with_decimation_policy(mut self, policy: DecimationPolicy) -> Self {
+        self.decimation = policy;
+        self
+    }
+    
     /// Initialize database schema
     fn init_schema(&self) -> Result<(), PersistenceError> {
         let conn = self.conn.lock().unwrap();
@@ -61,11 +77,17 @@ impl DatabaseStorage {
                 analysis_result TEXT,
                 notes TEXT,
                 created_at TEXT NOT NULL,
+                environment TEXT,
                 FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
             )",
             [],
         )?;
         
+        // Databases created before run environments were captured lack the column
+        if conn.prepare("SELECT environment FROM runs LIMIT 0").is_err() {
+            conn.execute("ALTER TABLE runs ADD COLUMN environment TEXT", [])?;
+        }
+        
         // Metrics table for efficient querying
         conn.execute(
             "CREATE TABLE IF NOT EXISTS metrics (
@@ -107,6 +129,64 @@ impl DatabaseStorage {
             [],
         )?;
         
+        // Full-text index over run names and notes
+        conn.execute(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS runs_fts USING fts5(
+                run_id UNINDEXED,
+                session_id UNINDEXED,
+                name,
+                notes
+            )",
+            [],
+        )?;
+        
+        // Older versions stored enum columns under their Debug names
+        normalize_enum_column::<WorkloadType>(&conn, "sessions", "profile_type")?;
+        normalize_enum_column::<MetricType>(&conn, "metrics", "metric_type")?;
+        
+        // Older versions stored hotspot readings as GPU temperature under the hotspot source
+        conn.execute(
+            "UPDATE metrics SET metric_type = ?1 WHERE metric_type = ?2 AND source_component = ?3",
+            params![
+                enum_to_column(&MetricType::GpuHotspotTemperature)?,
+                enum_to_column(&MetricType::GpuTemperature)?,
+                GPU_HOTSPOT_SOURCE
+            ],
+        )?;
+        
+        // Databases created before samples were unique may hold duplicates,
+        // which would keep the unique index from being created
+        let has_unique_index: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'index' AND name = 'idx_metrics_unique_sample')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_unique_index {
+            let removed = conn.execute(
+                "DELETE FROM metrics WHERE id NOT IN (
+                    SELECT MIN(id) FROM metrics
+                    GROUP BY run_id, timestamp, metric_type, source_component
+                )",
+                [],
+            )?;
+            if removed > 0 {
+                log::info!("Removed {} duplicate metric samples", removed);
+            }
+            conn.execute(
+                "CREATE UNIQUE INDEX idx_metrics_unique_sample
+                 ON metrics(run_id, timestamp, metric_type, source_component)",
+                [],
+            )?;
+        }
+        
+        // Index runs saved before the full-text table existed
+        conn.execute(
+            "INSERT INTO runs_fts (run_id, session_id, name, notes)
+             SELECT id, session_id, name, COALESCE(notes, '') FROM runs
+             WHERE id NOT IN (SELECT run_id FROM runs_fts)",
+            [],
+        )?;
+        
         Ok(())
     }
     
@@ -135,7 +215,7 @@ impl DatabaseStorage {
                     hardware_json,
                     session.profile.id,
                     session.profile.name,
-                    format!("{:?}", session.profile.workload_type),
+                    enum_to_column(&session.profile.workload_type)?,
                     now,
                     now,
                 ],
@@ -151,7 +231,20 @@ impl DatabaseStorage {
     }
     
     /// Save a run to the database
+    ///
+    /// The metric streams are decimated with the storage's policy first;
+    /// analyze the run before saving it.
     pub fn save_run(&self, run: &Run, session_id: &uuid::Uuid) -> Result<(), PersistenceError> {
+        let decimated;
+        let run = if self.decimation.is_full_resolution() {
+            run
+        } else {
+            let mut copy = run.clone();
+            self.decimation.apply(&mut copy);
+            decimated = copy;
+            &decimated
+        };
+        
         let metrics_json = serde_json::to_string(&run.metrics_streams)
             .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
         
@@ -160,6 +253,11 @@ impl DatabaseStorage {
             .transpose()
             .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
         
+        let environment_json = run.environment.as_ref()
+            .map(|e| serde_json::to_string(e))
+            .transpose()
+            .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        
         let now = Utc::now().to_rfc3339();
         
         // Lock connection, save run, then release lock
@@ -168,8 +266,8 @@ impl DatabaseStorage {
             
             conn.execute(
                 "INSERT OR REPLACE INTO runs (
-                    id, session_id, name, metrics_streams, analysis_result, notes, created_at
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    id, session_id, name, metrics_streams, analysis_result, notes, created_at, environment
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
                 params![
                     run.id.to_string(),
                     session_id.to_string(),
@@ -178,6 +276,21 @@ impl DatabaseStorage {
                     analysis_json,
                     run.notes,
                     now,
+                    environment_json,
+                ],
+            )?;
+            
+            conn.execute(
+                "DELETE FROM runs_fts WHERE run_id = ?1",
+                params![run.id.to_string()],
+            )?;
+            conn.execute(
+                "INSERT INTO runs_fts (run_id, session_id, name, notes) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    run.id.to_string(),
+                    session_id.to_string(),
+                    run.name,
+                    run.notes.clone().unwrap_or_default(),
                 ],
             )?;
         } // Lock released here
@@ -201,25 +314,46 @@ impl DatabaseStorage {
         // Insert metrics
         for (_, samples) in &run.metrics_streams {
             for sample in samples {
-                conn.execute(
-                    "INSERT INTO metrics (
-                        run_id, timestamp, metric_type, value, unit, source_component
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    params![
-                        run.id.to_string(),
-                        sample.timestamp.to_rfc3339(),
-                        format!("{:?}", sample.metric_type),
-                        sample.value,
-                        sample.unit,
-                        sample.source_component,
-                    ],
-                )?;
+                insert_metric(&conn, &run.id, sample)?;
             }
         }
         
         Ok(())
     }
     
+    /// Append samples to a run's stored metrics
+    ///
+    /// Samples already stored for the run, by timestamp, metric type, and
+    /// source component, are skipped, so overlapping batches from repeated
+    /// saves or imports can be appended safely. Returns the number of samples
+    /// inserted.
+    pub fn append_metrics(&self, run_id: &uuid::Uuid, samples: &[MetricSample]) -> Result<usize, PersistenceError> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let mut inserted = 0;
+        for sample in samples {
+            inserted += insert_metric(&tx, run_id, sample)?;
+        }
+        tx.commit()?;
+        Ok(inserted)
+    }
+    
+    /// Remove duplicate samples from a run's stored metrics
+    ///
+    /// Keeps the first sample stored for each timestamp, metric type, and
+    /// source component. Returns the number of samples removed.
+    pub fn deduplicate_run_metrics(&self, run_id: &uuid::Uuid) -> Result<usize, PersistenceError> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute(
+            "DELETE FROM metrics WHERE run_id = ?1 AND id NOT IN (
+                SELECT MIN(id) FROM metrics WHERE run_id = ?1
+                GROUP BY timestamp, metric_type, source_component
+            )",
+            params![run_id.to_string()],
+        )?;
+        Ok(removed)
+    }
+    
     /// Load a session from the database
     pub fn load_session(&self, session_id: &uuid::Uuid) -> Result<Session, PersistenceError> {
         // Load session data (lock, read, release)
@@ -262,13 +396,7 @@ impl DatabaseStorage {
         }).transpose()
             .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
         
-        let workload_type = match profile_type_str.as_str() {
-            "Gaming" => crate::core::domain::WorkloadType::Gaming,
-            "Rendering" => crate::core::domain::WorkloadType::Rendering,
-            "AI" => crate::core::domain::WorkloadType::AI,
-            "Productivity" => crate::core::domain::WorkloadType::Productivity,
-            _ => crate::core::domain::WorkloadType::General,
-        };
+        let workload_type: WorkloadType = enum_from_column(&profile_type_str)?;
         
         let profile = crate::core::domain::WorkloadProfile {
             id: profile_id,
@@ -299,7 +427,7 @@ impl DatabaseStorage {
         let conn = self.conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, name, metrics_streams, analysis_result, notes
+            "SELECT id, name, metrics_streams, analysis_result, notes, environment
              FROM runs WHERE session_id = ?1 ORDER BY created_at"
         )?;
         
@@ -310,22 +438,31 @@ impl DatabaseStorage {
                 row.get::<_, String>(2)?,
                 row.get::<_, Option<String>>(3)?,
                 row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
             ))
         })?;
         
         let mut runs = Vec::new();
         for row_result in run_rows {
-            let (id_str, name, metrics_json, analysis_json, notes) = row_result?;
+            let (id_str, name, metrics_json, analysis_json, notes, environment_json) = row_result?;
             
-            let metrics_streams: std::collections::HashMap<String, Vec<crate::core::domain::MetricSample>> =
+            let mut metrics_streams: std::collections::HashMap<String, Vec<crate::core::domain::MetricSample>> =
                 serde_json::from_str(&metrics_json)
                     .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+            for samples in metrics_streams.values_mut() {
+                migrate_hotspot_samples(samples);
+            }
             
             let analysis_result = analysis_json.map(|json| {
                 serde_json::from_str(&json)
                     .map_err(|e| PersistenceError::Deserialization(e.to_string()))
             }).transpose()?;
             
+            let environment = environment_json.map(|json| {
+                serde_json::from_str(&json)
+                    .map_err(|e| PersistenceError::Deserialization(e.to_string()))
+            }).transpose()?;
+            
             runs.push(Run {
                 id: uuid::Uuid::parse_str(&id_str)
                     .map_err(|e| PersistenceError::Deserialization(e.to_string()))?,
@@ -333,12 +470,60 @@ impl DatabaseStorage {
                 metrics_streams,
                 analysis_result,
                 notes,
+                environment,
             });
         }
         
         Ok(runs)
     }
     
+    /// Store an imported session, resolving an ID clash with `policy`
+    pub fn import_session(
+        &self,
+        session: Session,
+        policy: ImportConflictPolicy,
+    ) -> Result<ImportOutcome, PersistenceError> {
+        let exists = {
+            let conn = self.conn.lock().unwrap();
+            let count: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM sessions WHERE id = ?1",
+                params![session.id.to_string()],
+                |row| row.get(0),
+            )?;
+            count > 0
+        };
+        
+        let (session, outcome) = resolve_import_conflict(session, exists, policy);
+        if let Some(session) = session {
+            if matches!(outcome, ImportOutcome::Overwritten { .. }) {
+                self.delete_session_runs(&session.id)?;
+            }
+            self.save_session(&session)?;
+        }
+        
+        Ok(outcome)
+    }
+    
+    /// Remove the runs of a session so a replacement does not inherit them
+    fn delete_session_runs(&self, session_id: &uuid::Uuid) -> Result<(), PersistenceError> {
+        let conn = self.conn.lock().unwrap();
+        
+        conn.execute(
+            "DELETE FROM metrics WHERE run_id IN (SELECT id FROM runs WHERE session_id = ?1)",
+            params![session_id.to_string()],
+        )?;
+        conn.execute(
+            "DELETE FROM runs_fts WHERE session_id = ?1",
+            params![session_id.to_string()],
+        )?;
+        conn.execute(
+            "DELETE FROM runs WHERE session_id = ?1",
+            params![session_id.to_string()],
+        )?;
+        
+        Ok(())
+    }
+    
     /// List all sessions
     pub fn list_sessions(&self) -> Result<Vec<uuid::Uuid>, PersistenceError> {
         let conn = self.conn.lock().unwrap();
@@ -360,6 +545,50 @@ impl DatabaseStorage {
         Ok(session_ids)
     }
     
+    /// Summarize every session for listing, newest first
+    ///
+    /// Reads the sessions table and counts runs without loading their metrics.
+    pub fn list_session_summaries(&self) -> Result<Vec<SessionSummary>, PersistenceError> {
+        let conn = self.conn.lock().unwrap();
+        
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.profile_name, s.start_time, s.end_time,
+                    (SELECT COUNT(*) FROM runs r WHERE r.session_id = s.id)
+             FROM sessions s ORDER BY s.start_time DESC"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+        
+        let parse_time = |text: &str| {
+            DateTime::parse_from_rfc3339(text)
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|e| PersistenceError::Deserialization(e.to_string()))
+        };
+        
+        let mut summaries = Vec::new();
+        for row in rows {
+            let (id, name, start_time, end_time, run_count) = row?;
+            let id = uuid::Uuid::parse_str(&id)
+                .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+            summaries.push(SessionSummary {
+                id,
+                name,
+                start_time: parse_time(&start_time)?,
+                end_time: end_time.as_deref().map(parse_time).transpose()?,
+                run_count: run_count.max(0) as usize,
+            });
+        }
+        
+        Ok(summaries)
+    }
+    
     /// Delete old sessions based on retention policy
     pub fn cleanup_old_sessions(&self, retention_days: u32) -> Result<usize, PersistenceError> {
         let conn = self.conn.lock().unwrap();
@@ -373,17 +602,117 @@ impl DatabaseStorage {
             params![cutoff_str],
         )?;
         
+        // Virtual tables are not covered by foreign keys
+        conn.execute(
+            "DELETE FROM runs_fts WHERE session_id NOT IN (SELECT id FROM sessions)",
+            [],
+        )?;
+        
         // Vacuum database to reclaim space
         conn.execute("VACUUM", [])?;
         
         Ok(deleted)
     }
     
+    /// Get retention statistics for the sessions in the database
+    pub fn retention_stats(&self, policy: &RetentionPolicy) -> Result<RetentionStats, PersistenceError> {
+        Ok(RetentionStats::from_dates(&self.session_start_times()?, policy))
+    }
+    
+    /// Delete sessions past retention, keeping at least `min_sessions_to_keep`
+    ///
+    /// The oldest sessions are deleted first. Returns the number deleted.
+    pub fn cleanup_sessions(&self, policy: &RetentionPolicy) -> Result<usize, PersistenceError> {
+        let stats = self.retention_stats(policy)?;
+        if stats.sessions_to_delete == 0 {
+            return Ok(0);
+        }
+        
+        let conn = self.conn.lock().unwrap();
+        
+        // Foreign keys are not enforced, so runs and metrics are removed explicitly
+        let oldest = "SELECT id FROM sessions ORDER BY start_time ASC LIMIT ?1";
+        let limit = stats.sessions_to_delete as i64;
+        conn.execute(
+            &format!("DELETE FROM metrics WHERE run_id IN (SELECT id FROM runs WHERE session_id IN ({}))", oldest),
+            params![limit],
+        )?;
+        conn.execute(
+            &format!("DELETE FROM runs_fts WHERE session_id IN ({})", oldest),
+            params![limit],
+        )?;
+        conn.execute(
+            &format!("DELETE FROM runs WHERE session_id IN ({})", oldest),
+            params![limit],
+        )?;
+        let deleted = conn.execute(
+            &format!("DELETE FROM sessions WHERE id IN ({})", oldest),
+            params![limit],
+        )?;
+        
+        Ok(deleted)
+    }
+    
+    /// Start times of all sessions
+    fn session_start_times(&self) -> Result<Vec<DateTime<Utc>>, PersistenceError> {
+        let conn = self.conn.lock().unwrap();
+        
+        let mut stmt = conn.prepare("SELECT start_time FROM sessions")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        
+        let mut start_times = Vec::new();
+        for row in rows {
+            if let Ok(start_time) = DateTime::parse_from_rfc3339(&row?) {
+                start_times.push(start_time.with_timezone(&Utc));
+            }
+        }
+        
+        Ok(start_times)
+    }
+    
+    /// Search run names and notes using the full-text index
+    ///
+    /// The query is matched as a phrase, so punctuation in user input is not
+    /// interpreted as FTS syntax. Results are ordered by relevance.
+    pub fn search_runs(&self, query: &str) -> Result<Vec<RunMatch>, PersistenceError> {
+        let conn = self.conn.lock().unwrap();
+        
+        let phrase = format!("\"{}\"", query.replace('"', "\"\""));
+        let mut stmt = conn.prepare(
+            "SELECT run_id, session_id, name, snippet(runs_fts, -1, '[', ']', '...', 12)
+             FROM runs_fts WHERE runs_fts MATCH ?1 ORDER BY rank"
+        )?;
+        
+        let match_rows = stmt.query_map(params![phrase], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        
+        let mut matches = Vec::new();
+        for row_result in match_rows {
+            let (run_id, session_id, run_name, snippet) = row_result?;
+            matches.push(RunMatch {
+                session_id: uuid::Uuid::parse_str(&session_id)
+                    .map_err(|e| PersistenceError::Deserialization(e.to_string()))?,
+                run_id: uuid::Uuid::parse_str(&run_id)
+                    .map_err(|e| PersistenceError::Deserialization(e.to_string()))?,
+                run_name,
+                snippet,
+            });
+        }
+        
+        Ok(matches)
+    }
+    
     /// Query metrics efficiently
     pub fn query_metrics(
         &self,
         run_id: &uuid::Uuid,
-        metric_type: Option<&str>,
+        metric_type: Option<&MetricType>,
         start_time: Option<DateTime<Utc>>,
         end_time: Option<DateTime<Utc>>,
     ) -> Result<Vec<crate::core::domain::MetricSample>, PersistenceError> {
@@ -395,7 +724,7 @@ impl DatabaseStorage {
         
         if let Some(mt) = metric_type {
             query.push_str(" AND metric_type = ?2");
-            params_vec.push(Box::new(mt.to_string()));
+            params_vec.push(Box::new(enum_to_column(mt)?));
         }
         
         if let Some(st) = start_time {
@@ -425,14 +754,13 @@ impl DatabaseStorage {
         
         let mut samples = Vec::new();
         for row_result in metric_rows {
-            let (timestamp_str, _metric_type_str, value, unit, source_component) = row_result?;
+            let (timestamp_str, metric_type_str, value, unit, source_component) = row_result?;
             
             let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
                 .map_err(|e| PersistenceError::Deserialization(e.to_string()))?
                 .with_timezone(&Utc);
             
-            // Parse metric type (simplified - would need proper enum parsing)
-            let metric_type = crate::core::domain::MetricType::CpuUtilization; // Placeholder
+            let metric_type = enum_from_column(&metric_type_str)?;
             
             samples.push(crate::core::domain::MetricSample {
                 timestamp,
@@ -447,6 +775,90 @@ impl DatabaseStorage {
     }
 }
 
+/// Insert a sample into the metrics table unless it is already stored
+///
+/// Returns 1 when the sample was inserted and 0 when it was a duplicate.
+fn insert_metric(conn: &Connection, run_id: &uuid::Uuid, sample: &MetricSample) -> Result<usize, PersistenceError> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO metrics (
+            run_id, timestamp, metric_type, value, unit, source_component
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            run_id.to_string(),
+            sample.timestamp.to_rfc3339(),
+            enum_to_column(&sample.metric_type)?,
+            sample.value,
+            sample.unit,
+            sample.source_component,
+        ],
+    )?;
+    Ok(inserted)
+}
+
+/// Text stored in an enum column: the variant's serde name, as in exported JSON
+fn enum_to_column<T: Serialize>(value: &T) -> Result<String, PersistenceError> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => Ok(text),
+        Ok(other) => Err(PersistenceError::Serialization(format!("{} is not a unit variant", other))),
+        Err(e) => Err(PersistenceError::Serialization(e.to_string())),
+    }
+}
+
+/// Parse an enum column, accepting the Debug names written by older versions
+///
+/// Debug names map to serde names by snake-casing (`CpuUtilization`) or
+/// lowercasing (`AI`, `NVMe`). Unknown values are an error rather than a
+/// silent default.
+fn enum_from_column<T: DeserializeOwned>(text: &str) -> Result<T, PersistenceError> {
+    [text.to_string(), snake_case(text), text.to_lowercase()]
+        .into_iter()
+        .find_map(|candidate| serde_json::from_value(serde_json::Value::String(candidate)).ok())
+        .ok_or_else(|| PersistenceError::Deserialization(format!("Unknown enum value '{}'", text)))
+}
+
+/// `CpuUtilization` -> `cpu_utilization`
+fn snake_case(text: &str) -> String {
+    let mut snake = String::with_capacity(text.len() + 4);
+    for (i, c) in text.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Rewrite legacy Debug names in an enum column to serde names
+///
+/// Values that parse as neither are left for loading to report.
+fn normalize_enum_column<T: Serialize + DeserializeOwned>(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+) -> Result<(), PersistenceError> {
+    let stored: Vec<String> = conn
+        .prepare(&format!("SELECT DISTINCT {} FROM {}", column, table))?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    for text in stored {
+        let Ok(value) = enum_from_column::<T>(&text) else {
+            continue;
+        };
+        let canonical = enum_to_column(&value)?;
+        if canonical != text {
+            conn.execute(
+                &format!("UPDATE {} SET {} = ?1 WHERE {} = ?2", table, column, column),
+                params![canonical, text],
+            )?;
+        }
+    }
+    Ok(())
+}
+
 impl From<rusqlite::Error> for PersistenceError {
     fn from(err: rusqlite::Error) -> Self {
         PersistenceError::Database(err.to_string())
@@ -0,0 +1,145 @@
+//! Pre-save decimation of metric streams
+//!
+//! Utilization streams sampled every second are noisy and make up most of a
+//! saved run, while frame times and temperatures are where short spikes
+//! matter. A decimation policy reduces chosen metric types to one sample per
+//! interval when a run is saved; every other type keeps full resolution. The
+//! live buffer is never decimated.
+//!
+//! Decimation is lossy, so analysis should run on the full-resolution
+//! samples before the run is saved: the stored analysis result then reflects
+//! every sample, and re-analyzing a decimated run may differ slightly.
+
+use crate::core::domain::{MetricSample, MetricType, Run};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Interval the recommended policy reduces utilization streams to
+pub const RECOMMENDED_UTILIZATION_INTERVAL_SECONDS: u64 = 2;
+
+/// How the samples in one interval are combined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecimationMethod {
+    /// Average of the interval, smoothing out noise
+    #[default]
+    Mean,
+    /// Highest value of the interval, keeping spikes
+    Max,
+}
+
+/// Decimation of one metric type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecimationRule {
+    pub metric_type: MetricType,
+    /// Length of each interval; 0 or 1 keeps full resolution
+    pub interval_seconds: u64,
+    #[serde(default)]
+    pub method: DecimationMethod,
+}
+
+/// Per-metric-type decimation applied before a run is saved
+///
+/// The default policy has no rules and saves every sample.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecimationPolicy {
+    #[serde(default)]
+    pub rules: Vec<DecimationRule>,
+}
+
+impl DecimationPolicy {
+    /// Utilization reduced to 2-second means; frame times, temperatures and
+    /// everything else at full resolution
+    pub fn recommended() -> Self {
+        let rules = [
+            MetricType::CpuUtilization,
+            MetricType::CpuUtilizationPerCore,
+            MetricType::GpuUtilization,
+            MetricType::MemoryUsage,
+        ]
+        .into_iter()
+        .map(|metric_type| DecimationRule {
+            metric_type,
+            interval_seconds: RECOMMENDED_UTILIZATION_INTERVAL_SECONDS,
+            method: DecimationMethod::Mean,
+        })
+        .collect();
+        Self { rules }
+    }
+
+    /// Whether the policy leaves every stream at full resolution
+    pub fn is_full_resolution(&self) -> bool {
+        self.rules.iter().all(|rule| rule.interval_seconds <= 1)
+    }
+
+    /// Rule for a metric type; the last matching rule wins
+    pub fn rule_for(&self, metric_type: &MetricType) -> Option<&DecimationRule> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.metric_type == *metric_type && rule.interval_seconds > 1)
+    }
+
+    /// Decimate the metric streams of a run in place
+    ///
+    /// Samples are bucketed into intervals aligned to the epoch, separately
+    /// for each metric type and source component. Each bucket becomes one
+    /// sample at the time of its first sample. The analysis result, notes and
+    /// environment are kept as they are.
+    pub fn apply(&self, run: &mut Run) {
+        if self.is_full_resolution() {
+            return;
+        }
+        for samples in run.metrics_streams.values_mut() {
+            let decimated = self.decimate(std::mem::take(samples));
+            *samples = decimated;
+        }
+    }
+
+    /// Decimate one stream, keeping samples without a rule untouched
+    pub fn decimate(&self, samples: Vec<MetricSample>) -> Vec<MetricSample> {
+        let mut kept = Vec::with_capacity(samples.len());
+        // Bucket index into `buckets`, keyed by type, source and interval start
+        let mut index: HashMap<(MetricType, String, i64), usize> = HashMap::new();
+        let mut buckets: Vec<(DecimationMethod, MetricSample, usize)> = Vec::new();
+
+        for sample in samples {
+            let Some(rule) = self.rule_for(&sample.metric_type) else {
+                kept.push(sample);
+                continue;
+            };
+            let interval = rule.interval_seconds.min(i64::MAX as u64) as i64;
+            let key = (
+                sample.metric_type.clone(),
+                sample.source_component.clone(),
+                sample.timestamp.timestamp().div_euclid(interval),
+            );
+            match index.get(&key) {
+                Some(&i) => {
+                    let (method, bucket, count) = &mut buckets[i];
+                    match method {
+                        DecimationMethod::Mean => bucket.value += sample.value,
+                        DecimationMethod::Max => bucket.value = bucket.value.max(sample.value),
+                    }
+                    if sample.timestamp < bucket.timestamp {
+                        bucket.timestamp = sample.timestamp;
+                    }
+                    *count += 1;
+                }
+                None => {
+                    index.insert(key, buckets.len());
+                    buckets.push((rule.method, sample, 1));
+                }
+            }
+        }
+
+        kept.extend(buckets.into_iter().map(|(method, mut sample, count)| {
+            if method == DecimationMethod::Mean {
+                sample.value /= count as f64;
+            }
+            sample
+        }));
+        kept.sort_by_key(|s| s.timestamp);
+        kept
+    }
+}
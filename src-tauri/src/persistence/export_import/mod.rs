@@ -3,28 +3,45 @@
 //! This module provides functionality for exporting sessions for sharing
 //! and importing external benchmark data, following IMPLEMENTATION_PLAN.md Phase 4.3.
 
-use crate::core::domain::{Run, Session};
+use crate::core::domain::{HardwareConfig, MetricSample, Run, Session, WorkloadType};
 use crate::core::error::PersistenceError;
+use crate::metrics::{
+    aggregate_metrics, derive_missing_metrics, normalize_memory_samples, normalize_memory_streams, MetricAggregation,
+};
+use crate::persistence::binary::{is_binary_session_path, load_session_binary, save_session_binary};
+use crate::persistence::migration::{migrate_hotspot_samples, migrate_run_metrics, METRIC_SAMPLE_SCHEMA_VERSION};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json;
+use std::collections::BTreeMap;
 use std::path::Path;
 use tokio::fs;
+use uuid::Uuid;
 
-/// Export a session to a JSON file for sharing
+/// Export a session to a file for sharing
+///
+/// `.stio` paths are written in the binary format; anything else is JSON.
 pub async fn export_session(
     session: &Session,
     export_path: &Path,
 ) -> Result<(), PersistenceError> {
+    if is_binary_session_path(export_path) {
+        return save_session_binary(session, export_path).await;
+    }
+    
     // Create export format with metadata
     #[derive(serde::Serialize)]
     struct ExportedSession {
         export_version: u32,
         export_timestamp: String,
+        metrics_schema_version: u32,
         session: Session,
     }
     
     let exported = ExportedSession {
         export_version: 1,
         export_timestamp: chrono::Utc::now().to_rfc3339(),
+        metrics_schema_version: METRIC_SAMPLE_SCHEMA_VERSION,
         session: session.clone(),
     };
     
@@ -38,31 +55,34 @@ pub async fn export_session(
     Ok(())
 }
 
-/// Import a session from an exported JSON file
+/// Import a session from an exported file
+///
+/// `.stio` paths are read in the binary format; anything else is JSON.
+/// Metric samples written by older versions are migrated to the current shape.
 pub async fn import_session(import_path: &Path) -> Result<Session, PersistenceError> {
+    if is_binary_session_path(import_path) {
+        let mut session = load_session_binary(import_path).await?;
+        normalize_imported_session(&mut session);
+        return Ok(session);
+    }
+    
     let content = fs::read_to_string(import_path)
         .await
         .map_err(|e| PersistenceError::Io(e))?;
     
-    // Try to parse as exported format first
-    #[derive(serde::Deserialize)]
-    struct ExportedSession {
-        #[serde(default)]
-        export_version: u32,
-        #[serde(default)]
-        export_timestamp: Option<String>,
-        session: Session,
-    }
+    let mut document = parse_document(&content)?;
+    let metrics_version = metrics_schema_version(&document);
     
-    // Try exported format
-    if let Ok(exported) = serde_json::from_str::<ExportedSession>(&content) {
-        return Ok(exported.session);
-    }
+    // Exported format wraps the session; fall back to direct session format
+    let mut session = match document.get_mut("session") {
+        Some(session) => session.take(),
+        None => document,
+    };
+    migrate_session_metrics(&mut session, metrics_version)?;
     
-    // Fallback to direct session format
-    let session: Session = serde_json::from_str(&content)
+    let mut session: Session = serde_json::from_value(session)
         .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
-    
+    normalize_imported_session(&mut session);
     Ok(session)
 }
 
@@ -75,6 +95,7 @@ pub async fn export_sessions_batch(
     struct BatchExport {
         export_version: u32,
         export_timestamp: String,
+        metrics_schema_version: u32,
         session_count: usize,
         sessions: Vec<Session>,
     }
@@ -82,6 +103,7 @@ pub async fn export_sessions_batch(
     let batch = BatchExport {
         export_version: 1,
         export_timestamp: chrono::Utc::now().to_rfc3339(),
+        metrics_schema_version: METRIC_SAMPLE_SCHEMA_VERSION,
         session_count: sessions.len(),
         sessions: sessions.to_vec(),
     };
@@ -104,21 +126,86 @@ pub async fn import_sessions_batch(
         .await
         .map_err(|e| PersistenceError::Io(e))?;
     
-    #[derive(serde::Deserialize)]
-    struct BatchExport {
-        #[serde(default)]
-        export_version: u32,
-        #[serde(default)]
-        export_timestamp: Option<String>,
-        #[serde(default)]
-        session_count: Option<usize>,
-        sessions: Vec<Session>,
+    let mut document = parse_document(&content)?;
+    let metrics_version = metrics_schema_version(&document);
+    
+    let mut sessions = match document.get_mut("sessions") {
+        Some(serde_json::Value::Array(sessions)) => std::mem::take(sessions),
+        _ => {
+            return Err(PersistenceError::Deserialization(
+                "Batch export has no sessions array".to_string(),
+            ))
+        }
+    };
+    for session in &mut sessions {
+        migrate_session_metrics(session, metrics_version)?;
     }
     
-    let batch: BatchExport = serde_json::from_str(&content)
-        .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+    sessions
+        .into_iter()
+        .map(|session| {
+            let mut session: Session = serde_json::from_value(session)
+                .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+            normalize_imported_session(&mut session);
+            Ok(session)
+        })
+        .collect()
+}
+
+/// What to do when an imported session has the ID of a stored session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflictPolicy {
+    /// Keep the stored session and drop the imported one
+    Skip,
+    /// Replace the stored session, including its runs
+    Overwrite,
+    /// Store the imported session and its runs under fresh IDs
+    #[default]
+    AssignNewId,
+}
+
+/// How an imported session ended up in storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ImportOutcome {
+    /// No stored session had the ID
+    Imported { id: Uuid },
+    Skipped { id: Uuid },
+    Overwritten { id: Uuid },
+    /// Stored under `id` because `original_id` was taken
+    Reassigned { original_id: Uuid, id: Uuid },
+}
+
+/// Apply a conflict policy to an imported session
+///
+/// Returns the session to store, or `None` if it is skipped. Reassigned
+/// sessions also get fresh run IDs so their runs cannot replace the stored ones.
+pub fn resolve_import_conflict(
+    session: Session,
+    exists: bool,
+    policy: ImportConflictPolicy,
+) -> (Option<Session>, ImportOutcome) {
+    if !exists {
+        let id = session.id;
+        return (Some(session), ImportOutcome::Imported { id });
+    }
     
-    Ok(batch.sessions)
+    match policy {
+        ImportConflictPolicy::Skip => (None, ImportOutcome::Skipped { id: session.id }),
+        ImportConflictPolicy::Overwrite => {
+            let id = session.id;
+            (Some(session), ImportOutcome::Overwritten { id })
+        }
+        ImportConflictPolicy::AssignNewId => {
+            let reassigned = crate::core::sessions::clone_session(&session, &session.profile.name);
+            let outcome = ImportOutcome::Reassigned {
+                original_id: session.id,
+                id: reassigned.id,
+            };
+            (Some(reassigned), outcome)
+        }
+    }
 }
 
 /// Export a run for sharing
@@ -130,12 +217,14 @@ pub async fn export_run(
     struct ExportedRun {
         export_version: u32,
         export_timestamp: String,
+        metrics_schema_version: u32,
         run: Run,
     }
     
     let exported = ExportedRun {
         export_version: 1,
         export_timestamp: chrono::Utc::now().to_rfc3339(),
+        metrics_schema_version: METRIC_SAMPLE_SCHEMA_VERSION,
         run: run.clone(),
     };
     
@@ -150,29 +239,321 @@ pub async fn export_run(
 }
 
 /// Import a run from an exported file
+///
+/// Metric samples written by older versions are migrated to the current shape.
 pub async fn import_run(import_path: &Path) -> Result<Run, PersistenceError> {
     let content = fs::read_to_string(import_path)
         .await
         .map_err(|e| PersistenceError::Io(e))?;
     
-    #[derive(serde::Deserialize)]
-    struct ExportedRun {
-        #[serde(default)]
-        export_version: u32,
-        #[serde(default)]
-        export_timestamp: Option<String>,
-        run: Run,
-    }
+    let mut document = parse_document(&content)?;
+    let metrics_version = metrics_schema_version(&document);
     
-    // Try exported format
-    if let Ok(exported) = serde_json::from_str::<ExportedRun>(&content) {
-        return Ok(exported.run);
+    // Exported format wraps the run; fall back to direct run format
+    let mut run = match document.get_mut("run") {
+        Some(run) => run.take(),
+        None => document,
+    };
+    migrate_run_metrics(&mut run, metrics_version)?;
+    
+    let mut run: Run = serde_json::from_value(run)
+        .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+    // A lone run has no hardware snapshot, so only absolute samples are relabelled
+    normalize_imported_run(&mut run, None);
+    Ok(run)
+}
+
+/// Normalize the samples of every imported run
+///
+/// Uses the memory size from the session's hardware snapshot.
+fn normalize_imported_session(session: &mut Session) {
+    let total_mb = Some(session.hardware_config_snapshot.memory.total_mb);
+    for run in &mut session.runs {
+        normalize_imported_run(run, total_mb);
+    }
+}
+
+/// Express RAM usage both as percent and in MB, and derive FPS or frame
+/// times when the run recorded only one of them
+fn normalize_imported_run(run: &mut Run, total_mb: Option<u64>) {
+    for samples in run.metrics_streams.values_mut() {
+        migrate_hotspot_samples(samples);
+    }
+    normalize_memory_streams(&mut run.metrics_streams, total_mb);
+    derive_missing_metrics(run);
+}
+
+/// Parse an import file into a JSON document
+fn parse_document(content: &str) -> Result<serde_json::Value, PersistenceError> {
+    serde_json::from_str(content)
+        .map_err(|e| PersistenceError::Deserialization(e.to_string()))
+}
+
+/// Sample schema version recorded in an export; 0 for files that predate it
+fn metrics_schema_version(document: &serde_json::Value) -> u32 {
+    document
+        .get("metrics_schema_version")
+        .and_then(|v| v.as_u64())
+        .map_or(0, |v| v as u32)
+}
+
+/// Upgrade the sample arrays of every run in a serialized session
+fn migrate_session_metrics(session: &mut serde_json::Value, from_version: u32) -> Result<(), PersistenceError> {
+    if let Some(runs) = session.get_mut("runs").and_then(|r| r.as_array_mut()) {
+        for run in runs {
+            migrate_run_metrics(run, from_version)?;
+        }
+    }
+    Ok(())
+}
+
+/// Identifies snapshot documents among other JSON files
+pub const SNAPSHOT_FORMAT: &str = "stats-io-snapshot";
+
+/// Current schema version of snapshot documents
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+/// Shareable snapshot of the hardware and its most recent metrics
+///
+/// The document is self-describing so it can be pasted into a forum post or
+/// attached to an issue and still be understood without the session it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Always `SNAPSHOT_FORMAT`
+    pub format: String,
+    pub schema_version: u32,
+    /// Version of the application that created the snapshot
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Length of the metrics window in seconds
+    pub window_seconds: i64,
+    pub hardware: HardwareConfig,
+    pub metrics: Vec<MetricSample>,
+}
+
+impl Snapshot {
+    /// Build a snapshot from the hardware and a metrics buffer
+    ///
+    /// Only samples within `window_seconds` of the newest buffered sample are
+    /// kept. A window reaching back further than timestamps can represent
+    /// keeps the whole buffer.
+    pub fn new(hardware: HardwareConfig, buffer: &[MetricSample], window_seconds: i64) -> Self {
+        let metrics = match buffer.iter().map(|m| m.timestamp).max() {
+            Some(newest) => {
+                let window_start = Duration::try_seconds(window_seconds)
+                    .and_then(|window| newest.checked_sub_signed(window));
+                buffer
+                    .iter()
+                    .filter(|m| match window_start {
+                        Some(window_start) => m.timestamp >= window_start,
+                        None => true,
+                    })
+                    .cloned()
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        
+        Self {
+            format: SNAPSHOT_FORMAT.to_string(),
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: Utc::now(),
+            window_seconds,
+            hardware,
+            metrics,
+        }
     }
+}
+
+/// Export a snapshot to a JSON file for sharing
+pub async fn export_snapshot(
+    snapshot: &Snapshot,
+    export_path: &Path,
+) -> Result<(), PersistenceError> {
+    let json = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+    
+    fs::write(export_path, json)
+        .await
+        .map_err(PersistenceError::Io)?;
+    
+    Ok(())
+}
+
+/// Load a snapshot for inspection
+///
+/// Nothing is written to session storage; the snapshot is only returned.
+pub async fn import_snapshot(import_path: &Path) -> Result<Snapshot, PersistenceError> {
+    let content = fs::read_to_string(import_path)
+        .await
+        .map_err(PersistenceError::Io)?;
     
-    // Fallback to direct run format
-    let run: Run = serde_json::from_str(&content)
+    let mut snapshot: Snapshot = serde_json::from_str(&content)
         .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
     
-    Ok(run)
+    if snapshot.format != SNAPSHOT_FORMAT {
+        return Err(PersistenceError::Deserialization(format!(
+            "Not a snapshot document: format is '{}'",
+            snapshot.format
+        )));
+    }
+    if snapshot.schema_version > SNAPSHOT_SCHEMA_VERSION {
+        return Err(PersistenceError::SchemaVersionMismatch {
+            expected: SNAPSHOT_SCHEMA_VERSION,
+            found: snapshot.schema_version,
+        });
+    }
+    
+    migrate_hotspot_samples(&mut snapshot.metrics);
+    normalize_memory_samples(&mut snapshot.metrics, Some(snapshot.hardware.memory.total_mb));
+    Ok(snapshot)
+}
+
+/// Identifies anonymized contribution documents among other JSON files
+pub const CONTRIBUTION_FORMAT: &str = "stats-io-contribution";
+
+/// Current schema version of contribution documents
+pub const CONTRIBUTION_SCHEMA_VERSION: u32 = 1;
+
+/// Hardware class of a contribution: component models without identifiers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HardwareClass {
+    pub cpu_model: String,
+    pub cpu_cores: u32,
+    pub cpu_threads: u32,
+    pub gpu_models: Vec<String>,
+    /// Installed memory rounded to whole gigabytes
+    pub memory_gb: u64,
+}
+
+/// Anonymized aggregate statistics of a session for the reference dataset
+///
+/// Only the hardware class, the workload type and per-metric aggregates are
+/// kept. Timestamps, session and run IDs, names, notes, sample sources and
+/// machine identifiers such as PCI IDs never enter the document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contribution {
+    /// Always `CONTRIBUTION_FORMAT`
+    pub format: String,
+    pub schema_version: u32,
+    /// Version of the application that created the contribution
+    pub app_version: String,
+    pub hardware: HardwareClass,
+    pub workload_type: WorkloadType,
+    pub run_count: usize,
+    /// Aggregates over every run, keyed by metric type
+    pub metrics: BTreeMap<String, MetricAggregation>,
+}
+
+impl Contribution {
+    /// Build a contribution from a session
+    pub fn from_session(session: &Session) -> Self {
+        let hardware = &session.hardware_config_snapshot;
+        let samples: Vec<MetricSample> = session
+            .runs
+            .iter()
+            .flat_map(|run| run.metrics_streams.values().flatten().cloned())
+            .collect();
+        
+        Self {
+            format: CONTRIBUTION_FORMAT.to_string(),
+            schema_version: CONTRIBUTION_SCHEMA_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            hardware: HardwareClass {
+                cpu_model: hardware.cpu.model.clone(),
+                cpu_cores: hardware.cpu.cores,
+                cpu_threads: hardware.cpu.threads,
+                gpu_models: hardware.gpus.iter().map(|gpu| gpu.model.clone()).collect(),
+                memory_gb: (hardware.memory.total_mb + 512) / 1024,
+            },
+            workload_type: session.profile.workload_type.clone(),
+            run_count: session.runs.len(),
+            metrics: aggregate_metrics(&samples).into_iter().collect(),
+        }
+    }
+}
+
+/// Check that a serialized contribution leaks nothing identifying
+///
+/// Rejects any UUID or timestamp, the session's notes, the PCI IDs of its
+/// GPUs and the host name of this machine.
+pub fn validate_contribution(json: &str, session: &Session) -> Result<(), PersistenceError> {
+    let leak = |what: &str| Err(PersistenceError::PrivacyLeak(format!("contribution contains {}", what)));
+    
+    if contains_uuid(json) {
+        return leak("a UUID");
+    }
+    if contains_timestamp(json) {
+        return leak("a timestamp");
+    }
+    let mut notes = session.runs.iter().filter_map(|run| run.notes.as_deref());
+    if notes.any(|note| contains_json_string(json, note)) {
+        return leak("run notes");
+    }
+    let mut pci_ids = session.hardware_config_snapshot.gpus.iter().filter_map(|gpu| gpu.pci_id.as_deref());
+    if pci_ids.any(|id| contains_json_string(json, id)) {
+        return leak("a PCI ID");
+    }
+    if sysinfo::System::host_name().is_some_and(|host| contains_json_string(json, &host)) {
+        return leak("the host name");
+    }
+    Ok(())
+}
+
+/// Export an anonymized contribution of a session to a JSON file
+///
+/// The document is validated before anything is written, so a leaking
+/// contribution never reaches the disk.
+pub async fn export_anonymized_contribution(
+    session: &Session,
+    export_path: &Path,
+) -> Result<Contribution, PersistenceError> {
+    let contribution = Contribution::from_session(session);
+    let json = serde_json::to_string_pretty(&contribution)
+        .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+    validate_contribution(&json, session)?;
+    
+    fs::write(export_path, json)
+        .await
+        .map_err(PersistenceError::Io)?;
+    
+    Ok(contribution)
 }
 
+/// Whether serialized JSON contains a non-blank string, as escaped by serde_json
+fn contains_json_string(json: &str, value: &str) -> bool {
+    let value = value.trim();
+    if value.is_empty() {
+        return false;
+    }
+    let escaped = serde_json::to_string(value).unwrap_or_default();
+    json.contains(escaped.trim_matches('"'))
+}
+
+/// Whether the text contains a hyphenated UUID (8-4-4-4-12 hex digits)
+fn contains_uuid(text: &str) -> bool {
+    const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+    let bytes = text.as_bytes();
+    (0..bytes.len()).any(|start| {
+        let mut pos = start;
+        GROUPS.iter().enumerate().all(|(idx, &len)| {
+            let group_ok = bytes.len() >= pos + len && bytes[pos..pos + len].iter().all(u8::is_ascii_hexdigit);
+            pos += len;
+            let separator_ok = idx == GROUPS.len() - 1 || bytes.get(pos) == Some(&b'-');
+            pos += 1;
+            group_ok && separator_ok
+        })
+    })
+}
+
+/// Whether the text contains an ISO 8601 date-time such as `2024-01-31T12:00`
+fn contains_timestamp(text: &str) -> bool {
+    const PATTERN: &[u8] = b"dddd-dd-ddTdd:dd";
+    text.as_bytes().windows(PATTERN.len()).any(|window| {
+        window.iter().zip(PATTERN).all(|(&byte, &expected)| match expected {
+            b'd' => byte.is_ascii_digit(),
+            _ => byte == expected,
+        })
+    })
+}
@@ -2,6 +2,7 @@
 //!
 //! This module handles migration between different schema versions.
 
+use crate::core::domain::{unit_for, MetricSample, MetricType, Session, GPU_HOTSPOT_SOURCE};
 use crate::core::error::PersistenceError;
 use crate::persistence::models::CURRENT_SCHEMA_VERSION;
 
@@ -47,3 +48,90 @@ pub fn check_and_migrate(
         Ok(data.to_string())
     }
 }
+
+/// Current schema version of serialized metric samples
+///
+/// Version 0 is data written before samples were versioned.
+pub const METRIC_SAMPLE_SCHEMA_VERSION: u32 = 1;
+
+/// Source recorded for migrated samples that did not name one
+pub const UNKNOWN_SOURCE_COMPONENT: &str = "Unknown";
+
+/// Upgrade a serialized sample array to the current sample shape
+///
+/// Fields added since `from_version` are filled with defaults: a missing
+/// unit becomes the canonical unit of the sample's metric type and a missing
+/// source becomes `UNKNOWN_SOURCE_COMPONENT`. Fails for arrays written by a
+/// newer version.
+pub fn migrate_metric_samples(
+    mut samples: Vec<serde_json::Value>,
+    from_version: u32,
+) -> Result<Vec<MetricSample>, PersistenceError> {
+    upgrade_samples(&mut samples, from_version)?;
+    samples
+        .into_iter()
+        .map(|sample| {
+            serde_json::from_value(sample).map_err(|e| PersistenceError::Deserialization(e.to_string()))
+        })
+        .collect()
+}
+
+/// Upgrade the sample arrays of a serialized run in place
+pub fn migrate_run_metrics(run: &mut serde_json::Value, from_version: u32) -> Result<(), PersistenceError> {
+    if let Some(streams) = run.get_mut("metrics_streams").and_then(|s| s.as_object_mut()) {
+        for samples in streams.values_mut() {
+            if let Some(samples) = samples.as_array_mut() {
+                upgrade_samples(samples, from_version)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn upgrade_samples(samples: &mut [serde_json::Value], from_version: u32) -> Result<(), PersistenceError> {
+    if from_version > METRIC_SAMPLE_SCHEMA_VERSION {
+        return Err(PersistenceError::SchemaVersionMismatch {
+            expected: METRIC_SAMPLE_SCHEMA_VERSION,
+            found: from_version,
+        });
+    }
+    if from_version == METRIC_SAMPLE_SCHEMA_VERSION {
+        return Ok(());
+    }
+    
+    for sample in samples.iter_mut().filter_map(|s| s.as_object_mut()) {
+        if !sample.contains_key("unit") {
+            let unit = sample
+                .get("metric_type")
+                .and_then(|t| serde_json::from_value::<MetricType>(t.clone()).ok())
+                .map_or("", |metric_type| unit_for(&metric_type));
+            sample.insert("unit".to_string(), unit.into());
+        }
+        if !sample.contains_key("source_component") {
+            sample.insert("source_component".to_string(), UNKNOWN_SOURCE_COMPONENT.into());
+        }
+    }
+    Ok(())
+}
+
+/// Move hotspot readings recorded as `GpuTemperature` to `GpuHotspotTemperature`
+///
+/// Runs recorded before the hotspot had its own metric type told the two GPU
+/// sensors apart by the `GPU_HOTSPOT_SOURCE` source only.
+pub fn migrate_hotspot_samples(samples: &mut [MetricSample]) {
+    for sample in samples
+        .iter_mut()
+        .filter(|s| s.metric_type == MetricType::GpuTemperature && s.source_component == GPU_HOTSPOT_SOURCE)
+    {
+        sample.metric_type = MetricType::GpuHotspotTemperature;
+    }
+}
+
+/// Apply `migrate_hotspot_samples` to every run of a loaded session
+pub fn migrate_session_samples(session: &mut Session) {
+    for run in &mut session.runs {
+        for samples in run.metrics_streams.values_mut() {
+            migrate_hotspot_samples(samples);
+        }
+    }
+}
@@ -7,10 +7,13 @@ pub mod storage;
 pub mod migration;
 pub mod reports;
 pub mod database;
+pub mod decimation;
 pub mod export_import;
 pub mod retention;
+pub mod size;
+pub mod binary;
 
-pub use storage::SessionStorage;
+pub use storage::{HardwareSnapshotStorage, SessionStorage};
 pub use database::DatabaseStorage;
 
 use std::path::PathBuf;
@@ -27,9 +30,29 @@ pub fn get_app_data_dir(app: &AppHandle) -> Result<PathBuf, crate::core::error::
         ))
 }
 
+/// Get the directory holding session files
+pub fn get_sessions_dir(app: &AppHandle) -> Result<PathBuf, crate::core::error::PersistenceError> {
+    Ok(get_app_data_dir(app)?.join("sessions"))
+}
+
 /// Initialize session storage
 pub fn init_session_storage(app: &AppHandle) -> Result<SessionStorage, crate::core::error::PersistenceError> {
+    Ok(SessionStorage::new(get_sessions_dir(app)?))
+}
+
+/// Open the session database, if one has been created
+///
+/// The database is never created here, so file-only installs stay file-only.
+pub fn open_session_database(app: &AppHandle) -> Result<Option<DatabaseStorage>, crate::core::error::PersistenceError> {
+    let db_path = get_app_data_dir(app)?.join("sessions.db");
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    DatabaseStorage::new(db_path).map(Some)
+}
+
+/// Initialize storage for the last detected hardware configuration
+pub fn init_hardware_snapshot_storage(app: &AppHandle) -> Result<HardwareSnapshotStorage, crate::core::error::PersistenceError> {
     let base_dir = get_app_data_dir(app)?;
-    let sessions_dir = base_dir.join("sessions");
-    Ok(SessionStorage::new(sessions_dir))
+    Ok(HardwareSnapshotStorage::new(base_dir.join("last_hardware_config.json")))
 }
@@ -20,3 +20,24 @@ pub struct PersistedRun {
     pub schema_version: u32,
     pub run: crate::core::domain::Run,
 }
+
+/// A run matching a notes search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMatch {
+    pub session_id: uuid::Uuid,
+    pub run_id: uuid::Uuid,
+    pub run_name: String,
+    /// Excerpt of the matching text with the match in brackets
+    pub snippet: String,
+}
+
+/// Fields of a session needed to list it, without its hardware or metrics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub id: uuid::Uuid,
+    /// Sessions are listed under their profile's name
+    pub name: String,
+    pub start_time: chrono::DateTime<chrono::Utc>,
+    pub end_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub run_count: usize,
+}
@@ -3,11 +3,25 @@
 //! This module provides functionality for generating and exporting reports
 //! following AGENT.md Section 3.4 and IMPLEMENTATION_PLAN.md Phase 3.2.
 
+pub mod pdf;
+
 use crate::core::domain::{
-    HardwareConfig, Run, Session,
+    Bottleneck, BottleneckAnalysisResult, BottleneckType, HardwareConfig, MetricSample, MetricType,
+    Run, RunEnvironment, Session,
+};
+use crate::analysis::comparison::{compare_to_previous_run, ComparisonResult, DEFAULT_MIN_EFFECT_PERCENT};
+use crate::analysis::insights::{
+    active_time_phrase, generate_acknowledged_insights, grade_label, grade_subsystems, is_acknowledged, sort_easy_wins_first,
+    subsystem_name, BottleneckAcknowledgment, Recommendation, RecommendationCategory, GRADED_SUBSYSTEMS,
 };
-use crate::analysis::comparison::ComparisonResult;
+use crate::core::error::PersistenceError;
+use crate::metrics::utils::{aggregate_metrics, mean, MetricAggregation};
+use chrono::{DateTime, Local, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Report configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,8 +32,116 @@ pub struct ReportConfig {
     pub include_recommendations: bool,
     pub include_comparison: bool,
     pub format: ReportFormat,
+    /// Timezone for displayed timestamps; `None` or "local" uses the system zone
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Bottlenecks shown as dismissed and left out of the recommendations
+    #[serde(default)]
+    pub acknowledgments: Vec<BottleneckAcknowledgment>,
+    /// Sections to include, in output order; `None` derives the default
+    /// layout from the `include_*` flags
+    #[serde(default)]
+    pub sections: Option<Vec<ReportSection>>,
+    /// Severity thresholds for the tier classes of HTML bottleneck cards
+    #[serde(default)]
+    pub severity_bands: SeverityBands,
+    /// How much per-run detail to include; `None` picks a level from the
+    /// number of runs
+    #[serde(default)]
+    pub detail_level: Option<ReportDetailLevel>,
+}
+
+/// Lower bounds (inclusive) of the severity tiers; anything below `medium` is low
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeverityBands {
+    pub medium: u8,
+    pub high: u8,
+    pub critical: u8,
+}
+
+impl Default for SeverityBands {
+    fn default() -> Self {
+        Self { medium: 25, high: 50, critical: 75 }
+    }
+}
+
+impl SeverityBands {
+    /// Check that the thresholds are strictly ascending and within 1-100
+    pub fn validate(&self) -> Result<(), String> {
+        if self.medium == 0 || self.critical > 100 {
+            return Err("Severity thresholds must be between 1 and 100".to_string());
+        }
+        if self.medium >= self.high || self.high >= self.critical {
+            return Err(format!(
+                "Severity thresholds must be ascending (medium {}, high {}, critical {})",
+                self.medium, self.high, self.critical
+            ));
+        }
+        Ok(())
+    }
+
+    /// Tier name of a severity, used as the `severity-<tier>` CSS class
+    pub fn tier(&self, severity: u8) -> &'static str {
+        if severity >= self.critical {
+            "critical"
+        } else if severity >= self.high {
+            "high"
+        } else if severity >= self.medium {
+            "medium"
+        } else {
+            "low"
+        }
+    }
+}
+
+/// Sessions with up to this many runs get a full report when no level is set
+pub const AUTO_FULL_MAX_RUNS: usize = 10;
+
+/// Sessions with up to this many runs get a standard report when no level is set
+pub const AUTO_STANDARD_MAX_RUNS: usize = 30;
+
+/// Runs given per-run detail in a standard report, most severe first
+pub const STANDARD_DETAILED_RUNS: usize = 10;
+
+/// Bottlenecks listed in a summary report
+pub const SUMMARY_WORST_BOTTLENECKS: usize = 10;
+
+/// How much per-run detail a session report includes
+///
+/// Only the text, HTML, Markdown and PDF reports are shortened; JSON and CSV
+/// are data exports and always hold every run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportDetailLevel {
+    /// Session roll-up and the worst bottlenecks across all runs
+    Summary,
+    /// Every section, with per-run detail for the `STANDARD_DETAILED_RUNS`
+    /// most severe runs and a list of the others
+    #[default]
+    Standard,
+    /// Every section for every run
+    Full,
 }
 
+impl ReportDetailLevel {
+    /// Detail level suited to a session with `run_count` runs
+    pub fn for_run_count(run_count: usize) -> Self {
+        if run_count <= AUTO_FULL_MAX_RUNS {
+            ReportDetailLevel::Full
+        } else if run_count <= AUTO_STANDARD_MAX_RUNS {
+            ReportDetailLevel::Standard
+        } else {
+            ReportDetailLevel::Summary
+        }
+    }
+}
+
+/// Number of characters in a text report sparkline
+pub const SPARKLINE_WIDTH: usize = 40;
+
+/// Block characters used for sparklines, from lowest to highest
+const SPARKLINE_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
 /// Report format
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -28,6 +150,137 @@ pub enum ReportFormat {
     Html,
     Json,
     Pdf, // Note: PDF generation would require additional dependencies
+    Markdown,
+    Csv,
+}
+
+impl ReportFormat {
+    /// Format matching a file extension (case-insensitive), e.g. "md" or "PDF"
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "txt" | "text" => Some(ReportFormat::Text),
+            "html" | "htm" => Some(ReportFormat::Html),
+            "json" => Some(ReportFormat::Json),
+            "pdf" => Some(ReportFormat::Pdf),
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            "csv" => Some(ReportFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Section of a session report
+///
+/// Formats without a rendering of a section skip it; JSON and CSV are data
+/// exports and always hold every run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportSection {
+    /// CPU, memory and GPU of the machine
+    Hardware,
+    /// System state each run was captured under
+    Environment,
+    /// Cross-run roll-up of every metric
+    Trend,
+    /// Per-run sparklines of the key metrics
+    Charts,
+    /// Detected bottlenecks and subsystem grades
+    Analysis,
+    Recommendations,
+    /// Run notes and bottleneck onsets on the run timeline
+    Markers,
+    /// Latest run against the run recorded before it
+    Comparison,
+}
+
+impl ReportSection {
+    /// Every section, in the order of the default layout
+    pub const ALL: [ReportSection; 8] = [
+        ReportSection::Hardware,
+        ReportSection::Environment,
+        ReportSection::Trend,
+        ReportSection::Charts,
+        ReportSection::Analysis,
+        ReportSection::Recommendations,
+        ReportSection::Markers,
+        ReportSection::Comparison,
+    ];
+    
+    /// Serialized name of the section, e.g. "hardware"
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportSection::Hardware => "hardware",
+            ReportSection::Environment => "environment",
+            ReportSection::Trend => "trend",
+            ReportSection::Charts => "charts",
+            ReportSection::Analysis => "analysis",
+            ReportSection::Recommendations => "recommendations",
+            ReportSection::Markers => "markers",
+            ReportSection::Comparison => "comparison",
+        }
+    }
+    
+    /// Section with the given serialized name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|section| section.name() == name)
+    }
+}
+
+/// Check that no section is listed twice
+pub fn validate_sections(sections: &[ReportSection]) -> Result<(), String> {
+    for (idx, section) in sections.iter().enumerate() {
+        if sections[..idx].contains(section) {
+            return Err(format!("Report section '{}' is listed more than once", section.name()));
+        }
+    }
+    Ok(())
+}
+
+/// Parse section names in output order, rejecting unknown and duplicate names
+pub fn parse_sections(names: &[String]) -> Result<Vec<ReportSection>, String> {
+    let sections = names
+        .iter()
+        .map(|name| ReportSection::from_name(name.trim()).ok_or_else(|| format!("Unknown report section '{}'", name)))
+        .collect::<Result<Vec<_>, _>>()?;
+    validate_sections(&sections)?;
+    Ok(sections)
+}
+
+impl ReportConfig {
+    /// Detail level for a session with `run_count` runs
+    pub fn resolved_detail_level(&self, run_count: usize) -> ReportDetailLevel {
+        self.detail_level.unwrap_or_else(|| ReportDetailLevel::for_run_count(run_count))
+    }
+    
+    /// Sections to generate, in output order
+    ///
+    /// Without an explicit list the `include_*` flags select sections of the
+    /// default layout; markers are only included when listed. Duplicates in
+    /// an explicit list keep their first position.
+    pub fn resolved_sections(&self) -> Vec<ReportSection> {
+        match &self.sections {
+            Some(sections) => {
+                let mut resolved = Vec::with_capacity(sections.len());
+                for section in sections {
+                    if !resolved.contains(section) {
+                        resolved.push(*section);
+                    }
+                }
+                resolved
+            }
+            None => ReportSection::ALL
+                .into_iter()
+                .filter(|section| match section {
+                    ReportSection::Hardware | ReportSection::Environment => self.include_hardware,
+                    ReportSection::Trend | ReportSection::Charts => self.include_metrics,
+                    ReportSection::Analysis => self.include_analysis,
+                    ReportSection::Recommendations => self.include_recommendations,
+                    ReportSection::Markers => false,
+                    ReportSection::Comparison => self.include_comparison,
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Default for ReportConfig {
@@ -39,28 +292,305 @@ impl Default for ReportConfig {
             include_recommendations: true,
             include_comparison: false,
             format: ReportFormat::Html,
+            timezone: None,
+            acknowledgments: Vec::new(),
+            sections: None,
+            severity_bands: SeverityBands::default(),
+            detail_level: None,
         }
     }
 }
 
+/// Sections and per-run detail of a session report at its detail level
+struct ReportPlan {
+    level: ReportDetailLevel,
+    sections: Vec<ReportSection>,
+    /// Whether each run, by index, gets its per-run sections
+    detailed: Vec<bool>,
+}
+
+impl ReportPlan {
+    fn new(session: &Session, config: &ReportConfig) -> Self {
+        let level = config.resolved_detail_level(session.runs.len());
+        let run_count = session.runs.len();
+        let (sections, detailed) = match level {
+            ReportDetailLevel::Full => (config.resolved_sections(), vec![true; run_count]),
+            ReportDetailLevel::Summary => (vec![ReportSection::Trend], vec![false; run_count]),
+            ReportDetailLevel::Standard => {
+                let mut ranked: Vec<usize> = (0..run_count).collect();
+                // Stable, so runs of equal severity keep their order
+                ranked.sort_by_key(|&idx| std::cmp::Reverse(worst_severity(&session.runs[idx], config)));
+                let mut detailed = vec![false; run_count];
+                for idx in ranked.into_iter().take(STANDARD_DETAILED_RUNS) {
+                    detailed[idx] = true;
+                }
+                (config.resolved_sections(), detailed)
+            }
+        };
+        Self { level, sections, detailed }
+    }
+    
+    /// Runs left out of the per-run sections, as (index, run)
+    fn omitted_runs<'a>(&self, session: &'a Session) -> Vec<(usize, &'a Run)> {
+        session.runs.iter().enumerate().filter(|(idx, _)| !self.detailed[*idx]).collect()
+    }
+    
+    /// Runs given per-run sections, as (index, run)
+    fn detailed_runs<'a>(&'a self, session: &'a Session) -> impl Iterator<Item = (usize, &'a Run)> + 'a {
+        session.runs.iter().enumerate().filter(|(idx, _)| self.detailed[*idx])
+    }
+}
+
+/// Highest severity among a run's bottlenecks that were not dismissed
+fn worst_severity(run: &Run, config: &ReportConfig) -> u8 {
+    run.analysis_result
+        .iter()
+        .flat_map(|a| &a.bottlenecks)
+        .filter(|b| !is_acknowledged(b, &config.acknowledgments))
+        .map(|b| b.severity)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Most severe bottlenecks across a session that were not dismissed, as
+/// (run index, bottleneck)
+fn worst_bottlenecks<'a>(session: &'a Session, config: &ReportConfig) -> Vec<(usize, &'a Bottleneck)> {
+    let mut worst: Vec<(usize, &Bottleneck)> = session
+        .runs
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, run)| run.analysis_result.iter().flat_map(move |a| a.bottlenecks.iter().map(move |b| (idx, b))))
+        .filter(|(_, b)| !is_acknowledged(b, &config.acknowledgments))
+        .collect();
+    worst.sort_by_key(|(_, b)| std::cmp::Reverse(b.severity));
+    worst.truncate(SUMMARY_WORST_BOTTLENECKS);
+    worst
+}
+
+/// Note on what a shortened report leaves out, `None` when nothing is
+fn omitted_runs_note(plan: &ReportPlan) -> Option<String> {
+    let run_count = plan.detailed.len();
+    let detailed = plan.detailed.iter().filter(|d| **d).count();
+    match plan.level {
+        ReportDetailLevel::Summary => Some(format!(
+            "Summary of {} runs; generate a full report for per-run detail.",
+            run_count
+        )),
+        _ if detailed < run_count => Some(format!(
+            "Detail is shown for the {} most severe of {} runs; generate a full report for the others.",
+            detailed, run_count
+        )),
+        _ => None,
+    }
+}
+
+/// Number of bottlenecks in a run that were not dismissed
+fn active_bottleneck_count(run: &Run, config: &ReportConfig) -> usize {
+    run.analysis_result
+        .iter()
+        .flat_map(|a| &a.bottlenecks)
+        .filter(|b| !is_acknowledged(b, &config.acknowledgments))
+        .count()
+}
+
+/// Progress of an incremental session report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportProgress {
+    pub runs_processed: usize,
+    pub total_runs: usize,
+}
+
+/// Tracks per-run progress and cancellation while a report is built
+struct ProgressTracker<'a> {
+    cancel: &'a AtomicBool,
+    on_progress: &'a mut dyn FnMut(ReportProgress),
+    progress: ReportProgress,
+}
+
+impl ProgressTracker<'_> {
+    /// Record one more processed run, failing if cancellation was requested
+    fn advance(&mut self) -> Result<(), PersistenceError> {
+        if self.cancel.load(Ordering::Relaxed) {
+            return Err(PersistenceError::Cancelled("report generation".to_string()));
+        }
+        self.progress.runs_processed += 1;
+        (self.on_progress)(self.progress);
+        Ok(())
+    }
+}
+
+/// Statistics of one metric across every run of a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricRollup {
+    pub metric_type: MetricType,
+    pub unit: String,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Run holding the lowest sample
+    pub min_run: String,
+    /// Run holding the highest sample
+    pub max_run: String,
+}
+
+/// Cross-run summary of a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRollup {
+    /// One entry per metric type recorded in any run, ordered by metric type name
+    pub metrics: Vec<MetricRollup>,
+    /// Bottleneck type detected most often across all runs
+    pub most_common_bottleneck: Option<BottleneckType>,
+    /// Number of times the most common bottleneck was detected
+    pub most_common_bottleneck_count: usize,
+}
+
+/// Roll up every run of a session into per-metric extremes and averages
+///
+/// Ties for the most common bottleneck go to the type detected first.
+pub fn session_rollup(session: &Session) -> SessionRollup {
+    let mut by_metric: Vec<(MetricType, Vec<(&str, &MetricSample)>)> = Vec::new();
+    for run in &session.runs {
+        for sample in run.metrics_streams.values().flatten().filter(|s| s.value.is_finite()) {
+            match by_metric.iter_mut().find(|(metric_type, _)| *metric_type == sample.metric_type) {
+                Some((_, samples)) => samples.push((run.name.as_str(), sample)),
+                None => by_metric.push((sample.metric_type.clone(), vec![(run.name.as_str(), sample)])),
+            }
+        }
+    }
+    by_metric.sort_by_key(|(metric_type, _)| format!("{:?}", metric_type));
+    
+    let metrics = by_metric
+        .into_iter()
+        .filter_map(|(metric_type, samples)| {
+            let (min_run, min) = samples.iter().min_by(|a, b| a.1.value.total_cmp(&b.1.value))?;
+            let (max_run, max) = samples.iter().max_by(|a, b| a.1.value.total_cmp(&b.1.value))?;
+            Some(MetricRollup {
+                unit: samples[0].1.unit.clone(),
+                min: min.value,
+                max: max.value,
+                mean: mean(samples.iter().map(|(_, s)| s.value))?,
+                min_run: min_run.to_string(),
+                max_run: max_run.to_string(),
+                metric_type,
+            })
+        })
+        .collect();
+    
+    let mut counts: Vec<(BottleneckType, usize)> = Vec::new();
+    for bottleneck in session.runs.iter().filter_map(|r| r.analysis_result.as_ref()).flat_map(|a| &a.bottlenecks) {
+        match counts.iter_mut().find(|(t, _)| *t == bottleneck.bottleneck_type) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((bottleneck.bottleneck_type.clone(), 1)),
+        }
+    }
+    // Reverse so that `max_by_key`, which keeps the last maximum, prefers the earliest type
+    let most_common = counts.into_iter().rev().max_by_key(|(_, count)| *count);
+    
+    SessionRollup {
+        metrics,
+        most_common_bottleneck_count: most_common.as_ref().map_or(0, |(_, count)| *count),
+        most_common_bottleneck: most_common.map(|(t, _)| t),
+    }
+}
+
 /// Generate a comprehensive report for a session
 pub fn generate_session_report(
     session: &Session,
     hardware: &HardwareConfig,
     config: &ReportConfig,
 ) -> String {
+    let never_cancelled = AtomicBool::new(false);
+    // Without a cancellation request generation cannot fail
+    generate_session_report_incremental(session, hardware, config, &never_cancelled, |_| {})
+        .unwrap_or_default()
+}
+
+/// Generate a session report run by run
+///
+/// `on_progress` is called after each run has been processed. Setting
+/// `cancel` stops generation before the next run and returns
+/// `PersistenceError::Cancelled`.
+pub fn generate_session_report_incremental<F>(
+    session: &Session,
+    hardware: &HardwareConfig,
+    config: &ReportConfig,
+    cancel: &AtomicBool,
+    mut on_progress: F,
+) -> Result<String, PersistenceError>
+where
+    F: FnMut(ReportProgress),
+{
+    let mut tracker = ProgressTracker {
+        cancel,
+        on_progress: &mut on_progress,
+        progress: ReportProgress {
+            runs_processed: 0,
+            total_runs: session.runs.len(),
+        },
+    };
+    
+    match config.format {
+        ReportFormat::Text => generate_text_report(session, hardware, config, &mut tracker),
+        ReportFormat::Html => generate_html_report(session, hardware, config, &mut tracker),
+        ReportFormat::Json => generate_json_report(session, hardware, config, &mut tracker),
+        ReportFormat::Pdf => {
+            // A string cannot hold the binary PDF; `render_report` produces it.
+            // Here, generate HTML which can be converted to PDF
+            generate_html_report(session, hardware, config, &mut tracker)
+        }
+        ReportFormat::Markdown => generate_markdown_report(session, hardware, config, &mut tracker),
+        ReportFormat::Csv => generate_csv_report(session, &mut tracker),
+    }
+}
+
+/// Generate a session report as the bytes of a file in the configured format
+///
+/// Text formats are UTF-8; PDF is rendered from the text report.
+pub fn render_report(
+    session: &Session,
+    hardware: &HardwareConfig,
+    config: &ReportConfig,
+) -> Result<Vec<u8>, PersistenceError> {
     match config.format {
-        ReportFormat::Text => generate_text_report(session, hardware, config),
-        ReportFormat::Html => generate_html_report(session, hardware, config),
-        ReportFormat::Json => generate_json_report(session, hardware, config),
         ReportFormat::Pdf => {
-            // PDF generation would require additional dependencies
-            // For now, generate HTML which can be converted to PDF
-            generate_html_report(session, hardware, config)
+            let text_config = ReportConfig {
+                format: ReportFormat::Text,
+                ..config.clone()
+            };
+            Ok(pdf::text_to_pdf(&generate_session_report(session, hardware, &text_config)))
         }
+        _ => Ok(generate_session_report(session, hardware, config).into_bytes()),
     }
 }
 
+/// Generate a session report and write it to `path`
+///
+/// A recognized file extension decides the format, overriding
+/// `config.format`; otherwise the configured format is written. Rendering
+/// runs on the blocking pool, as large sessions take a while. Returns the
+/// number of bytes written.
+pub async fn write_report(
+    session: &Session,
+    hardware: &HardwareConfig,
+    config: &ReportConfig,
+    path: &Path,
+) -> Result<u64, PersistenceError> {
+    let mut config = config.clone();
+    if let Some(format) = path.extension().and_then(|e| e.to_str()).and_then(ReportFormat::from_extension) {
+        config.format = format;
+    }
+    
+    let (session, hardware) = (session.clone(), hardware.clone());
+    let bytes = tokio::task::spawn_blocking(move || render_report(&session, &hardware, &config))
+        .await
+        .map_err(|e| PersistenceError::Unknown(format!("Report rendering task failed: {}", e)))??;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, &bytes).await?;
+    Ok(bytes.len() as u64)
+}
+
 /// Generate a comparison report
 pub fn generate_comparison_report(
     comparison: &ComparisonResult,
@@ -73,6 +603,8 @@ pub fn generate_comparison_report(
         ReportFormat::Html => generate_html_comparison_report(comparison, run1, run2, config),
         ReportFormat::Json => generate_json_comparison_report(comparison, run1, run2, config),
         ReportFormat::Pdf => generate_html_comparison_report(comparison, run1, run2, config),
+        // Comparisons have no tabular layout; both get the text report
+        ReportFormat::Markdown | ReportFormat::Csv => generate_text_comparison_report(comparison, run1, run2, config),
     }
 }
 
@@ -81,7 +613,10 @@ fn generate_text_report(
     session: &Session,
     hardware: &HardwareConfig,
     config: &ReportConfig,
-) -> String {
+    tracker: &mut ProgressTracker,
+) -> Result<String, PersistenceError> {
+    let plan = ReportPlan::new(session, config);
+    let sections = plan.sections.clone();
     let mut report = String::new();
     
     report.push_str("=".repeat(80).as_str());
@@ -92,69 +627,359 @@ fn generate_text_report(
     
     report.push_str(&format!("Session: {}\n", session.id));
     report.push_str(&format!("Profile: {}\n", session.profile.name));
-    report.push_str(&format!("Started: {}\n", session.start_time));
+    let timezone = config.timezone.as_deref();
+    report.push_str(&format!("Started: {}\n", format_timestamp(&session.start_time, timezone)));
     if let Some(end_time) = session.end_time {
-        report.push_str(&format!("Ended: {}\n", end_time));
+        report.push_str(&format!("Ended: {}\n", format_timestamp(&end_time, timezone)));
     }
     report.push_str(&format!("Runs: {}\n", session.runs.len()));
     report.push_str("\n");
     
-    if config.include_hardware {
-        report.push_str("-".repeat(80).as_str());
-        report.push_str("\n");
-        report.push_str("HARDWARE CONFIGURATION\n");
-        report.push_str("-".repeat(80).as_str());
-        report.push_str("\n\n");
+    // Per-run sections are built in a single pass so progress can be reported per run
+    let mut trends = String::new();
+    let mut analysis_section = String::new();
+    let mut recommendations = String::new();
+    let mut markers = String::new();
+    
+    for (idx, run) in session.runs.iter().enumerate() {
+        if !plan.detailed[idx] {
+            tracker.advance()?;
+            continue;
+        }
         
-        report.push_str(&format!("CPU: {} ({} cores, {} threads)\n", 
-            hardware.cpu.model, hardware.cpu.cores, hardware.cpu.threads));
-        report.push_str(&format!("Memory: {:.2} GB\n", 
-            hardware.memory.total_mb as f64 / 1024.0));
-        if !hardware.gpus.is_empty() {
-            report.push_str(&format!("GPU: {}\n", hardware.gpus[0].model));
+        if sections.contains(&ReportSection::Charts) {
+            trends.push_str(&format!("Run {}: {}\n", idx + 1, run.name));
+            for (label, metric_type) in [
+                ("CPU", MetricType::CpuUtilization),
+                ("GPU", MetricType::GpuUtilization),
+                ("VRAM", MetricType::GpuVramUsagePercent),
+                ("Temp", MetricType::Temperature),
+                ("GPU Temp", MetricType::GpuTemperature),
+            ] {
+                let mut samples: Vec<&MetricSample> = run.metrics_streams
+                    .values()
+                    .flatten()
+                    .filter(|s| s.metric_type == metric_type)
+                    .collect();
+                samples.sort_by_key(|s| s.timestamp);
+                
+                let values: Vec<f64> = samples.iter().map(|s| s.value).collect();
+                // Empty streams are omitted rather than drawn as a flat line
+                if let Some(sparkline) = generate_sparkline(&values, SPARKLINE_WIDTH) {
+                    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+                    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                    trends.push_str(&format!("  {:<9}{}  min {:.1} / max {:.1} {}\n",
+                        label, sparkline, min, max, samples[0].unit));
+                }
+            }
+            trends.push_str("\n");
         }
-        report.push_str("\n");
-    }
-    
-    if config.include_analysis && !session.runs.is_empty() {
-        report.push_str("-".repeat(80).as_str());
-        report.push_str("\n");
-        report.push_str("BOTTLENECK ANALYSIS\n");
-        report.push_str("-".repeat(80).as_str());
-        report.push_str("\n\n");
         
-        for (idx, run) in session.runs.iter().enumerate() {
-            if let Some(analysis) = &run.analysis_result {
-                report.push_str(&format!("Run {}: {}\n", idx + 1, run.name));
-                report.push_str(&format!("  Bottlenecks detected: {}\n", analysis.bottlenecks.len()));
+        if sections.contains(&ReportSection::Markers) {
+            let run_markers = run_markers(run);
+            if !run_markers.is_empty() {
+                markers.push_str(&format!("Run {}: {}\n", idx + 1, run.name));
+                for (at, label) in run_markers {
+                    markers.push_str(&format!("  {:<9}{}\n", at, label));
+                }
+                markers.push('\n');
+            }
+        }
+        
+        if let Some(analysis) = &run.analysis_result {
+            if sections.contains(&ReportSection::Analysis) {
+                analysis_section.push_str(&format!("Run {}: {}\n", idx + 1, run.name));
+                analysis_section.push_str(&format!("  Bottlenecks detected: {}\n", analysis.bottlenecks.len()));
                 
                 for bottleneck in &analysis.bottlenecks {
-                    report.push_str(&format!("  - {} (Severity: {}/100)\n", 
-                        bottleneck.summary, bottleneck.severity));
-                    report.push_str(&format!("    {}\n", bottleneck.details));
+                    analysis_section.push_str(&format!("  - {} (Severity: {}/100){}\n", 
+                        bottleneck.summary, bottleneck.severity,
+                        if is_acknowledged(bottleneck, &config.acknowledgments) { " [dismissed]" } else { "" }));
+                    if let Some(onset) = onset_offset(bottleneck, run) {
+                        analysis_section.push_str(&format!("    Onset: {} into the run\n", onset));
+                    }
+                    if let Some(phrase) = active_time_phrase(bottleneck) {
+                        analysis_section.push_str(&format!("    Active: {}\n", phrase));
+                    }
+                    analysis_section.push_str(&format!("    {}\n", bottleneck.details));
                 }
-                report.push_str("\n");
+                
+                let grades = grade_subsystems(analysis, hardware, &run_samples(run));
+                analysis_section.push_str("  Grades:\n");
+                for subsystem in &GRADED_SUBSYSTEMS {
+                    analysis_section.push_str(&format!("    {:<9}{}\n",
+                        subsystem_name(subsystem), grade_label(&grades, subsystem)));
+                }
+                analysis_section.push_str("\n");
+            }
+            
+            if sections.contains(&ReportSection::Recommendations) {
+                recommendations.push_str(&format!("Run {} Recommendations:\n", idx + 1));
+                let mut recs = run_recommendations(analysis, session, config);
+                sort_easy_wins_first(&mut recs);
+                for (label, category) in [
+                    ("Configuration", RecommendationCategory::Config),
+                    ("Cooling", RecommendationCategory::Cooling),
+                    ("Upgrades", RecommendationCategory::Upgrade),
+                ] {
+                    let mut in_category = recs.iter().filter(|r| r.category == category).peekable();
+                    if in_category.peek().is_none() {
+                        continue;
+                    }
+                    recommendations.push_str(&format!("  {}:\n", label));
+                    for rec in in_category {
+                        recommendations.push_str(&format!("    - {}\n", rec.text()));
+                    }
+                }
+                recommendations.push_str("\n");
             }
         }
+        
+        tracker.advance()?;
     }
     
-    if config.include_recommendations && !session.runs.is_empty() {
-        report.push_str("-".repeat(80).as_str());
-        report.push_str("\n");
-        report.push_str("RECOMMENDATIONS\n");
-        report.push_str("-".repeat(80).as_str());
-        report.push_str("\n\n");
-        
-        for (idx, run) in session.runs.iter().enumerate() {
-            if let Some(_analysis) = &run.analysis_result {
-                report.push_str(&format!("Run {} Recommendations:\n", idx + 1));
-                // Recommendations would come from insights generation
+    for section in &sections {
+        match section {
+            ReportSection::Hardware => {
+                push_text_heading(&mut report, "HARDWARE CONFIGURATION");
+                report.push_str(&format!("CPU: {} ({} cores, {} threads)\n", 
+                    hardware.cpu.model, hardware.cpu.cores, hardware.cpu.threads));
+                report.push_str(&format!("Memory: {:.2} GB\n", 
+                    hardware.memory.total_mb as f64 / 1024.0));
+                if !hardware.gpus.is_empty() {
+                    report.push_str(&format!("GPU: {}\n", hardware.gpus[0].model));
+                }
                 report.push_str("\n");
             }
+            ReportSection::Environment => {
+                if plan.detailed_runs(session).all(|(_, r)| r.environment.is_none()) {
+                    continue;
+                }
+                push_text_heading(&mut report, "RUN ENVIRONMENT");
+                for (idx, run) in plan.detailed_runs(session) {
+                    if let Some(environment) = &run.environment {
+                        report.push_str(&format!("Run {} environment:\n", idx + 1));
+                        for (label, value) in environment_fields(environment) {
+                            report.push_str(&format!("  {:<20}{}\n", format!("{}:", label), value));
+                        }
+                        report.push_str("\n");
+                    }
+                }
+            }
+            // Cross-run roll-up, only useful once there is more than one run
+            ReportSection::Trend => {
+                if session.runs.len() < 2 {
+                    continue;
+                }
+                let rollup = session_rollup(session);
+                push_text_heading(&mut report, "SESSION SUMMARY");
+                for metric in &rollup.metrics {
+                    report.push_str(&format!("{:<24}min {:.1} ({}) / max {:.1} ({}) / mean {:.1} {}\n",
+                        format!("{:?}", metric.metric_type), metric.min, metric.min_run,
+                        metric.max, metric.max_run, metric.mean, metric.unit));
+                }
+                if let Some(bottleneck_type) = &rollup.most_common_bottleneck {
+                    report.push_str(&format!("\nMost common bottleneck: {:?} ({} detections)\n",
+                        bottleneck_type, rollup.most_common_bottleneck_count));
+                }
+                report.push_str("\n");
+            }
+            ReportSection::Charts => {
+                if !session.runs.is_empty() {
+                    push_text_heading(&mut report, "METRIC TRENDS");
+                    report.push_str(&trends);
+                }
+            }
+            ReportSection::Analysis => {
+                if !session.runs.is_empty() {
+                    push_text_heading(&mut report, "BOTTLENECK ANALYSIS");
+                    report.push_str(&analysis_section);
+                }
+            }
+            ReportSection::Recommendations => {
+                if !session.runs.is_empty() {
+                    push_text_heading(&mut report, "RECOMMENDATIONS");
+                    report.push_str(&recommendations);
+                }
+            }
+            ReportSection::Markers => {
+                if !markers.is_empty() {
+                    push_text_heading(&mut report, "MARKERS");
+                    report.push_str(&markers);
+                }
+            }
+            ReportSection::Comparison => {
+                if let Some(previous) = compare_to_previous_run(session, DEFAULT_MIN_EFFECT_PERCENT) {
+                    push_text_heading(&mut report, "COMPARISON WITH PREVIOUS RUN");
+                    report.push_str(&format!("{} vs {}\n", previous.latest_run_name, previous.previous_run_name));
+                    report.push_str(&format!("{}\n", previous.verdict));
+                    report.push_str(&format!("{}\n", previous.comparison.summary));
+                    report.push('\n');
+                }
+            }
         }
     }
     
-    report
+    // Shortened reports list what the per-run sections leave out
+    let omitted = plan.omitted_runs(session);
+    if plan.level == ReportDetailLevel::Summary {
+        push_text_heading(&mut report, "WORST BOTTLENECKS");
+        let worst = worst_bottlenecks(session, config);
+        if worst.is_empty() {
+            report.push_str("No bottlenecks detected.\n");
+        }
+        for (idx, bottleneck) in worst {
+            report.push_str(&format!("Run {}: {} - {} (Severity: {}/100)\n",
+                idx + 1, session.runs[idx].name, bottleneck.summary, bottleneck.severity));
+        }
+        report.push('\n');
+    } else if !omitted.is_empty() {
+        push_text_heading(&mut report, "OTHER RUNS");
+        for (idx, run) in omitted {
+            report.push_str(&format!("Run {}: {} ({} bottlenecks, worst severity {}/100)\n",
+                idx + 1, run.name, active_bottleneck_count(run, config), worst_severity(run, config)));
+        }
+        report.push('\n');
+    }
+    if let Some(note) = omitted_runs_note(&plan) {
+        report.push_str(&format!("{}\n", note));
+    }
+    
+    Ok(report)
+}
+
+/// Append a text report section heading framed by rules
+fn push_text_heading(report: &mut String, title: &str) {
+    report.push_str("-".repeat(80).as_str());
+    report.push('\n');
+    report.push_str(title);
+    report.push('\n');
+    report.push_str("-".repeat(80).as_str());
+    report.push_str("\n\n");
+}
+
+/// Timeline markers of a run as (offset, label), ordered by offset
+///
+/// Run notes are pinned to the start of the run; every bottleneck with a
+/// known onset adds a marker at that onset.
+fn run_markers(run: &Run) -> Vec<(String, String)> {
+    let mut markers: Vec<(i64, String, String)> = Vec::new();
+    if let Some(notes) = run.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        markers.push((0, "start".to_string(), format!("Note: {}", notes)));
+    }
+    let run_start = run.metrics_streams.values().flatten().map(|s| s.timestamp).min();
+    if let Some(analysis) = &run.analysis_result {
+        for bottleneck in &analysis.bottlenecks {
+            if let (Some(onset), Some(start)) = (bottleneck.onset, run_start) {
+                let seconds = (onset - start).num_seconds().max(0);
+                let at = onset_offset(bottleneck, run).unwrap_or_default();
+                markers.push((seconds, at, bottleneck.summary.clone()));
+            }
+        }
+    }
+    markers.sort_by_key(|(seconds, _, _)| *seconds);
+    markers.into_iter().map(|(_, at, label)| (at, label)).collect()
+}
+
+/// Labelled display values of the known environment fields
+fn environment_fields(environment: &RunEnvironment) -> Vec<(&'static str, String)> {
+    let mut fields = Vec::new();
+    if let Some(os) = &environment.os_version {
+        fields.push(("OS", os.clone()));
+    }
+    if !environment.gpu_driver_versions.is_empty() {
+        fields.push(("GPU drivers", environment.gpu_driver_versions.join(", ")));
+    }
+    if let Some(plan) = &environment.power_plan {
+        fields.push(("Power plan", plan.clone()));
+    }
+    if let Some(count) = environment.background_process_count {
+        fields.push(("Processes", count.to_string()));
+    }
+    if let Some(vm) = environment.virtual_machine {
+        fields.push(("Virtual machine", if vm { "Yes" } else { "No" }.to_string()));
+    }
+    if let Some(process) = &environment.foreground_process {
+        let name = process.name.clone().unwrap_or_else(|| "unknown".to_string());
+        fields.push(("Foreground app", format!("{} (pid {})", name, process.pid)));
+    }
+    fields
+}
+
+/// Time from the first sample of a run to a bottleneck's onset, e.g. "1m 05s"
+fn onset_offset(bottleneck: &Bottleneck, run: &Run) -> Option<String> {
+    let onset = bottleneck.onset?;
+    let run_start = run.metrics_streams.values().flatten().map(|s| s.timestamp).min()?;
+    let seconds = (onset - run_start).num_seconds().max(0);
+    Some(if seconds >= 60 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    })
+}
+
+/// All samples of a run across its metric streams
+fn run_samples(run: &Run) -> Vec<MetricSample> {
+    run.metrics_streams.values().flatten().cloned().collect()
+}
+
+/// Format a UTC timestamp for display in the given timezone
+///
+/// `None`, "local" and "auto" use the system timezone. Other values are IANA
+/// names (e.g. "America/New_York"); unknown names fall back to UTC.
+pub fn format_timestamp(timestamp: &DateTime<Utc>, timezone: Option<&str>) -> String {
+    const DISPLAY_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+    
+    match timezone.map(str::trim) {
+        None | Some("") | Some("local") | Some("auto") => {
+            timestamp.with_timezone(&Local).format(DISPLAY_FORMAT).to_string()
+        }
+        Some(name) => match name.parse::<Tz>() {
+            Ok(tz) => timestamp.with_timezone(&tz).format(DISPLAY_FORMAT).to_string(),
+            Err(_) => {
+                log::warn!("Unknown display timezone '{}', using UTC", name);
+                timestamp.format(DISPLAY_FORMAT).to_string()
+            }
+        },
+    }
+}
+
+/// Render values as a Unicode sparkline of at most `width` characters
+///
+/// Values are averaged into `width` buckets and scaled to the min/max of the
+/// buckets, so spiky data still uses the full block range. Returns `None` for
+/// an empty input.
+pub fn generate_sparkline(values: &[f64], width: usize) -> Option<String> {
+    if values.is_empty() || width == 0 {
+        return None;
+    }
+    
+    let width = width.min(values.len());
+    let buckets: Vec<f64> = (0..width)
+        .map(|i| {
+            let start = i * values.len() / width;
+            let end = (i + 1) * values.len() / width;
+            let bucket = &values[start..end];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect();
+    
+    let min = buckets.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = buckets.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    let top = (SPARKLINE_BLOCKS.len() - 1) as f64;
+    
+    Some(buckets
+        .iter()
+        .map(|v| {
+            let level = if range > 0.0 {
+                ((v - min) / range * top).round() as usize
+            } else {
+                0
+            };
+            SPARKLINE_BLOCKS[level.min(SPARKLINE_BLOCKS.len() - 1)]
+        })
+        .collect())
 }
 
 /// Generate HTML report
@@ -162,7 +987,8 @@ fn generate_html_report(
     session: &Session,
     hardware: &HardwareConfig,
     config: &ReportConfig,
-) -> String {
+    tracker: &mut ProgressTracker,
+) -> Result<String, PersistenceError> {
     let mut html = String::new();
     
     html.push_str("<!DOCTYPE html>\n");
@@ -186,56 +1012,214 @@ fn generate_html_report(
     html.push_str("      <h2>Session Information</h2>\n");
     html.push_str(&format!("      <p><strong>Session:</strong> {}</p>\n", session.id));
     html.push_str(&format!("      <p><strong>Profile:</strong> {}</p>\n", session.profile.name));
-    html.push_str(&format!("      <p><strong>Started:</strong> {}</p>\n", session.start_time));
+    let timezone = config.timezone.as_deref();
+    html.push_str(&format!("      <p><strong>Started:</strong> {}</p>\n",
+        format_timestamp(&session.start_time, timezone)));
     if let Some(end_time) = session.end_time {
-        html.push_str(&format!("      <p><strong>Ended:</strong> {}</p>\n", end_time));
+        html.push_str(&format!("      <p><strong>Ended:</strong> {}</p>\n",
+            format_timestamp(&end_time, timezone)));
     }
     html.push_str(&format!("      <p><strong>Runs:</strong> {}</p>\n", session.runs.len()));
     html.push_str("    </section>\n");
     
-    if config.include_hardware {
-        html.push_str("    <section class=\"hardware-section\">\n");
-        html.push_str("      <h2>Hardware Configuration</h2>\n");
-        html.push_str("      <div class=\"hardware-grid\">\n");
-        html.push_str(&format!(
-            "        <div class=\"hardware-card\">\n          <h3>CPU</h3>\n          <p>{}</p>\n          <p>{} cores, {} threads</p>\n        </div>\n",
-            hardware.cpu.model, hardware.cpu.cores, hardware.cpu.threads
-        ));
-        html.push_str(&format!(
-            "        <div class=\"hardware-card\">\n          <h3>Memory</h3>\n          <p>{:.2} GB</p>\n        </div>\n",
-            hardware.memory.total_mb as f64 / 1024.0
-        ));
-        if !hardware.gpus.is_empty() {
-            html.push_str(&format!(
-                "        <div class=\"hardware-card\">\n          <h3>GPU</h3>\n          <p>{}</p>\n        </div>\n",
-                hardware.gpus[0].model
-            ));
-        }
-        html.push_str("      </div>\n");
-        html.push_str("    </section>\n");
-    }
+    let plan = ReportPlan::new(session, config);
+    let sections = plan.sections.clone();
     
-    if config.include_analysis && !session.runs.is_empty() {
-        html.push_str("    <section class=\"analysis-section\">\n");
-        html.push_str("      <h2>Bottleneck Analysis</h2>\n");
-        
-        for (idx, run) in session.runs.iter().enumerate() {
+    // Per-run analysis is built in a single pass so progress can be reported per run
+    let mut analysis_section = String::new();
+    for (idx, run) in session.runs.iter().enumerate() {
+        if sections.contains(&ReportSection::Analysis) && plan.detailed[idx] {
             if let Some(analysis) = &run.analysis_result {
-                html.push_str(&format!("      <div class=\"run-analysis\">\n"));
-                html.push_str(&format!("        <h3>Run {}: {}</h3>\n", idx + 1, run.name));
-                html.push_str(&format!("        <p class=\"bottleneck-count\">Bottlenecks detected: {}</p>\n", 
+                analysis_section.push_str(&format!("      <div class=\"run-analysis\">\n"));
+                analysis_section.push_str(&format!("        <h3>Run {}: {}</h3>\n", idx + 1, run.name));
+                analysis_section.push_str(&format!("        <p class=\"bottleneck-count\">Bottlenecks detected: {}</p>\n", 
                     analysis.bottlenecks.len()));
                 
                 for bottleneck in &analysis.bottlenecks {
-                    html.push_str("        <div class=\"bottleneck-card\">\n");
-                    html.push_str(&format!("          <h4>{}</h4>\n", bottleneck.summary));
-                    html.push_str(&format!("          <p class=\"severity\">Severity: {}/100</p>\n", 
-                        bottleneck.severity));
-                    html.push_str(&format!("          <p>{}</p>\n", bottleneck.details));
-                    html.push_str("        </div>\n");
+                    let dismissed = is_acknowledged(bottleneck, &config.acknowledgments);
+                    let tier = config.severity_bands.tier(bottleneck.severity);
+                    analysis_section.push_str(&format!(
+                        "        <div class=\"bottleneck-card severity-{}{}\">\n",
+                        tier,
+                        if dismissed { " dismissed" } else { "" }
+                    ));
+                    analysis_section.push_str(&format!("          <h4>{}</h4>\n", bottleneck.summary));
+                    analysis_section.push_str(&format!(
+                        "          <p class=\"severity\"><span class=\"severity-badge severity-{}\">{}</span> Severity: {}/100</p>\n",
+                        tier, tier, bottleneck.severity));
+                    if dismissed {
+                        analysis_section.push_str("          <p class=\"dismissed\">Dismissed (acknowledged)</p>\n");
+                    }
+                    if let Some(onset) = onset_offset(bottleneck, run) {
+                        analysis_section.push_str(&format!("          <p class=\"onset\">Onset: {} into the run</p>\n", onset));
+                    }
+                    if let Some(phrase) = active_time_phrase(bottleneck) {
+                        analysis_section.push_str(&format!("          <p class=\"active-time\">{}</p>\n", phrase));
+                    }
+                    analysis_section.push_str(&format!("          <p>{}</p>\n", bottleneck.details));
+                    analysis_section.push_str("        </div>\n");
+                }
+                
+                let grades = grade_subsystems(analysis, hardware, &run_samples(run));
+                analysis_section.push_str("        <table class=\"grade-table\">\n");
+                analysis_section.push_str("          <tr><th>Subsystem</th><th>Grade</th></tr>\n");
+                for subsystem in &GRADED_SUBSYSTEMS {
+                    analysis_section.push_str(&format!("          <tr><td>{}</td><td>{}</td></tr>\n",
+                        subsystem_name(subsystem), grade_label(&grades, subsystem)));
+                }
+                analysis_section.push_str("        </table>\n");
+                analysis_section.push_str("      </div>\n");
+            }
+        }
+        
+        tracker.advance()?;
+    }
+    
+    // Charts and recommendations have no HTML rendering
+    for section in &sections {
+        match section {
+            ReportSection::Hardware => {
+                html.push_str("    <section class=\"hardware-section\">\n");
+                html.push_str("      <h2>Hardware Configuration</h2>\n");
+                html.push_str("      <div class=\"hardware-grid\">\n");
+                html.push_str(&format!(
+                    "        <div class=\"hardware-card\">\n          <h3>CPU</h3>\n          <p>{}</p>\n          <p>{} cores, {} threads</p>\n        </div>\n",
+                    hardware.cpu.model, hardware.cpu.cores, hardware.cpu.threads
+                ));
+                html.push_str(&format!(
+                    "        <div class=\"hardware-card\">\n          <h3>Memory</h3>\n          <p>{:.2} GB</p>\n        </div>\n",
+                    hardware.memory.total_mb as f64 / 1024.0
+                ));
+                if !hardware.gpus.is_empty() {
+                    html.push_str(&format!(
+                        "        <div class=\"hardware-card\">\n          <h3>GPU</h3>\n          <p>{}</p>\n        </div>\n",
+                        hardware.gpus[0].model
+                    ));
                 }
                 html.push_str("      </div>\n");
+                html.push_str("    </section>\n");
             }
+            ReportSection::Environment => {
+                if plan.detailed_runs(session).all(|(_, r)| r.environment.is_none()) {
+                    continue;
+                }
+                html.push_str("    <section class=\"environment-section\">\n");
+                html.push_str("      <h2>Run Environment</h2>\n");
+                for (idx, run) in plan.detailed_runs(session) {
+                    if let Some(environment) = &run.environment {
+                        html.push_str(&format!("      <h3>Run {} Environment</h3>\n", idx + 1));
+                        html.push_str("      <table class=\"environment-table\">\n");
+                        for (label, value) in environment_fields(environment) {
+                            html.push_str(&format!("        <tr><th>{}</th><td>{}</td></tr>\n", label, value));
+                        }
+                        html.push_str("      </table>\n");
+                    }
+                }
+                html.push_str("    </section>\n");
+            }
+            ReportSection::Trend => {
+                if session.runs.len() < 2 {
+                    continue;
+                }
+                let rollup = session_rollup(session);
+                html.push_str("    <section class=\"rollup-section\">\n");
+                html.push_str("      <h2>Session Summary</h2>\n");
+                html.push_str("      <table>\n");
+                html.push_str("        <tr><th>Metric</th><th>Min</th><th>Max</th><th>Mean</th><th>Unit</th></tr>\n");
+                for metric in &rollup.metrics {
+                    html.push_str(&format!(
+                        "        <tr><td>{:?}</td><td>{:.1} ({})</td><td>{:.1} ({})</td><td>{:.1}</td><td>{}</td></tr>\n",
+                        metric.metric_type, metric.min, metric.min_run, metric.max, metric.max_run, metric.mean, metric.unit
+                    ));
+                }
+                html.push_str("      </table>\n");
+                if let Some(bottleneck_type) = &rollup.most_common_bottleneck {
+                    html.push_str(&format!("      <p><strong>Most common bottleneck:</strong> {:?} ({} detections)</p>\n",
+                        bottleneck_type, rollup.most_common_bottleneck_count));
+                }
+                html.push_str("    </section>\n");
+            }
+            ReportSection::Analysis => {
+                if !session.runs.is_empty() {
+                    html.push_str("    <section class=\"analysis-section\">\n");
+                    html.push_str("      <h2>Bottleneck Analysis</h2>\n");
+                    html.push_str(&analysis_section);
+                    html.push_str("    </section>\n");
+                }
+            }
+            ReportSection::Markers => {
+                let mut markers = String::new();
+                for (idx, run) in plan.detailed_runs(session) {
+                    let run_markers = run_markers(run);
+                    if run_markers.is_empty() {
+                        continue;
+                    }
+                    markers.push_str(&format!("      <h3>Run {}: {}</h3>\n", idx + 1, run.name));
+                    markers.push_str("      <table class=\"marker-table\">\n");
+                    for (at, label) in run_markers {
+                        markers.push_str(&format!("        <tr><th>{}</th><td>{}</td></tr>\n", at, label));
+                    }
+                    markers.push_str("      </table>\n");
+                }
+                if !markers.is_empty() {
+                    html.push_str("    <section class=\"markers-section\">\n");
+                    html.push_str("      <h2>Markers</h2>\n");
+                    html.push_str(&markers);
+                    html.push_str("    </section>\n");
+                }
+            }
+            ReportSection::Comparison => {
+                if let Some(previous) = compare_to_previous_run(session, DEFAULT_MIN_EFFECT_PERCENT) {
+                    html.push_str("    <section class=\"comparison-section\">\n");
+                    html.push_str("      <h2>Comparison With Previous Run</h2>\n");
+                    html.push_str(&format!("      <p><strong>{}</strong> vs {}</p>\n",
+                        previous.latest_run_name, previous.previous_run_name));
+                    html.push_str(&format!("      <p>{}</p>\n", previous.verdict));
+                    html.push_str(&format!("      <p>{}</p>\n", previous.comparison.summary));
+                    html.push_str("    </section>\n");
+                }
+            }
+            ReportSection::Charts | ReportSection::Recommendations => {}
+        }
+    }
+    
+    // Shortened reports list what the per-run sections leave out
+    let omitted = plan.omitted_runs(session);
+    if plan.level == ReportDetailLevel::Summary {
+        html.push_str("    <section class=\"worst-bottlenecks-section\">\n");
+        html.push_str("      <h2>Worst Bottlenecks</h2>\n");
+        let worst = worst_bottlenecks(session, config);
+        if worst.is_empty() {
+            html.push_str("      <p>No bottlenecks detected.</p>\n");
+        } else {
+            html.push_str("      <table>\n");
+            html.push_str("        <tr><th>Run</th><th>Bottleneck</th><th>Severity</th></tr>\n");
+            for (idx, bottleneck) in worst {
+                html.push_str(&format!(
+                    "        <tr><td>{}: {}</td><td>{}</td><td><span class=\"severity-badge severity-{}\">{}</span> {}/100</td></tr>\n",
+                    idx + 1, session.runs[idx].name, bottleneck.summary,
+                    config.severity_bands.tier(bottleneck.severity), config.severity_bands.tier(bottleneck.severity),
+                    bottleneck.severity
+                ));
+            }
+            html.push_str("      </table>\n");
+        }
+        if let Some(note) = omitted_runs_note(&plan) {
+            html.push_str(&format!("      <p class=\"detail-note\">{}</p>\n", note));
+        }
+        html.push_str("    </section>\n");
+    } else if !omitted.is_empty() {
+        html.push_str("    <section class=\"other-runs-section\">\n");
+        html.push_str("      <h2>Other Runs</h2>\n");
+        html.push_str("      <table>\n");
+        html.push_str("        <tr><th>Run</th><th>Bottlenecks</th><th>Worst severity</th></tr>\n");
+        for (idx, run) in omitted {
+            html.push_str(&format!("        <tr><td>{}: {}</td><td>{}</td><td>{}/100</td></tr>\n",
+                idx + 1, run.name, active_bottleneck_count(run, config), worst_severity(run, config)));
+        }
+        html.push_str("      </table>\n");
+        if let Some(note) = omitted_runs_note(&plan) {
+            html.push_str(&format!("      <p class=\"detail-note\">{}</p>\n", note));
         }
         html.push_str("    </section>\n");
     }
@@ -244,19 +1228,21 @@ fn generate_html_report(
     html.push_str("</body>\n");
     html.push_str("</html>\n");
     
-    html
+    Ok(html)
 }
 
 /// Generate JSON report
 fn generate_json_report(
     session: &Session,
     hardware: &HardwareConfig,
-    _config: &ReportConfig,
-) -> String {
+    config: &ReportConfig,
+    tracker: &mut ProgressTracker,
+) -> Result<String, PersistenceError> {
     #[derive(Serialize)]
     struct JsonReport {
         session: SessionSummary,
         hardware: HardwareSummary,
+        rollup: SessionRollup,
         runs: Vec<RunSummary>,
     }
     
@@ -290,6 +1276,7 @@ fn generate_json_report(
         r#type: String,
         severity: u8,
         summary: String,
+        dismissed: bool,
     }
     
     let report = JsonReport {
@@ -307,25 +1294,417 @@ fn generate_json_report(
                 memory_mb: hardware.memory.total_mb,
                 gpu: hardware.gpus.first().map(|g| g.model.clone()),
             },
+        rollup: session_rollup(session),
+        runs: session.runs
+            .iter()
+            .map(|run| {
+                let summary = RunSummary {
+                    name: run.name.clone(),
+                    bottleneck_count: run.analysis_result.as_ref()
+                        .map(|a| a.bottlenecks.len())
+                        .unwrap_or(0),
+                    bottlenecks: run.analysis_result.as_ref()
+                        .map(|a| a.bottlenecks.iter().map(|b| BottleneckSummary {
+                            r#type: format!("{:?}", b.bottleneck_type),
+                            severity: b.severity,
+                            summary: b.summary.clone(),
+                            dismissed: is_acknowledged(b, &config.acknowledgments),
+                        }).collect())
+                        .unwrap_or_default(),
+                };
+                tracker.advance().map(|_| summary)
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    
+    Ok(serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string()))
+}
+
+/// Generate Markdown report
+fn generate_markdown_report(
+    session: &Session,
+    hardware: &HardwareConfig,
+    config: &ReportConfig,
+    tracker: &mut ProgressTracker,
+) -> Result<String, PersistenceError> {
+    let mut report = String::new();
+    let timezone = config.timezone.as_deref();
+    
+    report.push_str("# PC Rig Hardware & Bottleneck Analysis Report\n\n");
+    report.push_str(&format!("- **Session:** {}\n", session.id));
+    report.push_str(&format!("- **Profile:** {}\n", markdown_escape(&session.profile.name)));
+    report.push_str(&format!("- **Started:** {}\n", format_timestamp(&session.start_time, timezone)));
+    if let Some(end_time) = session.end_time {
+        report.push_str(&format!("- **Ended:** {}\n", format_timestamp(&end_time, timezone)));
+    }
+    report.push_str(&format!("- **Runs:** {}\n\n", session.runs.len()));
+    
+    let plan = ReportPlan::new(session, config);
+    let sections = plan.sections.clone();
+    
+    // Runs are built in a single pass so progress can be reported per run.
+    // Analysis and recommendations share each run's block, in configured order.
+    let mut runs = String::new();
+    for (idx, run) in session.runs.iter().enumerate() {
+        if !plan.detailed[idx] {
+            tracker.advance()?;
+            continue;
+        }
+        runs.push_str(&format!("## Run {}: {}\n\n", idx + 1, markdown_escape(&run.name)));
+        
+        match &run.analysis_result {
+            Some(analysis) => {
+                for section in &sections {
+                    match section {
+                        ReportSection::Analysis => {
+                            runs.push_str(&format!("### Bottlenecks ({})\n\n", analysis.bottlenecks.len()));
+                            for bottleneck in &analysis.bottlenecks {
+                                runs.push_str(&format!("- **{}** (Severity: {}/100)",
+                                    markdown_escape(&bottleneck.summary), bottleneck.severity));
+                                if is_acknowledged(bottleneck, &config.acknowledgments) {
+                                    runs.push_str(", dismissed");
+                                }
+                                if let Some(onset) = onset_offset(bottleneck, run) {
+                                    runs.push_str(&format!(", onset {} into the run", onset));
+                                }
+                                if let Some(phrase) = active_time_phrase(bottleneck) {
+                                    runs.push_str(&format!(", {}", phrase));
+                                }
+                                runs.push_str(&format!("\n  {}\n", markdown_escape(&bottleneck.details)));
+                            }
+                            
+                            let grades = grade_subsystems(analysis, hardware, &run_samples(run));
+                            runs.push_str("\n| Subsystem | Grade |\n|---|---|\n");
+                            for subsystem in &GRADED_SUBSYSTEMS {
+                                runs.push_str(&format!("| {} | {} |\n",
+                                    subsystem_name(subsystem), grade_label(&grades, subsystem)));
+                            }
+                            runs.push('\n');
+                        }
+                        ReportSection::Recommendations => {
+                            let mut recs = run_recommendations(analysis, session, config);
+                            sort_easy_wins_first(&mut recs);
+                            if !recs.is_empty() {
+                                runs.push_str("### Recommendations\n\n");
+                                for rec in &recs {
+                                    runs.push_str(&format!("- {}\n", markdown_escape(rec.text())));
+                                }
+                                runs.push('\n');
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            None => runs.push_str("_Not analyzed._\n\n"),
+        }
+        
+        tracker.advance()?;
+    }
+    
+    // Run blocks go where analysis or recommendations is first listed; charts
+    // have no Markdown rendering
+    let mut runs = Some(runs);
+    for section in &sections {
+        match section {
+            ReportSection::Hardware => {
+                report.push_str("## Hardware Configuration\n\n");
+                report.push_str(&format!("- **CPU:** {} ({} cores, {} threads)\n",
+                    markdown_escape(&hardware.cpu.model), hardware.cpu.cores, hardware.cpu.threads));
+                report.push_str(&format!("- **Memory:** {:.2} GB\n", hardware.memory.total_mb as f64 / 1024.0));
+                if let Some(gpu) = hardware.gpus.first() {
+                    report.push_str(&format!("- **GPU:** {}\n", markdown_escape(&gpu.model)));
+                }
+                report.push('\n');
+            }
+            ReportSection::Environment => {
+                if plan.detailed_runs(session).all(|(_, r)| r.environment.is_none()) {
+                    continue;
+                }
+                report.push_str("## Run Environment\n\n");
+                for (idx, run) in plan.detailed_runs(session) {
+                    if let Some(environment) = &run.environment {
+                        report.push_str(&format!("### Run {}\n\n", idx + 1));
+                        for (label, value) in environment_fields(environment) {
+                            report.push_str(&format!("- **{}:** {}\n", label, markdown_escape(&value)));
+                        }
+                        report.push('\n');
+                    }
+                }
+            }
+            ReportSection::Trend => {
+                if session.runs.len() < 2 {
+                    continue;
+                }
+                let rollup = session_rollup(session);
+                report.push_str("## Session Summary\n\n");
+                report.push_str("| Metric | Min | Max | Mean | Unit |\n");
+                report.push_str("|---|---|---|---|---|\n");
+                for metric in &rollup.metrics {
+                    report.push_str(&format!("| {:?} | {:.1} ({}) | {:.1} ({}) | {:.1} | {} |\n",
+                        metric.metric_type, metric.min, markdown_escape(&metric.min_run),
+                        metric.max, markdown_escape(&metric.max_run), metric.mean, metric.unit));
+                }
+                if let Some(bottleneck_type) = &rollup.most_common_bottleneck {
+                    report.push_str(&format!("\nMost common bottleneck: **{:?}** ({} detections)\n",
+                        bottleneck_type, rollup.most_common_bottleneck_count));
+                }
+                report.push('\n');
+            }
+            ReportSection::Analysis | ReportSection::Recommendations => {
+                if let Some(runs) = runs.take() {
+                    report.push_str(&runs);
+                }
+            }
+            ReportSection::Markers => {
+                let mut markers = String::new();
+                for (idx, run) in plan.detailed_runs(session) {
+                    let run_markers = run_markers(run);
+                    if run_markers.is_empty() {
+                        continue;
+                    }
+                    markers.push_str(&format!("### Run {}: {}\n\n", idx + 1, markdown_escape(&run.name)));
+                    for (at, label) in run_markers {
+                        markers.push_str(&format!("- **{}** {}\n", at, markdown_escape(&label)));
+                    }
+                    markers.push('\n');
+                }
+                if !markers.is_empty() {
+                    report.push_str("## Markers\n\n");
+                    report.push_str(&markers);
+                }
+            }
+            ReportSection::Comparison => {
+                if let Some(previous) = compare_to_previous_run(session, DEFAULT_MIN_EFFECT_PERCENT) {
+                    report.push_str("## Comparison With Previous Run\n\n");
+                    report.push_str(&format!("**{}** vs {}: {}\n\n",
+                        markdown_escape(&previous.latest_run_name), markdown_escape(&previous.previous_run_name),
+                        markdown_escape(&previous.verdict)));
+                    report.push_str(&format!("{}\n\n", markdown_escape(&previous.comparison.summary)));
+                }
+            }
+            ReportSection::Charts => {}
+        }
+    }
+    
+    // Shortened reports list what the per-run sections leave out
+    let omitted = plan.omitted_runs(session);
+    if plan.level == ReportDetailLevel::Summary {
+        report.push_str("## Worst Bottlenecks\n\n");
+        let worst = worst_bottlenecks(session, config);
+        if worst.is_empty() {
+            report.push_str("No bottlenecks detected.\n");
+        }
+        for (idx, bottleneck) in worst {
+            report.push_str(&format!("- Run {}: {} - **{}** (Severity: {}/100)\n",
+                idx + 1, markdown_escape(&session.runs[idx].name), markdown_escape(&bottleneck.summary),
+                bottleneck.severity));
+        }
+        report.push('\n');
+    } else if !omitted.is_empty() {
+        report.push_str("## Other Runs\n\n");
+        report.push_str("| Run | Bottlenecks | Worst severity |\n|---|---|---|\n");
+        for (idx, run) in omitted {
+            report.push_str(&format!("| {}: {} | {} | {}/100 |\n",
+                idx + 1, markdown_escape(&run.name), active_bottleneck_count(run, config),
+                worst_severity(run, config)));
+        }
+        report.push('\n');
+    }
+    if let Some(note) = omitted_runs_note(&plan) {
+        report.push_str(&format!("_{}_\n", note));
+    }
+    
+    Ok(report)
+}
+
+/// Recommendations for a run's unacknowledged bottlenecks
+fn run_recommendations(analysis: &BottleneckAnalysisResult, session: &Session, config: &ReportConfig) -> Vec<Recommendation> {
+    generate_acknowledged_insights(
+        analysis,
+        Some(&session.profile),
+        &[],
+        Some(&session.hardware_config_snapshot),
+        &config.acknowledgments,
+    )
+    .recommendations
+}
+
+/// Escape characters that Markdown would otherwise treat as formatting
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '|' | '[' | ']' | '<' | '>' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Generate CSV report
+///
+/// One row per run and metric type with the run's statistics for that metric,
+/// ordered by run and then metric name.
+fn generate_csv_report(session: &Session, tracker: &mut ProgressTracker) -> Result<String, PersistenceError> {
+    let mut report = String::from("run,metric,unit,count,min,avg,p95,max,bottlenecks\n");
+    
+    for run in &session.runs {
+        let samples = run_samples(run);
+        let units: HashMap<String, &str> = samples
+            .iter()
+            .map(|s| (format!("{:?}", s.metric_type), s.unit.as_str()))
+            .collect();
+        let aggregations: BTreeMap<String, MetricAggregation> = aggregate_metrics(&samples).into_iter().collect();
+        let bottlenecks = run.analysis_result.as_ref()
+            .map(|a| a.bottlenecks.iter().map(|b| format!("{:?}", b.bottleneck_type)).collect::<Vec<_>>().join(";"))
+            .unwrap_or_default();
+        
+        for (metric, aggregation) in &aggregations {
+            report.push_str(&format!("{},{},{},{},{:.2},{:.2},{},{:.2},{}\n",
+                csv_field(&run.name),
+                metric,
+                csv_field(units.get(metric).copied().unwrap_or("")),
+                aggregation.count,
+                aggregation.min,
+                aggregation.avg,
+                aggregation.p95.map(|p| format!("{:.2}", p)).unwrap_or_default(),
+                aggregation.max,
+                csv_field(&bottlenecks)));
+        }
+        
+        tracker.advance()?;
+    }
+    
+    Ok(report)
+}
+
+/// Most rows a wide CSV export may contain
+pub const MAX_WIDE_CSV_ROWS: u64 = 1_000_000;
+
+/// Export a run as a wide CSV with one column per metric and one row per grid step
+///
+/// All streams are resampled onto a common grid from the first to the last
+/// sample of the run, `interval_ms` apart. Each cell holds the most recent
+/// sample at or before the row's timestamp (forward fill, no interpolation),
+/// so values are never invented between readings; cells before a metric's
+/// first sample are left empty. Columns are named after the metric type, with
+/// the source appended when several sources report the same type (e.g. per
+/// core utilization). Non-finite readings are dropped.
+pub fn export_run_wide_csv(run: &Run, interval_ms: u64) -> Result<String, String> {
+    if interval_ms == 0 {
+        return Err("Resampling interval must be greater than zero".to_string());
+    }
+    
+    let samples: Vec<&MetricSample> = run.metrics_streams.values().flatten().filter(|s| s.value.is_finite()).collect();
+    let mut sources: HashMap<String, Vec<&str>> = HashMap::new();
+    for sample in &samples {
+        let names = sources.entry(format!("{:?}", sample.metric_type)).or_default();
+        if !names.contains(&sample.source_component.as_str()) {
+            names.push(&sample.source_component);
+        }
+    }
+    let mut columns: BTreeMap<String, Vec<(DateTime<Utc>, f64)>> = BTreeMap::new();
+    for sample in &samples {
+        let metric = format!("{:?}", sample.metric_type);
+        let column = if sources[&metric].len() > 1 {
+            format!("{} ({})", metric, sample.source_component)
+        } else {
+            metric
+        };
+        columns.entry(column).or_default().push((sample.timestamp, sample.value));
+    }
+    for points in columns.values_mut() {
+        points.sort_by_key(|(timestamp, _)| *timestamp);
+    }
+    
+    let mut report = String::from("timestamp,elapsed_ms");
+    for column in columns.keys() {
+        report.push(',');
+        report.push_str(&csv_field(column));
+    }
+    report.push('\n');
+    
+    let (Some(start), Some(end)) = (
+        samples.iter().map(|s| s.timestamp).min(),
+        samples.iter().map(|s| s.timestamp).max(),
+    ) else {
+        return Ok(report);
+    };
+    let rows = (end - start).num_milliseconds() as u64 / interval_ms + 1;
+    if rows > MAX_WIDE_CSV_ROWS {
+        return Err(format!(
+            "Resampling at {} ms would produce {} rows (limit {})",
+            interval_ms, rows, MAX_WIDE_CSV_ROWS
+        ));
+    }
+    
+    let mut cursors = vec![0usize; columns.len()];
+    for row in 0..rows {
+        let elapsed_ms = row * interval_ms;
+        let timestamp = start + chrono::Duration::milliseconds(elapsed_ms as i64);
+        report.push_str(&format!("{},{}", timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true), elapsed_ms));
+        for (points, cursor) in columns.values().zip(cursors.iter_mut()) {
+            while *cursor < points.len() && points[*cursor].0 <= timestamp {
+                *cursor += 1;
+            }
+            report.push(',');
+            if *cursor > 0 {
+                report.push_str(&format!("{:.2}", points[*cursor - 1].1));
+            }
+        }
+        report.push('\n');
+    }
+    
+    Ok(report)
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Full analysis result of a single run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunAnalysisExport {
+    pub run_id: uuid::Uuid,
+    pub name: String,
+    pub analysis_result: Option<BottleneckAnalysisResult>,
+}
+
+/// Full analysis results of a session, for consumption by tooling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisExport {
+    pub session_id: uuid::Uuid,
+    pub profile: String,
+    pub runs: Vec<RunAnalysisExport>,
+}
+
+/// Serialize the complete analysis result of every run in a session
+///
+/// Unlike the JSON report, which summarizes bottlenecks for people, this
+/// keeps evidence, time ranges and details so the analysis can be
+/// reconstructed.
+pub fn generate_analysis_json(session: &Session) -> Result<String, PersistenceError> {
+    let export = AnalysisExport {
+        session_id: session.id,
+        profile: session.profile.name.clone(),
         runs: session.runs
             .iter()
-            .map(|run| RunSummary {
+            .map(|run| RunAnalysisExport {
+                run_id: run.id,
                 name: run.name.clone(),
-                bottleneck_count: run.analysis_result.as_ref()
-                    .map(|a| a.bottlenecks.len())
-                    .unwrap_or(0),
-                bottlenecks: run.analysis_result.as_ref()
-                    .map(|a| a.bottlenecks.iter().map(|b| BottleneckSummary {
-                        r#type: format!("{:?}", b.bottleneck_type),
-                        severity: b.severity,
-                        summary: b.summary.clone(),
-                    }).collect())
-                    .unwrap_or_default(),
+                analysis_result: run.analysis_result.clone(),
             })
             .collect(),
     };
     
-    serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string())
+    serde_json::to_string_pretty(&export)
+        .map_err(|e| PersistenceError::Serialization(e.to_string()))
 }
 
 /// Generate text comparison report
@@ -350,6 +1729,22 @@ fn generate_text_comparison_report(
     report.push_str(&format!("Summary: {}\n", comparison.summary));
     report.push_str("\n");
     
+    if let Some(hardware_diff) = &comparison.hardware_diff {
+        report.push_str(&format!("Hardware: {}\n", hardware_diff.caption));
+        for difference in &hardware_diff.differences {
+            report.push_str(&format!("  - {}\n", difference.description));
+        }
+        report.push_str("\n");
+    }
+    
+    if !comparison.environment_warnings.is_empty() {
+        report.push_str("Warning: the runs were captured in different environments\n");
+        for warning in &comparison.environment_warnings {
+            report.push_str(&format!("  - {}\n", warning));
+        }
+        report.push_str("\n");
+    }
+    
     if !comparison.metric_deltas.is_empty() {
         report.push_str("-".repeat(80).as_str());
         report.push_str("\n");
@@ -417,6 +1812,31 @@ fn generate_html_comparison_report(
     html.push_str(&format!("    <p><strong>Run 2:</strong> {}</p>\n", comparison.run2_id));
     html.push_str(&format!("    <p class=\"summary\">{}</p>\n", comparison.summary));
     
+    if let Some(hardware_diff) = &comparison.hardware_diff {
+        html.push_str("    <section class=\"hardware-differences\">\n");
+        html.push_str("      <h2>Hardware Differences</h2>\n");
+        html.push_str(&format!("      <p class=\"hardware-caption\">{}</p>\n", hardware_diff.caption));
+        if !hardware_diff.differences.is_empty() {
+            html.push_str("      <ul>\n");
+            for difference in &hardware_diff.differences {
+                html.push_str(&format!("        <li>{}</li>\n", difference.description));
+            }
+            html.push_str("      </ul>\n");
+        }
+        html.push_str("    </section>\n");
+    }
+    
+    if !comparison.environment_warnings.is_empty() {
+        html.push_str("    <section class=\"environment-warnings\">\n");
+        html.push_str("      <h2>Environment Differences</h2>\n");
+        html.push_str("      <ul>\n");
+        for warning in &comparison.environment_warnings {
+            html.push_str(&format!("        <li>{}</li>\n", warning));
+        }
+        html.push_str("      </ul>\n");
+        html.push_str("    </section>\n");
+    }
+    
     if !comparison.metric_deltas.is_empty() {
         html.push_str("    <section class=\"metric-comparison\">\n");
         html.push_str("      <h2>Metric Changes</h2>\n");
@@ -0,0 +1,128 @@
+//! Minimal PDF rendering of plain-text reports
+//!
+//! Lays the text report out in a monospaced standard font on A4 pages. The
+//! standard fonts need no embedding, so the PDF is written directly without
+//! a PDF library.
+
+/// A4 page size in points
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+
+/// Page margin in points
+const MARGIN: f64 = 40.0;
+
+/// Font size and line spacing in points
+const FONT_SIZE: f64 = 8.0;
+const LINE_HEIGHT: f64 = 10.0;
+
+/// Lines that fit on one page
+const LINES_PER_PAGE: usize = ((PAGE_HEIGHT - 2.0 * MARGIN) / LINE_HEIGHT) as usize;
+
+/// ASCII stand-ins for the sparkline blocks, from lowest to highest
+const SPARKLINE_FALLBACK: [char; 8] = ['_', '.', '-', '~', '=', '+', '*', '#'];
+
+/// Render plain text as a PDF document
+///
+/// Lines are not wrapped; the 80-column text report fits the page width.
+/// Characters outside Latin-1 are replaced, since the standard fonts use
+/// WinAnsi encoding.
+pub fn text_to_pdf(text: &str) -> Vec<u8> {
+    let lines: Vec<&str> = text.lines().collect();
+    let pages: Vec<&[&str]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(LINES_PER_PAGE).collect()
+    };
+
+    // Objects 1-3 are the catalog, page tree and font; each page adds a page and its content
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 4 + 2 * i).collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_vec(),
+    ];
+
+    for (page, id) in pages.iter().zip(&page_ids) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                id + 1
+            )
+            .into_bytes(),
+        );
+
+        let mut content = format!(
+            "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+            FONT_SIZE,
+            LINE_HEIGHT,
+            MARGIN,
+            PAGE_HEIGHT - MARGIN - FONT_SIZE
+        )
+        .into_bytes();
+        for line in page.iter() {
+            content.push(b'(');
+            content.extend(encode_line(line));
+            content.extend_from_slice(b") Tj T*\n");
+        }
+        content.extend_from_slice(b"ET");
+
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (idx, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", idx + 1).into_bytes());
+        pdf.extend(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .into_bytes(),
+    );
+    pdf
+}
+
+/// Encode a line as a PDF string body in WinAnsi
+fn encode_line(line: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(line.len());
+    for c in line.chars() {
+        let c = super::SPARKLINE_BLOCKS
+            .iter()
+            .position(|&block| block == c)
+            .map_or(c, |level| SPARKLINE_FALLBACK[level]);
+        match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                bytes.push(c as u8);
+            }
+            ' '..='~' => bytes.push(c as u8),
+            // Latin-1 matches WinAnsi above 0xA0
+            '\u{a0}'..='\u{ff}' => bytes.push(c as u32 as u8),
+            '\t' => bytes.push(b' '),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
@@ -4,12 +4,14 @@
 //! configurable retention periods, following IMPLEMENTATION_PLAN.md Phase 4.3.
 
 use crate::core::error::PersistenceError;
+use crate::persistence::database::DatabaseStorage;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use tokio::fs;
 
 /// Retention policy configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RetentionPolicy {
     /// Number of days to retain sessions
     pub retention_days: u32,
@@ -29,6 +31,23 @@ impl Default for RetentionPolicy {
     }
 }
 
+impl RetentionPolicy {
+    /// Sessions that started before this time are past retention
+    ///
+    /// Returns `None` when the retention period reaches further back than
+    /// dates can, so no session is ever old enough to delete.
+    pub fn cutoff_date(&self) -> Option<DateTime<Utc>> {
+        Utc::now().checked_sub_signed(chrono::Duration::days(self.retention_days as i64))
+    }
+    
+    /// Number of sessions to delete, keeping at least `min_sessions_to_keep`
+    ///
+    /// The oldest sessions are deleted first, so only the count matters.
+    pub fn sessions_to_delete(&self, total_sessions: usize, past_retention: usize) -> usize {
+        past_retention.min(total_sessions.saturating_sub(self.min_sessions_to_keep))
+    }
+}
+
 /// Cleanup old sessions from file-based storage
 pub async fn cleanup_old_sessions_file(
     sessions_dir: &Path,
@@ -38,7 +57,7 @@ pub async fn cleanup_old_sessions_file(
         return Ok(0);
     }
     
-    let cutoff_date = Utc::now() - chrono::Duration::days(policy.retention_days as i64);
+    let cutoff_date = policy.cutoff_date();
     
     let mut entries = fs::read_dir(sessions_dir).await
         .map_err(|e| PersistenceError::Io(e))?;
@@ -68,16 +87,11 @@ pub async fn cleanup_old_sessions_file(
     // Sort by start_time (oldest first)
     session_files.sort_by_key(|(time, _)| *time);
     
-    // Calculate how many to delete
-    let total_sessions = session_files.len();
-    let mut to_delete = session_files.iter()
-        .filter(|(time, _)| *time < cutoff_date)
+    // Calculate how many to delete, keeping at least min_sessions_to_keep
+    let past_retention = session_files.iter()
+        .filter(|(time, _)| cutoff_date.is_some_and(|cutoff| *time < cutoff))
         .count();
-    
-    // Ensure we keep at least min_sessions_to_keep
-    if total_sessions - to_delete < policy.min_sessions_to_keep {
-        to_delete = total_sessions.saturating_sub(policy.min_sessions_to_keep);
-    }
+    let to_delete = policy.sessions_to_delete(session_files.len(), past_retention);
     
     // Delete old sessions
     let mut deleted = 0;
@@ -106,8 +120,6 @@ pub async fn get_retention_stats(
         });
     }
     
-    let cutoff_date = Utc::now() - chrono::Duration::days(policy.retention_days as i64);
-    
     let mut entries = fs::read_dir(sessions_dir).await
         .map_err(|e| PersistenceError::Io(e))?;
     
@@ -131,25 +143,11 @@ pub async fn get_retention_stats(
         }
     }
     
-    let total_sessions = session_dates.len();
-    let sessions_to_delete = session_dates.iter()
-        .filter(|&date| *date < cutoff_date)
-        .count()
-        .saturating_sub(total_sessions.saturating_sub(policy.min_sessions_to_keep));
-    
-    let oldest_session_date = session_dates.iter().min().copied();
-    let newest_session_date = session_dates.iter().max().copied();
-    
-    Ok(RetentionStats {
-        total_sessions,
-        sessions_to_delete,
-        oldest_session_date,
-        newest_session_date,
-    })
+    Ok(RetentionStats::from_dates(&session_dates, policy))
 }
 
 /// Retention statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RetentionStats {
     pub total_sessions: usize,
     pub sessions_to_delete: usize,
@@ -157,3 +155,69 @@ pub struct RetentionStats {
     pub newest_session_date: Option<DateTime<Utc>>,
 }
 
+
+impl RetentionStats {
+    /// Statistics for sessions with the given start times
+    pub fn from_dates(session_dates: &[DateTime<Utc>], policy: &RetentionPolicy) -> Self {
+        let cutoff_date = policy.cutoff_date();
+        let past_retention = session_dates.iter()
+            .filter(|date| cutoff_date.is_some_and(|cutoff| **date < cutoff))
+            .count();
+        
+        Self {
+            total_sessions: session_dates.len(),
+            sessions_to_delete: policy.sessions_to_delete(session_dates.len(), past_retention),
+            oldest_session_date: session_dates.iter().min().copied(),
+            newest_session_date: session_dates.iter().max().copied(),
+        }
+    }
+}
+
+/// Retention statistics of every session store
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionOverview {
+    /// Sessions saved as JSON files
+    pub files: RetentionStats,
+    /// Sessions in the SQLite database, if one exists
+    pub database: Option<RetentionStats>,
+}
+
+/// Result of a manual cleanup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CleanupReport {
+    /// Sessions deleted across all stores
+    pub deleted: usize,
+    /// Statistics after the cleanup
+    pub stats: RetentionOverview,
+}
+
+/// Get retention statistics for the file store and, if given, the database
+pub async fn retention_overview(
+    sessions_dir: &Path,
+    database: Option<&DatabaseStorage>,
+    policy: &RetentionPolicy,
+) -> Result<RetentionOverview, PersistenceError> {
+    Ok(RetentionOverview {
+        files: get_retention_stats(sessions_dir, policy).await?,
+        database: database.map(|db| db.retention_stats(policy)).transpose()?,
+    })
+}
+
+/// Delete sessions past retention from the file store and, if given, the database
+///
+/// Each store keeps at least `min_sessions_to_keep` of its newest sessions.
+pub async fn cleanup_now(
+    sessions_dir: &Path,
+    database: Option<&DatabaseStorage>,
+    policy: &RetentionPolicy,
+) -> Result<CleanupReport, PersistenceError> {
+    let mut deleted = cleanup_old_sessions_file(sessions_dir, policy).await?;
+    if let Some(db) = database {
+        deleted += db.cleanup_sessions(policy)?;
+    }
+    
+    Ok(CleanupReport {
+        deleted,
+        stats: retention_overview(sessions_dir, database, policy).await?,
+    })
+}
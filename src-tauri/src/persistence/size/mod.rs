@@ -0,0 +1,118 @@
+//! Session disk footprint estimation
+//!
+//! This module estimates how much space a session will take once saved, so
+//! the UI and the retention system can warn before very large saves. Only a
+//! bounded number of samples is serialized, so estimating stays cheap for
+//! sessions with millions of samples.
+
+use crate::core::domain::{MetricSample, Run, Session};
+use serde::Serialize;
+
+/// Samples serialized to measure the average sample size
+const SIZE_PROBE_SAMPLES: usize = 64;
+
+/// Indentation of a sample object inside a pretty-printed session file
+const PRETTY_SAMPLE_INDENT: usize = 10;
+
+/// Length of a UUID in its text form, stored as `run_id` on every metrics row
+const UUID_TEXT_BYTES: usize = 36;
+
+/// Approximate SQLite record, rowid and index overhead per metrics row
+const DB_ROW_OVERHEAD_BYTES: usize = 48;
+
+/// Estimated size above which a save is considered very large (100 MB)
+pub const LARGE_SESSION_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Estimated disk footprint of a session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SizeEstimate {
+    /// Samples across all runs
+    pub sample_count: usize,
+    /// Size of the pretty-printed session file
+    pub json_bytes: u64,
+    /// Size of the session in the SQLite database
+    pub db_bytes: u64,
+    /// Rows written to the metrics table
+    pub metrics_rows: usize,
+    /// Whether either estimate exceeds `LARGE_SESSION_BYTES`
+    pub is_large: bool,
+}
+
+/// Estimate the JSON and database size of a session
+///
+/// Everything except the samples is serialized exactly; samples are sized
+/// from an evenly spaced probe and extrapolated to the full count.
+pub fn estimate_session_size(session: &Session) -> SizeEstimate {
+    let samples: Vec<&MetricSample> = session
+        .runs
+        .iter()
+        .flat_map(|run| run.metrics_streams.values().flatten())
+        .collect();
+    let sample_count = samples.len();
+
+    let skeleton = Session {
+        id: session.id,
+        start_time: session.start_time,
+        end_time: session.end_time,
+        hardware_config_snapshot: session.hardware_config_snapshot.clone(),
+        profile: session.profile.clone(),
+        runs: session
+            .runs
+            .iter()
+            .map(|run| Run {
+                id: run.id,
+                name: run.name.clone(),
+                metrics_streams: run
+                    .metrics_streams
+                    .keys()
+                    .map(|key| (key.clone(), Vec::new()))
+                    .collect(),
+                analysis_result: run.analysis_result.clone(),
+                notes: run.notes.clone(),
+                environment: run.environment.clone(),
+            })
+            .collect(),
+    };
+    let skeleton_pretty = serde_json::to_string_pretty(&skeleton).map_or(0, |json| json.len());
+    let skeleton_compact = serde_json::to_string(&skeleton).map_or(0, |json| json.len());
+
+    let step = (sample_count / SIZE_PROBE_SAMPLES).max(1);
+    let probe: Vec<&MetricSample> = samples.iter().step_by(step).copied().collect();
+    let average = |size_of: &dyn Fn(&MetricSample) -> usize| -> f64 {
+        if probe.is_empty() {
+            0.0
+        } else {
+            probe.iter().map(|sample| size_of(sample)).sum::<usize>() as f64 / probe.len() as f64
+        }
+    };
+
+    // Pretty samples are indented and separated by ",\n"
+    let pretty_sample = average(&|sample| {
+        let json = serde_json::to_string_pretty(sample).unwrap_or_default();
+        json.len() + json.lines().count() * PRETTY_SAMPLE_INDENT + 2
+    });
+    // The runs table keeps a compact copy of every stream
+    let compact_sample = average(&|sample| serde_json::to_string(sample).map_or(0, |json| json.len() + 1));
+    let metrics_row = average(&|sample| {
+        UUID_TEXT_BYTES
+            + sample.timestamp.to_rfc3339().len()
+            // The metric type column holds the serde name, without JSON quotes
+            + serde_json::to_string(&sample.metric_type).map_or(0, |json| json.len().saturating_sub(2))
+            + std::mem::size_of::<f64>()
+            + sample.unit.len()
+            + sample.source_component.len()
+            + DB_ROW_OVERHEAD_BYTES
+    });
+
+    let json_bytes = skeleton_pretty as u64 + (pretty_sample * sample_count as f64) as u64;
+    let db_bytes = skeleton_compact as u64
+        + ((compact_sample + metrics_row) * sample_count as f64) as u64;
+
+    SizeEstimate {
+        sample_count,
+        json_bytes,
+        db_bytes,
+        metrics_rows: sample_count,
+        is_large: json_bytes.max(db_bytes) > LARGE_SESSION_BYTES,
+    }
+}
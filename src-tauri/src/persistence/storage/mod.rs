@@ -2,10 +2,19 @@
 //!
 //! This module provides file-based storage using JSON serialization.
 
-use crate::core::domain::Session;
+use crate::analysis::fleet::{summarize_session_issues, SessionIssueSummary};
+use crate::core::domain::{HardwareConfig, Session};
 use crate::core::error::PersistenceError;
+use crate::persistence::export_import::{resolve_import_conflict, ImportConflictPolicy, ImportOutcome};
+use crate::persistence::migration::migrate_session_samples;
+use crate::persistence::models::{RunMatch, SessionSummary};
+use chrono::{DateTime, Utc};
+use serde::de::{Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde::Deserialize;
 use serde_json;
-use std::path::PathBuf;
+use std::cmp::Reverse;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
 /// Session storage manager
@@ -59,8 +68,9 @@ impl SessionStorage {
             .await
             .map_err(|e| PersistenceError::Io(e))?;
         
-        let session: Session = serde_json::from_str(&content)
+        let mut session: Session = serde_json::from_str(&content)
             .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+        migrate_session_samples(&mut session);
         
         Ok(session)
     }
@@ -90,6 +100,29 @@ impl SessionStorage {
         Ok(session_ids)
     }
     
+    /// Summarize every saved session for listing, newest first
+    ///
+    /// Each file is parsed as a stream and only the header fields are kept:
+    /// hardware, analysis results and metric streams are skipped over
+    /// without being deserialized. Files that cannot be read are skipped.
+    pub async fn list_session_summaries(&self) -> Result<Vec<SessionSummary>, PersistenceError> {
+        let mut summaries = Vec::new();
+        
+        for session_id in self.list_sessions().await? {
+            let path = self.session_path(&session_id);
+            let summary = tokio::task::spawn_blocking(move || read_session_summary(&path))
+                .await
+                .map_err(|e| PersistenceError::Unknown(e.to_string()))?;
+            match summary {
+                Ok(summary) => summaries.push(summary),
+                Err(e) => log::warn!("Skipping session {} in session list: {}", session_id, e),
+            }
+        }
+        
+        summaries.sort_by_key(|summary| Reverse(summary.start_time));
+        Ok(summaries)
+    }
+    
     /// Delete a session
     pub async fn delete_session(&self, session_id: &uuid::Uuid) -> Result<(), PersistenceError> {
         let path = self.session_path(session_id);
@@ -102,4 +135,220 @@ impl SessionStorage {
         
         Ok(())
     }
+    
+    /// Store an imported session, resolving an ID clash with `policy`
+    pub async fn import_session(
+        &self,
+        session: Session,
+        policy: ImportConflictPolicy,
+    ) -> Result<ImportOutcome, PersistenceError> {
+        let exists = self.session_path(&session.id).exists();
+        let (session, outcome) = resolve_import_conflict(session, exists, policy);
+        
+        // The session file holds its runs, so overwriting it drops the old ones
+        if let Some(session) = session {
+            self.save_session(&session).await?;
+        }
+        
+        Ok(outcome)
+    }
+    
+    /// Search run names and notes across all sessions
+    ///
+    /// File storage has no index, so every session is loaded and scanned for
+    /// a case-insensitive substring match.
+    pub async fn search_runs(&self, query: &str) -> Result<Vec<RunMatch>, PersistenceError> {
+        let mut matches = Vec::new();
+        
+        for session_id in self.list_sessions().await? {
+            let session = match self.load_session(&session_id).await {
+                Ok(session) => session,
+                Err(e) => {
+                    log::warn!("Skipping session {} in run search: {}", session_id, e);
+                    continue;
+                }
+            };
+            
+            for run in &session.runs {
+                let snippet = run
+                    .notes
+                    .as_deref()
+                    .and_then(|notes| match_snippet(notes, query))
+                    .or_else(|| match_snippet(&run.name, query));
+                if let Some(snippet) = snippet {
+                    matches.push(RunMatch {
+                        session_id: session.id,
+                        run_id: run.id,
+                        run_name: run.name.clone(),
+                        snippet,
+                    });
+                }
+            }
+        }
+        
+        Ok(matches)
+    }
+    
+    /// Summarize every saved session with a bottleneck of at least `min_severity`
+    ///
+    /// Sessions are loaded one at a time and only their summaries are kept,
+    /// so memory use does not grow with the number of stored sessions.
+    /// Sessions that fail to load are skipped. The most severe come first.
+    pub async fn analyze_all_sessions(
+        &self,
+        min_severity: u8,
+    ) -> Result<Vec<SessionIssueSummary>, PersistenceError> {
+        let mut summaries = Vec::new();
+        
+        for session_id in self.list_sessions().await? {
+            let session = match self.load_session(&session_id).await {
+                Ok(session) => session,
+                Err(e) => {
+                    log::warn!("Skipping session {} in batch analysis: {}", session_id, e);
+                    continue;
+                }
+            };
+            summaries.extend(summarize_session_issues(&session, min_severity));
+        }
+        
+        summaries.sort_by(|a, b| {
+            b.worst_severity
+                .cmp(&a.worst_severity)
+                .then_with(|| b.start_time.cmp(&a.start_time))
+        });
+        Ok(summaries)
+    }
+}
+
+/// Header fields of a session file; everything else is skipped while parsing
+#[derive(Deserialize)]
+struct SessionHeader {
+    id: uuid::Uuid,
+    start_time: DateTime<Utc>,
+    end_time: Option<DateTime<Utc>>,
+    profile: ProfileHeader,
+    #[serde(deserialize_with = "count_elements")]
+    runs: usize,
+}
+
+#[derive(Deserialize)]
+struct ProfileHeader {
+    name: String,
+}
+
+/// Count the elements of a sequence without deserializing them
+fn count_elements<'de, D>(deserializer: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct CountVisitor;
+    
+    impl<'de> Visitor<'de> for CountVisitor {
+        type Value = usize;
+        
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a sequence")
+        }
+        
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<usize, A::Error> {
+            let mut count = 0;
+            while seq.next_element::<IgnoredAny>()?.is_some() {
+                count += 1;
+            }
+            Ok(count)
+        }
+    }
+    
+    deserializer.deserialize_seq(CountVisitor)
+}
+
+/// Read the summary of one session file
+fn read_session_summary(path: &Path) -> Result<SessionSummary, PersistenceError> {
+    let file = std::fs::File::open(path).map_err(PersistenceError::Io)?;
+    let header: SessionHeader = serde_json::from_reader(std::io::BufReader::new(file))
+        .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+    Ok(SessionSummary {
+        id: header.id,
+        name: header.profile.name,
+        start_time: header.start_time,
+        end_time: header.end_time,
+        run_count: header.runs,
+    })
+}
+
+/// Characters of context kept on each side of a match in search snippets
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// Excerpt of `text` around the first case-insensitive match of `query`
+///
+/// The match is wrapped in brackets like the database snippets.
+fn match_snippet(text: &str, query: &str) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let needle: Vec<char> = query.to_lowercase().chars().collect();
+    if needle.is_empty() {
+        return None;
+    }
+    let lowered: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+    let start = lowered.windows(needle.len()).position(|window| window == needle.as_slice())?;
+    let end = start + needle.len();
+    
+    let from = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let to = (end + SNIPPET_CONTEXT_CHARS).min(chars.len());
+    let slice = |range: std::ops::Range<usize>| chars[range].iter().collect::<String>();
+    Some(format!(
+        "{}{}[{}]{}{}",
+        if from > 0 { "..." } else { "" },
+        slice(from..start),
+        slice(start..end),
+        slice(end..to),
+        if to < chars.len() { "..." } else { "" },
+    ))
+}
+
+/// Storage for the most recently detected hardware configuration
+///
+/// Used on startup to detect hardware changes since the previous launch.
+pub struct HardwareSnapshotStorage {
+    path: PathBuf,
+}
+
+impl HardwareSnapshotStorage {
+    /// Create a new hardware snapshot storage at the given file path
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+    
+    /// Load the last saved configuration, if any
+    pub async fn load(&self) -> Result<Option<HardwareConfig>, PersistenceError> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        
+        let content = fs::read_to_string(&self.path)
+            .await
+            .map_err(PersistenceError::Io)?;
+        
+        let config: HardwareConfig = serde_json::from_str(&content)
+            .map_err(|e| PersistenceError::Deserialization(e.to_string()))?;
+        
+        Ok(Some(config))
+    }
+    
+    /// Save the configuration, replacing the previous snapshot
+    pub async fn save(&self, config: &HardwareConfig) -> Result<(), PersistenceError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(PersistenceError::Io)?;
+        }
+        
+        let json = serde_json::to_string_pretty(config)
+            .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+        
+        fs::write(&self.path, json)
+            .await
+            .map_err(PersistenceError::Io)?;
+        
+        Ok(())
+    }
 }
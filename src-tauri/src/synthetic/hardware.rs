@@ -0,0 +1,127 @@
+//! Synthetic hardware detector
+
+use super::{rig_for_seed, SYNTHETIC_PLATFORM};
+use crate::core::domain::{
+    CoolingInfo, CPUInfo, DisplayInfo, GPUInfo, HardwareConfig, MemoryInfo, MemoryModule,
+    MotherboardInfo, PSUInfo, StorageInfo, StorageType,
+};
+use crate::core::error::HardwareError;
+use crate::core::interfaces::HardwareDetector;
+use crate::hardware::detection::{detect_components, ComponentDetector, DETECTION_STEP_TIMEOUT};
+use async_trait::async_trait;
+
+/// Hardware detector reporting one of the synthetic rigs
+///
+/// The rig is picked by the seed; apart from the detection time the
+/// configuration is the same on every call.
+pub struct SyntheticHardwareDetector {
+    seed: u64,
+}
+
+impl SyntheticHardwareDetector {
+    /// Create a detector for the rig selected by `seed`
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+#[async_trait]
+impl ComponentDetector for SyntheticHardwareDetector {
+    fn platform(&self) -> &str {
+        SYNTHETIC_PLATFORM
+    }
+
+    async fn detect_cpu(&self) -> Result<CPUInfo, HardwareError> {
+        let rig = rig_for_seed(self.seed);
+        Ok(CPUInfo {
+            model: rig.cpu_model.to_string(),
+            vendor: rig.cpu_vendor.to_string(),
+            cores: rig.cores,
+            threads: rig.threads,
+            base_clock_mhz: Some(rig.base_clock_mhz),
+            boost_clock_mhz: Some(rig.boost_clock_mhz),
+            architecture: Some("x86_64".to_string()),
+            core_max_frequencies_mhz: vec![rig.boost_clock_mhz; rig.threads as usize],
+        })
+    }
+
+    async fn detect_memory(&self) -> Result<MemoryInfo, HardwareError> {
+        let module = MemoryModule {
+            size_mb: 16_384,
+            speed_mhz: Some(6000),
+            manufacturer: Some("Synthetic".to_string()),
+        };
+        Ok(MemoryInfo {
+            total_mb: 32_768,
+            channels: Some(2),
+            speed_mhz: Some(6000),
+            modules: vec![module.clone(), module],
+        })
+    }
+
+    async fn detect_gpus(&self) -> Result<Vec<GPUInfo>, HardwareError> {
+        let rig = rig_for_seed(self.seed);
+        Ok(vec![GPUInfo {
+            model: rig.gpu_model.to_string(),
+            vendor: rig.gpu_vendor.to_string(),
+            vram_total_mb: Some(rig.vram_total_mb),
+            driver_version: Some("synthetic".to_string()),
+            pci_id: None,
+        }])
+    }
+
+    async fn detect_storage(&self) -> Result<Vec<StorageInfo>, HardwareError> {
+        Ok(vec![StorageInfo {
+            model: "Synthetic NVMe SSD".to_string(),
+            capacity_mb: 1_000_000,
+            storage_type: StorageType::NVMe,
+            interface: Some("PCIe 4.0 x4".to_string()),
+            free_mb: Some(420_000),
+        }])
+    }
+
+    async fn detect_displays(&self) -> Result<Vec<DisplayInfo>, HardwareError> {
+        let rig = rig_for_seed(self.seed);
+        Ok(vec![DisplayInfo {
+            name: "Synthetic Display".to_string(),
+            resolution_width: 2560,
+            resolution_height: 1440,
+            refresh_rate_hz: Some(144),
+            gpu_attachment: Some(rig.gpu_model.to_string()),
+        }])
+    }
+
+    async fn detect_motherboard(&self) -> Result<Option<MotherboardInfo>, HardwareError> {
+        Ok(Some(MotherboardInfo {
+            model: "Synthetic Board".to_string(),
+            manufacturer: "Synthetic".to_string(),
+            chipset: None,
+            bios_version: None,
+        }))
+    }
+
+    async fn detect_psu(&self) -> Result<Option<PSUInfo>, HardwareError> {
+        Ok(Some(PSUInfo {
+            wattage: 750,
+            efficiency_rating: Some("80+ Gold".to_string()),
+        }))
+    }
+
+    async fn detect_cooling(&self) -> Result<Option<CoolingInfo>, HardwareError> {
+        Ok(Some(CoolingInfo {
+            cpu_cooler_type: Some("Air".to_string()),
+            case_fans: Some(3),
+        }))
+    }
+}
+
+#[async_trait]
+impl HardwareDetector for SyntheticHardwareDetector {
+    async fn get_hardware_config(&self) -> Result<HardwareConfig, HardwareError> {
+        detect_components(self, DETECTION_STEP_TIMEOUT).await
+    }
+
+    async fn refresh(&self) -> Result<HardwareConfig, HardwareError> {
+        self.get_hardware_config().await
+    }
+}
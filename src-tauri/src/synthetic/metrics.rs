@@ -0,0 +1,220 @@
+//! Synthetic metrics provider
+
+use super::{noise, rig_for_seed, SyntheticRig};
+use crate::core::domain::{MetricSample, MetricType};
+use crate::core::error::MetricsError;
+use crate::core::interfaces::{
+    CpuMetricsProvider, GpuMetricsProvider, MemoryMetricsProvider, SensorProvider,
+    StorageMetricsProvider,
+};
+use crate::metrics::collector::gpu_samples;
+use crate::metrics::models::{
+    CpuMetrics, GpuAdapterUtilization, GpuMetrics, MemoryMetrics, PcieLinkStatus, SensorKind, SensorReading, StorageMetrics,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Noise streams, one per synthetic reading
+const CPU_STREAM: u64 = 1;
+const CORE_STREAM: u64 = 2;
+const CPU_CLOCK_STREAM: u64 = 3;
+const CPU_TEMPERATURE_STREAM: u64 = 4;
+const GPU_STREAM: u64 = 5;
+const VRAM_STREAM: u64 = 6;
+const GPU_TEMPERATURE_STREAM: u64 = 7;
+const GPU_POWER_STREAM: u64 = 8;
+const MEMORY_STREAM: u64 = 9;
+const STORAGE_STREAM: u64 = 10;
+const FAN_STREAM: u64 = 11;
+
+/// Total system memory of every synthetic rig
+const TOTAL_MEMORY_MB: u64 = 32_768;
+
+/// Metrics provider replaying a seeded GPU-bound game load
+///
+/// Implements every provider trait the collector uses. Each kind of reading
+/// keeps its own tick counter, so the collector reading the CPU twice per
+/// tick does not shift the GPU sequence.
+pub struct SyntheticMetricsProvider {
+    seed: u64,
+    rig: &'static SyntheticRig,
+    cpu_tick: AtomicU64,
+    gpu_tick: AtomicU64,
+    memory_tick: AtomicU64,
+    storage_tick: AtomicU64,
+    sensor_tick: AtomicU64,
+}
+
+impl SyntheticMetricsProvider {
+    /// Create a provider for the rig and readings selected by `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rig: rig_for_seed(seed),
+            cpu_tick: AtomicU64::new(0),
+            gpu_tick: AtomicU64::new(0),
+            memory_tick: AtomicU64::new(0),
+            storage_tick: AtomicU64::new(0),
+            sensor_tick: AtomicU64::new(0),
+        }
+    }
+
+    /// `base` with up to `spread` of noise either way
+    fn jitter(&self, stream: u64, tick: u64, base: f64, spread: f64) -> f64 {
+        base + noise(self.seed, stream, tick) * spread
+    }
+
+    /// CPU reading at `tick`
+    pub fn cpu_metrics_at(&self, tick: u64) -> CpuMetrics {
+        let overall = self.jitter(CPU_STREAM, tick, 0.45, 0.08);
+        let per_core_utilization = (0..self.rig.threads as u64)
+            .map(|core| (overall + noise(self.seed, CORE_STREAM + core * 100, tick) * 0.15).clamp(0.0, 1.0))
+            .collect();
+        CpuMetrics {
+            overall_utilization: overall,
+            per_core_utilization,
+            temperature: Some(self.jitter(CPU_TEMPERATURE_STREAM, tick, 68.0, 3.0)),
+            fan_speed_percent: Some(45.0),
+            fan_speed_rpm: None,
+            clock_mhz: Some(self.jitter(CPU_CLOCK_STREAM, tick, self.rig.boost_clock_mhz - 200.0, 100.0)),
+        }
+    }
+
+    /// GPU reading at `tick`
+    pub fn gpu_metrics_at(&self, tick: u64) -> GpuMetrics {
+        let vram_used_mb = self.rig.vram_total_mb as f64 * self.jitter(VRAM_STREAM, tick, 0.6, 0.04);
+        GpuMetrics {
+            utilization: self.jitter(GPU_STREAM, tick, 0.97, 0.02).min(1.0),
+            vram_used_mb: Some(vram_used_mb as u64),
+            vram_total_mb: Some(self.rig.vram_total_mb),
+            temperature: Some(self.jitter(GPU_TEMPERATURE_STREAM, tick, 74.0, 2.0)),
+            hotspot_temperature: None,
+            clock_core_mhz: Some(2500.0),
+            clock_memory_mhz: Some(10_500.0),
+            power_watts: Some(self.jitter(GPU_POWER_STREAM, tick, self.rig.gpu_power_watts, 10.0)),
+            fan_speed_percent: Some(60.0),
+            pcie_link: Some(PcieLinkStatus {
+                gen_current: 4,
+                width_current: 16,
+                gen_max: 4,
+                width_max: 16,
+            }),
+            memory_controller_load: Some(40.0),
+            adapter_name: Some(self.rig.gpu_model.to_string()),
+        }
+    }
+
+    /// Memory reading at `tick`
+    pub fn memory_metrics_at(&self, tick: u64) -> MemoryMetrics {
+        MemoryMetrics {
+            used_mb: self.jitter(MEMORY_STREAM, tick, 14_000.0, 500.0) as u64,
+            total_mb: TOTAL_MEMORY_MB,
+            swap_used_mb: None,
+            swap_total_mb: None,
+        }
+    }
+
+    /// Storage reading at `tick`
+    pub fn storage_metrics_at(&self, tick: u64) -> StorageMetrics {
+        StorageMetrics {
+            read_throughput_mb_per_s: self.jitter(STORAGE_STREAM, tick, 20.0, 10.0),
+            write_throughput_mb_per_s: self.jitter(STORAGE_STREAM + 1, tick, 5.0, 3.0),
+            queue_depth: Some(1),
+            latency_ms: Some(0.3),
+        }
+    }
+
+    /// Sensor readings at `tick`
+    pub fn sensors_at(&self, tick: u64) -> Vec<SensorReading> {
+        vec![SensorReading {
+            name: "Synthetic CPU Fan".to_string(),
+            kind: SensorKind::Fan,
+            value: self.jitter(FAN_STREAM, tick, 1200.0, 50.0),
+            unit: "RPM".to_string(),
+        }]
+    }
+
+    /// Samples the collector would record for `tick`
+    ///
+    /// CPU, GPU, memory and storage readings converted the way the sampling
+    /// loop converts them.
+    pub fn samples_at(&self, tick: u64, timestamp: DateTime<Utc>) -> Vec<MetricSample> {
+        let cpu = self.cpu_metrics_at(tick);
+        let memory = self.memory_metrics_at(tick);
+        let storage = self.storage_metrics_at(tick);
+
+        let mut samples = vec![MetricSample::new(MetricType::CpuUtilization, cpu.overall_utilization * 100.0, "CPU")];
+        samples.extend(cpu.per_core_utilization.iter().enumerate().map(|(idx, util)| {
+            MetricSample::new(MetricType::CpuUtilizationPerCore, util * 100.0, format!("CPU Core {}", idx))
+        }));
+        samples.extend(cpu.clock_mhz.map(|clock| MetricSample::new(MetricType::CpuClock, clock, "CPU")));
+        samples.extend(cpu.temperature.map(|temp| MetricSample::new(MetricType::Temperature, temp, "CPU")));
+        samples.push(MetricSample::new(
+            MetricType::MemoryUsage,
+            memory.used_mb as f64 / memory.total_mb as f64 * 100.0,
+            "Memory",
+        ));
+        samples.push(MetricSample::new(MetricType::MemoryUsed, memory.used_mb as f64, "Memory"));
+        samples.push(MetricSample::new(MetricType::StorageReadThroughput, storage.read_throughput_mb_per_s, "Storage"));
+        samples.push(MetricSample::new(MetricType::StorageWriteThroughput, storage.write_throughput_mb_per_s, "Storage"));
+
+        let mut samples: Vec<MetricSample> = samples.into_iter().map(|s| s.with_timestamp(timestamp)).collect();
+        samples.extend(gpu_samples(&self.gpu_metrics_at(tick), "GPU", timestamp));
+        samples
+    }
+
+    /// `ticks` ticks of samples, `interval_seconds` apart, the last at `end`
+    pub fn metric_stream(&self, ticks: u64, interval_seconds: i64, end: DateTime<Utc>) -> Vec<MetricSample> {
+        (0..ticks)
+            .flat_map(|tick| {
+                let ticks_before_end = (ticks - 1 - tick) as i64;
+                self.samples_at(tick, end - Duration::seconds(ticks_before_end * interval_seconds))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl CpuMetricsProvider for SyntheticMetricsProvider {
+    async fn get_cpu_metrics(&self) -> Result<CpuMetrics, MetricsError> {
+        Ok(self.cpu_metrics_at(self.cpu_tick.fetch_add(1, Ordering::Relaxed)))
+    }
+}
+
+#[async_trait]
+impl GpuMetricsProvider for SyntheticMetricsProvider {
+    async fn get_gpu_metrics(&self) -> Result<GpuMetrics, MetricsError> {
+        Ok(self.gpu_metrics_at(self.gpu_tick.fetch_add(1, Ordering::Relaxed)))
+    }
+    
+    async fn get_adapter_utilization(&self) -> Result<Vec<GpuAdapterUtilization>, MetricsError> {
+        // The rig's only GPU, at the utilization of the latest GPU reading
+        let tick = self.gpu_tick.load(Ordering::Relaxed).saturating_sub(1);
+        Ok(vec![GpuAdapterUtilization {
+            adapter_name: self.rig.gpu_model.to_string(),
+            utilization: self.gpu_metrics_at(tick).utilization,
+        }])
+    }
+}
+
+#[async_trait]
+impl MemoryMetricsProvider for SyntheticMetricsProvider {
+    async fn get_memory_metrics(&self) -> Result<MemoryMetrics, MetricsError> {
+        Ok(self.memory_metrics_at(self.memory_tick.fetch_add(1, Ordering::Relaxed)))
+    }
+}
+
+#[async_trait]
+impl StorageMetricsProvider for SyntheticMetricsProvider {
+    async fn get_storage_metrics(&self) -> Result<StorageMetrics, MetricsError> {
+        Ok(self.storage_metrics_at(self.storage_tick.fetch_add(1, Ordering::Relaxed)))
+    }
+}
+
+#[async_trait]
+impl SensorProvider for SyntheticMetricsProvider {
+    async fn get_sensors(&self) -> Result<Vec<SensorReading>, MetricsError> {
+        Ok(self.sensors_at(self.sensor_tick.fetch_add(1, Ordering::Relaxed)))
+    }
+}
@@ -0,0 +1,124 @@
+//! Helpers for building metric fixtures in tests
+//!
+//! Only compiled with the `test-util` feature, which the crate's own
+//! dev-dependency on itself enables for `cargo test`. Tests across the crate build
+//! the same shapes of data by hand: a few subsystems held at fixed values for
+//! some seconds, one sample per tick. [`MetricStreamBuilder`] produces those
+//! streams with the units the collector would record.
+
+use crate::core::domain::{MetricSample, MetricType};
+use chrono::{DateTime, Duration, Utc};
+
+/// Fluent builder for a stream of constant-valued metric samples
+///
+/// ```ignore
+/// let samples = MetricStreamBuilder::new()
+///     .cpu(95.0)
+///     .gpu(30.0)
+///     .over_seconds(30)
+///     .interval(1)
+///     .build();
+/// ```
+///
+/// Ticks are spaced `interval` seconds apart starting `over_seconds` before
+/// the end time, so the last tick lands one interval before it. The end time
+/// defaults to now; pass a fixed one with [`ending_at`](Self::ending_at) to
+/// get the same stream on every run.
+#[derive(Debug, Clone)]
+pub struct MetricStreamBuilder {
+    metrics: Vec<(MetricType, f64, String)>,
+    duration_seconds: i64,
+    interval_seconds: i64,
+    end: Option<DateTime<Utc>>,
+}
+
+impl Default for MetricStreamBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricStreamBuilder {
+    /// Empty stream spanning 30 seconds at one tick per second
+    pub fn new() -> Self {
+        Self {
+            metrics: Vec::new(),
+            duration_seconds: 30,
+            interval_seconds: 1,
+            end: None,
+        }
+    }
+
+    /// Record `metric_type` at `value` on every tick
+    pub fn metric(mut self, metric_type: MetricType, value: f64, source_component: impl Into<String>) -> Self {
+        self.metrics.push((metric_type, value, source_component.into()));
+        self
+    }
+
+    /// Overall CPU utilization in percent
+    pub fn cpu(self, percent: f64) -> Self {
+        self.metric(MetricType::CpuUtilization, percent, "CPU")
+    }
+
+    /// GPU utilization in percent
+    pub fn gpu(self, percent: f64) -> Self {
+        self.metric(MetricType::GpuUtilization, percent, "GPU")
+    }
+
+    /// RAM usage in percent
+    pub fn ram(self, percent: f64) -> Self {
+        self.metric(MetricType::MemoryUsage, percent, "RAM")
+    }
+
+    /// VRAM usage in percent
+    pub fn vram(self, percent: f64) -> Self {
+        self.metric(MetricType::GpuVramUsagePercent, percent, "GPU")
+    }
+
+    /// CPU temperature in degrees Celsius
+    pub fn temperature(self, celsius: f64) -> Self {
+        self.metric(MetricType::Temperature, celsius, "CPU")
+    }
+
+    /// GPU temperature in degrees Celsius
+    pub fn gpu_temperature(self, celsius: f64) -> Self {
+        self.metric(MetricType::GpuTemperature, celsius, "GPU")
+    }
+
+    /// Frames per second
+    pub fn fps(self, fps: f64) -> Self {
+        self.metric(MetricType::Fps, fps, "Game")
+    }
+
+    /// Length of the stream in seconds
+    pub fn over_seconds(mut self, seconds: i64) -> Self {
+        self.duration_seconds = seconds;
+        self
+    }
+
+    /// Seconds between ticks, at least one
+    pub fn interval(mut self, seconds: i64) -> Self {
+        self.interval_seconds = seconds.max(1);
+        self
+    }
+
+    /// Time the stream ends at, instead of now
+    pub fn ending_at(mut self, end: DateTime<Utc>) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Samples ordered by tick, then by the order the metrics were added
+    pub fn build(&self) -> Vec<MetricSample> {
+        let end = self.end.unwrap_or_else(Utc::now);
+        let ticks = self.duration_seconds.max(0) / self.interval_seconds;
+        (0..ticks)
+            .flat_map(|tick| {
+                let timestamp = end - Duration::seconds(self.duration_seconds - tick * self.interval_seconds);
+                self.metrics.iter().map(move |(metric_type, value, source)| {
+                    MetricSample::new(metric_type.clone(), *value, source.clone()).with_timestamp(timestamp)
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,171 @@
+//! Unit tests for the share of a run each bottleneck was active
+//!
+//! Tests per-sample classification of sustained threshold violations following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::{active_time_phrase, generate_insights};
+    use stats_io_lib::analysis::rules::{bottleneck_active_fraction, detect_gpu_thermal};
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckType, EvidenceItem, MetricSample, MetricType, ThresholdOverrides,
+        ThresholdStatistic, WorkloadProfile, WorkloadType, GPU_HOTSPOT_SOURCE,
+    };
+    use chrono::{DateTime, Duration, Utc};
+    use std::collections::HashMap;
+
+    /// Gaming profile judged on the p90, so a half-loaded run still counts as GPU-bound
+    fn gaming_profile() -> WorkloadProfile {
+        WorkloadProfile {
+            id: "gaming".to_string(),
+            name: "Gaming".to_string(),
+            workload_type: WorkloadType::Gaming,
+            parameters: HashMap::new(),
+            threshold_overrides: Some(ThresholdOverrides {
+                cpu_high: None,
+                gpu_high: None,
+                ram_high: None,
+                vram_high: None,
+                statistic: ThresholdStatistic::Percentile(90.0),
+                combined_pressure: None,
+                sustained_seconds: None,
+            }),
+        }
+    }
+
+    /// One sample per second of the given metric
+    fn samples(
+        metric_type: MetricType,
+        start: DateTime<Utc>,
+        values: impl IntoIterator<Item = f64>,
+    ) -> Vec<MetricSample> {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                MetricSample::new(metric_type.clone(), value, "Test")
+                    .with_timestamp(start + Duration::seconds(i as i64))
+            })
+            .collect()
+    }
+
+    /// GPU pegged for the first minute and idle for the second
+    fn half_gpu_bound() -> Vec<MetricSample> {
+        let start = Utc::now() - Duration::seconds(120);
+        let gpu = (0..120).map(|i| if i < 60 { 98.0 } else { 5.0 });
+        let mut metrics = samples(MetricType::GpuUtilization, start, gpu);
+        metrics.extend(samples(MetricType::CpuUtilization, start, (0..120).map(|_| 30.0)));
+        metrics
+    }
+
+    fn gpu_bottleneck(metrics: &[MetricSample]) -> Bottleneck {
+        let result = AnalysisEngine::new().analyze_recorded(metrics, Some(120), Some(&gaming_profile()));
+        result
+            .bottlenecks
+            .into_iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Gpu)
+            .expect("GPU bottleneck")
+    }
+
+    #[test]
+    fn test_half_gpu_bound_run_is_active_half_the_time() {
+        let bottleneck = gpu_bottleneck(&half_gpu_bound());
+
+        assert!((bottleneck.active_fraction - 0.5).abs() < 0.02, "{}", bottleneck.active_fraction);
+        assert_eq!(active_time_phrase(&bottleneck).as_deref(), Some("GPU-bound 50% of the time"));
+    }
+
+    #[test]
+    fn test_insights_mention_active_time() {
+        let metrics = half_gpu_bound();
+        let result = AnalysisEngine::new().analyze_recorded(&metrics, Some(120), Some(&gaming_profile()));
+
+        let insights = generate_insights(&result, Some(&gaming_profile()));
+        assert!(insights.summary.contains("GPU-bound 50% of the time"), "{}", insights.summary);
+    }
+
+    #[test]
+    fn test_brief_spikes_do_not_count_as_active() {
+        let start = Utc::now() - Duration::seconds(20);
+        // Two-second spikes never reach ONSET_MIN_SAMPLES consecutive ticks
+        let gpu = (0..20).map(|i| if i % 4 < 2 { 99.0 } else { 10.0 });
+        let metrics = samples(MetricType::GpuUtilization, start, gpu);
+        let bottleneck = Bottleneck {
+            bottleneck_type: BottleneckType::Gpu,
+            severity: 50,
+            evidence: vec![EvidenceItem {
+                metric_type: MetricType::GpuUtilization,
+                threshold: 90.0,
+                actual_value: 99.0,
+                time_range_start: start,
+                time_range_end: start + Duration::seconds(19),
+                source_component: None,
+            }],
+            summary: "GPU-bound".to_string(),
+            details: String::new(),
+            onset: None,
+            active_fraction: 0.0,
+        };
+
+        assert_eq!(bottleneck_active_fraction(&bottleneck, &metrics), 0.0);
+        assert_eq!(active_time_phrase(&bottleneck), None);
+    }
+
+    #[test]
+    fn test_missing_evidence_metric_yields_zero() {
+        let metrics = half_gpu_bound();
+        let mut bottleneck = gpu_bottleneck(&metrics);
+        bottleneck.evidence[0].metric_type = MetricType::GpuVramUsage;
+
+        assert_eq!(bottleneck_active_fraction(&bottleneck, &metrics), 0.0);
+    }
+
+    #[test]
+    fn test_hotspot_gap_is_recomputed_per_tick() {
+        let start = Utc::now() - Duration::seconds(120);
+        // Gap of 30°C for the first minute and 10°C for the second, 20°C on average
+        let mut metrics = samples(MetricType::GpuTemperature, start, (0..120).map(|_| 70.0));
+        metrics.extend(
+            (0..120).map(|i| {
+                MetricSample::new(MetricType::GpuHotspotTemperature, if i < 60 { 100.0 } else { 80.0 }, GPU_HOTSPOT_SOURCE)
+                    .with_timestamp(start + Duration::seconds(i))
+            }),
+        );
+        let gap = detect_gpu_thermal(&metrics)
+            .into_iter()
+            .find(|b| b.summary.contains("above edge"))
+            .expect("hotspot gap bottleneck");
+
+        // Every temperature reading is above the 20°C gap threshold, but only half the gaps are
+        let fraction = bottleneck_active_fraction(&gap, &metrics);
+        assert!((fraction - 0.5).abs() < 0.02, "{}", fraction);
+    }
+
+    #[test]
+    fn test_evidence_source_component_limits_readings() {
+        let start = Utc::now() - Duration::seconds(20);
+        let mut metrics = samples(MetricType::GpuUtilization, start, (0..20).map(|_| 99.0));
+        metrics.extend((0..20).map(|i| {
+            MetricSample::new(MetricType::GpuUtilization, if i < 5 { 95.0 } else { 10.0 }, "Other GPU")
+                .with_timestamp(start + Duration::seconds(i))
+        }));
+        let bottleneck = Bottleneck {
+            bottleneck_type: BottleneckType::Gpu,
+            severity: 50,
+            evidence: vec![EvidenceItem {
+                metric_type: MetricType::GpuUtilization,
+                threshold: 90.0,
+                actual_value: 95.0,
+                time_range_start: start,
+                time_range_end: start + Duration::seconds(19),
+                source_component: Some("Other GPU".to_string()),
+            }],
+            summary: "GPU-bound".to_string(),
+            details: String::new(),
+            onset: None,
+            active_fraction: 0.0,
+        };
+
+        assert_eq!(bottleneck_active_fraction(&bottleneck, &metrics), 0.25);
+    }
+}
@@ -0,0 +1,64 @@
+//! Unit tests for analysis window coverage
+//!
+//! Tests the reported data span against the requested window following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::analysis_coverage;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckAnalysisResult, MetricSample, MetricType};
+    use chrono::{Duration, Utc};
+
+    /// One CPU sample per second over the last `seconds` seconds
+    fn create_samples(seconds: i64) -> Vec<MetricSample> {
+        let now = Utc::now();
+        (0..=seconds)
+            .map(|i| {
+                MetricSample::new(MetricType::CpuUtilization, 40.0, "CPU")
+                    .with_timestamp(now - Duration::seconds(seconds - i))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_coverage_matches_data_span() {
+        let samples = create_samples(30);
+        let result = AnalysisEngine::new().analyze_recorded(&samples, Some(3600), None);
+
+        let coverage = result.coverage.expect("coverage");
+        assert_eq!(coverage.requested_seconds, 3600);
+        assert_eq!(coverage.covered_seconds, 30);
+        assert_eq!(
+            coverage.warning.as_deref(),
+            Some("Analyzed the last 30s of a requested 60m window")
+        );
+    }
+
+    #[test]
+    fn test_full_window_has_no_warning() {
+        let samples = create_samples(120);
+        let result = AnalysisEngine::new().analyze_recorded(&samples, Some(60), None);
+
+        let coverage = result.coverage.expect("coverage");
+        // Only samples inside the window count towards coverage
+        assert_eq!(coverage.covered_seconds, 60);
+        assert_eq!(coverage.warning, None);
+    }
+
+    #[test]
+    fn test_empty_data() {
+        let coverage = analysis_coverage(&[], 90);
+        assert_eq!(coverage.covered_seconds, 0);
+        assert_eq!(
+            coverage.warning.as_deref(),
+            Some("No samples fall within the requested 1m 30s window")
+        );
+    }
+
+    #[test]
+    fn test_legacy_result_without_coverage() {
+        let legacy: BottleneckAnalysisResult =
+            serde_json::from_str(r#"{"bottlenecks": [], "timestamp": "2024-01-01T00:00:00Z"}"#).unwrap();
+        assert!(legacy.coverage.is_none());
+    }
+}
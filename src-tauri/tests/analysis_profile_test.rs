@@ -0,0 +1,161 @@
+//! Unit tests for named analysis profiles
+//!
+//! Tests applying and persisting analysis profiles following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, Run, ThresholdOverrides, ThresholdStatistic,
+        WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::sessions::analyze_run_with_profile;
+    use stats_io_lib::core::settings::{AnalysisProfile, SettingsManager, UserSettings};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_gaming_profile(threshold_overrides: Option<ThresholdOverrides>) -> WorkloadProfile {
+        WorkloadProfile {
+            id: "gaming".to_string(),
+            name: "Gaming".to_string(),
+            workload_type: WorkloadType::Gaming,
+            parameters: HashMap::new(),
+            threshold_overrides,
+        }
+    }
+
+    fn thresholds(cpu_high: Option<f64>, statistic: ThresholdStatistic) -> ThresholdOverrides {
+        ThresholdOverrides {
+            cpu_high,
+            gpu_high: None,
+            ram_high: None,
+            vram_high: None,
+            statistic,
+            combined_pressure: None,
+            sustained_seconds: None,
+        }
+    }
+
+    fn strict_profile() -> AnalysisProfile {
+        AnalysisProfile {
+            id: "strict".to_string(),
+            name: "Strict 60s p95".to_string(),
+            window_seconds: Some(60),
+            thresholds: thresholds(Some(85.0), ThresholdStatistic::Percentile(95.0)),
+        }
+    }
+
+    fn lenient_profile() -> AnalysisProfile {
+        AnalysisProfile {
+            id: "lenient".to_string(),
+            name: "Lenient 10s mean".to_string(),
+            window_seconds: Some(10),
+            thresholds: thresholds(Some(90.0), ThresholdStatistic::Mean),
+        }
+    }
+
+    /// Two minutes of CPU pegged at 95% with a dip to 40% every fourth second
+    fn create_run() -> Run {
+        let start = Utc::now() - Duration::seconds(120);
+        let samples = |metric_type: MetricType, value: &dyn Fn(i64) -> f64| -> Vec<MetricSample> {
+            (0..120)
+                .map(|i| {
+                    MetricSample::new(metric_type.clone(), value(i), "Test")
+                        .with_timestamp(start + Duration::seconds(i))
+                })
+                .collect()
+        };
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert(
+            "cpu".to_string(),
+            samples(MetricType::CpuUtilization, &|i| if i % 4 == 3 { 40.0 } else { 95.0 }),
+        );
+        metrics_streams.insert("gpu".to_string(), samples(MetricType::GpuUtilization, &|_| 50.0));
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn bottleneck_types(analysis_profile: &AnalysisProfile) -> Vec<BottleneckType> {
+        let result = analyze_run_with_profile(
+            &AnalysisEngine::new(),
+            &create_run(),
+            &create_gaming_profile(None),
+            analysis_profile,
+        )
+        .unwrap();
+        result.bottlenecks.into_iter().map(|b| b.bottleneck_type).collect()
+    }
+
+    #[test]
+    fn test_profiles_yield_different_bottlenecks() {
+        let strict = bottleneck_types(&strict_profile());
+        let lenient = bottleneck_types(&lenient_profile());
+
+        assert!(strict.contains(&BottleneckType::Cpu));
+        assert!(!lenient.contains(&BottleneckType::Cpu));
+        assert_ne!(strict, lenient);
+    }
+
+    #[test]
+    fn test_apply_keeps_unset_workload_thresholds() {
+        let workload = create_gaming_profile(Some(ThresholdOverrides {
+            cpu_high: Some(70.0),
+            gpu_high: Some(80.0),
+            ram_high: None,
+            vram_high: None,
+            statistic: ThresholdStatistic::Mean,
+            combined_pressure: None,
+            sustained_seconds: None,
+        }));
+        let applied = strict_profile().apply_to(&workload);
+        let overrides = applied.threshold_overrides.unwrap();
+
+        assert_eq!(overrides.cpu_high, Some(85.0));
+        assert_eq!(overrides.gpu_high, Some(80.0));
+        assert_eq!(overrides.statistic, ThresholdStatistic::Percentile(95.0));
+        assert_eq!(applied.workload_type, WorkloadType::Gaming);
+    }
+
+    #[test]
+    fn test_profiles_persist_in_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+
+        let mut manager = SettingsManager::new(settings_path.clone()).unwrap();
+        manager.save_analysis_profile(strict_profile()).unwrap();
+        manager.save_analysis_profile(lenient_profile()).unwrap();
+
+        let mut renamed = strict_profile();
+        renamed.name = "Strict".to_string();
+        manager.save_analysis_profile(renamed).unwrap();
+
+        let reloaded = SettingsManager::new(settings_path.clone()).unwrap();
+        let profiles = &reloaded.get_settings().analysis_profiles;
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].name, "Strict");
+        assert_eq!(profiles[1].window_seconds, Some(10));
+
+        assert!(manager.delete_analysis_profile("lenient").unwrap());
+        assert!(!manager.delete_analysis_profile("lenient").unwrap());
+        let reloaded = SettingsManager::new(settings_path).unwrap();
+        assert_eq!(reloaded.get_settings().analysis_profiles.len(), 1);
+    }
+
+    #[test]
+    fn test_settings_without_profiles_deserialize() {
+        let json = serde_json::to_value(UserSettings::default()).unwrap();
+        let mut object = json.as_object().unwrap().clone();
+        object.remove("analysis_profiles");
+
+        let settings: UserSettings = serde_json::from_value(serde_json::Value::Object(object)).unwrap();
+        assert!(settings.analysis_profiles.is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+//! Unit tests for the one-call analysis API
+//!
+//! Tests analyze_and_explain end-to-end on fixture streams following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::BottleneckAcknowledgment;
+    use stats_io_lib::analysis::{analyze_and_explain, AnalysisConfig};
+    use stats_io_lib::core::domain::{
+        BottleneckType, CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::test_support::MetricStreamBuilder;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn create_hardware() -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                architecture: None,
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: None,
+                speed_mhz: None,
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    fn create_profile() -> WorkloadProfile {
+        WorkloadProfile {
+            id: "gaming".to_string(),
+            name: "Gaming".to_string(),
+            workload_type: WorkloadType::Gaming,
+            parameters: HashMap::new(),
+            threshold_overrides: None,
+        }
+    }
+
+    #[test]
+    fn test_cpu_bound_stream_is_explained() {
+        let samples = MetricStreamBuilder::new().cpu(97.0).gpu(40.0).ram(50.0).over_seconds(60).build();
+
+        let report = analyze_and_explain(&samples, &create_hardware(), &create_profile(), &AnalysisConfig::default());
+
+        let primary = report.primary_bottleneck.expect("primary bottleneck");
+        assert_eq!(primary.bottleneck_type, BottleneckType::Cpu);
+        assert_eq!(report.analysis.bottlenecks[0].bottleneck_type, BottleneckType::Cpu);
+        assert!(!report.insights.summary.is_empty());
+        assert!(!report.insights.recommendations.is_empty());
+        assert_eq!(report.insights.severity, primary.severity);
+        assert_eq!(report.health_score, 100 - primary.severity);
+        assert!(report.health_score < 100);
+        assert!(report.grades[&BottleneckType::Cpu] > 'A');
+        assert!(report.analysis.coverage.is_some());
+    }
+
+    #[test]
+    fn test_idle_stream_is_healthy() {
+        let samples = MetricStreamBuilder::new().cpu(20.0).gpu(10.0).ram(30.0).over_seconds(60).build();
+
+        let report = analyze_and_explain(&samples, &create_hardware(), &create_profile(), &AnalysisConfig::default());
+
+        assert!(report.primary_bottleneck.is_none());
+        assert_eq!(report.health_score, 100);
+        assert_eq!(report.grades[&BottleneckType::Cpu], 'A');
+    }
+
+    #[test]
+    fn test_acknowledged_bottleneck_is_not_primary() {
+        let samples = MetricStreamBuilder::new().cpu(97.0).gpu(40.0).ram(50.0).over_seconds(60).build();
+        let config = AnalysisConfig {
+            acknowledgments: vec![BottleneckAcknowledgment {
+                bottleneck_type: BottleneckType::Cpu,
+                max_severity: None,
+                note: None,
+                acknowledged_at: Utc::now(),
+            }],
+            ..AnalysisConfig::default()
+        };
+
+        let report = analyze_and_explain(&samples, &create_hardware(), &create_profile(), &config);
+
+        assert!(report.analysis.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Cpu));
+        assert!(report.primary_bottleneck.is_none());
+        assert_eq!(report.health_score, 100);
+        assert_eq!(report.insights.dismissed.len(), 1);
+    }
+}
@@ -0,0 +1,101 @@
+//! Unit tests for background download detection
+//!
+//! Tests that updater disk writes are classified as interference following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::advanced::is_background_downloader;
+    use stats_io_lib::analysis::rules::{analyze_bottlenecks, detect_background_download};
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::metrics::models::ProcessDiskUsage;
+    use stats_io_lib::metrics::providers::disk_processes::process_disk_samples;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    fn general_profile() -> WorkloadProfile {
+        WorkloadProfile {
+            id: "general".to_string(),
+            name: "General".to_string(),
+            workload_type: WorkloadType::General,
+            parameters: HashMap::new(),
+            threshold_overrides: None,
+        }
+    }
+
+    /// 20 ticks of saturated storage with `writer` doing most of the writing
+    fn create_samples(writer: &str, writer_mb_per_s: f64, system_write_mb_per_s: f64) -> Vec<MetricSample> {
+        let now = Utc::now();
+        let writers = vec![
+            ProcessDiskUsage { pid: 4120, name: writer.to_string(), write_mb_per_s: writer_mb_per_s },
+            ProcessDiskUsage { pid: 880, name: "explorer.exe".to_string(), write_mb_per_s: 2.0 },
+        ];
+        (0..20)
+            .flat_map(|i| {
+                let timestamp = now - Duration::seconds(20 - i);
+                let mut samples = vec![
+                    MetricSample::new(MetricType::StorageWriteThroughput, system_write_mb_per_s, "Storage")
+                        .with_timestamp(timestamp),
+                    MetricSample::new(MetricType::StorageQueueDepth, 15.0, "Storage").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::CpuUtilization, 40.0, "CPU").with_timestamp(timestamp),
+                ];
+                samples.extend(process_disk_samples(&writers, timestamp));
+                samples
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_updater_writes_classified_as_interference() {
+        let samples = create_samples("steam.exe", 85.0, 90.0);
+        let result = analyze_bottlenecks(&samples, 30, Some(&general_profile()));
+
+        let types: Vec<&BottleneckType> = result.bottlenecks.iter().map(|b| &b.bottleneck_type).collect();
+        assert_eq!(types, vec![&BottleneckType::BackgroundDownload], "{:?}", types);
+
+        let download = &result.bottlenecks[0];
+        assert!(download.summary.contains("steam.exe (pid 4120)"), "{}", download.summary);
+        assert!(download.summary.contains("85.0 MB/s"));
+        assert_eq!(download.evidence[0].source_component.as_deref(), Some("steam.exe (pid 4120)"));
+        assert_eq!(download.evidence[0].metric_type, MetricType::ProcessDiskWriteThroughput);
+        assert_eq!(download.severity, 42);
+
+        let insights = generate_insights(&result, Some(&general_profile()));
+        assert!(insights.recommendations.iter().any(|r| r.text.starts_with("Pause downloads")));
+    }
+
+    #[test]
+    fn test_workload_writes_remain_storage_bottleneck() {
+        let samples = create_samples("blender.exe", 85.0, 90.0);
+        assert!(detect_background_download(&samples).is_none());
+
+        let result = analyze_bottlenecks(&samples, 30, Some(&general_profile()));
+        let types: Vec<&BottleneckType> = result.bottlenecks.iter().map(|b| &b.bottleneck_type).collect();
+        assert_eq!(types, vec![&BottleneckType::Storage]);
+    }
+
+    #[test]
+    fn test_minor_updater_share_is_ignored() {
+        // The updater writes steadily but the workload writes far more
+        assert!(detect_background_download(&create_samples("steam.exe", 30.0, 400.0)).is_none());
+        // Too little to matter
+        assert!(detect_background_download(&create_samples("steam.exe", 5.0, 6.0)).is_none());
+        // Without system write samples the process writes decide alone
+        let without_system: Vec<MetricSample> = create_samples("TiWorker.exe", 60.0, 0.0)
+            .into_iter()
+            .filter(|s| s.metric_type != MetricType::StorageWriteThroughput)
+            .collect();
+        assert!(detect_background_download(&without_system).is_some());
+    }
+
+    #[test]
+    fn test_downloader_names() {
+        assert!(is_background_downloader("steam.exe (pid 4120)"));
+        assert!(is_background_downloader("EpicGamesLauncher.exe (pid 12)"));
+        assert!(is_background_downloader("packagekitd (pid 900)"));
+        assert!(!is_background_downloader("steam_game.exe (pid 4121)"));
+        assert!(!is_background_downloader("blender (pid 77)"));
+    }
+}
@@ -0,0 +1,72 @@
+//! Unit tests for high-frequency benchmark capture
+//!
+//! Tests tick cadence, validation, and buffer isolation following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::error::MetricsError;
+    use stats_io_lib::metrics::collector::{BENCHMARK_MAX_DURATION, BENCHMARK_MIN_INTERVAL_MS};
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+    use std::collections::HashSet;
+    use sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_one_second_capture_at_250ms() {
+        let collector = MetricsCollector::new(MetricsCollectorConfig::default());
+        let run = collector.benchmark_capture(Duration::from_secs(1), 250).await.unwrap();
+
+        let cpu = &run.metrics_streams["cpu_utilization"];
+        let ticks: HashSet<_> = cpu.iter().map(|m| m.timestamp).collect();
+        assert!((3..=4).contains(&ticks.len()), "{} ticks", ticks.len());
+        assert!(run.metrics_streams.contains_key("memory_usage"));
+        assert!(run.notes.is_none());
+
+        // Ticks are evenly spaced rather than bunched together
+        let span = cpu.last().unwrap().timestamp - cpu.first().unwrap().timestamp;
+        assert!(span.num_milliseconds() >= 450, "span {} ms", span.num_milliseconds());
+    }
+
+    #[tokio::test]
+    async fn test_minimum_interval_follows_cpu_update_interval() {
+        let minimum = MINIMUM_CPU_UPDATE_INTERVAL.as_millis() as u64;
+        assert_eq!(BENCHMARK_MIN_INTERVAL_MS, minimum);
+
+        let collector = MetricsCollector::new(MetricsCollectorConfig::default());
+        let run = collector.benchmark_capture(Duration::from_secs(1), minimum).await.unwrap();
+
+        let minimum = minimum as i64;
+        assert_eq!(run.name, format!("Benchmark capture ({} ms)", minimum));
+        let cpu = &run.metrics_streams["cpu_utilization"];
+        for pair in cpu.windows(2) {
+            let gap = (pair[1].timestamp - pair[0].timestamp).num_milliseconds();
+            assert!(gap >= minimum - 20, "gap {} ms", gap);
+        }
+        // Only utilization is captured, not temperature
+        assert!(!run.metrics_streams.contains_key("temperature"));
+    }
+
+    #[tokio::test]
+    async fn test_capture_leaves_shared_buffer_untouched() {
+        let collector = MetricsCollector::new(MetricsCollectorConfig::default());
+        collector.benchmark_capture(Duration::from_millis(100), BENCHMARK_MIN_INTERVAL_MS).await.unwrap();
+
+        assert!(collector.get_buffer().await.is_empty());
+        assert_eq!(collector.health().await.buffered_samples, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_out_of_range_settings() {
+        let collector = MetricsCollector::new(MetricsCollectorConfig::default());
+
+        let too_fast = collector
+            .benchmark_capture(Duration::from_secs(1), BENCHMARK_MIN_INTERVAL_MS - 1)
+            .await;
+        assert!(matches!(too_fast, Err(MetricsError::InvalidValue(_))));
+
+        let too_long = collector
+            .benchmark_capture(BENCHMARK_MAX_DURATION + Duration::from_secs(1), BENCHMARK_MIN_INTERVAL_MS)
+            .await;
+        assert!(matches!(too_long, Err(MetricsError::InvalidValue(_))));
+    }
+}
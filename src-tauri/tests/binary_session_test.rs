@@ -0,0 +1,230 @@
+//! Unit tests for the binary session format
+//!
+//! Tests `.stio` round trips, headers, and load speed following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata,
+        HardwareConfig, MemoryInfo, MetricSample, MetricType, Run, Session, WorkloadProfile,
+        WorkloadType,
+    };
+    use stats_io_lib::core::error::PersistenceError;
+    use stats_io_lib::core::profiles::WorkloadProfiles;
+    use stats_io_lib::persistence::export_import::{export_session, import_session};
+    use stats_io_lib::persistence::binary::{
+        decode_session, encode_session, load_session_binary, load_session_file, save_session_binary,
+        save_session_file, BINARY_SESSION_MAGIC, BINARY_SESSION_VERSION,
+    };
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use std::time::Instant;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_test_session(runs: Vec<Run>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: Some(Utc::now()),
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Baseline".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs,
+        }
+    }
+
+    fn create_test_run(name: &str, samples_per_stream: i64) -> Run {
+        let start = Utc::now();
+        let mut metrics_streams = HashMap::new();
+        for (key, metric_type, source) in [
+            ("cpu", MetricType::CpuUtilization, "CPU"),
+            ("gpu", MetricType::GpuUtilization, "GPU 0"),
+            ("memory", MetricType::MemoryUsage, "Memory"),
+        ] {
+            let samples = (0..samples_per_stream)
+                .map(|i| {
+                    MetricSample::new(metric_type.clone(), (i % 100) as f64 + 0.25, source)
+                        .with_timestamp(start + Duration::milliseconds(i * 500))
+                })
+                .collect();
+            metrics_streams.insert(key.to_string(), samples);
+        }
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: vec![Bottleneck {
+                    bottleneck_type: BottleneckType::Gpu,
+                    severity: 65,
+                    evidence: vec![],
+                    summary: "GPU bound".to_string(),
+                    details: String::new(),
+                    onset: None,
+                    active_fraction: 0.0,
+                }],
+                timestamp: start,
+                coverage: None,
+            }),
+            notes: Some(format!("{} notes", name)),
+            environment: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_binary_round_trip_matches_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let session = create_test_session(vec![create_test_run("Stock", 50), create_test_run("Tuned", 50)]);
+        let binary_path = temp_dir.path().join("session.stio");
+        let json_path = temp_dir.path().join("session.json");
+
+        save_session_binary(&session, &binary_path).await.unwrap();
+        save_session_file(&session, &json_path).await.unwrap();
+
+        let from_binary = load_session_binary(&binary_path).await.unwrap();
+        let from_json = load_session_file(&json_path).await.unwrap();
+        assert_eq!(from_binary.id, session.id);
+        assert_eq!(
+            serde_json::to_value(&from_binary).unwrap(),
+            serde_json::to_value(&from_json).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_format_selected_by_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let session = create_test_session(vec![create_test_run("Stock", 10)]);
+        let binary_path = temp_dir.path().join("session.STIO");
+        let json_path = temp_dir.path().join("session.json");
+
+        save_session_file(&session, &binary_path).await.unwrap();
+        save_session_file(&session, &json_path).await.unwrap();
+
+        let binary = std::fs::read(&binary_path).unwrap();
+        assert_eq!(&binary[..4], BINARY_SESSION_MAGIC);
+        assert_eq!(std::fs::read(&json_path).unwrap()[0], b'{');
+        assert_eq!(load_session_file(&binary_path).await.unwrap().id, session.id);
+        assert_eq!(load_session_file(&json_path).await.unwrap().id, session.id);
+    }
+
+    #[tokio::test]
+    async fn test_export_import_selects_format_by_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let session = create_test_session(vec![create_test_run("Stock", 20)]);
+        let binary_path = temp_dir.path().join("shared.stio");
+        let json_path = temp_dir.path().join("shared.json");
+
+        export_session(&session, &binary_path).await.unwrap();
+        export_session(&session, &json_path).await.unwrap();
+
+        assert_eq!(&std::fs::read(&binary_path).unwrap()[..4], BINARY_SESSION_MAGIC);
+        assert_eq!(std::fs::read(&json_path).unwrap()[0], b'{');
+
+        let from_binary = import_session(&binary_path).await.unwrap();
+        let from_json = import_session(&json_path).await.unwrap();
+        assert_eq!(from_binary.id, session.id);
+        assert_eq!(
+            serde_json::to_value(&from_binary).unwrap(),
+            serde_json::to_value(&from_json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_preset_profiles_round_trip() {
+        for profile in WorkloadProfiles::get_presets() {
+            let mut session = create_test_session(vec![create_test_run("Stock", 10)]);
+            session.profile = profile;
+
+            let decoded = decode_session(&encode_session(&session).unwrap())
+                .unwrap_or_else(|e| panic!("profile {} failed to decode: {}", session.profile.id, e));
+
+            assert_eq!(decoded.profile.id, session.profile.id);
+            assert_eq!(decoded.profile.parameters, session.profile.parameters);
+            assert_eq!(
+                serde_json::to_value(&decoded).unwrap(),
+                serde_json::to_value(&session).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_header_is_checked() {
+        let session = create_test_session(vec![]);
+        let mut bytes = encode_session(&session).unwrap();
+        assert_eq!(&bytes[4..8], &BINARY_SESSION_VERSION.to_le_bytes());
+
+        bytes[4..8].copy_from_slice(&(BINARY_SESSION_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            decode_session(&bytes),
+            Err(PersistenceError::SchemaVersionMismatch { found, .. }) if found == BINARY_SESSION_VERSION + 1
+        ));
+
+        assert!(matches!(decode_session(b"{\"id\": 1}"), Err(PersistenceError::Deserialization(_))));
+        assert!(matches!(decode_session(b"ST"), Err(PersistenceError::Deserialization(_))));
+    }
+
+    #[test]
+    fn test_large_session_loads_faster_than_json() {
+        let session = create_test_session((0..4).map(|i| create_test_run(&format!("Run {}", i), 10_000)).collect());
+        let binary = encode_session(&session).unwrap();
+        let json = serde_json::to_string(&session).unwrap();
+        assert!(binary.len() < json.len());
+
+        // Best of several loads to keep scheduler noise out of the comparison
+        let best_of = |load: &dyn Fn() -> Session| {
+            (0..3)
+                .map(|_| {
+                    let start = Instant::now();
+                    let loaded = load();
+                    assert_eq!(loaded.runs.len(), 4);
+                    start.elapsed()
+                })
+                .min()
+                .unwrap()
+        };
+        let binary_time = best_of(&|| decode_session(&binary).unwrap());
+        let json_time = best_of(&|| serde_json::from_str(&json).unwrap());
+
+        assert!(
+            binary_time * 2 < json_time,
+            "binary load took {:?}, JSON load took {:?}",
+            binary_time,
+            json_time
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Unit tests for boost clock sustainability
+//!
+//! Tests sustained versus peak clock measurement following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::boost::{analyze_boost_sustainability, BoostComponent};
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run, GPU_HOTSPOT_SOURCE};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    /// 5000 MHz for 30 seconds, then 100 MHz less per second down to 4400 MHz
+    fn decaying_clock(seconds: i64) -> Vec<(i64, f64)> {
+        (0..seconds)
+            .map(|i| {
+                let clock = if i < 30 { 5000.0 } else { (5000.0 - 100.0 * (i - 30) as f64).max(4400.0) };
+                (i, clock)
+            })
+            .collect()
+    }
+
+    fn create_run(clock_metric: MetricType, clocks: &[(i64, f64)], temperature_metric: Option<MetricType>) -> Run {
+        let start = Utc::now();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert(
+            "clock".to_string(),
+            clocks
+                .iter()
+                .map(|&(i, clock)| {
+                    MetricSample::new(clock_metric.clone(), clock, "Package").with_timestamp(start + Duration::seconds(i))
+                })
+                .collect(),
+        );
+        if let Some(temperature_metric) = temperature_metric {
+            // Heats up from 60°C to 95°C over the first 40 seconds
+            metrics_streams.insert(
+                "temperature".to_string(),
+                clocks
+                    .iter()
+                    .map(|&(i, _)| {
+                        let temp = 60.0 + 35.0 * (i.min(40) as f64 / 40.0);
+                        MetricSample::new(temperature_metric.clone(), temp, "Package")
+                            .with_timestamp(start + Duration::seconds(i))
+                    })
+                    .collect(),
+            );
+        }
+        Run {
+            id: Uuid::new_v4(),
+            name: "Cinebench loop".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    #[test]
+    fn test_decaying_clock_sustain_and_delta() {
+        let run = create_run(MetricType::CpuClock, &decaying_clock(120), Some(MetricType::Temperature));
+        let results = analyze_boost_sustainability(&run);
+
+        assert_eq!(results.len(), 1);
+        let cpu = &results[0];
+        assert_eq!(cpu.component, BoostComponent::Cpu);
+        assert_eq!(cpu.peak_clock_mhz, 5000.0);
+        // 4900 MHz at 31 s is within 3% of the peak; 4800 MHz at 32 s is not
+        assert_eq!(cpu.sustained_seconds, Some(32.0));
+        assert_eq!(cpu.settled_clock_mhz, 4400.0);
+        assert!((cpu.settled_delta_percent + 12.0).abs() < 1e-9);
+        assert!(cpu.settled_temperature > cpu.boost_temperature);
+        assert!(cpu.summary.starts_with("CPU sustains boost for 32 seconds, then -12.0%"), "{}", cpu.summary);
+    }
+
+    #[test]
+    fn test_single_spike_is_not_the_peak() {
+        let mut clocks: Vec<(i64, f64)> = (0..60).map(|i| (i, 4500.0)).collect();
+        clocks[10].1 = 5200.0;
+        let run = create_run(MetricType::GpuClock, &clocks, Some(MetricType::GpuTemperature));
+        let results = analyze_boost_sustainability(&run);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].component, BoostComponent::Gpu);
+        assert_eq!(results[0].peak_clock_mhz, 4500.0);
+        assert_eq!(results[0].sustained_seconds, None);
+        assert_eq!(results[0].settled_delta_percent, 0.0);
+        assert!(results[0].summary.contains("whole run"));
+    }
+
+    #[test]
+    fn test_readings_come_from_the_primary_source() {
+        // The primary GPU decays while a second, idle GPU holds a higher clock
+        // and the hotspot runs 20°C above the edge temperature
+        let mut run = create_run(MetricType::GpuClock, &decaying_clock(120), Some(MetricType::GpuTemperature));
+        let start = run.metrics_streams["clock"][0].timestamp;
+        for samples in run.metrics_streams.values_mut() {
+            for sample in samples.iter_mut() {
+                sample.source_component = "GPU".to_string();
+            }
+        }
+        let extra: Vec<MetricSample> = (0..120)
+            .flat_map(|i| {
+                let timestamp = start + Duration::seconds(i);
+                [
+                    MetricSample::new(MetricType::GpuClock, 5500.0, "GPU 1").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::GpuHotspotTemperature, 115.0, GPU_HOTSPOT_SOURCE).with_timestamp(timestamp),
+                ]
+            })
+            .collect();
+        run.metrics_streams.insert("other".to_string(), extra);
+
+        let results = analyze_boost_sustainability(&run);
+        let gpu = &results[0];
+        assert_eq!(gpu.peak_clock_mhz, 5000.0);
+        assert_eq!(gpu.sustained_seconds, Some(32.0));
+        assert!(gpu.settled_temperature <= 95.0, "{}", gpu.settled_temperature);
+    }
+
+    #[test]
+    fn test_requires_clock_and_temperature() {
+        let without_temperature = create_run(MetricType::CpuClock, &decaying_clock(120), None);
+        assert!(analyze_boost_sustainability(&without_temperature).is_empty());
+
+        let too_short = create_run(MetricType::CpuClock, &decaying_clock(5), Some(MetricType::Temperature));
+        assert!(analyze_boost_sustainability(&too_short).is_empty());
+    }
+}
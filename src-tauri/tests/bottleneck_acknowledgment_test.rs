@@ -0,0 +1,128 @@
+//! Unit tests for bottleneck acknowledgments
+//!
+//! Tests that acknowledged bottlenecks are dismissed rather than re-surfaced following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::alerts::{AlertConfig, AlertWatcher};
+    use stats_io_lib::analysis::insights::{generate_acknowledged_insights, generate_insights, BottleneckAcknowledgment};
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, MetricSample, MetricType,
+    };
+    use stats_io_lib::core::settings::SettingsManager;
+    use chrono::{Duration, Utc};
+    use tempfile::TempDir;
+
+    fn bottleneck(bottleneck_type: BottleneckType, severity: u8) -> Bottleneck {
+        Bottleneck {
+            summary: format!("{:?}-bound", bottleneck_type),
+            bottleneck_type,
+            severity,
+            evidence: vec![],
+            details: String::new(),
+            onset: None,
+            active_fraction: 0.0,
+        }
+    }
+
+    fn result(bottlenecks: Vec<Bottleneck>) -> BottleneckAnalysisResult {
+        BottleneckAnalysisResult {
+            bottlenecks,
+            timestamp: Utc::now(),
+            coverage: None,
+        }
+    }
+
+    fn acknowledge(bottleneck_type: BottleneckType, max_severity: Option<u8>) -> BottleneckAcknowledgment {
+        BottleneckAcknowledgment {
+            bottleneck_type,
+            max_severity,
+            note: Some("Expected on this machine".to_string()),
+            acknowledged_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_acknowledged_gpu_bottleneck_is_dismissed() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        let mut manager = SettingsManager::new(settings_path.clone()).unwrap();
+        manager.acknowledge_bottleneck(acknowledge(BottleneckType::Gpu, None)).unwrap();
+        // Acknowledgments persist across restarts
+        let acknowledgments = SettingsManager::new(settings_path).unwrap().get_settings().acknowledgments.clone();
+        assert_eq!(acknowledgments.len(), 1);
+
+        let raw = result(vec![bottleneck(BottleneckType::Gpu, 70), bottleneck(BottleneckType::Cpu, 40)]);
+        let insights = generate_acknowledged_insights(&raw, None, &[], None, &acknowledgments);
+
+        assert_eq!(insights.dismissed.len(), 1);
+        assert_eq!(insights.dismissed[0].bottleneck_type, BottleneckType::Gpu);
+        assert!(insights.recommendations.iter().all(|r| r.bottleneck_type != Some(BottleneckType::Gpu)));
+        assert!(insights.recommendations.iter().any(|r| r.bottleneck_type == Some(BottleneckType::Cpu)));
+        assert_eq!(insights.summary, "Cpu-bound");
+        assert_eq!(insights.severity, 40);
+        // The raw result still records the GPU bottleneck
+        assert_eq!(raw.bottlenecks.len(), 2);
+
+        let only_gpu = generate_acknowledged_insights(
+            &result(vec![bottleneck(BottleneckType::Gpu, 70)]),
+            None,
+            &[],
+            None,
+            &acknowledgments,
+        );
+        assert_eq!(only_gpu.severity, 0);
+        assert!(only_gpu.summary.contains("1 acknowledged bottleneck(s) dismissed"), "{}", only_gpu.summary);
+    }
+
+    #[test]
+    fn test_severity_ceiling_resurfaces_worse_bottlenecks() {
+        let acknowledgments = vec![acknowledge(BottleneckType::Gpu, Some(60))];
+
+        let mild = generate_acknowledged_insights(
+            &result(vec![bottleneck(BottleneckType::Gpu, 60)]),
+            None,
+            &[],
+            None,
+            &acknowledgments,
+        );
+        assert_eq!(mild.dismissed.len(), 1);
+
+        let severe_result = result(vec![bottleneck(BottleneckType::Gpu, 85)]);
+        let severe = generate_acknowledged_insights(&severe_result, None, &[], None, &acknowledgments);
+        assert!(severe.dismissed.is_empty());
+        assert_eq!(severe.recommendations, generate_insights(&severe_result, None).recommendations);
+    }
+
+    #[test]
+    fn test_unacknowledge_and_replace() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = SettingsManager::new(temp_dir.path().join("settings.json")).unwrap();
+        manager.acknowledge_bottleneck(acknowledge(BottleneckType::Gpu, None)).unwrap();
+        manager.acknowledge_bottleneck(acknowledge(BottleneckType::Gpu, Some(50))).unwrap();
+        assert_eq!(manager.get_settings().acknowledgments.len(), 1);
+        assert_eq!(manager.get_settings().acknowledgments[0].max_severity, Some(50));
+
+        assert!(manager.unacknowledge_bottleneck(&BottleneckType::Gpu).unwrap());
+        assert!(!manager.unacknowledge_bottleneck(&BottleneckType::Gpu).unwrap());
+        assert!(manager.get_settings().acknowledgments.is_empty());
+    }
+
+    #[test]
+    fn test_acknowledged_bottleneck_does_not_alert() {
+        let now = Utc::now();
+        let samples = vec![
+            MetricSample::new(MetricType::Temperature, 96.0, "CPU").with_timestamp(now - Duration::seconds(1)),
+            MetricSample::new(MetricType::Temperature, 97.0, "CPU").with_timestamp(now),
+        ];
+
+        let mut watcher = AlertWatcher::new(AlertConfig::default())
+            .with_acknowledgments(vec![acknowledge(BottleneckType::Thermal, None)]);
+        watcher.push(samples.clone());
+        assert!(watcher.evaluate(now).is_empty());
+
+        let mut watcher = AlertWatcher::new(AlertConfig::default());
+        watcher.push(samples);
+        assert_eq!(watcher.evaluate(now).len(), 1);
+    }
+}
@@ -0,0 +1,73 @@
+//! Unit tests for live bottleneck alerts
+//!
+//! Tests that severe live bottlenecks raise debounced alerts following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::alerts::{watch_bottlenecks, AlertConfig, AlertWatcher};
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use chrono::{DateTime, Duration, Utc};
+    use tokio::sync::broadcast;
+
+    fn temperature(value: f64, timestamp: DateTime<Utc>) -> MetricSample {
+        MetricSample::new(MetricType::Temperature, value, "CPU").with_timestamp(timestamp)
+    }
+
+    #[tokio::test]
+    async fn test_critical_thermal_stream_alerts_once() {
+        let (sender, receiver) = broadcast::channel(64);
+        let watcher = tokio::spawn(async move {
+            let mut alerts = Vec::new();
+            watch_bottlenecks(
+                receiver,
+                std::time::Duration::from_millis(10),
+                AlertWatcher::new(AlertConfig::default()),
+                |alert| alerts.push(alert),
+            )
+            .await;
+            alerts
+        });
+
+        for _ in 0..10 {
+            sender.send(vec![temperature(97.0, Utc::now())]).unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(15)).await;
+        }
+        drop(sender);
+
+        let alerts = watcher.await.unwrap();
+        assert_eq!(alerts.len(), 1, "{:?}", alerts);
+        assert_eq!(alerts[0].bottleneck_type, BottleneckType::Thermal);
+        assert!(alerts[0].severity >= 80);
+    }
+
+    #[test]
+    fn test_cleared_condition_realerts_after_cooldown() {
+        let start = Utc::now();
+        let mut watcher = AlertWatcher::new(AlertConfig {
+            min_severity: 80,
+            cooldown: Duration::seconds(60),
+        });
+        let mut alerts_at = |watcher: &mut AlertWatcher, seconds: i64, value: f64| {
+            let now = start + Duration::seconds(seconds);
+            watcher.push(vec![temperature(value, now)]);
+            watcher.evaluate(now).len()
+        };
+
+        assert_eq!(alerts_at(&mut watcher, 0, 97.0), 1);
+        assert_eq!(alerts_at(&mut watcher, 5, 97.0), 0);
+        // Cleared once the hot samples leave the window, then back within the cooldown
+        assert_eq!(alerts_at(&mut watcher, 40, 60.0), 0);
+        assert_eq!(alerts_at(&mut watcher, 45, 97.0), 0);
+        assert_eq!(alerts_at(&mut watcher, 80, 60.0), 0);
+        assert_eq!(alerts_at(&mut watcher, 120, 97.0), 1);
+    }
+
+    #[test]
+    fn test_below_min_severity_is_ignored() {
+        let now = Utc::now();
+        let mut watcher = AlertWatcher::new(AlertConfig::default());
+        // Warm but below the throttle point
+        watcher.push(vec![temperature(84.0, now - Duration::seconds(1)), temperature(85.0, now)]);
+        assert!(watcher.evaluate(now).is_empty());
+    }
+}
@@ -210,7 +210,7 @@ mod tests {
 
     #[test]
     fn test_workload_profile_threshold_overrides() {
-        use stats_io_lib::core::domain::ThresholdOverrides;
+        use stats_io_lib::core::domain::{ThresholdOverrides, ThresholdStatistic};
         
         let mut metrics = create_cpu_bound_metrics();
         // Lower CPU values to test threshold override
@@ -230,6 +230,9 @@ mod tests {
                 gpu_high: None,
                 ram_high: None,
                 vram_high: None,
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
             }),
         };
         
@@ -0,0 +1,152 @@
+//! Unit tests for bottleneck onset detection
+//!
+//! Tests when a sustained threshold violation first began following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::{bottleneck_onset, ONSET_MIN_SAMPLES};
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckType, EvidenceItem, MetricSample, MetricType, Run, Session,
+        WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::sessions::analyze_run;
+    use stats_io_lib::persistence::reports::{generate_session_report, ReportConfig, ReportFormat};
+    use chrono::{DateTime, Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn rendering_profile() -> WorkloadProfile {
+        WorkloadProfile {
+            id: "rendering".to_string(),
+            name: "Rendering".to_string(),
+            workload_type: WorkloadType::Rendering,
+            parameters: HashMap::new(),
+            threshold_overrides: None,
+        }
+    }
+
+    /// One CPU sample per second: 80% for `calm_seconds`, then 100%
+    fn create_samples(start: DateTime<Utc>, calm_seconds: i64, total_seconds: i64) -> Vec<MetricSample> {
+        (0..total_seconds)
+            .map(|i| {
+                let value = if i < calm_seconds { 80.0 } else { 100.0 };
+                MetricSample::new(MetricType::CpuUtilization, value, "CPU")
+                    .with_timestamp(start + Duration::seconds(i))
+            })
+            .collect()
+    }
+
+    fn create_run(samples: Vec<MetricSample>) -> Run {
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("cpu".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Boss fight".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn create_bottleneck(metric_type: MetricType, threshold: f64, actual_value: f64) -> Bottleneck {
+        Bottleneck {
+            bottleneck_type: BottleneckType::Cpu,
+            severity: 60,
+            evidence: vec![EvidenceItem {
+                metric_type,
+                threshold,
+                actual_value,
+                time_range_start: Utc::now(),
+                time_range_end: Utc::now(),
+                source_component: None,
+            }],
+            summary: "Bottleneck".to_string(),
+            details: String::new(),
+            onset: None,
+            active_fraction: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_onset_twenty_seconds_into_run() {
+        let start = Utc::now() - Duration::minutes(5);
+        let mut run = create_run(create_samples(start, 20, 120));
+
+        let result = analyze_run(&AnalysisEngine::new(), &run, &rendering_profile(), None).unwrap();
+        let cpu = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Cpu)
+            .expect("CPU bottleneck");
+        assert_eq!(cpu.onset, Some(start + Duration::seconds(20)));
+
+        run.analysis_result = Some(result);
+        let session = Session {
+            id: Uuid::new_v4(),
+            start_time: start,
+            end_time: None,
+            hardware_config_snapshot: serde_json::from_value(serde_json::json!({
+                "cpu": {"model": "Test CPU", "vendor": "Test", "cores": 8, "threads": 16},
+                "gpus": [],
+                "memory": {"total_mb": 16384, "modules": []},
+                "storage_devices": [],
+                "displays": [],
+                "metadata": {"detection_time": Utc::now(), "platform": "Test", "warnings": [], "schema_version": 1}
+            }))
+            .unwrap(),
+            profile: rendering_profile(),
+            runs: vec![run],
+        };
+        let config = ReportConfig {
+            format: ReportFormat::Text,
+            ..ReportConfig::default()
+        };
+        let report = generate_session_report(&session, &session.hardware_config_snapshot, &config);
+        assert!(report.contains("Onset: 20s into the run"));
+    }
+
+    #[test]
+    fn test_brief_spikes_do_not_set_onset() {
+        let start = Utc::now();
+        let mut samples = create_samples(start, 60, 60);
+        // Isolated spikes shorter than the onset run
+        for i in [5, 15, 25] {
+            samples[i].value = 100.0;
+        }
+        for sample in samples.iter_mut().skip(40).take(ONSET_MIN_SAMPLES) {
+            sample.value = 95.0;
+        }
+
+        let onset = bottleneck_onset(&create_bottleneck(MetricType::CpuUtilization, 85.0, 90.0), &samples);
+        assert_eq!(onset, Some(start + Duration::seconds(40)));
+    }
+
+    #[test]
+    fn test_floor_threshold_onset() {
+        // FPS dropping below a 60 FPS target from second 10
+        let start = Utc::now();
+        let samples: Vec<MetricSample> = (0..30)
+            .map(|i| {
+                let value = if i < 10 { 90.0 } else { 45.0 };
+                MetricSample::new(MetricType::Fps, value, "Game").with_timestamp(start + Duration::seconds(i))
+            })
+            .collect();
+
+        let onset = bottleneck_onset(&create_bottleneck(MetricType::Fps, 60.0, 52.0), &samples);
+        assert_eq!(onset, Some(start + Duration::seconds(10)));
+
+        // No samples of the evidence metric
+        assert_eq!(bottleneck_onset(&create_bottleneck(MetricType::GpuUtilization, 90.0, 95.0), &samples), None);
+    }
+
+    #[test]
+    fn test_legacy_bottleneck_without_onset() {
+        let legacy: Bottleneck = serde_json::from_str(
+            r#"{"bottleneck_type": "cpu", "severity": 70, "evidence": [], "summary": "CPU-bound", "details": ""}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.onset, None);
+    }
+}
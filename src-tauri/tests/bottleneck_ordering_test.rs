@@ -0,0 +1,105 @@
+//! Unit tests for bottleneck ordering
+//!
+//! Tests that analysis output is sorted deterministically following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::comparison::compare_runs;
+    use stats_io_lib::analysis::rules::{
+        analyze_bottlenecks_with_rules, sort_bottlenecks, AnalysisContext, BottleneckRule,
+    };
+    use stats_io_lib::core::domain::{Bottleneck, BottleneckType, MetricSample, MetricType, Run};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn bottleneck(bottleneck_type: BottleneckType, severity: u8, summary: &str) -> Bottleneck {
+        Bottleneck {
+            bottleneck_type,
+            severity,
+            evidence: vec![],
+            summary: summary.to_string(),
+            details: String::new(),
+            onset: None,
+            active_fraction: 0.0,
+        }
+    }
+
+    /// Rule reporting a fixed set of bottlenecks
+    struct FixedRule(Vec<Bottleneck>);
+
+    impl BottleneckRule for FixedRule {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn detect(&self, _metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+            self.0.first().cloned()
+        }
+
+        fn detect_all(&self, _metrics: &[MetricSample], _ctx: &AnalysisContext) -> Vec<Bottleneck> {
+            self.0.clone()
+        }
+    }
+
+    fn summaries(bottlenecks: &[Bottleneck]) -> Vec<&str> {
+        bottlenecks.iter().map(|b| b.summary.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rule_output_sorted_by_severity_then_type() {
+        let rules: Vec<Box<dyn BottleneckRule>> = vec![
+            Box::new(FixedRule(vec![
+                bottleneck(BottleneckType::Thermal, 40, "thermal"),
+                bottleneck(BottleneckType::Gpu, 70, "gpu"),
+            ])),
+            Box::new(FixedRule(vec![
+                bottleneck(BottleneckType::Vram, 70, "vram"),
+                bottleneck(BottleneckType::Cpu, 70, "cpu"),
+                bottleneck(BottleneckType::Storage, 95, "storage"),
+            ])),
+        ];
+        let samples = vec![MetricSample::new(MetricType::CpuUtilization, 50.0, "CPU")];
+
+        let result = analyze_bottlenecks_with_rules(&samples, 30, None, &rules);
+        assert_eq!(summaries(&result.bottlenecks), vec!["storage", "cpu", "gpu", "vram", "thermal"]);
+
+        // Reversing the rule output gives the same order
+        let mut reversed: Vec<Bottleneck> = result.bottlenecks.iter().rev().cloned().collect();
+        sort_bottlenecks(&mut reversed);
+        assert_eq!(summaries(&reversed), summaries(&result.bottlenecks));
+    }
+
+    #[test]
+    fn test_comparison_follows_bottleneck_order() {
+        let run = |bottlenecks: Vec<Bottleneck>| Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams: HashMap::new(),
+            analysis_result: Some(stats_io_lib::core::domain::BottleneckAnalysisResult {
+                bottlenecks,
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: None,
+            environment: None,
+        };
+        let run1 = run(vec![
+            bottleneck(BottleneckType::Thermal, 60, "thermal"),
+            bottleneck(BottleneckType::Ram, 50, "ram"),
+        ]);
+        let run2 = run(vec![
+            bottleneck(BottleneckType::Gpu, 90, "gpu"),
+            bottleneck(BottleneckType::Thermal, 30, "thermal"),
+        ]);
+
+        for _ in 0..5 {
+            let changes: Vec<String> = compare_runs(&run1, &run2)
+                .bottleneck_changes
+                .into_iter()
+                .map(|c| c.bottleneck_type)
+                .collect();
+            assert_eq!(changes, vec!["Gpu", "Thermal", "Ram"]);
+        }
+    }
+}
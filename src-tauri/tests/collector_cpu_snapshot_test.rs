@@ -0,0 +1,138 @@
+//! Unit tests for the collector's CPU reading
+//!
+//! Tests that each tick reads the CPU provider once following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use stats_io_lib::core::domain::MetricType;
+    use stats_io_lib::core::error::MetricsError;
+    use stats_io_lib::core::interfaces::{
+        CpuMetricsProvider, GpuMetricsProvider, MemoryMetricsProvider, SensorProvider,
+        StorageMetricsProvider,
+    };
+    use stats_io_lib::metrics::collector::cpu_samples;
+    use stats_io_lib::metrics::models::{CpuMetrics, GpuMetrics, MemoryMetrics, SensorReading, StorageMetrics};
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+    use chrono::Utc;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// CPU provider counting its calls; each reading differs from the last
+    #[derive(Default)]
+    struct CountingCpuProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CpuMetricsProvider for CountingCpuProvider {
+        async fn get_cpu_metrics(&self) -> Result<CpuMetrics, MetricsError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) as f64;
+            Ok(CpuMetrics {
+                overall_utilization: 0.5,
+                per_core_utilization: vec![0.4, 0.6],
+                temperature: Some(60.0 + call),
+                fan_speed_percent: Some(40.0),
+                fan_speed_rpm: None,
+                clock_mhz: Some(4000.0 + call),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl GpuMetricsProvider for CountingCpuProvider {
+        async fn get_gpu_metrics(&self) -> Result<GpuMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("GPU".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl MemoryMetricsProvider for CountingCpuProvider {
+        async fn get_memory_metrics(&self) -> Result<MemoryMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("Memory".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl StorageMetricsProvider for CountingCpuProvider {
+        async fn get_storage_metrics(&self) -> Result<StorageMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("Storage".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl SensorProvider for CountingCpuProvider {
+        async fn get_sensors(&self) -> Result<Vec<SensorReading>, MetricsError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cpu_provider_read_once_per_tick() {
+        let provider = Arc::new(CountingCpuProvider::default());
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms: 20,
+            ..MetricsCollectorConfig::default()
+        };
+        let collector = MetricsCollector::with_providers(
+            config,
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+        );
+
+        collector.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        collector.stop().await;
+        // Let an in-flight tick finish
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let buffer = collector.get_buffer().await;
+        let ticks: HashSet<_> = buffer
+            .iter()
+            .filter(|s| s.metric_type == MetricType::CpuUtilization)
+            .map(|s| s.timestamp)
+            .collect();
+        assert!(ticks.len() >= 3, "only {} ticks", ticks.len());
+        assert_eq!(provider.calls.load(Ordering::SeqCst), ticks.len());
+
+        // Temperature and clock of a tick come from the same reading
+        for tick in &ticks {
+            let value = |metric_type: MetricType| {
+                buffer
+                    .iter()
+                    .find(|s| s.timestamp == *tick && s.metric_type == metric_type)
+                    .map(|s| s.value)
+                    .unwrap()
+            };
+            assert_eq!(value(MetricType::Temperature) - 60.0, value(MetricType::CpuClock) - 4000.0);
+        }
+    }
+
+    #[test]
+    fn test_cpu_samples_share_timestamp() {
+        let reading = CpuMetrics {
+            overall_utilization: 0.75,
+            per_core_utilization: vec![0.5, 1.0],
+            temperature: Some(72.0),
+            fan_speed_percent: None,
+            fan_speed_rpm: Some(1800.0),
+            clock_mhz: None,
+        };
+        let timestamp = Utc::now();
+        let samples = cpu_samples(&reading, timestamp);
+
+        assert_eq!(samples.len(), 5);
+        assert!(samples.iter().all(|s| s.timestamp == timestamp));
+        assert_eq!(samples[0].metric_type, MetricType::CpuUtilization);
+        assert_eq!(samples[0].value, 75.0);
+        let temperature = samples.iter().find(|s| s.metric_type == MetricType::Temperature).unwrap();
+        assert_eq!(temperature.value, 72.0);
+        let fan = samples.iter().find(|s| s.metric_type == MetricType::FanSpeed).unwrap();
+        assert_eq!(fan.unit, "RPM");
+    }
+}
@@ -0,0 +1,202 @@
+//! Unit tests for collector reconfiguration
+//!
+//! Tests that a live collector adopts new sampling settings following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use stats_io_lib::core::domain::{MetricSample, MetricType};
+    use stats_io_lib::core::error::MetricsError;
+    use stats_io_lib::core::interfaces::{
+        CpuMetricsProvider, GpuMetricsProvider, MemoryMetricsProvider, SensorProvider,
+        StorageMetricsProvider,
+    };
+    use stats_io_lib::metrics::models::{CpuMetrics, GpuMetrics, MemoryMetrics, SensorReading, StorageMetrics};
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Provider reporting only a fixed CPU load
+    struct CpuOnlyProvider;
+
+    #[async_trait]
+    impl CpuMetricsProvider for CpuOnlyProvider {
+        async fn get_cpu_metrics(&self) -> Result<CpuMetrics, MetricsError> {
+            Ok(CpuMetrics {
+                overall_utilization: 0.5,
+                per_core_utilization: vec![],
+                temperature: None,
+                fan_speed_percent: None,
+                fan_speed_rpm: None,
+                clock_mhz: None,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl GpuMetricsProvider for CpuOnlyProvider {
+        async fn get_gpu_metrics(&self) -> Result<GpuMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("GPU".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl MemoryMetricsProvider for CpuOnlyProvider {
+        async fn get_memory_metrics(&self) -> Result<MemoryMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("Memory".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl StorageMetricsProvider for CpuOnlyProvider {
+        async fn get_storage_metrics(&self) -> Result<StorageMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("Storage".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl SensorProvider for CpuOnlyProvider {
+        async fn get_sensors(&self) -> Result<Vec<SensorReading>, MetricsError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn create_collector(sampling_interval_ms: u64, buffer_size: usize) -> MetricsCollector {
+        let provider = Arc::new(CpuOnlyProvider);
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms,
+            buffer_size,
+            ..MetricsCollectorConfig::default()
+        };
+        MetricsCollector::with_providers(
+            config,
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider,
+        )
+    }
+
+    async fn cpu_sample_count(collector: &MetricsCollector) -> usize {
+        collector
+            .get_buffer()
+            .await
+            .iter()
+            .filter(|s| s.metric_type == MetricType::CpuUtilization)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_running_collector_adopts_new_interval() {
+        let collector = create_collector(10_000, 600);
+        collector.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        // Only the immediate first tick has run
+        assert_eq!(cpu_sample_count(&collector).await, 1);
+
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms: 20,
+            ..collector.config().await
+        };
+        collector.reconfigure(config).await.unwrap();
+        assert!(collector.is_running().await);
+        assert_eq!(collector.config().await.sampling_interval_ms, 20);
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        collector.stop().await;
+
+        // The sample taken before the change is kept
+        let count = cpu_sample_count(&collector).await;
+        assert!(count >= 6, "only {} samples after switching to 20 ms", count);
+    }
+
+    #[tokio::test]
+    async fn test_stopped_collector_stays_stopped_and_buffer_shrinks() {
+        let collector = create_collector(1000, 10);
+        let samples: Vec<MetricSample> = (0..8)
+            .map(|i| MetricSample::new(MetricType::CpuUtilization, i as f64, "CPU"))
+            .collect();
+        collector.record_samples(&samples).await;
+
+        let config = MetricsCollectorConfig {
+            buffer_size: 5,
+            ..collector.config().await
+        };
+        collector.reconfigure(config).await.unwrap();
+        assert!(!collector.is_running().await);
+
+        let buffer = collector.get_buffer().await;
+        assert_eq!(buffer.iter().map(|s| s.value).collect::<Vec<_>>(), vec![3.0, 4.0, 5.0, 6.0, 7.0]);
+        let health = collector.health().await;
+        assert_eq!(health.buffer_size, 5);
+        assert_eq!(health.buffered_samples, 5);
+        assert_eq!(health.evicted_samples, 3);
+    }
+
+    #[tokio::test]
+    async fn test_reconfigure_keeps_live_percentiles_and_health_counters() {
+        let collector = create_collector(10_000, 600);
+        collector.start().await.unwrap();
+        let samples = vec![
+            MetricSample::new(MetricType::GpuUtilization, 40.0, "GPU"),
+            MetricSample::new(MetricType::GpuUtilization, 60.0, "GPU"),
+            MetricSample {
+                unit: "percent".to_string(),
+                ..MetricSample::new(MetricType::GpuUtilization, 120.0, "GPU")
+            },
+            MetricSample {
+                unit: "percent".to_string(),
+                ..MetricSample::new(MetricType::GpuUtilization, -5.0, "GPU")
+            },
+        ];
+        collector.record_samples(&samples).await;
+        let before = collector.live_percentile(&MetricType::GpuUtilization, 100.0).await;
+        assert!(before.is_some());
+
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms: 20_000,
+            ..collector.config().await
+        };
+        collector.reconfigure(config).await.unwrap();
+        assert!(collector.is_running().await);
+
+        // Only a restart through start() begins a new collection
+        assert_eq!(collector.live_percentile(&MetricType::GpuUtilization, 100.0).await, before);
+        let health = collector.health().await;
+        assert_eq!(health.clamped_samples, 1);
+        assert_eq!(health.rejected_samples, 1);
+
+        collector.stop().await;
+        collector.start().await.unwrap();
+        assert!(collector.live_percentile(&MetricType::GpuUtilization, 100.0).await.is_none());
+        assert_eq!(collector.health().await.clamped_samples, 0);
+        collector.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reconfigurations_leave_one_loop_running() {
+        let collector = Arc::new(create_collector(1000, 600));
+        collector.start().await.unwrap();
+
+        let tasks: Vec<_> = (1..=4u64)
+            .map(|i| {
+                let collector = collector.clone();
+                tokio::spawn(async move {
+                    let config = MetricsCollectorConfig {
+                        sampling_interval_ms: 10_000 * i,
+                        ..collector.config().await
+                    };
+                    collector.reconfigure(config).await
+                })
+            })
+            .collect();
+        // None of the restarts failed because another had already started the loop
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+        assert!(collector.is_running().await);
+        assert!(collector.config().await.sampling_interval_ms >= 10_000);
+        collector.stop().await;
+    }
+}
@@ -0,0 +1,61 @@
+//! Unit tests for combined pressure detection
+//!
+//! Tests balanced high load across several subsystems following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::{analyze_bottlenecks, detect_combined_pressure};
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, ThresholdOverrides, ThresholdStatistic,
+    };
+    use stats_io_lib::test_support::MetricStreamBuilder;
+
+    /// CPU, GPU and RAM held at the given values for 20 seconds
+    fn create_samples(cpu: f64, gpu: f64, ram: f64) -> Vec<MetricSample> {
+        MetricStreamBuilder::new().cpu(cpu).gpu(gpu).ram(ram).over_seconds(20).build()
+    }
+
+    #[test]
+    fn test_three_subsystems_below_threshold_trigger() {
+        // Defaults: CPU 85%, GPU 90%, RAM 90%
+        let samples = create_samples(82.0, 87.0, 86.0);
+        let result = analyze_bottlenecks(&samples, 30, None);
+
+        assert_eq!(result.bottlenecks.len(), 1, "{:?}", result.bottlenecks);
+        let combined = &result.bottlenecks[0];
+        assert_eq!(combined.bottleneck_type, BottleneckType::CombinedLoad);
+        assert!(combined.severity >= 30 && combined.severity <= 50);
+        assert_eq!(combined.evidence.len(), 3);
+        assert!(combined.details.contains("CPU at 82%"));
+    }
+
+    #[test]
+    fn test_not_raised_when_one_subsystem_exceeds_or_too_few_are_near() {
+        // The GPU over its threshold is reported on its own
+        let result = analyze_bottlenecks(&create_samples(78.0, 95.0, 86.0), 30, None);
+        assert!(result.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Gpu));
+        assert!(result.bottlenecks.iter().all(|b| b.bottleneck_type != BottleneckType::CombinedLoad));
+
+        // Only two subsystems near their thresholds
+        assert!(detect_combined_pressure(&create_samples(82.0, 87.0, 60.0), None).is_none());
+    }
+
+    #[test]
+    fn test_fraction_is_configurable() {
+        let samples = create_samples(72.0, 77.0, 77.0);
+        assert!(detect_combined_pressure(&samples, None).is_none());
+
+        let thresholds = ThresholdOverrides {
+            cpu_high: None,
+            gpu_high: None,
+            ram_high: None,
+            vram_high: None,
+            statistic: ThresholdStatistic::Mean,
+            combined_pressure: Some(0.8),
+            sustained_seconds: None,
+        };
+        let combined = detect_combined_pressure(&samples, Some(&thresholds)).expect("combined pressure");
+        assert_eq!(combined.bottleneck_type, BottleneckType::CombinedLoad);
+        assert_eq!(combined.evidence[0].threshold, 85.0 * 0.8);
+    }
+}
@@ -0,0 +1,87 @@
+//! Unit tests for command error mapping
+//!
+//! Tests that internal errors map to structured command errors following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::error::{
+        AppError, CommandError, HardwareError, MetricsError, PersistenceError, SessionError,
+    };
+    use stats_io_lib::core::settings::SettingsError;
+
+    #[test]
+    fn test_hardware_error_codes() {
+        let error: CommandError = HardwareError::SensorNotAvailable("GPU temperature".to_string()).into();
+
+        assert_eq!(error.kind(), "hardware");
+        assert_eq!(error.code(), "HARDWARE_SENSOR_UNAVAILABLE");
+        assert_eq!(error.message(), "Sensor not available: GPU temperature");
+
+        let error: CommandError = HardwareError::UnsupportedPlatform("plan9".to_string()).into();
+        assert_eq!(error.code(), "HARDWARE_UNSUPPORTED_PLATFORM");
+    }
+
+    #[test]
+    fn test_metrics_error_codes() {
+        let error: CommandError = MetricsError::ProviderNotAvailable("nvidia-smi not found".to_string()).into();
+
+        assert_eq!(error.kind(), "metrics");
+        assert_eq!(error.code(), "METRICS_PROVIDER_UNAVAILABLE");
+        assert!(error.message().contains("nvidia-smi not found"));
+    }
+
+    #[test]
+    fn test_persistence_io_error_codes() {
+        let not_found = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let error: CommandError = PersistenceError::Io(not_found).into();
+        assert_eq!(error.code(), "PERSISTENCE_NOT_FOUND");
+
+        #[cfg(windows)]
+        let disk_full = std::io::Error::from_raw_os_error(112);
+        #[cfg(not(windows))]
+        let disk_full = std::io::Error::from_raw_os_error(28);
+        let error: CommandError = PersistenceError::Io(disk_full).into();
+        assert_eq!(error.kind(), "persistence");
+        assert_eq!(error.code(), "PERSISTENCE_DISK_FULL");
+
+        let error: CommandError = PersistenceError::SchemaVersionMismatch { expected: 1, found: 2 }.into();
+        assert_eq!(error.code(), "PERSISTENCE_SCHEMA_MISMATCH");
+    }
+
+    #[test]
+    fn test_session_and_settings_error_codes() {
+        let error: CommandError = SessionError::RunNotFound("abc".to_string()).into();
+        assert_eq!(error.kind(), "session");
+        assert_eq!(error.code(), "SESSION_RUN_NOT_FOUND");
+
+        let error: CommandError = SettingsError::DeserializationError("bad json".to_string()).into();
+        assert_eq!(error.kind(), "settings");
+        assert_eq!(error.code(), "SETTINGS_DESERIALIZATION");
+    }
+
+    #[test]
+    fn test_app_error_delegates_to_inner_error() {
+        let app_error: AppError = PersistenceError::FileNotFound("session.json".to_string()).into();
+        let error: CommandError = app_error.into();
+
+        assert_eq!(error.kind(), "persistence");
+        assert_eq!(error.code(), "PERSISTENCE_FILE_NOT_FOUND");
+
+        let error: CommandError = AppError::Unknown("boom".to_string()).into();
+        assert_eq!(error.kind(), "internal");
+    }
+
+    #[test]
+    fn test_command_error_serialization() {
+        let error = CommandError::validation("INVALID_SESSION_ID", "Invalid session ID");
+
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(json["kind"], "validation");
+        assert_eq!(json["code"], "INVALID_SESSION_ID");
+        assert_eq!(json["message"], "Invalid session ID");
+
+        let parsed: CommandError = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, error);
+    }
+}
@@ -0,0 +1,82 @@
+//! Unit tests for noise-aware comparison significance
+//!
+//! Tests that run-to-run noise is not reported as a change following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::comparison::{
+        compare_runs, compare_runs_with_min_effect, DEFAULT_MIN_EFFECT_PERCENT,
+    };
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run};
+    use stats_io_lib::core::settings::ThresholdSettings;
+    use stats_io_lib::metrics::utils::std_dev;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    /// CPU utilization alternating `spread` above and below `average`
+    fn create_run(average: f64, spread: f64) -> Run {
+        let samples = (0..40)
+            .map(|i| {
+                let offset = if i % 2 == 0 { spread } else { -spread };
+                MetricSample::new(MetricType::CpuUtilization, average + offset, "CPU")
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("cpu".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    #[test]
+    fn test_six_percent_within_noise_is_not_flagged() {
+        let result = compare_runs(&create_run(50.0, 8.0), &create_run(53.0, 8.0));
+        let delta = &result.metric_deltas["CpuUtilization"];
+
+        assert!((delta.delta_percent - 6.0).abs() < 1e-9);
+        assert!(delta.run1_stddev > 8.0);
+        assert!(!delta.significant);
+        assert_eq!(result.summary, "No significant changes detected between runs.");
+    }
+
+    #[test]
+    fn test_clean_six_percent_is_flagged() {
+        let result = compare_runs(&create_run(50.0, 0.5), &create_run(53.0, 0.5));
+        let delta = &result.metric_deltas["CpuUtilization"];
+
+        assert!((delta.delta_percent - 6.0).abs() < 1e-9);
+        assert!(delta.significant);
+        assert_eq!(result.summary, "1 metric(s) changed significantly");
+    }
+
+    #[test]
+    fn test_noisier_run_sets_the_bar() {
+        // Clean baseline, noisy second run
+        let result = compare_runs(&create_run(50.0, 0.5), &create_run(53.0, 8.0));
+        assert!(!result.metric_deltas["CpuUtilization"].significant);
+    }
+
+    #[test]
+    fn test_minimum_effect_size_is_configurable() {
+        let run1 = create_run(50.0, 0.1);
+        let run2 = create_run(51.5, 0.1);
+
+        // A clean 3% change is below the default minimum effect
+        assert!(!compare_runs(&run1, &run2).metric_deltas["CpuUtilization"].significant);
+        assert!(compare_runs_with_min_effect(&run1, &run2, 2.0).metric_deltas["CpuUtilization"].significant);
+
+        assert_eq!(ThresholdSettings::default().min_comparison_effect_percent, DEFAULT_MIN_EFFECT_PERCENT);
+    }
+
+    #[test]
+    fn test_std_dev() {
+        assert_eq!(std_dev(&[1.0]), None);
+        assert_eq!(std_dev(&[2.0, 2.0, 2.0]), Some(0.0));
+        assert!((std_dev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap() - 2.138).abs() < 1e-3);
+    }
+}
@@ -4,10 +4,12 @@
 
 #[cfg(test)]
 mod tests {
-    use stats_io_lib::analysis::comparison::{compare_runs, BottleneckStatus};
+    use stats_io_lib::analysis::comparison::{
+        compare_runs, compare_to_previous_run, BottleneckStatus, DEFAULT_MIN_EFFECT_PERCENT,
+    };
     use stats_io_lib::core::domain::{
         Bottleneck, BottleneckAnalysisResult, BottleneckType, MetricSample,
-        MetricType, Run,
+        MetricType, Run, Session, WorkloadProfile, WorkloadType,
     };
     use std::collections::HashMap;
 
@@ -58,8 +60,10 @@ mod tests {
             analysis_result: Some(BottleneckAnalysisResult {
                 bottlenecks,
                 timestamp: chrono::Utc::now(),
+                coverage: None,
             }),
             notes: None,
+            environment: None,
         }
     }
 
@@ -77,6 +81,8 @@ mod tests {
                 evidence: vec![],
                 summary: "CPU-bound".to_string(),
                 details: "High CPU usage".to_string(),
+                onset: None,
+                active_fraction: 0.0,
             }],
         );
 
@@ -92,6 +98,8 @@ mod tests {
                 evidence: vec![],
                 summary: "GPU-bound".to_string(),
                 details: "High GPU usage".to_string(),
+                onset: None,
+                active_fraction: 0.0,
             }],
         );
 
@@ -117,6 +125,8 @@ mod tests {
                 evidence: vec![],
                 summary: "CPU-bound".to_string(),
                 details: "High CPU usage".to_string(),
+                onset: None,
+                active_fraction: 0.0,
             }],
         );
 
@@ -174,5 +184,73 @@ mod tests {
         assert_eq!(delta.delta, 10.0);
         assert!((delta.delta_percent - 12.5).abs() < 0.1); // 10/80 * 100 = 12.5%
     }
-}
 
+    fn cpu_bottleneck(severity: u8) -> Bottleneck {
+        Bottleneck {
+            bottleneck_type: BottleneckType::Cpu,
+            severity,
+            evidence: vec![],
+            summary: "CPU-bound".to_string(),
+            details: "High CPU usage".to_string(),
+            onset: None,
+            active_fraction: 0.0,
+        }
+    }
+
+    fn create_session(runs: Vec<Run>) -> Session {
+        Session {
+            id: uuid::Uuid::new_v4(),
+            start_time: chrono::Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: serde_json::from_value(serde_json::json!({
+                "cpu": {"model": "Test CPU", "vendor": "Test", "cores": 8, "threads": 16},
+                "gpus": [],
+                "memory": {"total_mb": 16_384, "modules": []},
+                "storage_devices": [],
+                "displays": [],
+                "metadata": {"detection_time": chrono::Utc::now(), "platform": "Test", "warnings": [], "schema_version": 1}
+            }))
+            .unwrap(),
+            profile: WorkloadProfile {
+                id: "gaming".to_string(),
+                name: "Gaming".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs,
+        }
+    }
+
+    #[test]
+    fn test_compare_to_previous_run_uses_latest_two() {
+        let session = create_session(vec![
+            create_test_run("00000000-0000-0000-0000-000000000007", "Stock", 95.0, 50.0, 60.0, vec![cpu_bottleneck(90)]),
+            create_test_run("00000000-0000-0000-0000-000000000008", "Undervolt", 92.0, 50.0, 60.0, vec![cpu_bottleneck(80)]),
+            create_test_run("00000000-0000-0000-0000-000000000009", "Undervolt + PBO", 88.0, 50.0, 60.0, vec![cpu_bottleneck(60)]),
+        ]);
+
+        let result = compare_to_previous_run(&session, DEFAULT_MIN_EFFECT_PERCENT).unwrap();
+
+        assert_eq!(result.previous_run_name, "Undervolt");
+        assert_eq!(result.latest_run_name, "Undervolt + PBO");
+        assert_eq!(result.comparison.run1_id, session.runs[1].id.to_string());
+        assert_eq!(result.comparison.run2_id, session.runs[2].id.to_string());
+        assert_eq!(result.comparison.bottleneck_changes[0].severity_delta, -20);
+        assert_eq!(result.verdict, "Better than the previous run (bottleneck severity -20)");
+    }
+
+    #[test]
+    fn test_compare_to_previous_run_needs_two_runs() {
+        let single = create_session(vec![create_test_run(
+            "00000000-0000-0000-0000-00000000000a",
+            "Only",
+            50.0,
+            50.0,
+            50.0,
+            vec![],
+        )]);
+        assert!(compare_to_previous_run(&single, DEFAULT_MIN_EFFECT_PERCENT).is_none());
+        assert!(compare_to_previous_run(&create_session(vec![]), DEFAULT_MIN_EFFECT_PERCENT).is_none());
+    }
+}
@@ -0,0 +1,110 @@
+//! Unit tests for anonymized contribution export
+//!
+//! Tests that contributions keep aggregates and drop identifying content following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run, Session, WorkloadProfile, WorkloadType};
+    use stats_io_lib::core::error::PersistenceError;
+    use stats_io_lib::persistence::export_import::{
+        export_anonymized_contribution, validate_contribution, Contribution, CONTRIBUTION_FORMAT,
+    };
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    const NOTES: &str = "Tested at Alice's flat on the new 4K monitor";
+
+    fn create_session() -> Session {
+        let start = Utc::now();
+        let samples: Vec<MetricSample> = (0..10)
+            .map(|i| {
+                MetricSample::new(MetricType::GpuUtilization, 90.0 + i as f64, "NVIDIA GeForce RTX 4070 #0")
+                    .with_timestamp(start + Duration::seconds(i))
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("gpu".to_string(), samples);
+
+        Session {
+            id: Uuid::new_v4(),
+            start_time: start,
+            end_time: Some(start + Duration::seconds(10)),
+            hardware_config_snapshot: serde_json::from_value(serde_json::json!({
+                "cpu": {"model": "AMD Ryzen 7 7800X3D", "vendor": "AMD", "cores": 8, "threads": 16},
+                "gpus": [{"model": "NVIDIA GeForce RTX 4070", "vendor": "NVIDIA", "pci_id": "PCI\\VEN_10DE&DEV_2786&SUBSYS_51611028"}],
+                "memory": {"total_mb": 32_700, "modules": []},
+                "storage_devices": [],
+                "displays": [],
+                "metadata": {"detection_time": start, "platform": "Test", "warnings": [], "schema_version": 1}
+            }))
+            .unwrap(),
+            profile: WorkloadProfile {
+                id: "gaming".to_string(),
+                name: "My Cyberpunk Preset".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![Run {
+                id: Uuid::new_v4(),
+                name: "Night City drive".to_string(),
+                metrics_streams,
+                analysis_result: None,
+                notes: Some(NOTES.to_string()),
+                environment: None,
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_contribution_drops_identifying_content() {
+        let session = create_session();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("contribution.json");
+
+        let contribution = export_anonymized_contribution(&session, &path).await.unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+
+        assert!(!json.contains(NOTES));
+        assert!(!json.contains(&session.id.to_string()));
+        assert!(!json.contains(&session.runs[0].id.to_string()));
+        assert!(!json.contains("Night City drive"));
+        assert!(!json.contains("My Cyberpunk Preset"));
+        assert!(!json.contains("VEN_10DE"));
+        assert!(!json.contains("#0"));
+        assert!(!json.contains(&session.start_time.format("%Y-%m-%d").to_string()));
+
+        assert_eq!(contribution.format, CONTRIBUTION_FORMAT);
+        assert_eq!(contribution.hardware.cpu_model, "AMD Ryzen 7 7800X3D");
+        assert_eq!(contribution.hardware.gpu_models, vec!["NVIDIA GeForce RTX 4070"]);
+        assert_eq!(contribution.hardware.memory_gb, 32);
+        assert_eq!(contribution.workload_type, WorkloadType::Gaming);
+        assert_eq!(contribution.run_count, 1);
+        let gpu = &contribution.metrics["GpuUtilization"];
+        assert_eq!(gpu.count, 10);
+        assert_eq!(gpu.max, 99.0);
+    }
+
+    #[test]
+    fn test_validation_rejects_leaks() {
+        let session = create_session();
+        let clean = serde_json::to_string(&Contribution::from_session(&session)).unwrap();
+        assert!(validate_contribution(&clean, &session).is_ok());
+
+        let leaking = [
+            format!("{{\"id\": \"{}\"}}", Uuid::new_v4()),
+            "{\"at\": \"2024-03-01T18:30:00Z\"}".to_string(),
+            format!("{{\"comment\": \"{}\"}}", NOTES),
+            "{\"gpu\": \"PCI\\\\VEN_10DE&DEV_2786&SUBSYS_51611028\"}".to_string(),
+        ];
+        for json in leaking {
+            assert!(
+                matches!(validate_contribution(&json, &session), Err(PersistenceError::PrivacyLeak(_))),
+                "{} should be rejected",
+                json
+            );
+        }
+    }
+}
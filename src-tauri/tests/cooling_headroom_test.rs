@@ -0,0 +1,145 @@
+//! Unit tests for cooling headroom analysis
+//!
+//! Tests fan-curve and cooling-capacity detection following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::advanced::{detect_cooling_headroom, GPU_EDGE_THROTTLE_TEMP};
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckAnalysisResult, BottleneckType, MetricSample, MetricType,
+    };
+    use chrono::{Duration, Utc};
+
+    fn create_thermal_metrics(
+        temp_type: MetricType,
+        component: &str,
+        temp: f64,
+        fan_percent: f64,
+    ) -> Vec<MetricSample> {
+        let mut metrics = Vec::new();
+        for i in 0..10 {
+            let timestamp = Utc::now() - Duration::seconds(i);
+            metrics.push(MetricSample {
+                timestamp,
+                metric_type: temp_type.clone(),
+                value: temp,
+                unit: "Celsius".to_string(),
+                source_component: component.to_string(),
+            });
+            metrics.push(MetricSample {
+                timestamp,
+                metric_type: MetricType::FanSpeed,
+                value: fan_percent,
+                unit: "percent".to_string(),
+                source_component: component.to_string(),
+            });
+        }
+        metrics
+    }
+
+    #[test]
+    fn test_high_temp_with_fan_headroom() {
+        let metrics = create_thermal_metrics(MetricType::GpuTemperature, "GPU", 84.0, 45.0);
+
+        let bottleneck = detect_cooling_headroom(&metrics).unwrap();
+
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Thermal);
+        assert!(bottleneck.summary.contains("fan curve"));
+        assert!(bottleneck
+            .evidence
+            .iter()
+            .any(|e| e.metric_type == MetricType::FanSpeed && e.actual_value == 45.0));
+    }
+
+    #[test]
+    fn test_high_temp_with_fans_at_max() {
+        let metrics = create_thermal_metrics(MetricType::Temperature, "CPU", 92.0, 100.0);
+
+        let bottleneck = detect_cooling_headroom(&metrics).unwrap();
+
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Thermal);
+        assert!(bottleneck.summary.contains("cooling capacity"));
+        assert!(bottleneck.severity >= 80);
+    }
+
+    #[test]
+    fn test_no_cooling_issue_when_cool_or_without_fan_data() {
+        let cool = create_thermal_metrics(MetricType::Temperature, "CPU", 55.0, 40.0);
+        assert!(detect_cooling_headroom(&cool).is_none());
+
+        let no_fans: Vec<MetricSample> = create_thermal_metrics(MetricType::Temperature, "CPU", 92.0, 40.0)
+            .into_iter()
+            .filter(|m| m.metric_type != MetricType::FanSpeed)
+            .collect();
+        assert!(detect_cooling_headroom(&no_fans).is_none());
+    }
+
+    #[test]
+    fn test_recommendations_differ_by_fan_state() {
+        let fan_curve = detect_cooling_headroom(
+            &create_thermal_metrics(MetricType::Temperature, "CPU", 88.0, 50.0),
+        ).unwrap();
+        let capacity = detect_cooling_headroom(
+            &create_thermal_metrics(MetricType::Temperature, "CPU", 88.0, 100.0),
+        ).unwrap();
+
+        let fan_curve_insights = generate_insights(
+            &BottleneckAnalysisResult { bottlenecks: vec![fan_curve], timestamp: Utc::now(), coverage: None },
+            None,
+        );
+        let capacity_insights = generate_insights(
+            &BottleneckAnalysisResult { bottlenecks: vec![capacity], timestamp: Utc::now(), coverage: None },
+            None,
+        );
+
+        assert!(fan_curve_insights.recommendations.iter().any(|r| r.text().contains("fan curve")));
+        assert!(capacity_insights.recommendations.iter().any(|r| r.text().contains("lacks capacity")));
+        assert_ne!(fan_curve_insights.recommendations, capacity_insights.recommendations);
+    }
+
+    #[test]
+    fn test_engine_reports_cooling_headroom_next_to_throttling() {
+        let metrics = create_thermal_metrics(MetricType::Temperature, "CPU", 88.0, 50.0);
+
+        let result = AnalysisEngine::new().analyze(&metrics, Some(60), None);
+
+        let thermal: Vec<_> = result
+            .bottlenecks
+            .iter()
+            .filter(|b| b.bottleneck_type == BottleneckType::Thermal)
+            .collect();
+        // The throttling verdict is kept and the fan-curve diagnosis added to it
+        assert_eq!(thermal.len(), 2);
+        assert!(thermal.iter().any(|b| b.summary.contains("fan curve")));
+        assert!(thermal.iter().any(|b| !b.summary.contains("fan curve")));
+    }
+
+    #[test]
+    fn test_brief_heat_is_not_a_cooling_issue() {
+        // Hot for two seconds of ten
+        let metrics: Vec<MetricSample> = create_thermal_metrics(MetricType::Temperature, "CPU", 92.0, 40.0)
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut m)| {
+                if m.metric_type == MetricType::Temperature && i >= 4 {
+                    m.value = 55.0;
+                }
+                m
+            })
+            .collect();
+        assert!(detect_cooling_headroom(&metrics).is_none());
+    }
+
+    #[test]
+    fn test_gpu_judged_against_edge_throttle_temperature() {
+        // Above the CPU warning level but below the GPU edge limit
+        let warm = create_thermal_metrics(MetricType::GpuTemperature, "GPU", GPU_EDGE_THROTTLE_TEMP - 3.0, 45.0);
+        assert!(detect_cooling_headroom(&warm).is_none());
+
+        let hot = create_thermal_metrics(MetricType::GpuTemperature, "GPU", GPU_EDGE_THROTTLE_TEMP, 45.0);
+        let bottleneck = detect_cooling_headroom(&hot).unwrap();
+        assert_eq!(bottleneck.evidence[0].threshold, GPU_EDGE_THROTTLE_TEMP);
+    }
+}
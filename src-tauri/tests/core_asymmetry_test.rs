@@ -0,0 +1,119 @@
+//! Unit tests for heterogeneous core loading analysis
+//!
+//! Tests P-core/E-core classification and asymmetric loading detection following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::{
+        classify_core_types, detect_heterogeneous_core_loading, CoreType, HeterogeneousCoreRule,
+    };
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckAnalysisResult, BottleneckType, CPUInfo, MetricSample, MetricType,
+    };
+    use chrono::{Duration, Utc};
+
+    /// 4 P-cores at 5.0 GHz followed by 4 E-cores at 3.8 GHz
+    fn hybrid_frequencies() -> Vec<f64> {
+        vec![5000.0, 5000.0, 5000.0, 5000.0, 3800.0, 3800.0, 3800.0, 3800.0]
+    }
+
+    fn hybrid_cpu() -> CPUInfo {
+        CPUInfo {
+            model: "Hybrid CPU".to_string(),
+            vendor: "Intel".to_string(),
+            cores: 8,
+            threads: 8,
+            base_clock_mhz: None,
+            boost_clock_mhz: None,
+            architecture: None,
+            core_max_frequencies_mhz: hybrid_frequencies(),
+        }
+    }
+
+    fn create_per_core_metrics(p_core_load: f64, e_core_load: f64) -> Vec<MetricSample> {
+        let mut metrics = Vec::new();
+        for i in 0..10 {
+            let timestamp = Utc::now() - Duration::seconds(i);
+            for core in 0..8 {
+                metrics.push(MetricSample {
+                    timestamp,
+                    metric_type: MetricType::CpuUtilizationPerCore,
+                    value: if core < 4 { p_core_load } else { e_core_load },
+                    unit: "%".to_string(),
+                    source_component: format!("CPU Core {}", core),
+                });
+            }
+        }
+        metrics
+    }
+
+    #[test]
+    fn test_classify_core_types() {
+        let types = classify_core_types(&hybrid_frequencies());
+
+        assert_eq!(&types[..4], &[CoreType::Performance; 4]);
+        assert_eq!(&types[4..], &[CoreType::Efficiency; 4]);
+
+        let uniform = classify_core_types(&[4200.0, 4200.0, 4150.0]);
+        assert!(uniform.iter().all(|t| *t == CoreType::Performance));
+        assert!(classify_core_types(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_detects_load_confined_to_e_cores() {
+        let core_types = classify_core_types(&hybrid_frequencies());
+        let metrics = create_per_core_metrics(5.0, 95.0);
+
+        let bottleneck = detect_heterogeneous_core_loading(&metrics, &core_types).unwrap();
+
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Cpu);
+        assert!(bottleneck.summary.contains("efficiency cores"));
+        assert!(bottleneck.evidence.iter().any(|e| e.actual_value == 95.0));
+        assert!(bottleneck.evidence.iter().any(|e| e.actual_value == 5.0));
+    }
+
+    #[test]
+    fn test_no_detection_for_balanced_or_uniform_cpus() {
+        let core_types = classify_core_types(&hybrid_frequencies());
+        let balanced = create_per_core_metrics(90.0, 90.0);
+        assert!(detect_heterogeneous_core_loading(&balanced, &core_types).is_none());
+
+        let uniform = classify_core_types(&[5000.0; 8]);
+        let e_core_heavy = create_per_core_metrics(5.0, 95.0);
+        assert!(detect_heterogeneous_core_loading(&e_core_heavy, &uniform).is_none());
+    }
+
+    #[test]
+    fn test_rule_only_built_for_hybrid_cpus() {
+        assert!(HeterogeneousCoreRule::from_cpu(&hybrid_cpu()).is_some());
+
+        let mut uniform = hybrid_cpu();
+        uniform.core_max_frequencies_mhz = vec![5000.0; 8];
+        assert!(HeterogeneousCoreRule::from_cpu(&uniform).is_none());
+
+        uniform.core_max_frequencies_mhz = vec![];
+        assert!(HeterogeneousCoreRule::from_cpu(&uniform).is_none());
+    }
+
+    #[test]
+    fn test_engine_with_rule_recommends_scheduler_fixes() {
+        let mut engine = AnalysisEngine::new();
+        engine.register_rule(Box::new(HeterogeneousCoreRule::from_cpu(&hybrid_cpu()).unwrap()));
+
+        let result = engine.analyze(&create_per_core_metrics(5.0, 95.0), Some(60), None);
+        let bottleneck = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.summary.contains("efficiency cores"))
+            .cloned()
+            .unwrap();
+
+        let insights = generate_insights(
+            &BottleneckAnalysisResult { bottlenecks: vec![bottleneck], timestamp: Utc::now(), coverage: None },
+            None,
+        );
+        assert!(insights.recommendations.iter().any(|r| r.text().contains("affinity")));
+    }
+}
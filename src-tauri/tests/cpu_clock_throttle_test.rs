@@ -0,0 +1,116 @@
+//! Unit tests for CPU clock throttling detection
+//!
+//! Tests power-limit and thermal clock throttling under load following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::advanced::{CPU_CLOCK_THROTTLE_RATIO, TEMP_CRITICAL_THRESHOLD};
+    use stats_io_lib::analysis::rules::{detect_cpu_clock_throttle, CpuClockThrottleRule};
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckType, CPUInfo, MetricSample, MetricType};
+    use chrono::{Duration, Utc};
+
+    fn create_cpu(base_clock_mhz: Option<f64>, boost_clock_mhz: Option<f64>) -> CPUInfo {
+        CPUInfo {
+            model: "Test CPU".to_string(),
+            vendor: "Test Vendor".to_string(),
+            architecture: Some("x86_64".to_string()),
+            cores: 8,
+            threads: 16,
+            base_clock_mhz,
+            boost_clock_mhz,
+            core_max_frequencies_mhz: vec![],
+        }
+    }
+
+    /// Utilization, clock, and temperature readings taken on the same ticks
+    fn create_metrics(utilization: f64, clock: f64, temperature: f64) -> Vec<MetricSample> {
+        let now = Utc::now();
+        (0..10)
+            .flat_map(|i| {
+                let timestamp = now - Duration::seconds(10 - i);
+                vec![
+                    MetricSample::new(MetricType::CpuUtilization, utilization, "CPU").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::CpuClock, clock, "CPU").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::Temperature, temperature, "CPU").with_timestamp(timestamp),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_low_clocks_at_moderate_temperature_are_power_limited() {
+        let cpu = create_cpu(Some(3600.0), Some(5000.0));
+        let bottleneck = detect_cpu_clock_throttle(&create_metrics(95.0, 1800.0, 62.0), &cpu)
+            .expect("clock throttling");
+
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Cpu);
+        assert_eq!(bottleneck.summary, "CPU clocks held below rated speed under load");
+        assert!(bottleneck.details.contains("not thermal throttling"));
+        assert!(bottleneck.details.contains("power plan"));
+
+        let clock = &bottleneck.evidence[0];
+        assert_eq!(clock.metric_type, MetricType::CpuClock);
+        assert_eq!(clock.threshold, 3600.0 * CPU_CLOCK_THROTTLE_RATIO);
+        assert_eq!(clock.actual_value, 1800.0);
+        assert_eq!(bottleneck.evidence[1].actual_value, 62.0);
+    }
+
+    #[test]
+    fn test_low_clocks_when_hot_are_thermal() {
+        let cpu = create_cpu(Some(3600.0), None);
+        let bottleneck = detect_cpu_clock_throttle(&create_metrics(95.0, 2200.0, 96.0), &cpu)
+            .expect("clock throttling");
+
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Thermal);
+        assert_eq!(bottleneck.summary, "CPU clocks throttled by temperature");
+        assert_eq!(bottleneck.evidence[1].threshold, TEMP_CRITICAL_THRESHOLD);
+    }
+
+    #[test]
+    fn test_idle_downclocking_is_not_flagged() {
+        let cpu = create_cpu(Some(3600.0), Some(5000.0));
+        assert!(detect_cpu_clock_throttle(&create_metrics(15.0, 800.0, 40.0), &cpu).is_none());
+    }
+
+    #[test]
+    fn test_clocks_near_rated_speed_are_not_flagged() {
+        let cpu = create_cpu(Some(3600.0), Some(5000.0));
+        assert!(detect_cpu_clock_throttle(&create_metrics(95.0, 4400.0, 70.0), &cpu).is_none());
+    }
+
+    #[test]
+    fn test_short_dips_are_not_sustained() {
+        let cpu = create_cpu(Some(3600.0), Some(5000.0));
+        let metrics: Vec<MetricSample> = create_metrics(95.0, 1800.0, 62.0).into_iter().take(9).collect();
+        assert!(detect_cpu_clock_throttle(&metrics, &cpu).is_none());
+    }
+
+    #[test]
+    fn test_boost_clock_used_without_base_clock() {
+        let cpu = create_cpu(None, Some(5000.0));
+        let bottleneck = detect_cpu_clock_throttle(&create_metrics(90.0, 3000.0, 65.0), &cpu)
+            .expect("clock throttling");
+        assert_eq!(bottleneck.evidence[0].threshold, 5000.0 * CPU_CLOCK_THROTTLE_RATIO);
+
+        assert!(CpuClockThrottleRule::from_cpu(&create_cpu(None, None)).is_none());
+    }
+
+    #[test]
+    fn test_engine_recommends_power_plan_change() {
+        let mut engine = AnalysisEngine::new();
+        engine.register_rule(Box::new(
+            CpuClockThrottleRule::from_cpu(&create_cpu(Some(3600.0), Some(5000.0))).unwrap(),
+        ));
+        let result = engine.analyze(&create_metrics(95.0, 1800.0, 62.0), Some(60), None);
+
+        assert!(result
+            .bottlenecks
+            .iter()
+            .any(|b| b.summary == "CPU clocks held below rated speed under load"));
+
+        let insights = generate_insights(&result, None);
+        assert!(insights.recommendations.iter().any(|r| r.text().contains("power plan")));
+    }
+}
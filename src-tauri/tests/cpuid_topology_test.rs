@@ -0,0 +1,57 @@
+//! Unit tests for the CPUID physical core fallback
+//!
+//! Tests core count preference and the CPUID topology on the host following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::hardware::cpuid::{cpuid_core_count, cpuid_threads_per_core, physical_core_count};
+
+    fn host_threads() -> u32 {
+        std::thread::available_parallelism().map(|n| n.get() as u32).unwrap_or(1)
+    }
+
+    #[test]
+    fn test_os_core_count_is_preferred() {
+        assert_eq!(physical_core_count(Some(8), 16), 8);
+    }
+
+    #[test]
+    fn test_implausible_os_count_falls_back() {
+        let cores = physical_core_count(Some(32), 16);
+        assert!((1..=16).contains(&cores));
+
+        let cores = physical_core_count(Some(0), 16);
+        assert!((1..=16).contains(&cores));
+    }
+
+    #[test]
+    fn test_fallback_never_exceeds_threads() {
+        let threads = host_threads();
+        let cores = physical_core_count(None, threads);
+
+        assert!(cores >= 1 && cores <= threads);
+        assert_eq!(threads % cores, 0);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn test_cpuid_reports_plausible_topology() {
+        // Hybrid CPUs are not derived from CPUID
+        let Some(threads_per_core) = cpuid_threads_per_core() else {
+            return;
+        };
+        assert!((1..=8).contains(&threads_per_core), "{} threads per core", threads_per_core);
+
+        let threads = threads_per_core * 4;
+        assert_eq!(cpuid_core_count(threads), Some(4));
+    }
+
+    #[test]
+    fn test_uneven_thread_count_is_not_derived() {
+        match cpuid_threads_per_core() {
+            Some(1) | None => {}
+            Some(threads_per_core) => assert_eq!(cpuid_core_count(threads_per_core + 1), None),
+        }
+        assert_eq!(cpuid_core_count(0), None);
+    }
+}
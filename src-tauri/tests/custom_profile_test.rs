@@ -0,0 +1,139 @@
+//! Unit tests for custom workload profiles
+//!
+//! Tests validating, persisting and analyzing with user-defined profiles following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, ThresholdOverrides, ThresholdStatistic,
+        WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::profiles::WorkloadProfiles;
+    use stats_io_lib::core::settings::{SettingsManager, UserSettings};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn custom_profile(cpu_high: f64) -> WorkloadProfile {
+        let mut parameters = HashMap::new();
+        parameters.insert("target_fps".to_string(), serde_json::Value::Number(144.into()));
+        WorkloadProfile {
+            id: "competitive_shooter".to_string(),
+            name: "Competitive Shooter".to_string(),
+            workload_type: WorkloadType::Gaming,
+            parameters,
+            threshold_overrides: Some(ThresholdOverrides {
+                cpu_high: Some(cpu_high),
+                gpu_high: Some(95.0),
+                ram_high: None,
+                vram_high: None,
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
+            }),
+        }
+    }
+
+    /// One minute of CPU steady at 80%
+    fn cpu_samples() -> Vec<MetricSample> {
+        let start = Utc::now() - Duration::seconds(60);
+        (0..60)
+            .flat_map(|i| {
+                let timestamp = start + Duration::seconds(i);
+                [
+                    MetricSample::new(MetricType::CpuUtilization, 80.0, "Test").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::GpuUtilization, 50.0, "Test").with_timestamp(timestamp),
+                ]
+            })
+            .collect()
+    }
+
+    fn finds_cpu_bottleneck(profile: &WorkloadProfile) -> bool {
+        AnalysisEngine::new()
+            .analyze_recorded(&cpu_samples(), Some(60), Some(profile))
+            .bottlenecks
+            .iter()
+            .any(|b| b.bottleneck_type == BottleneckType::Cpu)
+    }
+
+    #[test]
+    fn test_custom_profile_persists_and_resolves() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+
+        let mut manager = SettingsManager::new(settings_path.clone()).unwrap();
+        manager.save_custom_profile(custom_profile(70.0)).unwrap();
+
+        let reloaded = SettingsManager::new(settings_path.clone()).unwrap();
+        let custom = &reloaded.get_settings().custom_profiles;
+        let resolved = WorkloadProfiles::resolve("competitive_shooter", custom).unwrap();
+        assert_eq!(resolved.name, "Competitive Shooter");
+        assert_eq!(resolved.parameters["target_fps"], serde_json::json!(144));
+        assert_eq!(resolved.threshold_overrides.unwrap().cpu_high, Some(70.0));
+
+        // Presets still resolve alongside custom profiles
+        assert!(WorkloadProfiles::resolve("gaming_1080p_60fps", custom).is_some());
+        assert!(WorkloadProfiles::resolve("missing", custom).is_none());
+    }
+
+    #[test]
+    fn test_custom_profile_update_and_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+
+        let mut manager = SettingsManager::new(settings_path.clone()).unwrap();
+        manager.save_custom_profile(custom_profile(70.0)).unwrap();
+        manager.save_custom_profile(custom_profile(75.0)).unwrap();
+        assert_eq!(manager.get_settings().custom_profiles.len(), 1);
+
+        assert!(manager.delete_custom_profile("competitive_shooter").unwrap());
+        assert!(!manager.delete_custom_profile("competitive_shooter").unwrap());
+
+        let reloaded = SettingsManager::new(settings_path).unwrap();
+        let custom = &reloaded.get_settings().custom_profiles;
+        assert!(custom.is_empty());
+        assert!(WorkloadProfiles::resolve("competitive_shooter", custom).is_none());
+    }
+
+    #[test]
+    fn test_custom_profile_thresholds_drive_analysis() {
+        let preset = WorkloadProfiles::get_by_id("gaming_1080p_60fps").unwrap();
+
+        assert!(finds_cpu_bottleneck(&custom_profile(70.0)));
+        assert!(!finds_cpu_bottleneck(&preset));
+    }
+
+    #[test]
+    fn test_validate_rejects_preset_ids() {
+        let mut profile = custom_profile(70.0);
+        assert!(WorkloadProfiles::validate_custom(&profile).is_ok());
+
+        profile.id = "gaming_1080p_60fps".to_string();
+        let error = WorkloadProfiles::validate_custom(&profile).unwrap_err();
+        assert!(error.contains("preset"));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_profiles() {
+        let mut unnamed = custom_profile(70.0);
+        unnamed.name = "  ".to_string();
+        assert!(WorkloadProfiles::validate_custom(&unnamed).is_err());
+
+        assert!(WorkloadProfiles::validate_custom(&custom_profile(120.0)).is_err());
+
+        let mut percentile = custom_profile(70.0);
+        percentile.threshold_overrides.as_mut().unwrap().statistic = ThresholdStatistic::Percentile(150.0);
+        assert!(WorkloadProfiles::validate_custom(&percentile).is_err());
+    }
+
+    #[test]
+    fn test_settings_without_custom_profiles_deserialize() {
+        let json = serde_json::to_value(UserSettings::default()).unwrap();
+        let mut object = json.as_object().unwrap().clone();
+        object.remove("custom_profiles");
+
+        let settings: UserSettings = serde_json::from_value(serde_json::Value::Object(object)).unwrap();
+        assert!(settings.custom_profiles.is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+//! Unit tests for pluggable bottleneck rules
+//!
+//! Tests registering custom rules with the analysis engine following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::{AnalysisContext, BottleneckRule};
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{Bottleneck, BottleneckType, MetricSample, MetricType};
+    use chrono::{Duration, Utc};
+
+    struct AlwaysFiresRule;
+
+    impl BottleneckRule for AlwaysFiresRule {
+        fn name(&self) -> &str {
+            "always_fires"
+        }
+
+        fn detect(&self, metrics: &[MetricSample], _ctx: &AnalysisContext) -> Option<Bottleneck> {
+            Some(Bottleneck {
+                bottleneck_type: BottleneckType::Storage,
+                severity: 42,
+                evidence: vec![],
+                summary: "Custom rule fired".to_string(),
+                details: format!("Saw {} samples", metrics.len()),
+                onset: None,
+                active_fraction: 0.0,
+            })
+        }
+    }
+
+    fn create_cpu_metrics(value: f64) -> Vec<MetricSample> {
+        (0..10)
+            .map(|i| MetricSample {
+                timestamp: Utc::now() - Duration::seconds(i),
+                metric_type: MetricType::CpuUtilization,
+                value,
+                unit: "%".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_custom_rule_appears_in_results() {
+        let engine = AnalysisEngine::with_rules(vec![Box::new(AlwaysFiresRule)]);
+
+        let result = engine.analyze(&create_cpu_metrics(95.0), Some(60), None);
+
+        assert_eq!(result.bottlenecks.len(), 1);
+        assert_eq!(result.bottlenecks[0].summary, "Custom rule fired");
+        assert_eq!(result.bottlenecks[0].details, "Saw 10 samples");
+    }
+
+    #[test]
+    fn test_custom_rule_alongside_builtin_rules() {
+        let mut engine = AnalysisEngine::new();
+        engine.register_rule(Box::new(AlwaysFiresRule));
+
+        let result = engine.analyze(&create_cpu_metrics(95.0), Some(60), None);
+
+        assert!(result.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Cpu));
+        assert!(result.bottlenecks.iter().any(|b| b.summary == "Custom rule fired"));
+        assert_eq!(engine.rule_names().last().map(String::as_str), Some("always_fires"));
+    }
+
+    #[test]
+    fn test_default_engine_matches_builtin_analysis() {
+        let metrics = create_cpu_metrics(95.0);
+
+        let from_engine = AnalysisEngine::new().analyze(&metrics, Some(60), None);
+        let from_rules = stats_io_lib::analysis::rules::analyze_bottlenecks(&metrics, 60, None);
+
+        assert_eq!(from_engine.bottlenecks.len(), from_rules.bottlenecks.len());
+    }
+}
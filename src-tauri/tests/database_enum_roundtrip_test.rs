@@ -0,0 +1,218 @@
+//! Unit tests for enum columns in the SQLite store
+//!
+//! Tests that enum values round-trip through the database following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata, HardwareConfig,
+        MemoryInfo, MetricSample, MetricType, Run, Session, StorageInfo, StorageType, WorkloadProfile,
+        WorkloadType,
+    };
+    use stats_io_lib::persistence::DatabaseStorage;
+    use chrono::Utc;
+    use rusqlite::Connection;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    const WORKLOAD_TYPES: [WorkloadType; 5] = [
+        WorkloadType::Gaming,
+        WorkloadType::Rendering,
+        WorkloadType::AI,
+        WorkloadType::Productivity,
+        WorkloadType::General,
+    ];
+
+    const BOTTLENECK_TYPES: [BottleneckType; 13] = [
+        BottleneckType::Cpu,
+        BottleneckType::Gpu,
+        BottleneckType::Ram,
+        BottleneckType::Vram,
+        BottleneckType::Storage,
+        BottleneckType::Thermal,
+        BottleneckType::Bandwidth,
+        BottleneckType::Responsiveness,
+        BottleneckType::InputPipeline,
+        BottleneckType::FrameCap,
+        BottleneckType::CombinedLoad,
+        BottleneckType::BackgroundDownload,
+        BottleneckType::HybridGraphics,
+    ];
+
+    fn drive(storage_type: StorageType) -> StorageInfo {
+        StorageInfo {
+            model: format!("{:?} drive", storage_type),
+            capacity_mb: 1_000_000,
+            storage_type,
+            interface: None,
+            free_mb: None,
+        }
+    }
+
+    fn create_session(workload_type: WorkloadType) -> Session {
+        let bottlenecks = BOTTLENECK_TYPES
+            .iter()
+            .map(|bottleneck_type| Bottleneck {
+                bottleneck_type: bottleneck_type.clone(),
+                severity: 50,
+                evidence: vec![],
+                summary: String::new(),
+                details: String::new(),
+                onset: None,
+                active_fraction: 0.0,
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert(
+            "gpu_vram_usage_percent".to_string(),
+            vec![MetricSample::new(MetricType::GpuVramUsagePercent, 75.0, "GPU")],
+        );
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: None,
+                    boost_clock_mhz: None,
+                    architecture: None,
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: None,
+                    speed_mhz: None,
+                    modules: vec![],
+                },
+                storage_devices: vec![
+                    drive(StorageType::SSD),
+                    drive(StorageType::HDD),
+                    drive(StorageType::NVMe),
+                    drive(StorageType::Unknown),
+                ],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: format!("{:?} profile", workload_type),
+                workload_type,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![Run {
+                id: Uuid::new_v4(),
+                name: "Run".to_string(),
+                metrics_streams,
+                analysis_result: Some(BottleneckAnalysisResult {
+                    bottlenecks,
+                    timestamp: Utc::now(),
+                    coverage: None,
+                }),
+                notes: None,
+                environment: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_every_workload_type_round_trips() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(temp_dir.path().join("test.db")).unwrap();
+
+        for workload_type in WORKLOAD_TYPES {
+            let session = create_session(workload_type.clone());
+            storage.save_session(&session).unwrap();
+
+            let loaded = storage.load_session(&session.id).unwrap();
+            assert_eq!(loaded.profile.workload_type, workload_type);
+
+            let storage_types: Vec<String> = loaded
+                .hardware_config_snapshot
+                .storage_devices
+                .iter()
+                .map(|d| format!("{:?}", d.storage_type))
+                .collect();
+            assert_eq!(storage_types, vec!["SSD", "HDD", "NVMe", "Unknown"]);
+
+            let bottleneck_types: Vec<BottleneckType> = loaded.runs[0]
+                .analysis_result
+                .as_ref()
+                .unwrap()
+                .bottlenecks
+                .iter()
+                .map(|b| b.bottleneck_type.clone())
+                .collect();
+            assert_eq!(bottleneck_types, BOTTLENECK_TYPES.to_vec());
+        }
+    }
+
+    #[test]
+    fn test_metric_rows_keep_their_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(temp_dir.path().join("test.db")).unwrap();
+        let session = create_session(WorkloadType::Gaming);
+        let run_id = session.runs[0].id;
+        storage.save_session(&session).unwrap();
+
+        let rows = storage
+            .query_metrics(&run_id, Some(&MetricType::GpuVramUsagePercent), None, None)
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].metric_type, MetricType::GpuVramUsagePercent);
+        assert!(storage.query_metrics(&run_id, Some(&MetricType::CpuUtilization), None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_debug_names_from_older_versions_are_migrated() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let session = create_session(WorkloadType::AI);
+        let run_id = session.runs[0].id;
+        {
+            let storage = DatabaseStorage::new(&db_path).unwrap();
+            storage.save_session(&session).unwrap();
+        }
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute("UPDATE sessions SET profile_type = 'AI'", []).unwrap();
+            conn.execute("UPDATE metrics SET metric_type = 'GpuVramUsagePercent'", []).unwrap();
+        }
+
+        let storage = DatabaseStorage::new(&db_path).unwrap();
+        assert_eq!(storage.load_session(&session.id).unwrap().profile.workload_type, WorkloadType::AI);
+        let rows = storage
+            .query_metrics(&run_id, Some(&MetricType::GpuVramUsagePercent), None, None)
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_value_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let session = create_session(WorkloadType::Rendering);
+        let storage = DatabaseStorage::new(&db_path).unwrap();
+        storage.save_session(&session).unwrap();
+
+        Connection::open(&db_path)
+            .unwrap()
+            .execute("UPDATE sessions SET profile_type = 'simulation'", [])
+            .unwrap();
+        assert!(storage.load_session(&session.id).is_err());
+    }
+}
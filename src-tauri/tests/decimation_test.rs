@@ -0,0 +1,129 @@
+//! Unit tests for pre-save decimation
+//!
+//! Tests per-metric-type decimation of saved runs following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MetricSample, MetricType, Run, Session,
+        WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::persistence::decimation::{DecimationMethod, DecimationPolicy, DecimationRule};
+    use stats_io_lib::persistence::DatabaseStorage;
+    use chrono::{Duration, TimeZone, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    /// 20 seconds of CPU utilization alternating 40/60% and frame times, one sample per second
+    fn create_run() -> Run {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let mut cpu = Vec::new();
+        let mut frame_time = Vec::new();
+        for i in 0..20 {
+            let timestamp = start + Duration::seconds(i);
+            let value = if i % 2 == 0 { 40.0 } else { 60.0 };
+            cpu.push(MetricSample::new(MetricType::CpuUtilization, value, "CPU").with_timestamp(timestamp));
+            frame_time.push(MetricSample::new(MetricType::FrameTime, 16.0 + i as f64, "Game").with_timestamp(timestamp));
+        }
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("cpu_utilization".to_string(), cpu);
+        metrics_streams.insert("frame_time".to_string(), frame_time);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run 1".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn create_session(run: Run) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: None,
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: None,
+                    boost_clock_mhz: None,
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: None,
+                    speed_mhz: None,
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Decimated".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![run],
+        }
+    }
+
+    #[test]
+    fn test_saved_utilization_is_reduced_and_frame_times_kept() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(temp_dir.path().join("test.db"))
+            .unwrap()
+            .with_decimation_policy(DecimationPolicy::recommended());
+        let session = create_session(create_run());
+        let run_id = session.runs[0].id;
+        storage.save_session(&session).unwrap();
+
+        let cpu_rows = storage.query_metrics(&run_id, Some(&MetricType::CpuUtilization), None, None).unwrap();
+        let frame_rows = storage.query_metrics(&run_id, Some(&MetricType::FrameTime), None, None).unwrap();
+        assert_eq!(cpu_rows.len(), 10);
+        assert!(cpu_rows.iter().all(|s| s.value == 50.0), "{:?}", cpu_rows);
+        assert_eq!(frame_rows.len(), 20);
+
+        let loaded = storage.load_session(&session.id).unwrap();
+        assert_eq!(loaded.runs[0].metrics_streams["cpu_utilization"].len(), 10);
+        let frame_values: Vec<f64> = loaded.runs[0].metrics_streams["frame_time"].iter().map(|s| s.value).collect();
+        assert_eq!(frame_values, (0..20).map(|i| 16.0 + i as f64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_default_policy_and_max_method() {
+        let mut run = create_run();
+        DecimationPolicy::default().apply(&mut run);
+        assert_eq!(run.metrics_streams["cpu_utilization"].len(), 20);
+
+        let policy = DecimationPolicy {
+            rules: vec![DecimationRule {
+                metric_type: MetricType::CpuUtilization,
+                interval_seconds: 5,
+                method: DecimationMethod::Max,
+            }],
+        };
+        policy.apply(&mut run);
+        let cpu = &run.metrics_streams["cpu_utilization"];
+        assert_eq!(cpu.len(), 4);
+        assert!(cpu.iter().all(|s| s.value == 60.0));
+        assert!(cpu.windows(2).all(|w| w[1].timestamp - w[0].timestamp == Duration::seconds(5)));
+    }
+}
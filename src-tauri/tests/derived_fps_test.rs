@@ -0,0 +1,98 @@
+//! Unit tests for deriving FPS and frame times
+//!
+//! Tests filling in the missing half of FPS and frame-time data following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run};
+    use stats_io_lib::metrics::derive_missing_metrics;
+    use stats_io_lib::persistence::export_import::{export_run, import_run};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_run(metric_type: MetricType, values: &[f64]) -> Run {
+        let start = Utc::now();
+        let samples = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                MetricSample::new(metric_type.clone(), *value, "Game")
+                    .with_timestamp(start + Duration::milliseconds(i as i64 * 16))
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("capture".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Imported".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn samples_of(run: &Run, metric_type: MetricType) -> Vec<&MetricSample> {
+        let mut samples: Vec<&MetricSample> = run
+            .metrics_streams
+            .values()
+            .flatten()
+            .filter(|s| s.metric_type == metric_type)
+            .collect();
+        samples.sort_by_key(|s| s.timestamp);
+        samples
+    }
+
+    #[tokio::test]
+    async fn test_import_derives_fps_from_frame_times() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("run.json");
+        // Zero and negative frame times are glitches and yield no FPS sample
+        let run = create_run(MetricType::FrameTime, &[16.0, 20.0, 0.0, 8.0, -4.0, 10.0]);
+        export_run(&run, &path).await.unwrap();
+
+        let imported = import_run(&path).await.unwrap();
+        let frame_times = samples_of(&imported, MetricType::FrameTime);
+        let fps = samples_of(&imported, MetricType::Fps);
+
+        assert_eq!(frame_times.len(), 6);
+        assert_eq!(fps.len(), 4);
+        let valid: Vec<&&MetricSample> = frame_times.iter().filter(|s| s.value > 0.0).collect();
+        for (frame_time, fps) in valid.iter().zip(&fps) {
+            assert_eq!(fps.timestamp, frame_time.timestamp);
+            assert!((fps.value - 1000.0 / frame_time.value).abs() < 1e-9);
+            assert_eq!(fps.unit, "fps");
+            assert_eq!(fps.source_component, "Game");
+        }
+        assert_eq!(fps[0].value, 62.5);
+        assert!(imported.metrics_streams.contains_key("fps"));
+    }
+
+    #[test]
+    fn test_frame_times_derived_from_fps() {
+        let mut run = create_run(MetricType::Fps, &[50.0, 125.0, f64::NAN]);
+
+        assert_eq!(derive_missing_metrics(&mut run), 2);
+        let frame_times = samples_of(&run, MetricType::FrameTime);
+        assert_eq!(frame_times[0].value, 20.0);
+        assert_eq!(frame_times[1].value, 8.0);
+        assert_eq!(frame_times[0].unit, "ms");
+    }
+
+    #[test]
+    fn test_nothing_derived_when_both_or_neither_present() {
+        let mut run = create_run(MetricType::FrameTime, &[16.0]);
+        run.metrics_streams.insert(
+            "fps".to_string(),
+            vec![MetricSample::new(MetricType::Fps, 60.0, "Game")],
+        );
+        assert_eq!(derive_missing_metrics(&mut run), 0);
+        assert_eq!(samples_of(&run, MetricType::Fps).len(), 1);
+
+        let mut cpu_only = create_run(MetricType::CpuUtilization, &[50.0]);
+        assert_eq!(derive_missing_metrics(&mut cpu_only), 0);
+        assert_eq!(cpu_only.metrics_streams.len(), 1);
+    }
+}
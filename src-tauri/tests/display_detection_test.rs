@@ -0,0 +1,103 @@
+//! Unit tests for display detection
+//!
+//! Tests parsing of xrandr and system_profiler display modes following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::FrameCapRule;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use stats_io_lib::hardware::display::{parse_system_profiler_displays, parse_xrandr_displays};
+    use chrono::{Duration, Utc};
+
+    const XRANDR: &str = "\
+Screen 0: minimum 8 x 8, current 4480 x 1440, maximum 32767 x 32767
+DP-0 connected primary 2560x1440+0+0 (normal left inverted right x axis y axis) 597mm x 336mm
+   2560x1440     59.95 +  143.97*
+   1920x1080     60.00    119.88
+HDMI-0 disconnected (normal left inverted right x axis y axis)
+DP-2 connected 1920x1080+2560+0 (normal left inverted right x axis y axis) 527mm x 296mm
+   1920x1080     60.00*+  50.00
+DP-4 connected (normal left inverted right x axis y axis)
+   1920x1080     60.00 +
+";
+
+    const SYSTEM_PROFILER: &str = "\
+Graphics/Displays:
+
+    Apple M1 Pro:
+
+      Chipset Model: Apple M1 Pro
+      Type: GPU
+      Bus: Built-In
+      Total Number of Cores: 16
+      Displays:
+        Color LCD:
+          Display Type: Built-in Liquid Retina XDR Display
+          Resolution: 3456 x 2234 Retina
+          Main Display: Yes
+          UI Looks like: 1728 x 1117 @ 120.00Hz
+        DELL U2720Q:
+          Resolution: 3840 x 2160 (2160p/4K UHD 1 - Ultra High Definition)
+          UI Looks like: 1920 x 1080 @ 60.00Hz
+          Connection Type: Thunderbolt/DisplayPort
+";
+
+    #[test]
+    fn test_xrandr_current_modes() {
+        let displays = parse_xrandr_displays(XRANDR);
+
+        let names: Vec<&str> = displays.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["DP-0", "DP-2", "DP-4"]);
+        assert_eq!((displays[0].resolution_width, displays[0].resolution_height), (2560, 1440));
+        assert_eq!(displays[0].refresh_rate_hz, Some(144));
+        assert_eq!(displays[1].refresh_rate_hz, Some(60));
+        // Connected but switched off
+        assert_eq!(displays[2].refresh_rate_hz, None);
+        assert!(parse_xrandr_displays("").is_empty());
+    }
+
+    #[test]
+    fn test_system_profiler_modes() {
+        let displays = parse_system_profiler_displays(SYSTEM_PROFILER);
+
+        assert_eq!(displays.len(), 2);
+        assert_eq!(displays[0].name, "Color LCD");
+        assert_eq!((displays[0].resolution_width, displays[0].resolution_height), (3456, 2234));
+        assert_eq!(displays[0].refresh_rate_hz, Some(120));
+        assert_eq!(displays[0].gpu_attachment.as_deref(), Some("Apple M1 Pro"));
+        assert_eq!(displays[1].name, "DELL U2720Q");
+        assert_eq!(displays[1].refresh_rate_hz, Some(60));
+
+        let legacy = "      Displays:\n        LG HDR WQHD:\n          Resolution: 2560 x 1440 @ 144 Hz\n";
+        assert_eq!(parse_system_profiler_displays(legacy)[0].refresh_rate_hz, Some(144));
+    }
+
+    #[test]
+    fn test_detected_refresh_rate_enables_frame_cap_rule() {
+        let rule = FrameCapRule::from_displays(&parse_xrandr_displays(XRANDR)).expect("frame cap rule");
+        let mut engine = AnalysisEngine::new();
+        engine.register_rule(Box::new(rule));
+
+        // Pinned at 60 fps on the 144 Hz display with idle hardware
+        let now = Utc::now();
+        let metrics: Vec<MetricSample> = (0..30)
+            .flat_map(|i| {
+                let timestamp = now - Duration::seconds(30 - i);
+                vec![
+                    MetricSample::new(MetricType::Fps, 59.9, "Game").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::GpuUtilization, 50.0, "GPU").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::CpuUtilization, 30.0, "CPU").with_timestamp(timestamp),
+                ]
+            })
+            .collect();
+
+        let result = engine.analyze(&metrics, Some(60), None);
+        let frame_cap = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::FrameCap)
+            .expect("frame cap bottleneck");
+        assert!(frame_cap.summary.contains("144 Hz"), "{}", frame_cap.summary);
+    }
+}
@@ -0,0 +1,88 @@
+//! Unit tests for DPC latency detection
+//!
+//! Tests typeperf parsing and the responsiveness rule following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::detect_dpc_latency;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckAnalysisResult, BottleneckType, MetricSample, MetricType,
+    };
+    use stats_io_lib::metrics::providers::latency::{latency_samples, parse_typeperf_output};
+    use chrono::{Duration, Utc};
+
+    const HIGH_DPC_SNAPSHOT: &str = "\r
+\"(PDH-CSV 4.0)\",\"\\\\DESKTOP-1\\Processor Information(_Total)\\% DPC Time\",\"\\\\DESKTOP-1\\Processor Information(_Total)\\% Interrupt Time\"\r
+\"10/15/2026 14:03:21.512\",\"12.500000\",\"1.250000\"\r
+Exiting, please wait...\r
+The command completed successfully.\r
+";
+
+    fn create_latency_metrics(dpc: f64, interrupt: f64) -> Vec<MetricSample> {
+        let parsed = stats_io_lib::metrics::models::LatencyMetrics {
+            dpc_time_percent: dpc,
+            interrupt_time_percent: interrupt,
+        };
+        (0..10)
+            .flat_map(|i| latency_samples(&parsed, Utc::now() - Duration::seconds(i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_typeperf_snapshot() {
+        let parsed = parse_typeperf_output(HIGH_DPC_SNAPSHOT).unwrap();
+
+        assert_eq!(parsed.dpc_time_percent, 12.5);
+        assert_eq!(parsed.interrupt_time_percent, 1.25);
+
+        let samples = latency_samples(&parsed, Utc::now());
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].metric_type, MetricType::DpcTime);
+        assert_eq!(samples[0].value, 12.5);
+        assert_eq!(samples[1].metric_type, MetricType::InterruptTime);
+    }
+
+    #[test]
+    fn test_parse_rejects_unrelated_output() {
+        assert!(parse_typeperf_output("").is_none());
+        assert!(parse_typeperf_output("Error: No valid counters.").is_none());
+    }
+
+    #[test]
+    fn test_high_dpc_snapshot_triggers_rule() {
+        let parsed = parse_typeperf_output(HIGH_DPC_SNAPSHOT).unwrap();
+        let metrics = latency_samples(&parsed, Utc::now());
+
+        let bottleneck = detect_dpc_latency(&metrics).unwrap();
+
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Responsiveness);
+        assert!(bottleneck
+            .evidence
+            .iter()
+            .any(|e| e.metric_type == MetricType::DpcTime && e.actual_value == 12.5));
+
+        let insights = generate_insights(
+            &BottleneckAnalysisResult { bottlenecks: vec![bottleneck], timestamp: Utc::now(), coverage: None },
+            None,
+        );
+        assert!(insights.recommendations.iter().any(|r| r.text().contains("drivers")));
+    }
+
+    #[test]
+    fn test_low_latency_not_flagged() {
+        assert!(detect_dpc_latency(&create_latency_metrics(0.4, 0.2)).is_none());
+        assert!(detect_dpc_latency(&[]).is_none());
+    }
+
+    #[test]
+    fn test_engine_reports_responsiveness() {
+        let result = AnalysisEngine::new().analyze(&create_latency_metrics(9.0, 0.5), Some(60), None);
+
+        assert!(result
+            .bottlenecks
+            .iter()
+            .any(|b| b.bottleneck_type == BottleneckType::Responsiveness));
+    }
+}
@@ -56,6 +56,7 @@ mod tests {
             threads: 16,
             base_clock_mhz: Some(3000.0),
             boost_clock_mhz: Some(4500.0),
+            core_max_frequencies_mhz: vec![],
         };
 
         let memory = stats_io_lib::core::domain::MemoryInfo {
@@ -134,6 +135,7 @@ mod tests {
             },
             analysis_result: Some(analysis_result),
             notes: Some("Test run notes".to_string()),
+            environment: None,
         };
         
         // 5. Add run to session
@@ -0,0 +1,106 @@
+//! Unit tests for empty and non-finite metric streams
+//!
+//! Tests that aggregations skip missing data instead of producing NaN following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::comparison::compare_runs;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run};
+    use stats_io_lib::metrics::{aggregate_metrics, mean, percentile};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn create_run(streams: Vec<(&str, Vec<MetricSample>)>) -> Run {
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams: streams
+                .into_iter()
+                .map(|(key, samples)| (key.to_string(), samples))
+                .collect::<HashMap<_, _>>(),
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn samples(metric_type: MetricType, values: &[f64]) -> Vec<MetricSample> {
+        values
+            .iter()
+            .map(|value| MetricSample::new(metric_type.clone(), *value, "Test"))
+            .collect()
+    }
+
+    #[test]
+    fn test_mean_of_empty_input_is_none() {
+        assert_eq!(mean(Vec::<f64>::new()), None);
+        assert_eq!(mean(vec![f64::NAN, f64::INFINITY]), None);
+        assert_eq!(mean(vec![1.0, f64::NAN, 3.0]), Some(2.0));
+    }
+
+    #[test]
+    fn test_percentile_ignores_non_finite_values() {
+        assert_eq!(percentile(&[], 50.0), None);
+        assert_eq!(percentile(&[f64::NAN], 50.0), None);
+        assert_eq!(percentile(&[f64::NAN, 10.0, 20.0, 30.0], 100.0), Some(30.0));
+    }
+
+    #[test]
+    fn test_aggregation_skips_metrics_without_finite_values() {
+        let mut metrics = samples(MetricType::CpuUtilization, &[f64::NAN]);
+        metrics.extend(samples(MetricType::GpuUtilization, &[40.0, f64::NAN, 60.0]));
+
+        let aggregated = aggregate_metrics(&metrics);
+        assert!(!aggregated.contains_key("CpuUtilization"));
+        let gpu = &aggregated["GpuUtilization"];
+        assert_eq!(gpu.avg, 50.0);
+        assert_eq!(gpu.count, 2);
+    }
+
+    #[test]
+    fn test_compare_empty_run_with_populated_run() {
+        let empty = create_run(vec![
+            ("cpu", vec![]),
+            ("gpu", samples(MetricType::GpuUtilization, &[f64::NAN])),
+        ]);
+        let populated = create_run(vec![
+            ("cpu", samples(MetricType::CpuUtilization, &[50.0, 70.0])),
+            ("gpu", samples(MetricType::GpuUtilization, &[80.0])),
+        ]);
+
+        for result in [compare_runs(&empty, &populated), compare_runs(&populated, &empty)] {
+            assert!(result.metric_deltas.is_empty());
+            let json = serde_json::to_string(&result).unwrap();
+            assert!(!json.contains("null") && !json.contains("NaN"));
+        }
+    }
+
+    #[test]
+    fn test_compare_keeps_finite_deltas() {
+        let run1 = create_run(vec![("cpu", samples(MetricType::CpuUtilization, &[0.0, f64::NAN]))]);
+        let run2 = create_run(vec![("cpu", samples(MetricType::CpuUtilization, &[40.0]))]);
+
+        let result = compare_runs(&run1, &run2);
+        let delta = &result.metric_deltas["CpuUtilization"];
+        assert_eq!(delta.run1_avg, 0.0);
+        assert_eq!(delta.run2_avg, 40.0);
+        for value in [delta.run1_avg, delta.run2_avg, delta.delta, delta.delta_percent] {
+            assert!(value.is_finite());
+        }
+    }
+
+    #[test]
+    fn test_analysis_of_non_finite_samples_does_not_panic() {
+        let mut metrics = samples(MetricType::StorageQueueDepth, &[f64::NAN, 12.0]);
+        metrics.extend(samples(MetricType::GpuUtilization, &[f64::NAN]));
+        metrics.extend(samples(MetricType::Temperature, &[f64::NAN]));
+
+        let result = AnalysisEngine::new().analyze(&metrics, Some(60), None);
+        for bottleneck in &result.bottlenecks {
+            for evidence in &bottleneck.evidence {
+                assert!(evidence.actual_value.is_finite());
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Unit tests for foreground application attribution
+//!
+//! Tests CPU/GPU attribution to the focused application following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::{
+        attribute_foreground, generate_insights, ForegroundScenario, UserFacingInsights,
+    };
+    use stats_io_lib::core::domain::{BottleneckAnalysisResult, ForegroundProcess, MetricSample, MetricType};
+    use stats_io_lib::hardware::foreground::parse_pid;
+    use stats_io_lib::metrics::models::ProcessCpuUsage;
+    use stats_io_lib::metrics::providers::cpu_processes::{busiest_processes, process_cpu_samples, CPU_PROCESS_TOP_N};
+    use chrono::{Duration, Utc};
+
+    const GAME_PID: u32 = 4242;
+
+    fn game() -> ForegroundProcess {
+        ForegroundProcess { pid: GAME_PID, name: Some("game.exe".to_string()) }
+    }
+
+    fn process(pid: u32, name: &str, cpu_percent: f64) -> ProcessCpuUsage {
+        ProcessCpuUsage { pid, name: name.to_string(), cpu_percent }
+    }
+
+    /// Ten ticks of system totals plus a snapshot of per-process usage each tick
+    fn create_metrics(system_cpu: f64, system_gpu: f64, snapshot: &[ProcessCpuUsage], game_gpu: f64) -> Vec<MetricSample> {
+        let start = Utc::now() - Duration::seconds(10);
+        let mut metrics = Vec::new();
+        for i in 0..10 {
+            let timestamp = start + Duration::seconds(i);
+            metrics.push(MetricSample::new(MetricType::CpuUtilization, system_cpu, "CPU").with_timestamp(timestamp));
+            metrics.push(MetricSample::new(MetricType::GpuUtilization, system_gpu, "GPU").with_timestamp(timestamp));
+            metrics.extend(process_cpu_samples(snapshot, timestamp));
+            metrics.push(
+                MetricSample::new(MetricType::ProcessGpuUtilization, game_gpu, format!("game.exe (pid {})", GAME_PID))
+                    .with_timestamp(timestamp),
+            );
+        }
+        metrics
+    }
+
+    #[test]
+    fn test_app_limited_attribution() {
+        let snapshot = [process(GAME_PID, "game.exe", 72.0), process(880, "explorer.exe", 8.0)];
+        let metrics = create_metrics(80.0, 96.0, &snapshot, 94.0);
+
+        let attribution = attribute_foreground(&metrics, &game()).unwrap();
+        let cpu = attribution.cpu.as_ref().unwrap();
+        assert!((cpu.process_percent - 72.0).abs() < 1e-9);
+        assert!((cpu.share_percent - 90.0).abs() < 1e-9);
+        let gpu = attribution.gpu.as_ref().unwrap();
+        assert!((gpu.share_percent - 94.0 / 96.0 * 100.0).abs() < 1e-9);
+        assert_eq!(attribution.scenario, ForegroundScenario::AppLimited);
+        assert!(attribution.describe().starts_with("game.exe used 72% of total CPU (90% of all CPU use)"));
+    }
+
+    #[test]
+    fn test_system_contended_attribution() {
+        // A background encoder takes most of a busy CPU
+        let snapshot = [process(777, "encoder.exe", 60.0), process(GAME_PID, "game.exe", 25.0)];
+        let metrics = create_metrics(90.0, 70.0, &snapshot, 65.0);
+
+        let attribution = attribute_foreground(&metrics, &game()).unwrap();
+        assert_eq!(attribution.scenario, ForegroundScenario::SystemContended);
+        assert_eq!(attribution.contended_resources(), vec!["CPU"]);
+        assert!(attribution.describe().contains("other processes are competing for the CPU"));
+
+        let result = BottleneckAnalysisResult { bottlenecks: vec![], timestamp: Utc::now(), coverage: None };
+        let insights: UserFacingInsights = generate_insights(&result, None).with_foreground_attribution(attribution);
+        assert!(insights.summary.contains("game.exe used 25% of total CPU"));
+        assert!(insights.recommendations.iter().any(|r| r.id == "foreground-contention"));
+        assert!(insights.foreground.is_some());
+    }
+
+    #[test]
+    fn test_missing_ticks_count_as_idle() {
+        let start = Utc::now();
+        let mut metrics = Vec::new();
+        for i in 0..4 {
+            let timestamp = start + Duration::seconds(i);
+            metrics.push(MetricSample::new(MetricType::CpuUtilization, 50.0, "CPU").with_timestamp(timestamp));
+            // The game only makes the per-process list in the first two ticks
+            let snapshot = if i < 2 {
+                vec![process(GAME_PID, "game.exe", 40.0)]
+            } else {
+                vec![process(990, "other.exe", 45.0)]
+            };
+            metrics.extend(process_cpu_samples(&snapshot, timestamp));
+        }
+
+        let attribution = attribute_foreground(&metrics, &game()).unwrap();
+        let cpu = attribution.cpu.unwrap();
+        assert!((cpu.process_percent - 20.0).abs() < 1e-9);
+        assert!(attribution.gpu.is_none());
+    }
+
+    #[test]
+    fn test_attribution_unavailable_without_process_samples() {
+        let metrics = vec![MetricSample::new(MetricType::CpuUtilization, 50.0, "CPU")];
+        assert!(attribute_foreground(&metrics, &game()).is_none());
+    }
+
+    #[test]
+    fn test_foreground_process_always_sampled() {
+        let mut snapshot: Vec<ProcessCpuUsage> = (1..=8).map(|pid| process(pid, "busy", 10.0 + pid as f64)).collect();
+        snapshot.push(process(GAME_PID, "game.exe", 0.0));
+        snapshot.push(process(9, "idle", 0.0));
+
+        let top = busiest_processes(snapshot, Some(GAME_PID));
+        assert_eq!(top.len(), CPU_PROCESS_TOP_N + 1);
+        assert_eq!(top[0].pid, 8);
+        assert!(top.iter().any(|p| p.pid == GAME_PID));
+        assert!(!top.iter().any(|p| p.pid == 9));
+    }
+
+    #[test]
+    fn test_parse_pid() {
+        assert_eq!(parse_pid("4242\n"), Some(4242));
+        assert_eq!(parse_pid("\n  17  \n"), Some(17));
+        assert_eq!(parse_pid("0"), None);
+        assert_eq!(parse_pid("no window"), None);
+        assert_eq!(parse_pid(""), None);
+    }
+}
@@ -0,0 +1,103 @@
+//! Unit tests for frame rate cap detection
+//!
+//! Tests VSync and frame limiter detection against the display refresh rate following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::{detect_frame_cap, FrameCapRule};
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckType, DisplayInfo, MetricSample, MetricType};
+    use chrono::{Duration, Utc};
+
+    fn create_display(refresh_rate_hz: Option<u32>) -> DisplayInfo {
+        DisplayInfo {
+            name: "Test Display".to_string(),
+            resolution_width: 2560,
+            resolution_height: 1440,
+            refresh_rate_hz,
+            gpu_attachment: None,
+        }
+    }
+
+    /// FPS, GPU, and CPU readings for 30 seconds of gameplay
+    fn create_metrics(fps: impl Fn(i64) -> f64, gpu: f64, cpu: f64) -> Vec<MetricSample> {
+        let now = Utc::now();
+        (0..30)
+            .flat_map(|i| {
+                let timestamp = now - Duration::seconds(30 - i);
+                vec![
+                    MetricSample::new(MetricType::Fps, fps(i), "Game").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::GpuUtilization, gpu, "GPU").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::CpuUtilization, cpu, "CPU").with_timestamp(timestamp),
+                ]
+            })
+            .collect()
+    }
+
+    /// Frame rate pinned at 60 fps with an occasional dip
+    fn capped_at_60(i: i64) -> f64 {
+        if i % 10 == 9 {
+            52.0
+        } else {
+            59.8 + (i % 3) as f64 * 0.2
+        }
+    }
+
+    #[test]
+    fn test_capped_fps_with_idle_hardware_is_flagged() {
+        let bottleneck = detect_frame_cap(&create_metrics(capped_at_60, 50.0, 30.0), 144.0)
+            .expect("frame cap");
+
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::FrameCap);
+        assert!(bottleneck.summary.starts_with("Frame rate capped at 60 fps on a 144 Hz display"));
+        assert!(bottleneck.details.contains("VSync"));
+        assert_eq!(bottleneck.evidence[0].metric_type, MetricType::Fps);
+        assert!((bottleneck.evidence[0].actual_value - 60.0).abs() < 1.0);
+        assert_eq!(bottleneck.evidence[1].actual_value, 50.0);
+    }
+
+    #[test]
+    fn test_busy_gpu_is_a_hardware_limit() {
+        assert!(detect_frame_cap(&create_metrics(capped_at_60, 97.0, 30.0), 144.0).is_none());
+    }
+
+    #[test]
+    fn test_busy_cpu_is_a_hardware_limit() {
+        assert!(detect_frame_cap(&create_metrics(capped_at_60, 50.0, 85.0), 144.0).is_none());
+    }
+
+    #[test]
+    fn test_fps_at_refresh_rate_is_not_flagged() {
+        assert!(detect_frame_cap(&create_metrics(capped_at_60, 50.0, 30.0), 60.0).is_none());
+        assert!(detect_frame_cap(&create_metrics(|_| 142.0, 50.0, 30.0), 144.0).is_none());
+    }
+
+    #[test]
+    fn test_fluctuating_fps_has_no_ceiling() {
+        let fluctuating = |i: i64| 70.0 + (i % 7) as f64 * 8.0;
+        assert!(detect_frame_cap(&create_metrics(fluctuating, 50.0, 30.0), 144.0).is_none());
+    }
+
+    #[test]
+    fn test_rule_uses_fastest_display() {
+        assert!(FrameCapRule::from_displays(&[create_display(None)]).is_none());
+        assert!(FrameCapRule::from_displays(&[]).is_none());
+
+        // 60 fps is at the refresh rate of the 60 Hz display but capped for the 144 Hz one
+        let displays = [create_display(Some(60)), create_display(Some(144))];
+        let mut engine = AnalysisEngine::new();
+        engine.register_rule(Box::new(FrameCapRule::from_displays(&displays).unwrap()));
+        let result = engine.analyze(&create_metrics(capped_at_60, 50.0, 30.0), Some(60), None);
+
+        let frame_cap = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::FrameCap)
+            .expect("frame cap");
+        assert!(frame_cap.summary.contains("144 Hz"));
+
+        let insights = generate_insights(&result, None);
+        assert!(insights.recommendations.iter().any(|r| r.text().contains("VSync")));
+    }
+}
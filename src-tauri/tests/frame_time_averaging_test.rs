@@ -0,0 +1,64 @@
+//! Unit tests for frame-time based FPS averaging
+//!
+//! Tests that comparisons average FPS through frame times following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::comparison::{compare_runs, run_average};
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run};
+    use stats_io_lib::metrics::utils::{harmonic_mean, mean};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn create_run(metric_type: MetricType, values: &[f64]) -> Run {
+        let samples = values
+            .iter()
+            .map(|value| MetricSample::new(metric_type.clone(), *value, "Game"))
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("frames".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    #[test]
+    fn test_fps_delta_uses_frame_time_average() {
+        // Stuttery: alternating 30 and 120 FPS frames, 48 FPS by frame time but 75 by plain mean
+        let stuttery: Vec<f64> = (0..40).map(|i| if i % 2 == 0 { 30.0 } else { 120.0 }).collect();
+        let steady = vec![60.0; 40];
+        assert_eq!(mean(stuttery.iter().copied()), Some(75.0));
+
+        let result = compare_runs(&create_run(MetricType::Fps, &stuttery), &create_run(MetricType::Fps, &steady));
+        let delta = &result.metric_deltas["Fps"];
+
+        assert!((delta.run1_avg - 48.0).abs() < 1e-9);
+        assert!((delta.run2_avg - 60.0).abs() < 1e-9);
+        // The steady run is faster, although the plain mean says it is 20% slower
+        assert!((delta.delta_percent - 25.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_frame_time_keeps_arithmetic_mean() {
+        let frame_times = [1000.0 / 30.0, 1000.0 / 120.0];
+        let average = run_average("FrameTime", &frame_times).unwrap();
+        assert!((average - 1000.0 / 48.0).abs() < 1e-9);
+
+        // Both views of the same frames agree
+        let fps = run_average("Fps", &[30.0, 120.0]).unwrap();
+        assert!((fps - 1000.0 / average).abs() < 1e-9);
+        assert_eq!(run_average("CpuUtilization", &[30.0, 120.0]), Some(75.0));
+    }
+
+    #[test]
+    fn test_harmonic_mean_skips_unusable_readings() {
+        assert_eq!(harmonic_mean([60.0, 0.0, f64::NAN, -5.0]), Some(60.0));
+        assert_eq!(harmonic_mean([0.0]), None);
+        assert_eq!(harmonic_mean(Vec::new()), None);
+    }
+}
@@ -4,8 +4,24 @@
 
 #[cfg(test)]
 mod tests {
-    use stats_io_lib::metrics::providers::gpu::GpuMetricsProviderImpl;
+    use stats_io_lib::metrics::providers::gpu::{
+        adapter_engine_utilization, adapter_luid_key, parse_engine_utilization_by_adapter,
+        parse_nvidia_adapter_utilization, GpuMetricsProviderImpl,
+    };
+    use std::collections::HashMap;
     use stats_io_lib::core::interfaces::GpuMetricsProvider;
+    use stats_io_lib::core::settings::GpuUtilizationSource;
+
+    /// typeperf snapshot with two processes on one 3D engine and one on another
+    const ENGINE_SNAPSHOT: &str = concat!(
+        "\r\n",
+        "\"(PDH-CSV 4.0)\",",
+        "\"\\\\PC\\GPU Engine(pid_1200_luid_0x00000000_0x0000C6A3_phys_0_eng_0_engtype_3D)\\Utilization Percentage\",",
+        "\"\\\\PC\\GPU Engine(pid_4410_luid_0x00000000_0x0000C6A3_phys_0_eng_0_engtype_3D)\\Utilization Percentage\",",
+        "\"\\\\PC\\GPU Engine(pid_4410_luid_0x00000000_0x0000C6A3_phys_0_eng_1_engtype_3D)\\Utilization Percentage\"\r\n",
+        "\"01/15/2025 10:00:01.000\",\"35.500000\",\"22.250000\",\"12.000000\"\r\n",
+        "Exiting, please wait...\r\n",
+    );
 
     #[tokio::test]
     async fn test_gpu_metrics_provider_creation() {
@@ -113,5 +129,80 @@ mod tests {
         // Values may differ (if GPU is active), but structure should be consistent
         assert_eq!(metrics1.utilization >= 0.0, metrics2.utilization >= 0.0);
     }
-}
 
+    #[test]
+    fn test_engine_counters_sum_processes_per_engine() {
+        // 35.5% + 22.25% on engine 0 beats 12% on engine 1
+        let by_adapter = parse_engine_utilization_by_adapter(ENGINE_SNAPSHOT);
+        assert_eq!(by_adapter.len(), 1);
+        assert!((by_adapter[&adapter_luid_key(0, 0xC6A3)] - 0.5775).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_engine_counters_clamped_to_full_load() {
+        let snapshot = ENGINE_SNAPSHOT
+            .replace("35.500000", "80.000000")
+            .replace("22.250000", "40.000000");
+        assert_eq!(parse_engine_utilization_by_adapter(&snapshot)[&adapter_luid_key(0, 0xC6A3)], 1.0);
+    }
+
+    #[test]
+    fn test_engine_counters_without_data_row() {
+        let header_only: String = ENGINE_SNAPSHOT.lines().take(2).collect::<Vec<_>>().join("\r\n");
+        assert!(parse_engine_utilization_by_adapter(&header_only).is_empty());
+        assert!(parse_engine_utilization_by_adapter("").is_empty());
+    }
+
+    #[test]
+    fn test_engine_counters_split_by_adapter() {
+        // A second adapter idling on its own engine
+        let snapshot = ENGINE_SNAPSHOT
+            .replace("pid_4410_luid_0x00000000_0x0000C6A3_phys_0_eng_1", "pid_4410_luid_0x00000000_0x0001D2B4_phys_0_eng_0");
+        let by_adapter = parse_engine_utilization_by_adapter(&snapshot);
+
+        assert_eq!(by_adapter.len(), 2);
+        assert!((by_adapter[&adapter_luid_key(0, 0xC6A3)] - 0.5775).abs() < 1e-9);
+        assert!((by_adapter[&adapter_luid_key(0, 0x1D2B4)] - 0.12).abs() < 1e-9);
+        assert!(parse_engine_utilization_by_adapter("").is_empty());
+    }
+
+    #[test]
+    fn test_engine_utilization_taken_from_the_driver_adapter() {
+        // A busy integrated GPU next to the discrete GPU the driver metrics describe
+        let snapshot = ENGINE_SNAPSHOT
+            .replace("pid_4410_luid_0x00000000_0x0000C6A3_phys_0_eng_1", "pid_4410_luid_0x00000000_0x0001D2B4_phys_0_eng_0");
+        let by_luid = parse_engine_utilization_by_adapter(&snapshot);
+        let names = HashMap::from([
+            (adapter_luid_key(0, 0xC6A3), "Intel(R) UHD Graphics 770".to_string()),
+            (adapter_luid_key(0, 0x1D2B4), "NVIDIA GeForce RTX 4070".to_string()),
+        ]);
+
+        let discrete = adapter_engine_utilization(&by_luid, &names, Some("NVIDIA GeForce RTX 4070")).unwrap();
+        assert!((discrete - 0.12).abs() < 1e-9);
+        // Unknown adapters are not guessed at
+        assert_eq!(adapter_engine_utilization(&by_luid, &names, None), None);
+        assert_eq!(adapter_engine_utilization(&by_luid, &names, Some("AMD Radeon RX 7800 XT")), None);
+
+        // An adapter without engine instances is idle
+        let idle = HashMap::from([(adapter_luid_key(0, 0xC6A3), 0.4)]);
+        assert_eq!(adapter_engine_utilization(&idle, &names, Some("NVIDIA GeForce RTX 4070")), Some(0.0));
+    }
+
+    #[test]
+    fn test_nvidia_adapter_utilization_per_gpu() {
+        let adapters = parse_nvidia_adapter_utilization(
+            "NVIDIA GeForce RTX 3060 Laptop GPU, 87\nNVIDIA RTX A2000, [N/A]\n",
+        );
+        assert_eq!(adapters.len(), 1);
+        assert_eq!(adapters[0].adapter_name, "NVIDIA GeForce RTX 3060 Laptop GPU");
+        assert!((adapters[0].utilization - 0.87).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_engine_source_falls_back_gracefully() {
+        // Engine counters only exist on Windows; elsewhere the driver source is used
+        let provider = GpuMetricsProviderImpl::with_utilization_source(GpuUtilizationSource::Engine3d);
+        let metrics = provider.get_gpu_metrics().await.unwrap();
+        assert!((0.0..=1.0).contains(&metrics.utilization));
+    }
+}
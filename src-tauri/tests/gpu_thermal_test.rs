@@ -0,0 +1,140 @@
+//! Unit tests for GPU edge and hotspot thermal analysis
+//!
+//! Tests per-sensor throttle limits and cooler mount detection following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::advanced::{
+        GPU_EDGE_THROTTLE_TEMP, GPU_HOTSPOT_DELTA_THRESHOLD, GPU_HOTSPOT_THROTTLE_TEMP,
+    };
+    use stats_io_lib::analysis::rules::detect_gpu_thermal;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType, GPU_HOTSPOT_SOURCE};
+    use chrono::{DateTime, Duration, Utc};
+
+    /// Edge and hotspot readings of one GPU taken on the same ticks
+    fn create_gpu_temperatures_at(now: DateTime<Utc>, source: &str, edge: f64, hotspot: f64) -> Vec<MetricSample> {
+        (0..10)
+            .flat_map(|i| {
+                let timestamp = now - Duration::seconds(10 - i);
+                vec![
+                    MetricSample::new(MetricType::GpuTemperature, edge, source).with_timestamp(timestamp),
+                    MetricSample::new(MetricType::GpuHotspotTemperature, hotspot, source).with_timestamp(timestamp),
+                ]
+            })
+            .collect()
+    }
+
+    fn create_gpu_temperatures(edge: f64, hotspot: f64) -> Vec<MetricSample> {
+        create_gpu_temperatures_at(Utc::now(), "GPU", edge, hotspot)
+    }
+
+    #[test]
+    fn test_large_hotspot_gap_flags_mount_issue() {
+        // Normal edge temperature with a hotspot 30°C above it
+        let bottlenecks = detect_gpu_thermal(&create_gpu_temperatures(65.0, 95.0));
+
+        assert_eq!(bottlenecks.len(), 1);
+        let mount = &bottlenecks[0];
+        assert_eq!(mount.bottleneck_type, BottleneckType::Thermal);
+        assert!(mount.summary.starts_with("GPU hotspot runs 30°C above edge"));
+        assert!(mount.details.contains("thermal paste"));
+        assert_eq!(mount.evidence[0].threshold, GPU_HOTSPOT_DELTA_THRESHOLD);
+        assert_eq!(mount.evidence[0].actual_value, 30.0);
+    }
+
+    #[test]
+    fn test_normal_hotspot_gap_is_not_flagged() {
+        assert!(detect_gpu_thermal(&create_gpu_temperatures(70.0, 82.0)).is_empty());
+    }
+
+    #[test]
+    fn test_hotspot_compared_against_its_own_limit() {
+        // Past the edge limit but well within the hotspot limit
+        let hotspot = GPU_EDGE_THROTTLE_TEMP + 7.0;
+        let metrics: Vec<MetricSample> = create_gpu_temperatures(75.0, hotspot)
+            .into_iter()
+            .filter(|m| m.metric_type == MetricType::GpuHotspotTemperature)
+            .collect();
+        assert!(detect_gpu_thermal(&metrics).is_empty());
+
+        let bottlenecks = detect_gpu_thermal(&create_gpu_temperatures(80.0, 108.0));
+        let throttle = bottlenecks
+            .iter()
+            .find(|b| b.summary == "GPU hotspot temperature at throttle limit")
+            .expect("hotspot throttling");
+        assert_eq!(throttle.evidence[0].threshold, GPU_HOTSPOT_THROTTLE_TEMP);
+        assert_eq!(throttle.evidence[0].actual_value, 108.0);
+    }
+
+    #[test]
+    fn test_edge_compared_against_edge_limit() {
+        let bottlenecks = detect_gpu_thermal(&create_gpu_temperatures(86.0, 98.0));
+
+        assert_eq!(bottlenecks.len(), 1);
+        assert_eq!(bottlenecks[0].summary, "GPU edge temperature at throttle limit");
+        assert_eq!(bottlenecks[0].evidence[0].threshold, GPU_EDGE_THROTTLE_TEMP);
+    }
+
+    #[test]
+    fn test_edge_only_stream_has_no_gap() {
+        let metrics: Vec<MetricSample> = create_gpu_temperatures(70.0, 100.0)
+            .into_iter()
+            .filter(|m| m.metric_type == MetricType::GpuTemperature)
+            .collect();
+        assert!(detect_gpu_thermal(&metrics).is_empty());
+    }
+
+    #[test]
+    fn test_gap_pairs_each_gpu_with_its_own_edge_sensor() {
+        // A well-mounted card next to a cool one with a poor mount, read on the same ticks
+        let now = Utc::now();
+        let mut metrics = create_gpu_temperatures_at(now, "GPU 0", 80.0, 90.0);
+        metrics.extend(create_gpu_temperatures_at(now, "GPU 1", 40.0, 70.0));
+
+        let bottlenecks = detect_gpu_thermal(&metrics);
+
+        // Cross-pairing would average gaps of 10, 50, -30 and 30
+        assert_eq!(bottlenecks.len(), 1);
+        assert_eq!(bottlenecks[0].evidence[0].actual_value, 20.0);
+    }
+
+    #[test]
+    fn test_legacy_hotspot_source_pairs_with_single_gpu() {
+        let metrics: Vec<MetricSample> = create_gpu_temperatures(65.0, 95.0)
+            .into_iter()
+            .map(|m| match m.metric_type {
+                MetricType::GpuHotspotTemperature => MetricSample { source_component: GPU_HOTSPOT_SOURCE.to_string(), ..m },
+                _ => m,
+            })
+            .collect();
+
+        let bottlenecks = detect_gpu_thermal(&metrics);
+        assert_eq!(bottlenecks.len(), 1);
+        assert_eq!(bottlenecks[0].evidence[0].actual_value, 30.0);
+
+        // With a second card the legacy hotspot cannot be attributed
+        let mut metrics = metrics;
+        metrics.extend(
+            create_gpu_temperatures_at(metrics[0].timestamp + Duration::seconds(10), "GPU 1", 40.0, 50.0)
+                .into_iter()
+                .filter(|m| m.metric_type == MetricType::GpuTemperature),
+        );
+        assert!(detect_gpu_thermal(&metrics).is_empty());
+    }
+
+    #[test]
+    fn test_engine_reports_mount_issue_with_repaste_advice() {
+        let engine = AnalysisEngine::new();
+        let result = engine.analyze(&create_gpu_temperatures(65.0, 95.0), Some(60), None);
+
+        assert!(result
+            .bottlenecks
+            .iter()
+            .any(|b| b.summary.starts_with("GPU hotspot runs")));
+
+        let insights = generate_insights(&result, None);
+        assert!(insights.recommendations.iter().any(|r| r.text().contains("Repaste")));
+    }
+}
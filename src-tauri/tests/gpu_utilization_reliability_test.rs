@@ -0,0 +1,86 @@
+//! Unit tests for GPU utilization reliability checks
+//!
+//! Tests that zero utilization under visible GPU load is flagged following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::{analyze_bottlenecks, gpu_utilization_warnings};
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use stats_io_lib::metrics::collector::gpu_samples;
+    use stats_io_lib::metrics::models::GpuMetrics;
+    use chrono::{Duration, Utc};
+
+    /// GPU readings for 10 ticks, as the collector records them
+    fn create_samples(utilization: f64, vram_used_mb: u64, power_watts: f64) -> Vec<MetricSample> {
+        let now = Utc::now();
+        let gpu = GpuMetrics {
+            utilization,
+            vram_used_mb: Some(vram_used_mb),
+            vram_total_mb: Some(12_288),
+            temperature: Some(70.0),
+            hotspot_temperature: None,
+            clock_core_mhz: None,
+            clock_memory_mhz: None,
+            power_watts: Some(power_watts),
+            fan_speed_percent: None,
+            pcie_link: None,
+            memory_controller_load: None,
+            adapter_name: None,
+        };
+        (0..10)
+            .flat_map(|i| gpu_samples(&gpu, "GPU", now - Duration::seconds(10 - i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_zero_utilization_under_load_is_flagged() {
+        let samples = create_samples(0.0, 10_000, 220.0);
+        assert!(samples.iter().all(|s| s.metric_type != MetricType::GpuUtilization));
+        assert!(samples.iter().any(|s| s.metric_type == MetricType::GpuPower && s.value == 220.0));
+
+        let warnings = gpu_utilization_warnings(&samples);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("10 of 10"), "{}", warnings[0]);
+        assert!(warnings[0].contains("unreliable"));
+
+        let coverage = analyze_bottlenecks(&samples, 30, None).coverage.expect("coverage");
+        assert_eq!(coverage.data_warnings, warnings);
+    }
+
+    #[test]
+    fn test_unreliable_gpu_utilization_does_not_make_cpu_bound() {
+        let now = Utc::now();
+        let cpu: Vec<MetricSample> = (0..10)
+            .map(|i| {
+                MetricSample::new(MetricType::CpuUtilization, 95.0, "CPU").with_timestamp(now - Duration::seconds(10 - i))
+            })
+            .collect();
+        let is_cpu_bound = |samples: &[MetricSample]| {
+            analyze_bottlenecks(samples, 30, None)
+                .bottlenecks
+                .iter()
+                .any(|b| b.bottleneck_type == BottleneckType::Cpu)
+        };
+
+        // Busy GPU whose utilization reads 0%: no headroom can be confirmed
+        let mut samples = create_samples(0.0, 10_000, 220.0);
+        samples.extend(cpu.iter().cloned());
+        assert!(!is_cpu_bound(&samples));
+
+        // Idle GPU
+        let mut samples = create_samples(0.0, 800, 15.0);
+        samples.extend(cpu.iter().cloned());
+        assert!(is_cpu_bound(&samples));
+    }
+
+    #[test]
+    fn test_idle_or_reporting_gpu_is_not_flagged() {
+        // Idle: little VRAM in use and low power
+        assert!(gpu_utilization_warnings(&create_samples(0.0, 800, 15.0)).is_empty());
+        // Busy and reporting utilization
+        assert!(gpu_utilization_warnings(&create_samples(0.85, 10_000, 220.0)).is_empty());
+        // Too few ticks to judge
+        let samples: Vec<MetricSample> = create_samples(0.0, 10_000, 220.0).into_iter().take(6).collect();
+        assert!(gpu_utilization_warnings(&samples).is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+//! Unit tests for GPU VRAM percentage samples
+//!
+//! Tests per-device VRAM percentages and VRAM detection following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::analyze_bottlenecks;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use stats_io_lib::core::profiles::WorkloadProfiles;
+    use stats_io_lib::metrics::collector::gpu_samples;
+    use stats_io_lib::metrics::models::GpuMetrics;
+    use chrono::{Duration, Utc};
+
+    fn create_gpu_metrics(vram_used_mb: Option<u64>, vram_total_mb: Option<u64>) -> GpuMetrics {
+        GpuMetrics {
+            utilization: 0.5,
+            vram_used_mb,
+            vram_total_mb,
+            temperature: Some(65.0),
+            hotspot_temperature: None,
+            clock_core_mhz: None,
+            clock_memory_mhz: None,
+            power_watts: None,
+            fan_speed_percent: None,
+            pcie_link: None,
+            memory_controller_load: None,
+            adapter_name: None,
+        }
+    }
+
+    fn vram_percent(samples: &[MetricSample]) -> Option<&MetricSample> {
+        samples.iter().find(|m| m.metric_type == MetricType::GpuVramUsagePercent)
+    }
+
+    fn create_vram_percent_samples(source: &str, value: f64) -> Vec<MetricSample> {
+        let now = Utc::now();
+        (0..30)
+            .map(|i| {
+                MetricSample::new(MetricType::GpuVramUsagePercent, value, source)
+                    .with_timestamp(now - Duration::seconds(30 - i))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_percent_sample_alongside_mb() {
+        let samples = gpu_samples(&create_gpu_metrics(Some(8192), Some(10240)), "GPU", Utc::now());
+
+        let percent = vram_percent(&samples).unwrap();
+        assert!((percent.value - 80.0).abs() < 1e-9);
+        assert_eq!(percent.unit, "percent");
+        assert!(samples
+            .iter()
+            .any(|m| m.metric_type == MetricType::GpuVramUsage && m.value == 8192.0));
+    }
+
+    #[test]
+    fn test_percent_uses_each_device_total() {
+        let small = gpu_samples(&create_gpu_metrics(Some(4096), Some(8192)), "GPU 0", Utc::now());
+        let large = gpu_samples(&create_gpu_metrics(Some(4096), Some(24576)), "GPU 1", Utc::now());
+
+        assert!((vram_percent(&small).unwrap().value - 50.0).abs() < 1e-9);
+        assert!((vram_percent(&large).unwrap().value - 16.666).abs() < 1e-3);
+        assert_eq!(vram_percent(&large).unwrap().source_component, "GPU 1");
+    }
+
+    #[test]
+    fn test_no_percent_without_total() {
+        assert!(vram_percent(&gpu_samples(&create_gpu_metrics(Some(4096), None), "GPU", Utc::now())).is_none());
+        assert!(vram_percent(&gpu_samples(&create_gpu_metrics(Some(4096), Some(0)), "GPU", Utc::now())).is_none());
+    }
+
+    #[test]
+    fn test_high_vram_percent_flagged() {
+        let profile = WorkloadProfiles::gaming_1080p_60fps();
+        let mut metrics = create_vram_percent_samples("GPU 0", 40.0);
+        metrics.extend(create_vram_percent_samples("GPU 1", 95.0));
+
+        let result = analyze_bottlenecks(&metrics, 60, Some(&profile));
+        let vram = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Vram)
+            .expect("VRAM bottleneck");
+        assert!(vram.summary.contains("GPU 1"));
+        assert_eq!(vram.evidence[0].metric_type, MetricType::GpuVramUsagePercent);
+        assert!((vram.evidence[0].actual_value - 95.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_low_vram_percent_not_flagged() {
+        let profile = WorkloadProfiles::gaming_1080p_60fps();
+        let metrics = create_vram_percent_samples("GPU", 60.0);
+
+        let result = analyze_bottlenecks(&metrics, 60, Some(&profile));
+        assert!(!result.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Vram));
+    }
+}
@@ -0,0 +1,129 @@
+//! Unit tests for hardware change detection
+//!
+//! Tests diffing hardware configurations between sessions following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, GPUInfo, HardwareConfig, MemoryInfo, StorageInfo, StorageType,
+    };
+    use stats_io_lib::hardware::change::{detect_hardware_change, HardwareComponent};
+    use stats_io_lib::persistence::HardwareSnapshotStorage;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn gpu(model: &str) -> GPUInfo {
+        GPUInfo {
+            model: model.to_string(),
+            vendor: "NVIDIA".to_string(),
+            vram_total_mb: Some(8192),
+            driver_version: None,
+            pci_id: None,
+        }
+    }
+
+    fn create_hardware() -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                architecture: Some("x86_64".to_string()),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: Some(3000.0),
+                boost_clock_mhz: Some(4500.0),
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![gpu("RTX 3070")],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: Some(2),
+                speed_mhz: Some(3200),
+                modules: vec![],
+            },
+            storage_devices: vec![StorageInfo {
+                model: "Test SSD".to_string(),
+                capacity_mb: 1_000_000,
+                storage_type: StorageType::NVMe,
+                interface: None,
+                free_mb: None,
+            }],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    #[test]
+    fn test_no_changes_for_identical_hardware() {
+        let hardware = create_hardware();
+
+        assert!(detect_hardware_change(&hardware, &hardware.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_gpu_swap_detected() {
+        let prev = create_hardware();
+        let mut cur = create_hardware();
+        cur.gpus = vec![gpu("RTX 4080")];
+
+        let changes = detect_hardware_change(&prev, &cur);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].component, HardwareComponent::Gpu);
+        assert_eq!(changes[0].previous.as_deref(), Some("RTX 3070"));
+        assert_eq!(changes[0].current.as_deref(), Some("RTX 4080"));
+        assert_eq!(changes[0].description, "GPU changed from RTX 3070 to RTX 4080");
+    }
+
+    #[test]
+    fn test_ram_capacity_increase_detected() {
+        let prev = create_hardware();
+        let mut cur = create_hardware();
+        cur.memory.total_mb = 32768;
+
+        let changes = detect_hardware_change(&prev, &cur);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].component, HardwareComponent::Memory);
+        assert_eq!(changes[0].description, "RAM capacity increased from 16.0 GB to 32.0 GB");
+    }
+
+    #[test]
+    fn test_added_and_removed_devices() {
+        let prev = create_hardware();
+        let mut cur = create_hardware();
+        cur.gpus.push(gpu("RTX 3070"));
+        cur.storage_devices.clear();
+
+        let changes = detect_hardware_change(&prev, &cur);
+
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().any(|c| c.component == HardwareComponent::Gpu
+            && c.previous.is_none()
+            && c.description == "GPU added: RTX 3070"));
+        assert!(changes.iter().any(|c| c.component == HardwareComponent::Storage
+            && c.current.is_none()
+            && c.description == "Storage device removed: Test SSD"));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_storage_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = HardwareSnapshotStorage::new(temp_dir.path().join("last_hardware_config.json"));
+
+        assert!(storage.load().await.unwrap().is_none());
+
+        let hardware = create_hardware();
+        storage.save(&hardware).await.unwrap();
+        let loaded = storage.load().await.unwrap().unwrap();
+        assert!(detect_hardware_change(&hardware, &loaded).is_empty());
+    }
+}
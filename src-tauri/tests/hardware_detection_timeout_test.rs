@@ -0,0 +1,146 @@
+//! Unit tests for concurrent hardware detection with timeouts
+//!
+//! Tests per-step timeouts and partial results with mock sub-detectors following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use stats_io_lib::core::domain::{
+        CPUInfo, DisplayInfo, GPUInfo, MemoryInfo, StorageInfo, StorageType,
+    };
+    use stats_io_lib::core::error::HardwareError;
+    use stats_io_lib::hardware::detection::{detect_components, ComponentDetector};
+    use std::time::{Duration, Instant};
+
+    const STEP_TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// Detector whose steps take a configurable time
+    #[derive(Default)]
+    struct MockDetector {
+        cpu_delay: Duration,
+        gpu_delay: Duration,
+        storage_delay: Duration,
+        storage_error: bool,
+    }
+
+    #[async_trait]
+    impl ComponentDetector for MockDetector {
+        fn platform(&self) -> &str {
+            "mock"
+        }
+
+        async fn detect_cpu(&self) -> Result<CPUInfo, HardwareError> {
+            tokio::time::sleep(self.cpu_delay).await;
+            Ok(CPUInfo {
+                model: "Mock CPU".to_string(),
+                vendor: "Mock".to_string(),
+                architecture: None,
+                cores: 4,
+                threads: 8,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                core_max_frequencies_mhz: vec![],
+            })
+        }
+
+        async fn detect_memory(&self) -> Result<MemoryInfo, HardwareError> {
+            Ok(MemoryInfo {
+                total_mb: 8192,
+                channels: None,
+                speed_mhz: None,
+                modules: vec![],
+            })
+        }
+
+        async fn detect_gpus(&self) -> Result<Vec<GPUInfo>, HardwareError> {
+            tokio::time::sleep(self.gpu_delay).await;
+            Ok(vec![GPUInfo {
+                model: "Mock GPU".to_string(),
+                vendor: "Mock".to_string(),
+                vram_total_mb: Some(4096),
+                driver_version: None,
+                pci_id: None,
+            }])
+        }
+
+        async fn detect_storage(&self) -> Result<Vec<StorageInfo>, HardwareError> {
+            tokio::time::sleep(self.storage_delay).await;
+            if self.storage_error {
+                return Err(HardwareError::DetectionFailed("query failed".to_string()));
+            }
+            Ok(vec![StorageInfo {
+                model: "Mock SSD".to_string(),
+                capacity_mb: 512000,
+                storage_type: StorageType::SSD,
+                interface: None,
+                free_mb: None,
+            }])
+        }
+
+        async fn detect_displays(&self) -> Result<Vec<DisplayInfo>, HardwareError> {
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hung_step_times_out_with_partial_results() {
+        let detector = MockDetector {
+            storage_delay: Duration::from_secs(30),
+            ..Default::default()
+        };
+
+        let start = Instant::now();
+        let config = detect_components(&detector, STEP_TIMEOUT).await.unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5), "took {:?}", start.elapsed());
+
+        assert!(config.storage_devices.is_empty());
+        assert_eq!(config.cpu.model, "Mock CPU");
+        assert_eq!(config.gpus[0].model, "Mock GPU");
+        assert_eq!(config.memory.total_mb, 8192);
+        assert_eq!(config.metadata.platform, "mock");
+        assert_eq!(
+            config.metadata.warnings,
+            vec!["Storage detection timed out; results may be incomplete.".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_steps_run_concurrently() {
+        let detector = MockDetector {
+            cpu_delay: Duration::from_millis(300),
+            gpu_delay: Duration::from_millis(300),
+            storage_delay: Duration::from_millis(300),
+            ..Default::default()
+        };
+
+        let start = Instant::now();
+        let config = detect_components(&detector, Duration::from_secs(5)).await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(800), "took {:?}", start.elapsed());
+        assert!(config.metadata.warnings.is_empty());
+        assert_eq!(config.storage_devices.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_optional_step_is_reported() {
+        let detector = MockDetector {
+            storage_error: true,
+            ..Default::default()
+        };
+
+        let config = detect_components(&detector, STEP_TIMEOUT).await.unwrap();
+        assert!(config.storage_devices.is_empty());
+        assert!(config.metadata.warnings[0].starts_with("Storage detection failed"));
+        assert!(config.motherboard.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_required_step_timeout_fails_detection() {
+        let detector = MockDetector {
+            cpu_delay: Duration::from_secs(30),
+            ..Default::default()
+        };
+
+        let result = detect_components(&detector, STEP_TIMEOUT).await;
+        assert!(matches!(result, Err(HardwareError::Timeout(_))));
+    }
+}
@@ -0,0 +1,190 @@
+//! Unit tests for imported hardware comparison
+//!
+//! Tests CPU, GPU and RAM class differences between imported and local configurations following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::comparison::{compare_imported_run, compare_runs, diff_hardware, ClassDifference};
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, GPUInfo, HardwareConfig, MemoryInfo, MetricSample, MetricType, Run,
+    };
+    use stats_io_lib::hardware::change::HardwareComponent;
+    use stats_io_lib::persistence::reports::{generate_comparison_report, ReportConfig, ReportFormat};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn create_hardware(cpu: &str, gpus: &[(&str, u64)], ram_mb: u64) -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: cpu.to_string(),
+                vendor: "Test Vendor".to_string(),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                architecture: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: gpus
+                .iter()
+                .map(|(model, vram_mb)| GPUInfo {
+                    model: model.to_string(),
+                    vendor: "NVIDIA".to_string(),
+                    vram_total_mb: Some(*vram_mb),
+                    driver_version: None,
+                    pci_id: None,
+                })
+                .collect(),
+            memory: MemoryInfo {
+                total_mb: ram_mb,
+                channels: None,
+                speed_mhz: Some(3200),
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    fn create_run(gpu: f64) -> Run {
+        let samples = (0..10)
+            .map(|_| MetricSample::new(MetricType::GpuUtilization, gpu, "GPU"))
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("gpu".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    #[test]
+    fn test_lower_class_imported_gpu_is_reported() {
+        let imported = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 3060", 12288)], 32768);
+        let local = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 4080", 16384)], 32768);
+
+        let diff = diff_hardware(&imported, &local);
+
+        assert_eq!(diff.differences.len(), 1);
+        let gpu = &diff.differences[0];
+        assert_eq!(gpu.component, HardwareComponent::Gpu);
+        assert_eq!(gpu.imported, "NVIDIA GeForce RTX 3060 (12 GB)");
+        assert_eq!(gpu.local, "NVIDIA GeForce RTX 4080 (16 GB)");
+        assert_eq!(gpu.imported_class.as_deref(), Some("Entry-level"));
+        assert_eq!(gpu.local_class.as_deref(), Some("Enthusiast"));
+        assert_eq!(gpu.difference, ClassDifference::Lower);
+        assert!(gpu.description.contains("lower class"));
+        assert!(diff.caption.contains("RTX 3060"));
+        assert!(diff.caption.contains("RTX 4080"));
+    }
+
+    #[test]
+    fn test_identical_hardware_has_no_differences() {
+        let hardware = create_hardware("Intel Core i5-13600K", &[("NVIDIA GeForce RTX 4070", 12288)], 16384);
+
+        let diff = diff_hardware(&hardware, &hardware.clone());
+
+        assert!(diff.differences.is_empty());
+        assert!(diff.caption.contains("same CPU, GPU and RAM"));
+    }
+
+    #[test]
+    fn test_cpu_and_ram_classes_are_compared() {
+        let imported = create_hardware("Intel Core i9-14900K", &[("NVIDIA GeForce RTX 4070", 12288)], 16384);
+        let local = create_hardware("Intel Core i5-12400", &[("NVIDIA GeForce RTX 4070", 12288)], 32768);
+
+        let diff = diff_hardware(&imported, &local);
+
+        let components: Vec<HardwareComponent> = diff.differences.iter().map(|d| d.component).collect();
+        assert_eq!(components, vec![HardwareComponent::Cpu, HardwareComponent::Memory]);
+        assert_eq!(diff.differences[0].difference, ClassDifference::Higher);
+        assert_eq!(diff.differences[1].difference, ClassDifference::Lower);
+        assert_eq!(diff.differences[1].imported, "16 GB @ 3200 MHz");
+    }
+
+    #[test]
+    fn test_same_tier_models_are_same_class() {
+        let imported = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 4090", 24576)], 32768);
+        let local = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 4080", 16384)], 32768);
+
+        let diff = diff_hardware(&imported, &local);
+
+        assert_eq!(diff.differences[0].difference, ClassDifference::Same);
+        assert!(diff.caption.contains("same class"));
+    }
+
+    #[test]
+    fn test_discrete_gpu_is_compared_over_integrated() {
+        let imported = create_hardware(
+            "AMD Ryzen 7 7800X3D",
+            &[("AMD Radeon(TM) Graphics", 512), ("NVIDIA GeForce RTX 3060", 12288)],
+            32768,
+        );
+        let local = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 3060", 12288)], 32768);
+
+        assert!(diff_hardware(&imported, &local).differences.is_empty());
+    }
+
+    #[test]
+    fn test_unlisted_models_have_unknown_class() {
+        let imported = create_hardware("Test CPU A", &[], 32768);
+        let local = create_hardware("Test CPU B", &[], 32768);
+
+        let diff = diff_hardware(&imported, &local);
+
+        assert_eq!(diff.differences.len(), 1);
+        assert_eq!(diff.differences[0].difference, ClassDifference::Unknown);
+        assert_eq!(diff.differences[0].imported_class, None);
+    }
+
+    #[test]
+    fn test_comparison_report_is_captioned() {
+        let imported = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 3060", 12288)], 32768);
+        let local = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 4080", 16384)], 32768);
+        let run1 = create_run(95.0);
+        let run2 = create_run(70.0);
+        let mut comparison = compare_runs(&run1, &run2);
+        comparison.hardware_diff = Some(diff_hardware(&imported, &local));
+
+        let config = ReportConfig { format: ReportFormat::Text, ..ReportConfig::default() };
+        let text = generate_comparison_report(&comparison, &run1, &run2, &config);
+        assert!(text.contains("Hardware: The imported session ran on different hardware"));
+        assert!(text.contains("the imported GPU is a lower class"));
+
+        let config = ReportConfig { format: ReportFormat::Html, ..ReportConfig::default() };
+        let html = generate_comparison_report(&comparison, &run1, &run2, &config);
+        assert!(html.contains("hardware-differences"));
+    }
+
+    #[test]
+    fn test_imported_run_comparison_carries_hardware_diff() {
+        let imported = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 3060", 12288)], 32768);
+        let local = create_hardware("AMD Ryzen 7 7800X3D", &[("NVIDIA GeForce RTX 4080", 16384)], 32768);
+        let run1 = create_run(95.0);
+        let run2 = create_run(70.0);
+
+        let comparison = compare_imported_run(&run1, &imported, &run2, &local, 5.0);
+        let diff = comparison.hardware_diff.expect("hardware diff");
+        assert_eq!(diff.differences.len(), 1);
+        assert_eq!(diff.differences[0].component, HardwareComponent::Gpu);
+        assert_eq!(comparison.run1_id, run1.id.to_string());
+
+        // A session recorded on this PC has nothing to caption
+        let same = compare_imported_run(&run1, &local, &run2, &local, 5.0);
+        assert!(same.hardware_diff.is_none());
+    }
+}
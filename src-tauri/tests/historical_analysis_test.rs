@@ -0,0 +1,104 @@
+//! Unit tests for historical run analysis
+//!
+//! Tests analyzing saved runs whose metrics lie in the past following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, Run, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::sessions::analyze_run;
+    use chrono::{DateTime, Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn cpu_bound_samples(end: DateTime<Utc>, seconds: i64) -> Vec<MetricSample> {
+        (0..seconds)
+            .map(|i| MetricSample {
+                timestamp: end - Duration::seconds(seconds - 1 - i),
+                metric_type: MetricType::CpuUtilization,
+                value: 98.0,
+                unit: "percent".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect()
+    }
+
+    fn rendering_profile() -> WorkloadProfile {
+        WorkloadProfile {
+            id: "rendering".to_string(),
+            name: "Rendering".to_string(),
+            workload_type: WorkloadType::Rendering,
+            parameters: HashMap::new(),
+            threshold_overrides: None,
+        }
+    }
+
+    fn has_cpu_bottleneck(result: &stats_io_lib::core::domain::BottleneckAnalysisResult) -> bool {
+        result
+            .bottlenecks
+            .iter()
+            .any(|b| b.bottleneck_type == BottleneckType::Cpu)
+    }
+
+    #[test]
+    fn test_live_analysis_ignores_hour_old_samples() {
+        let samples = cpu_bound_samples(Utc::now() - Duration::hours(1), 60);
+        let result = AnalysisEngine::new().analyze(&samples, None, None);
+        assert!(result.bottlenecks.is_empty());
+    }
+
+    #[test]
+    fn test_recorded_analysis_windows_to_newest_sample() {
+        let samples = cpu_bound_samples(Utc::now() - Duration::hours(1), 60);
+        let result = AnalysisEngine::new().analyze_recorded(&samples, None, None);
+        assert!(has_cpu_bottleneck(&result));
+    }
+
+    #[test]
+    fn test_analyze_at_explicit_reference_time() {
+        let end = Utc::now() - Duration::hours(1);
+        let samples = cpu_bound_samples(end, 60);
+        let engine = AnalysisEngine::new();
+
+        assert!(has_cpu_bottleneck(&engine.analyze_at(&samples, Some(30), None, end)));
+        // A window ending before the capture started sees no samples
+        let before = end - Duration::hours(1);
+        assert!(engine.analyze_at(&samples, Some(30), None, before).bottlenecks.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_hour_old_run() {
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert(
+            "cpu".to_string(),
+            cpu_bound_samples(Utc::now() - Duration::hours(1), 120),
+        );
+        let run = Run {
+            id: Uuid::new_v4(),
+            name: "Yesterday's capture".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        };
+
+        let result = analyze_run(&AnalysisEngine::new(), &run, &rendering_profile(), None)
+            .expect("run has samples");
+        assert!(has_cpu_bottleneck(&result));
+    }
+
+    #[test]
+    fn test_analyze_empty_run_returns_none() {
+        let run = Run {
+            id: Uuid::new_v4(),
+            name: "Empty".to_string(),
+            metrics_streams: HashMap::new(),
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        };
+        assert!(analyze_run(&AnalysisEngine::new(), &run, &rendering_profile(), None).is_none());
+    }
+}
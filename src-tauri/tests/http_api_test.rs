@@ -0,0 +1,254 @@
+//! Unit tests for the HTTP JSON API
+//!
+//! Tests the read-only endpoints against an in-process server following TESTING_PLAN.md.
+
+#![cfg(feature = "http-api")]
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MetricSample, MetricType,
+        Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::http_api::{serve, HttpApiConfig, HttpApiServer};
+    use stats_io_lib::metrics::{init_metrics_collector, MetricsCollectorConfig};
+    use stats_io_lib::persistence::SessionStorage;
+    use chrono::{Duration, Utc};
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use uuid::Uuid;
+
+    fn create_test_session() -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Test Profile".to_string(),
+                workload_type: WorkloadType::General,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![],
+        }
+    }
+
+    async fn start_server(dir: &TempDir, token: Option<&str>) -> HttpApiServer {
+        let config = HttpApiConfig {
+            port: 0,
+            token: token.map(str::to_string),
+            ..Default::default()
+        };
+        serve(config, SessionStorage::new(dir.path().to_path_buf()))
+            .await
+            .expect("server should bind")
+    }
+
+    async fn request(addr: SocketAddr, method: &str, path: &str, token: Option<&str>) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let auth = token
+            .map(|t| format!("Authorization: Bearer {}\r\n", t))
+            .unwrap_or_default();
+        let head = format!("{} {} HTTP/1.1\r\nHost: localhost\r\n{}\r\n", method, path, auth);
+        stream.write_all(head.as_bytes()).await.unwrap();
+
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw).await.unwrap();
+        let (head, body) = raw.split_once("\r\n\r\n").expect("response has a body");
+        assert!(head.contains("Content-Type: application/json"));
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).expect("body is JSON"))
+    }
+
+    async fn get(addr: SocketAddr, path: &str) -> (u16, Value) {
+        request(addr, "GET", path, None).await
+    }
+
+    #[test]
+    fn test_default_config_binds_localhost() {
+        let config = HttpApiConfig::default();
+        assert!(config.bind_address.is_loopback());
+        assert!(config.token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sessions_endpoints() {
+        let dir = TempDir::new().unwrap();
+        let session = create_test_session();
+        SessionStorage::new(dir.path().to_path_buf())
+            .save_session(&session)
+            .await
+            .unwrap();
+        let server = start_server(&dir, None).await;
+
+        let (status, body) = get(server.local_addr(), "/sessions").await;
+        assert_eq!(status, 200);
+        assert_eq!(body, serde_json::json!([session.id.to_string()]));
+
+        let (status, body) = get(server.local_addr(), &format!("/sessions/{}", session.id)).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["id"], session.id.to_string());
+        assert_eq!(body["hardware_config_snapshot"]["cpu"]["model"], "Test CPU");
+        assert!(body["runs"].is_array());
+
+        server.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_session_errors_use_command_error_shape() {
+        let dir = TempDir::new().unwrap();
+        let server = start_server(&dir, None).await;
+
+        let (status, body) = get(server.local_addr(), "/sessions/not-a-uuid").await;
+        assert_eq!(status, 400);
+        assert_eq!(body["kind"], "validation");
+        assert_eq!(body["code"], "INVALID_SESSION_ID");
+
+        let (status, body) = get(server.local_addr(), &format!("/sessions/{}", Uuid::new_v4())).await;
+        assert_eq!(status, 404);
+        assert_eq!(body["code"], "PERSISTENCE_FILE_NOT_FOUND");
+
+        let (status, _) = get(server.local_addr(), "/unknown").await;
+        assert_eq!(status, 404);
+
+        server.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_hardware_endpoint() {
+        let dir = TempDir::new().unwrap();
+        let server = start_server(&dir, None).await;
+
+        let (status, body) = get(server.local_addr(), "/hardware").await;
+        assert_eq!(status, 200);
+        assert!(body["cpu"]["model"].is_string());
+        assert!(body["memory"]["total_mb"].is_number());
+        assert!(body["gpus"].is_array());
+
+        server.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_latest_metrics_endpoint() {
+        let collector = init_metrics_collector(MetricsCollectorConfig::default());
+        let latest = Utc::now();
+        let samples: Vec<MetricSample> = [latest - Duration::seconds(1), latest]
+            .iter()
+            .map(|timestamp| MetricSample {
+                timestamp: *timestamp,
+                metric_type: MetricType::CpuUtilization,
+                value: 42.0,
+                unit: "percent".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect();
+        collector.record_samples(&samples).await;
+
+        let dir = TempDir::new().unwrap();
+        let server = start_server(&dir, None).await;
+
+        let (status, body) = get(server.local_addr(), "/metrics/latest").await;
+        assert_eq!(status, 200);
+        let samples = body.as_array().unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0]["metric_type"], "cpu_utilization");
+        assert_eq!(samples[0]["value"], 42.0);
+
+        server.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_token_is_required_when_configured() {
+        let dir = TempDir::new().unwrap();
+        let server = start_server(&dir, Some("secret")).await;
+
+        let (status, body) = get(server.local_addr(), "/sessions").await;
+        assert_eq!(status, 401);
+        assert_eq!(body["code"], "HTTP_UNAUTHORIZED");
+
+        let (status, _) = request(server.local_addr(), "GET", "/sessions", Some("wrong")).await;
+        assert_eq!(status, 401);
+        let (status, _) = request(server.local_addr(), "GET", "/sessions", Some("secreT")).await;
+        assert_eq!(status, 401);
+
+        let (status, body) = request(server.local_addr(), "GET", "/sessions", Some("secret")).await;
+        assert_eq!(status, 200);
+        assert!(body.is_array());
+
+        server.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_idle_connection_times_out() {
+        let dir = TempDir::new().unwrap();
+        let config = HttpApiConfig {
+            port: 0,
+            read_timeout: std::time::Duration::from_millis(100),
+            ..Default::default()
+        };
+        let server = serve(config, SessionStorage::new(dir.path().to_path_buf()))
+            .await
+            .expect("server should bind");
+
+        // Start a request but never finish the head
+        let mut stream = TcpStream::connect(server.local_addr()).await.unwrap();
+        stream.write_all(b"GET /sessions HTTP/1.1\r\n").await.unwrap();
+
+        let mut raw = String::new();
+        tokio::time::timeout(std::time::Duration::from_secs(5), stream.read_to_string(&mut raw))
+            .await
+            .expect("server should close the idle connection")
+            .unwrap();
+        assert!(raw.starts_with("HTTP/1.1 408 Request Timeout"), "{}", raw);
+        assert!(raw.contains("HTTP_REQUEST_TIMEOUT"));
+
+        server.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_api_is_read_only() {
+        let dir = TempDir::new().unwrap();
+        let server = start_server(&dir, None).await;
+
+        let (status, body) = request(server.local_addr(), "DELETE", "/sessions", None).await;
+        assert_eq!(status, 405);
+        assert_eq!(body["code"], "HTTP_METHOD_NOT_ALLOWED");
+
+        server.shutdown();
+    }
+}
@@ -0,0 +1,332 @@
+//! Unit tests for hybrid graphics misrouting detection
+//!
+//! Tests detection of games running on the integrated GPU following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::{
+        classify_gpu, detect_hybrid_graphics_misrouting, GpuKind, HybridGraphicsRule,
+    };
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckType, CPUInfo, DetectionMetadata, GPUInfo, HardwareConfig, MemoryInfo, MetricSample, MetricType,
+        WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::error::MetricsError;
+    use stats_io_lib::core::interfaces::{
+        CpuMetricsProvider, GpuMetricsProvider, MemoryMetricsProvider, SensorProvider,
+        StorageMetricsProvider,
+    };
+    use stats_io_lib::metrics::collector::match_gpu_model;
+    use stats_io_lib::metrics::models::{
+        CpuMetrics, GpuAdapterUtilization, GpuMetrics, MemoryMetrics, SensorReading, StorageMetrics,
+    };
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    const IGPU: &str = "Intel(R) UHD Graphics 630";
+    const DGPU: &str = "NVIDIA GeForce RTX 3060 Laptop GPU";
+
+    fn create_gpu(model: &str, vendor: &str) -> GPUInfo {
+        GPUInfo {
+            model: model.to_string(),
+            vendor: vendor.to_string(),
+            vram_total_mb: None,
+            driver_version: None,
+            pci_id: None,
+        }
+    }
+
+    fn hybrid_gpus() -> Vec<GPUInfo> {
+        vec![create_gpu(IGPU, "Intel"), create_gpu(DGPU, "NVIDIA")]
+    }
+
+    fn profile(workload_type: WorkloadType) -> WorkloadProfile {
+        WorkloadProfile {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            workload_type,
+            parameters: HashMap::new(),
+            threshold_overrides: None,
+        }
+    }
+
+    /// 30 seconds of per-GPU utilization; a `None` dGPU reading is a sleeping GPU
+    fn create_metrics(igpu: f64, dgpu: Option<f64>) -> Vec<MetricSample> {
+        let now = Utc::now();
+        (0..30)
+            .flat_map(|i| {
+                let timestamp = now - Duration::seconds(30 - i);
+                let mut samples = vec![
+                    MetricSample::new(MetricType::GpuUtilization, igpu, IGPU).with_timestamp(timestamp),
+                    MetricSample::new(MetricType::CpuUtilization, 45.0, "CPU").with_timestamp(timestamp),
+                ];
+                if let Some(dgpu) = dgpu {
+                    samples.push(MetricSample::new(MetricType::GpuUtilization, dgpu, DGPU).with_timestamp(timestamp));
+                }
+                samples
+            })
+            .collect()
+    }
+
+    fn models(kind: GpuKind) -> Vec<String> {
+        hybrid_gpus()
+            .into_iter()
+            .filter(|gpu| classify_gpu(gpu) == Some(kind))
+            .map(|gpu| gpu.model)
+            .collect()
+    }
+
+    #[test]
+    fn test_classify_gpus() {
+        assert_eq!(classify_gpu(&create_gpu(IGPU, "Intel")), Some(GpuKind::Integrated));
+        assert_eq!(classify_gpu(&create_gpu("AMD Radeon(TM) Graphics", "AMD")), Some(GpuKind::Integrated));
+        assert_eq!(classify_gpu(&create_gpu("AMD Radeon 780M", "AMD")), Some(GpuKind::Integrated));
+        assert_eq!(classify_gpu(&create_gpu(DGPU, "NVIDIA")), Some(GpuKind::Discrete));
+        assert_eq!(classify_gpu(&create_gpu("AMD Radeon RX 7600M XT", "AMD")), Some(GpuKind::Discrete));
+        assert_eq!(classify_gpu(&create_gpu("Unknown GPU", "Unknown")), None);
+    }
+
+    #[test]
+    fn test_loaded_igpu_with_idle_dgpu_is_flagged() {
+        let integrated = models(GpuKind::Integrated);
+        let discrete = models(GpuKind::Discrete);
+
+        for dgpu in [Some(3.0), None] {
+            let bottleneck = detect_hybrid_graphics_misrouting(&create_metrics(95.0, dgpu), &integrated, &discrete)
+                .expect("misrouting");
+            assert_eq!(bottleneck.bottleneck_type, BottleneckType::HybridGraphics);
+            assert!(bottleneck.severity >= 80, "{}", bottleneck.severity);
+            assert!(bottleneck.summary.contains(IGPU) && bottleneck.summary.contains(DGPU));
+
+            assert_eq!(bottleneck.evidence.len(), 2);
+            assert_eq!(bottleneck.evidence[0].source_component.as_deref(), Some(IGPU));
+            assert_eq!(bottleneck.evidence[0].actual_value, 95.0);
+            assert_eq!(bottleneck.evidence[1].source_component.as_deref(), Some(DGPU));
+            assert_eq!(bottleneck.evidence[1].actual_value, dgpu.unwrap_or(0.0));
+        }
+    }
+
+    #[test]
+    fn test_busy_dgpu_or_quiet_igpu_is_not_flagged() {
+        let integrated = models(GpuKind::Integrated);
+        let discrete = models(GpuKind::Discrete);
+
+        assert!(detect_hybrid_graphics_misrouting(&create_metrics(95.0, Some(90.0)), &integrated, &discrete).is_none());
+        assert!(detect_hybrid_graphics_misrouting(&create_metrics(20.0, Some(3.0)), &integrated, &discrete).is_none());
+        // Samples from the primary GPU cannot be attributed to either device
+        let unattributed: Vec<MetricSample> = create_metrics(95.0, None)
+            .into_iter()
+            .map(|mut m| {
+                m.source_component = "GPU".to_string();
+                m
+            })
+            .collect();
+        assert!(detect_hybrid_graphics_misrouting(&unattributed, &integrated, &discrete).is_none());
+    }
+
+    #[test]
+    fn test_rule_needs_a_discrete_gpu() {
+        assert!(HybridGraphicsRule::from_gpus(&[create_gpu(IGPU, "Intel")]).is_none());
+        assert!(HybridGraphicsRule::from_gpus(&[create_gpu(DGPU, "NVIDIA")]).is_none());
+        assert!(HybridGraphicsRule::from_gpus(&[create_gpu(IGPU, "Intel"), create_gpu("Unknown GPU", "Unknown")]).is_none());
+        assert!(HybridGraphicsRule::from_gpus(&hybrid_gpus()).is_some());
+    }
+
+    #[test]
+    fn test_gaming_stream_on_igpu_gives_high_severity_advice() {
+        let mut engine = AnalysisEngine::new();
+        engine.register_rule(Box::new(HybridGraphicsRule::from_gpus(&hybrid_gpus()).unwrap()));
+        let metrics = create_metrics(95.0, Some(2.0));
+
+        let gaming = engine.analyze(&metrics, Some(60), Some(&profile(WorkloadType::Gaming)));
+        let misrouting = gaming
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::HybridGraphics)
+            .expect("hybrid graphics bottleneck");
+        assert!(misrouting.severity >= 80);
+
+        let insights = generate_insights(&gaming, None);
+        let advice: Vec<&str> = insights
+            .recommendations
+            .iter()
+            .filter(|r| r.bottleneck_type == Some(BottleneckType::HybridGraphics))
+            .map(|r| r.text())
+            .collect();
+        assert!(advice.iter().any(|t| t.contains("High performance")), "{:?}", advice);
+        assert!(advice.iter().any(|t| t.contains("charger")));
+        assert!(advice.iter().any(|t| t.contains("battery saver")));
+
+        // Other workloads are not checked
+        let rendering = engine.analyze(&metrics, Some(60), Some(&profile(WorkloadType::Rendering)));
+        assert!(rendering.bottlenecks.iter().all(|b| b.bottleneck_type != BottleneckType::HybridGraphics));
+    }
+
+    /// Providers of a laptop rendering a game on its integrated GPU
+    struct MisroutedLaptop;
+
+    #[async_trait]
+    impl GpuMetricsProvider for MisroutedLaptop {
+        async fn get_gpu_metrics(&self) -> Result<GpuMetrics, MetricsError> {
+            // nvidia-smi sees only the idle discrete GPU
+            Ok(GpuMetrics {
+                utilization: 0.02,
+                vram_used_mb: Some(300),
+                vram_total_mb: Some(6144),
+                temperature: Some(45.0),
+                hotspot_temperature: None,
+                clock_core_mhz: None,
+                clock_memory_mhz: None,
+                power_watts: None,
+                fan_speed_percent: None,
+                pcie_link: None,
+                memory_controller_load: None,
+                adapter_name: None,
+            })
+        }
+
+        async fn get_adapter_utilization(&self) -> Result<Vec<GpuAdapterUtilization>, MetricsError> {
+            Ok(vec![
+                GpuAdapterUtilization { adapter_name: IGPU.to_string(), utilization: 0.95 },
+                GpuAdapterUtilization { adapter_name: DGPU.to_string(), utilization: 0.02 },
+                GpuAdapterUtilization { adapter_name: "Microsoft Remote Display Adapter".to_string(), utilization: 0.5 },
+            ])
+        }
+    }
+
+    #[async_trait]
+    impl CpuMetricsProvider for MisroutedLaptop {
+        async fn get_cpu_metrics(&self) -> Result<CpuMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("CPU".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl MemoryMetricsProvider for MisroutedLaptop {
+        async fn get_memory_metrics(&self) -> Result<MemoryMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("Memory".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl StorageMetricsProvider for MisroutedLaptop {
+        async fn get_storage_metrics(&self) -> Result<StorageMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("Storage".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl SensorProvider for MisroutedLaptop {
+        async fn get_sensors(&self) -> Result<Vec<SensorReading>, MetricsError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn hardware(gpus: Vec<GPUInfo>) -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                architecture: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus,
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: None,
+                speed_mhz: None,
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    async fn collect(gpus: Vec<GPUInfo>) -> Vec<MetricSample> {
+        let provider = Arc::new(MisroutedLaptop);
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms: 10,
+            ..MetricsCollectorConfig::default()
+        };
+        let collector = MetricsCollector::with_providers(
+            config,
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider,
+        );
+        collector.set_hardware_config(&hardware(gpus)).await;
+
+        collector.start().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+        collector.stop().await;
+        collector.get_buffer().await
+    }
+
+    #[tokio::test]
+    async fn test_collected_adapter_utilization_flags_misrouting() {
+        let metrics = collect(hybrid_gpus()).await;
+
+        let igpu: Vec<&MetricSample> = metrics
+            .iter()
+            .filter(|m| m.metric_type == MetricType::GpuAdapterUtilization && m.source_component == IGPU)
+            .collect();
+        assert!(igpu.len() >= 5, "{}", igpu.len());
+        assert!(metrics.iter().all(|m| m.source_component != "Microsoft Remote Display Adapter"));
+
+        let mut engine = AnalysisEngine::new();
+        engine.register_rule(Box::new(HybridGraphicsRule::from_gpus(&hybrid_gpus()).unwrap()));
+        let result = engine.analyze(&metrics, Some(60), Some(&profile(WorkloadType::Gaming)));
+        let misrouting = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::HybridGraphics)
+            .expect("hybrid graphics bottleneck");
+        assert_eq!(misrouting.evidence[0].source_component.as_deref(), Some(IGPU));
+        assert_eq!(misrouting.evidence[1].source_component.as_deref(), Some(DGPU));
+    }
+
+    #[tokio::test]
+    async fn test_single_gpu_records_no_adapter_utilization() {
+        let metrics = collect(vec![create_gpu(DGPU, "NVIDIA")]).await;
+
+        assert!(metrics.iter().any(|m| m.metric_type == MetricType::GpuUtilization));
+        assert!(metrics.iter().all(|m| m.metric_type != MetricType::GpuAdapterUtilization));
+    }
+
+    #[test]
+    fn test_adapter_names_match_detected_models() {
+        let models = vec![IGPU.to_string(), DGPU.to_string()];
+        assert_eq!(match_gpu_model(IGPU, &models), Some(&models[0]));
+        assert_eq!(match_gpu_model("NVIDIA GeForce RTX 3060", &models), Some(&models[1]));
+        assert_eq!(match_gpu_model("Microsoft Basic Render Driver", &models), None);
+
+        // Linux detects some GPUs by vendor alone
+        let by_vendor = vec!["Intel Integrated Graphics".to_string(), "AMD GPU (detected)".to_string()];
+        assert_eq!(match_gpu_model("AMD Radeon RX 7600M XT", &by_vendor), Some(&by_vendor[1]));
+
+        // Two GPUs from one vendor cannot be told apart by vendor
+        let two_amd = vec!["AMD Radeon(TM) Graphics".to_string(), "AMD Radeon RX 7600M XT".to_string()];
+        assert_eq!(match_gpu_model("AMD GPU", &two_amd), None);
+        assert_eq!(match_gpu_model("AMD Radeon RX 7600M XT", &two_amd), Some(&two_amd[1]));
+    }
+}
@@ -0,0 +1,146 @@
+//! Unit tests for idle baseline capture and subtraction
+//!
+//! Tests baseline averaging, subtraction, and persistence following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::baseline::subtract_baseline;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use stats_io_lib::core::settings::{IdleBaseline, SettingsManager};
+    use chrono::{Duration, Utc};
+    use tempfile::TempDir;
+
+    fn create_cpu_stream(values: &[f64]) -> Vec<MetricSample> {
+        let start = Utc::now() - Duration::seconds(values.len() as i64);
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| MetricSample {
+                timestamp: start + Duration::seconds(i as i64),
+                metric_type: MetricType::CpuUtilization,
+                value: *value,
+                unit: "percent".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect()
+    }
+
+    fn sample(metric_type: MetricType, value: f64, source: &str) -> MetricSample {
+        MetricSample {
+            timestamp: Utc::now(),
+            metric_type,
+            value,
+            unit: "percent".to_string(),
+            source_component: source.to_string(),
+        }
+    }
+
+    fn create_idle_baseline() -> IdleBaseline {
+        let idle = create_cpu_stream(&[8.0, 10.0, 12.0]);
+        IdleBaseline::from_samples(&idle, 3)
+    }
+
+    #[test]
+    fn test_baseline_averages_idle_samples() {
+        let baseline = create_idle_baseline();
+
+        assert_eq!(baseline.duration_seconds, 3);
+        assert_eq!(baseline.average_for(&MetricType::CpuUtilization, "CPU"), Some(10.0));
+    }
+
+    #[test]
+    fn test_baseline_ignores_temperature() {
+        let samples = vec![MetricSample {
+            timestamp: Utc::now(),
+            metric_type: MetricType::Temperature,
+            value: 40.0,
+            unit: "Celsius".to_string(),
+            source_component: "CPU".to_string(),
+        }];
+
+        let baseline = IdleBaseline::from_samples(&samples, 1);
+
+        assert!(baseline.source_averages.is_empty());
+    }
+
+    #[test]
+    fn test_baseline_ignores_capacity_metrics() {
+        let samples = vec![
+            sample(MetricType::MemoryUsage, 40.0, "System"),
+            sample(MetricType::GpuVramUsagePercent, 20.0, "GPU 0"),
+            sample(MetricType::MemorySwapUsage, 5.0, "System"),
+            sample(MetricType::StorageQueueDepth, 1.0, "Disk 0"),
+        ];
+
+        let baseline = IdleBaseline::from_samples(&samples, 1);
+        let under_load = vec![sample(MetricType::MemoryUsage, 95.0, "System")];
+        let adjusted = subtract_baseline(&under_load, &baseline);
+
+        assert!(baseline.source_averages.is_empty());
+        assert_eq!(adjusted[0].value, 95.0);
+    }
+
+    #[test]
+    fn test_baseline_is_kept_per_source() {
+        let idle = vec![
+            sample(MetricType::GpuUtilization, 2.0, "GPU 0"),
+            sample(MetricType::GpuUtilization, 4.0, "GPU 0"),
+            sample(MetricType::GpuUtilization, 30.0, "GPU 1"),
+        ];
+        let baseline = IdleBaseline::from_samples(&idle, 2);
+
+        assert_eq!(baseline.average_for(&MetricType::GpuUtilization, "GPU 0"), Some(3.0));
+        assert_eq!(baseline.average_for(&MetricType::GpuUtilization, "GPU 1"), Some(30.0));
+
+        let under_load = vec![
+            sample(MetricType::GpuUtilization, 90.0, "GPU 0"),
+            sample(MetricType::GpuUtilization, 90.0, "GPU 1"),
+            sample(MetricType::GpuUtilization, 90.0, "GPU 2"),
+        ];
+        let values: Vec<f64> = subtract_baseline(&under_load, &baseline).iter().map(|s| s.value).collect();
+
+        // A source with no idle samples is left untouched
+        assert_eq!(values, vec![87.0, 60.0, 90.0]);
+    }
+
+    #[test]
+    fn test_subtract_idle_cpu_baseline() {
+        let baseline = create_idle_baseline();
+        let stream = create_cpu_stream(&[95.0, 60.0, 5.0]);
+
+        let adjusted = subtract_baseline(&stream, &baseline);
+
+        let values: Vec<f64> = adjusted.iter().map(|s| s.value).collect();
+        // Negative results are clamped to zero
+        assert_eq!(values, vec![85.0, 50.0, 0.0]);
+        assert_eq!(adjusted[0].timestamp, stream[0].timestamp);
+    }
+
+    #[test]
+    fn test_analysis_reports_workload_attributable_cpu() {
+        let baseline = create_idle_baseline();
+        let stream = create_cpu_stream(&[92.0; 10]);
+        let engine = AnalysisEngine::new();
+
+        let total = engine.analyze(&stream, Some(60), None);
+        let attributable = engine.analyze_with_baseline(&stream, Some(60), None, Some(&baseline));
+
+        assert!(total.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Cpu));
+        assert!(!attributable.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Cpu));
+    }
+
+    #[test]
+    fn test_baseline_persists_in_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        let mut manager = SettingsManager::new(settings_path.clone()).unwrap();
+        assert!(manager.get_settings().idle_baseline.is_none());
+
+        manager.update_idle_baseline(Some(create_idle_baseline())).unwrap();
+
+        let reloaded = SettingsManager::new(settings_path).unwrap();
+        let baseline = reloaded.get_settings().idle_baseline.clone().unwrap();
+        assert_eq!(baseline.average_for(&MetricType::CpuUtilization, "CPU"), Some(10.0));
+    }
+}
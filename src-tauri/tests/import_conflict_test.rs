@@ -0,0 +1,234 @@
+//! Unit tests for import conflict handling
+//!
+//! Tests duplicate session IDs on import in both storage backends following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MetricSample, MetricType, Run,
+        Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::persistence::export_import::{ImportConflictPolicy, ImportOutcome};
+    use stats_io_lib::persistence::{DatabaseStorage, SessionStorage};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_test_session(run_names: &[&str]) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: Some(Utc::now()),
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Baseline".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: run_names.iter().map(|name| create_test_run(name)).collect(),
+        }
+    }
+
+    fn create_test_run(name: &str) -> Run {
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert(
+            "cpu".to_string(),
+            vec![MetricSample::new(MetricType::CpuUtilization, 50.0, "CPU")],
+        );
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    /// A different session that reuses the ID of `stored`
+    fn create_duplicate(stored: &Session) -> Session {
+        let mut duplicate = create_test_session(&["Imported"]);
+        duplicate.id = stored.id;
+        duplicate.profile.name = "Imported".to_string();
+        duplicate
+    }
+
+    fn run_names(session: &Session) -> Vec<String> {
+        let mut names: Vec<String> = session.runs.iter().map(|r| r.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_default_policy_assigns_new_id() {
+        assert_eq!(ImportConflictPolicy::default(), ImportConflictPolicy::AssignNewId);
+        let policy: ImportConflictPolicy = serde_json::from_str("\"skip\"").unwrap();
+        assert_eq!(policy, ImportConflictPolicy::Skip);
+    }
+
+    #[tokio::test]
+    async fn test_file_storage_without_conflict() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().to_path_buf());
+        let session = create_test_session(&["Run"]);
+
+        let outcome = storage.import_session(session.clone(), ImportConflictPolicy::Skip).await.unwrap();
+        assert_eq!(outcome, ImportOutcome::Imported { id: session.id });
+        assert!(storage.load_session(&session.id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_file_storage_skip() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().to_path_buf());
+        let stored = create_test_session(&["Original A", "Original B"]);
+        storage.save_session(&stored).await.unwrap();
+
+        let outcome = storage
+            .import_session(create_duplicate(&stored), ImportConflictPolicy::Skip)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, ImportOutcome::Skipped { id: stored.id });
+        let loaded = storage.load_session(&stored.id).await.unwrap();
+        assert_eq!(loaded.profile.name, "Baseline");
+        assert_eq!(run_names(&loaded), vec!["Original A", "Original B"]);
+    }
+
+    #[tokio::test]
+    async fn test_file_storage_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().to_path_buf());
+        let stored = create_test_session(&["Original A", "Original B"]);
+        storage.save_session(&stored).await.unwrap();
+
+        let outcome = storage
+            .import_session(create_duplicate(&stored), ImportConflictPolicy::Overwrite)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, ImportOutcome::Overwritten { id: stored.id });
+        assert_eq!(storage.list_sessions().await.unwrap().len(), 1);
+        let loaded = storage.load_session(&stored.id).await.unwrap();
+        assert_eq!(loaded.profile.name, "Imported");
+        assert_eq!(run_names(&loaded), vec!["Imported"]);
+    }
+
+    #[tokio::test]
+    async fn test_file_storage_assign_new_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().to_path_buf());
+        let stored = create_test_session(&["Original"]);
+        storage.save_session(&stored).await.unwrap();
+        let duplicate = create_duplicate(&stored);
+        let duplicate_run_id = duplicate.runs[0].id;
+
+        let outcome = storage
+            .import_session(duplicate, ImportConflictPolicy::default())
+            .await
+            .unwrap();
+
+        let new_id = match outcome {
+            ImportOutcome::Reassigned { original_id, id } => {
+                assert_eq!(original_id, stored.id);
+                id
+            }
+            other => panic!("Expected reassignment, got {:?}", other),
+        };
+        assert_ne!(new_id, stored.id);
+        assert_eq!(storage.list_sessions().await.unwrap().len(), 2);
+
+        let original = storage.load_session(&stored.id).await.unwrap();
+        assert_eq!(run_names(&original), vec!["Original"]);
+        let imported = storage.load_session(&new_id).await.unwrap();
+        assert_eq!(imported.profile.name, "Imported");
+        assert_eq!(run_names(&imported), vec!["Imported"]);
+        assert_ne!(imported.runs[0].id, duplicate_run_id);
+    }
+
+    #[test]
+    fn test_database_skip() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(temp_dir.path().join("test.db")).unwrap();
+        let stored = create_test_session(&["Original A", "Original B"]);
+        storage.save_session(&stored).unwrap();
+
+        let outcome = storage
+            .import_session(create_duplicate(&stored), ImportConflictPolicy::Skip)
+            .unwrap();
+
+        assert_eq!(outcome, ImportOutcome::Skipped { id: stored.id });
+        let loaded = storage.load_session(&stored.id).unwrap();
+        assert_eq!(run_names(&loaded), vec!["Original A", "Original B"]);
+    }
+
+    #[test]
+    fn test_database_overwrite_drops_old_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(temp_dir.path().join("test.db")).unwrap();
+        let stored = create_test_session(&["Original A", "Original B"]);
+        storage.save_session(&stored).unwrap();
+
+        let outcome = storage
+            .import_session(create_duplicate(&stored), ImportConflictPolicy::Overwrite)
+            .unwrap();
+
+        assert_eq!(outcome, ImportOutcome::Overwritten { id: stored.id });
+        assert_eq!(storage.list_sessions().unwrap().len(), 1);
+        let loaded = storage.load_session(&stored.id).unwrap();
+        assert_eq!(loaded.profile.name, "Imported");
+        assert_eq!(run_names(&loaded), vec!["Imported"]);
+        assert!(storage.search_runs("Original").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_database_assign_new_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(temp_dir.path().join("test.db")).unwrap();
+        let stored = create_test_session(&["Original"]);
+        storage.save_session(&stored).unwrap();
+
+        let outcome = storage
+            .import_session(create_duplicate(&stored), ImportConflictPolicy::AssignNewId)
+            .unwrap();
+
+        let ImportOutcome::Reassigned { id: new_id, .. } = outcome else {
+            panic!("Expected reassignment, got {:?}", outcome);
+        };
+        assert_eq!(storage.list_sessions().unwrap().len(), 2);
+        assert_eq!(run_names(&storage.load_session(&stored.id).unwrap()), vec!["Original"]);
+        assert_eq!(run_names(&storage.load_session(&new_id).unwrap()), vec!["Imported"]);
+    }
+}
@@ -0,0 +1,47 @@
+//! Unit tests for locale-independent number parsing
+//!
+//! Tests decimal and thousands separators from non-US locales following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::metrics::parse_locale_number;
+    use stats_io_lib::metrics::providers::latency::parse_typeperf_output;
+
+    #[test]
+    fn test_both_conventions_parse_to_same_value() {
+        assert_eq!(parse_locale_number("1.234,56"), Some(1234.56));
+        assert_eq!(parse_locale_number("1234.56"), Some(1234.56));
+        assert_eq!(parse_locale_number("1,234.56"), Some(1234.56));
+        assert_eq!(parse_locale_number("1234,56"), Some(1234.56));
+    }
+
+    #[test]
+    fn test_thousands_separators() {
+        assert_eq!(parse_locale_number("1 234,56"), Some(1234.56));
+        assert_eq!(parse_locale_number("1\u{a0}234,56"), Some(1234.56));
+        assert_eq!(parse_locale_number("1'234.56"), Some(1234.56));
+        assert_eq!(parse_locale_number("1,234,567"), Some(1234567.0));
+        assert_eq!(parse_locale_number("1.234.567"), Some(1234567.0));
+    }
+
+    #[test]
+    fn test_plain_and_invalid_values() {
+        assert_eq!(parse_locale_number(" 42 "), Some(42.0));
+        assert_eq!(parse_locale_number("-0,5"), Some(-0.5));
+        assert_eq!(parse_locale_number(""), None);
+        assert_eq!(parse_locale_number("[N/A]"), None);
+        assert_eq!(parse_locale_number("1,2,3.4.5"), None);
+    }
+
+    #[test]
+    fn test_typeperf_with_comma_decimals() {
+        let snapshot = "\r
+\"(PDH-CSV 4.0)\",\"\\\\DESKTOP-1\\Processor Information(_Total)\\% DPC Time\",\"\\\\DESKTOP-1\\Processor Information(_Total)\\% Interrupt Time\"\r
+\"15.10.2026 14:03:21.512\",\"12,500000\",\"1,250000\"\r
+";
+        let parsed = parse_typeperf_output(snapshot).unwrap();
+
+        assert_eq!(parsed.dpc_time_percent, 12.5);
+        assert_eq!(parsed.interrupt_time_percent, 1.25);
+    }
+}
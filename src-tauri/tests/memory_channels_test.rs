@@ -0,0 +1,152 @@
+//! Unit tests for memory channel detection
+//!
+//! Tests channel inference from detected modules following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights_for_hardware;
+    use stats_io_lib::analysis::memory::{detect_channel_configuration, platform_channel_count};
+    use stats_io_lib::core::domain::{
+        BottleneckAnalysisResult, CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MemoryModule,
+    };
+    use stats_io_lib::hardware::memory::parse_physical_memory;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn memory(module_sizes_mb: &[u64], channels: Option<u32>) -> MemoryInfo {
+        MemoryInfo {
+            total_mb: module_sizes_mb.iter().sum(),
+            channels,
+            speed_mhz: Some(3200),
+            modules: module_sizes_mb
+                .iter()
+                .map(|&size_mb| MemoryModule {
+                    size_mb,
+                    speed_mhz: Some(3200),
+                    manufacturer: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn hardware_with(cpu_model: &str, memory: MemoryInfo) -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: cpu_model.to_string(),
+                vendor: "Test Vendor".to_string(),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                architecture: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![],
+            memory,
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    fn channel_recommendations(hardware: &HardwareConfig) -> Vec<String> {
+        let result = BottleneckAnalysisResult {
+            bottlenecks: vec![],
+            timestamp: Utc::now(),
+            coverage: None,
+        };
+        generate_insights_for_hardware(&result, None, &[], hardware)
+            .recommendations
+            .into_iter()
+            .filter(|r| r.id == "memory-channels")
+            .map(|r| r.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_single_stick_is_flagged() {
+        // Some adapters report the DIMM's sub-channels; one module is still single channel
+        let status = detect_channel_configuration(&memory(&[16384], Some(2)));
+        assert_eq!(status.active_channels, Some(1));
+        assert!(!status.mismatched_modules);
+        assert!(status.below_potential(2));
+
+        let recs = channel_recommendations(&hardware_with("Ryzen 5 5600G", memory(&[16384], None)));
+        assert_eq!(recs.len(), 1);
+        assert!(recs[0].contains("single-channel instead of dual-channel"), "{}", recs[0]);
+    }
+
+    #[test]
+    fn test_matched_dual_channel_kit_is_ok() {
+        let status = detect_channel_configuration(&memory(&[8192, 8192], None));
+        assert_eq!(status.active_channels, Some(2));
+        assert!(!status.mismatched_modules);
+        assert!(!status.below_potential(2));
+
+        assert!(channel_recommendations(&hardware_with("Ryzen 5 5600G", memory(&[8192, 8192], Some(2)))).is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_pair_and_quad_channel_platform() {
+        let recs = channel_recommendations(&hardware_with("Core i7-12700K", memory(&[8192, 16384], None)));
+        assert_eq!(recs.len(), 1);
+        assert!(recs[0].contains("differ in size"));
+
+        let threadripper = hardware_with("AMD Ryzen Threadripper 3960X", memory(&[16384, 16384], None));
+        assert_eq!(platform_channel_count(&threadripper.cpu), 4);
+        let recs = channel_recommendations(&threadripper);
+        assert!(recs[0].contains("dual-channel instead of quad-channel"), "{}", recs[0]);
+    }
+
+    #[test]
+    fn test_windows_placeholder_module_is_unknown() {
+        // The module older Windows snapshots recorded in place of the real ones
+        let placeholder = MemoryInfo {
+            total_mb: 32768,
+            channels: None,
+            speed_mhz: None,
+            modules: vec![MemoryModule {
+                size_mb: 32768,
+                speed_mhz: None,
+                manufacturer: None,
+            }],
+        };
+        assert_eq!(detect_channel_configuration(&placeholder).active_channels, None);
+        assert!(channel_recommendations(&hardware_with("Core i7-12700K", placeholder)).is_empty());
+    }
+
+    #[test]
+    fn test_physical_memory_rows_parsed() {
+        let rows = vec![
+            json!({"Capacity": "17179869184", "Speed": 3200, "ConfiguredClockSpeed": 3000, "Manufacturer": "Kingston "}),
+            json!({"Capacity": "17179869184", "Speed": 3200, "ConfiguredClockSpeed": 0, "Manufacturer": ""}),
+            json!({"Capacity": null, "Speed": null, "Manufacturer": "Empty slot"}),
+        ];
+        let modules = parse_physical_memory(&rows);
+
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0].size_mb, 16384);
+        assert_eq!(modules[0].speed_mhz, Some(3000));
+        assert_eq!(modules[0].manufacturer.as_deref(), Some("Kingston"));
+        assert_eq!(modules[1].speed_mhz, Some(3200));
+        assert_eq!(modules[1].manufacturer, None);
+
+        let memory = MemoryInfo { total_mb: 32768, channels: None, speed_mhz: Some(3000), modules };
+        assert_eq!(detect_channel_configuration(&memory).active_channels, Some(2));
+    }
+
+    #[test]
+    fn test_unknown_modules_are_not_flagged() {
+        assert_eq!(detect_channel_configuration(&memory(&[], None)).active_channels, None);
+        assert_eq!(detect_channel_configuration(&memory(&[], Some(2))).active_channels, Some(2));
+        assert!(channel_recommendations(&hardware_with("Apple M2", memory(&[], None))).is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+//! Unit tests for memory leak trend detection
+//!
+//! Tests telling climbing memory use apart from flat high usage following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::leak::{detect_memory_leak_trend, LEAK_MIN_SAMPLES};
+    use stats_io_lib::analysis::rules::analyze_bottlenecks;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType, Run};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    /// One memory reading per second for `seconds`, valued by `value_at(second)`
+    fn create_run(metric_type: MetricType, seconds: i64, value_at: impl Fn(i64) -> f64) -> Run {
+        let start = Utc::now() - Duration::seconds(seconds);
+        let samples: Vec<MetricSample> = (0..seconds)
+            .map(|i| {
+                MetricSample::new(metric_type.clone(), value_at(i), "Memory").with_timestamp(start + Duration::seconds(i))
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("memory".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    /// Small deterministic jitter so readings are not a perfect line
+    fn jitter(i: i64) -> f64 {
+        ((i * 7919) % 11) as f64 - 5.0
+    }
+
+    #[test]
+    fn test_ramping_memory_is_flagged() {
+        // 4 GB climbing by 5 MB per second for ten minutes
+        let run = create_run(MetricType::MemoryUsed, 600, |i| 4096.0 + 5.0 * i as f64 + jitter(i));
+
+        let trend = detect_memory_leak_trend(&run).expect("leak trend");
+
+        assert_eq!(trend.metric_type, MetricType::MemoryUsed);
+        assert!((trend.slope_per_minute - 300.0).abs() < 5.0, "{}", trend.slope_per_minute);
+        assert!(trend.growth_percent > 60.0);
+        assert!(trend.r_squared > 0.99);
+        assert!(trend.summary.contains("leak"));
+        assert_eq!(trend.recommendation.id, "memory-leak");
+        assert!(trend.recommendation.text.contains("Restart"));
+    }
+
+    #[test]
+    fn test_flat_high_memory_is_not_a_leak() {
+        // Memory nearly full but steady: a capacity problem, not a leak
+        let run = create_run(MetricType::MemoryUsage, 600, |i| 95.0 + jitter(i) / 10.0);
+
+        assert!(detect_memory_leak_trend(&run).is_none());
+
+        let samples: Vec<MetricSample> = run.metrics_streams.values().flatten().cloned().collect();
+        let result = analyze_bottlenecks(&samples, 600, None);
+        assert!(result.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Ram));
+    }
+
+    #[test]
+    fn test_released_memory_is_not_a_leak() {
+        // Climbs and is collected every two minutes
+        let run = create_run(MetricType::MemoryUsed, 600, |i| 4096.0 + 10.0 * (i % 120) as f64);
+
+        assert!(detect_memory_leak_trend(&run).is_none());
+    }
+
+    #[test]
+    fn test_short_runs_are_not_judged() {
+        let run = create_run(MetricType::MemoryUsed, 30, |i| 4096.0 + 50.0 * i as f64);
+        assert!(detect_memory_leak_trend(&run).is_none());
+
+        let run = create_run(MetricType::MemoryUsed, LEAK_MIN_SAMPLES as i64 - 1, |i| 4096.0 + 50.0 * i as f64);
+        assert!(detect_memory_leak_trend(&run).is_none());
+    }
+
+    #[test]
+    fn test_percent_usage_is_used_without_absolute_readings() {
+        let run = create_run(MetricType::MemoryUsage, 600, |i| 40.0 + 0.05 * i as f64);
+
+        let trend = detect_memory_leak_trend(&run).expect("leak trend");
+
+        assert_eq!(trend.metric_type, MetricType::MemoryUsage);
+        assert!(trend.summary.contains('%'));
+    }
+}
@@ -0,0 +1,157 @@
+//! Unit tests for RAM usage normalization
+//!
+//! Tests that memory samples are expressed both as percent and MB following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType};
+    use stats_io_lib::metrics::utils::normalize_memory_samples;
+    use stats_io_lib::persistence::export_import::import_session;
+    use chrono::{Duration, Utc};
+    use tempfile::TempDir;
+
+    fn values(samples: &[MetricSample], metric_type: MetricType) -> Vec<f64> {
+        samples.iter().filter(|s| s.metric_type == metric_type).map(|s| s.value).collect()
+    }
+
+    #[tokio::test]
+    async fn test_imported_megabytes_become_percent_of_total() {
+        let start = Utc::now();
+        let samples: Vec<serde_json::Value> = [4096.0, 8192.0, 12288.0]
+            .iter()
+            .enumerate()
+            .map(|(i, used)| {
+                serde_json::json!({
+                    "timestamp": start + Duration::seconds(i as i64),
+                    "metric_type": "memory_usage",
+                    "value": used,
+                    "unit": "MB",
+                    "source_component": "Memory"
+                })
+            })
+            .collect();
+        let document = serde_json::json!({
+            "metrics_schema_version": 1,
+            "session": {
+                "id": uuid::Uuid::new_v4(),
+                "start_time": start,
+                "end_time": null,
+                "hardware_config_snapshot": {
+                    "cpu": {"model": "Test CPU", "vendor": "Test", "cores": 8, "threads": 16},
+                    "gpus": [],
+                    "memory": {"total_mb": 16_384, "modules": []},
+                    "storage_devices": [],
+                    "displays": [],
+                    "metadata": {"detection_time": start, "platform": "Test", "warnings": [], "schema_version": 1}
+                },
+                "profile": {"id": "general", "name": "General", "workload_type": "general", "parameters": {}, "threshold_overrides": null},
+                "runs": [{
+                    "id": uuid::Uuid::new_v4(),
+                    "name": "Imported",
+                    "metrics_streams": {"memory": samples},
+                    "analysis_result": null
+                }]
+            }
+        });
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.json");
+        std::fs::write(&path, serde_json::to_string(&document).unwrap()).unwrap();
+
+        let session = import_session(&path).await.unwrap();
+        let memory = &session.runs[0].metrics_streams["memory"];
+
+        assert_eq!(values(memory, MetricType::MemoryUsage), vec![25.0, 50.0, 75.0]);
+        assert!(memory
+            .iter()
+            .filter(|s| s.metric_type == MetricType::MemoryUsage)
+            .all(|s| s.unit == "percent"));
+        assert_eq!(values(memory, MetricType::MemoryUsed), vec![4096.0, 8192.0, 12288.0]);
+    }
+
+    #[tokio::test]
+    async fn test_separate_memory_streams_gain_no_duplicates() {
+        // Benchmark captures keep one stream per metric type
+        let start = Utc::now();
+        let stream = |metric_type: &str, unit: &str, values: [f64; 3]| -> Vec<serde_json::Value> {
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    serde_json::json!({
+                        "timestamp": start + Duration::milliseconds(10 * i as i64),
+                        "metric_type": metric_type,
+                        "value": value,
+                        "unit": unit,
+                        "source_component": "Memory"
+                    })
+                })
+                .collect()
+        };
+        let document = serde_json::json!({
+            "metrics_schema_version": 1,
+            "session": {
+                "id": uuid::Uuid::new_v4(),
+                "start_time": start,
+                "end_time": null,
+                "hardware_config_snapshot": {
+                    "cpu": {"model": "Test CPU", "vendor": "Test", "cores": 8, "threads": 16},
+                    "gpus": [],
+                    "memory": {"total_mb": 16_384, "modules": []},
+                    "storage_devices": [],
+                    "displays": [],
+                    "metadata": {"detection_time": start, "platform": "Test", "warnings": [], "schema_version": 1}
+                },
+                "profile": {"id": "general", "name": "General", "workload_type": "general", "parameters": {}, "threshold_overrides": null},
+                "runs": [{
+                    "id": uuid::Uuid::new_v4(),
+                    "name": "Benchmark capture (10 ms)",
+                    "metrics_streams": {
+                        "memory_usage": stream("memory_usage", "percent", [25.0, 50.0, 75.0]),
+                        "memory_used": stream("memory_used", "MB", [4096.0, 8192.0, 12288.0])
+                    },
+                    "analysis_result": null
+                }]
+            }
+        });
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.json");
+        std::fs::write(&path, serde_json::to_string(&document).unwrap()).unwrap();
+
+        let session = import_session(&path).await.unwrap();
+        let streams = &session.runs[0].metrics_streams;
+        assert_eq!(streams["memory_usage"].len(), 3);
+        assert_eq!(streams["memory_used"].len(), 3);
+        assert_eq!(streams.len(), 2);
+    }
+
+    #[test]
+    fn test_percent_samples_gain_megabytes() {
+        let now = Utc::now();
+        let mut samples = vec![
+            MetricSample::new(MetricType::MemoryUsage, 50.0, "Memory").with_timestamp(now),
+            MetricSample::new(MetricType::MemoryUsage, 25.0, "Memory").with_timestamp(now + Duration::seconds(1)),
+            MetricSample::new(MetricType::MemoryUsed, 4096.0, "Memory").with_timestamp(now + Duration::seconds(1)),
+        ];
+        normalize_memory_samples(&mut samples, Some(16_384));
+
+        // The second tick already has both representations
+        assert_eq!(samples.len(), 4);
+        assert_eq!(values(&samples, MetricType::MemoryUsed), vec![4096.0, 8192.0]);
+    }
+
+    #[test]
+    fn test_unknown_total_only_relabels() {
+        let mut samples = vec![
+            MetricSample {
+                unit: "GB".to_string(),
+                ..MetricSample::new(MetricType::MemoryUsage, 12.0, "Memory")
+            },
+            MetricSample::new(MetricType::MemoryUsage, 40.0, "Memory"),
+        ];
+        normalize_memory_samples(&mut samples, None);
+
+        assert_eq!(samples.len(), 2);
+        assert_eq!(values(&samples, MetricType::MemoryUsed), vec![12.0 * 1024.0]);
+        assert_eq!(values(&samples, MetricType::MemoryUsage), vec![40.0]);
+    }
+}
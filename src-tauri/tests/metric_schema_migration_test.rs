@@ -0,0 +1,217 @@
+//! Unit tests for metric sample schema migration
+//!
+//! Tests upgrading older sample arrays during import following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run, Session, GPU_HOTSPOT_SOURCE};
+    use stats_io_lib::core::error::PersistenceError;
+    use stats_io_lib::persistence::database::DatabaseStorage;
+    use stats_io_lib::persistence::export_import::{export_run, import_run, import_session};
+    use stats_io_lib::persistence::migration::{
+        migrate_hotspot_samples, migrate_metric_samples, METRIC_SAMPLE_SCHEMA_VERSION, UNKNOWN_SOURCE_COMPONENT,
+    };
+    use stats_io_lib::persistence::storage::SessionStorage;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    /// Samples as written before units and sources were recorded
+    fn legacy_samples() -> serde_json::Value {
+        json!([
+            { "timestamp": "2024-01-01T12:00:00Z", "metric_type": "gpu_temperature", "value": 71.0 },
+            { "timestamp": "2024-01-01T12:00:01Z", "metric_type": "cpu_utilization", "value": 42.5 },
+        ])
+    }
+
+    fn legacy_run() -> serde_json::Value {
+        json!({
+            "id": Uuid::new_v4(),
+            "name": "Legacy run",
+            "metrics_streams": { "gpu": legacy_samples() },
+            "analysis_result": null,
+            "notes": null,
+        })
+    }
+
+    #[test]
+    fn test_missing_fields_filled_with_defaults() {
+        let samples = legacy_samples().as_array().unwrap().clone();
+        let migrated = migrate_metric_samples(samples, 0).unwrap();
+
+        assert_eq!(migrated.len(), 2);
+        assert_eq!(migrated[0].metric_type, MetricType::GpuTemperature);
+        assert_eq!(migrated[0].unit, "Celsius");
+        assert_eq!(migrated[1].unit, "percent");
+        assert!(migrated.iter().all(|m| m.source_component == UNKNOWN_SOURCE_COMPONENT));
+    }
+
+    #[test]
+    fn test_recorded_fields_are_kept() {
+        let samples = vec![json!({
+            "timestamp": "2024-01-01T12:00:00Z",
+            "metric_type": "fan_speed",
+            "value": 1200.0,
+            "unit": "RPM",
+            "source_component": "CPU Fan",
+        })];
+        let migrated = migrate_metric_samples(samples, 0).unwrap();
+
+        assert_eq!(migrated[0].unit, "RPM");
+        assert_eq!(migrated[0].source_component, "CPU Fan");
+    }
+
+    #[test]
+    fn test_current_version_is_not_rewritten() {
+        let samples = legacy_samples().as_array().unwrap().clone();
+        assert!(matches!(
+            migrate_metric_samples(samples, METRIC_SAMPLE_SCHEMA_VERSION),
+            Err(PersistenceError::Deserialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        let result = migrate_metric_samples(Vec::new(), METRIC_SAMPLE_SCHEMA_VERSION + 1);
+        assert!(matches!(result, Err(PersistenceError::SchemaVersionMismatch { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_import_legacy_run_and_session() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Export written before metrics_schema_version existed
+        let run_path = temp_dir.path().join("run.json");
+        let exported = json!({ "export_version": 1, "export_timestamp": "2024-01-01T12:00:00Z", "run": legacy_run() });
+        std::fs::write(&run_path, exported.to_string()).unwrap();
+        let run = import_run(&run_path).await.unwrap();
+        assert_eq!(run.metrics_streams["gpu"][0].unit, "Celsius");
+        assert_eq!(run.metrics_streams["gpu"][1].source_component, UNKNOWN_SOURCE_COMPONENT);
+
+        // Bare run without the export wrapper
+        std::fs::write(&run_path, legacy_run().to_string()).unwrap();
+        assert_eq!(import_run(&run_path).await.unwrap().metrics_streams["gpu"].len(), 2);
+
+        let session_path = temp_dir.path().join("session.json");
+        let session = json!({
+            "id": Uuid::new_v4(),
+            "start_time": "2024-01-01T12:00:00Z",
+            "end_time": null,
+            "hardware_config_snapshot": {
+                "cpu": { "model": "CPU", "vendor": "Vendor", "architecture": null, "cores": 4, "threads": 8,
+                         "base_clock_mhz": null, "boost_clock_mhz": null },
+                "gpus": [],
+                "memory": { "total_mb": 8192, "channels": null, "speed_mhz": null, "modules": [] },
+                "storage_devices": [],
+                "motherboard": null,
+                "psu": null,
+                "cooling": null,
+                "displays": [],
+                "metadata": { "detection_time": "2024-01-01T12:00:00Z", "platform": "Test", "warnings": [], "schema_version": 1 },
+            },
+            "profile": { "id": "general", "name": "General", "workload_type": "general", "parameters": {}, "threshold_overrides": null },
+            "runs": [legacy_run()],
+        });
+        std::fs::write(&session_path, json!({ "export_version": 1, "session": session }).to_string()).unwrap();
+        let session = import_session(&session_path).await.unwrap();
+        assert_eq!(session.runs[0].metrics_streams["gpu"][0].unit, "Celsius");
+    }
+
+    /// Session recorded before the hotspot had its own metric type
+    fn legacy_hotspot_session() -> serde_json::Value {
+        json!({
+            "id": Uuid::new_v4(),
+            "start_time": "2024-01-01T12:00:00Z",
+            "end_time": null,
+            "hardware_config_snapshot": {
+                "cpu": { "model": "CPU", "vendor": "Vendor", "architecture": null, "cores": 4, "threads": 8,
+                         "base_clock_mhz": null, "boost_clock_mhz": null },
+                "gpus": [],
+                "memory": { "total_mb": 8192, "channels": null, "speed_mhz": null, "modules": [] },
+                "storage_devices": [],
+                "motherboard": null,
+                "psu": null,
+                "cooling": null,
+                "displays": [],
+                "metadata": { "detection_time": "2024-01-01T12:00:00Z", "platform": "Test", "warnings": [], "schema_version": 1 },
+            },
+            "profile": { "id": "general", "name": "General", "workload_type": "general", "parameters": {}, "threshold_overrides": null },
+            "runs": [{
+                "id": Uuid::new_v4(),
+                "name": "Legacy run",
+                "metrics_streams": { "gpu": [
+                    { "timestamp": "2024-01-01T12:00:00Z", "metric_type": "gpu_temperature", "value": 70.0,
+                      "unit": "Celsius", "source_component": "GPU" },
+                    { "timestamp": "2024-01-01T12:00:00Z", "metric_type": "gpu_temperature", "value": 88.0,
+                      "unit": "Celsius", "source_component": GPU_HOTSPOT_SOURCE },
+                ] },
+                "analysis_result": null,
+                "notes": null,
+            }],
+        })
+    }
+
+    fn gpu_metric_types(session: &Session) -> Vec<MetricType> {
+        session.runs[0].metrics_streams["gpu"].iter().map(|s| s.metric_type.clone()).collect()
+    }
+
+    #[test]
+    fn test_hotspot_samples_get_their_own_metric_type() {
+        let mut samples = vec![
+            MetricSample::new(MetricType::GpuTemperature, 70.0, "GPU"),
+            MetricSample::new(MetricType::GpuTemperature, 88.0, GPU_HOTSPOT_SOURCE),
+            MetricSample::new(MetricType::FanSpeed, 40.0, GPU_HOTSPOT_SOURCE),
+        ];
+        migrate_hotspot_samples(&mut samples);
+
+        assert_eq!(samples[0].metric_type, MetricType::GpuTemperature);
+        assert_eq!(samples[1].metric_type, MetricType::GpuHotspotTemperature);
+        assert_eq!(samples[2].metric_type, MetricType::FanSpeed);
+    }
+
+    #[tokio::test]
+    async fn test_saved_hotspot_samples_migrated_on_load() {
+        let expected = vec![MetricType::GpuTemperature, MetricType::GpuHotspotTemperature];
+        let session: Session = serde_json::from_value(legacy_hotspot_session()).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let storage = SessionStorage::new(temp_dir.path().join("sessions"));
+        storage.save_session(&session).await.unwrap();
+        assert_eq!(gpu_metric_types(&storage.load_session(&session.id).await.unwrap()), expected);
+
+        let database = DatabaseStorage::new(temp_dir.path().join("sessions.db")).unwrap();
+        database.save_session(&session).unwrap();
+        assert_eq!(gpu_metric_types(&database.load_session(&session.id).unwrap()), expected);
+
+        let path = temp_dir.path().join("session.json");
+        std::fs::write(&path, json!({ "export_version": 1, "session": legacy_hotspot_session() }).to_string()).unwrap();
+        assert_eq!(gpu_metric_types(&import_session(&path).await.unwrap()), expected);
+    }
+
+    #[tokio::test]
+    async fn test_export_records_schema_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("run.json");
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert(
+            "cpu".to_string(),
+            vec![MetricSample::new(MetricType::CpuUtilization, 50.0, "CPU")],
+        );
+        let run = Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        };
+
+        export_run(&run, &path).await.unwrap();
+        let document: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(document["metrics_schema_version"], METRIC_SAMPLE_SCHEMA_VERSION);
+
+        let imported = import_run(&path).await.unwrap();
+        assert_eq!(imported.metrics_streams["cpu"][0].source_component, "CPU");
+    }
+}
@@ -0,0 +1,60 @@
+//! Unit tests for the metric stream builder
+//!
+//! Tests sample counts, units and tick spacing of built fixtures following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{unit_for, MetricType};
+    use stats_io_lib::test_support::MetricStreamBuilder;
+    use chrono::{Duration, TimeZone, Utc};
+
+    #[test]
+    fn test_counts_units_and_spacing() {
+        let end = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let samples = MetricStreamBuilder::new()
+            .cpu(95.0)
+            .gpu(30.0)
+            .temperature(88.0)
+            .over_seconds(30)
+            .interval(2)
+            .ending_at(end)
+            .build();
+
+        assert_eq!(samples.len(), 15 * 3);
+        for sample in &samples {
+            assert_eq!(sample.unit, unit_for(&sample.metric_type));
+        }
+        assert!(samples.iter().any(|s| s.metric_type == MetricType::Temperature && s.unit == "Celsius"));
+
+        let cpu: Vec<_> = samples
+            .iter()
+            .filter(|s| s.metric_type == MetricType::CpuUtilization)
+            .collect();
+        assert_eq!(cpu.len(), 15);
+        assert!(cpu.iter().all(|s| s.value == 95.0 && s.unit == "percent"));
+        assert_eq!(cpu[0].timestamp, end - Duration::seconds(30));
+        assert_eq!(cpu[14].timestamp, end - Duration::seconds(2));
+        assert!(cpu.windows(2).all(|w| w[1].timestamp - w[0].timestamp == Duration::seconds(2)));
+    }
+
+    #[test]
+    fn test_fixed_end_replays_identically() {
+        let end = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let builder = MetricStreamBuilder::new().ram(91.0).vram(70.0).over_seconds(10).ending_at(end);
+        let first = builder.build();
+        let second = builder.build();
+
+        assert_eq!(first.len(), 20);
+        assert!(first.iter().zip(&second).all(|(a, b)| {
+            a.timestamp == b.timestamp && a.metric_type == b.metric_type && a.value == b.value
+        }));
+    }
+
+    #[test]
+    fn test_empty_and_degenerate_spans() {
+        assert!(MetricStreamBuilder::new().over_seconds(30).build().is_empty());
+        assert!(MetricStreamBuilder::new().cpu(50.0).over_seconds(0).build().is_empty());
+        // A zero interval is treated as one second
+        assert_eq!(MetricStreamBuilder::new().cpu(50.0).over_seconds(5).interval(0).build().len(), 5);
+    }
+}
@@ -0,0 +1,142 @@
+//! Unit tests for canonical metric units
+//!
+//! Tests unit assignment at sample construction following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{unit_for, MetricSample, MetricType};
+    use stats_io_lib::metrics::models::LatencyMetrics;
+    use stats_io_lib::metrics::providers::latency::latency_samples;
+    use chrono::{Duration, Utc};
+
+    /// Every metric type; the match fails to compile when a variant is added
+    fn all_metric_types() -> Vec<MetricType> {
+        let all = vec![
+            MetricType::CpuUtilization,
+            MetricType::CpuUtilizationPerCore,
+            MetricType::GpuUtilization,
+            MetricType::GpuVramUsage,
+            MetricType::GpuVramUsagePercent,
+            MetricType::GpuTemperature,
+            MetricType::GpuClock,
+            MetricType::CpuClock,
+            MetricType::MemoryUsage,
+            MetricType::MemorySwapUsage,
+            MetricType::StorageReadThroughput,
+            MetricType::StorageWriteThroughput,
+            MetricType::StorageQueueDepth,
+            MetricType::MemoryReadThroughput,
+            MetricType::MemoryWriteThroughput,
+            MetricType::GpuMemoryTransfer,
+            MetricType::PcieLinkGen,
+            MetricType::PcieLinkWidth,
+            MetricType::PcieLinkGenMax,
+            MetricType::PcieLinkWidthMax,
+            MetricType::Temperature,
+            MetricType::FanSpeed,
+            MetricType::DpcTime,
+            MetricType::InterruptTime,
+            MetricType::Fps,
+            MetricType::FrameTime,
+            MetricType::RenderTime,
+            MetricType::GpuMemoryControllerLoad,
+            MetricType::Voltage,
+            MetricType::ProcessGpuUtilization,
+            MetricType::MemoryUsed,
+            MetricType::GpuPower,
+            MetricType::ProcessDiskWriteThroughput,
+            MetricType::ProcessCpuUtilization,
+            MetricType::SensorTemperature,
+            MetricType::GpuAdapterUtilization,
+            MetricType::GpuHotspotTemperature,
+        ];
+        for metric_type in &all {
+            match metric_type {
+                MetricType::CpuUtilization
+                | MetricType::CpuUtilizationPerCore
+                | MetricType::GpuUtilization
+                | MetricType::GpuVramUsage
+                | MetricType::GpuVramUsagePercent
+                | MetricType::GpuTemperature
+                | MetricType::GpuClock
+                | MetricType::CpuClock
+                | MetricType::MemoryUsage
+                | MetricType::MemorySwapUsage
+                | MetricType::StorageReadThroughput
+                | MetricType::StorageWriteThroughput
+                | MetricType::StorageQueueDepth
+                | MetricType::MemoryReadThroughput
+                | MetricType::MemoryWriteThroughput
+                | MetricType::GpuMemoryTransfer
+                | MetricType::PcieLinkGen
+                | MetricType::PcieLinkWidth
+                | MetricType::PcieLinkGenMax
+                | MetricType::PcieLinkWidthMax
+                | MetricType::Temperature
+                | MetricType::FanSpeed
+                | MetricType::DpcTime
+                | MetricType::InterruptTime
+                | MetricType::Fps
+                | MetricType::FrameTime
+                | MetricType::RenderTime
+                | MetricType::GpuMemoryControllerLoad
+                | MetricType::Voltage
+                | MetricType::ProcessGpuUtilization
+                | MetricType::MemoryUsed
+                | MetricType::GpuPower
+                | MetricType::ProcessDiskWriteThroughput
+                | MetricType::ProcessCpuUtilization
+                | MetricType::SensorTemperature
+                | MetricType::GpuAdapterUtilization
+                | MetricType::GpuHotspotTemperature => {}
+            }
+        }
+        all
+    }
+
+    #[test]
+    fn test_every_metric_type_has_a_unit() {
+        for metric_type in all_metric_types() {
+            assert!(!unit_for(&metric_type).is_empty(), "{:?} has no unit", metric_type);
+        }
+    }
+
+    #[test]
+    fn test_canonical_units() {
+        assert_eq!(unit_for(&MetricType::CpuUtilization), "percent");
+        assert_eq!(unit_for(&MetricType::GpuVramUsage), "MB");
+        assert_eq!(unit_for(&MetricType::Temperature), "Celsius");
+        assert_eq!(unit_for(&MetricType::StorageReadThroughput), "MB/s");
+        assert_eq!(unit_for(&MetricType::FrameTime), "ms");
+    }
+
+    #[test]
+    fn test_constructor_sets_canonical_unit() {
+        for metric_type in all_metric_types() {
+            let sample = MetricSample::new(metric_type.clone(), 1.0, "Test");
+            assert_eq!(sample.unit, unit_for(&metric_type));
+            assert_eq!(sample.metric_type, metric_type);
+            assert_eq!(sample.source_component, "Test");
+        }
+    }
+
+    #[test]
+    fn test_with_timestamp_overrides_time() {
+        let timestamp = Utc::now() - Duration::hours(1);
+        let sample = MetricSample::new(MetricType::GpuUtilization, 80.0, "GPU".to_string())
+            .with_timestamp(timestamp);
+        assert_eq!(sample.timestamp, timestamp);
+        assert_eq!(sample.value, 80.0);
+    }
+
+    #[test]
+    fn test_provider_samples_use_canonical_units() {
+        let metrics = LatencyMetrics {
+            dpc_time_percent: 1.0,
+            interrupt_time_percent: 0.5,
+        };
+        for sample in latency_samples(&metrics, Utc::now()) {
+            assert_eq!(sample.unit, unit_for(&sample.metric_type));
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Unit tests for batched live metric delivery
+//!
+//! Tests flush cadence and sample preservation of the coalescer following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType};
+    use stats_io_lib::core::settings::SamplingSettings;
+    use stats_io_lib::metrics::coalescer::{coalesce, DEFAULT_FLUSH_INTERVAL_MS};
+    use std::time::{Duration, Instant};
+    use tokio::sync::broadcast;
+
+    fn create_batch(tick: usize, size: usize) -> Vec<MetricSample> {
+        (0..size)
+            .map(|i| MetricSample::new(MetricType::CpuUtilization, (tick * size + i) as f64, "CPU"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rapid_batches_emitted_at_flush_cadence() {
+        let (sender, receiver) = broadcast::channel(100);
+        let mut emits: Vec<(Instant, Vec<MetricSample>)> = Vec::new();
+
+        let producer = tokio::spawn(async move {
+            for tick in 0..20 {
+                sender.send(create_batch(tick, 5)).unwrap();
+                tokio::time::sleep(Duration::from_millis(25)).await;
+            }
+        });
+        coalesce(receiver, Duration::from_millis(100), |samples| {
+            emits.push((Instant::now(), samples))
+        })
+        .await;
+        producer.await.unwrap();
+
+        // 20 batches over ~500 ms collapse into a handful of emits
+        assert!((3..=7).contains(&emits.len()), "{} emits", emits.len());
+        for pair in emits.windows(2).take(emits.len().saturating_sub(2)) {
+            assert!(pair[1].0 - pair[0].0 >= Duration::from_millis(80));
+        }
+
+        let values: Vec<f64> = emits.iter().flat_map(|(_, samples)| samples.iter().map(|m| m.value)).collect();
+        assert_eq!(values.len(), 100);
+        assert!(values.iter().enumerate().all(|(i, value)| *value == i as f64));
+    }
+
+    #[tokio::test]
+    async fn test_idle_intervals_emit_nothing() {
+        let (sender, receiver) = broadcast::channel::<Vec<MetricSample>>(10);
+        let mut emit_count = 0;
+
+        let producer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            drop(sender);
+        });
+        coalesce(receiver, Duration::from_millis(20), |_| emit_count += 1).await;
+        producer.await.unwrap();
+
+        assert_eq!(emit_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pending_samples_flushed_on_close() {
+        let (sender, receiver) = broadcast::channel(10);
+        sender.send(create_batch(0, 3)).unwrap();
+        drop(sender);
+
+        let mut emits = Vec::new();
+        coalesce(receiver, Duration::from_secs(60), |samples| emits.push(samples)).await;
+
+        assert_eq!(emits.len(), 1);
+        assert_eq!(emits[0].len(), 3);
+    }
+
+    #[test]
+    fn test_flush_interval_defaults_when_missing_from_settings() {
+        let json = r#"{"interval_ms": 1000, "buffer_size": 3600}"#;
+        let sampling: SamplingSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(sampling.event_flush_interval_ms, DEFAULT_FLUSH_INTERVAL_MS);
+        assert_eq!(SamplingSettings::default().event_flush_interval_ms, DEFAULT_FLUSH_INTERVAL_MS);
+    }
+}
@@ -4,6 +4,7 @@
 
 #[cfg(test)]
 mod tests {
+    use stats_io_lib::core::settings::BufferOverflowPolicy;
     use stats_io_lib::metrics::collector::{MetricsCollector, MetricsCollectorConfig};
     use stats_io_lib::metrics::providers::{
         SysInfoCpuMetricsProvider, SysInfoMemoryMetricsProvider,
@@ -21,6 +22,7 @@ mod tests {
         let config = MetricsCollectorConfig {
             sampling_interval_ms: 1000,
             buffer_size: 1000,
+            ..Default::default()
         };
         
         let collector = MetricsCollector::new(config.clone());
@@ -35,6 +37,7 @@ mod tests {
         let config = MetricsCollectorConfig {
             sampling_interval_ms: 100,
             buffer_size: 100,
+            ..Default::default()
         };
         
         let collector = MetricsCollector::new(config);
@@ -59,6 +62,7 @@ mod tests {
         let config = MetricsCollectorConfig {
             sampling_interval_ms: 50,
             buffer_size: 10, // Small buffer to test overflow
+            ..Default::default()
         };
         
         let collector = MetricsCollector::new(config.clone());
@@ -80,6 +84,7 @@ mod tests {
         let config = MetricsCollectorConfig {
             sampling_interval_ms: 100,
             buffer_size: 100,
+            ..Default::default()
         };
         
         let collector = MetricsCollector::new(config);
@@ -173,5 +178,85 @@ mod tests {
         assert!(agg.avg > 0.0);
         assert_eq!(agg.count, 10);
     }
-}
 
+    fn cpu_samples(count: usize) -> Vec<stats_io_lib::core::domain::MetricSample> {
+        use stats_io_lib::core::domain::{MetricSample, MetricType};
+        use chrono::Utc;
+        
+        (0..count)
+            .map(|i| MetricSample {
+                timestamp: Utc::now(),
+                metric_type: MetricType::CpuUtilization,
+                value: i as f64,
+                unit: "percent".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_buffer_eviction_is_reported_in_health() {
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms: 1000,
+            buffer_size: 10,
+            overflow_policy: BufferOverflowPolicy::EvictOldest,
+            ..Default::default()
+        };
+        let collector = MetricsCollector::new(config);
+        
+        collector.record_samples(&cpu_samples(10)).await;
+        let health = collector.health().await;
+        assert!(!health.is_evicting());
+        assert!(health.eviction_started_at.is_none());
+        
+        collector.record_samples(&cpu_samples(5)).await;
+        let health = collector.health().await;
+        assert!(health.is_evicting());
+        assert_eq!(health.evicted_samples, 5);
+        assert!(health.eviction_started_at.is_some());
+        assert_eq!(health.buffered_samples, 10);
+        assert_eq!(collector.get_buffer().await.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_unbounded_buffer_grows_until_saved() {
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms: 1000,
+            buffer_size: 10,
+            overflow_policy: BufferOverflowPolicy::UnboundedUntilSaved,
+            ..Default::default()
+        };
+        let collector = MetricsCollector::new(config);
+        
+        collector.record_samples(&cpu_samples(25)).await;
+        let buffer = collector.get_buffer().await;
+        assert_eq!(buffer.len(), 25);
+        assert_eq!(buffer[0].value, 0.0);
+        assert!(!collector.health().await.is_evicting());
+        
+        collector.mark_saved().await;
+        let buffer = collector.get_buffer().await;
+        assert_eq!(buffer.len(), 10);
+        assert_eq!(buffer[0].value, 15.0);
+        assert_eq!(collector.health().await.buffered_samples, 10);
+    }
+
+    #[tokio::test]
+    async fn test_mark_saved_clears_eviction_warning() {
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms: 1000,
+            buffer_size: 4,
+            overflow_policy: BufferOverflowPolicy::EvictOldest,
+            ..Default::default()
+        };
+        let collector = MetricsCollector::new(config);
+        
+        collector.record_samples(&cpu_samples(6)).await;
+        assert!(collector.health().await.is_evicting());
+        
+        collector.mark_saved().await;
+        let health = collector.health().await;
+        assert!(!health.is_evicting());
+        assert_eq!(health.buffered_samples, 4);
+    }
+}
@@ -0,0 +1,203 @@
+//! Unit tests for de-duplicating stored metric samples
+//!
+//! Tests appending overlapping batches and repairing duplicated runs following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MetricSample, MetricType, Run, Session,
+        WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::persistence::DatabaseStorage;
+    use chrono::{DateTime, Duration, Utc};
+    use rusqlite::{params, Connection};
+    use std::collections::HashMap;
+    use std::path::Path;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    /// CPU samples one second apart for the seconds in `range`
+    fn cpu_samples(start: DateTime<Utc>, range: std::ops::Range<i64>) -> Vec<MetricSample> {
+        range
+            .map(|i| {
+                MetricSample::new(MetricType::CpuUtilization, 50.0 + i as f64, "CPU")
+                    .with_timestamp(start + Duration::seconds(i))
+            })
+            .collect()
+    }
+
+    fn create_run(samples: Vec<MetricSample>) -> Run {
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("cpu".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn create_session(run: Run) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: None,
+                    boost_clock_mhz: None,
+                    architecture: None,
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: None,
+                    speed_mhz: None,
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Test Profile".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![run],
+        }
+    }
+
+    /// Save a session holding one run without samples, returning the run's ID
+    fn save_empty_run(storage: &DatabaseStorage) -> Uuid {
+        let session = create_session(create_run(vec![]));
+        storage.save_session(&session).unwrap();
+        session.runs[0].id
+    }
+
+    fn stored_count(db_path: &Path, run_id: &Uuid) -> i64 {
+        Connection::open(db_path)
+            .unwrap()
+            .query_row(
+                "SELECT COUNT(*) FROM metrics WHERE run_id = ?1",
+                params![run_id.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_overlapping_batches_are_not_duplicated() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let storage = DatabaseStorage::new(&db_path).unwrap();
+        let run_id = save_empty_run(&storage);
+        let start = Utc::now() - Duration::seconds(60);
+
+        assert_eq!(storage.append_metrics(&run_id, &cpu_samples(start, 0..20)).unwrap(), 20);
+        // Seconds 10-19 were already stored
+        assert_eq!(storage.append_metrics(&run_id, &cpu_samples(start, 10..30)).unwrap(), 10);
+        assert_eq!(storage.append_metrics(&run_id, &cpu_samples(start, 0..30)).unwrap(), 0);
+
+        let samples = storage.query_metrics(&run_id, None, None, None).unwrap();
+        assert_eq!(samples.len(), 30);
+        assert_eq!(stored_count(&db_path, &run_id), 30);
+        // The first stored value of a duplicate is kept
+        assert_eq!(samples[15].value, 65.0);
+    }
+
+    #[test]
+    fn test_other_sources_at_the_same_time_are_kept() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(temp_dir.path().join("test.db")).unwrap();
+        let run_id = save_empty_run(&storage);
+        let timestamp = Utc::now();
+        let batch = vec![
+            MetricSample::new(MetricType::GpuUtilization, 90.0, "GPU 0").with_timestamp(timestamp),
+            MetricSample::new(MetricType::GpuUtilization, 10.0, "GPU 1").with_timestamp(timestamp),
+            MetricSample::new(MetricType::GpuTemperature, 70.0, "GPU 0").with_timestamp(timestamp),
+        ];
+
+        assert_eq!(storage.append_metrics(&run_id, &batch).unwrap(), 3);
+        assert_eq!(storage.append_metrics(&run_id, &batch).unwrap(), 0);
+        assert_eq!(storage.query_metrics(&run_id, None, None, None).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_saving_a_run_after_appending_keeps_counts() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let storage = DatabaseStorage::new(&db_path).unwrap();
+        let start = Utc::now() - Duration::seconds(60);
+        let session = create_session(create_run(cpu_samples(start, 0..30)));
+        let run = &session.runs[0];
+
+        storage.save_session(&session).unwrap();
+        assert_eq!(storage.append_metrics(&run.id, &cpu_samples(start, 20..40)).unwrap(), 10);
+        storage.save_run(run, &session.id).unwrap();
+        storage.save_run(run, &session.id).unwrap();
+
+        // A full save replaces the appended samples with the run's streams
+        assert_eq!(stored_count(&db_path, &run.id), 30);
+    }
+
+    #[test]
+    fn test_legacy_duplicates_are_repaired() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("legacy.db");
+        let run_id = Uuid::new_v4();
+        let other_run_id = Uuid::new_v4();
+        let timestamp = Utc::now().to_rfc3339();
+
+        // Schema of databases created before samples were unique
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute(
+            "CREATE TABLE metrics (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                metric_type TEXT NOT NULL,
+                value REAL NOT NULL,
+                unit TEXT NOT NULL,
+                source_component TEXT NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+        for (run, value) in [(&run_id, 40.0), (&run_id, 41.0), (&run_id, 42.0), (&other_run_id, 50.0)] {
+            conn.execute(
+                "INSERT INTO metrics (run_id, timestamp, metric_type, value, unit, source_component)
+                 VALUES (?1, ?2, 'cpu_utilization', ?3, '%', 'CPU')",
+                params![run.to_string(), timestamp, value],
+            )
+            .unwrap();
+        }
+        drop(conn);
+
+        let storage = DatabaseStorage::new(&db_path).unwrap();
+
+        let samples = storage.query_metrics(&run_id, None, None, None).unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].value, 40.0);
+        assert_eq!(stored_count(&db_path, &other_run_id), 1);
+        // Nothing is left for the repair to remove
+        assert_eq!(storage.deduplicate_run_metrics(&run_id).unwrap(), 0);
+    }
+}
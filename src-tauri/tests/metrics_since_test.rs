@@ -0,0 +1,91 @@
+//! Unit tests for incremental metrics buffer reads
+//!
+//! Tests that clients receive only new samples and resync after eviction following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType};
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+    use chrono::{DateTime, Duration, Utc};
+
+    /// CPU and memory samples for one sampling tick
+    fn tick(timestamp: DateTime<Utc>) -> Vec<MetricSample> {
+        vec![
+            MetricSample::new(MetricType::CpuUtilization, 50.0, "CPU").with_timestamp(timestamp),
+            MetricSample::new(MetricType::MemoryUsage, 40.0, "RAM").with_timestamp(timestamp),
+        ]
+    }
+
+    fn collector(buffer_size: usize) -> MetricsCollector {
+        MetricsCollector::new(MetricsCollectorConfig {
+            buffer_size,
+            ..MetricsCollectorConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_returns_only_new_samples() {
+        let collector = collector(100);
+        let start = Utc::now() - Duration::seconds(60);
+        for i in 0..3 {
+            collector.record_samples(&tick(start + Duration::seconds(i))).await;
+        }
+
+        let first = collector.get_metrics_since(None).await;
+        assert!(first.resync);
+        assert_eq!(first.samples.len(), 6);
+        assert_eq!(first.oldest_timestamp, Some(start));
+        let last_seen = first.samples.last().unwrap().timestamp;
+
+        let unchanged = collector.get_metrics_since(Some(last_seen)).await;
+        assert!(!unchanged.resync);
+        assert!(unchanged.samples.is_empty());
+
+        for i in 3..5 {
+            collector.record_samples(&tick(start + Duration::seconds(i))).await;
+        }
+        let delta = collector.get_metrics_since(Some(last_seen)).await;
+        assert!(!delta.resync);
+        assert_eq!(delta.samples.len(), 4);
+        assert!(delta.samples.iter().all(|s| s.timestamp > last_seen));
+        assert_eq!(delta.samples[0].timestamp, start + Duration::seconds(3));
+    }
+
+    #[tokio::test]
+    async fn test_flags_resync_after_eviction() {
+        // Room for two ticks
+        let collector = collector(4);
+        let start = Utc::now() - Duration::seconds(60);
+        collector.record_samples(&tick(start)).await;
+        let last_seen = collector.get_metrics_since(None).await.samples.last().unwrap().timestamp;
+
+        // Two more ticks evict the one the client holds
+        collector.record_samples(&tick(start + Duration::seconds(1))).await;
+        let kept = collector.get_metrics_since(Some(last_seen)).await;
+        assert!(!kept.resync);
+        assert_eq!(kept.samples.len(), 2);
+
+        collector.record_samples(&tick(start + Duration::seconds(2))).await;
+        collector.record_samples(&tick(start + Duration::seconds(3))).await;
+        assert!(collector.health().await.is_evicting());
+
+        let delta = collector.get_metrics_since(Some(last_seen)).await;
+        assert!(delta.resync);
+        assert_eq!(delta.samples.len(), 4);
+        assert_eq!(delta.oldest_timestamp, Some(start + Duration::seconds(2)));
+        assert_eq!(delta.samples[0].timestamp, start + Duration::seconds(2));
+    }
+
+    #[tokio::test]
+    async fn test_empty_buffer() {
+        let collector = collector(10);
+        let first = collector.get_metrics_since(None).await;
+        assert!(first.resync);
+        assert!(first.samples.is_empty());
+        assert_eq!(first.oldest_timestamp, None);
+
+        let later = collector.get_metrics_since(Some(Utc::now())).await;
+        assert!(!later.resync);
+        assert!(later.samples.is_empty());
+    }
+}
@@ -74,6 +74,7 @@ mod tests {
         let config = MetricsCollectorConfig {
             sampling_interval_ms: 100,
             buffer_size: 100,
+            ..Default::default()
         };
         
         let collector = MetricsCollector::new(config);
@@ -108,6 +109,7 @@ mod tests {
         let config = MetricsCollectorConfig {
             sampling_interval_ms: 100,
             buffer_size: 100,
+            ..Default::default()
         };
         
         let collector = MetricsCollector::new(config);
@@ -168,6 +170,7 @@ mod tests {
         let config = MetricsCollectorConfig {
             sampling_interval_ms: 100,
             buffer_size: 100,
+            ..Default::default()
         };
         
         let collector = MetricsCollector::new(config);
@@ -202,6 +205,7 @@ mod tests {
         let config = MetricsCollectorConfig {
             sampling_interval_ms: 100,
             buffer_size: 10,
+            ..Default::default()
         };
         
         let collector = MetricsCollector::new(config);
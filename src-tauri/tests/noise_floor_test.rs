@@ -0,0 +1,87 @@
+//! Unit tests for collection noise floors
+//!
+//! Tests suppression of tiny idle readings following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType};
+    use stats_io_lib::core::settings::{NoiseFloors, SamplingSettings};
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+
+    #[test]
+    fn test_sub_floor_storage_samples_suppressed() {
+        let floors = NoiseFloors::default();
+        let mut samples = vec![
+            MetricSample::new(MetricType::StorageReadThroughput, 0.02, "Storage"),
+            MetricSample::new(MetricType::StorageReadThroughput, 45.0, "Storage"),
+            MetricSample::new(MetricType::StorageWriteThroughput, 0.05, "Storage"),
+            MetricSample::new(MetricType::StorageWriteThroughput, 12.5, "Storage"),
+        ];
+
+        floors.suppress(&mut samples);
+
+        let values: Vec<f64> = samples.iter().map(|m| m.value).collect();
+        assert_eq!(values, vec![45.0, 12.5]);
+    }
+
+    #[test]
+    fn test_gpu_floor_and_unfloored_metrics() {
+        let floors = NoiseFloors::default();
+        let mut samples = vec![
+            MetricSample::new(MetricType::GpuUtilization, 0.4, "GPU"),
+            MetricSample::new(MetricType::GpuUtilization, 35.0, "GPU"),
+            // Metrics without a floor keep zero and tiny readings
+            MetricSample::new(MetricType::CpuUtilization, 0.1, "CPU"),
+            MetricSample::new(MetricType::StorageQueueDepth, 0.0, "Storage"),
+        ];
+
+        floors.suppress(&mut samples);
+
+        assert_eq!(samples.len(), 3);
+        assert!(samples.iter().all(|m| m.metric_type != MetricType::GpuUtilization || m.value == 35.0));
+    }
+
+    #[test]
+    fn test_floors_are_configurable() {
+        let floors = NoiseFloors {
+            storage_read_throughput: 50.0,
+            ..NoiseFloors::default()
+        };
+        let mut samples = vec![MetricSample::new(MetricType::StorageReadThroughput, 45.0, "Storage")];
+
+        floors.suppress(&mut samples);
+        assert!(samples.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recorded_samples_are_floored() {
+        let collector = MetricsCollector::new(MetricsCollectorConfig::default());
+        collector
+            .record_samples(&[
+                MetricSample::new(MetricType::StorageReadThroughput, 0.02, "Storage"),
+                MetricSample::new(MetricType::StorageReadThroughput, 45.0, "Storage"),
+            ])
+            .await;
+
+        let buffer = collector.get_buffer().await;
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer[0].value, 45.0);
+        let p50 = collector.live_percentile(&MetricType::StorageReadThroughput, 50.0).await;
+        assert_eq!(p50, Some(45.0));
+    }
+
+    #[test]
+    fn test_settings_defaults() {
+        assert_eq!(SamplingSettings::default().noise_floors, NoiseFloors::default());
+        assert_eq!(MetricsCollectorConfig::default().noise_floors, NoiseFloors::default());
+
+        // Settings saved before noise floors existed
+        let settings: SamplingSettings =
+            serde_json::from_str(r#"{"interval_ms": 1000, "buffer_size": 3600}"#).unwrap();
+        assert_eq!(settings.noise_floors, NoiseFloors::default());
+
+        let partial: NoiseFloors = serde_json::from_str(r#"{"gpu_utilization": 2.5}"#).unwrap();
+        assert_eq!(partial.gpu_utilization, 2.5);
+        assert_eq!(partial.storage_read_throughput, NoiseFloors::default().storage_read_throughput);
+    }
+}
@@ -0,0 +1,110 @@
+//! Unit tests for PCIe link downgrade detection
+//!
+//! Tests GPU links running below their maximum generation or width following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights;
+    use stats_io_lib::analysis::rules::advanced::PCIE_LINK_LOAD_THRESHOLD;
+    use stats_io_lib::analysis::rules::detect_pcie_link_downgrade;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use stats_io_lib::metrics::collector::gpu_samples;
+    use stats_io_lib::metrics::models::{GpuMetrics, PcieLinkStatus};
+    use stats_io_lib::metrics::providers::gpu::parse_pcie_link;
+    use chrono::{Duration, Utc};
+
+    fn create_link(gen_current: u32, width_current: u32, gen_max: u32, width_max: u32) -> PcieLinkStatus {
+        PcieLinkStatus {
+            gen_current,
+            width_current,
+            gen_max,
+            width_max,
+        }
+    }
+
+    /// GPU readings for one device taken on ten consecutive ticks
+    fn create_metrics(source: &str, utilization: f64, link: PcieLinkStatus) -> Vec<MetricSample> {
+        let gpu = GpuMetrics {
+            utilization: utilization / 100.0,
+            vram_used_mb: None,
+            vram_total_mb: None,
+            temperature: None,
+            hotspot_temperature: None,
+            clock_core_mhz: None,
+            clock_memory_mhz: None,
+            power_watts: None,
+            fan_speed_percent: None,
+            pcie_link: Some(link),
+            memory_controller_load: None,
+            adapter_name: None,
+        };
+        let now = Utc::now();
+        (0..10)
+            .flat_map(|i| gpu_samples(&gpu, source, now - Duration::seconds(10 - i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_narrow_link_under_load_is_flagged() {
+        let bottlenecks = detect_pcie_link_downgrade(&create_metrics("GPU", 95.0, create_link(4, 8, 4, 16)));
+
+        assert_eq!(bottlenecks.len(), 1);
+        let bottleneck = &bottlenecks[0];
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Bandwidth);
+        assert_eq!(bottleneck.severity, 50);
+        assert_eq!(bottleneck.evidence.len(), 1);
+        assert_eq!(bottleneck.evidence[0].metric_type, MetricType::PcieLinkWidth);
+        assert_eq!(bottleneck.evidence[0].threshold, 16.0);
+        assert_eq!(bottleneck.evidence[0].actual_value, 8.0);
+        assert!(bottleneck.summary.contains("Gen4 x8 instead of Gen4 x16"));
+    }
+
+    #[test]
+    fn test_matched_link_is_not_flagged() {
+        assert!(detect_pcie_link_downgrade(&create_metrics("GPU", 95.0, create_link(4, 16, 4, 16))).is_empty());
+    }
+
+    #[test]
+    fn test_idle_downshift_is_not_flagged() {
+        // GPUs drop to Gen1 at idle to save power
+        let idle = create_metrics("GPU", PCIE_LINK_LOAD_THRESHOLD - 10.0, create_link(1, 16, 4, 16));
+        assert!(detect_pcie_link_downgrade(&idle).is_empty());
+    }
+
+    #[test]
+    fn test_slower_generation_under_load_is_flagged() {
+        let bottlenecks = detect_pcie_link_downgrade(&create_metrics("GPU", 90.0, create_link(3, 16, 4, 16)));
+
+        assert_eq!(bottlenecks.len(), 1);
+        assert_eq!(bottlenecks[0].evidence[0].metric_type, MetricType::PcieLinkGen);
+        assert_eq!(bottlenecks[0].severity, 50);
+    }
+
+    #[test]
+    fn test_each_device_judged_separately() {
+        let mut metrics = create_metrics("GPU 0", 95.0, create_link(4, 16, 4, 16));
+        metrics.extend(create_metrics("GPU 1", 95.0, create_link(4, 4, 4, 16)));
+
+        let bottlenecks = detect_pcie_link_downgrade(&metrics);
+        assert_eq!(bottlenecks.len(), 1);
+        assert!(bottlenecks[0].summary.starts_with("GPU 1"));
+        assert_eq!(bottlenecks[0].severity, 75);
+    }
+
+    #[test]
+    fn test_engine_reports_link_advice() {
+        let result = AnalysisEngine::new().analyze(&create_metrics("GPU", 95.0, create_link(4, 8, 4, 16)), Some(60), None);
+        let insights = generate_insights(&result, None);
+
+        assert!(result.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Bandwidth));
+        assert!(insights.recommendations.iter().any(|r| r.text().contains("share lanes")));
+    }
+
+    #[test]
+    fn test_parse_nvidia_smi_fields() {
+        assert_eq!(parse_pcie_link(&["4", "8", "4", "16"]), Some(create_link(4, 8, 4, 16)));
+        assert_eq!(parse_pcie_link(&["4", "[N/A]", "4", "16"]), None);
+        assert_eq!(parse_pcie_link(&["4", "8"]), None);
+    }
+}
@@ -0,0 +1,112 @@
+//! Unit tests for percentile-based threshold checks
+//!
+//! Tests mean vs. percentile threshold statistics following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::{analyze_bottlenecks, window_statistic};
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, ThresholdOverrides, ThresholdStatistic,
+        WorkloadProfile, WorkloadType,
+    };
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    /// CPU pegged at 95% with a brief dip to 20% every fifth sample
+    fn create_pegged_with_dips() -> Vec<MetricSample> {
+        (0..20)
+            .map(|i| MetricSample {
+                timestamp: Utc::now() - Duration::seconds(i),
+                metric_type: MetricType::CpuUtilization,
+                value: if i % 5 == 0 { 20.0 } else { 95.0 },
+                unit: "%".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect()
+    }
+
+    fn create_profile(statistic: ThresholdStatistic) -> WorkloadProfile {
+        WorkloadProfile {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            workload_type: WorkloadType::Gaming,
+            parameters: HashMap::new(),
+            threshold_overrides: Some(ThresholdOverrides {
+                cpu_high: Some(85.0),
+                gpu_high: None,
+                ram_high: None,
+                vram_high: None,
+                statistic,
+                combined_pressure: None,
+                sustained_seconds: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_dips_pull_mean_below_threshold_but_not_p95() {
+        let values: Vec<f64> = create_pegged_with_dips().iter().map(|m| m.value).collect();
+
+        let mean = window_statistic(&values, ThresholdStatistic::Mean);
+        let p95 = window_statistic(&values, ThresholdStatistic::Percentile(95.0));
+
+        assert!(mean < 85.0, "mean was {}", mean);
+        assert!(p95 > 85.0, "p95 was {}", p95);
+    }
+
+    #[test]
+    fn test_detector_fires_only_in_percentile_mode() {
+        let metrics = create_pegged_with_dips();
+
+        let mean_result = analyze_bottlenecks(&metrics, 60, Some(&create_profile(ThresholdStatistic::Mean)));
+        assert!(!mean_result.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Cpu));
+
+        let p95_result = analyze_bottlenecks(
+            &metrics,
+            60,
+            Some(&create_profile(ThresholdStatistic::Percentile(95.0))),
+        );
+        let cpu = p95_result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Cpu)
+            .unwrap();
+        assert!(cpu.summary.contains("P95"));
+        assert_eq!(cpu.evidence[0].actual_value, 95.0);
+    }
+
+    #[test]
+    fn test_vram_detector_honors_statistic() {
+        // VRAM nearly full with brief dips, as when a game streams assets out
+        let metrics: Vec<MetricSample> = create_pegged_with_dips()
+            .into_iter()
+            .map(|m| MetricSample::new(MetricType::GpuVramUsagePercent, m.value, "GPU 0").with_timestamp(m.timestamp))
+            .collect();
+
+        let mean_result = analyze_bottlenecks(&metrics, 60, Some(&create_profile(ThresholdStatistic::Mean)));
+        assert!(!mean_result.bottlenecks.iter().any(|b| b.bottleneck_type == BottleneckType::Vram));
+
+        let p95_result = analyze_bottlenecks(
+            &metrics,
+            60,
+            Some(&create_profile(ThresholdStatistic::Percentile(95.0))),
+        );
+        let vram = p95_result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Vram)
+            .expect("VRAM bottleneck");
+        assert!(vram.summary.contains("P95"));
+        assert_eq!(vram.evidence[0].actual_value, 95.0);
+    }
+
+    #[test]
+    fn test_statistic_defaults_to_mean_when_missing() {
+        let json = r#"{"cpu_high": 80.0, "gpu_high": null, "ram_high": null, "vram_high": null}"#;
+        let overrides: ThresholdOverrides = serde_json::from_str(json).unwrap();
+        assert_eq!(overrides.statistic, ThresholdStatistic::Mean);
+
+        let p95 = serde_json::to_value(ThresholdStatistic::Percentile(95.0)).unwrap();
+        assert_eq!(p95, serde_json::json!({"percentile": 95.0}));
+    }
+}
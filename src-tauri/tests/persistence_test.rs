@@ -23,6 +23,7 @@ mod tests {
             threads: 16,
             base_clock_mhz: Some(3000.0),
             boost_clock_mhz: Some(4500.0),
+            core_max_frequencies_mhz: vec![],
         };
 
         let memory = MemoryInfo {
@@ -112,6 +113,7 @@ mod tests {
             metrics_streams: HashMap::new(), // Empty to avoid potential serialization issues
             analysis_result: None,
             notes: None,
+            environment: None,
         };
         session.runs.push(run);
         
@@ -0,0 +1,107 @@
+//! Unit tests for per-process GPU attribution
+//!
+//! Tests GPU engine counter parsing and top-process evidence following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::analyze_bottlenecks;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use stats_io_lib::metrics::models::ProcessGpuUsage;
+    use stats_io_lib::metrics::providers::gpu_processes::{
+        parse_process_gpu_usage, process_gpu_samples, top_gpu_process,
+    };
+    use chrono::{Duration, Utc};
+
+    /// `typeperf "\GPU Engine(*)\Utilization Percentage" -sc 1` with a game,
+    /// a browser decoding video and the desktop window manager
+    const SNAPSHOT: &str = r#"
+"(PDH-CSV 4.0)","\\DESKTOP\GPU Engine(pid_4242_luid_0x00000000_0x0000C6A3_phys_0_eng_0_engtype_3D)\Utilization Percentage","\\DESKTOP\GPU Engine(pid_4242_luid_0x00000000_0x0000C6A3_phys_0_eng_1_engtype_Copy)\Utilization Percentage","\\DESKTOP\GPU Engine(pid_9100_luid_0x00000000_0x0000C6A3_phys_0_eng_0_engtype_3D)\Utilization Percentage","\\DESKTOP\GPU Engine(pid_9100_luid_0x00000000_0x0000C6A3_phys_0_eng_3_engtype_VideoDecode)\Utilization Percentage","\\DESKTOP\GPU Engine(pid_1336_luid_0x00000000_0x0000C6A3_phys_0_eng_0_engtype_3D)\Utilization Percentage","\\DESKTOP\GPU Engine(pid_7000_luid_0x00000000_0x0000C6A3_phys_0_eng_0_engtype_3D)\Utilization Percentage"
+"03/14/2024 20:15:01.123","88.500000","4.000000","2.000000","21.000000","3.250000","0.000000"
+"#;
+
+    #[test]
+    fn test_parse_engine_snapshot() {
+        let usage = parse_process_gpu_usage(SNAPSHOT);
+
+        let pids: Vec<u32> = usage.iter().map(|p| p.pid).collect();
+        // The idle process is dropped; the browser counts its busiest engine
+        assert_eq!(pids, vec![4242, 9100, 1336]);
+        assert_eq!(usage[0].utilization_percent, 88.5);
+        assert_eq!(usage[1].utilization_percent, 21.0);
+        assert!(usage.iter().all(|p| p.name.is_none()));
+
+        assert!(parse_process_gpu_usage("").is_empty());
+        assert!(parse_process_gpu_usage("Error: No valid counters.").is_empty());
+    }
+
+    #[test]
+    fn test_top_consumer_over_window() {
+        let start = Utc::now() - Duration::seconds(30);
+        let mut samples = Vec::new();
+        for i in 0..10 {
+            let mut usage = vec![ProcessGpuUsage {
+                pid: 4242,
+                name: Some("Game.exe".to_string()),
+                utilization_percent: 80.0,
+            }];
+            // A background encoder spikes briefly and is absent otherwise
+            if i == 0 {
+                usage.push(ProcessGpuUsage {
+                    pid: 5555,
+                    name: Some("obs64.exe".to_string()),
+                    utilization_percent: 99.0,
+                });
+            }
+            samples.extend(process_gpu_samples(&usage, start + Duration::seconds(i)));
+        }
+
+        let (process, utilization) = top_gpu_process(&samples, start, start + Duration::seconds(30)).unwrap();
+        assert_eq!(process, "Game.exe (pid 4242)");
+        assert_eq!(utilization, 80.0);
+
+        assert!(top_gpu_process(&samples, start - Duration::seconds(20), start - Duration::seconds(10)).is_none());
+    }
+
+    #[test]
+    fn test_gpu_bottleneck_names_process() {
+        let now = Utc::now();
+        let mut samples = Vec::new();
+        for i in 0..20 {
+            let timestamp = now - Duration::seconds(20 - i);
+            samples.push(MetricSample::new(MetricType::GpuUtilization, 99.0, "GPU").with_timestamp(timestamp));
+            samples.push(MetricSample::new(MetricType::CpuUtilization, 40.0, "CPU").with_timestamp(timestamp));
+            samples.extend(process_gpu_samples(
+                &[ProcessGpuUsage {
+                    pid: 4242,
+                    name: Some("Game.exe".to_string()),
+                    utilization_percent: 97.0,
+                }],
+                timestamp,
+            ));
+        }
+
+        let result = analyze_bottlenecks(&samples, 30, None);
+        let gpu = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Gpu)
+            .expect("GPU bottleneck");
+        let evidence = gpu
+            .evidence
+            .iter()
+            .find(|e| e.metric_type == MetricType::ProcessGpuUtilization)
+            .expect("process evidence");
+        assert_eq!(evidence.source_component.as_deref(), Some("Game.exe (pid 4242)"));
+        assert_eq!(evidence.actual_value, 97.0);
+        assert!(gpu.details.contains("Game.exe (pid 4242)"));
+
+        // Without per-process samples the bottleneck is reported unchanged
+        let plain: Vec<MetricSample> = samples
+            .into_iter()
+            .filter(|s| s.metric_type != MetricType::ProcessGpuUtilization)
+            .collect();
+        let result = analyze_bottlenecks(&plain, 30, None);
+        let gpu = result.bottlenecks.iter().find(|b| b.bottleneck_type == BottleneckType::Gpu).unwrap();
+        assert!(gpu.evidence.iter().all(|e| e.source_component.is_none()));
+    }
+}
@@ -0,0 +1,117 @@
+//! Unit tests for structured recommendations
+//!
+//! Tests recommendation metadata, ordering, and legacy migration following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::{
+        generate_insights, sort_easy_wins_first, RecommendationCategory, RecommendationDifficulty,
+        UserFacingInsights,
+    };
+    use stats_io_lib::core::domain::{Bottleneck, BottleneckAnalysisResult, BottleneckType};
+    use stats_io_lib::core::profiles::WorkloadProfiles;
+    use chrono::Utc;
+    use serde_json::json;
+    use std::collections::HashSet;
+
+    fn create_bottleneck(bottleneck_type: BottleneckType, severity: u8) -> Bottleneck {
+        Bottleneck {
+            bottleneck_type,
+            severity,
+            evidence: vec![],
+            summary: "Bottleneck".to_string(),
+            details: String::new(),
+            onset: None,
+            active_fraction: 0.0,
+        }
+    }
+
+    fn create_result(bottlenecks: Vec<Bottleneck>) -> BottleneckAnalysisResult {
+        BottleneckAnalysisResult {
+            bottlenecks,
+            timestamp: Utc::now(),
+            coverage: None,
+        }
+    }
+
+    #[test]
+    fn test_cpu_recommendations_carry_type_and_priority() {
+        let profile = WorkloadProfiles::gaming_1080p_60fps();
+        let result = create_result(vec![create_bottleneck(BottleneckType::Cpu, 72)]);
+        let insights = generate_insights(&result, Some(&profile));
+
+        assert!(!insights.recommendations.is_empty());
+        for rec in &insights.recommendations {
+            assert_eq!(rec.bottleneck_type, Some(BottleneckType::Cpu));
+            assert_eq!(rec.priority, 72);
+            assert!(!rec.text().is_empty());
+        }
+        assert!(insights.recommendations.iter().any(|r| r.category == RecommendationCategory::Upgrade
+            && r.difficulty == RecommendationDifficulty::Advanced));
+        assert!(insights.recommendations.iter().any(|r| r.category == RecommendationCategory::Config
+            && r.difficulty == RecommendationDifficulty::Easy));
+
+        let ids: HashSet<_> = insights.recommendations.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids.len(), insights.recommendations.len());
+    }
+
+    #[test]
+    fn test_more_severe_bottleneck_ranks_higher() {
+        let result = create_result(vec![
+            create_bottleneck(BottleneckType::Thermal, 40),
+            create_bottleneck(BottleneckType::Cpu, 85),
+        ]);
+        let insights = generate_insights(&result, None);
+
+        let thermal = insights.recommendations.iter()
+            .find(|r| r.bottleneck_type == Some(BottleneckType::Thermal))
+            .unwrap();
+        let cpu = insights.recommendations.iter()
+            .find(|r| r.bottleneck_type == Some(BottleneckType::Cpu))
+            .unwrap();
+        assert!(cpu.priority > thermal.priority);
+        assert_eq!(thermal.category, RecommendationCategory::Cooling);
+    }
+
+    #[test]
+    fn test_easy_wins_first() {
+        let result = create_result(vec![
+            create_bottleneck(BottleneckType::Ram, 50),
+            create_bottleneck(BottleneckType::Cpu, 80),
+        ]);
+        let mut recommendations = generate_insights(&result, None).recommendations;
+        sort_easy_wins_first(&mut recommendations);
+
+        assert_eq!(recommendations[0].difficulty, RecommendationDifficulty::Easy);
+        assert_eq!(recommendations[0].bottleneck_type, Some(BottleneckType::Cpu));
+        assert!(recommendations.windows(2).all(|w| w[0].difficulty <= w[1].difficulty));
+        assert_eq!(recommendations.last().unwrap().difficulty, RecommendationDifficulty::Advanced);
+    }
+
+    #[test]
+    fn test_no_bottlenecks_recommendation_has_no_type() {
+        let insights = generate_insights(&create_result(vec![]), None);
+        assert_eq!(insights.recommendations.len(), 1);
+        assert_eq!(insights.recommendations[0].bottleneck_type, None);
+    }
+
+    #[test]
+    fn test_structured_round_trip_and_legacy_strings() {
+        let result = create_result(vec![create_bottleneck(BottleneckType::Gpu, 60)]);
+        let insights = generate_insights(&result, None);
+        let round_trip: UserFacingInsights =
+            serde_json::from_str(&serde_json::to_string(&insights).unwrap()).unwrap();
+        assert_eq!(round_trip.recommendations, insights.recommendations);
+
+        let legacy: UserFacingInsights = serde_json::from_value(json!({
+            "summary": "GPU bound",
+            "recommendations": ["Lower graphics settings.", "Consider upgrading the GPU."],
+            "severity": 60,
+        }))
+        .unwrap();
+        assert_eq!(legacy.recommendations.len(), 2);
+        assert_eq!(legacy.recommendations[1].text(), "Consider upgrading the GPU.");
+        assert_eq!(legacy.recommendations[1].bottleneck_type, None);
+        assert_ne!(legacy.recommendations[0].id, legacy.recommendations[1].id);
+    }
+}
@@ -0,0 +1,162 @@
+//! Unit tests for reference comparison
+//!
+//! Tests matching hardware to the bundled reference dataset following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::reference::{
+        compare_to_reference, match_reference_tier, reference_tiers, ReferenceRating,
+    };
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, GPUInfo, HardwareConfig, MemoryInfo, MetricSample, MetricType,
+        Run, WorkloadType,
+    };
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn create_hardware(cpu_model: &str, gpu_models: &[&str]) -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: cpu_model.to_string(),
+                vendor: "Test Vendor".to_string(),
+                architecture: Some("x86_64".to_string()),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: Some(3000.0),
+                boost_clock_mhz: Some(4500.0),
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: gpu_models
+                .iter()
+                .map(|model| GPUInfo {
+                    model: model.to_string(),
+                    vendor: "NVIDIA".to_string(),
+                    vram_total_mb: Some(8192),
+                    driver_version: None,
+                    pci_id: None,
+                })
+                .collect(),
+            memory: MemoryInfo {
+                total_mb: 32768,
+                channels: Some(2),
+                speed_mhz: Some(6000),
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    fn create_run(values: &[(MetricType, f64)]) -> Run {
+        let mut metrics_streams = HashMap::new();
+        for (metric_type, value) in values {
+            let samples: Vec<MetricSample> = (0..10)
+                .map(|_| MetricSample::new(metric_type.clone(), *value, "Test"))
+                .collect();
+            metrics_streams.insert(format!("{:?}", metric_type), samples);
+        }
+        Run {
+            id: Uuid::new_v4(),
+            name: "Benchmark".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    #[test]
+    fn test_bundled_dataset_loads() {
+        let tiers = reference_tiers();
+        assert!(tiers.len() >= 4);
+        for tier in tiers {
+            assert!(!tier.gpu_models.is_empty());
+            assert!(tier.workloads.contains_key(&WorkloadType::Gaming));
+        }
+    }
+
+    #[test]
+    fn test_known_gpu_matches_its_tier() {
+        let hardware = create_hardware("Unknown CPU", &["NVIDIA GeForce RTX 4090"]);
+        let (tier, model) = match_reference_tier(&hardware).unwrap();
+        assert_eq!(tier.id, "enthusiast");
+        assert_eq!(model, "RTX 4090");
+    }
+
+    #[test]
+    fn test_longest_model_name_wins() {
+        let hardware = create_hardware("Unknown CPU", &["NVIDIA GeForce RTX 4060 Ti"]);
+        let (tier, model) = match_reference_tier(&hardware).unwrap();
+        assert_eq!(model, "RTX 4060 Ti");
+        assert_eq!(tier.id, "mid_range");
+    }
+
+    #[test]
+    fn test_falls_back_to_cpu_match() {
+        let hardware = create_hardware("AMD Ryzen 5 7600X 6-Core Processor", &["Unknown GPU"]);
+        let (tier, model) = match_reference_tier(&hardware).unwrap();
+        assert_eq!(tier.id, "mid_range");
+        assert_eq!(model, "Ryzen 5 7600X");
+    }
+
+    #[test]
+    fn test_unknown_hardware_has_no_reference() {
+        let hardware = create_hardware("Mystery CPU", &["Mystery GPU"]);
+        assert!(match_reference_tier(&hardware).is_none());
+        let run = create_run(&[(MetricType::Fps, 100.0)]);
+        assert!(compare_to_reference(&run, &hardware, &WorkloadType::Gaming).is_none());
+    }
+
+    #[test]
+    fn test_flags_underperforming_run() {
+        let hardware = create_hardware("Unknown CPU", &["NVIDIA GeForce RTX 4090"]);
+        // Enthusiast gaming median is 144 FPS and 68°C GPU
+        let run = create_run(&[(MetricType::Fps, 90.0), (MetricType::GpuTemperature, 69.0)]);
+
+        let comparison = compare_to_reference(&run, &hardware, &WorkloadType::Gaming).unwrap();
+        assert_eq!(comparison.tier_id, "enthusiast");
+        assert_eq!(comparison.overall, ReferenceRating::Below);
+        assert!(comparison.summary.starts_with("Below typical"));
+
+        let fps = comparison.metrics.iter().find(|m| m.metric_type == MetricType::Fps).unwrap();
+        assert_eq!(fps.rating, ReferenceRating::Below);
+        assert_eq!(fps.reference_median, 144.0);
+        assert!(fps.delta_percent < -30.0);
+
+        let temp = comparison
+            .metrics
+            .iter()
+            .find(|m| m.metric_type == MetricType::GpuTemperature)
+            .unwrap();
+        assert_eq!(temp.rating, ReferenceRating::Typical);
+    }
+
+    #[test]
+    fn test_lower_is_better_metrics() {
+        let hardware = create_hardware("Unknown CPU", &["NVIDIA GeForce RTX 3060"]);
+        // Entry-level gaming median frame time is 16.7 ms
+        let run = create_run(&[(MetricType::FrameTime, 10.0)]);
+
+        let comparison = compare_to_reference(&run, &hardware, &WorkloadType::Gaming).unwrap();
+        assert_eq!(comparison.overall, ReferenceRating::Above);
+    }
+
+    #[test]
+    fn test_unlisted_workload_uses_general_medians() {
+        let hardware = create_hardware("Unknown CPU", &["NVIDIA GeForce RTX 4090"]);
+        let run = create_run(&[(MetricType::Temperature, 50.0)]);
+
+        let comparison = compare_to_reference(&run, &hardware, &WorkloadType::Productivity).unwrap();
+        assert_eq!(comparison.overall, ReferenceRating::Typical);
+    }
+}
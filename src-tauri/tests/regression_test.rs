@@ -90,6 +90,7 @@ mod tests {
                     threads: 8,
                     base_clock_mhz: None,
                     boost_clock_mhz: None,
+                    core_max_frequencies_mhz: vec![],
                 },
                 gpus: vec![],
                 memory: stats_io_lib::core::domain::MemoryInfo {
@@ -142,8 +143,10 @@ mod tests {
             analysis_result: Some(BottleneckAnalysisResult {
                 bottlenecks: vec![],
                 timestamp: Utc::now(),
+                coverage: None,
             }),
             notes: Some("Test".to_string()),
+            environment: None,
         };
         
         // Verify run can be serialized
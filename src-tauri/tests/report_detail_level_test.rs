@@ -0,0 +1,179 @@
+//! Unit tests for report detail levels
+//!
+//! Tests automatic level selection and the sections omitted per level following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata, HardwareConfig,
+        MemoryInfo, MetricSample, MetricType, Run, Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::persistence::reports::{
+        generate_session_report, ReportConfig, ReportDetailLevel, ReportFormat,
+    };
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    /// Run with a CPU bottleneck of the given severity
+    fn create_run(name: &str, severity: u8) -> Run {
+        let start = Utc::now() - Duration::seconds(30);
+        let samples = (0..30)
+            .map(|i| {
+                MetricSample::new(MetricType::CpuUtilization, 40.0 + severity as f64 / 2.0, "CPU")
+                    .with_timestamp(start + Duration::seconds(i))
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("cpu".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: vec![Bottleneck {
+                    bottleneck_type: BottleneckType::Cpu,
+                    severity,
+                    evidence: vec![],
+                    summary: format!("{} CPU saturated", name),
+                    details: "CPU utilization stayed high".to_string(),
+                    onset: None,
+                    active_fraction: 0.0,
+                }],
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: None,
+            environment: None,
+        }
+    }
+
+    /// Session of `run_count` runs; run N has severity N, so the last is worst
+    fn create_session(run_count: usize) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: None,
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: None,
+                    boost_clock_mhz: None,
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: None,
+                    speed_mhz: None,
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "gaming".to_string(),
+                name: "Gaming".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: (1..=run_count).map(|i| create_run(&format!("Pass {:02}", i), i as u8)).collect(),
+        }
+    }
+
+    fn report(session: &Session, format: ReportFormat, detail_level: Option<ReportDetailLevel>) -> String {
+        let config = ReportConfig {
+            format,
+            detail_level,
+            ..ReportConfig::default()
+        };
+        generate_session_report(session, &session.hardware_config_snapshot, &config)
+    }
+
+    #[test]
+    fn test_detail_level_follows_run_count() {
+        assert_eq!(ReportDetailLevel::for_run_count(1), ReportDetailLevel::Full);
+        assert_eq!(ReportDetailLevel::for_run_count(10), ReportDetailLevel::Full);
+        assert_eq!(ReportDetailLevel::for_run_count(11), ReportDetailLevel::Standard);
+        assert_eq!(ReportDetailLevel::for_run_count(30), ReportDetailLevel::Standard);
+        assert_eq!(ReportDetailLevel::for_run_count(50), ReportDetailLevel::Summary);
+
+        let config = ReportConfig { detail_level: Some(ReportDetailLevel::Full), ..ReportConfig::default() };
+        assert_eq!(config.resolved_detail_level(50), ReportDetailLevel::Full);
+    }
+
+    #[test]
+    fn test_summary_report_omits_per_run_sections() {
+        let session = create_session(40);
+
+        let text = report(&session, ReportFormat::Text, Some(ReportDetailLevel::Summary));
+        assert!(text.contains("SESSION SUMMARY"));
+        assert!(text.contains("WORST BOTTLENECKS"));
+        assert!(text.contains("Run 40: Pass 40 - Pass 40 CPU saturated (Severity: 40/100)"));
+        assert!(!text.contains("\nBOTTLENECK ANALYSIS"));
+        assert!(!text.contains("METRIC TRENDS"));
+        assert!(!text.contains("Bottlenecks detected:"));
+        // Only the ten worst bottlenecks are listed
+        assert!(!text.contains("Run 30: Pass 30"));
+
+        let html = report(&session, ReportFormat::Html, Some(ReportDetailLevel::Summary));
+        assert!(html.contains("worst-bottlenecks-section"));
+        assert!(!html.contains("<h3>Run 1: Pass 01</h3>"));
+
+        let markdown = report(&session, ReportFormat::Markdown, Some(ReportDetailLevel::Summary));
+        assert!(markdown.contains("## Worst Bottlenecks"));
+        assert!(!markdown.contains("## Run "));
+    }
+
+    #[test]
+    fn test_large_session_defaults_to_summary() {
+        let session = create_session(40);
+
+        let auto = report(&session, ReportFormat::Text, None);
+        let summary = report(&session, ReportFormat::Text, Some(ReportDetailLevel::Summary));
+        assert_eq!(auto, summary);
+    }
+
+    #[test]
+    fn test_standard_report_details_most_severe_runs() {
+        let session = create_session(15);
+
+        let text = report(&session, ReportFormat::Text, Some(ReportDetailLevel::Standard));
+        assert!(text.contains("\nBOTTLENECK ANALYSIS"));
+        assert!(text.contains("Run 15: Pass 15\n  Bottlenecks detected: 1"));
+        assert!(text.contains("Run 6: Pass 06\n  Bottlenecks detected: 1"));
+        assert!(!text.contains("Run 5: Pass 05\n  Bottlenecks detected"));
+        // The rest are listed briefly
+        assert!(text.contains("OTHER RUNS"));
+        assert!(text.contains("Run 5: Pass 05 (1 bottlenecks, worst severity 5/100)"));
+        assert!(text.contains("Detail is shown for the 10 most severe of 15 runs"));
+
+        let markdown = report(&session, ReportFormat::Markdown, Some(ReportDetailLevel::Standard));
+        assert_eq!(markdown.matches("## Run ").count(), 10);
+        assert!(markdown.contains("## Other Runs"));
+    }
+
+    #[test]
+    fn test_full_report_details_every_run() {
+        let session = create_session(15);
+
+        let text = report(&session, ReportFormat::Text, Some(ReportDetailLevel::Full));
+        assert!(text.contains("Run 1: Pass 01\n  Bottlenecks detected: 1"));
+        assert!(!text.contains("OTHER RUNS"));
+        assert!(!text.contains("Detail is shown"));
+    }
+}
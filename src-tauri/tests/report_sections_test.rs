@@ -0,0 +1,228 @@
+//! Unit tests for configurable report sections
+//!
+//! Tests section ordering, inclusion and validation following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata, HardwareConfig,
+        MemoryInfo, MetricSample, MetricType, Run, Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::persistence::reports::{
+        generate_session_report, parse_sections, validate_sections, ReportConfig, ReportFormat, ReportSection,
+    };
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn create_run(name: &str, cpu: f64, notes: Option<&str>) -> Run {
+        let start = Utc::now() - Duration::seconds(60);
+        let samples = (0..60)
+            .map(|i| MetricSample {
+                timestamp: start + Duration::seconds(i),
+                metric_type: MetricType::CpuUtilization,
+                value: cpu,
+                unit: "%".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("cpu".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: vec![Bottleneck {
+                    bottleneck_type: BottleneckType::Cpu,
+                    severity: 70,
+                    evidence: vec![],
+                    summary: "CPU saturated".to_string(),
+                    details: "CPU utilization stayed high".to_string(),
+                    onset: Some(start + Duration::seconds(15)),
+                    active_fraction: 0.0,
+                }],
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: notes.map(str::to_string),
+            environment: None,
+        }
+    }
+
+    fn create_session() -> Session {
+        let hardware = HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                architecture: None,
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: None,
+                speed_mhz: None,
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        };
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: hardware,
+            profile: WorkloadProfile {
+                id: "gaming".to_string(),
+                name: "Gaming".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![
+                create_run("Stock", 95.0, Some("Stock clocks")),
+                create_run("Tuned", 60.0, None),
+            ],
+        }
+    }
+
+    fn report(format: ReportFormat, sections: Option<Vec<ReportSection>>) -> String {
+        let session = create_session();
+        let config = ReportConfig {
+            format,
+            sections,
+            ..ReportConfig::default()
+        };
+        generate_session_report(&session, &session.hardware_config_snapshot, &config)
+    }
+
+    /// Position of each heading, failing if one is missing
+    ///
+    /// Text headings are matched on their own line, as the report title
+    /// also mentions bottleneck analysis.
+    fn positions(report: &str, headings: &[&str]) -> Vec<usize> {
+        headings
+            .iter()
+            .map(|heading| report.find(heading).unwrap_or_else(|| panic!("missing {}", heading)))
+            .collect()
+    }
+
+    #[test]
+    fn test_text_report_follows_custom_section_order() {
+        let text = report(
+            ReportFormat::Text,
+            Some(vec![
+                ReportSection::Recommendations,
+                ReportSection::Markers,
+                ReportSection::Hardware,
+                ReportSection::Comparison,
+                ReportSection::Analysis,
+            ]),
+        );
+
+        let found = positions(&text, &[
+            "\nRECOMMENDATIONS\n",
+            "\nMARKERS\n",
+            "\nHARDWARE CONFIGURATION\n",
+            "\nCOMPARISON WITH PREVIOUS RUN\n",
+            "\nBOTTLENECK ANALYSIS\n",
+        ]);
+        assert!(found.windows(2).all(|pair| pair[0] < pair[1]), "out of order: {:?}", found);
+
+        // Unlisted sections are left out
+        assert!(!text.contains("\nSESSION SUMMARY\n"));
+        assert!(!text.contains("\nMETRIC TRENDS\n"));
+        // Notes are pinned to the start, bottlenecks at their onset
+        assert!(text.contains("start    Note: Stock clocks"));
+        assert!(text.contains("15s      CPU saturated"));
+        assert!(text.contains("Tuned vs Stock"));
+    }
+
+    #[test]
+    fn test_markdown_report_follows_custom_section_order() {
+        let markdown = report(
+            ReportFormat::Markdown,
+            Some(vec![ReportSection::Recommendations, ReportSection::Trend, ReportSection::Hardware]),
+        );
+
+        let found = positions(&markdown, &["### Recommendations", "## Session Summary", "## Hardware Configuration"]);
+        assert!(found.windows(2).all(|pair| pair[0] < pair[1]), "out of order: {:?}", found);
+        assert!(!markdown.contains("### Bottlenecks"));
+    }
+
+    #[test]
+    fn test_html_report_follows_custom_section_order() {
+        let html = report(
+            ReportFormat::Html,
+            Some(vec![ReportSection::Analysis, ReportSection::Hardware]),
+        );
+
+        let found = positions(&html, &["<h2>Bottleneck Analysis</h2>", "<h2>Hardware Configuration</h2>"]);
+        assert!(found[0] < found[1]);
+        assert!(!html.contains("<h2>Session Summary</h2>"));
+    }
+
+    #[test]
+    fn test_default_sections_keep_current_layout() {
+        let config = ReportConfig::default();
+        assert_eq!(config.resolved_sections(), vec![
+            ReportSection::Hardware,
+            ReportSection::Environment,
+            ReportSection::Trend,
+            ReportSection::Charts,
+            ReportSection::Analysis,
+            ReportSection::Recommendations,
+        ]);
+
+        let without_hardware = ReportConfig { include_hardware: false, ..ReportConfig::default() };
+        assert!(!without_hardware.resolved_sections().contains(&ReportSection::Hardware));
+
+        let text = report(ReportFormat::Text, None);
+        let found = positions(&text, &[
+            "\nHARDWARE CONFIGURATION\n",
+            "\nSESSION SUMMARY\n",
+            "\nMETRIC TRENDS\n",
+            "\nBOTTLENECK ANALYSIS\n",
+            "\nRECOMMENDATIONS\n",
+        ]);
+        assert!(found.windows(2).all(|pair| pair[0] < pair[1]), "out of order: {:?}", found);
+        assert!(!text.contains("\nMARKERS\n"));
+    }
+
+    #[test]
+    fn test_unknown_and_duplicate_sections_rejected() {
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            parse_sections(&names(&["markers", "hardware"])).unwrap(),
+            vec![ReportSection::Markers, ReportSection::Hardware]
+        );
+        assert!(parse_sections(&names(&["hardware", "gauges"])).unwrap_err().contains("gauges"));
+        assert!(parse_sections(&names(&["trend", "analysis", "trend"])).unwrap_err().contains("trend"));
+        assert!(validate_sections(&[ReportSection::Charts, ReportSection::Charts]).is_err());
+
+        // Serialized configs use the same names
+        let config: ReportConfig = serde_json::from_str(
+            r#"{"include_hardware": true, "include_metrics": true, "include_analysis": true,
+                "include_recommendations": true, "include_comparison": false, "format": "text",
+                "sections": ["environment", "comparison"]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.sections, Some(vec![ReportSection::Environment, ReportSection::Comparison]));
+        assert!(serde_json::from_str::<ReportSection>(r#""gauges""#).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Unit tests for HTML report severity tiers
+//!
+//! Tests severity banding and tier classes on bottleneck cards following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata, HardwareConfig,
+        MemoryInfo, Run, Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::persistence::reports::{generate_session_report, ReportConfig, ReportFormat, SeverityBands};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn create_bottleneck(summary: &str, severity: u8) -> Bottleneck {
+        Bottleneck {
+            bottleneck_type: BottleneckType::Cpu,
+            severity,
+            evidence: vec![],
+            summary: summary.to_string(),
+            details: "Details".to_string(),
+            onset: None,
+            active_fraction: 0.0,
+        }
+    }
+
+    fn create_session(severities: &[u8]) -> Session {
+        let hardware = HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                architecture: None,
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: None,
+                speed_mhz: None,
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        };
+        let run = Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams: HashMap::new(),
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: severities
+                    .iter()
+                    .map(|severity| create_bottleneck(&format!("Bottleneck {}", severity), *severity))
+                    .collect(),
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: None,
+            environment: None,
+        };
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: hardware,
+            profile: WorkloadProfile {
+                id: "gaming".to_string(),
+                name: "Gaming".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![run],
+        }
+    }
+
+    /// Opening tag of the card whose heading is `summary`
+    fn card_tag<'a>(html: &'a str, summary: &str) -> &'a str {
+        let heading = html.find(&format!("<h4>{}</h4>", summary)).expect("card heading");
+        let start = html[..heading].rfind("<div class=\"bottleneck-card").expect("card tag");
+        let end = start + html[start..].find('>').unwrap();
+        &html[start..=end]
+    }
+
+    fn html_report(severities: &[u8], bands: SeverityBands) -> String {
+        let session = create_session(severities);
+        let config = ReportConfig {
+            format: ReportFormat::Html,
+            severity_bands: bands,
+            ..ReportConfig::default()
+        };
+        generate_session_report(&session, &session.hardware_config_snapshot, &config)
+    }
+
+    #[test]
+    fn test_default_tiers() {
+        let bands = SeverityBands::default();
+        assert_eq!(bands.tier(0), "low");
+        assert_eq!(bands.tier(24), "low");
+        assert_eq!(bands.tier(25), "medium");
+        assert_eq!(bands.tier(50), "high");
+        assert_eq!(bands.tier(75), "critical");
+        assert_eq!(bands.tier(100), "critical");
+    }
+
+    #[test]
+    fn test_html_applies_tier_classes() {
+        let html = html_report(&[20, 40, 95], SeverityBands::default());
+
+        assert_eq!(card_tag(&html, "Bottleneck 20"), "<div class=\"bottleneck-card severity-low\">");
+        assert_eq!(card_tag(&html, "Bottleneck 40"), "<div class=\"bottleneck-card severity-medium\">");
+        assert_eq!(card_tag(&html, "Bottleneck 95"), "<div class=\"bottleneck-card severity-critical\">");
+        assert!(html.contains("<span class=\"severity-badge severity-critical\">critical</span> Severity: 95/100"));
+        assert!(html.contains(".severity-badge.severity-critical"));
+    }
+
+    #[test]
+    fn test_html_uses_configured_bands() {
+        let bands = SeverityBands { medium: 20, high: 50, critical: 99 };
+        let html = html_report(&[30, 60, 95], bands);
+
+        assert_eq!(card_tag(&html, "Bottleneck 30"), "<div class=\"bottleneck-card severity-medium\">");
+        assert_eq!(card_tag(&html, "Bottleneck 60"), "<div class=\"bottleneck-card severity-high\">");
+        assert_eq!(card_tag(&html, "Bottleneck 95"), "<div class=\"bottleneck-card severity-high\">");
+    }
+
+    #[test]
+    fn test_validate_bands() {
+        assert!(SeverityBands::default().validate().is_ok());
+        assert!(SeverityBands { medium: 50, high: 50, critical: 90 }.validate().is_err());
+        assert!(SeverityBands { medium: 0, high: 50, critical: 90 }.validate().is_err());
+        assert!(SeverityBands { medium: 40, high: 70, critical: 101 }.validate().is_err());
+    }
+}
@@ -5,12 +5,18 @@
 #[cfg(test)]
 mod tests {
     use stats_io_lib::persistence::reports::{
-        generate_session_report, ReportConfig, ReportFormat,
+        format_timestamp, generate_analysis_json, generate_session_report,
+        generate_session_report_incremental, generate_sparkline, session_rollup, write_report, ReportConfig,
+        ReportFormat, ReportProgress, SeverityBands, SPARKLINE_WIDTH,
     };
+    use stats_io_lib::core::error::PersistenceError;
     use stats_io_lib::core::domain::{
-        HardwareConfig, Session, WorkloadProfile, WorkloadType,
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, EvidenceItem, HardwareConfig,
+        MetricSample, MetricType, Run, Session, WorkloadProfile, WorkloadType, GPU_HOTSPOT_SOURCE,
     };
-    use chrono::Utc;
+    use chrono::{Duration, TimeZone, Utc};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tempfile::TempDir;
     use uuid::Uuid;
 
     fn create_test_session() -> Session {
@@ -22,6 +28,7 @@ mod tests {
             threads: 16,
             base_clock_mhz: Some(3000.0),
             boost_clock_mhz: Some(4500.0),
+            core_max_frequencies_mhz: vec![],
         };
 
         let memory = stats_io_lib::core::domain::MemoryInfo {
@@ -77,6 +84,11 @@ mod tests {
             include_recommendations: true,
             include_comparison: false,
             format: ReportFormat::Text,
+            timezone: None,
+            acknowledgments: vec![],
+            sections: None,
+            severity_bands: SeverityBands::default(),
+            detail_level: None,
         };
 
         let report = generate_session_report(&session, &hardware, &config);
@@ -97,6 +109,11 @@ mod tests {
             include_recommendations: true,
             include_comparison: false,
             format: ReportFormat::Html,
+            timezone: None,
+            acknowledgments: vec![],
+            sections: None,
+            severity_bands: SeverityBands::default(),
+            detail_level: None,
         };
 
         let report = generate_session_report(&session, &hardware, &config);
@@ -117,6 +134,11 @@ mod tests {
             include_recommendations: true,
             include_comparison: false,
             format: ReportFormat::Json,
+            timezone: None,
+            acknowledgments: vec![],
+            sections: None,
+            severity_bands: SeverityBands::default(),
+            detail_level: None,
         };
 
         let report = generate_session_report(&session, &hardware, &config);
@@ -138,6 +160,11 @@ mod tests {
             include_recommendations: true,
             include_comparison: false,
             format: ReportFormat::Text,
+            timezone: None,
+            acknowledgments: vec![],
+            sections: None,
+            severity_bands: SeverityBands::default(),
+            detail_level: None,
         };
 
         let report = generate_session_report(&session, &hardware, &config);
@@ -145,5 +172,488 @@ mod tests {
         // Should not contain hardware section
         assert!(!report.contains("HARDWARE CONFIGURATION"));
     }
-}
 
+    #[test]
+    fn test_sparkline_ramp_uses_full_range() {
+        let ramp: Vec<f64> = (0..100).map(|i| i as f64).collect();
+
+        let sparkline = generate_sparkline(&ramp, SPARKLINE_WIDTH).unwrap();
+
+        assert_eq!(sparkline.chars().count(), SPARKLINE_WIDTH);
+        assert_eq!(sparkline.chars().next(), Some('▁'));
+        assert_eq!(sparkline.chars().last(), Some('█'));
+        for block in ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'] {
+            assert!(sparkline.contains(block));
+        }
+    }
+
+    #[test]
+    fn test_sparkline_empty_and_spiky() {
+        assert!(generate_sparkline(&[], SPARKLINE_WIDTH).is_none());
+
+        // A single spike is normalized to the top block
+        let mut spiky = vec![1.0; 10];
+        spiky[5] = 1000.0;
+        let sparkline = generate_sparkline(&spiky, SPARKLINE_WIDTH).unwrap();
+        assert_eq!(sparkline.chars().count(), 10);
+        assert_eq!(sparkline.chars().filter(|c| *c == '█').count(), 1);
+    }
+
+    #[test]
+    fn test_text_report_includes_sparklines() {
+        let mut session = create_test_session();
+        let start = Utc::now() - Duration::seconds(120);
+        let cpu_samples = (0..120)
+            .map(|i| MetricSample {
+                timestamp: start + Duration::seconds(i),
+                metric_type: MetricType::CpuUtilization,
+                value: i as f64 / 120.0 * 100.0,
+                unit: "%".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect();
+        let mut metrics_streams = std::collections::HashMap::new();
+        metrics_streams.insert("cpu".to_string(), cpu_samples);
+        session.runs.push(Run {
+            id: Uuid::new_v4(),
+            name: "Ramp".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        });
+        let hardware = session.hardware_config_snapshot.clone();
+        let config = ReportConfig {
+            include_hardware: false,
+            include_metrics: true,
+            include_analysis: false,
+            include_recommendations: false,
+            include_comparison: false,
+            format: ReportFormat::Text,
+            timezone: None,
+            acknowledgments: vec![],
+            sections: None,
+            severity_bands: SeverityBands::default(),
+            detail_level: None,
+        };
+
+        let report = generate_session_report(&session, &hardware, &config);
+
+        let cpu_line = report.lines().find(|l| l.trim_start().starts_with("CPU ")).unwrap();
+        let blocks = cpu_line.chars().filter(|c| ('▁'..='█').contains(c)).count();
+        assert_eq!(blocks, SPARKLINE_WIDTH);
+        // Streams without samples are omitted
+        assert!(!report.contains("GPU "));
+    }
+
+    #[test]
+    fn test_gpu_temperature_sparkline_leaves_out_hotspot() {
+        let mut session = create_test_session();
+        let start = Utc::now() - Duration::seconds(60);
+        let gpu_samples = (0..60)
+            .flat_map(|i| {
+                let timestamp = start + Duration::seconds(i);
+                [
+                    MetricSample::new(MetricType::GpuTemperature, 60.0 + i as f64 / 6.0, "GPU").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::GpuHotspotTemperature, 95.0, GPU_HOTSPOT_SOURCE).with_timestamp(timestamp),
+                ]
+            })
+            .collect();
+        let mut metrics_streams = std::collections::HashMap::new();
+        metrics_streams.insert("gpu".to_string(), gpu_samples);
+        session.runs.push(Run {
+            id: Uuid::new_v4(),
+            name: "Warmup".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        });
+        let hardware = session.hardware_config_snapshot.clone();
+        let config = ReportConfig {
+            include_hardware: false,
+            include_metrics: true,
+            include_analysis: false,
+            include_recommendations: false,
+            include_comparison: false,
+            format: ReportFormat::Text,
+            timezone: None,
+            acknowledgments: vec![],
+            sections: None,
+            severity_bands: SeverityBands::default(),
+            detail_level: None,
+        };
+
+        let report = generate_session_report(&session, &hardware, &config);
+
+        let gpu_line = report.lines().find(|l| l.trim_start().starts_with("GPU Temp")).unwrap();
+        assert!(gpu_line.contains("min 60.0 / max 69.8"), "{}", gpu_line);
+    }
+
+    #[test]
+    fn test_format_timestamp_applies_configured_zone() {
+        let winter = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        let summer = Utc.with_ymd_and_hms(2024, 7, 15, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            format_timestamp(&winter, Some("America/New_York")),
+            "2024-01-15 07:00:00 -05:00"
+        );
+        assert_eq!(
+            format_timestamp(&summer, Some("America/New_York")),
+            "2024-07-15 08:00:00 -04:00"
+        );
+        assert_eq!(
+            format_timestamp(&winter, Some("Asia/Kolkata")),
+            "2024-01-15 17:30:00 +05:30"
+        );
+        // Unknown zones fall back to UTC
+        assert_eq!(
+            format_timestamp(&winter, Some("Not/A_Zone")),
+            "2024-01-15 12:00:00 +00:00"
+        );
+    }
+
+    #[test]
+    fn test_text_report_uses_configured_zone() {
+        let mut session = create_test_session();
+        session.start_time = Utc.with_ymd_and_hms(2024, 3, 1, 23, 30, 0).unwrap();
+        let hardware = session.hardware_config_snapshot.clone();
+        let config = ReportConfig {
+            include_hardware: false,
+            include_metrics: false,
+            include_analysis: false,
+            include_recommendations: false,
+            include_comparison: false,
+            format: ReportFormat::Text,
+            timezone: Some("Europe/Berlin".to_string()),
+            acknowledgments: vec![],
+            sections: None,
+            severity_bands: SeverityBands::default(),
+            detail_level: None,
+        };
+
+        let report = generate_session_report(&session, &hardware, &config);
+
+        assert!(report.contains("Started: 2024-03-02 00:30:00 +01:00"));
+    }
+
+    fn create_session_with_runs(count: usize) -> Session {
+        let mut session = create_test_session();
+        for i in 0..count {
+            let sample = MetricSample {
+                timestamp: Utc::now() - Duration::seconds(i as i64),
+                metric_type: MetricType::CpuUtilization,
+                value: (i % 100) as f64,
+                unit: "%".to_string(),
+                source_component: "CPU".to_string(),
+            };
+            let mut metrics_streams = std::collections::HashMap::new();
+            metrics_streams.insert("cpu".to_string(), vec![sample]);
+            session.runs.push(Run {
+                id: Uuid::new_v4(),
+                name: format!("Run {}", i),
+                metrics_streams,
+                analysis_result: None,
+                notes: None,
+                environment: None,
+            });
+        }
+        session
+    }
+
+    #[test]
+    fn test_report_progress_is_monotonic_to_completion() {
+        let session = create_session_with_runs(250);
+        let hardware = session.hardware_config_snapshot.clone();
+
+        for format in [ReportFormat::Text, ReportFormat::Html, ReportFormat::Json] {
+            let config = ReportConfig { format, ..ReportConfig::default() };
+            let cancel = AtomicBool::new(false);
+            let mut updates: Vec<ReportProgress> = Vec::new();
+
+            let report = generate_session_report_incremental(
+                &session, &hardware, &config, &cancel, |p| updates.push(p),
+            ).unwrap();
+
+            assert!(!report.is_empty());
+            assert_eq!(updates.len(), 250);
+            assert!(updates.windows(2).all(|w| w[1].runs_processed == w[0].runs_processed + 1));
+            assert!(updates.iter().all(|p| p.total_runs == 250));
+            assert_eq!(updates.last().unwrap().runs_processed, 250);
+        }
+    }
+
+    #[test]
+    fn test_incremental_report_matches_plain_report() {
+        let session = create_session_with_runs(5);
+        let hardware = session.hardware_config_snapshot.clone();
+        let config = ReportConfig { format: ReportFormat::Text, ..ReportConfig::default() };
+
+        let incremental = generate_session_report_incremental(
+            &session, &hardware, &config, &AtomicBool::new(false), |_| {},
+        ).unwrap();
+
+        assert_eq!(incremental, generate_session_report(&session, &hardware, &config));
+    }
+
+    #[test]
+    fn test_report_generation_can_be_cancelled() {
+        let session = create_session_with_runs(100);
+        let hardware = session.hardware_config_snapshot.clone();
+        let config = ReportConfig { format: ReportFormat::Html, ..ReportConfig::default() };
+        let cancel = AtomicBool::new(false);
+        let mut processed = 0;
+
+        let result = generate_session_report_incremental(
+            &session, &hardware, &config, &cancel, |p| {
+                processed = p.runs_processed;
+                if p.runs_processed == 10 {
+                    cancel.store(true, Ordering::Relaxed);
+                }
+            },
+        );
+
+        assert!(matches!(result, Err(PersistenceError::Cancelled(_))));
+        assert_eq!(processed, 10);
+    }
+
+    #[test]
+    fn test_analysis_json_keeps_evidence() {
+        let mut session = create_test_session();
+        let start = Utc::now() - Duration::seconds(30);
+        session.runs.push(Run {
+            id: Uuid::new_v4(),
+            name: "Analyzed".to_string(),
+            metrics_streams: std::collections::HashMap::new(),
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: vec![Bottleneck {
+                    bottleneck_type: BottleneckType::Cpu,
+                    severity: 75,
+                    evidence: vec![EvidenceItem {
+                        metric_type: MetricType::CpuUtilization,
+                        threshold: 85.0,
+                        actual_value: 97.5,
+                        time_range_start: start,
+                        time_range_end: Utc::now(),
+                        source_component: None,
+                    }],
+                    summary: "CPU-bound".to_string(),
+                    details: "CPU pegged".to_string(),
+                    onset: None,
+                    active_fraction: 0.0,
+                }],
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: None,
+            environment: None,
+        });
+
+        let json: serde_json::Value = serde_json::from_str(&generate_analysis_json(&session).unwrap()).unwrap();
+
+        let bottleneck = &json["runs"][0]["analysis_result"]["bottlenecks"][0];
+        assert_eq!(bottleneck["details"], "CPU pegged");
+        let evidence = bottleneck["evidence"].as_array().unwrap();
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0]["threshold"], 85.0);
+        assert_eq!(evidence[0]["actual_value"], 97.5);
+        assert!(evidence[0]["time_range_start"].is_string());
+    }
+
+    #[test]
+    fn test_text_report_includes_grade_table() {
+        let mut session = create_test_session();
+        session.runs.push(Run {
+            id: Uuid::new_v4(),
+            name: "Graded".to_string(),
+            metrics_streams: std::collections::HashMap::new(),
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: vec![],
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: None,
+            environment: None,
+        });
+        let hardware = session.hardware_config_snapshot.clone();
+        let config = ReportConfig { format: ReportFormat::Text, ..ReportConfig::default() };
+
+        let report = generate_session_report(&session, &hardware, &config);
+
+        assert!(report.contains("  Grades:\n"));
+        assert!(report.contains("    CPU      A\n"));
+        assert!(report.contains("    GPU      N/A\n"));
+    }
+
+    #[test]
+    fn test_text_report_groups_recommendations_by_category() {
+        let mut session = create_test_session();
+        session.runs.push(Run {
+            id: Uuid::new_v4(),
+            name: "CPU bound".to_string(),
+            metrics_streams: std::collections::HashMap::new(),
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: vec![Bottleneck {
+                    bottleneck_type: BottleneckType::Cpu,
+                    severity: 70,
+                    evidence: vec![],
+                    summary: "CPU bound".to_string(),
+                    details: String::new(),
+                    onset: None,
+                    active_fraction: 0.0,
+                }],
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: None,
+            environment: None,
+        });
+        let hardware = session.hardware_config_snapshot.clone();
+        let config = ReportConfig { format: ReportFormat::Text, ..ReportConfig::default() };
+
+        let report = generate_session_report(&session, &hardware, &config);
+
+        let configuration = report.find("  Configuration:\n").expect("configuration group");
+        let upgrades = report.find("  Upgrades:\n").expect("upgrades group");
+        assert!(configuration < upgrades);
+        // Easiest configuration change comes first
+        assert!(report[configuration..upgrades].starts_with("  Configuration:\n    - Close background applications"));
+        assert!(report[upgrades..].contains("    - For gaming: Consider upgrading"));
+        assert!(!report.contains("  Cooling:\n"));
+    }
+
+    /// Run with the given CPU utilization readings and detected bottleneck types
+    fn create_rollup_run(name: &str, cpu: &[f64], bottleneck_types: &[BottleneckType]) -> Run {
+        let samples = cpu
+            .iter()
+            .map(|value| MetricSample::new(MetricType::CpuUtilization, *value, "CPU"))
+            .collect();
+        let mut metrics_streams = std::collections::HashMap::new();
+        metrics_streams.insert("cpu".to_string(), samples);
+        metrics_streams.insert(
+            "gpu".to_string(),
+            vec![MetricSample::new(MetricType::GpuUtilization, 60.0, "GPU")],
+        );
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: bottleneck_types
+                    .iter()
+                    .map(|bottleneck_type| Bottleneck {
+                        bottleneck_type: bottleneck_type.clone(),
+                        severity: 60,
+                        evidence: vec![],
+                        summary: "Bottleneck".to_string(),
+                        details: String::new(),
+                        onset: None,
+                        active_fraction: 0.0,
+                    })
+                    .collect(),
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn create_rollup_session() -> Session {
+        let mut session = create_test_session();
+        session.runs = vec![
+            create_rollup_run("Stock", &[40.0, 50.0, 60.0], &[BottleneckType::Gpu]),
+            create_rollup_run("Tuned", &[20.0, 30.0], &[BottleneckType::Cpu, BottleneckType::Thermal]),
+            create_rollup_run("Stress", &[90.0, 95.0], &[BottleneckType::Cpu]),
+        ];
+        session
+    }
+
+    #[test]
+    fn test_session_rollup_extremes_across_runs() {
+        let rollup = session_rollup(&create_rollup_session());
+
+        assert_eq!(rollup.metrics.len(), 2);
+        let cpu = rollup.metrics.iter().find(|m| m.metric_type == MetricType::CpuUtilization).unwrap();
+        assert_eq!(cpu.min, 20.0);
+        assert_eq!(cpu.min_run, "Tuned");
+        assert_eq!(cpu.max, 95.0);
+        assert_eq!(cpu.max_run, "Stress");
+        assert!((cpu.mean - 55.0).abs() < 1e-9);
+        assert_eq!(cpu.unit, "percent");
+
+        assert_eq!(rollup.most_common_bottleneck, Some(BottleneckType::Cpu));
+        assert_eq!(rollup.most_common_bottleneck_count, 2);
+    }
+
+    #[test]
+    fn test_rollup_rendered_in_each_format() {
+        let session = create_rollup_session();
+        let hardware = session.hardware_config_snapshot.clone();
+        let render = |format| generate_session_report(&session, &hardware, &ReportConfig { format, ..ReportConfig::default() });
+
+        let text = render(ReportFormat::Text);
+        assert!(text.contains("SESSION SUMMARY"));
+        assert!(text.contains("min 20.0 (Tuned) / max 95.0 (Stress) / mean 55.0 percent"));
+        assert!(text.contains("Most common bottleneck: Cpu (2 detections)"));
+
+        let html = render(ReportFormat::Html);
+        assert!(html.contains("<h2>Session Summary</h2>"));
+        assert!(html.contains("<td>95.0 (Stress)</td>"));
+
+        let json: serde_json::Value = serde_json::from_str(&render(ReportFormat::Json)).unwrap();
+        assert_eq!(json["rollup"]["most_common_bottleneck"], "cpu");
+    }
+
+    #[test]
+    fn test_single_run_report_has_no_rollup() {
+        let mut session = create_test_session();
+        session.runs.push(create_rollup_run("Only", &[50.0], &[]));
+        let hardware = session.hardware_config_snapshot.clone();
+        let config = ReportConfig { format: ReportFormat::Text, ..ReportConfig::default() };
+
+        let rollup = session_rollup(&session);
+        assert_eq!(rollup.most_common_bottleneck, None);
+        assert!(!generate_session_report(&session, &hardware, &config).contains("SESSION SUMMARY"));
+    }
+
+    #[tokio::test]
+    async fn test_write_report_infers_format_from_extension() {
+        let session = create_rollup_session();
+        let hardware = session.hardware_config_snapshot.clone();
+        let dir = TempDir::new().unwrap();
+        // The extension wins over the configured HTML format
+        let config = ReportConfig { format: ReportFormat::Html, ..ReportConfig::default() };
+
+        let mut written = std::collections::HashMap::new();
+        for extension in ["txt", "html", "md", "json", "csv", "pdf"] {
+            let path = dir.path().join("reports").join(format!("session.{}", extension));
+            let bytes = write_report(&session, &hardware, &config, &path).await.unwrap();
+            let contents = std::fs::read(&path).unwrap();
+            assert_eq!(bytes, contents.len() as u64);
+            written.insert(extension, contents);
+        }
+        let text = |extension| String::from_utf8(written[extension].clone()).unwrap();
+
+        assert!(text("txt").contains("BOTTLENECK ANALYSIS"));
+        assert!(text("html").contains("<h2>Session Summary</h2>"));
+        assert!(text("md").starts_with("# PC Rig Hardware & Bottleneck Analysis Report"));
+        assert!(text("md").contains("## Run 3: Stress"));
+        let json: serde_json::Value = serde_json::from_str(&text("json")).unwrap();
+        assert_eq!(json["runs"].as_array().unwrap().len(), 3);
+        let csv = text("csv");
+        assert!(csv.starts_with("run,metric,unit,count,min,avg,p95,max,bottlenecks\n"));
+        assert!(csv.contains("\nTuned,CpuUtilization,percent,2,20.00,25.00,"));
+        assert!(csv.contains(",Cpu;Thermal\n"));
+        let pdf = &written["pdf"];
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        assert!(pdf.windows(11).any(|w| w == b"(Run 3: Str"));
+
+        // Unknown extensions keep the configured format
+        let path = dir.path().join("session.report");
+        write_report(&session, &hardware, &config, &path).await.unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("<h2>Session Summary</h2>"));
+    }
+}
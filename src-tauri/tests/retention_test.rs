@@ -0,0 +1,230 @@
+//! Unit tests for retention statistics and manual cleanup
+//!
+//! Tests the operations behind the retention commands for file and database
+//! storage following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MetricSample, MetricType, Run,
+        Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::settings::AdvancedSettings;
+    use stats_io_lib::persistence::retention::{
+        cleanup_now, retention_overview, RetentionPolicy, RetentionStats,
+    };
+    use stats_io_lib::persistence::{DatabaseStorage, SessionStorage};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_test_session(age_days: i64) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now() - Duration::days(age_days),
+            end_time: Some(Utc::now() - Duration::days(age_days)),
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Baseline".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![create_test_run()],
+        }
+    }
+
+    fn create_test_run() -> Run {
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert(
+            "cpu".to_string(),
+            vec![MetricSample::new(MetricType::CpuUtilization, 50.0, "CPU")],
+        );
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn create_policy(min_sessions_to_keep: usize) -> RetentionPolicy {
+        RetentionPolicy {
+            retention_days: 30,
+            auto_cleanup_enabled: false,
+            min_sessions_to_keep,
+        }
+    }
+
+    /// Three sessions past a 30-day retention and two recent ones
+    fn create_sessions() -> Vec<Session> {
+        [120, 90, 60, 5, 1].into_iter().map(create_test_session).collect()
+    }
+
+    #[tokio::test]
+    async fn test_file_cleanup_keeps_minimum() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().to_path_buf());
+        let sessions = create_sessions();
+        for session in &sessions {
+            storage.save_session(session).await.unwrap();
+        }
+        let policy = create_policy(3);
+
+        let before = retention_overview(temp_dir.path(), None, &policy).await.unwrap();
+        assert_eq!(before.files.total_sessions, 5);
+        assert_eq!(before.files.sessions_to_delete, 2);
+        assert!(before.database.is_none());
+
+        let report = cleanup_now(temp_dir.path(), None, &policy).await.unwrap();
+        assert_eq!(report.deleted, 2);
+        assert_eq!(report.stats.files.total_sessions, 3);
+        assert_eq!(report.stats.files.sessions_to_delete, 0);
+
+        // The oldest sessions go first
+        let remaining = storage.list_sessions().await.unwrap();
+        assert!(!remaining.contains(&sessions[0].id));
+        assert!(!remaining.contains(&sessions[1].id));
+        assert!(remaining.contains(&sessions[2].id));
+    }
+
+    #[tokio::test]
+    async fn test_database_cleanup_keeps_minimum() {
+        let temp_dir = TempDir::new().unwrap();
+        let database = DatabaseStorage::new(temp_dir.path().join("sessions.db")).unwrap();
+        let sessions_dir = temp_dir.path().join("sessions");
+        let sessions = create_sessions();
+        for session in &sessions {
+            database.save_session(session).unwrap();
+        }
+        let policy = create_policy(3);
+
+        let before = retention_overview(&sessions_dir, Some(&database), &policy).await.unwrap();
+        assert_eq!(before.files.total_sessions, 0);
+        let db_stats = before.database.unwrap();
+        assert_eq!(db_stats.total_sessions, 5);
+        assert_eq!(db_stats.sessions_to_delete, 2);
+
+        let report = cleanup_now(&sessions_dir, Some(&database), &policy).await.unwrap();
+        assert_eq!(report.deleted, 2);
+        assert_eq!(report.stats.database.as_ref().unwrap().total_sessions, 3);
+        assert_eq!(report.stats.database.unwrap().sessions_to_delete, 0);
+
+        assert!(database.load_session(&sessions[0].id).is_err());
+        assert!(database.load_session(&sessions[2].id).is_ok());
+        assert_eq!(database.search_runs("Run").unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_across_both_stores() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().join("sessions"));
+        let database = DatabaseStorage::new(temp_dir.path().join("sessions.db")).unwrap();
+        for session in create_sessions() {
+            storage.save_session(&session).await.unwrap();
+            database.save_session(&session).unwrap();
+        }
+
+        let report = cleanup_now(&temp_dir.path().join("sessions"), Some(&database), &create_policy(0))
+            .await
+            .unwrap();
+        assert_eq!(report.deleted, 6);
+        assert_eq!(report.stats.files.total_sessions, 2);
+        assert_eq!(report.stats.database.unwrap().total_sessions, 2);
+    }
+
+    #[tokio::test]
+    async fn test_minimum_above_total_deletes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().to_path_buf());
+        for session in create_sessions() {
+            storage.save_session(&session).await.unwrap();
+        }
+
+        let report = cleanup_now(temp_dir.path(), None, &create_policy(10)).await.unwrap();
+        assert_eq!(report.deleted, 0);
+        assert_eq!(report.stats.files.total_sessions, 5);
+    }
+
+    #[test]
+    fn test_stats_count_sessions_past_retention() {
+        let now = Utc::now();
+        let dates: Vec<_> = (0..20).map(|i| now - Duration::days(if i < 5 { 100 } else { 1 })).collect();
+
+        // Plenty of recent sessions remain, so every old one can go
+        let stats = RetentionStats::from_dates(&dates, &create_policy(10));
+        assert_eq!(stats.total_sessions, 20);
+        assert_eq!(stats.sessions_to_delete, 5);
+
+        let stats = RetentionStats::from_dates(&dates, &create_policy(18));
+        assert_eq!(stats.sessions_to_delete, 2);
+    }
+
+    #[test]
+    fn test_policy_from_settings() {
+        let settings = AdvancedSettings {
+            session_retention_days: Some(14),
+            min_sessions_to_keep: 4,
+            auto_cleanup_enabled: true,
+            ..AdvancedSettings::default()
+        };
+        assert_eq!(
+            settings.retention_policy(),
+            RetentionPolicy {
+                retention_days: 14,
+                auto_cleanup_enabled: true,
+                min_sessions_to_keep: 4,
+            }
+        );
+
+        // Unlimited retention never marks sessions for deletion
+        let unlimited = AdvancedSettings {
+            session_retention_days: None,
+            ..AdvancedSettings::default()
+        };
+        let dates = vec![Utc::now() - Duration::days(10_000); 20];
+        let stats = RetentionStats::from_dates(&dates, &unlimited.retention_policy());
+        assert_eq!(stats.sessions_to_delete, 0);
+
+        // Settings saved before the retention fields existed
+        let legacy: AdvancedSettings = serde_json::from_str(
+            r#"{"enable_debug_logging": false, "auto_save_sessions": true, "session_retention_days": 30}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.min_sessions_to_keep, RetentionPolicy::default().min_sessions_to_keep);
+        assert!(!legacy.auto_cleanup_enabled);
+    }
+}
@@ -0,0 +1,86 @@
+//! Unit tests for rule explanation traces
+//!
+//! Tests that traces record each rule's inputs and decision following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::{TraceComparison, CPU_HIGH_THRESHOLD, GPU_HIGH_THRESHOLD};
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use chrono::{DateTime, Duration, Utc};
+
+    /// 20 seconds of CPU and GPU utilization ending at `now`
+    fn create_samples(now: DateTime<Utc>, cpu: f64, gpu: f64) -> Vec<MetricSample> {
+        (0..20)
+            .flat_map(|i| {
+                let timestamp = now - Duration::seconds(20 - i);
+                vec![
+                    MetricSample::new(MetricType::CpuUtilization, cpu, "CPU").with_timestamp(timestamp),
+                    MetricSample::new(MetricType::GpuUtilization, gpu, "GPU").with_timestamp(timestamp),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cpu_rule_trace_shows_threshold_comparison_and_fire() {
+        let now = Utc::now();
+        let engine = AnalysisEngine::new();
+        let samples = create_samples(now, 95.0, 40.0);
+
+        let traces = engine.trace_at(&samples, Some(30), None, now);
+        assert_eq!(
+            traces.iter().map(|t| t.rule.clone()).collect::<Vec<_>>(),
+            engine.rule_names()
+        );
+
+        let workload = traces.iter().find(|t| t.rule == "workload").expect("workload trace");
+        assert!(workload.fired, "{}", workload.decision);
+        assert_eq!(workload.bottlenecks, vec![(BottleneckType::Cpu, 67)]);
+        assert!(workload.decision.starts_with("Fired: Cpu"), "{}", workload.decision);
+        assert_eq!(workload.sample_count, 40);
+
+        let cpu = &workload.checks[0];
+        assert_eq!(cpu.metric_type, MetricType::CpuUtilization);
+        assert_eq!(cpu.statistic, "Average");
+        assert_eq!(cpu.value, Some(95.0));
+        assert_eq!(cpu.threshold, CPU_HIGH_THRESHOLD);
+        assert_eq!(cpu.comparison, TraceComparison::Above);
+        assert_eq!(cpu.sample_count, 20);
+        assert!(cpu.passed);
+
+        let gpu_headroom = &workload.checks[1];
+        assert_eq!(gpu_headroom.metric_type, MetricType::GpuUtilization);
+        assert_eq!(gpu_headroom.comparison, TraceComparison::Below);
+        assert!(gpu_headroom.passed);
+
+        // The GPU rule's check is recorded even though it did not fire
+        let gpu = workload
+            .checks
+            .iter()
+            .find(|c| c.metric_type == MetricType::GpuUtilization && c.threshold == GPU_HIGH_THRESHOLD)
+            .expect("GPU check");
+        assert!(!gpu.passed);
+
+        // Decisions match the analysis
+        let result = engine.analyze_at(&samples, Some(30), None, now);
+        let fired: usize = traces.iter().map(|t| t.bottlenecks.len()).sum();
+        assert_eq!(fired, result.bottlenecks.len());
+    }
+
+    #[test]
+    fn test_quiet_rule_explains_failed_check() {
+        let now = Utc::now();
+        let traces = AnalysisEngine::new().trace_at(&create_samples(now, 60.0, 50.0), Some(30), None, now);
+
+        let workload = traces.iter().find(|t| t.rule == "workload").unwrap();
+        assert!(!workload.fired);
+        assert!(workload.bottlenecks.is_empty());
+        assert_eq!(workload.checks[0].value, Some(60.0));
+        assert!(!workload.checks[0].passed);
+        assert!(workload.decision.contains("60.0 is not above 85.0"), "{}", workload.decision);
+        assert!(workload.decision.contains("no MemoryUsage samples"), "{}", workload.decision);
+
+        assert!(traces.iter().all(|t| !t.fired));
+    }
+}
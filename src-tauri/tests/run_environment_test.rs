@@ -0,0 +1,230 @@
+//! Unit tests for run environment capture
+//!
+//! Tests reproducibility metadata and environment comparison warnings following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::comparison::{compare_runs, environment_differences};
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, GPUInfo, HardwareConfig, MemoryInfo, MetricSample, MetricType,
+        MotherboardInfo, Run, RunEnvironment, Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::hardware::environment::{
+        capture_run_environment, is_virtual_machine_vendor, parse_power_scheme,
+    };
+    use stats_io_lib::persistence::reports::{
+        generate_comparison_report, generate_session_report, ReportConfig, ReportFormat,
+    };
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+    use stats_io_lib::persistence::DatabaseStorage;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_hardware(driver_version: &str) -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                architecture: Some("x86_64".to_string()),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: Some(3000.0),
+                boost_clock_mhz: Some(4500.0),
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![GPUInfo {
+                model: "RTX 3070".to_string(),
+                vendor: "NVIDIA".to_string(),
+                vram_total_mb: Some(8192),
+                driver_version: Some(driver_version.to_string()),
+                pci_id: None,
+            }],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: Some(2),
+                speed_mhz: Some(3200),
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    fn create_environment(driver: &str) -> RunEnvironment {
+        RunEnvironment {
+            os_version: Some("Windows 11 Pro 23H2 (22631)".to_string()),
+            gpu_driver_versions: vec![format!("RTX 3070 {}", driver)],
+            power_plan: Some("Balanced".to_string()),
+            background_process_count: Some(200),
+            virtual_machine: Some(false),
+            foreground_process: None,
+        }
+    }
+
+    fn create_run(name: &str, environment: Option<RunEnvironment>) -> Run {
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert(
+            "cpu".to_string(),
+            vec![MetricSample::new(MetricType::CpuUtilization, 50.0, "CPU")],
+        );
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_environment_captured() {
+        let environment = capture_run_environment(&create_hardware("551.86")).await;
+
+        assert_eq!(environment.gpu_driver_versions, vec!["RTX 3070 551.86".to_string()]);
+        assert!(environment.background_process_count.is_some_and(|count| count > 0));
+    }
+
+    #[tokio::test]
+    async fn test_collector_keeps_environment_from_collection_start() {
+        let collector = MetricsCollector::new(MetricsCollectorConfig::default());
+        assert!(collector.run_environment().await.is_none());
+
+        let environment = capture_run_environment(&create_hardware("551.86")).await;
+        collector.set_run_environment(environment.clone()).await;
+        assert_eq!(collector.run_environment().await, Some(environment));
+    }
+
+    #[test]
+    fn test_driver_difference_flagged() {
+        let run1 = create_run("Before", Some(create_environment("551.86")));
+        let run2 = create_run("After", Some(create_environment("560.70")));
+
+        let comparison = compare_runs(&run1, &run2);
+        assert_eq!(comparison.environment_warnings.len(), 1);
+        assert!(comparison.environment_warnings[0].contains("GPU driver"));
+        assert!(comparison.environment_warnings[0].contains("560.70"));
+
+        let config = ReportConfig {
+            format: ReportFormat::Text,
+            ..ReportConfig::default()
+        };
+        let report = generate_comparison_report(&comparison, &run1, &run2, &config);
+        assert!(report.contains("different environments"));
+    }
+
+    #[test]
+    fn test_matching_or_unknown_environments_not_flagged() {
+        let environment = create_environment("551.86");
+        assert!(environment_differences(&environment, &environment.clone()).is_empty());
+
+        // Runs saved before environments were captured
+        let comparison = compare_runs(
+            &create_run("Old", None),
+            &create_run("New", Some(environment.clone())),
+        );
+        assert!(comparison.environment_warnings.is_empty());
+
+        // Unknown fields are not treated as changes
+        let partial = RunEnvironment {
+            power_plan: None,
+            ..environment.clone()
+        };
+        assert!(environment_differences(&environment, &partial).is_empty());
+    }
+
+    #[test]
+    fn test_material_differences_flagged() {
+        let base = create_environment("551.86");
+        let changed = RunEnvironment {
+            power_plan: Some("High performance".to_string()),
+            background_process_count: Some(320),
+            virtual_machine: Some(true),
+            ..base.clone()
+        };
+        assert_eq!(environment_differences(&base, &changed).len(), 3);
+
+        // A handful of extra processes is ordinary fluctuation
+        let similar = RunEnvironment {
+            background_process_count: Some(210),
+            ..base.clone()
+        };
+        assert!(environment_differences(&base, &similar).is_empty());
+    }
+
+    #[test]
+    fn test_power_scheme_and_vm_vendor_parsing() {
+        assert_eq!(
+            parse_power_scheme("Power Scheme GUID: 381b4222-f694-41f0-9685-ff5bb260df2e  (Balanced)"),
+            Some("Balanced".to_string())
+        );
+        assert_eq!(parse_power_scheme("Power Scheme GUID: 381b4222"), None);
+
+        assert!(is_virtual_machine_vendor("VMware, Inc."));
+        assert!(is_virtual_machine_vendor("Microsoft Corporation Virtual Machine"));
+        assert!(!is_virtual_machine_vendor("ASUSTeK COMPUTER INC."));
+    }
+
+    #[tokio::test]
+    async fn test_vm_motherboard_detected() {
+        let mut hardware = create_hardware("551.86");
+        hardware.motherboard = Some(MotherboardInfo {
+            model: "VirtualBox".to_string(),
+            manufacturer: "innotek GmbH".to_string(),
+            chipset: None,
+            bios_version: None,
+        });
+
+        assert_eq!(capture_run_environment(&hardware).await.virtual_machine, Some(true));
+    }
+
+    #[test]
+    fn test_environment_persisted_and_reported() {
+        let session = Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: create_hardware("551.86"),
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Baseline".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![create_run("Run", Some(create_environment("551.86")))],
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let database = DatabaseStorage::new(temp_dir.path().join("sessions.db")).unwrap();
+        database.save_session(&session).unwrap();
+        let loaded = database.load_session(&session.id).unwrap();
+        assert_eq!(loaded.runs[0].environment, Some(create_environment("551.86")));
+
+        let config = ReportConfig {
+            format: ReportFormat::Text,
+            ..ReportConfig::default()
+        };
+        let report = generate_session_report(&session, &session.hardware_config_snapshot, &config);
+        assert!(report.contains("Run 1 environment:"));
+        assert!(report.contains("RTX 3070 551.86"));
+
+        // Runs saved before the field existed
+        let legacy: Run = serde_json::from_str(
+            r#"{"id": "00000000-0000-0000-0000-000000000001", "name": "Old", "metrics_streams": {}, "analysis_result": null, "notes": null}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.environment, None);
+    }
+}
@@ -0,0 +1,136 @@
+//! Unit tests for regenerating the insights of a saved run
+//!
+//! Tests insights rebuilt from a stored analysis result following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::BottleneckAcknowledgment;
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata, HardwareConfig,
+        MemoryInfo, Run, Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::sessions::run_insights;
+    use stats_io_lib::persistence::storage::SessionStorage;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn create_run(analysis_result: Option<BottleneckAnalysisResult>) -> Run {
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams: HashMap::new(),
+            analysis_result,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn cpu_analysis() -> BottleneckAnalysisResult {
+        BottleneckAnalysisResult {
+            bottlenecks: vec![Bottleneck {
+                bottleneck_type: BottleneckType::Cpu,
+                severity: 70,
+                evidence: vec![],
+                summary: "CPU-bound: average CPU utilization is 97.0%".to_string(),
+                details: "CPU utilization stayed high".to_string(),
+                onset: None,
+                active_fraction: 0.0,
+            }],
+            timestamp: Utc::now(),
+            coverage: None,
+        }
+    }
+
+    fn create_session(runs: Vec<Run>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: None,
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: None,
+                    boost_clock_mhz: None,
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: None,
+                    speed_mhz: None,
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "gaming".to_string(),
+                name: "Gaming".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stored_cpu_bottleneck_yields_cpu_recommendations() {
+        let dir = std::env::temp_dir().join(format!("stats-io-run-insights-{}", Uuid::new_v4()));
+        let storage = SessionStorage::new(dir.clone());
+        let session = create_session(vec![create_run(Some(cpu_analysis()))]);
+        storage.save_session(&session).await.unwrap();
+
+        let loaded = storage.load_session(&session.id).await.unwrap();
+        let insights = run_insights(&loaded, &loaded.runs[0], &[]);
+
+        assert_eq!(insights.severity, 70);
+        assert!(insights.summary.contains("CPU-bound"));
+        assert!(!insights.recommendations.is_empty());
+        assert!(insights
+            .recommendations
+            .iter()
+            .all(|r| r.bottleneck_type == Some(BottleneckType::Cpu)));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_unanalyzed_run_has_empty_insights() {
+        let session = create_session(vec![create_run(None)]);
+
+        let insights = run_insights(&session, &session.runs[0], &[]);
+
+        assert!(insights.recommendations.is_empty());
+        assert_eq!(insights.severity, 0);
+    }
+
+    #[test]
+    fn test_acknowledged_bottleneck_is_dismissed() {
+        let session = create_session(vec![create_run(Some(cpu_analysis()))]);
+        let acknowledgments = vec![BottleneckAcknowledgment {
+            bottleneck_type: BottleneckType::Cpu,
+            max_severity: None,
+            note: None,
+            acknowledged_at: Utc::now(),
+        }];
+
+        let insights = run_insights(&session, &session.runs[0], &acknowledgments);
+
+        assert_eq!(insights.dismissed.len(), 1);
+        assert_eq!(insights.severity, 0);
+    }
+}
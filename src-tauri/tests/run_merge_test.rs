@@ -0,0 +1,151 @@
+//! Unit tests for run merging
+//!
+//! Tests combining runs of a session following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MetricSample, MetricType, Run,
+        Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::error::SessionError;
+    use stats_io_lib::core::sessions::merge_runs;
+    use chrono::{DateTime, Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn create_test_session(runs: Vec<Run>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Test Profile".to_string(),
+                workload_type: WorkloadType::General,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs,
+        }
+    }
+
+    fn create_test_run(name: &str, start: DateTime<Utc>, samples: i64, notes: Option<&str>) -> Run {
+        let cpu_samples = (0..samples)
+            .map(|i| MetricSample {
+                timestamp: start + Duration::seconds(i),
+                metric_type: MetricType::CpuUtilization,
+                value: 50.0,
+                unit: "%".to_string(),
+                source_component: "CPU".to_string(),
+            })
+            .collect();
+
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("cpu".to_string(), cpu_samples);
+
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: notes.map(|n| n.to_string()),
+            environment: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_adjacent_runs() {
+        let start = Utc::now() - Duration::seconds(60);
+        // Second run overlaps the last sample of the first
+        let run1 = create_test_run("Part 1", start, 10, Some("first half"));
+        let run2 = create_test_run("Part 2", start + Duration::seconds(9), 10, Some("second half"));
+        let ids = vec![run1.id, run2.id];
+        let mut session = create_test_session(vec![run1, run2]);
+
+        let merged = merge_runs(&mut session, &ids, "Full capture").unwrap();
+
+        let samples = &merged.metrics_streams["cpu"];
+        assert_eq!(samples.len(), 19);
+        assert!(samples.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+        assert_eq!(merged.name, "Full capture");
+        assert_eq!(merged.notes.as_deref(), Some("first half\n\nsecond half"));
+        assert!(merged.analysis_result.is_some());
+
+        assert_eq!(session.runs.len(), 1);
+        assert_eq!(session.runs[0].id, merged.id);
+    }
+
+    #[test]
+    fn test_merge_keeps_position_of_first_run() {
+        let start = Utc::now() - Duration::seconds(60);
+        let before = create_test_run("Before", start - Duration::seconds(30), 5, None);
+        let run1 = create_test_run("Part 1", start, 5, None);
+        let run2 = create_test_run("Part 2", start + Duration::seconds(5), 5, None);
+        let ids = vec![run2.id, run1.id];
+        let mut session = create_test_session(vec![before, run1, run2]);
+
+        let merged = merge_runs(&mut session, &ids, "Merged").unwrap();
+
+        assert_eq!(session.runs.len(), 2);
+        assert_eq!(session.runs[0].name, "Before");
+        assert_eq!(session.runs[1].id, merged.id);
+        assert_eq!(merged.metrics_streams["cpu"].len(), 10);
+        assert!(merged.notes.is_none());
+    }
+
+    #[test]
+    fn test_merge_rejects_run_from_other_session() {
+        let start = Utc::now() - Duration::seconds(60);
+        let run1 = create_test_run("Part 1", start, 5, None);
+        let foreign = create_test_run("Foreign", start, 5, None);
+        let ids = vec![run1.id, foreign.id];
+        let mut session = create_test_session(vec![run1]);
+
+        let result = merge_runs(&mut session, &ids, "Merged");
+
+        assert!(matches!(result, Err(SessionError::RunNotFound(_))));
+        assert_eq!(session.runs.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_requires_two_runs() {
+        let run1 = create_test_run("Part 1", Utc::now(), 5, None);
+        let ids = vec![run1.id, run1.id];
+        let mut session = create_test_session(vec![run1]);
+
+        let result = merge_runs(&mut session, &ids, "Merged");
+
+        assert!(matches!(result, Err(SessionError::InvalidOperation(_))));
+    }
+}
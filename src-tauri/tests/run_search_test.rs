@@ -0,0 +1,146 @@
+//! Unit tests for run notes search
+//!
+//! Tests the database full-text index and the file storage fallback following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, Run, Session, WorkloadProfile,
+        WorkloadType,
+    };
+    use stats_io_lib::persistence::{DatabaseStorage, SessionStorage};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_run(name: &str, notes: Option<&str>) -> Run {
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams: HashMap::new(),
+            analysis_result: None,
+            notes: notes.map(str::to_string),
+            environment: None,
+        }
+    }
+
+    fn create_session(runs: Vec<Run>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Test Profile".to_string(),
+                workload_type: WorkloadType::General,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs,
+        }
+    }
+
+    fn create_sessions() -> Vec<Session> {
+        vec![
+            create_session(vec![
+                create_run("Baseline", Some("Stock cooler, fans on auto")),
+                create_run("After repaste", Some("Temperatures dropped 8C after repaste with new compound")),
+            ]),
+            create_session(vec![
+                create_run("Overclock", Some("Memory at 3600, paste unchanged")),
+                create_run("Untitled", None),
+            ]),
+        ]
+    }
+
+    #[test]
+    fn test_database_phrase_search() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(&temp_dir.path().join("sessions.db")).unwrap();
+        let sessions = create_sessions();
+        for session in &sessions {
+            storage.save_session(session).unwrap();
+        }
+
+        let matches = storage.search_runs("after repaste").unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].session_id, sessions[0].id);
+        assert_eq!(matches[0].run_id, sessions[0].runs[1].id);
+        assert!(matches[0].snippet.to_lowercase().contains("[after repaste]"));
+
+        // Words must appear as a phrase
+        assert!(storage.search_runs("repaste after compound").unwrap().is_empty());
+        assert!(storage.search_runs("\"unbalanced").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_database_index_follows_updates() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = DatabaseStorage::new(&temp_dir.path().join("sessions.db")).unwrap();
+        let mut session = create_sessions().remove(1);
+        storage.save_session(&session).unwrap();
+        assert_eq!(storage.search_runs("paste").unwrap().len(), 1);
+
+        session.runs[0].notes = Some("Memory at 3600".to_string());
+        storage.save_session(&session).unwrap();
+        assert!(storage.search_runs("paste").unwrap().is_empty());
+        assert_eq!(storage.search_runs("overclock").unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_storage_substring_fallback() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().to_path_buf());
+        let sessions = create_sessions();
+        for session in &sessions {
+            storage.save_session(session).await.unwrap();
+        }
+
+        let matches = storage.search_runs("AFTER REPASTE").await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].run_id, sessions[0].runs[1].id);
+        assert_eq!(matches[0].snippet, "Temperatures dropped 8C [after repaste] with new compound");
+
+        // Substring matching also finds words inside other words
+        let matches = storage.search_runs("paste").await.unwrap();
+        assert_eq!(matches.len(), 2);
+
+        // Runs without notes are matched by name
+        let matches = storage.search_runs("untitled").await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].snippet, "[Untitled]");
+
+        assert!(storage.search_runs("undervolt").await.unwrap().is_empty());
+    }
+}
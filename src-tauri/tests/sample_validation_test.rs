@@ -0,0 +1,69 @@
+//! Unit tests for sample plausibility checks
+//!
+//! Tests clamping and rejection of implausible readings following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType};
+    use stats_io_lib::metrics::validation::{validate_sample, validate_samples, Plausibility};
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+
+    #[test]
+    fn test_negative_temperature_rejected_and_overshoot_clamped() {
+        let mut cold = MetricSample::new(MetricType::Temperature, -5.0, "CPU");
+        assert_eq!(validate_sample(&mut cold), Plausibility::Rejected);
+
+        let mut overshoot = MetricSample::new(MetricType::CpuUtilization, 140.0, "CPU");
+        assert_eq!(validate_sample(&mut overshoot), Plausibility::Clamped);
+        assert_eq!(overshoot.value, 100.0);
+
+        let mut normal = MetricSample::new(MetricType::GpuTemperature, 72.0, "GPU");
+        assert_eq!(validate_sample(&mut normal), Plausibility::Valid);
+        assert_eq!(normal.value, 72.0);
+    }
+
+    #[test]
+    fn test_ranges_per_metric_type() {
+        let mut samples = vec![
+            MetricSample::new(MetricType::GpuUtilization, -1.0, "GPU"),
+            MetricSample::new(MetricType::GpuUtilization, 400.0, "GPU"),
+            MetricSample::new(MetricType::Temperature, 151.0, "CPU"),
+            MetricSample::new(MetricType::CpuClock, 65_535.0, "CPU"),
+            MetricSample::new(MetricType::StorageReadThroughput, -3.0, "Storage"),
+            MetricSample::new(MetricType::Fps, f64::NAN, "Game"),
+            MetricSample::new(MetricType::StorageReadThroughput, 0.0, "Storage"),
+            MetricSample::new(MetricType::CpuClock, 5_200.0, "CPU"),
+            MetricSample::new(MetricType::Voltage, -12.0, "nct6798 -12V"),
+        ];
+        // Fan speed read as RPM is not a percentage
+        let mut rpm = MetricSample::new(MetricType::FanSpeed, 1_450.0, "CPU");
+        rpm.unit = "RPM".to_string();
+        samples.push(rpm);
+
+        let counts = validate_samples(&mut samples);
+        assert_eq!(counts.rejected, 6);
+        assert_eq!(counts.clamped, 0);
+        assert_eq!(samples.len(), 4);
+        assert!(samples.iter().any(|s| s.metric_type == MetricType::FanSpeed && s.value == 1_450.0));
+    }
+
+    #[tokio::test]
+    async fn test_collector_counts_rejections_in_health() {
+        let collector = MetricsCollector::new(MetricsCollectorConfig::default());
+        collector
+            .record_samples(&[
+                MetricSample::new(MetricType::Temperature, -5.0, "CPU"),
+                MetricSample::new(MetricType::CpuUtilization, 140.0, "CPU"),
+                MetricSample::new(MetricType::CpuUtilization, 40.0, "CPU"),
+            ])
+            .await;
+
+        let health = collector.health().await;
+        assert_eq!(health.rejected_samples, 1);
+        assert_eq!(health.clamped_samples, 1);
+
+        let buffer = collector.get_buffer().await;
+        assert_eq!(buffer.len(), 2);
+        assert!(buffer.iter().all(|s| s.metric_type == MetricType::CpuUtilization && s.value <= 100.0));
+    }
+}
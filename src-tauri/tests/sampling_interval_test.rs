@@ -0,0 +1,66 @@
+//! Unit tests for sampling loop tick behavior
+//!
+//! Tests catch-up behavior of the sampling interval after a stall following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::settings::{MissedTickBehavior, SamplingSettings};
+    use stats_io_lib::metrics::collector::sampling_interval;
+    use stats_io_lib::metrics::MetricsCollectorConfig;
+    use std::time::{Duration, Instant};
+
+    const PERIOD: Duration = Duration::from_millis(20);
+
+    /// Stall the loop for several periods, consume the late tick, then time
+    /// the next three ticks
+    async fn ticks_after_stall(behavior: MissedTickBehavior) -> Duration {
+        let mut interval = sampling_interval(PERIOD, behavior);
+        interval.tick().await;
+
+        // Block the runtime thread like a slow provider call would
+        std::thread::sleep(PERIOD * 6);
+        interval.tick().await;
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            interval.tick().await;
+        }
+        start.elapsed()
+    }
+
+    #[tokio::test]
+    async fn test_delay_does_not_burst_after_stall() {
+        let elapsed = ticks_after_stall(MissedTickBehavior::Delay).await;
+        assert!(
+            elapsed >= PERIOD * 3 - Duration::from_millis(5),
+            "catch-up ticks fired after {:?}",
+            elapsed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burst_catches_up_immediately() {
+        let elapsed = ticks_after_stall(MissedTickBehavior::Burst).await;
+        assert!(elapsed < PERIOD, "expected a burst, ticks took {:?}", elapsed);
+    }
+
+    #[test]
+    fn test_defaults_to_delay() {
+        assert_eq!(MissedTickBehavior::default(), MissedTickBehavior::Delay);
+        assert_eq!(SamplingSettings::default().missed_tick_behavior, MissedTickBehavior::Delay);
+        assert_eq!(MetricsCollectorConfig::default().missed_tick_behavior, MissedTickBehavior::Delay);
+    }
+
+    #[test]
+    fn test_settings_without_behavior_deserialize() {
+        let settings: SamplingSettings =
+            serde_json::from_str(r#"{"interval_ms": 500, "buffer_size": 100}"#).unwrap();
+        assert_eq!(settings.missed_tick_behavior, MissedTickBehavior::Delay);
+
+        let settings: SamplingSettings = serde_json::from_str(
+            r#"{"interval_ms": 500, "buffer_size": 100, "missed_tick_behavior": "skip"}"#,
+        )
+        .unwrap();
+        assert_eq!(settings.missed_tick_behavior, MissedTickBehavior::Skip);
+    }
+}
@@ -0,0 +1,139 @@
+//! Unit tests for hardware sensor enumeration
+//!
+//! Tests hwmon parsing, sensor samples and cooling inference following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::detect_enhanced_thermal_bottleneck;
+    use stats_io_lib::core::domain::MetricType;
+    use stats_io_lib::metrics::collector::sensor_samples;
+    use stats_io_lib::metrics::models::{SensorKind, SensorReading};
+    use stats_io_lib::metrics::providers::sensors::{cooling_info_from_sensors, read_hwmon_sensors};
+    use chrono::Utc;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn write_chip(root: &Path, dir: &str, files: &[(&str, &str)]) {
+        let chip = root.join(dir);
+        fs::create_dir_all(&chip).unwrap();
+        for (name, content) in files {
+            fs::write(chip.join(name), format!("{}\n", content)).unwrap();
+        }
+    }
+
+    /// A Super I/O chip, a CPU temperature chip and a GPU driver chip
+    fn create_hwmon_fixture() -> TempDir {
+        let root = TempDir::new().unwrap();
+        write_chip(root.path(), "hwmon0", &[
+            ("name", "k10temp"),
+            ("temp1_input", "54250"),
+            ("temp1_label", "Tctl"),
+        ]);
+        write_chip(root.path(), "hwmon1", &[
+            ("name", "amdgpu"),
+            ("temp1_input", "61000"),
+            ("fan1_input", "1200"),
+        ]);
+        write_chip(root.path(), "hwmon2", &[
+            ("name", "nct6798"),
+            ("fan1_input", "1150"),
+            ("fan1_label", "CPU Fan"),
+            ("fan2_input", "820"),
+            ("fan3_input", "0"),
+            ("in0_input", "1216"),
+            ("in0_label", "Vcore"),
+            ("temp7_input", "-128000"),
+            ("pwm1", "128"),
+        ]);
+        root
+    }
+
+    #[test]
+    fn test_hwmon_fixture_parsed() {
+        let root = create_hwmon_fixture();
+        let readings = read_hwmon_sensors(root.path());
+
+        let names: Vec<&str> = readings.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["k10temp Tctl", "nct6798 CPU Fan", "nct6798 fan2", "nct6798 fan3", "nct6798 Vcore"]
+        );
+
+        assert_eq!(readings[0].kind, SensorKind::Temperature);
+        assert!((readings[0].value - 54.25).abs() < 1e-9);
+        assert_eq!(readings[0].unit, "Celsius");
+        assert_eq!(readings[1].kind, SensorKind::Fan);
+        assert_eq!(readings[1].value, 1150.0);
+        assert_eq!(readings[1].unit, "RPM");
+        assert_eq!(readings[4].kind, SensorKind::Voltage);
+        assert!((readings[4].value - 1.216).abs() < 1e-9);
+        assert_eq!(readings[4].unit, "volts");
+    }
+
+    #[test]
+    fn test_missing_hwmon_root_is_empty() {
+        let root = TempDir::new().unwrap();
+        assert!(read_hwmon_sensors(&root.path().join("missing")).is_empty());
+        assert!(read_hwmon_sensors(root.path()).is_empty());
+    }
+
+    #[test]
+    fn test_sensor_samples() {
+        let root = create_hwmon_fixture();
+        let samples = sensor_samples(&read_hwmon_sensors(root.path()), Utc::now());
+
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[0].metric_type, MetricType::SensorTemperature);
+        assert_eq!(samples[0].unit, "Celsius");
+        assert_eq!(samples[0].source_component, "k10temp Tctl");
+        assert_eq!(samples[1].metric_type, MetricType::FanSpeed);
+        assert_eq!(samples[1].unit, "RPM");
+        assert_eq!(samples[4].metric_type, MetricType::Voltage);
+    }
+
+    #[test]
+    fn test_hot_board_sensors_are_not_cpu_temperature() {
+        let readings: Vec<SensorReading> = ["nvme Composite", "VRM MOS"]
+            .iter()
+            .map(|name| SensorReading {
+                name: name.to_string(),
+                kind: SensorKind::Temperature,
+                value: 95.0,
+                unit: "Celsius".to_string(),
+            })
+            .collect();
+        let samples: Vec<_> = (0..10).flat_map(|_| sensor_samples(&readings, Utc::now())).collect();
+
+        assert!(samples.iter().all(|m| m.metric_type != MetricType::Temperature));
+        assert!(detect_enhanced_thermal_bottleneck(&samples).is_none());
+    }
+
+    #[test]
+    fn test_cooling_inferred_from_fans() {
+        let root = create_hwmon_fixture();
+        let cooling = cooling_info_from_sensors(&read_hwmon_sensors(root.path())).unwrap();
+        // fan2 spins; fan3 reports 0 RPM from an empty header
+        assert_eq!(cooling.case_fans, Some(1));
+        assert_eq!(cooling.cpu_cooler_type, None);
+
+        let with_pump = vec![
+            SensorReading {
+                name: "Pump".to_string(),
+                kind: SensorKind::Fan,
+                value: 2400.0,
+                unit: "RPM".to_string(),
+            },
+            SensorReading {
+                name: "Chassis Fan #1".to_string(),
+                kind: SensorKind::Fan,
+                value: 900.0,
+                unit: "RPM".to_string(),
+            },
+        ];
+        let cooling = cooling_info_from_sensors(&with_pump).unwrap();
+        assert_eq!(cooling.cpu_cooler_type.as_deref(), Some("Liquid"));
+
+        assert!(cooling_info_from_sensors(&[]).is_none());
+    }
+}
@@ -0,0 +1,160 @@
+//! Unit tests for session cloning
+//!
+//! Tests deep copies of sessions with fresh IDs following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata,
+        HardwareConfig, MemoryInfo, MetricSample, MetricType, Run, Session, WorkloadProfile,
+        WorkloadType,
+    };
+    use stats_io_lib::core::sessions::clone_session;
+    use stats_io_lib::persistence::SessionStorage;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_test_session(runs: Vec<Run>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: Some(Utc::now()),
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Baseline".to_string(),
+                workload_type: WorkloadType::Gaming,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs,
+        }
+    }
+
+    fn create_test_run(name: &str, cpu: f64) -> Run {
+        let start = Utc::now();
+        let samples = (0..10)
+            .map(|i| {
+                MetricSample::new(MetricType::CpuUtilization, cpu, "CPU")
+                    .with_timestamp(start + Duration::seconds(i))
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("cpu".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks: vec![Bottleneck {
+                    bottleneck_type: BottleneckType::Cpu,
+                    severity: 70,
+                    evidence: vec![],
+                    summary: "CPU bound".to_string(),
+                    details: String::new(),
+                    onset: None,
+                    active_fraction: 0.0,
+                }],
+                timestamp: start,
+                coverage: None,
+            }),
+            notes: Some(format!("{} notes", name)),
+            environment: None,
+        }
+    }
+
+    fn create_multi_run_session() -> Session {
+        create_test_session(vec![create_test_run("Stock", 90.0), create_test_run("Tuned", 80.0)])
+    }
+
+    #[test]
+    fn test_clone_has_new_ids_and_same_content() {
+        let original = create_multi_run_session();
+        let clone = clone_session(&original, "Variant");
+
+        assert_ne!(clone.id, original.id);
+        assert_eq!(clone.profile.name, "Variant");
+        assert_eq!(clone.profile.workload_type, original.profile.workload_type);
+        assert_eq!(clone.start_time, original.start_time);
+        assert_eq!(clone.runs.len(), 2);
+
+        for (cloned, source) in clone.runs.iter().zip(&original.runs) {
+            assert_ne!(cloned.id, source.id);
+            assert_eq!(cloned.name, source.name);
+            assert_eq!(cloned.notes, source.notes);
+            assert_eq!(
+                serde_json::to_value(&cloned.metrics_streams).unwrap(),
+                serde_json::to_value(&source.metrics_streams).unwrap()
+            );
+            assert_eq!(
+                serde_json::to_value(&cloned.analysis_result).unwrap(),
+                serde_json::to_value(&source.analysis_result).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_editing_clone_leaves_original_unchanged() {
+        let original = create_multi_run_session();
+        let mut clone = clone_session(&original, "Variant");
+
+        clone.runs[0].metrics_streams.get_mut("cpu").unwrap()[0].value = 1.0;
+        clone.runs[0].analysis_result.as_mut().unwrap().bottlenecks.clear();
+        clone.runs.pop();
+
+        assert_eq!(original.runs.len(), 2);
+        assert_eq!(original.runs[0].metrics_streams["cpu"][0].value, 90.0);
+        assert_eq!(original.runs[0].analysis_result.as_ref().unwrap().bottlenecks.len(), 1);
+        assert_eq!(original.profile.name, "Baseline");
+    }
+
+    #[tokio::test]
+    async fn test_clone_persists_alongside_original() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(temp_dir.path().to_path_buf());
+        let original = create_multi_run_session();
+        storage.save_session(&original).await.unwrap();
+
+        let clone = clone_session(&storage.load_session(&original.id).await.unwrap(), "Variant");
+        storage.save_session(&clone).await.unwrap();
+
+        let ids = storage.list_sessions().await.unwrap();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&original.id) && ids.contains(&clone.id));
+
+        let loaded = storage.load_session(&clone.id).await.unwrap();
+        assert_eq!(loaded.runs[1].metrics_streams["cpu"].len(), 10);
+        assert_eq!(storage.load_session(&original.id).await.unwrap().profile.name, "Baseline");
+    }
+}
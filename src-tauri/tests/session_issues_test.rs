@@ -0,0 +1,159 @@
+//! Unit tests for batch analysis across sessions
+//!
+//! Tests that only sessions with severe bottlenecks are reported following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata, GPUInfo,
+        HardwareConfig, MemoryInfo, MetricSample, MetricType, Run, Session, WorkloadProfile,
+        WorkloadType,
+    };
+    use stats_io_lib::persistence::SessionStorage;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn analyzed_run(name: &str, bottlenecks: &[(BottleneckType, u8)]) -> Run {
+        let bottlenecks = bottlenecks
+            .iter()
+            .map(|(bottleneck_type, severity)| Bottleneck {
+                bottleneck_type: bottleneck_type.clone(),
+                severity: *severity,
+                evidence: vec![],
+                summary: format!("{:?} bottleneck", bottleneck_type),
+                details: String::new(),
+                onset: None,
+                active_fraction: 0.0,
+            })
+            .collect();
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams: HashMap::new(),
+            analysis_result: Some(BottleneckAnalysisResult {
+                bottlenecks,
+                timestamp: Utc::now(),
+                coverage: None,
+            }),
+            notes: None,
+            environment: None,
+        }
+    }
+
+    /// Run saved without an analysis, holding 20 seconds at the given temperature
+    fn unanalyzed_run(name: &str, temperature: f64) -> Run {
+        let now = Utc::now();
+        let samples = (0..20)
+            .map(|i| {
+                MetricSample::new(MetricType::Temperature, temperature, "CPU")
+                    .with_timestamp(now - Duration::seconds(20 - i))
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("temperature".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    fn create_session(rig: &str, runs: Vec<Run>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: format!("{} CPU", rig),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: None,
+                    boost_clock_mhz: None,
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![GPUInfo {
+                    model: "Test GPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    vram_total_mb: Some(8192),
+                    driver_version: None,
+                    pci_id: None,
+                }],
+                memory: MemoryInfo {
+                    total_mb: 32768,
+                    channels: Some(2),
+                    speed_mhz: None,
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: rig.to_string(),
+                workload_type: WorkloadType::General,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_sessions_with_critical_bottlenecks_are_returned() {
+        let dir = TempDir::new().unwrap();
+        let storage = SessionStorage::new(dir.path().to_path_buf());
+        let sessions = vec![
+            create_session("Rig A", vec![
+                analyzed_run("Warmup", &[(BottleneckType::Cpu, 40)]),
+                analyzed_run("Load", &[(BottleneckType::Cpu, 60), (BottleneckType::Thermal, 95)]),
+            ]),
+            create_session("Rig B", vec![analyzed_run("Load", &[(BottleneckType::Gpu, 70)])]),
+            create_session("Rig C", vec![unanalyzed_run("Overnight", 97.0)]),
+            create_session("Rig D", vec![unanalyzed_run("Overnight", 60.0)]),
+            create_session("Rig E", vec![]),
+        ];
+        for session in &sessions {
+            storage.save_session(session).await.unwrap();
+        }
+        // Unreadable files are skipped
+        std::fs::write(dir.path().join(format!("{}.json", Uuid::new_v4())), "{").unwrap();
+
+        let summaries = storage.analyze_all_sessions(90).await.unwrap();
+
+        let names: Vec<&str> = summaries.iter().map(|s| s.session_name.as_str()).collect();
+        assert_eq!(names.len(), 2, "{:?}", names);
+        assert!(names.contains(&"Rig A") && names.contains(&"Rig C"));
+        assert!(summaries.windows(2).all(|w| w[0].worst_severity >= w[1].worst_severity));
+
+        let rig_a = summaries.iter().find(|s| s.session_name == "Rig A").unwrap();
+        assert_eq!(rig_a.session_id, sessions[0].id);
+        assert_eq!(rig_a.worst_severity, 95);
+        assert_eq!(rig_a.runs.len(), 1);
+        assert_eq!(rig_a.runs[0].bottleneck_type, BottleneckType::Thermal);
+        assert_eq!(rig_a.hardware, "Rig A CPU / Test GPU / 32 GB RAM (Test)");
+        assert!(rig_a.summary.contains("1 of 2 runs affected"), "{}", rig_a.summary);
+
+        let rig_c = summaries.iter().find(|s| s.session_name == "Rig C").unwrap();
+        assert_eq!(rig_c.runs[0].bottleneck_type, BottleneckType::Thermal);
+
+        // A lower threshold picks up the moderate GPU bottleneck too
+        assert_eq!(storage.analyze_all_sessions(70).await.unwrap().len(), 3);
+    }
+}
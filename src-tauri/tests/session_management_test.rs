@@ -24,6 +24,7 @@ mod tests {
             threads: 16,
             base_clock_mhz: Some(3000.0),
             boost_clock_mhz: Some(4500.0),
+            core_max_frequencies_mhz: vec![],
         };
 
         let memory = MemoryInfo {
@@ -166,6 +167,7 @@ mod tests {
             },
             analysis_result: None,
             notes: Some("Test notes".to_string()),
+            environment: None,
         };
         
         session.runs.push(run);
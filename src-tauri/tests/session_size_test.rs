@@ -0,0 +1,138 @@
+//! Unit tests for session size estimation
+//!
+//! Tests estimated disk footprint against actual serialized sizes following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MetricSample, MetricType, Run,
+        Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::persistence::size::{estimate_session_size, LARGE_SESSION_BYTES};
+    use stats_io_lib::persistence::DatabaseStorage;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn create_run(samples_per_stream: usize) -> Run {
+        let start = Utc::now();
+        let mut metrics_streams = HashMap::new();
+        for (key, metric_type, source) in [
+            ("cpu", MetricType::CpuUtilization, "CPU"),
+            ("gpu", MetricType::GpuUtilization, "GPU"),
+            ("memory", MetricType::MemoryUsage, "Memory"),
+        ] {
+            let samples = (0..samples_per_stream)
+                .map(|i| {
+                    MetricSample::new(metric_type.clone(), 10.0 + (i % 90) as f64 * 0.37, source)
+                        .with_timestamp(start + Duration::milliseconds(i as i64 * 1000))
+                })
+                .collect();
+            metrics_streams.insert(key.to_string(), samples);
+        }
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: Some("Benchmark pass".to_string()),
+            environment: None,
+        }
+    }
+
+    fn create_session(runs: Vec<Run>) -> Session {
+        Session {
+            id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Test Profile".to_string(),
+                workload_type: WorkloadType::General,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs,
+        }
+    }
+
+    #[test]
+    fn test_counts_samples_and_rows() {
+        let session = create_session(vec![create_run(500), create_run(250)]);
+        let estimate = estimate_session_size(&session);
+
+        assert_eq!(estimate.sample_count, 2250);
+        assert_eq!(estimate.metrics_rows, 2250);
+        assert!(!estimate.is_large);
+        assert!(estimate.json_bytes < LARGE_SESSION_BYTES);
+    }
+
+    #[test]
+    fn test_json_estimate_close_to_actual_size() {
+        let session = create_session(vec![create_run(1000), create_run(400)]);
+        let estimate = estimate_session_size(&session);
+        let actual = serde_json::to_string_pretty(&session).unwrap().len() as f64;
+
+        let error = (estimate.json_bytes as f64 - actual).abs() / actual;
+        assert!(error < 0.05, "estimate {} vs actual {}", estimate.json_bytes, actual);
+    }
+
+    #[test]
+    fn test_db_estimate_within_bound_of_database_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("sessions.db");
+        let session = create_session(vec![create_run(2000)]);
+        let estimate = estimate_session_size(&session);
+
+        let storage = DatabaseStorage::new(&db_path).unwrap();
+        storage.save_session(&session).unwrap();
+        drop(storage);
+        let actual = std::fs::metadata(&db_path).unwrap().len() as f64;
+
+        let ratio = estimate.db_bytes as f64 / actual;
+        assert!((0.5..2.0).contains(&ratio), "estimate {} vs file {}", estimate.db_bytes, actual);
+    }
+
+    #[test]
+    fn test_empty_session() {
+        let session = create_session(vec![]);
+        let estimate = estimate_session_size(&session);
+
+        assert_eq!(estimate.sample_count, 0);
+        assert_eq!(
+            estimate.json_bytes,
+            serde_json::to_string_pretty(&session).unwrap().len() as u64
+        );
+    }
+}
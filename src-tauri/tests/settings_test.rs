@@ -37,6 +37,8 @@ mod tests {
             vram_high: 85.0,
             temperature_warning: 75.0,
             temperature_critical: 85.0,
+            min_comparison_effect_percent: 5.0,
+            trim_fraction: 0.0,
         };
 
         manager.update_thresholds(new_thresholds.clone()).unwrap();
@@ -82,5 +84,15 @@ mod tests {
         let settings = manager.get_settings();
         assert_eq!(settings.thresholds.cpu_high, 85.0); // Back to default
     }
-}
 
+    #[test]
+    fn test_display_timezone_defaults_to_local() {
+        let (manager, _temp_dir) = create_temp_settings_manager();
+        assert_eq!(manager.get_settings().units.display_timezone, "local");
+
+        // Settings saved before the field existed still load
+        let legacy = r#"{"temperature":"celsius","memory":"gb"}"#;
+        let units: stats_io_lib::core::settings::UnitPreferences = serde_json::from_str(legacy).unwrap();
+        assert_eq!(units.display_timezone, "local");
+    }
+}
@@ -0,0 +1,186 @@
+//! Unit tests for bottleneck severity scaling
+//!
+//! Tests severity against the threshold excess following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::alerts::{AlertConfig, AlertWatcher};
+    use stats_io_lib::analysis::insights::grades::severity_grade;
+    use stats_io_lib::analysis::rules::{analyze_bottlenecks, calculate_severity, detect_gpu_thermal};
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, ThresholdOverrides, ThresholdStatistic, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::persistence::reports::SeverityBands;
+    use stats_io_lib::test_support::MetricStreamBuilder;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    const THRESHOLDS: [f64; 5] = [50.0, 70.0, 85.0, 90.0, 99.0];
+
+    #[test]
+    fn test_zero_at_threshold_and_hundred_at_max() {
+        for threshold in THRESHOLDS {
+            assert_eq!(calculate_severity(threshold, threshold), 0, "threshold {}", threshold);
+            assert_eq!(calculate_severity(threshold - 10.0, threshold), 0, "threshold {}", threshold);
+            assert_eq!(calculate_severity(100.0, threshold), 100, "threshold {}", threshold);
+            assert_eq!(calculate_severity(150.0, threshold), 100, "threshold {}", threshold);
+        }
+    }
+
+    #[test]
+    fn test_monotonic_across_thresholds() {
+        for threshold in THRESHOLDS {
+            let mut previous = 0;
+            let mut value = threshold;
+            while value <= 100.0 {
+                let severity = calculate_severity(value, threshold);
+                assert!(severity >= previous, "{} at threshold {} dropped to {}", value, threshold, severity);
+                previous = severity;
+                value += 0.25;
+            }
+        }
+    }
+
+    #[test]
+    fn test_marginal_excess_is_low() {
+        assert!(calculate_severity(90.1, 90.0) <= 1);
+        assert_eq!(calculate_severity(95.0, 90.0), 50);
+        assert_eq!(calculate_severity(92.5, 85.0), 50);
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert_eq!(calculate_severity(f64::NAN, 90.0), 0);
+        assert_eq!(calculate_severity(f64::INFINITY, 90.0), 100);
+        assert_eq!(calculate_severity(101.0, 100.0), 100);
+        assert_eq!(calculate_severity(100.0, 100.0), 0);
+    }
+
+    #[test]
+    fn test_cpu_severity_uses_overridden_threshold() {
+        let samples = MetricStreamBuilder::new().cpu(85.0).gpu(40.0).over_seconds(20).build();
+        let profile = WorkloadProfile {
+            id: "strict".to_string(),
+            name: "Strict".to_string(),
+            workload_type: WorkloadType::Gaming,
+            parameters: HashMap::new(),
+            threshold_overrides: Some(ThresholdOverrides {
+                cpu_high: Some(70.0),
+                gpu_high: None,
+                ram_high: None,
+                vram_high: None,
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds: None,
+            }),
+        };
+
+        let result = analyze_bottlenecks(&samples, 30, Some(&profile));
+        let cpu = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Cpu)
+            .expect("CPU bottleneck");
+        assert_eq!(cpu.severity, 50);
+    }
+
+    #[test]
+    fn test_typical_cpu_bound_run_alerts_grades_and_tiers() {
+        let now = Utc::now();
+        let samples = MetricStreamBuilder::new().cpu(95.0).gpu(40.0).over_seconds(20).ending_at(now).build();
+
+        let result = analyze_bottlenecks(&samples, 30, None);
+        let cpu = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Cpu)
+            .expect("CPU bottleneck");
+        assert_eq!(cpu.severity, 67);
+        assert_eq!(severity_grade(cpu.severity), 'D');
+        assert_eq!(SeverityBands::default().tier(cpu.severity), "high");
+
+        let mut watcher = AlertWatcher::new(AlertConfig::default());
+        watcher.push(samples);
+        let alerts = watcher.evaluate(now);
+        assert!(alerts.iter().any(|a| a.bottleneck_type == BottleneckType::Cpu), "{:?}", alerts);
+    }
+
+    #[test]
+    fn test_marginal_cpu_bound_run_is_low_and_quiet() {
+        let now = Utc::now();
+        let samples = MetricStreamBuilder::new().cpu(86.0).gpu(40.0).over_seconds(20).ending_at(now).build();
+
+        let result = analyze_bottlenecks(&samples, 30, None);
+        let cpu = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Cpu)
+            .expect("CPU bottleneck");
+        assert_eq!(severity_grade(cpu.severity), 'B');
+        assert_eq!(SeverityBands::default().tier(cpu.severity), "low");
+
+        let mut watcher = AlertWatcher::new(AlertConfig::default());
+        watcher.push(samples);
+        assert!(watcher.evaluate(now).is_empty());
+    }
+
+    #[test]
+    fn test_marginal_advanced_finding_ranks_below_cpu_bound() {
+        let samples = MetricStreamBuilder::new()
+            .cpu(95.0)
+            .gpu(40.0)
+            .metric(MetricType::DpcTime, 6.0, "System")
+            .over_seconds(20)
+            .build();
+
+        let result = analyze_bottlenecks(&samples, 30, None);
+        let types: Vec<_> = result.bottlenecks.iter().map(|b| b.bottleneck_type.clone()).collect();
+        assert_eq!(types, vec![BottleneckType::Cpu, BottleneckType::Responsiveness]);
+    }
+
+    #[test]
+    fn test_storage_severity_scales_from_queue_threshold() {
+        let samples = MetricStreamBuilder::new()
+            .metric(MetricType::StorageQueueDepth, 25.0, "Disk 0")
+            .over_seconds(20)
+            .build();
+
+        let result = analyze_bottlenecks(&samples, 30, None);
+        let storage = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Storage)
+            .expect("storage bottleneck");
+        // Halfway between the threshold of 10 and full severity at 40
+        assert_eq!(storage.severity, 50);
+    }
+
+    #[test]
+    fn test_gpu_thermal_severity_scales_from_limit() {
+        let now = Utc::now();
+        let temperatures = |edge: f64, hotspot: f64| -> Vec<MetricSample> {
+            (0..10)
+                .flat_map(|i| {
+                    let timestamp = now - Duration::seconds(10 - i);
+                    vec![
+                        MetricSample::new(MetricType::GpuTemperature, edge, "GPU").with_timestamp(timestamp),
+                        MetricSample::new(MetricType::GpuHotspotTemperature, hotspot, "GPU").with_timestamp(timestamp),
+                    ]
+                })
+                .collect()
+        };
+
+        // 5°C past the 83°C edge limit, with a normal hotspot gap
+        let throttle = detect_gpu_thermal(&temperatures(88.0, 98.0));
+        assert_eq!(throttle.len(), 1);
+        assert_eq!(throttle[0].severity, 50);
+
+        // A 30°C gap is two thirds of the way to full severity at 35°C
+        let gap = detect_gpu_thermal(&temperatures(65.0, 95.0));
+        assert_eq!(gap[0].severity, 67);
+
+        // Cooler contact never outranks hardware limits
+        let gap = detect_gpu_thermal(&temperatures(50.0, 100.0));
+        assert_eq!(gap[0].severity, 80);
+    }
+}
@@ -0,0 +1,134 @@
+//! Unit tests for hardware and metrics snapshots
+//!
+//! Tests building, exporting, and importing shareable snapshots following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, GPUInfo, HardwareConfig, MemoryInfo, MetricSample, MetricType,
+    };
+    use stats_io_lib::core::error::PersistenceError;
+    use stats_io_lib::persistence::export_import::{
+        export_snapshot, import_snapshot, Snapshot, SNAPSHOT_FORMAT, SNAPSHOT_SCHEMA_VERSION,
+    };
+    use chrono::{Duration, Utc};
+    use tempfile::TempDir;
+
+    fn create_test_hardware() -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                architecture: Some("x86_64".to_string()),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: Some(3000.0),
+                boost_clock_mhz: Some(4500.0),
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![GPUInfo {
+                model: "Test GPU".to_string(),
+                vendor: "NVIDIA".to_string(),
+                vram_total_mb: Some(8192),
+                driver_version: None,
+                pci_id: None,
+            }],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: Some(2),
+                speed_mhz: Some(3200),
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    /// One GPU temperature sample per second for the last `seconds` seconds
+    fn create_buffer(seconds: i64) -> Vec<MetricSample> {
+        let newest = Utc::now();
+        (0..seconds)
+            .map(|age| {
+                MetricSample::new(MetricType::GpuTemperature, 60.0 + age as f64, "GPU")
+                    .with_timestamp(newest - Duration::seconds(age))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_snapshot_keeps_latest_window() {
+        let snapshot = Snapshot::new(create_test_hardware(), &create_buffer(60), 5);
+
+        assert_eq!(snapshot.format, SNAPSHOT_FORMAT);
+        assert_eq!(snapshot.schema_version, SNAPSHOT_SCHEMA_VERSION);
+        assert!(!snapshot.app_version.is_empty());
+        assert_eq!(snapshot.window_seconds, 5);
+        // Samples aged 0 through 5 seconds
+        assert_eq!(snapshot.metrics.len(), 6);
+        assert!(snapshot.metrics.iter().all(|m| m.value <= 65.0));
+    }
+
+    #[test]
+    fn test_huge_window_keeps_whole_buffer() {
+        let snapshot = Snapshot::new(create_test_hardware(), &create_buffer(60), i64::MAX);
+        assert_eq!(snapshot.metrics.len(), 60);
+        assert_eq!(snapshot.window_seconds, i64::MAX);
+    }
+
+    #[test]
+    fn test_snapshot_of_empty_buffer() {
+        let snapshot = Snapshot::new(create_test_hardware(), &[], 10);
+        assert!(snapshot.metrics.is_empty());
+        assert_eq!(snapshot.hardware.cpu.model, "Test CPU");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("snapshot.json");
+        let snapshot = Snapshot::new(create_test_hardware(), &create_buffer(20), 10);
+
+        export_snapshot(&snapshot, &path).await.unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"format\": \"stats-io-snapshot\""));
+
+        let imported = import_snapshot(&path).await.unwrap();
+        assert_eq!(imported.schema_version, snapshot.schema_version);
+        assert_eq!(imported.created_at, snapshot.created_at);
+        assert_eq!(imported.hardware.gpus[0].model, "Test GPU");
+        assert_eq!(imported.metrics.len(), snapshot.metrics.len());
+        assert_eq!(imported.metrics[0].timestamp, snapshot.metrics[0].timestamp);
+        assert_eq!(imported.metrics[0].unit, "Celsius");
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_other_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("snapshot.json");
+        let mut snapshot = Snapshot::new(create_test_hardware(), &[], 10);
+
+        snapshot.format = "something-else".to_string();
+        export_snapshot(&snapshot, &path).await.unwrap();
+        assert!(matches!(
+            import_snapshot(&path).await,
+            Err(PersistenceError::Deserialization(_))
+        ));
+
+        snapshot.format = SNAPSHOT_FORMAT.to_string();
+        snapshot.schema_version = SNAPSHOT_SCHEMA_VERSION + 1;
+        export_snapshot(&snapshot, &path).await.unwrap();
+        assert!(matches!(
+            import_snapshot(&path).await,
+            Err(PersistenceError::SchemaVersionMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+//! Unit tests for storage-aware recommendations
+//!
+//! Tests that drive free space and type tailor storage advice following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::{
+        generate_insights, generate_insights_for_hardware, is_nearly_full,
+    };
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata,
+        HardwareConfig, MemoryInfo, StorageInfo, StorageType,
+    };
+    use chrono::Utc;
+
+    fn storage_result() -> BottleneckAnalysisResult {
+        BottleneckAnalysisResult {
+            bottlenecks: vec![Bottleneck {
+                bottleneck_type: BottleneckType::Storage,
+                severity: 60,
+                evidence: vec![],
+                summary: "Storage-bound".to_string(),
+                details: String::new(),
+                onset: None,
+                active_fraction: 0.0,
+            }],
+            timestamp: Utc::now(),
+            coverage: None,
+        }
+    }
+
+    fn drive(storage_type: StorageType, capacity_mb: u64, free_mb: Option<u64>) -> StorageInfo {
+        StorageInfo {
+            model: format!("{:?} drive", storage_type),
+            capacity_mb,
+            storage_type,
+            interface: None,
+            free_mb,
+        }
+    }
+
+    fn hardware_with(storage_devices: Vec<StorageInfo>) -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                architecture: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: None,
+                speed_mhz: None,
+                modules: vec![],
+            },
+            storage_devices,
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    fn texts(hardware: &HardwareConfig) -> Vec<String> {
+        generate_insights_for_hardware(&storage_result(), None, &[], hardware)
+            .recommendations
+            .into_iter()
+            .map(|r| r.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_nearly_full_hdd_and_healthy_nvme_differ() {
+        let full_hdd = texts(&hardware_with(vec![drive(StorageType::HDD, 1_000_000, Some(40_000))]));
+        let healthy_nvme = texts(&hardware_with(vec![drive(StorageType::NVMe, 1_000_000, Some(600_000))]));
+
+        assert!(full_hdd[0].starts_with("Free up space on the nearly full drive"), "{:?}", full_hdd);
+        assert_eq!(full_hdd.iter().filter(|t| t.contains("Free up")).count(), 1);
+        assert!(full_hdd.iter().any(|t| t.contains("Defragment the hard drive")));
+
+        assert!(healthy_nvme.iter().all(|t| !t.to_lowercase().contains("defragment")), "{:?}", healthy_nvme);
+        assert!(healthy_nvme.iter().all(|t| !t.contains("Free up")));
+        assert_ne!(full_hdd, healthy_nvme);
+    }
+
+    #[test]
+    fn test_unknown_drive_details_keep_generic_advice() {
+        let generic: Vec<String> = generate_insights(&storage_result(), None)
+            .recommendations
+            .into_iter()
+            .map(|r| r.text)
+            .collect();
+
+        assert_eq!(texts(&hardware_with(vec![])), generic);
+        assert_eq!(texts(&hardware_with(vec![drive(StorageType::Unknown, 1_000_000, None)])), generic);
+    }
+
+    #[test]
+    fn test_unattributed_bottleneck_on_several_drives_keeps_generic_advice() {
+        let generic: Vec<String> = generate_insights(&storage_result(), None)
+            .recommendations
+            .into_iter()
+            .map(|r| r.text)
+            .collect();
+        // The nearly full HDD may not be the drive under load
+        let drives = vec![
+            drive(StorageType::NVMe, 1_000_000, Some(600_000)),
+            drive(StorageType::HDD, 1_000_000, Some(40_000)),
+        ];
+
+        assert_eq!(texts(&hardware_with(drives)), generic);
+    }
+
+    #[test]
+    fn test_nearly_full_threshold() {
+        assert!(is_nearly_full(&drive(StorageType::SSD, 500_000, Some(49_999))));
+        assert!(!is_nearly_full(&drive(StorageType::SSD, 500_000, Some(50_000))));
+        assert!(!is_nearly_full(&drive(StorageType::SSD, 500_000, None)));
+        assert!(!is_nearly_full(&drive(StorageType::SSD, 0, Some(0))));
+    }
+}
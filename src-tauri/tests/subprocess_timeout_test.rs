@@ -0,0 +1,78 @@
+//! Unit tests for subprocess timeouts and retries of command-line providers
+//!
+//! Tests timeouts, retries and failure counting with mock commands following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::error::MetricsError;
+    use stats_io_lib::metrics::providers::subprocess::{
+        output_with_retry, run_with_timeout, subprocess_failures,
+    };
+    use std::time::{Duration, Instant};
+    use tokio::process::Command;
+
+    const TIMEOUT: Duration = Duration::from_millis(200);
+
+    /// Shell command, so the mocks only depend on `sh`
+    fn shell(script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", script]);
+        cmd
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hanging_command_times_out() {
+        let baseline = subprocess_failures();
+        let started = Instant::now();
+
+        let result = output_with_retry(&mut shell("sleep 10"), TIMEOUT).await;
+
+        // Timeouts are not retried, so a single timeout bounds the call
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(matches!(result, Err(MetricsError::Timeout(_))));
+        assert!(subprocess_failures().since(&baseline).timeouts >= 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_successful_command_returns_output() {
+        let output = run_with_timeout(&mut shell("echo 42"), TIMEOUT).await.unwrap();
+
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "42");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_transient_failure_is_retried_once() {
+        let marker = std::env::temp_dir().join(format!("stats-io-retry-{}", uuid::Uuid::new_v4()));
+        let marker = marker.to_string_lossy().to_string();
+        // Fails on the first run and succeeds once the marker exists
+        let script = format!("if [ -e '{0}' ]; then echo ok; else touch '{0}'; exit 1; fi", marker);
+        let baseline = subprocess_failures();
+
+        let output = output_with_retry(&mut shell(&script), TIMEOUT).await.unwrap();
+
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+        assert!(subprocess_failures().since(&baseline).failures >= 1);
+        let _ = std::fs::remove_file(&marker);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_persistent_failure_is_reported() {
+        let result = output_with_retry(&mut shell("exit 3"), TIMEOUT).await;
+
+        assert!(matches!(result, Err(MetricsError::CollectionFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_missing_program_is_not_retried() {
+        let started = Instant::now();
+
+        let result = output_with_retry(&mut Command::new("stats-io-no-such-program"), TIMEOUT).await;
+
+        assert!(started.elapsed() < TIMEOUT);
+        assert!(matches!(result, Err(MetricsError::Io(_))));
+    }
+}
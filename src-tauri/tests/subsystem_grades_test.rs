@@ -0,0 +1,133 @@
+//! Unit tests for subsystem grading
+//!
+//! Tests A–F grades per subsystem following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::grades::{severity_grade, temperature_grade};
+    use stats_io_lib::analysis::insights::{grade_label, grade_subsystems};
+    use stats_io_lib::core::domain::{
+        Bottleneck, BottleneckAnalysisResult, BottleneckType, CPUInfo, DetectionMetadata,
+        HardwareConfig, MemoryInfo, MetricSample, MetricType,
+    };
+    use chrono::Utc;
+
+    fn create_hardware() -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                architecture: None,
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: None,
+                speed_mhz: None,
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    fn create_result(bottlenecks: Vec<(BottleneckType, u8)>) -> BottleneckAnalysisResult {
+        BottleneckAnalysisResult {
+            bottlenecks: bottlenecks
+                .into_iter()
+                .map(|(bottleneck_type, severity)| Bottleneck {
+                    bottleneck_type,
+                    severity,
+                    evidence: vec![],
+                    summary: String::new(),
+                    details: String::new(),
+                    onset: None,
+                    active_fraction: 0.0,
+                })
+                .collect(),
+            timestamp: Utc::now(),
+            coverage: None,
+        }
+    }
+
+    fn temperature_sample(value: f64) -> MetricSample {
+        MetricSample {
+            timestamp: Utc::now(),
+            metric_type: MetricType::Temperature,
+            value,
+            unit: "Celsius".to_string(),
+            source_component: "CPU".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_severe_thermal_bottleneck_grades_f() {
+        let result = create_result(vec![(BottleneckType::Thermal, 95)]);
+
+        let grades = grade_subsystems(&result, &create_hardware(), &[temperature_sample(70.0)]);
+
+        assert_eq!(grades[&BottleneckType::Thermal], 'F');
+    }
+
+    #[test]
+    fn test_cpu_without_bottleneck_grades_a() {
+        let grades = grade_subsystems(&create_result(vec![]), &create_hardware(), &[]);
+
+        assert_eq!(grades[&BottleneckType::Cpu], 'A');
+        assert_eq!(grades[&BottleneckType::Ram], 'A');
+    }
+
+    #[test]
+    fn test_subsystems_without_data_are_not_applicable() {
+        let grades = grade_subsystems(&create_result(vec![]), &create_hardware(), &[]);
+
+        assert_eq!(grade_label(&grades, &BottleneckType::Gpu), "N/A");
+        assert_eq!(grade_label(&grades, &BottleneckType::Thermal), "N/A");
+        assert_eq!(grade_label(&grades, &BottleneckType::Storage), "N/A");
+    }
+
+    #[test]
+    fn test_thermal_grade_uses_absolute_temperature() {
+        let grades = grade_subsystems(
+            &create_result(vec![]),
+            &create_hardware(),
+            &[temperature_sample(60.0), temperature_sample(92.0)],
+        );
+
+        assert_eq!(grades[&BottleneckType::Thermal], 'F');
+    }
+
+    #[test]
+    fn test_grade_boundaries() {
+        assert_eq!(severity_grade(0), 'A');
+        assert_eq!(severity_grade(20), 'B');
+        assert_eq!(severity_grade(30), 'C');
+        assert_eq!(severity_grade(60), 'D');
+        assert_eq!(severity_grade(80), 'F');
+        assert_eq!(temperature_grade(65.0), 'A');
+        assert_eq!(temperature_grade(82.0), 'C');
+    }
+
+    #[test]
+    fn test_vram_bottleneck_counts_against_gpu() {
+        let result = create_result(vec![(BottleneckType::Vram, 70)]);
+
+        let grades = grade_subsystems(&result, &create_hardware(), &[]);
+
+        assert_eq!(grades[&BottleneckType::Gpu], 'D');
+    }
+}
@@ -0,0 +1,151 @@
+//! Unit tests for sustained storage and bandwidth detection
+//!
+//! Tests that brief I/O and bandwidth bursts are ignored while sustained saturation is reported following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::advanced::{
+        detect_memory_bus_saturation, detect_memory_bus_saturation_with_min_duration,
+        detect_pcie_saturation, detect_pcie_saturation_with_min_duration,
+    };
+    use stats_io_lib::analysis::rules::{
+        analyze_bottlenecks, sustained_violation, SUSTAINED_MIN_SECONDS,
+    };
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, ThresholdOverrides, ThresholdStatistic, WorkloadProfile,
+        WorkloadType,
+    };
+    use chrono::{DateTime, Duration, Utc};
+    use std::collections::HashMap;
+
+    fn create_profile(sustained_seconds: Option<f64>) -> WorkloadProfile {
+        WorkloadProfile {
+            id: "productivity".to_string(),
+            name: "Productivity".to_string(),
+            workload_type: WorkloadType::Productivity,
+            parameters: HashMap::new(),
+            threshold_overrides: Some(ThresholdOverrides {
+                cpu_high: None,
+                gpu_high: None,
+                ram_high: None,
+                vram_high: None,
+                statistic: ThresholdStatistic::Mean,
+                combined_pressure: None,
+                sustained_seconds,
+            }),
+        }
+    }
+
+    /// One reading of each metric per second for 60 seconds, at `high` during
+    /// seconds `burst` and at `low` otherwise
+    fn create_samples(
+        metric_types: &[MetricType],
+        burst: std::ops::Range<i64>,
+        high: f64,
+        low: f64,
+    ) -> Vec<MetricSample> {
+        let start = Utc::now() - Duration::seconds(60);
+        (0..60)
+            .flat_map(|i| {
+                let value = if burst.contains(&i) { high } else { low };
+                metric_types.iter().map(move |metric_type| {
+                    MetricSample::new(metric_type.clone(), value, "Storage").with_timestamp(start + Duration::seconds(i))
+                })
+            })
+            .collect()
+    }
+
+    fn storage_types(samples: &[MetricSample], profile: &WorkloadProfile) -> Vec<BottleneckType> {
+        analyze_bottlenecks(samples, 60, Some(profile))
+            .bottlenecks
+            .into_iter()
+            .filter(|b| b.bottleneck_type == BottleneckType::Storage)
+            .map(|b| b.bottleneck_type)
+            .collect()
+    }
+
+    #[test]
+    fn test_one_second_storage_burst_does_not_fire() {
+        // Queue depth spikes for two readings, one second apart
+        let samples = create_samples(&[MetricType::StorageQueueDepth], 30..32, 40.0, 1.0);
+
+        assert!(storage_types(&samples, &create_profile(None)).is_empty());
+    }
+
+    #[test]
+    fn test_sustained_storage_saturation_fires() {
+        let samples = create_samples(&[MetricType::StorageQueueDepth], 20..40, 40.0, 1.0);
+
+        let result = analyze_bottlenecks(&samples, 60, Some(&create_profile(None)));
+        let storage = result
+            .bottlenecks
+            .iter()
+            .find(|b| b.bottleneck_type == BottleneckType::Storage)
+            .expect("storage bottleneck");
+
+        // Evidence covers the saturated stretch, not the whole window
+        let evidence = &storage.evidence[0];
+        assert_eq!(evidence.actual_value, 40.0);
+        assert_eq!((evidence.time_range_end - evidence.time_range_start).num_seconds(), 19);
+        assert!(storage.details.contains("19 seconds"));
+    }
+
+    #[test]
+    fn test_minimum_duration_is_configurable() {
+        let samples = create_samples(&[MetricType::StorageQueueDepth], 20..28, 40.0, 1.0);
+
+        assert_eq!(storage_types(&samples, &create_profile(None)), vec![BottleneckType::Storage]);
+        assert!(storage_types(&samples, &create_profile(Some(10.0))).is_empty());
+        // Zero seconds restores firing on a single saturated reading
+        let spike = create_samples(&[MetricType::StorageQueueDepth], 30..31, 40.0, 1.0);
+        assert!(storage_types(&spike, &create_profile(None)).is_empty());
+        assert_eq!(storage_types(&spike, &create_profile(Some(0.0))), vec![BottleneckType::Storage]);
+    }
+
+    #[test]
+    fn test_pcie_burst_vs_sustained_transfer() {
+        let types = [MetricType::StorageReadThroughput, MetricType::StorageWriteThroughput];
+
+        let burst = create_samples(&types, 30..32, 8000.0, 100.0);
+        assert!(detect_pcie_saturation(&burst).is_none());
+
+        let sustained = create_samples(&types, 10..50, 8000.0, 100.0);
+        let bottleneck = detect_pcie_saturation(&sustained).expect("PCIe saturation");
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Bandwidth);
+        assert_eq!(bottleneck.evidence[0].actual_value, 16000.0);
+        assert!(detect_pcie_saturation_with_min_duration(&sustained, 60.0).is_none());
+    }
+
+    #[test]
+    fn test_memory_bus_burst_vs_sustained_load() {
+        let types = [MetricType::MemoryReadThroughput, MetricType::MemoryWriteThroughput];
+
+        let burst = create_samples(&types, 30..33, 25000.0, 5000.0);
+        assert!(detect_memory_bus_saturation(&burst).is_none());
+        assert!(detect_memory_bus_saturation_with_min_duration(&burst, 2.0).is_some());
+
+        let sustained = create_samples(&types, 0..60, 25000.0, 5000.0);
+        let bottleneck = detect_memory_bus_saturation(&sustained).expect("memory bus saturation");
+        assert!(bottleneck.details.contains("59 seconds"));
+    }
+
+    #[test]
+    fn test_sustained_violation_picks_longest_unbroken_stretch() {
+        let start: DateTime<Utc> = Utc::now();
+        let values = [20.0, 20.0, 5.0, 20.0, f64::NAN, 20.0, 30.0, 20.0, 20.0, 5.0];
+        let ticks: Vec<(DateTime<Utc>, f64)> = values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| (start + Duration::seconds(i as i64), value))
+            .collect();
+
+        let violation = sustained_violation(&ticks, 2.0, |v| v > 10.0).expect("violation");
+        assert_eq!(violation.start, start + Duration::seconds(5));
+        assert_eq!(violation.duration_seconds(), 3.0);
+        assert_eq!(violation.mean, 22.5);
+        assert_eq!(violation.peak, 30.0);
+
+        assert!(sustained_violation(&ticks, 4.0, |v| v > 10.0).is_none());
+        assert!(sustained_violation(&ticks, SUSTAINED_MIN_SECONDS, |v| v > 10.0).is_none());
+    }
+}
@@ -0,0 +1,111 @@
+//! Unit tests for the synthetic hardware and metrics source
+//!
+//! Tests the analysis, insights and report pipeline end to end against seeded data following TESTING_PLAN.md.
+
+#![cfg(feature = "synthetic")]
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::generate_insights_for_hardware;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, Run, Session, WorkloadProfile, WorkloadType,
+    };
+    use stats_io_lib::core::interfaces::{GpuMetricsProvider, HardwareDetector};
+    use stats_io_lib::core::sessions::analyze_run;
+    use stats_io_lib::persistence::reports::{generate_session_report, ReportConfig, ReportFormat};
+    use stats_io_lib::synthetic::{SyntheticHardwareDetector, SyntheticMetricsProvider, SYNTHETIC_PLATFORM};
+    use chrono::{TimeZone, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    const SEED: u64 = 7;
+
+    fn values(samples: &[MetricSample]) -> Vec<(MetricType, f64)> {
+        samples.iter().map(|s| (s.metric_type.clone(), s.value)).collect()
+    }
+
+    fn create_run(samples: Vec<MetricSample>) -> Run {
+        let mut metrics_streams: HashMap<String, Vec<MetricSample>> = HashMap::new();
+        for sample in samples {
+            let key = serde_json::to_value(&sample.metric_type).unwrap().as_str().unwrap().to_string();
+            metrics_streams.entry(key).or_default().push(sample);
+        }
+        Run {
+            id: Uuid::new_v4(),
+            name: "Synthetic game".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_same_seed_same_hardware_and_streams() {
+        let first = SyntheticHardwareDetector::new(SEED).get_hardware_config().await.unwrap();
+        let second = SyntheticHardwareDetector::new(SEED).refresh().await.unwrap();
+        assert_eq!(first.metadata.platform, SYNTHETIC_PLATFORM);
+        assert_eq!(first.cpu.model, second.cpu.model);
+        assert_eq!(first.gpus[0].model, second.gpus[0].model);
+        assert!(first.metadata.warnings.is_empty());
+
+        let end = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let stream = SyntheticMetricsProvider::new(SEED).metric_stream(30, 1, end);
+        assert_eq!(values(&stream), values(&SyntheticMetricsProvider::new(SEED).metric_stream(30, 1, end)));
+        assert_ne!(values(&stream), values(&SyntheticMetricsProvider::new(SEED + 3).metric_stream(30, 1, end)));
+        assert_eq!(stream.last().unwrap().timestamp, end);
+
+        // The provider traits replay the same sequence tick by tick
+        let provider = SyntheticMetricsProvider::new(SEED);
+        for tick in 0..3 {
+            let live = provider.get_gpu_metrics().await.unwrap();
+            assert_eq!(live.utilization, provider.gpu_metrics_at(tick).utilization);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_reports_gpu_bottleneck() {
+        let hardware = SyntheticHardwareDetector::new(SEED).get_hardware_config().await.unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let mut run = create_run(SyntheticMetricsProvider::new(SEED).metric_stream(60, 1, end));
+        let profile = WorkloadProfile {
+            id: "gaming".to_string(),
+            name: "Synthetic gaming".to_string(),
+            workload_type: WorkloadType::Gaming,
+            parameters: HashMap::new(),
+            threshold_overrides: None,
+        };
+
+        let engine = AnalysisEngine::for_hardware(&hardware);
+        let result = analyze_run(&engine, &run, &profile, None).expect("analysis");
+        let types: Vec<&BottleneckType> = result.bottlenecks.iter().map(|b| &b.bottleneck_type).collect();
+        assert!(types.contains(&&BottleneckType::Gpu), "{:?}", types);
+        assert!(!types.contains(&&BottleneckType::Cpu) && !types.contains(&&BottleneckType::Ram));
+
+        let insights = generate_insights_for_hardware(&result, Some(&profile), &[], &hardware);
+        assert!(insights
+            .recommendations
+            .iter()
+            .any(|r| r.bottleneck_type == Some(BottleneckType::Gpu)));
+
+        run.analysis_result = Some(result);
+        let session = Session {
+            id: Uuid::new_v4(),
+            start_time: end,
+            end_time: None,
+            hardware_config_snapshot: hardware.clone(),
+            profile,
+            runs: vec![run],
+        };
+        let config = ReportConfig {
+            format: ReportFormat::Text,
+            ..ReportConfig::default()
+        };
+        let report = generate_session_report(&session, &hardware, &config);
+        assert!(report.contains(&format!("CPU: {}", hardware.cpu.model)), "{}", report);
+        assert!(report.contains(&format!("GPU: {}", hardware.gpus[0].model)));
+        assert!(report.contains("Run 1 Recommendations:"));
+        assert!(report.contains("For gaming: Consider upgrading to a more powerful GPU."));
+    }
+}
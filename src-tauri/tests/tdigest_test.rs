@@ -0,0 +1,107 @@
+//! Unit tests for streaming percentiles
+//!
+//! Tests t-digest accuracy and live collector percentiles following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType};
+    use stats_io_lib::core::settings::BufferOverflowPolicy;
+    use stats_io_lib::metrics::collector::{MetricsCollector, MetricsCollectorConfig};
+    use stats_io_lib::metrics::percentile;
+    use stats_io_lib::metrics::tdigest::TDigest;
+
+    /// Deterministic pseudo-random values in [0, 1)
+    fn uniform_stream(count: usize) -> Vec<f64> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 11) as f64 / (1u64 << 53) as f64
+            })
+            .collect()
+    }
+
+    /// Long-tailed stream resembling frame times: mostly ~16 ms with rare spikes
+    fn frame_time_stream(count: usize) -> Vec<f64> {
+        uniform_stream(count)
+            .into_iter()
+            .map(|u| 16.0 - 4.0 * (1.0 - u).ln())
+            .collect()
+    }
+
+    #[test]
+    fn test_p95_matches_exact_on_large_stream() {
+        let values = frame_time_stream(200_000);
+        let mut digest = TDigest::default();
+        for &value in &values {
+            digest.add(value);
+        }
+
+        assert_eq!(digest.count(), values.len() as u64);
+        for p in [50.0, 95.0, 99.0] {
+            let exact = percentile(&values, p).unwrap();
+            let estimate = digest.percentile(p).unwrap();
+            assert!(
+                (estimate - exact).abs() / exact < 0.01,
+                "p{} estimate {} vs exact {}",
+                p,
+                estimate,
+                exact
+            );
+        }
+    }
+
+    #[test]
+    fn test_extremes_and_small_inputs() {
+        let mut digest = TDigest::default();
+        assert_eq!(digest.percentile(95.0), None);
+
+        digest.add(42.0);
+        assert_eq!(digest.percentile(95.0), Some(42.0));
+
+        for value in uniform_stream(10_000) {
+            digest.add(value * 100.0);
+        }
+        assert_eq!(digest.percentile(0.0), digest.min());
+        assert_eq!(digest.percentile(100.0), digest.max());
+        assert_eq!(digest.percentile(101.0), None);
+    }
+
+    #[test]
+    fn test_non_finite_values_ignored() {
+        let mut digest = TDigest::default();
+        digest.add(f64::NAN);
+        digest.add(f64::INFINITY);
+        digest.add(10.0);
+
+        assert_eq!(digest.count(), 1);
+        assert_eq!(digest.percentile(50.0), Some(10.0));
+    }
+
+    #[tokio::test]
+    async fn test_live_percentiles_outlast_buffer() {
+        let collector = MetricsCollector::new(MetricsCollectorConfig {
+            buffer_size: 100,
+            overflow_policy: BufferOverflowPolicy::EvictOldest,
+            ..Default::default()
+        });
+        let samples: Vec<MetricSample> = (0..10_000)
+            .map(|i| MetricSample::new(MetricType::CpuUtilization, (i % 100) as f64, "CPU"))
+            .collect();
+        collector.record_samples(&samples).await;
+
+        // The buffer only holds the last 100 samples, the digest saw them all
+        assert_eq!(collector.get_buffer().await.len(), 100);
+        let p95 = collector.live_percentile(&MetricType::CpuUtilization, 95.0).await.unwrap();
+        assert!((p95 - 94.05).abs() < 1.0, "p95 {}", p95);
+
+        let stats = collector.live_percentiles().await;
+        let cpu = &stats["CpuUtilization"];
+        assert_eq!(cpu.count, 10_000);
+        assert_eq!(cpu.min, 0.0);
+        assert_eq!(cpu.max, 99.0);
+        assert!(collector.live_percentile(&MetricType::GpuUtilization, 95.0).await.is_none());
+    }
+}
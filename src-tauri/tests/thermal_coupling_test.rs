@@ -0,0 +1,144 @@
+//! Unit tests for thermal coupling detection
+//!
+//! Tests that throttling is only confirmed when clocks or FPS drop with rising
+//! temperature following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::advanced::HOT_WITHOUT_THROTTLING_SEVERITY;
+    use stats_io_lib::analysis::rules::detect_thermal_coupling;
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{BottleneckType, MetricSample, MetricType};
+    use chrono::{Duration, Utc};
+
+    /// Ticks of (temperature, performance) readings, one second apart
+    fn create_stream(
+        temp_type: MetricType,
+        perf_type: MetricType,
+        source: &str,
+        ticks: &[(f64, f64)],
+    ) -> Vec<MetricSample> {
+        let start = Utc::now() - Duration::seconds(ticks.len() as i64);
+        ticks
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (temp, perf))| {
+                let timestamp = start + Duration::seconds(i as i64);
+                [
+                    MetricSample::new(temp_type.clone(), *temp, source).with_timestamp(timestamp),
+                    MetricSample::new(perf_type.clone(), *perf, source).with_timestamp(timestamp),
+                ]
+            })
+            .collect()
+    }
+
+    /// Ten cool ticks followed by ten hot ones
+    fn warm_up(cool: (f64, f64), hot: (f64, f64)) -> Vec<(f64, f64)> {
+        let mut ticks = vec![cool; 10];
+        ticks.extend(vec![hot; 10]);
+        ticks
+    }
+
+    #[test]
+    fn test_cpu_clock_drop_confirms_throttling() {
+        let metrics = create_stream(
+            MetricType::Temperature,
+            MetricType::CpuClock,
+            "CPU",
+            &warm_up((70.0, 4500.0), (92.0, 3600.0)),
+        );
+
+        let bottleneck = detect_thermal_coupling(&metrics).expect("thermal bottleneck");
+        assert_eq!(bottleneck.bottleneck_type, BottleneckType::Thermal);
+        assert_eq!(bottleneck.severity, 90);
+        assert!(bottleneck.summary.contains("CPU thermal throttling"));
+        assert_eq!(bottleneck.evidence[0].metric_type, MetricType::Temperature);
+        assert_eq!(bottleneck.evidence[0].actual_value, 92.0);
+        assert_eq!(bottleneck.evidence[1].metric_type, MetricType::CpuClock);
+        assert_eq!(bottleneck.evidence[1].threshold, 4500.0);
+        assert_eq!(bottleneck.evidence[1].actual_value, 3600.0);
+    }
+
+    #[test]
+    fn test_steady_clocks_downgrade_to_warning() {
+        let metrics = create_stream(
+            MetricType::Temperature,
+            MetricType::CpuClock,
+            "CPU",
+            &warm_up((70.0, 4500.0), (92.0, 4480.0)),
+        );
+
+        let bottleneck = detect_thermal_coupling(&metrics).expect("temperature warning");
+        assert_eq!(bottleneck.severity, HOT_WITHOUT_THROTTLING_SEVERITY);
+        assert!(bottleneck.summary.contains("without throttling"));
+    }
+
+    #[test]
+    fn test_gpu_fps_drop_confirms_throttling() {
+        let mut metrics = create_stream(
+            MetricType::GpuTemperature,
+            MetricType::GpuClock,
+            "GPU 0",
+            &warm_up((72.0, 1900.0), (86.0, 1900.0)),
+        );
+        let fps = create_stream(
+            MetricType::GpuTemperature,
+            MetricType::Fps,
+            "GPU 0",
+            &warm_up((72.0, 120.0), (86.0, 96.0)),
+        );
+        metrics.extend(fps.into_iter().filter(|m| m.metric_type == MetricType::Fps));
+
+        let bottleneck = detect_thermal_coupling(&metrics).expect("thermal bottleneck");
+        assert_eq!(bottleneck.severity, 90);
+        assert!(bottleneck.summary.starts_with("GPU 0"));
+        assert!(bottleneck.summary.contains("frame rate"));
+        assert_eq!(bottleneck.evidence[0].metric_type, MetricType::GpuTemperature);
+        assert_eq!(bottleneck.evidence[1].metric_type, MetricType::Fps);
+    }
+
+    #[test]
+    fn test_no_verdict_without_both_sides() {
+        // Hot for the whole window: nothing to compare against
+        let always_hot = create_stream(MetricType::Temperature, MetricType::CpuClock, "CPU", &[(92.0, 3600.0); 20]);
+        assert!(detect_thermal_coupling(&always_hot).is_none());
+
+        // Never reaches the throttle point
+        let cool = create_stream(MetricType::Temperature, MetricType::CpuClock, "CPU", &[(70.0, 4500.0); 20]);
+        assert!(detect_thermal_coupling(&cool).is_none());
+
+        // No clock or FPS samples
+        let temps_only: Vec<MetricSample> = create_stream(
+            MetricType::Temperature,
+            MetricType::CpuClock,
+            "CPU",
+            &warm_up((70.0, 4500.0), (92.0, 3600.0)),
+        )
+        .into_iter()
+        .filter(|m| m.metric_type == MetricType::Temperature)
+        .collect();
+        assert!(detect_thermal_coupling(&temps_only).is_none());
+    }
+
+    #[test]
+    fn test_engine_reports_coupled_and_uncoupled_streams() {
+        let engine = AnalysisEngine::new();
+        let thermal_severity = |ticks: Vec<(f64, f64)>| {
+            let metrics = create_stream(MetricType::Temperature, MetricType::CpuClock, "CPU", &ticks);
+            engine
+                .analyze(&metrics, Some(60), None)
+                .bottlenecks
+                .iter()
+                .filter(|b| b.bottleneck_type == BottleneckType::Thermal)
+                .map(|b| b.severity)
+                .max()
+        };
+
+        assert_eq!(thermal_severity(warm_up((70.0, 4500.0), (92.0, 3600.0))), Some(90));
+        // Temperature alone would be reported as critical throttling
+        assert_eq!(
+            thermal_severity(warm_up((70.0, 4500.0), (92.0, 4500.0))),
+            Some(HOT_WITHOUT_THROTTLING_SEVERITY)
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Unit tests for thermal trends across sessions
+//!
+//! Tests trend slopes and history-aware cooling recommendations following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::insights::{generate_insights, generate_insights_with_trends};
+    use stats_io_lib::analysis::trend::{
+        analyze_session_trend, CoolingDiagnosis, TREND_MIN_SESSIONS,
+    };
+    use stats_io_lib::analysis::AnalysisEngine;
+    use stats_io_lib::core::domain::{
+        BottleneckType, CPUInfo, DetectionMetadata, HardwareConfig, MemoryInfo, MetricSample,
+        MetricType, Run, Session, WorkloadProfile, WorkloadType, GPU_HOTSPOT_SOURCE,
+    };
+    use chrono::{DateTime, Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    /// Steady CPU temperature readings ending at the current time
+    fn create_temperatures(value: f64) -> Vec<MetricSample> {
+        let now = Utc::now();
+        (0..30)
+            .map(|i| {
+                MetricSample::new(MetricType::Temperature, value, "CPU")
+                    .with_timestamp(now - Duration::seconds(30 - i))
+            })
+            .collect()
+    }
+
+    fn create_session(start_time: DateTime<Utc>, temperature: f64) -> Session {
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("temperature".to_string(), create_temperatures(temperature));
+        Session {
+            id: Uuid::new_v4(),
+            start_time,
+            end_time: None,
+            hardware_config_snapshot: HardwareConfig {
+                cpu: CPUInfo {
+                    model: "Test CPU".to_string(),
+                    vendor: "Test Vendor".to_string(),
+                    architecture: Some("x86_64".to_string()),
+                    cores: 8,
+                    threads: 16,
+                    base_clock_mhz: Some(3000.0),
+                    boost_clock_mhz: Some(4500.0),
+                    core_max_frequencies_mhz: vec![],
+                },
+                gpus: vec![],
+                memory: MemoryInfo {
+                    total_mb: 16384,
+                    channels: Some(2),
+                    speed_mhz: Some(3200),
+                    modules: vec![],
+                },
+                storage_devices: vec![],
+                motherboard: None,
+                psu: None,
+                cooling: None,
+                displays: vec![],
+                metadata: DetectionMetadata {
+                    detection_time: Utc::now(),
+                    platform: "Test".to_string(),
+                    warnings: vec![],
+                    schema_version: 1,
+                },
+            },
+            profile: WorkloadProfile {
+                id: "test-profile".to_string(),
+                name: "Test Profile".to_string(),
+                workload_type: WorkloadType::General,
+                parameters: HashMap::new(),
+                threshold_overrides: None,
+            },
+            runs: vec![Run {
+                id: Uuid::new_v4(),
+                name: "Run".to_string(),
+                metrics_streams,
+                analysis_result: None,
+                notes: None,
+                environment: None,
+            }],
+        }
+    }
+
+    /// One session per month, oldest first
+    fn create_history(temperatures: &[f64]) -> Vec<Session> {
+        let first = Utc::now() - Duration::days(30 * temperatures.len() as i64);
+        temperatures
+            .iter()
+            .enumerate()
+            .map(|(month, temperature)| create_session(first + Duration::days(30 * month as i64), *temperature))
+            .collect()
+    }
+
+    fn thermal_recommendations(history: &[Session], current: f64) -> Vec<String> {
+        let trend = analyze_session_trend(history, MetricType::Temperature).expect("trend");
+        let result = AnalysisEngine::new().analyze(&create_temperatures(current), Some(60), None);
+        assert!(result
+            .bottlenecks
+            .iter()
+            .any(|b| b.bottleneck_type == BottleneckType::Thermal));
+        generate_insights_with_trends(&result, None, &[trend])
+            .recommendations
+            .into_iter()
+            .map(|r| r.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_rising_temperatures_yield_degradation_wording() {
+        let history = create_history(&[70.0, 74.0, 78.0, 82.0, 87.0]);
+        let trend = analyze_session_trend(&history, MetricType::Temperature).unwrap();
+
+        assert!((trend.slope_per_month - 4.2).abs() < 0.1, "slope {}", trend.slope_per_month);
+        assert_eq!(trend.points.len(), 5);
+        assert_eq!(trend.cooling_diagnosis(), Some(CoolingDiagnosis::Degradation));
+
+        let recommendations = thermal_recommendations(&history, 87.0);
+        assert!(recommendations.iter().any(|r| r.contains("degradation")));
+        assert!(!recommendations.iter().any(|r| r.contains("since the first recorded session")));
+    }
+
+    #[test]
+    fn test_high_from_first_session_yields_undersized_wording() {
+        let history = create_history(&[88.0, 87.0, 88.0, 88.5, 87.5]);
+        let trend = analyze_session_trend(&history, MetricType::Temperature).unwrap();
+
+        assert!(trend.slope_per_month.abs() < 1.0);
+        assert_eq!(trend.cooling_diagnosis(), Some(CoolingDiagnosis::UndersizedCooling));
+
+        let recommendations = thermal_recommendations(&history, 88.0);
+        assert!(recommendations.iter().any(|r| r.contains("undersized")));
+        assert!(!recommendations.iter().any(|r| r.contains("risen steadily")));
+    }
+
+    #[test]
+    fn test_gpu_trend_follows_edge_not_hotspot() {
+        // Edge steady at 70 °C while the hotspot runs 15 °C hotter
+        let mut history = create_history(&[70.0, 70.0, 70.0, 70.0]);
+        for session in &mut history {
+            let run = &mut session.runs[0];
+            let temperatures = run.metrics_streams.remove("temperature").unwrap();
+            let gpu: Vec<MetricSample> = temperatures
+                .iter()
+                .flat_map(|s| {
+                    [
+                        MetricSample::new(MetricType::GpuTemperature, s.value, "GPU").with_timestamp(s.timestamp),
+                        MetricSample::new(MetricType::GpuHotspotTemperature, s.value + 15.0, GPU_HOTSPOT_SOURCE)
+                            .with_timestamp(s.timestamp),
+                    ]
+                })
+                .collect();
+            run.metrics_streams.insert("gpu".to_string(), gpu);
+        }
+
+        let trend = analyze_session_trend(&history, MetricType::GpuTemperature).unwrap();
+        assert!(trend.points.iter().all(|point| point.value == 70.0));
+    }
+
+    #[test]
+    fn test_sessions_are_ordered_by_start_time() {
+        let mut history = create_history(&[70.0, 75.0, 80.0, 85.0]);
+        history.reverse();
+        let trend = analyze_session_trend(&history, MetricType::Temperature).unwrap();
+
+        assert_eq!(trend.points[0].value, 70.0);
+        assert!(trend.slope_per_month > 0.0);
+        assert!((trend.span_days - 90.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_short_or_sparse_history_has_no_diagnosis() {
+        let history = create_history(&[70.0, 90.0]);
+        assert!(TREND_MIN_SESSIONS > history.len());
+        assert!(analyze_session_trend(&history, MetricType::Temperature).is_none());
+
+        // Three sessions within a single week
+        let now = Utc::now();
+        let recent: Vec<Session> = (0..3)
+            .map(|day| create_session(now - Duration::days(6 - day * 3), 80.0 + day as f64 * 5.0))
+            .collect();
+        let trend = analyze_session_trend(&recent, MetricType::Temperature).unwrap();
+        assert_eq!(trend.cooling_diagnosis(), None);
+
+        // Sessions without the metric are skipped
+        assert!(analyze_session_trend(&create_history(&[80.0; 5]), MetricType::GpuTemperature).is_none());
+    }
+
+    #[test]
+    fn test_without_trends_recommendations_stay_generic() {
+        let result = AnalysisEngine::new().analyze(&create_temperatures(88.0), Some(60), None);
+        let with_empty = generate_insights_with_trends(&result, None, &[]);
+        let generic = generate_insights(&result, None);
+
+        assert_eq!(with_empty.recommendations, generic.recommendations);
+        assert!(!generic.recommendations.iter().any(|r| r.text().contains("degradation") || r.text().contains("undersized")));
+    }
+}
@@ -0,0 +1,101 @@
+//! Unit tests for outlier-trimmed means
+//!
+//! Tests trimmed means in aggregation and detection following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::rules::{analyze_bottlenecks, window_statistic};
+    use stats_io_lib::core::domain::{
+        BottleneckType, MetricSample, MetricType, ThresholdOverrides, ThresholdStatistic, WorkloadProfile,
+        WorkloadType,
+    };
+    use stats_io_lib::metrics::{aggregate_metrics, aggregate_metrics_trimmed, mean, trimmed_mean};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    /// Nineteen 70°C readings and one 300°C sensor glitch
+    fn glitched_temperatures() -> Vec<f64> {
+        let mut values = vec![70.0; 20];
+        values[7] = 300.0;
+        values
+    }
+
+    #[test]
+    fn test_outlier_skews_mean_not_trimmed_mean() {
+        let values = glitched_temperatures();
+
+        assert!((mean(values.iter().copied()).unwrap() - 81.5).abs() < 1e-9);
+        assert_eq!(trimmed_mean(&values, 0.05), Some(70.0));
+    }
+
+    #[test]
+    fn test_zero_fraction_is_plain_mean() {
+        let values = vec![0.1, 0.2, 0.3, 97.0, f64::NAN];
+        assert_eq!(trimmed_mean(&values, 0.0), mean(values.iter().copied()));
+        assert_eq!(trimmed_mean(&[], 0.1), None);
+        // Trimming never removes every value
+        assert_eq!(trimmed_mean(&[1.0, 2.0, 9.0], 0.5), Some(2.0));
+    }
+
+    #[test]
+    fn test_aggregation_reports_trimmed_average() {
+        let now = Utc::now();
+        let samples: Vec<MetricSample> = glitched_temperatures()
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                MetricSample::new(MetricType::Temperature, value, "CPU").with_timestamp(now + Duration::seconds(i as i64))
+            })
+            .collect();
+
+        let trimmed = &aggregate_metrics_trimmed(&samples, 0.05)["Temperature"];
+        assert_eq!(trimmed.trimmed_avg, 70.0);
+        assert!((trimmed.avg - 81.5).abs() < 1e-9);
+
+        let plain = &aggregate_metrics(&samples)["Temperature"];
+        assert_eq!(plain.trimmed_avg, plain.avg);
+    }
+
+    #[test]
+    fn test_trimmed_mean_in_detection() {
+        // GPU pegged at 96% with two 0% blips from missed reads
+        let now = Utc::now();
+        let samples: Vec<MetricSample> = (0..20)
+            .map(|i| {
+                let value = if i == 4 || i == 12 { 0.0 } else { 96.0 };
+                MetricSample::new(MetricType::GpuUtilization, value, "GPU")
+                    .with_timestamp(now - Duration::seconds(20 - i))
+            })
+            .collect();
+        let values: Vec<f64> = samples.iter().map(|s| s.value).collect();
+        assert!(window_statistic(&values, ThresholdStatistic::Mean) < 90.0);
+        assert_eq!(window_statistic(&values, ThresholdStatistic::TrimmedMean(0.1)), 96.0);
+
+        let profile = |statistic| WorkloadProfile {
+            id: "gaming".to_string(),
+            name: "Gaming".to_string(),
+            workload_type: WorkloadType::Gaming,
+            parameters: HashMap::new(),
+            threshold_overrides: Some(ThresholdOverrides {
+                cpu_high: None,
+                gpu_high: None,
+                ram_high: None,
+                vram_high: None,
+                statistic,
+                combined_pressure: None,
+                sustained_seconds: None,
+            }),
+        };
+        let is_gpu_bound = |statistic| {
+            analyze_bottlenecks(&samples, 30, Some(&profile(statistic)))
+                .bottlenecks
+                .iter()
+                .any(|b| b.bottleneck_type == BottleneckType::Gpu)
+        };
+        assert!(!is_gpu_bound(ThresholdStatistic::Mean));
+        assert!(is_gpu_bound(ThresholdStatistic::TrimmedMean(0.1)));
+
+        let parsed: ThresholdStatistic = serde_json::from_str(r#"{"trimmed_mean": 0.1}"#).unwrap();
+        assert_eq!(parsed, ThresholdStatistic::TrimmedMean(0.1));
+    }
+}
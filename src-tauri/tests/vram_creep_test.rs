@@ -0,0 +1,136 @@
+//! Unit tests for VRAM creep detection
+//!
+//! Tests telling VRAM creeping toward capacity apart from steady high usage following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::analysis::leak::{detect_vram_creep, LEAK_MIN_SAMPLES};
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run};
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    const VRAM_TOTAL_MB: u64 = 8192;
+
+    /// One VRAM reading per second per source for `seconds`, valued by `value_at(source, second)`
+    fn create_run(sources: &[&str], seconds: i64, value_at: impl Fn(usize, i64) -> f64) -> Run {
+        let start = Utc::now() - Duration::seconds(seconds);
+        let samples: Vec<MetricSample> = sources
+            .iter()
+            .enumerate()
+            .flat_map(|(source_idx, source)| {
+                let value_at = &value_at;
+                (0..seconds).map(move |i| {
+                    MetricSample::new(MetricType::GpuVramUsage, value_at(source_idx, i), *source)
+                        .with_timestamp(start + Duration::seconds(i))
+                })
+            })
+            .collect();
+        let mut metrics_streams = HashMap::new();
+        metrics_streams.insert("gpu".to_string(), samples);
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams,
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    /// Small deterministic jitter so readings are not a perfect line
+    fn jitter(i: i64) -> f64 {
+        ((i * 7919) % 11) as f64 - 5.0
+    }
+
+    #[test]
+    fn test_creeping_vram_is_flagged() {
+        // 5 GB creeping by 3 MB per second for twenty minutes, ending near 8.5 GB
+        let run = create_run(&["GPU"], 1200, |_, i| 5000.0 + 3.0 * i as f64 + jitter(i));
+
+        let trend = detect_vram_creep(&run, VRAM_TOTAL_MB).expect("VRAM creep");
+
+        assert_eq!(trend.source_component, "GPU");
+        assert_eq!(trend.total_mb, VRAM_TOTAL_MB);
+        assert!((trend.slope_mb_per_minute - 180.0).abs() < 5.0, "{}", trend.slope_mb_per_minute);
+        assert!(trend.end_mb > 8000.0);
+        assert!(trend.r_squared > 0.99);
+        assert!(trend.minutes_to_full.is_none());
+        assert!(trend.summary.contains("stutter"));
+        assert_eq!(trend.recommendation.id, "vram-creep");
+        assert!(trend.recommendation.text.contains("driver"));
+    }
+
+    #[test]
+    fn test_creep_reports_time_to_full() {
+        // Ends around 7 GB, growing 60 MB per minute
+        let run = create_run(&["GPU"], 1200, |_, i| 5800.0 + i as f64 + jitter(i));
+
+        let trend = detect_vram_creep(&run, VRAM_TOTAL_MB).expect("VRAM creep");
+
+        let minutes = trend.minutes_to_full.expect("time to full");
+        assert!((minutes - (VRAM_TOTAL_MB as f64 - trend.end_mb) / trend.slope_mb_per_minute).abs() < 1e-9);
+        assert!(minutes > 10.0 && minutes < 30.0, "{}", minutes);
+    }
+
+    #[test]
+    fn test_steady_high_vram_is_not_creep() {
+        // Nearly full but flat: a capacity problem, not creep
+        let run = create_run(&["GPU"], 1200, |_, i| 7800.0 + jitter(i));
+
+        assert!(detect_vram_creep(&run, VRAM_TOTAL_MB).is_none());
+    }
+
+    #[test]
+    fn test_growth_far_from_capacity_is_not_creep() {
+        // Rises steadily but ends well short of the card's total
+        let run = create_run(&["GPU"], 1200, |_, i| 1000.0 + i as f64 + jitter(i));
+
+        assert!(detect_vram_creep(&run, VRAM_TOTAL_MB).is_none());
+    }
+
+    #[test]
+    fn test_released_vram_is_not_creep() {
+        // Fills and is evicted every five minutes, e.g. on level loads
+        let run = create_run(&["GPU"], 1200, |_, i| 5000.0 + 10.0 * (i % 300) as f64);
+
+        assert!(detect_vram_creep(&run, VRAM_TOTAL_MB).is_none());
+    }
+
+    #[test]
+    fn test_creeping_gpu_is_picked_among_several() {
+        let run = create_run(&["GPU 0", "GPU 1"], 1200, |source, i| {
+            if source == 0 { 3000.0 + jitter(i) } else { 5000.0 + 3.0 * i as f64 + jitter(i) }
+        });
+
+        let trend = detect_vram_creep(&run, VRAM_TOTAL_MB).expect("VRAM creep");
+        assert_eq!(trend.source_component, "GPU 1");
+    }
+
+    #[test]
+    fn test_each_gpu_is_judged_against_its_recorded_total() {
+        // A 6 GB card creeping to ~5.6 GB while the fallback total is a larger card's
+        let mut run = create_run(&["GPU"], 1200, |_, i| 4000.0 + 1.35 * i as f64 + jitter(i));
+        let percent: Vec<MetricSample> = run.metrics_streams["gpu"]
+            .iter()
+            .map(|s| {
+                MetricSample::new(MetricType::GpuVramUsagePercent, s.value / 6144.0 * 100.0, "GPU")
+                    .with_timestamp(s.timestamp)
+            })
+            .collect();
+        run.metrics_streams.get_mut("gpu").unwrap().extend(percent);
+
+        let trend = detect_vram_creep(&run, 16384).expect("VRAM creep");
+        assert_eq!(trend.total_mb, 6144);
+        assert!(detect_vram_creep(&run, 0).is_some());
+    }
+
+    #[test]
+    fn test_unknown_total_or_short_runs_are_not_judged() {
+        let run = create_run(&["GPU"], 1200, |_, i| 5000.0 + 3.0 * i as f64);
+        assert!(detect_vram_creep(&run, 0).is_none());
+
+        let run = create_run(&["GPU"], LEAK_MIN_SAMPLES as i64 - 1, |_, i| 7000.0 + 50.0 * i as f64);
+        assert!(detect_vram_creep(&run, VRAM_TOTAL_MB).is_none());
+    }
+}
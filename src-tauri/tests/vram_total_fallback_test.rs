@@ -0,0 +1,183 @@
+//! Unit tests for the VRAM total fallback
+//!
+//! Tests that detected VRAM totals complete used-only readings following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use stats_io_lib::core::domain::{
+        CPUInfo, DetectionMetadata, GPUInfo, HardwareConfig, MemoryInfo, MetricType,
+    };
+    use stats_io_lib::core::error::MetricsError;
+    use stats_io_lib::core::interfaces::{
+        CpuMetricsProvider, GpuMetricsProvider, MemoryMetricsProvider, SensorProvider,
+        StorageMetricsProvider,
+    };
+    use stats_io_lib::metrics::collector::fill_vram_total;
+    use stats_io_lib::metrics::models::{CpuMetrics, GpuMetrics, MemoryMetrics, SensorReading, StorageMetrics};
+    use stats_io_lib::metrics::{MetricsCollector, MetricsCollectorConfig};
+    use chrono::Utc;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn used_only(vram_used_mb: u64) -> GpuMetrics {
+        GpuMetrics {
+            utilization: 0.6,
+            vram_used_mb: Some(vram_used_mb),
+            vram_total_mb: None,
+            temperature: None,
+            hotspot_temperature: None,
+            clock_core_mhz: None,
+            clock_memory_mhz: None,
+            power_watts: None,
+            fan_speed_percent: None,
+            pcie_link: None,
+            memory_controller_load: None,
+            adapter_name: None,
+        }
+    }
+
+    /// GPU provider reporting used VRAM without a total, as the WMI fallback does
+    struct UsedOnlyGpuProvider;
+
+    #[async_trait]
+    impl GpuMetricsProvider for UsedOnlyGpuProvider {
+        async fn get_gpu_metrics(&self) -> Result<GpuMetrics, MetricsError> {
+            Ok(used_only(6144))
+        }
+    }
+
+    #[async_trait]
+    impl CpuMetricsProvider for UsedOnlyGpuProvider {
+        async fn get_cpu_metrics(&self) -> Result<CpuMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("CPU".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl MemoryMetricsProvider for UsedOnlyGpuProvider {
+        async fn get_memory_metrics(&self) -> Result<MemoryMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("Memory".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl StorageMetricsProvider for UsedOnlyGpuProvider {
+        async fn get_storage_metrics(&self) -> Result<StorageMetrics, MetricsError> {
+            Err(MetricsError::ProviderNotAvailable("Storage".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl SensorProvider for UsedOnlyGpuProvider {
+        async fn get_sensors(&self) -> Result<Vec<SensorReading>, MetricsError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn hardware_with_vram(vram_total_mb: Option<u64>) -> HardwareConfig {
+        HardwareConfig {
+            cpu: CPUInfo {
+                model: "Test CPU".to_string(),
+                vendor: "Test Vendor".to_string(),
+                cores: 8,
+                threads: 16,
+                base_clock_mhz: None,
+                boost_clock_mhz: None,
+                architecture: None,
+                core_max_frequencies_mhz: vec![],
+            },
+            gpus: vec![GPUInfo {
+                model: "Radeon Test".to_string(),
+                vendor: "AMD".to_string(),
+                vram_total_mb,
+                driver_version: None,
+                pci_id: None,
+            }],
+            memory: MemoryInfo {
+                total_mb: 16384,
+                channels: None,
+                speed_mhz: None,
+                modules: vec![],
+            },
+            storage_devices: vec![],
+            motherboard: None,
+            psu: None,
+            cooling: None,
+            displays: vec![],
+            metadata: DetectionMetadata {
+                detection_time: Utc::now(),
+                platform: "Test".to_string(),
+                warnings: vec![],
+                schema_version: 1,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_percent_stream_produced_from_detected_total() {
+        let provider = Arc::new(UsedOnlyGpuProvider);
+        let config = MetricsCollectorConfig {
+            sampling_interval_ms: 10,
+            ..MetricsCollectorConfig::default()
+        };
+        let collector = MetricsCollector::with_providers(
+            config,
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider.clone(),
+            provider,
+        );
+        collector.set_hardware_config(&hardware_with_vram(Some(8192))).await;
+
+        collector.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        collector.stop().await;
+
+        let buffer = collector.get_buffer().await;
+        let percents: Vec<f64> = buffer
+            .iter()
+            .filter(|s| s.metric_type == MetricType::GpuVramUsagePercent)
+            .map(|s| s.value)
+            .collect();
+        assert!(!percents.is_empty());
+        assert!(percents.iter().all(|&p| p == 75.0), "{:?}", percents);
+        assert!(buffer.iter().any(|s| s.metric_type == MetricType::GpuVramUsage && s.value == 6144.0));
+    }
+
+    #[test]
+    fn test_reported_total_is_kept_and_unknown_device_ignored() {
+        let models = vec!["Radeon Test".to_string()];
+        let mut reported = GpuMetrics { vram_total_mb: Some(12_288), ..used_only(6144) };
+        fill_vram_total(&mut reported, &[Some(8192)], &models);
+        assert_eq!(reported.vram_total_mb, Some(12_288));
+
+        let mut zero_total = GpuMetrics { vram_total_mb: Some(0), ..used_only(6144) };
+        fill_vram_total(&mut zero_total, &[Some(8192)], &models);
+        assert_eq!(zero_total.vram_total_mb, Some(8192));
+
+        let mut unknown_adapter = GpuMetrics { adapter_name: Some("Arc A770".to_string()), ..used_only(6144) };
+        fill_vram_total(&mut unknown_adapter, &[Some(8192)], &models);
+        assert_eq!(unknown_adapter.vram_total_mb, None);
+
+        let mut undetected_total = used_only(6144);
+        fill_vram_total(&mut undetected_total, &[None], &models);
+        assert_eq!(undetected_total.vram_total_mb, None);
+    }
+
+    #[test]
+    fn test_total_matched_by_adapter_on_several_gpus() {
+        let models = vec!["Intel(R) UHD Graphics 630".to_string(), "AMD Radeon RX 6600M".to_string()];
+        let totals = [Some(1024), Some(8192)];
+
+        let mut discrete = GpuMetrics { adapter_name: Some("AMD Radeon RX 6600M".to_string()), ..used_only(6144) };
+        fill_vram_total(&mut discrete, &totals, &models);
+        assert_eq!(discrete.vram_total_mb, Some(8192));
+
+        // Without an adapter name the readings cannot be attributed
+        let mut unnamed = used_only(6144);
+        fill_vram_total(&mut unnamed, &totals, &models);
+        assert_eq!(unnamed.vram_total_mb, None);
+    }
+}
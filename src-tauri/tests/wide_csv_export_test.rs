@@ -0,0 +1,98 @@
+//! Unit tests for the wide CSV run export
+//!
+//! Tests resampling of streams onto a common grid following TESTING_PLAN.md.
+
+#[cfg(test)]
+mod tests {
+    use stats_io_lib::core::domain::{MetricSample, MetricType, Run};
+    use stats_io_lib::persistence::reports::export_run_wide_csv;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    fn create_stream(metric_type: MetricType, source: &str, points: &[(i64, f64)]) -> Vec<MetricSample> {
+        points
+            .iter()
+            .map(|(offset_ms, value)| MetricSample {
+                timestamp: start() + Duration::milliseconds(*offset_ms),
+                metric_type: metric_type.clone(),
+                value: *value,
+                unit: String::new(),
+                source_component: source.to_string(),
+            })
+            .collect()
+    }
+
+    fn create_run(streams: Vec<(&str, Vec<MetricSample>)>) -> Run {
+        Run {
+            id: Uuid::new_v4(),
+            name: "Run".to_string(),
+            metrics_streams: streams.into_iter().map(|(key, samples)| (key.to_string(), samples)).collect::<HashMap<_, _>>(),
+            analysis_result: None,
+            notes: None,
+            environment: None,
+        }
+    }
+
+    #[test]
+    fn test_aligns_streams_on_different_cadences() {
+        // CPU every 500 ms, GPU every second starting 250 ms later
+        let run = create_run(vec![
+            ("cpu", create_stream(MetricType::CpuUtilization, "CPU", &[(0, 10.0), (500, 20.0), (1000, 30.0), (1500, 40.0), (2000, 50.0)])),
+            ("gpu", create_stream(MetricType::GpuUtilization, "GPU", &[(250, 80.0), (1250, 90.0)])),
+        ]);
+
+        let csv = export_run_wide_csv(&run, 500).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "timestamp,elapsed_ms,CpuUtilization,GpuUtilization");
+        assert_eq!(&lines[1..], &[
+            "2026-01-01T12:00:00.000Z,0,10.00,",
+            "2026-01-01T12:00:00.500Z,500,20.00,80.00",
+            "2026-01-01T12:00:01.000Z,1000,30.00,80.00",
+            "2026-01-01T12:00:01.500Z,1500,40.00,90.00",
+            "2026-01-01T12:00:02.000Z,2000,50.00,90.00",
+        ]);
+    }
+
+    #[test]
+    fn test_coarser_interval_takes_latest_sample() {
+        let run = create_run(vec![
+            ("cpu", create_stream(MetricType::CpuUtilization, "CPU", &[(0, 10.0), (500, 20.0), (1000, 30.0), (1500, 40.0), (2000, 50.0)])),
+        ]);
+
+        let csv = export_run_wide_csv(&run, 1000).unwrap();
+        let values: Vec<&str> = csv.lines().skip(1).map(|line| line.rsplit(',').next().unwrap()).collect();
+
+        assert_eq!(values, vec!["10.00", "30.00", "50.00"]);
+    }
+
+    #[test]
+    fn test_shared_metric_type_splits_by_source() {
+        let run = create_run(vec![
+            ("core0", create_stream(MetricType::CpuUtilization, "Core 0", &[(0, 10.0)])),
+            ("core1", create_stream(MetricType::CpuUtilization, "Core 1", &[(0, 20.0)])),
+        ]);
+
+        let csv = export_run_wide_csv(&run, 1000).unwrap();
+
+        assert_eq!(csv.lines().next().unwrap(), "timestamp,elapsed_ms,CpuUtilization (Core 0),CpuUtilization (Core 1)");
+        assert_eq!(csv.lines().nth(1).unwrap(), "2026-01-01T12:00:00.000Z,0,10.00,20.00");
+    }
+
+    #[test]
+    fn test_empty_run_and_invalid_interval() {
+        let empty = create_run(vec![]);
+        assert_eq!(export_run_wide_csv(&empty, 1000).unwrap(), "timestamp,elapsed_ms\n");
+        assert!(export_run_wide_csv(&empty, 0).is_err());
+
+        let long = create_run(vec![
+            ("cpu", create_stream(MetricType::CpuUtilization, "CPU", &[(0, 10.0), (3_600_000, 20.0)])),
+        ]);
+        assert!(export_run_wide_csv(&long, 1).is_err());
+    }
+}
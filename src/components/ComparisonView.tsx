@@ -23,6 +23,7 @@ import type {
   MetricDelta,
   BottleneckStatus,
 } from "../types/index";
+import { getErrorMessage } from "../utils/errors";
 
 export function ComparisonView() {
   const { sessions, loadSessions } = useSessions();
@@ -82,11 +83,12 @@ export function ComparisonView() {
       const result = await invoke<ComparisonResult>("compare_runs_command", {
         run1: run1Data.run,
         run2: run2Data.run,
+        run1SessionId: run1Data.sessionId,
       });
 
       setComparisonResult(result);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to compare runs");
+      setError(getErrorMessage(err, "Failed to compare runs"));
     } finally {
       setLoading(false);
     }
@@ -165,6 +167,30 @@ export function ComparisonView() {
             <p>{comparisonResult.summary}</p>
           </section>
 
+          {comparisonResult.environment_warnings?.length > 0 && (
+            <section className="environment-warnings">
+              <h2>Environment Differences</h2>
+              <p>These runs were captured under different conditions, so deltas may not reflect hardware changes.</p>
+              <ul>
+                {comparisonResult.environment_warnings.map((warning) => (
+                  <li key={warning}>{warning}</li>
+                ))}
+              </ul>
+            </section>
+          )}
+
+          {comparisonResult.hardware_diff && (
+            <section className="hardware-differences">
+              <h2>Hardware Differences</h2>
+              <p>{comparisonResult.hardware_diff.caption}</p>
+              <ul>
+                {comparisonResult.hardware_diff.differences.map((difference) => (
+                  <li key={difference.component}>{difference.description}</li>
+                ))}
+              </ul>
+            </section>
+          )}
+
           {/* Metric Deltas */}
           {metricChartData.length > 0 && (
             <section className="metric-comparison">
@@ -211,6 +237,11 @@ export function ComparisonView() {
                         <td className={delta.delta_percent >= 0 ? "delta-positive" : "delta-negative"}>
                           {delta.delta_percent >= 0 ? "+" : ""}
                           {delta.delta_percent.toFixed(1)}%
+                          {delta.significant === false && (
+                            <span className="delta-noise" title="Within run-to-run noise">
+                              {" "}(noise)
+                            </span>
+                          )}
                         </td>
                       </tr>
                     ))}
@@ -11,11 +11,17 @@ import { useKeyboardNavigation } from "../hooks/useKeyboardNavigation";
 import { useEffect, useMemo, useState } from "react";
 import { LineChart, Line, XAxis, YAxis, CartesianGrid, Tooltip, Legend, ResponsiveContainer } from "recharts";
 import { CpuCoreHeatmap, MemoryStackedChart, CorrelationScatterPlot, MultiMetricChart } from "./AdvancedCharts";
-import { exportReportInFormat } from "../utils/reports";
+import { exportAnalysisJson, exportReportInFormat } from "../utils/reports";
 
 export function Dashboard() {
-  const { hardware, loading: hardwareLoading, error: hardwareError } = useHardware();
-  const { metrics, isCollecting, startCollection, stopCollection } = useMetrics();
+  const {
+    hardware,
+    loading: hardwareLoading,
+    error: hardwareError,
+    hardwareChanges,
+    dismissHardwareChanges,
+  } = useHardware();
+  const { metrics, health, isCollecting, startCollection, stopCollection } = useMetrics();
   const { analysisResult, insights, analyze } = useAnalysis();
   const { currentSession, createSession, endSession, addRun } = useSessions();
   const { profiles, selectedProfile, setSelectedProfile } = useProfiles();
@@ -78,6 +84,14 @@ export function Dashboard() {
     return Array.from(merged.values());
   }, [metrics]);
 
+  // Easy wins first, then the most urgent
+  const sortedRecommendations = useMemo(() => {
+    const effort = { easy: 0, moderate: 1, advanced: 2 };
+    return [...(insights?.recommendations ?? [])].sort(
+      (a, b) => effort[a.difficulty] - effort[b.difficulty] || b.priority - a.priority
+    );
+  }, [insights]);
+
   // Get current metric values for gauges
   const currentMetrics = useMemo(() => {
     if (metrics.length === 0) {
@@ -142,6 +156,53 @@ export function Dashboard() {
     <div className="dashboard" ref={containerRef}>
       <h1>PC Rig Hardware & Bottleneck Analyzer</h1>
 
+      {hardwareChanges.length > 0 && (
+        <div className="hardware-change-notice" role="status">
+          <h2>Hardware changed since last launch</h2>
+          <ul>
+            {hardwareChanges.map((change, idx) => (
+              <li key={idx}>{change.description}</li>
+            ))}
+          </ul>
+          <p>
+            Comparisons with earlier runs may no longer be meaningful. Consider
+            re-capturing the idle baseline in Settings.
+          </p>
+          <button onClick={dismissHardwareChanges}>Dismiss</button>
+        </div>
+      )}
+
+      {health && health.evicted_samples > 0 && (
+        <div className="hardware-change-notice" role="status">
+          <p>
+            The metrics buffer is full ({health.buffer_size} samples), so the
+            oldest {health.evicted_samples} samples have been dropped. Save the
+            run or switch the buffer policy in Settings to keep the whole
+            capture.
+          </p>
+        </div>
+      )}
+
+      {health && health.rejected_samples > 0 && (
+        <div className="hardware-change-notice" role="status">
+          <p>
+            {health.rejected_samples} implausible sensor readings (such as
+            negative temperatures) were discarded. A sensor or its driver may
+            be faulty.
+          </p>
+        </div>
+      )}
+
+      {health && health.subprocess_timeouts > 0 && (
+        <div className="hardware-change-notice" role="status">
+          <p>
+            A monitoring tool such as nvidia-smi stopped responding{" "}
+            {health.subprocess_timeouts} times and was stopped. GPU readings
+            may be missing for those moments.
+          </p>
+        </div>
+      )}
+
       {/* Hardware Summary */}
       <section className="hardware-summary collapsible-section">
         <div className="section-header">
@@ -358,6 +419,9 @@ export function Dashboard() {
               >
                 Export JSON Report
               </button>
+              <button onClick={() => exportAnalysisJson(currentSession)}>
+                Export Raw Analysis
+              </button>
             </>
           )}
             </div>
@@ -490,13 +554,29 @@ export function Dashboard() {
               <div className="recommendations">
                 <h3>Recommendations</h3>
                 <ul>
-                  {insights.recommendations.map((rec, idx) => (
-                    <li key={idx}>{rec}</li>
+                  {sortedRecommendations.map((rec) => (
+                    <li key={rec.id}>
+                      <span className={`recommendation-tag difficulty-${rec.difficulty}`}>
+                        {rec.difficulty}
+                      </span>
+                      <span className="recommendation-tag">{rec.category}</span>
+                      {rec.text}
+                    </li>
                   ))}
                 </ul>
               </div>
             )}
           </div>
+          {analysisResult?.coverage?.warning && (
+            <p className="analysis-coverage-warning">
+              {analysisResult.coverage.warning}
+            </p>
+          )}
+          {analysisResult?.coverage?.data_warnings?.map((warning, idx) => (
+            <p key={idx} className="analysis-coverage-warning">
+              {warning}
+            </p>
+          ))}
           {analysisResult && analysisResult.bottlenecks.length > 0 && (
             <div className="bottlenecks">
               <h3>Detected Bottlenecks</h3>
@@ -507,6 +587,16 @@ export function Dashboard() {
                     {bottleneck.severity})
                   </h4>
                   <p>{bottleneck.summary}</p>
+                  {bottleneck.onset && (
+                    <p className="bottleneck-onset">
+                      Since {new Date(bottleneck.onset).toLocaleTimeString()}
+                    </p>
+                  )}
+                  {!!bottleneck.active_fraction && (
+                    <p className="bottleneck-active-time">
+                      Active {Math.round(bottleneck.active_fraction * 100)}% of the time
+                    </p>
+                  )}
                   <details>
                     <summary>Details</summary>
                     <p>{bottleneck.details}</p>
@@ -6,7 +6,39 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { useKeyboardNavigation } from "../hooks/useKeyboardNavigation";
-import type { UserSettings } from "../types/index";
+import type {
+  BufferOverflowPolicy,
+  GpuUtilizationSource,
+  MissedTickBehavior,
+  CleanupReport,
+  NoiseFloors,
+  RetentionOverview,
+  UserSettings,
+} from "../types/index";
+import { getErrorMessage } from "../utils/errors";
+
+/// Collector defaults, used when saved settings predate noise floors
+const DEFAULT_NOISE_FLOORS: NoiseFloors = {
+  gpu_utilization: 1,
+  storage_read_throughput: 0.1,
+  storage_write_throughput: 0.1,
+};
+
+/// IANA zones offered for report timestamps, besides local time and UTC
+const COMMON_TIMEZONES = [
+  "America/Los_Angeles",
+  "America/Denver",
+  "America/Chicago",
+  "America/New_York",
+  "America/Sao_Paulo",
+  "Europe/London",
+  "Europe/Berlin",
+  "Europe/Moscow",
+  "Asia/Kolkata",
+  "Asia/Shanghai",
+  "Asia/Tokyo",
+  "Australia/Sydney",
+];
 
 export function SettingsView() {
   const [settings, setSettings] = useState<UserSettings | null>(null);
@@ -14,12 +46,41 @@ export function SettingsView() {
   const [saving, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [success, setSuccess] = useState<string | null>(null);
+  const [retention, setRetention] = useState<RetentionOverview | null>(null);
+  const [cleaningUp, setCleaningUp] = useState(false);
   const containerRef = useKeyboardNavigation();
 
   useEffect(() => {
     loadSettings();
+    loadRetentionStats();
   }, []);
 
+  const loadRetentionStats = async () => {
+    try {
+      setRetention(await invoke<RetentionOverview>("get_retention_stats_command"));
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to load retention statistics"));
+    }
+  };
+
+  const cleanupNow = async () => {
+    if (!confirm("Delete sessions older than the retention period now?")) {
+      return;
+    }
+    try {
+      setCleaningUp(true);
+      setError(null);
+      const report = await invoke<CleanupReport>("cleanup_now_command");
+      setRetention(report.stats);
+      setSuccess(`Deleted ${report.deleted} session${report.deleted === 1 ? "" : "s"}`);
+      setTimeout(() => setSuccess(null), 3000);
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to clean up sessions"));
+    } finally {
+      setCleaningUp(false);
+    }
+  };
+
   const loadSettings = async () => {
     try {
       setLoading(true);
@@ -27,7 +88,7 @@ export function SettingsView() {
       const loadedSettings = await invoke<UserSettings>("get_settings");
       setSettings(loadedSettings);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to load settings");
+      setError(getErrorMessage(err, "Failed to load settings"));
     } finally {
       setLoading(false);
     }
@@ -40,10 +101,11 @@ export function SettingsView() {
       setSuccess(null);
       await invoke("update_settings", { settings: updatedSettings });
       setSettings(updatedSettings);
+      await loadRetentionStats();
       setSuccess("Settings saved successfully!");
       setTimeout(() => setSuccess(null), 3000);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to save settings");
+      setError(getErrorMessage(err, "Failed to save settings"));
     } finally {
       setSaving(false);
     }
@@ -61,7 +123,7 @@ export function SettingsView() {
       setSuccess("Settings reset to defaults!");
       setTimeout(() => setSuccess(null), 3000);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to reset settings");
+      setError(getErrorMessage(err, "Failed to reset settings"));
     } finally {
       setSaving(false);
     }
@@ -229,6 +291,52 @@ export function SettingsView() {
               }}
             />
           </div>
+
+          <div className="setting-item">
+            <label htmlFor="min-comparison-effect">Minimum Comparison Change (%)</label>
+            <input
+              id="min-comparison-effect"
+              type="number"
+              min="0"
+              max="100"
+              step="0.5"
+              value={settings.thresholds.min_comparison_effect_percent ?? 5}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  thresholds: {
+                    ...settings.thresholds,
+                    min_comparison_effect_percent: parseFloat(e.target.value) || 0,
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            />
+            <small>Smaller changes, or changes within run-to-run noise, are not reported</small>
+          </div>
+
+          <div className="setting-item">
+            <label htmlFor="trim-fraction">Trimmed Mean Cutoff (%)</label>
+            <input
+              id="trim-fraction"
+              type="number"
+              min="0"
+              max="50"
+              step="1"
+              value={(settings.thresholds.trim_fraction ?? 0) * 100}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  thresholds: {
+                    ...settings.thresholds,
+                    trim_fraction: (parseFloat(e.target.value) || 0) / 100,
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            />
+            <small>Share of the lowest and highest samples ignored by trimmed averages; 0 disables trimming</small>
+          </div>
         </div>
       </section>
 
@@ -284,6 +392,175 @@ export function SettingsView() {
             />
             <small>Number of samples to keep in memory</small>
           </div>
+
+          <div className="setting-item">
+            <label htmlFor="event-flush-interval">Live Update Interval (ms)</label>
+            <input
+              id="event-flush-interval"
+              type="number"
+              min="50"
+              max="5000"
+              step="50"
+              value={settings.sampling.event_flush_interval_ms ?? 250}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  sampling: {
+                    ...settings.sampling,
+                    event_flush_interval_ms: parseInt(e.target.value) || 250,
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            />
+            <small>How often batched samples are sent to live charts</small>
+          </div>
+
+          <div className="setting-item">
+            <label htmlFor="gpu-noise-floor">GPU Utilization Noise Floor (%)</label>
+            <input
+              id="gpu-noise-floor"
+              type="number"
+              min="0"
+              max="10"
+              step="0.1"
+              value={settings.sampling.noise_floors?.gpu_utilization ?? DEFAULT_NOISE_FLOORS.gpu_utilization}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  sampling: {
+                    ...settings.sampling,
+                    noise_floors: {
+                      ...DEFAULT_NOISE_FLOORS,
+                      ...settings.sampling.noise_floors,
+                      gpu_utilization: parseFloat(e.target.value) || 0,
+                    },
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            />
+            <small>Readings below this are dropped as idle noise</small>
+          </div>
+
+          <div className="setting-item">
+            <label htmlFor="storage-read-noise-floor">Storage Read Noise Floor (MB/s)</label>
+            <input
+              id="storage-read-noise-floor"
+              type="number"
+              min="0"
+              max="10"
+              step="0.1"
+              value={settings.sampling.noise_floors?.storage_read_throughput ?? DEFAULT_NOISE_FLOORS.storage_read_throughput}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  sampling: {
+                    ...settings.sampling,
+                    noise_floors: {
+                      ...DEFAULT_NOISE_FLOORS,
+                      ...settings.sampling.noise_floors,
+                      storage_read_throughput: parseFloat(e.target.value) || 0,
+                    },
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            />
+          </div>
+
+          <div className="setting-item">
+            <label htmlFor="storage-write-noise-floor">Storage Write Noise Floor (MB/s)</label>
+            <input
+              id="storage-write-noise-floor"
+              type="number"
+              min="0"
+              max="10"
+              step="0.1"
+              value={settings.sampling.noise_floors?.storage_write_throughput ?? DEFAULT_NOISE_FLOORS.storage_write_throughput}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  sampling: {
+                    ...settings.sampling,
+                    noise_floors: {
+                      ...DEFAULT_NOISE_FLOORS,
+                      ...settings.sampling.noise_floors,
+                      storage_write_throughput: parseFloat(e.target.value) || 0,
+                    },
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            />
+          </div>
+
+          <div className="setting-item">
+            <label htmlFor="overflow-policy">When Buffer Is Full</label>
+            <select
+              id="overflow-policy"
+              value={settings.sampling.overflow_policy ?? "evict_oldest"}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  sampling: {
+                    ...settings.sampling,
+                    overflow_policy: e.target.value as BufferOverflowPolicy,
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            >
+              <option value="evict_oldest">Drop oldest samples</option>
+              <option value="unbounded_until_saved">Keep everything until the run is saved</option>
+            </select>
+            <small>Dropping samples loses the start of long captures</small>
+          </div>
+
+          <div className="setting-item">
+            <label htmlFor="missed-tick-behavior">After a Sampling Stall</label>
+            <select
+              id="missed-tick-behavior"
+              value={settings.sampling.missed_tick_behavior ?? "delay"}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  sampling: {
+                    ...settings.sampling,
+                    missed_tick_behavior: e.target.value as MissedTickBehavior,
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            >
+              <option value="delay">Delay: resume at the normal interval</option>
+              <option value="skip">Skip: drop missed samples, keep the schedule</option>
+              <option value="burst">Burst: catch up on missed samples at once</option>
+            </select>
+            <small>Burst keeps the sample count but distorts throughput rates</small>
+          </div>
+
+          <div className="setting-item">
+            <label htmlFor="gpu-utilization-source">GPU Utilization Source</label>
+            <select
+              id="gpu-utilization-source"
+              value={settings.sampling.gpu_utilization_source ?? "driver"}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  sampling: {
+                    ...settings.sampling,
+                    gpu_utilization_source: e.target.value as GpuUtilizationSource,
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            >
+              <option value="driver">Driver (nvidia-smi)</option>
+              <option value="engine3d">3D engine counters (Windows, matches Task Manager)</option>
+            </select>
+            <small>Engine counters also cover AMD and Intel GPUs on Windows</small>
+          </div>
         </div>
       </section>
 
@@ -332,6 +609,32 @@ export function SettingsView() {
               <option value="gb">Gigabytes (GB)</option>
             </select>
           </div>
+
+          <div className="setting-item">
+            <label htmlFor="display-timezone">Report Timezone</label>
+            <select
+              id="display-timezone"
+              value={settings.units.display_timezone ?? "local"}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  units: {
+                    ...settings.units,
+                    display_timezone: e.target.value,
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            >
+              <option value="local">System local time</option>
+              <option value="UTC">UTC</option>
+              {COMMON_TIMEZONES.map((zone) => (
+                <option key={zone} value={zone}>
+                  {zone}
+                </option>
+              ))}
+            </select>
+          </div>
         </div>
       </section>
 
@@ -451,6 +754,43 @@ export function SettingsView() {
             />
             <small>Set to 0 or leave empty for unlimited retention</small>
           </div>
+
+          <div className="setting-item">
+            <label htmlFor="min-sessions-to-keep">Minimum Sessions to Keep</label>
+            <input
+              id="min-sessions-to-keep"
+              type="number"
+              min="0"
+              value={settings.advanced.min_sessions_to_keep ?? 10}
+              onChange={(e) => {
+                const newSettings = {
+                  ...settings,
+                  advanced: {
+                    ...settings.advanced,
+                    min_sessions_to_keep: Math.max(0, parseInt(e.target.value) || 0),
+                  },
+                };
+                setSettings(newSettings);
+              }}
+            />
+            <small>Cleanup never deletes the newest sessions below this count</small>
+          </div>
+
+          <div className="setting-item">
+            <label>Stored Sessions</label>
+            {retention && (
+              <small>
+                {retention.files.total_sessions} saved
+                {retention.database && `, ${retention.database.total_sessions} in database`}
+                {"; "}
+                {retention.files.sessions_to_delete + (retention.database?.sessions_to_delete ?? 0)} past
+                retention
+              </small>
+            )}
+            <button className="btn-secondary" onClick={cleanupNow} disabled={cleaningUp || saving}>
+              {cleaningUp ? "Cleaning Up..." : "Clean Up Now"}
+            </button>
+          </div>
         </div>
       </section>
 
@@ -3,11 +3,29 @@
 import { useState, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  BoostSustainability,
   BottleneckAnalysisResult,
+  MemoryLeakTrend,
   MetricSample,
+  MetricType,
+  SessionTrend,
   UserFacingInsights,
+  VramCreepTrend,
   WorkloadProfile,
 } from "../types/index";
+import { getErrorMessage } from "../utils/errors";
+
+const THERMAL_METRICS: MetricType[] = ["temperature", "gpu_temperature"];
+
+/// Temperature history across saved sessions, used to tailor cooling advice
+async function fetchThermalTrends(): Promise<SessionTrend[]> {
+  const trends = await Promise.all(
+    THERMAL_METRICS.map((metricType) =>
+      invoke<SessionTrend | null>("analyze_session_trend", { metricType })
+    )
+  );
+  return trends.filter((trend): trend is SessionTrend => trend !== null);
+}
 
 export function useAnalysis() {
   const [analysisResult, setAnalysisResult] =
@@ -38,13 +56,36 @@ export function useAnalysis() {
         // Generate insights
         const generatedInsights = await invoke<UserFacingInsights>(
           "generate_insights",
-          { result, profile: profile ?? null }
+          { result, profile: profile ?? null, metrics }
         );
         setInsights(generatedInsights);
       } catch (err) {
-        setError(
-          err instanceof Error ? err.message : "Failed to analyze bottlenecks"
+        setError(getErrorMessage(err, "Failed to analyze bottlenecks"));
+      } finally {
+        setLoading(false);
+      }
+    },
+    []
+  );
+
+  const analyzeRunWithProfile = useCallback(
+    async (runId: string, analysisProfileId: string, profile?: WorkloadProfile) => {
+      try {
+        setLoading(true);
+        setError(null);
+        const result = await invoke<BottleneckAnalysisResult>("analyze_with_profile", {
+          runId,
+          analysisProfileId,
+        });
+        setAnalysisResult(result);
+
+        const generatedInsights = await invoke<UserFacingInsights>(
+          "generate_insights",
+          { result, profile: profile ?? null, trends: await fetchThermalTrends() }
         );
+        setInsights(generatedInsights);
+      } catch (err) {
+        setError(getErrorMessage(err, "Failed to analyze run"));
       } finally {
         setLoading(false);
       }
@@ -52,12 +93,85 @@ export function useAnalysis() {
     []
   );
 
+  const analyzeRun = useCallback(
+    async (runId: string, timeWindowSeconds?: number, profile?: WorkloadProfile) => {
+      try {
+        setLoading(true);
+        setError(null);
+        const result = await invoke<BottleneckAnalysisResult>("analyze_run", {
+          runId,
+          timeWindowSeconds: timeWindowSeconds ?? null,
+        });
+        setAnalysisResult(result);
+
+        const generatedInsights = await invoke<UserFacingInsights>(
+          "generate_insights",
+          { result, profile: profile ?? null, trends: await fetchThermalTrends() }
+        );
+        setInsights(generatedInsights);
+      } catch (err) {
+        setError(getErrorMessage(err, "Failed to analyze run"));
+      } finally {
+        setLoading(false);
+      }
+    },
+    []
+  );
+
+  const loadRunInsights = useCallback(async (sessionId: string, runId: string) => {
+    try {
+      setError(null);
+      const runInsights = await invoke<UserFacingInsights>("get_run_insights", { sessionId, runId });
+      setInsights(runInsights);
+      return runInsights;
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to load run insights"));
+      return null;
+    }
+  }, []);
+
+  const analyzeBoostSustainability = useCallback(async (runId: string) => {
+    try {
+      setError(null);
+      return await invoke<BoostSustainability[]>("analyze_boost_sustainability", { runId });
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to analyze boost clocks"));
+      return [];
+    }
+  }, []);
+
+  const detectMemoryLeakTrend = useCallback(async (runId: string) => {
+    try {
+      setError(null);
+      return await invoke<MemoryLeakTrend | null>("detect_memory_leak_trend", { runId });
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to check memory trend"));
+      return null;
+    }
+  }, []);
+
+  const detectVramCreep = useCallback(async (runId: string) => {
+    try {
+      setError(null);
+      return await invoke<VramCreepTrend | null>("detect_vram_creep", { runId });
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to check VRAM trend"));
+      return null;
+    }
+  }, []);
+
   return {
     analysisResult,
     insights,
     loading,
     error,
     analyze,
+    analyzeRun,
+    analyzeRunWithProfile,
+    loadRunInsights,
+    analyzeBoostSustainability,
+    detectMemoryLeakTrend,
+    detectVramCreep,
   };
 }
 
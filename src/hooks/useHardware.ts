@@ -2,12 +2,15 @@
 
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { HardwareConfig } from "../types/index";
+import { listen } from "@tauri-apps/api/event";
+import type { HardwareChange, HardwareConfig, Snapshot } from "../types/index";
+import { getErrorMessage } from "../utils/errors";
 
 export function useHardware() {
   const [hardware, setHardware] = useState<HardwareConfig | null>(null);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const [hardwareChanges, setHardwareChanges] = useState<HardwareChange[]>([]);
 
   const loadHardware = async () => {
     try {
@@ -16,7 +19,7 @@ export function useHardware() {
       const config = await invoke<HardwareConfig>("get_hardware_config");
       setHardware(config);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to load hardware");
+      setError(getErrorMessage(err, "Failed to load hardware"));
     } finally {
       setLoading(false);
     }
@@ -29,16 +32,58 @@ export function useHardware() {
       const config = await invoke<HardwareConfig>("refresh_hardware_config");
       setHardware(config);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to refresh hardware");
+      setError(getErrorMessage(err, "Failed to refresh hardware"));
     } finally {
       setLoading(false);
     }
   };
 
+  const dismissHardwareChanges = async () => {
+    try {
+      await invoke("dismiss_hardware_changes");
+    } finally {
+      setHardwareChanges([]);
+    }
+  };
+
+  const exportSnapshot = async (path: string, windowSeconds?: number) => {
+    return invoke<Snapshot>("export_snapshot", {
+      path,
+      windowSeconds: windowSeconds ?? null,
+    });
+  };
+
+  // Loads a snapshot for inspection only; nothing is saved
+  const importSnapshot = async (path: string) => {
+    return invoke<Snapshot>("import_snapshot", { path });
+  };
+
   useEffect(() => {
     loadHardware();
   }, []);
 
-  return { hardware, loading, error, refreshHardware };
+  // Changes may be detected before or after this hook mounts
+  useEffect(() => {
+    invoke<HardwareChange[]>("get_hardware_changes")
+      .then(setHardwareChanges)
+      .catch(() => setHardwareChanges([]));
+    const unlisten = listen<HardwareChange[]>("hardware-changed", (event) => {
+      setHardwareChanges(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return {
+    hardware,
+    loading,
+    error,
+    refreshHardware,
+    hardwareChanges,
+    dismissHardwareChanges,
+    exportSnapshot,
+    importSnapshot,
+  };
 }
 
@@ -1,13 +1,18 @@
 //! React hook for metrics collection
 
-import { useState, useEffect, useCallback } from "react";
+import { useState, useEffect, useCallback, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { MetricSample } from "../types/index";
+import type { CollectorHealth, LivePercentiles, MetricSample, MetricsDelta, Run } from "../types/index";
+import { getErrorMessage } from "../utils/errors";
 
 export function useMetrics() {
   const [metrics, setMetrics] = useState<MetricSample[]>([]);
+  const [health, setHealth] = useState<CollectorHealth | null>(null);
+  const [livePercentiles, setLivePercentiles] = useState<Record<string, LivePercentiles>>({});
   const [isCollecting, setIsCollecting] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  // Timestamp of the newest sample received, passed back as-is to fetch only newer ones
+  const lastTimestamp = useRef<string | null>(null);
 
   const startCollection = useCallback(async (samplingIntervalMs?: number) => {
     try {
@@ -15,7 +20,7 @@ export function useMetrics() {
       await invoke("start_metrics_collection", { samplingIntervalMs });
       setIsCollecting(true);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to start metrics collection");
+      setError(getErrorMessage(err, "Failed to start metrics collection"));
       setIsCollecting(false);
     }
   }, []);
@@ -26,20 +31,53 @@ export function useMetrics() {
       await invoke("stop_metrics_collection");
       setIsCollecting(false);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to stop metrics collection");
+      setError(getErrorMessage(err, "Failed to stop metrics collection"));
     }
   }, []);
 
   const loadMetrics = useCallback(async () => {
     try {
       setError(null);
-      const buffer = await invoke<MetricSample[]>("get_metrics_buffer");
-      setMetrics(buffer);
+      const [delta, collectorHealth, percentiles] = await Promise.all([
+        invoke<MetricsDelta>("get_metrics_since", { lastTimestamp: lastTimestamp.current }),
+        invoke<CollectorHealth>("get_metrics_health"),
+        invoke<Record<string, LivePercentiles>>("get_live_percentiles"),
+      ]);
+      if (delta.samples.length > 0) {
+        lastTimestamp.current = delta.samples[delta.samples.length - 1].timestamp;
+      }
+      setMetrics((previous) => {
+        if (delta.resync) {
+          return delta.samples;
+        }
+        // Drop what the collector has evicted so the copy stays bounded
+        const oldest = delta.oldest_timestamp ? Date.parse(delta.oldest_timestamp) : null;
+        const retained =
+          oldest === null ? previous : previous.filter((sample) => Date.parse(sample.timestamp) >= oldest);
+        return delta.samples.length > 0 || retained.length !== previous.length
+          ? [...retained, ...delta.samples]
+          : previous;
+      });
+      setHealth(collectorHealth);
+      setLivePercentiles(percentiles);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to load metrics");
+      setError(getErrorMessage(err, "Failed to load metrics"));
     }
   }, []);
 
+  const benchmarkCapture = useCallback(
+    async (durationMs: number, intervalMs: number): Promise<Run | null> => {
+      try {
+        setError(null);
+        return await invoke<Run>("benchmark_capture", { durationMs, intervalMs });
+      } catch (err) {
+        setError(getErrorMessage(err, "Failed to run benchmark capture"));
+        return null;
+      }
+    },
+    []
+  );
+
   useEffect(() => {
     if (isCollecting) {
       // Poll metrics buffer periodically (Tauri events can be added later)
@@ -53,10 +91,13 @@ export function useMetrics() {
 
   return {
     metrics,
+    health,
+    livePercentiles,
     isCollecting,
     error,
     startCollection,
     stopCollection,
     loadMetrics,
+    benchmarkCapture,
   };
 }
@@ -3,6 +3,7 @@
 import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import type { WorkloadProfile } from "../types/index";
+import { getErrorMessage } from "../utils/errors";
 
 export function useProfiles() {
   const [profiles, setProfiles] = useState<WorkloadProfile[]>([]);
@@ -14,15 +15,18 @@ export function useProfiles() {
     try {
       setLoading(true);
       setError(null);
-      const presetProfiles = await invoke<WorkloadProfile[]>("get_preset_profiles");
-      setProfiles(presetProfiles);
+      const [presetProfiles, customProfiles] = await Promise.all([
+        invoke<WorkloadProfile[]>("get_preset_profiles"),
+        invoke<WorkloadProfile[]>("get_custom_profiles"),
+      ]);
+      setProfiles([...presetProfiles, ...customProfiles]);
       
       // Set default profile if none selected
       if (!selectedProfile && presetProfiles.length > 0) {
         setSelectedProfile(presetProfiles[0]);
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to load profiles");
+      setError(getErrorMessage(err, "Failed to load profiles"));
     } finally {
       setLoading(false);
     }
@@ -35,11 +39,49 @@ export function useProfiles() {
       setSelectedProfile(profile);
       return profile;
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to load profile");
+      setError(getErrorMessage(err, "Failed to load profile"));
       throw err;
     }
   }, []);
 
+  const createCustomProfile = useCallback(async (profile: WorkloadProfile) => {
+    try {
+      setError(null);
+      const created = await invoke<WorkloadProfile>("create_custom_profile", { profile });
+      await loadProfiles();
+      return created;
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to create profile"));
+      throw err;
+    }
+  }, [loadProfiles]);
+
+  const updateCustomProfile = useCallback(async (profile: WorkloadProfile) => {
+    try {
+      setError(null);
+      const updated = await invoke<WorkloadProfile>("update_custom_profile", { profile });
+      await loadProfiles();
+      return updated;
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to update profile"));
+      throw err;
+    }
+  }, [loadProfiles]);
+
+  const deleteCustomProfile = useCallback(async (id: string) => {
+    try {
+      setError(null);
+      await invoke("delete_custom_profile", { id });
+      if (selectedProfile?.id === id) {
+        setSelectedProfile(null);
+      }
+      await loadProfiles();
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to delete profile"));
+      throw err;
+    }
+  }, [loadProfiles, selectedProfile]);
+
   useEffect(() => {
     loadProfiles();
   }, [loadProfiles]);
@@ -52,6 +94,9 @@ export function useProfiles() {
     setSelectedProfile,
     loadProfileById,
     loadProfiles,
+    createCustomProfile,
+    updateCustomProfile,
+    deleteCustomProfile,
   };
 }
 
@@ -2,7 +2,19 @@
 
 import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { Session, WorkloadProfile, HardwareConfig, Run } from "../types/index";
+import type {
+  Session,
+  WorkloadProfile,
+  HardwareConfig,
+  Run,
+  SizeEstimate,
+  RunMatch,
+  SessionSummary,
+  RetentionOverview,
+  RetentionPolicy,
+  CleanupReport,
+} from "../types/index";
+import { getErrorMessage } from "../utils/errors";
 
 export function useSessions() {
   const [sessions, setSessions] = useState<Session[]>([]);
@@ -25,7 +37,7 @@ export function useSessions() {
       
       setSessions(loadedSessions);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "Failed to load sessions");
+      setError(getErrorMessage(err, "Failed to load sessions"));
     } finally {
       setLoading(false);
     }
@@ -48,7 +60,7 @@ export function useSessions() {
         await loadSessions();
         return session;
       } catch (err) {
-        setError(err instanceof Error ? err.message : "Failed to create session");
+        setError(getErrorMessage(err, "Failed to create session"));
         throw err;
       }
     },
@@ -66,7 +78,7 @@ export function useSessions() {
         await loadSessions();
         return session;
       } catch (err) {
-        setError(err instanceof Error ? err.message : "Failed to end session");
+        setError(getErrorMessage(err, "Failed to end session"));
         throw err;
       }
     },
@@ -87,13 +99,68 @@ export function useSessions() {
         await loadSessions();
         return session;
       } catch (err) {
-        setError(err instanceof Error ? err.message : "Failed to add run");
+        setError(getErrorMessage(err, "Failed to add run"));
         throw err;
       }
     },
     [currentSession, loadSessions]
   );
 
+  // Lets callers warn before saving very large sessions
+  const estimateSize = useCallback(async (session: Session) => {
+    return invoke<SizeEstimate>("estimate_session_size", { session });
+  }, []);
+
+  const searchRuns = useCallback(async (query: string) => {
+    return invoke<RunMatch[]>("search_runs", { query });
+  }, []);
+
+  // Names and dates for session lists, without loading any metrics
+  const listSessionSummaries = useCallback(async () => {
+    return invoke<SessionSummary[]>("list_session_summaries");
+  }, []);
+
+  const cloneSession = useCallback(
+    async (sessionId: string, newName: string) => {
+      try {
+        setError(null);
+        const cloneId = await invoke<string>("clone_session", { sessionId, newName });
+        await loadSessions();
+        return cloneId;
+      } catch (err) {
+        setError(getErrorMessage(err, "Failed to clone session"));
+        throw err;
+      }
+    },
+    [loadSessions]
+  );
+
+  const getRetentionStats = useCallback(async () => {
+    return invoke<RetentionOverview>("get_retention_stats_command");
+  }, []);
+
+  const cleanupNow = useCallback(async () => {
+    try {
+      setError(null);
+      const report = await invoke<CleanupReport>("cleanup_now_command");
+      await loadSessions();
+      return report;
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to clean up sessions"));
+      throw err;
+    }
+  }, [loadSessions]);
+
+  const updateRetentionPolicy = useCallback(async (policy: RetentionPolicy) => {
+    try {
+      setError(null);
+      return await invoke<RetentionOverview>("update_retention_policy", { policy });
+    } catch (err) {
+      setError(getErrorMessage(err, "Failed to update retention policy"));
+      throw err;
+    }
+  }, []);
+
   useEffect(() => {
     loadSessions();
   }, [loadSessions]);
@@ -106,6 +173,13 @@ export function useSessions() {
     createSession,
     endSession,
     addRun,
+    estimateSize,
+    searchRuns,
+    listSessionSummaries,
+    cloneSession,
+    getRetentionStats,
+    cleanupNow,
+    updateRetentionPolicy,
     loadSessions,
     setCurrentSession,
   };
@@ -24,6 +24,7 @@ export interface CPUInfo {
   base_clock_mhz: number | null;
   boost_clock_mhz: number | null;
   architecture: string | null;
+  core_max_frequencies_mhz?: number[];
 }
 
 export interface GPUInfo {
@@ -52,6 +53,7 @@ export interface StorageInfo {
   capacity_mb: number;
   storage_type: StorageType;
   interface: string | null;
+  free_mb?: number | null; // free space across the device's volumes
 }
 
 export type StorageType = "ssd" | "hdd" | "nvme" | "unknown";
@@ -81,6 +83,54 @@ export interface DisplayInfo {
   gpu_attachment: string | null;
 }
 
+export type HardwareComponent = "cpu" | "gpu" | "memory" | "storage";
+
+export interface HardwareChange {
+  component: HardwareComponent;
+  previous: string | null;
+  current: string | null;
+  description: string;
+}
+
+export interface Snapshot {
+  format: "stats-io-snapshot";
+  schema_version: number;
+  app_version: string;
+  created_at: string; // ISO 8601 string
+  window_seconds: number;
+  hardware: HardwareConfig;
+  metrics: MetricSample[];
+}
+
+export interface HardwareClass {
+  cpu_model: string;
+  cpu_cores: number;
+  cpu_threads: number;
+  gpu_models: string[];
+  memory_gb: number;
+}
+
+export interface MetricAggregation {
+  min: number;
+  max: number;
+  avg: number;
+  trimmed_avg: number; // equals avg when nothing is trimmed
+  p50: number | null;
+  p95: number | null;
+  p99: number | null;
+  count: number;
+}
+
+export interface Contribution {
+  format: "stats-io-contribution";
+  schema_version: number;
+  app_version: string;
+  hardware: HardwareClass;
+  workload_type: WorkloadType;
+  run_count: number;
+  metrics: Record<string, MetricAggregation>;
+}
+
 export interface DetectionMetadata {
   detection_time: string; // ISO 8601 string
   platform: string;
@@ -102,23 +152,49 @@ export type MetricType =
   | "cpu_utilization_per_core"
   | "gpu_utilization"
   | "gpu_vram_usage"
+  | "gpu_vram_usage_percent"
   | "gpu_temperature"
   | "gpu_clock"
+  | "cpu_clock"
   | "memory_usage"
   | "memory_swap_usage"
   | "storage_read_throughput"
   | "storage_write_throughput"
   | "storage_queue_depth"
+  | "pcie_link_gen"
+  | "pcie_link_width"
+  | "pcie_link_gen_max"
+  | "pcie_link_width_max"
   | "temperature"
   | "fan_speed"
+  | "dpc_time"
+  | "interrupt_time"
   | "fps"
   | "frame_time"
-  | "render_time";
+  | "render_time"
+  | "gpu_memory_controller_load"
+  | "voltage"
+  | "process_gpu_utilization"
+  | "memory_used"
+  | "gpu_power"
+  | "process_disk_write_throughput"
+  | "process_cpu_utilization"
+  | "sensor_temperature"
+  | "gpu_adapter_utilization"
+  | "gpu_hotspot_temperature";
 
 // Analysis types
 export interface BottleneckAnalysisResult {
   bottlenecks: Bottleneck[];
   timestamp: string; // ISO 8601 string
+  coverage?: AnalysisCoverage | null;
+}
+
+export interface AnalysisCoverage {
+  requested_seconds: number;
+  covered_seconds: number;
+  warning: string | null; // set when the data spans much less than the requested window
+  data_warnings?: string[]; // readings that look wrong, such as GPU utilization stuck at 0 under load
 }
 
 export interface Bottleneck {
@@ -127,6 +203,8 @@ export interface Bottleneck {
   evidence: EvidenceItem[];
   summary: string;
   details: string;
+  onset?: string | null; // ISO 8601 string
+  active_fraction?: number; // 0-1 share of the window the bottleneck was active
 }
 
 export type BottleneckType =
@@ -136,7 +214,13 @@ export type BottleneckType =
   | "vram"
   | "storage"
   | "thermal"
-  | "bandwidth";
+  | "bandwidth"
+  | "responsiveness"
+  | "input_pipeline"
+  | "frame_cap"
+  | "combined_load"
+  | "background_download"
+  | "hybrid_graphics";
 
 export interface EvidenceItem {
   metric_type: MetricType;
@@ -144,12 +228,133 @@ export interface EvidenceItem {
   actual_value: number;
   time_range_start: string; // ISO 8601 string
   time_range_end: string; // ISO 8601 string
+  source_component?: string | null; // component or process the evidence is about
+}
+
+export type RecommendationCategory = "upgrade" | "config" | "cooling";
+
+export type RecommendationDifficulty = "easy" | "moderate" | "advanced";
+
+export interface Recommendation {
+  id: string;
+  bottleneck_type: BottleneckType | null;
+  priority: number; // 0-100, severity of the addressed bottleneck
+  category: RecommendationCategory;
+  text: string;
+  difficulty: RecommendationDifficulty;
 }
 
 export interface UserFacingInsights {
   summary: string;
-  recommendations: string[];
+  recommendations: Recommendation[];
   severity: number; // 0-100
+  dismissed?: Bottleneck[]; // acknowledged bottlenecks
+  foreground?: ForegroundAttribution | null;
+}
+
+export interface ResourceAttribution {
+  system_percent: number;
+  process_percent: number;
+  share_percent: number;
+}
+
+export interface ForegroundAttribution {
+  process: ForegroundProcess;
+  cpu: ResourceAttribution | null;
+  gpu: ResourceAttribution | null;
+  scenario: "app_limited" | "system_contended";
+}
+
+export interface TrendPoint {
+  session_id: string;
+  start_time: string; // ISO 8601 string
+  value: number; // 95th percentile across the session's runs
+}
+
+/// Worst bottleneck of one run, from `analyze_all_sessions`
+export interface RunIssue {
+  run_id: string;
+  run_name: string;
+  bottleneck_type: BottleneckType;
+  severity: number;
+  summary: string;
+}
+
+/// Saved session with a run over the requested severity
+export interface SessionIssueSummary {
+  session_id: string;
+  session_name: string;
+  start_time: string;
+  hardware: string; // CPU, GPUs, memory and platform
+  worst_severity: number;
+  summary: string;
+  runs: RunIssue[]; // most severe first
+}
+
+export interface SessionTrend {
+  metric_type: MetricType;
+  points: TrendPoint[];
+  slope_per_month: number;
+  span_days: number;
+}
+
+export interface BoostSustainability {
+  component: "cpu" | "gpu";
+  peak_clock_mhz: number;
+  settled_clock_mhz: number;
+  settled_delta_percent: number;
+  sustained_seconds: number | null; // null when clocks never left the peak
+  boost_temperature: number;
+  settled_temperature: number;
+  summary: string;
+}
+
+export interface MemoryLeakTrend {
+  metric_type: MetricType; // memory_used (MB) or memory_usage (%)
+  start_value: number;
+  end_value: number;
+  slope_per_minute: number;
+  growth_percent: number;
+  r_squared: number;
+  max_recovery: number;
+  duration_seconds: number;
+  summary: string;
+  recommendation: Recommendation;
+}
+
+export interface VramCreepTrend {
+  source_component: string;
+  start_mb: number;
+  end_mb: number;
+  total_mb: number;
+  slope_mb_per_minute: number;
+  growth_percent: number;
+  r_squared: number;
+  max_recovery_mb: number;
+  duration_seconds: number;
+  minutes_to_full: number | null; // null once usage has reached the total
+  summary: string;
+  recommendation: Recommendation;
+}
+
+// Rule explanation traces (debugging)
+export interface TraceCheck {
+  metric_type: MetricType;
+  statistic: string;
+  value: number | null; // null when the window held no sample of the metric
+  threshold: number;
+  comparison: "above" | "below";
+  sample_count: number;
+  passed: boolean;
+}
+
+export interface RuleTrace {
+  rule: string;
+  sample_count: number;
+  checks: TraceCheck[];
+  fired: boolean;
+  bottlenecks: [BottleneckType, number][]; // type and severity
+  decision: string;
 }
 
 // Session types
@@ -162,12 +367,51 @@ export interface Session {
   runs: Run[];
 }
 
+export interface SizeEstimate {
+  sample_count: number;
+  json_bytes: number;
+  db_bytes: number;
+  metrics_rows: number;
+  is_large: boolean; // Worth warning about before saving
+}
+
+/// Session header for lists, without hardware or metrics
+export interface SessionSummary {
+  id: string;
+  name: string;
+  start_time: string; // ISO 8601 string
+  end_time: string | null;
+  run_count: number;
+}
+
+export interface RunMatch {
+  session_id: string;
+  run_id: string;
+  run_name: string;
+  snippet: string; // Match wrapped in brackets
+}
+
 export interface Run {
   id: string;
   name: string;
   metrics_streams: Record<string, MetricSample[]>;
   analysis_result: BottleneckAnalysisResult | null;
   notes: string | null;
+  environment?: RunEnvironment | null;
+}
+
+export interface RunEnvironment {
+  os_version: string | null;
+  gpu_driver_versions: string[];
+  power_plan: string | null;
+  background_process_count: number | null;
+  virtual_machine: boolean | null;
+  foreground_process?: ForegroundProcess | null;
+}
+
+export interface ForegroundProcess {
+  pid: number;
+  name: string | null;
 }
 
 export interface WorkloadProfile {
@@ -185,8 +429,16 @@ export interface ThresholdOverrides {
   gpu_high: number | null;
   ram_high: number | null;
   vram_high: number | null;
+  statistic?: ThresholdStatistic;
+  combined_pressure?: number | null;
+  sustained_seconds?: number | null;
 }
 
+export type ThresholdStatistic =
+  | "mean"
+  | { percentile: number }
+  | { trimmed_mean: number }; // share trimmed from each end, 0 - 0.5
+
 // Comparison types
 export interface ComparisonResult {
   run1_id: string;
@@ -194,6 +446,32 @@ export interface ComparisonResult {
   metric_deltas: Record<string, MetricDelta>;
   bottleneck_changes: BottleneckChange[];
   summary: string;
+  environment_warnings: string[];
+  hardware_diff?: HardwareDiff | null; // set when run 1 comes from an imported session
+}
+
+export type ClassDifference = "higher" | "same" | "lower" | "unknown";
+
+export interface ComponentDiff {
+  component: HardwareComponent;
+  imported: string;
+  local: string;
+  imported_class: string | null;
+  local_class: string | null;
+  difference: ClassDifference; // imported relative to local
+  description: string;
+}
+
+export interface HardwareDiff {
+  differences: ComponentDiff[];
+  caption: string;
+}
+
+export interface PreviousRunComparison {
+  previous_run_name: string;
+  latest_run_name: string;
+  comparison: ComparisonResult;
+  verdict: string;
 }
 
 export interface MetricDelta {
@@ -203,6 +481,9 @@ export interface MetricDelta {
   delta: number;
   delta_percent: number;
   unit: string;
+  run1_stddev: number;
+  run2_stddev: number;
+  significant: boolean; // delta exceeds both the minimum effect and run noise
 }
 
 export interface BottleneckChange {
@@ -215,6 +496,69 @@ export interface BottleneckChange {
 
 export type BottleneckStatus = "new" | "resolved" | "improved" | "worsened" | "unchanged";
 
+export type ReferenceRating = "above" | "typical" | "below";
+
+export interface ReferenceMetricComparison {
+  metric_type: MetricType;
+  run_average: number;
+  reference_median: number;
+  delta_percent: number;
+  rating: ReferenceRating;
+}
+
+export interface ReferenceComparison {
+  tier_id: string;
+  tier_name: string;
+  matched_model: string;
+  workload_type: WorkloadType;
+  metrics: ReferenceMetricComparison[];
+  overall: ReferenceRating;
+  summary: string;
+}
+
+// Report types
+export interface ReportProgress {
+  report_id: string;
+  runs_processed: number;
+  total_runs: number;
+}
+
+export type ReportFormat = "text" | "html" | "json" | "pdf" | "markdown" | "csv";
+
+export interface ReportConfig {
+  include_hardware: boolean;
+  include_metrics: boolean;
+  include_analysis: boolean;
+  include_recommendations: boolean;
+  include_comparison: boolean;
+  format: ReportFormat;
+  timezone?: string | null;
+  acknowledgments?: BottleneckAcknowledgment[];
+  sections?: ReportSection[] | null;
+  severity_bands?: SeverityBands;
+  detail_level?: ReportDetailLevel | null;
+}
+
+/** How much per-run detail a session report includes */
+export type ReportDetailLevel = 'summary' | 'standard' | 'full';
+
+/** Inclusive lower bounds of the HTML report severity tiers */
+export interface SeverityBands {
+  medium: number;
+  high: number;
+  critical: number;
+}
+
+export type ReportSection =
+  | 'hardware'
+  | 'environment'
+  | 'trend'
+  | 'charts'
+  | 'analysis'
+  | 'recommendations'
+  | 'markers'
+  | 'comparison';
+
 // Settings types
 export interface UserSettings {
   thresholds: ThresholdSettings;
@@ -222,6 +566,53 @@ export interface UserSettings {
   units: UnitPreferences;
   theme: ThemePreferences;
   advanced: AdvancedSettings;
+  idle_baseline?: IdleBaseline | null;
+  analysis_profiles?: AnalysisProfile[];
+  custom_profiles?: WorkloadProfile[];
+  alerts?: AlertSettings;
+  acknowledgments?: BottleneckAcknowledgment[];
+}
+
+/// Bottleneck type dismissed in insights, reports, and alerts
+export interface BottleneckAcknowledgment {
+  bottleneck_type: BottleneckType;
+  max_severity?: number | null; // 0-100; more severe bottlenecks still surface
+  note?: string | null;
+  acknowledged_at: string;
+}
+
+/// Live bottleneck alerts raised while metrics are collected
+export interface AlertSettings {
+  enabled: boolean;
+  min_severity: number; // 0-100
+  cooldown_seconds: number;
+}
+
+/// Payload of the `bottleneck-alert` event
+export interface BottleneckAlert {
+  bottleneck_type: BottleneckType;
+  severity: number;
+  summary: string;
+  detected_at: string;
+}
+
+export interface AnalysisProfile {
+  id: string;
+  name: string;
+  window_seconds: number | null; // null analyzes the whole run
+  thresholds: ThresholdOverrides;
+}
+
+export interface IdleBaseline {
+  captured_at: string;
+  duration_seconds: number;
+  source_averages: BaselineAverage[];
+}
+
+export interface BaselineAverage {
+  metric_type: MetricType;
+  source_component: string;
+  average: number;
 }
 
 export interface ThresholdSettings {
@@ -231,16 +622,65 @@ export interface ThresholdSettings {
   vram_high: number;
   temperature_warning: number;
   temperature_critical: number;
+  min_comparison_effect_percent?: number;
+  trim_fraction?: number; // share of samples trimmed from each end for trimmed means
 }
 
 export interface SamplingSettings {
   interval_ms: number;
   buffer_size: number;
+  overflow_policy?: BufferOverflowPolicy;
+  missed_tick_behavior?: MissedTickBehavior;
+  gpu_utilization_source?: GpuUtilizationSource;
+  event_flush_interval_ms?: number;
+  noise_floors?: NoiseFloors;
+}
+
+/// Readings below these values are dropped at collection time
+export interface NoiseFloors {
+  gpu_utilization: number; // percent
+  storage_read_throughput: number; // MB/s
+  storage_write_throughput: number; // MB/s
+}
+
+export type BufferOverflowPolicy = "evict_oldest" | "unbounded_until_saved";
+
+export type MissedTickBehavior = "burst" | "delay" | "skip";
+
+export type GpuUtilizationSource = "driver" | "engine3d";
+
+export interface CollectorHealth {
+  buffered_samples: number;
+  buffer_size: number;
+  overflow_policy: BufferOverflowPolicy;
+  evicted_samples: number;
+  eviction_started_at: string | null; // ISO 8601 string
+  clamped_samples: number;
+  rejected_samples: number;
+  subprocess_timeouts: number;
+  subprocess_failures: number;
+}
+
+/// Result of `get_metrics_since`
+export interface MetricsDelta {
+  samples: MetricSample[];
+  resync: boolean; // replace the local copy instead of appending
+  oldest_timestamp: string | null; // ISO 8601 string
+}
+
+export interface LivePercentiles {
+  count: number;
+  min: number;
+  max: number;
+  p50: number;
+  p95: number;
+  p99: number;
 }
 
 export interface UnitPreferences {
   temperature: "celsius" | "fahrenheit";
   memory: "mb" | "gb";
+  display_timezone: string;
 }
 
 export interface ThemePreferences {
@@ -252,4 +692,60 @@ export interface AdvancedSettings {
   enable_debug_logging: boolean;
   auto_save_sessions: boolean;
   session_retention_days: number | null;
+  min_sessions_to_keep: number;
+  auto_cleanup_enabled: boolean;
+  decimation?: DecimationPolicy;
+}
+
+/// Metric types reduced to one sample per interval when a run is saved
+export interface DecimationPolicy {
+  rules: DecimationRule[];
+}
+
+export interface DecimationRule {
+  metric_type: MetricType;
+  interval_seconds: number;
+  method?: DecimationMethod;
+}
+
+export type DecimationMethod = "mean" | "max";
+
+export interface RetentionPolicy {
+  retention_days: number;
+  auto_cleanup_enabled: boolean;
+  min_sessions_to_keep: number;
+}
+
+export interface RetentionStats {
+  total_sessions: number;
+  sessions_to_delete: number;
+  oldest_session_date: string | null;
+  newest_session_date: string | null;
+}
+
+export interface RetentionOverview {
+  files: RetentionStats;
+  database: RetentionStats | null;
+}
+
+export interface CleanupReport {
+  deleted: number;
+  stats: RetentionOverview;
+}
+
+// Command error types
+export type CommandErrorKind =
+  | "hardware"
+  | "metrics"
+  | "analysis"
+  | "persistence"
+  | "session"
+  | "settings"
+  | "validation"
+  | "internal";
+
+export interface CommandError {
+  kind: CommandErrorKind;
+  code: string;
+  message: string;
 }
@@ -0,0 +1,27 @@
+//! Command error utilities
+//!
+//! Helpers for handling the structured errors returned by backend commands.
+
+import type { CommandError } from "../types/index";
+
+/// Check whether a rejected invoke value is a structured command error
+export function isCommandError(err: unknown): err is CommandError {
+  return (
+    typeof err === "object" &&
+    err !== null &&
+    "kind" in err &&
+    "code" in err &&
+    "message" in err
+  );
+}
+
+/// Get a displayable message from a rejected invoke value
+export function getErrorMessage(err: unknown, fallback: string): string {
+  if (isCommandError(err)) {
+    return err.message;
+  }
+  if (err instanceof Error) {
+    return err.message;
+  }
+  return typeof err === "string" ? err : fallback;
+}
@@ -7,12 +7,19 @@
 // For now, we'll use the invoke command to handle file operations
 // The backend will handle file dialogs and writing
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type {
   Session,
   HardwareConfig,
   ComparisonResult,
   Run,
+  ReportProgress,
+  ReportConfig,
+  ReportSection,
+  ReportDetailLevel,
+  Contribution,
 } from "../types/index";
+import { isCommandError } from "./errors";
 
 /// Export report in a specific format
 export async function exportReportInFormat(
@@ -24,10 +31,28 @@ export async function exportReportInFormat(
     includeMetrics?: boolean;
     includeAnalysis?: boolean;
     includeRecommendations?: boolean;
+    /// Sections in output order; omitted uses the default layout
+    sections?: ReportSection[];
+    /// Omitted picks a level from the number of runs
+    detailLevel?: ReportDetailLevel;
+    /// Identifies the report in progress events and to `cancelReportGeneration`
+    reportId?: string;
+    onProgress?: (progress: ReportProgress) => void;
   }
 ): Promise<void> {
+  const reportId = options?.reportId ?? crypto.randomUUID();
+  const onProgress = options?.onProgress;
+  const unlisten = onProgress
+    ? await listen<ReportProgress>("report-progress", (event) => {
+        if (event.payload.report_id === reportId) {
+          onProgress(event.payload);
+        }
+      })
+    : undefined;
+
   try {
     const report = await invoke<string>("generate_report", {
+      reportId,
       session,
       hardware,
       format,
@@ -35,6 +60,8 @@ export async function exportReportInFormat(
       includeMetrics: options?.includeMetrics ?? true,
       includeAnalysis: options?.includeAnalysis ?? true,
       includeRecommendations: options?.includeRecommendations ?? true,
+      sections: options?.sections ?? null,
+      detailLevel: options?.detailLevel ?? null,
     });
 
     // For MVP, use browser download
@@ -52,11 +79,81 @@ export async function exportReportInFormat(
     document.body.removeChild(a);
     URL.revokeObjectURL(url);
   } catch (error) {
+    if (isCommandError(error) && error.code === "PERSISTENCE_CANCELLED") {
+      return;
+    }
     console.error("Failed to export report:", error);
     alert("Failed to export report");
+  } finally {
+    unlisten?.();
+  }
+}
+
+/// Cancel the session report generated under `reportId`
+export async function cancelReportGeneration(reportId: string): Promise<void> {
+  await invoke("cancel_report_generation", { reportId });
+}
+
+/// Export the full analysis result of every run, including evidence
+export async function exportAnalysisJson(session: Session): Promise<void> {
+  try {
+    const json = await invoke<string>("export_analysis_json", { session });
+
+    const blob = new Blob([json], { type: "application/json" });
+    const url = URL.createObjectURL(blob);
+    const a = document.createElement("a");
+    a.href = url;
+    a.download = `analysis-${session.id}-${Date.now()}.json`;
+    document.body.appendChild(a);
+    a.click();
+    document.body.removeChild(a);
+    URL.revokeObjectURL(url);
+  } catch (error) {
+    console.error("Failed to export analysis:", error);
+    alert("Failed to export analysis");
+  }
+}
+
+/// Export a run as a wide CSV, one column per metric resampled every `intervalMs`
+export async function exportRunWideCsv(run: Run, intervalMs: number): Promise<void> {
+  try {
+    const csv = await invoke<string>("export_run_wide_csv", { run, intervalMs });
+
+    const blob = new Blob([csv], { type: "text/csv" });
+    const url = URL.createObjectURL(blob);
+    const a = document.createElement("a");
+    a.href = url;
+    a.download = `run-${run.id}-${intervalMs}ms.csv`;
+    document.body.appendChild(a);
+    a.click();
+    document.body.removeChild(a);
+    URL.revokeObjectURL(url);
+  } catch (error) {
+    console.error("Failed to export run CSV:", error);
+    alert("Failed to export run CSV");
   }
 }
 
+/// Write a report for a saved session to a file
+///
+/// The file extension picks the format when it differs from `config.format`.
+/// Resolves to the number of bytes written.
+export async function writeReport(
+  sessionId: string,
+  config: ReportConfig,
+  outPath: string
+): Promise<number> {
+  return invoke<number>("write_report", { sessionId, config, outPath });
+}
+
+/// Export an anonymized contribution of a session for the reference dataset
+export async function exportAnonymizedContribution(
+  session: Session,
+  path: string
+): Promise<Contribution> {
+  return invoke<Contribution>("export_anonymized_contribution", { session, path });
+}
+
 /// Export comparison report
 export async function exportComparisonReport(
   comparison: ComparisonResult,